@@ -1,8 +1,16 @@
 extern crate engine;
+use std::env;
 use std::fs::read_to_string;
-use engine::exec;
+use engine::{exec, exec_module};
 
 fn main() {
-    let buffer = read_to_string("tests/js/test.js").unwrap();
-    exec(buffer);
+    let args: Vec<String> = env::args().collect();
+    match args.get(1) {
+        // Run a file as a module, loading its imports from the file system
+        Some(flag) if flag == "--module" => exec_module(&args[2]),
+        _ => {
+            let buffer = read_to_string("tests/js/test.js").unwrap();
+            exec(buffer);
+        }
+    }
 }
@@ -0,0 +1,470 @@
+//! # Async functions
+//!
+//! https://tc39.es/ecma262/#sec-async-function-definitions
+//!
+//! The interpreter runs code by recursing over the syntax tree, so it can't leave a function part way through and
+//! come back to it later. Instead, the body of an async function is rewritten when the function is made, so every
+//! `await` is a statement of its own, either `await x;` or `var %await0 = await x;` where `x` has no `await` in it.
+//! Values which were worked out before an `await` and are used after it are kept in `%await` variables too.
+//!
+//! When the body reaches an `await`, the rest of it is kept as a list of segments, which are code to run in an
+//! environment: the rest of the innermost block first, then the rest of the blocks and loops around it.
+//! The segments are run by a promise reaction once the awaited value settles, so the caller carries on in the
+//! meantime, as if the body's execution context had been suspended.
+//! The top level bodies of modules are run the same way, for top level `await`.
+
+use std::collections::HashMap;
+
+use crate::environment::lexical_environment::Environment;
+use crate::exec::Interpreter;
+use crate::js::error::StackFrame;
+use crate::js::function::NativeFunctionData;
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+use crate::syntax::ast::constant::Const;
+use crate::syntax::ast::expr::{Expr, ExprDef};
+use crate::syntax::ast::module::ExportDecl;
+use gc::Gc;
+
+/// The private field of a resume function, holding the id of the continuation it resumes
+static CONTINUATION_ID: &str = "ContinuationId";
+
+/// Code to run in an environment, which is part of the rest of a suspended body
+#[derive(Clone)]
+pub struct Segment {
+    pub environment: Environment,
+    pub code: Expr,
+}
+
+/// Where a body has got to when it awaits a value
+pub struct Suspension {
+    /// The value being awaited
+    pub value: Value,
+    /// The variable the awaited value is assigned to, for `var %await0 = await x;`
+    pub binding: Option<String>,
+    /// The environment the `await` was reached in, which the binding is declared from
+    pub environment: Environment,
+    /// The rest of the body, innermost first
+    pub segments: Vec<Segment>,
+}
+
+/// How far running part of a body got
+pub enum AsyncStep {
+    /// The body ran to the end, with this completion value
+    Done(Value),
+    /// The body reached an `await`
+    Await(Suspension),
+}
+
+/// What a body is being run for, which stays the same each time it is resumed
+#[derive(Clone)]
+pub struct AsyncContext {
+    /// The promise capability settled with the completion of the body
+    pub capability: Value,
+    /// The call stack frame the body runs in
+    pub frame: StackFrame,
+    /// The key of the module whose top level body this is, or `None` for an async function
+    pub module: Option<String>,
+}
+
+/// A suspended body, waiting for the value it awaited to settle
+pub struct Continuation {
+    pub binding: Option<String>,
+    pub environment: Environment,
+    pub segments: Vec<Segment>,
+    pub context: AsyncContext,
+}
+
+/// The bodies waiting for awaited values, by id.
+/// A continuation is taken out when its awaited value settles, so it is only ever resumed once
+pub struct Continuations {
+    next_id: u64,
+    suspended: HashMap<u64, Continuation>,
+}
+
+impl Continuations {
+    /// Make an empty set of continuations
+    pub fn new() -> Self {
+        Continuations {
+            next_id: 0,
+            suspended: HashMap::new(),
+        }
+    }
+
+    /// Keep a continuation until it is resumed, returning its id
+    pub fn insert(&mut self, continuation: Continuation) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.suspended.insert(id, continuation);
+        id
+    }
+
+    /// Take out the continuation with an id, or `None` if it has already been resumed
+    pub fn take(&mut self, id: u64) -> Option<Continuation> {
+        self.suspended.remove(&id)
+    }
+}
+
+impl Default for Continuations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Make the function a promise reaction calls to resume a continuation
+pub fn resume_function(func: NativeFunctionData, id: u64) -> Value {
+    let resume = to_value(func);
+    resume.set_private_field_slice(CONTINUATION_ID, to_value(id as f64));
+    resume
+}
+
+/// Resume a continuation with the value its awaited promise was fulfilled with
+pub fn resume_fulfilled(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let id = f.get_private_field_slice(CONTINUATION_ID).to_num() as u64;
+    interpreter.resume_async(id, Ok(arg(&args, 0)))?;
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Resume a continuation with the reason its awaited promise was rejected, which is thrown from the `await`
+pub fn resume_rejected(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let id = f.get_private_field_slice(CONTINUATION_ID).to_num() as u64;
+    interpreter.resume_async(id, Err(arg(&args, 0)))?;
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Returns true if running the expression can reach an `await`.
+/// Functions made inside it are not searched, as their bodies run when they are called
+pub fn contains_await(expr: &Expr) -> bool {
+    let any = |exprs: &[Expr]| exprs.iter().any(contains_await);
+    match expr.def {
+        ExprDef::AwaitExpr(_) => true,
+        ExprDef::FunctionDeclExpr(_, _, _, _)
+        | ExprDef::ArrowFunctionDeclExpr(_, _, _)
+        | ExprDef::AsyncFunctionDeclExpr(_, _, _, _)
+        | ExprDef::AsyncArrowFunctionDeclExpr(_, _, _)
+        | ExprDef::ConstExpr(_)
+        | ExprDef::LocalExpr(_)
+        | ExprDef::ThisExpr
+        | ExprDef::ImportDeclExpr(_, _)
+        | ExprDef::ReturnExpr(None) => false,
+        ExprDef::BinOpExpr(_, ref a, ref b)
+        | ExprDef::GetFieldExpr(ref a, ref b)
+        | ExprDef::WhileLoopExpr(ref a, ref b)
+        | ExprDef::WithExpr(ref a, ref b)
+        | ExprDef::AssignExpr(ref a, ref b) => contains_await(a) || contains_await(b),
+        ExprDef::UnaryOpExpr(_, ref a)
+        | ExprDef::GetConstFieldExpr(ref a, _)
+        | ExprDef::ImportCallExpr(ref a)
+        | ExprDef::ReturnExpr(Some(ref a))
+        | ExprDef::ThrowExpr(ref a)
+        | ExprDef::TypeOfExpr(ref a) => contains_await(a),
+        ExprDef::ConstructExpr(ref callee, ref args) | ExprDef::CallExpr(ref callee, ref args) => {
+            contains_await(callee) || any(args)
+        }
+        ExprDef::BlockExpr(ref exprs) | ExprDef::ArrayDeclExpr(ref exprs) => any(exprs),
+        ExprDef::IfExpr(ref cond, ref then, ref otherwise) => {
+            contains_await(cond) || contains_await(then) || otherwise.as_deref().is_some_and(contains_await)
+        }
+        ExprDef::SwitchExpr(ref value, ref cases, ref default) => {
+            contains_await(value)
+                || cases.iter().any(|(test, block)| contains_await(test) || any(block))
+                || default.as_deref().is_some_and(contains_await)
+        }
+        ExprDef::ObjectDeclExpr(ref entries) => entries.iter().any(|(_, value)| contains_await(value)),
+        ExprDef::VarDeclExpr(ref vars) | ExprDef::LetDeclExpr(ref vars) | ExprDef::ConstDeclExpr(ref vars) => {
+            vars.iter().any(|(_, value)| value.as_ref().is_some_and(contains_await))
+        }
+        ExprDef::ExportDeclExpr(ref decl) => match *decl {
+            ExportDecl::Decl(ref expr) | ExportDecl::Default(ref expr) => contains_await(expr),
+            ExportDecl::Named(_, _) | ExportDecl::All(_, _) => false,
+        },
+    }
+}
+
+/// Rewrite the body of an async function or module so every `await` is a statement of its own, which the
+/// interpreter can stop at. Bodies without an `await` are returned unchanged
+pub fn normalize(body: &Expr) -> Expr {
+    if !contains_await(body) {
+        return body.clone();
+    }
+    Normalizer { next_temp: 0 }.block(body)
+}
+
+/// Make an expression at the same position as another
+fn at(expr: &Expr, def: ExprDef) -> Expr {
+    Expr { def, pos: expr.pos.clone() }
+}
+
+/// Returns true if running the expression gives the same result before and after an `await`,
+/// so it doesn't need keeping in a variable
+fn is_constant(expr: &Expr) -> bool {
+    matches!(
+        expr.def,
+        ExprDef::ConstExpr(_)
+            | ExprDef::ThisExpr
+            | ExprDef::FunctionDeclExpr(None, _, _, _)
+            | ExprDef::ArrowFunctionDeclExpr(_, _, _)
+            | ExprDef::AsyncFunctionDeclExpr(None, _, _, _)
+            | ExprDef::AsyncArrowFunctionDeclExpr(_, _, _)
+    )
+}
+
+/// Rewrites bodies for `normalize`, naming the variables it introduces
+struct Normalizer {
+    next_temp: usize,
+}
+
+impl Normalizer {
+    /// A new variable name, which scripts can't use as it isn't an identifier
+    fn temp(&mut self) -> String {
+        let name = format!("%await{}", self.next_temp);
+        self.next_temp += 1;
+        name
+    }
+
+    /// Keep the value of an expression in a new variable, returning an expression which reads it
+    fn hoist(&mut self, expr: Expr, out: &mut Vec<Expr>) -> Expr {
+        let name = self.temp();
+        let read = at(&expr, ExprDef::LocalExpr(name.clone()));
+        let pos = expr.pos.clone();
+        out.push(Expr { def: ExprDef::VarDeclExpr(vec![(name, Some(expr))]), pos });
+        read
+    }
+
+    /// Rewrite a statement as a block
+    fn block(&mut self, statement: &Expr) -> Expr {
+        let statements = self.block_statements(statement);
+        at(statement, ExprDef::BlockExpr(statements))
+    }
+
+    /// Rewrite a statement as the statements of a block
+    fn block_statements(&mut self, statement: &Expr) -> Vec<Expr> {
+        match statement.def {
+            ExprDef::BlockExpr(ref statements) => self.statements(statements),
+            _ => {
+                let mut out = Vec::new();
+                self.statement(statement, &mut out);
+                out
+            }
+        }
+    }
+
+    /// Rewrite a list of statements
+    fn statements(&mut self, statements: &[Expr]) -> Vec<Expr> {
+        let mut out = Vec::new();
+        for statement in statements.iter() {
+            self.statement(statement, &mut out);
+        }
+        out
+    }
+
+    /// Rewrite a statement, adding it to `out` after the statements it needs to run first
+    fn statement(&mut self, statement: &Expr, out: &mut Vec<Expr>) {
+        if !contains_await(statement) {
+            out.push(statement.clone());
+            return;
+        }
+        let def = match statement.def {
+            ExprDef::BlockExpr(_) => {
+                let block = self.block(statement);
+                out.push(block);
+                return;
+            }
+            ExprDef::AwaitExpr(ref value) => ExprDef::AwaitExpr(Box::new(self.expr(value, out))),
+            ExprDef::IfExpr(ref cond, ref then, ref otherwise) => ExprDef::IfExpr(
+                Box::new(self.expr(cond, out)),
+                Box::new(self.block(then)),
+                otherwise.as_ref().map(|otherwise| Box::new(self.block(otherwise))),
+            ),
+            ExprDef::WhileLoopExpr(ref cond, ref body) => {
+                // The statements working out the condition run again at the end of each pass of the loop
+                let mut before = Vec::new();
+                let cond = self.expr(cond, &mut before);
+                out.extend(before.iter().cloned());
+                let mut body = self.block_statements(body);
+                body.extend(before);
+                ExprDef::WhileLoopExpr(Box::new(cond), Box::new(at(statement, ExprDef::BlockExpr(body))))
+            }
+            ExprDef::WithExpr(ref obj, ref body) => {
+                ExprDef::WithExpr(Box::new(self.expr(obj, out)), Box::new(self.block(body)))
+            }
+            ExprDef::SwitchExpr(ref value, ref cases, ref default) => {
+                let mut operands = vec![value.as_ref()];
+                operands.extend(cases.iter().map(|(test, _)| test));
+                let mut values = self.operands(&operands, out).into_iter();
+                // The cases after an `await` are checked against the value again, so it is kept in a variable
+                let value = values.next().unwrap();
+                let value = if is_constant(&value) { value } else { self.hoist(value, out) };
+                let cases = values
+                    .zip(cases.iter())
+                    .map(|(test, (_, block))| (test, self.statements(block)))
+                    .collect();
+                let default = default.as_ref().map(|default| Box::new(self.block(default)));
+                ExprDef::SwitchExpr(Box::new(value), cases, default)
+            }
+            // Declarations are split up, so each variable is bound before the next one's value is worked out
+            ExprDef::VarDeclExpr(ref vars) | ExprDef::LetDeclExpr(ref vars) | ExprDef::ConstDeclExpr(ref vars) => {
+                for (name, value) in vars.iter() {
+                    let value = value.as_ref().map(|value| self.expr(value, out));
+                    let vars = vec![(name.clone(), value)];
+                    out.push(at(
+                        statement,
+                        match statement.def {
+                            ExprDef::VarDeclExpr(_) => ExprDef::VarDeclExpr(vars),
+                            ExprDef::LetDeclExpr(_) => ExprDef::LetDeclExpr(vars),
+                            _ => ExprDef::ConstDeclExpr(vars),
+                        },
+                    ));
+                }
+                return;
+            }
+            // The exported names were found before the body was rewritten, so the declaration can stand alone
+            ExprDef::ExportDeclExpr(ExportDecl::Decl(ref decl)) => return self.statement(decl, out),
+            ExprDef::ExportDeclExpr(ExportDecl::Default(ref value)) => {
+                ExprDef::ExportDeclExpr(ExportDecl::Default(Box::new(self.expr(value, out))))
+            }
+            ExprDef::ReturnExpr(Some(ref value)) => ExprDef::ReturnExpr(Some(Box::new(self.expr(value, out)))),
+            ExprDef::ThrowExpr(ref value) => ExprDef::ThrowExpr(Box::new(self.expr(value, out))),
+            _ => {
+                let expr = self.expr(statement, out);
+                out.push(expr);
+                return;
+            }
+        };
+        out.push(at(statement, def));
+    }
+
+    /// Rewrite an expression so it has no `await` in it, adding the statements it needs to run first to `out`
+    fn expr(&mut self, expr: &Expr, out: &mut Vec<Expr>) -> Expr {
+        if !contains_await(expr) {
+            return expr.clone();
+        }
+        let def = match expr.def {
+            ExprDef::AwaitExpr(ref value) => {
+                let value = self.expr(value, out);
+                return self.hoist(at(expr, ExprDef::AwaitExpr(Box::new(value))), out);
+            }
+            ExprDef::BinOpExpr(ref op, ref a, ref b) => {
+                let mut operands = self.operands(&[a, b], out).into_iter();
+                ExprDef::BinOpExpr(op.clone(), Box::new(operands.next().unwrap()), Box::new(operands.next().unwrap()))
+            }
+            ExprDef::UnaryOpExpr(ref op, ref a) => ExprDef::UnaryOpExpr(op.clone(), Box::new(self.expr(a, out))),
+            ExprDef::TypeOfExpr(ref a) => ExprDef::TypeOfExpr(Box::new(self.expr(a, out))),
+            ExprDef::ImportCallExpr(ref a) => ExprDef::ImportCallExpr(Box::new(self.expr(a, out))),
+            ExprDef::GetConstFieldExpr(ref obj, ref field) => {
+                ExprDef::GetConstFieldExpr(Box::new(self.expr(obj, out)), field.clone())
+            }
+            ExprDef::GetFieldExpr(ref obj, ref field) => {
+                let mut operands = self.operands(&[obj, field], out).into_iter();
+                ExprDef::GetFieldExpr(Box::new(operands.next().unwrap()), Box::new(operands.next().unwrap()))
+            }
+            ExprDef::CallExpr(ref callee, ref args) => match callee.def {
+                // Methods keep being called on their object, so it is the `this` value
+                ExprDef::GetConstFieldExpr(ref obj, ref field) => {
+                    let mut operands = vec![obj.as_ref()];
+                    operands.extend(args.iter());
+                    let mut operands = self.operands(&operands, out).into_iter();
+                    let obj = operands.next().unwrap();
+                    let callee = at(callee, ExprDef::GetConstFieldExpr(Box::new(obj), field.clone()));
+                    ExprDef::CallExpr(Box::new(callee), operands.collect())
+                }
+                ExprDef::GetFieldExpr(ref obj, ref field) => {
+                    let mut operands = vec![obj.as_ref(), field.as_ref()];
+                    operands.extend(args.iter());
+                    let mut operands = self.operands(&operands, out).into_iter();
+                    let (obj, field) = (operands.next().unwrap(), operands.next().unwrap());
+                    let callee = at(callee, ExprDef::GetFieldExpr(Box::new(obj), Box::new(field)));
+                    ExprDef::CallExpr(Box::new(callee), operands.collect())
+                }
+                // Names are left alone, as they decide `this` inside `with` and whether `eval` is direct
+                ExprDef::LocalExpr(_) => {
+                    let args: Vec<&Expr> = args.iter().collect();
+                    ExprDef::CallExpr(callee.clone(), self.operands(&args, out))
+                }
+                _ => {
+                    let mut operands = vec![callee.as_ref()];
+                    operands.extend(args.iter());
+                    let mut operands = self.operands(&operands, out).into_iter();
+                    let callee = operands.next().unwrap();
+                    ExprDef::CallExpr(Box::new(callee), operands.collect())
+                }
+            },
+            ExprDef::ConstructExpr(ref callee, ref args) => {
+                let mut operands = vec![callee.as_ref()];
+                operands.extend(args.iter());
+                let mut operands = self.operands(&operands, out).into_iter();
+                let callee = operands.next().unwrap();
+                ExprDef::ConstructExpr(Box::new(callee), operands.collect())
+            }
+            ExprDef::ArrayDeclExpr(ref items) => {
+                let items: Vec<&Expr> = items.iter().collect();
+                ExprDef::ArrayDeclExpr(self.operands(&items, out))
+            }
+            ExprDef::ObjectDeclExpr(ref entries) => {
+                let values: Vec<&Expr> = entries.iter().map(|(_, value)| value).collect();
+                let values = self.operands(&values, out);
+                let entries = entries.iter().map(|(key, _)| key.clone()).zip(values).collect();
+                ExprDef::ObjectDeclExpr(Box::new(entries))
+            }
+            // The value of an assignment is worked out before the object and field it is assigned to
+            ExprDef::AssignExpr(ref target, ref value) => match target.def {
+                ExprDef::GetConstFieldExpr(ref obj, ref field) => {
+                    let mut operands = self.operands(&[value, obj], out).into_iter();
+                    let value = operands.next().unwrap();
+                    let target = at(target, ExprDef::GetConstFieldExpr(Box::new(operands.next().unwrap()), field.clone()));
+                    ExprDef::AssignExpr(Box::new(target), Box::new(value))
+                }
+                ExprDef::GetFieldExpr(ref obj, ref field) => {
+                    let mut operands = self.operands(&[value, obj, field], out).into_iter();
+                    let value = operands.next().unwrap();
+                    let (obj, field) = (operands.next().unwrap(), operands.next().unwrap());
+                    let target = at(target, ExprDef::GetFieldExpr(Box::new(obj), Box::new(field)));
+                    ExprDef::AssignExpr(Box::new(target), Box::new(value))
+                }
+                _ => ExprDef::AssignExpr(target.clone(), Box::new(self.expr(value, out))),
+            },
+            // A conditional with an `await` in one of its branches becomes an `if` statement setting a variable
+            ExprDef::IfExpr(ref cond, ref then, ref otherwise) => {
+                let cond = self.expr(cond, out);
+                let name = self.temp();
+                let branch = |normalizer: &mut Normalizer, value: Option<&Expr>| {
+                    let mut statements = Vec::new();
+                    let value = match value {
+                        Some(value) => normalizer.expr(value, &mut statements),
+                        None => at(expr, ExprDef::ConstExpr(Const::Undefined)),
+                    };
+                    statements.push(at(expr, ExprDef::VarDeclExpr(vec![(name.clone(), Some(value))])));
+                    Box::new(at(expr, ExprDef::BlockExpr(statements)))
+                };
+                let then = branch(self, Some(then));
+                let otherwise = branch(self, otherwise.as_deref());
+                out.push(at(expr, ExprDef::IfExpr(Box::new(cond), then, Some(otherwise))));
+                ExprDef::LocalExpr(name)
+            }
+            // Statements used as expressions, such as the body of an arrow function, are run for their effects
+            _ => {
+                self.statement(expr, out);
+                ExprDef::ConstExpr(Const::Undefined)
+            }
+        };
+        at(expr, def)
+    }
+
+    /// Rewrite operands which are worked out from left to right. The ones before the last `await` are kept in
+    /// variables, so they aren't worked out again after it
+    fn operands(&mut self, operands: &[&Expr], out: &mut Vec<Expr>) -> Vec<Expr> {
+        let last = match operands.iter().rposition(|operand| contains_await(operand)) {
+            Some(last) => last,
+            None => return operands.iter().map(|operand| (*operand).clone()).collect(),
+        };
+        let mut rewritten = Vec::with_capacity(operands.len());
+        for (i, operand) in operands.iter().enumerate() {
+            let operand = self.expr(operand, out);
+            rewritten.push(if i < last && !is_constant(&operand) {
+                self.hoist(operand, out)
+            } else {
+                operand
+            });
+        }
+        rewritten
+    }
+}
@@ -1,15 +1,31 @@
 use gc::Gc;
 
-use crate::{syntax::{lexer::Lexer, parser::Parser}, exec::{Executor, Interpreter}, js::value::ValueData};
+use crate::{syntax::{lexer::Lexer, parser::Parser}, exec::{Executor, Interpreter}, js::value::{ResultValue, ValueData}, realm::Realm};
 
 pub fn run_script(script: String) -> Gc<ValueData>{
-    let mut lexer = Lexer::new(&script);
+    let mut engine: Interpreter = Executor::new();
+    let result = forward(&mut engine, &script).unwrap();
+    engine.run_jobs().unwrap();
+
+    result
+}
+
+/// Run a script on an existing interpreter, keeping its global state and pending jobs
+pub fn forward(engine: &mut Interpreter, script: &str) -> ResultValue {
+    let mut lexer = Lexer::new(script);
     lexer.lex().unwrap();
     let tokens = lexer.tokens;
-    let expr = Parser::new(tokens).parse_all().unwrap();
+    let expr = Parser::new(tokens).with_source(script).parse_all().unwrap();
 
-    let mut engine: Interpreter = Executor::new();
-    let result = engine.run(&expr).unwrap();
+    engine.run(&expr)
+}
+
+/// Run a script at the top level of a realm, sharing the interpreter's jobs and modules with its other realms
+pub fn forward_in_realm(engine: &mut Interpreter, realm: &Realm, script: &str) -> ResultValue {
+    let mut lexer = Lexer::new(script);
+    lexer.lex().unwrap();
+    let tokens = lexer.tokens;
+    let expr = Parser::new(tokens).with_source(script).parse_all().unwrap();
 
-    return result
-}
\ No newline at end of file
+    engine.run_in_realm(realm, &expr)
+}
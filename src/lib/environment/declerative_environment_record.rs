@@ -6,7 +6,8 @@
 //! More info:  [ECMA-262 sec-declarative-environment-records](https://tc39.github.io/ecma262/#sec-declarative-environment-records)
 
 use crate::environment::environment_record_trait::EnvironmentRecordTrait;
-use crate::environment::lexical_environment::{Environment, EnvironmentType};
+use crate::environment::lexical_environment::{Environment, EnvironmentError, EnvironmentType};
+use crate::js::error::ErrorKind;
 use crate::js::value::{Value, ValueData};
 use gc::Gc;
 use std::collections::hash_map::HashMap;
@@ -24,6 +25,11 @@ pub struct DeclerativeEnvironmentRecordBinding {
     pub strict: bool,
 }
 
+/// The error for binding a name twice in the same scope
+pub fn already_declared(name: &str) -> EnvironmentError {
+    EnvironmentError::new(ErrorKind::SyntaxError, &format!("Identifier '{}' has already been declared", name))
+}
+
 /// A declarative Environment Record binds the set of identifiers defined by the
 /// declarations contained within its scope.
 #[derive(Trace, Finalize, Clone)]
@@ -37,10 +43,9 @@ impl EnvironmentRecordTrait for DeclerativeEnvironmentRecord {
         self.env_rec.contains_key(name)
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    fn create_mutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
         if self.env_rec.contains_key(&name) {
-            // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
+            return Err(already_declared(&name));
         }
 
         self.env_rec.insert(
@@ -52,12 +57,12 @@ impl EnvironmentRecordTrait for DeclerativeEnvironmentRecord {
                 strict: false,
             },
         );
+        Ok(())
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) {
+    fn create_immutable_binding(&mut self, name: String, strict: bool) -> Result<(), EnvironmentError> {
         if self.env_rec.contains_key(&name) {
-            // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
+            return Err(already_declared(&name));
         }
 
         self.env_rec.insert(
@@ -69,33 +74,33 @@ impl EnvironmentRecordTrait for DeclerativeEnvironmentRecord {
                 strict: strict,
             },
         );
+        Ok(())
     }
 
-    fn initialize_binding(&mut self, name: String, value: Value) {
+    fn initialize_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError> {
         match self.env_rec.get_mut(&name) {
             Some(ref mut record) => {
                 match record.value {
-                    Some(_) => {
-                        // TODO: change this when error handling comes into play
-                        panic!("Identifier {} has already been defined", name);
-                    }
+                    Some(_) => return Err(already_declared(&name)),
                     None => record.value = Some(value),
                 }
             }
             None => {}
         }
+        Ok(())
     }
 
-    fn set_mutable_binding(&mut self, name: String, value: Value, mut strict: bool) {
+    fn set_mutable_binding(&mut self, name: String, value: Value, mut strict: bool) -> Result<(), EnvironmentError> {
         if self.env_rec.get(&name).is_none() {
-            if strict == true {
-                // TODO: change this when error handling comes into play
-                panic!("Reference Error: Cannot set mutable binding for {}", name);
+            if strict {
+                return Err(EnvironmentError::new(
+                    ErrorKind::ReferenceError,
+                    &format!("{} is not defined", name),
+                ));
             }
 
-            self.create_mutable_binding(name.clone(), true);
-            self.initialize_binding(name.clone(), value);
-            return;
+            self.create_mutable_binding(name.clone(), true)?;
+            return self.initialize_binding(name, value);
         }
 
         let record: &mut DeclerativeEnvironmentRecordBinding = self.env_rec.get_mut(&name).unwrap();
@@ -103,27 +108,30 @@ impl EnvironmentRecordTrait for DeclerativeEnvironmentRecord {
             strict = true
         }
         if record.value.is_none() {
-            // TODO: change this when error handling comes into play
-            panic!("Reference Error: Cannot set mutable binding for {}", name);
+            return Err(EnvironmentError::new(
+                ErrorKind::ReferenceError,
+                &format!("Cannot access '{}' before initialization", name),
+            ));
         }
 
         if record.mutable {
             record.value = Some(value);
         } else {
             if strict {
-                // TODO: change this when error handling comes into play
-                panic!("TypeError: Cannot mutate an immutable binding {}", name);
+                return Err(EnvironmentError::new(ErrorKind::TypeError, "Assignment to constant variable."));
             }
         }
+        Ok(())
     }
 
-    fn get_binding_value(&self, name: String, _strict: bool) -> Value {
-        if self.env_rec.get(&name).is_some() && self.env_rec.get(&name).unwrap().value.is_some() {
-            let record: &DeclerativeEnvironmentRecordBinding = self.env_rec.get(&name).unwrap();
-            record.value.as_ref().unwrap().clone()
-        } else {
-            // TODO: change this when error handling comes into play
-            panic!("ReferenceError: Cannot get binding value for {}", name);
+    fn get_binding_value(&self, name: String, _strict: bool) -> Result<Value, EnvironmentError> {
+        match self.env_rec.get(&name) {
+            Some(&DeclerativeEnvironmentRecordBinding { value: Some(ref value), .. }) => Ok(value.clone()),
+            Some(_) => Err(EnvironmentError::new(
+                ErrorKind::ReferenceError,
+                &format!("Cannot access '{}' before initialization", name),
+            )),
+            None => Err(EnvironmentError::new(ErrorKind::ReferenceError, &format!("{} is not defined", name))),
         }
     }
 
@@ -144,6 +152,10 @@ impl EnvironmentRecordTrait for DeclerativeEnvironmentRecord {
         false
     }
 
+    fn get_this_binding(&self) -> Result<Value, EnvironmentError> {
+        Ok(Gc::new(ValueData::Undefined))
+    }
+
     fn has_super_binding(&self) -> bool {
         false
     }
@@ -153,7 +165,7 @@ impl EnvironmentRecordTrait for DeclerativeEnvironmentRecord {
     }
 
     fn get_outer_environment(&self) -> Option<Environment> {
-        None
+        self.outer_env.clone()
     }
 
     fn set_outer_environment(&mut self, env: Environment) {
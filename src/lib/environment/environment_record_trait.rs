@@ -8,7 +8,7 @@
 //!
 //! There are 5 Environment record kinds. They all have methods in common, these are implemented as a the `EnvironmentRecordTrait`
 //!
-use crate::environment::lexical_environment::{Environment, EnvironmentType};
+use crate::environment::lexical_environment::{Environment, EnvironmentError, EnvironmentType};
 use crate::js::value::Value;
 use gc::{Finalize, Trace};
 
@@ -22,30 +22,33 @@ pub trait EnvironmentRecordTrait: Trace + Finalize {
 
     /// Create a new but uninitialized mutable binding in an Environment Record. The String value N is the text of the bound name.
     /// If the Boolean argument deletion is true the binding may be subsequently deleted.
-    fn create_mutable_binding(&mut self, name: String, deletion: bool);
+    /// It is a SyntaxError if the name is already bound.
+    fn create_mutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError>;
 
     /// Create a new but uninitialized immutable binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// If strict is true then attempts to set it after it has been initialized will always throw an exception,
     /// regardless of the strict mode setting of operations that reference that binding.
-    fn create_immutable_binding(&mut self, name: String, strict: bool);
+    /// It is a SyntaxError if the name is already bound.
+    fn create_immutable_binding(&mut self, name: String, strict: bool) -> Result<(), EnvironmentError>;
 
     /// Set the value of an already existing but uninitialized binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// V is the value for the binding and is a value of any ECMAScript language type.
-    fn initialize_binding(&mut self, name: String, value: Value);
+    fn initialize_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError>;
 
     /// Set the value of an already existing mutable binding in an Environment Record.
     /// The String value `name` is the text of the bound name.
     /// value is the `value` for the binding and may be a value of any ECMAScript language type. S is a Boolean flag.
     /// If `strict` is true and the binding cannot be set throw a TypeError exception.
-    fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool);
+    fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) -> Result<(), EnvironmentError>;
 
     /// Returns the value of an already existing binding from an Environment Record.
     /// The String value N is the text of the bound name.
     /// S is used to identify references originating in strict mode code or that
     /// otherwise require strict mode reference semantics.
-    fn get_binding_value(&self, name: String, strict: bool) -> Value;
+    /// It is a ReferenceError if the binding hasn't been initialized.
+    fn get_binding_value(&self, name: String, strict: bool) -> Result<Value, EnvironmentError>;
 
     /// Delete a binding from an Environment Record.
     /// The String value name is the text of the bound name.
@@ -57,6 +60,10 @@ pub trait EnvironmentRecordTrait: Trace + Finalize {
     /// Return true if it does and false if it does not.
     fn has_this_binding(&self) -> bool;
 
+    /// Return the value of this Environment Record's `this` binding.
+    /// Only call this if `has_this_binding` is true.
+    fn get_this_binding(&self) -> Result<Value, EnvironmentError>;
+
     /// Determine if an Environment Record establishes a super method binding.
     /// Return true if it does and false if it does not.
     fn has_super_binding(&self) -> bool;
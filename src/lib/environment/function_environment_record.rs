@@ -8,9 +8,10 @@
 //! from within the function.
 //! More info:  https://tc39.github.io/ecma262/#sec-function-environment-records
 
-use crate::environment::declerative_environment_record::DeclerativeEnvironmentRecordBinding;
+use crate::environment::declerative_environment_record::{already_declared, DeclerativeEnvironmentRecordBinding};
 use crate::environment::environment_record_trait::EnvironmentRecordTrait;
-use crate::environment::lexical_environment::{Environment, EnvironmentType};
+use crate::environment::lexical_environment::{Environment, EnvironmentError, EnvironmentType};
+use crate::js::error::ErrorKind;
 use crate::js::value::{Value, ValueData};
 use gc::Gc;
 use std::collections::hash_map::HashMap;
@@ -51,40 +52,24 @@ pub struct FunctionEnvironmentRecord {
 }
 
 impl FunctionEnvironmentRecord {
-    pub fn bind_this_value(&mut self, value: Value) {
+    pub fn bind_this_value(&mut self, value: Value) -> Result<(), EnvironmentError> {
         match self.this_binding_status {
             // You can not bind an arrow function, their `this` value comes from the lexical scope above
-            BindingStatus::Lexical => {
-                // TODO: change this when error handling comes into play
-                panic!("Cannot bind to an arrow function!");
-            }
+            BindingStatus::Lexical => Err(EnvironmentError::new(
+                ErrorKind::ReferenceError,
+                "Arrow functions don't have their own 'this' to bind",
+            )),
             // You can not bind a function twice
             BindingStatus::Initialized => {
-                // TODO: change this when error handling comes into play
-                panic!("Reference Error: Cannot bind to an initialised function!");
+                Err(EnvironmentError::new(ErrorKind::ReferenceError, "Super constructor may only be called once"))
             }
-
             BindingStatus::Uninitialized => {
                 self.this_value = value;
                 self.this_binding_status = BindingStatus::Initialized;
+                Ok(())
             }
         }
     }
-
-    pub fn get_this_binding(&self) -> Value {
-        match self.this_binding_status {
-            BindingStatus::Lexical => {
-                // TODO: change this when error handling comes into play
-                panic!("There is no this for a lexical function record");
-            }
-            BindingStatus::Uninitialized => {
-                // TODO: change this when error handling comes into play
-                panic!("Reference Error: Unitialised binding for this function");
-            }
-
-            BindingStatus::Initialized => self.this_value.clone(),
-        }
-    }
 }
 
 impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
@@ -94,10 +79,9 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         self.env_rec.contains_key(name)
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    fn create_mutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
         if self.env_rec.contains_key(&name) {
-            // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
+            return Err(already_declared(&name));
         }
 
         self.env_rec.insert(
@@ -109,12 +93,12 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
                 strict: false,
             },
         );
+        Ok(())
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) {
+    fn create_immutable_binding(&mut self, name: String, strict: bool) -> Result<(), EnvironmentError> {
         if self.env_rec.contains_key(&name) {
-            // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
+            return Err(already_declared(&name));
         }
 
         self.env_rec.insert(
@@ -126,33 +110,33 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
                 strict: strict,
             },
         );
+        Ok(())
     }
 
-    fn initialize_binding(&mut self, name: String, value: Value) {
+    fn initialize_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError> {
         match self.env_rec.get_mut(&name) {
             Some(ref mut record) => {
                 match record.value {
-                    Some(_) => {
-                        // TODO: change this when error handling comes into play
-                        panic!("Identifier {} has already been defined", name);
-                    }
+                    Some(_) => return Err(already_declared(&name)),
                     None => record.value = Some(value),
                 }
             }
             None => {}
         }
+        Ok(())
     }
 
-    fn set_mutable_binding(&mut self, name: String, value: Value, mut strict: bool) {
+    fn set_mutable_binding(&mut self, name: String, value: Value, mut strict: bool) -> Result<(), EnvironmentError> {
         if self.env_rec.get(&name).is_none() {
-            if strict == true {
-                // TODO: change this when error handling comes into play
-                panic!("Reference Error: Cannot set mutable binding for {}", name);
+            if strict {
+                return Err(EnvironmentError::new(
+                    ErrorKind::ReferenceError,
+                    &format!("{} is not defined", name),
+                ));
             }
 
-            self.create_mutable_binding(name.clone(), true);
-            self.initialize_binding(name.clone(), value);
-            return;
+            self.create_mutable_binding(name.clone(), true)?;
+            return self.initialize_binding(name, value);
         }
 
         let record: &mut DeclerativeEnvironmentRecordBinding = self.env_rec.get_mut(&name).unwrap();
@@ -161,27 +145,30 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         }
 
         if record.value.is_none() {
-            // TODO: change this when error handling comes into play
-            panic!("Reference Error: Cannot set mutable binding for {}", name);
+            return Err(EnvironmentError::new(
+                ErrorKind::ReferenceError,
+                &format!("Cannot access '{}' before initialization", name),
+            ));
         }
 
         if record.mutable {
             record.value = Some(value);
         } else {
             if strict {
-                // TODO: change this when error handling comes into play
-                panic!("TypeError: Cannot mutate an immutable binding {}", name);
+                return Err(EnvironmentError::new(ErrorKind::TypeError, "Assignment to constant variable."));
             }
         }
+        Ok(())
     }
 
-    fn get_binding_value(&self, name: String, _strict: bool) -> Value {
-        if self.env_rec.get(&name).is_some() && self.env_rec.get(&name).unwrap().value.is_some() {
-            let record: &DeclerativeEnvironmentRecordBinding = self.env_rec.get(&name).unwrap();
-            record.value.as_ref().unwrap().clone()
-        } else {
-            // TODO: change this when error handling comes into play
-            panic!("ReferenceError: Cannot get binding value for {}", name);
+    fn get_binding_value(&self, name: String, _strict: bool) -> Result<Value, EnvironmentError> {
+        match self.env_rec.get(&name) {
+            Some(&DeclerativeEnvironmentRecordBinding { value: Some(ref value), .. }) => Ok(value.clone()),
+            Some(_) => Err(EnvironmentError::new(
+                ErrorKind::ReferenceError,
+                &format!("Cannot access '{}' before initialization", name),
+            )),
+            None => Err(EnvironmentError::new(ErrorKind::ReferenceError, &format!("{} is not defined", name))),
         }
     }
 
@@ -218,6 +205,20 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         }
     }
 
+    fn get_this_binding(&self) -> Result<Value, EnvironmentError> {
+        match self.this_binding_status {
+            BindingStatus::Lexical => Err(EnvironmentError::new(
+                ErrorKind::ReferenceError,
+                "Arrow functions don't have their own 'this'",
+            )),
+            BindingStatus::Uninitialized => Err(EnvironmentError::new(
+                ErrorKind::ReferenceError,
+                "Must call super constructor before accessing 'this'",
+            )),
+            BindingStatus::Initialized => Ok(self.this_value.clone()),
+        }
+    }
+
     fn with_base_object(&self) -> Value {
         Gc::new(ValueData::Undefined)
     }
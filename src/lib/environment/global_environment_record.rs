@@ -7,10 +7,11 @@
 //! that occur within a Script.
 //! More info:  https://tc39.github.io/ecma262/#sec-global-environment-records
 
-use crate::environment::declerative_environment_record::DeclerativeEnvironmentRecord;
+use crate::environment::declerative_environment_record::{already_declared, DeclerativeEnvironmentRecord};
 use crate::environment::environment_record_trait::EnvironmentRecordTrait;
-use crate::environment::lexical_environment::{Environment, EnvironmentType};
+use crate::environment::lexical_environment::{Environment, EnvironmentError, EnvironmentType};
 use crate::environment::object_environment_record::ObjectEnvironmentRecord;
+use crate::js::error::ErrorKind;
 use crate::js::value::{Value, ValueData};
 use gc::Gc;
 use std::collections::HashSet;
@@ -24,10 +25,6 @@ pub struct GlobalEnvironmentRecord {
 }
 
 impl GlobalEnvironmentRecord {
-    pub fn get_this_binding(&self) -> Value {
-        return self.global_this_binding.clone();
-    }
-
     pub fn has_var_decleration(&self, name: &String) -> bool {
         return self.var_names.contains(name);
     }
@@ -50,20 +47,21 @@ impl GlobalEnvironmentRecord {
         }
     }
 
-    pub fn create_global_var_binding(&mut self, name: String, deletion: bool) {
+    pub fn create_global_var_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
         let obj_rec = &mut self.object_record;
         let global_object = &obj_rec.bindings;
         let has_property = global_object.has_field(name.clone());
         let extensible = global_object.is_extensible();
         if !has_property && extensible {
-            obj_rec.create_mutable_binding(name.clone(), deletion);
-            obj_rec.initialize_binding(name.clone(), Gc::new(ValueData::Undefined));
+            obj_rec.create_mutable_binding(name.clone(), deletion)?;
+            obj_rec.initialize_binding(name.clone(), Gc::new(ValueData::Undefined))?;
         }
 
         let var_declared_names = &mut self.var_names;
         if !var_declared_names.contains(&name) {
             var_declared_names.insert(name.clone());
         }
+        Ok(())
     }
 
     pub fn create_global_function_binding(&mut self, name: String, value: Value, deletion: bool) {
@@ -102,26 +100,24 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         self.object_record.has_binding(name)
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    fn create_mutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
         if self.declerative_record.has_binding(&name) {
-            // TODO: change to exception
-            panic!("Binding already exists!");
+            return Err(already_declared(&name));
         }
 
         self.declerative_record
             .create_mutable_binding(name.clone(), deletion)
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) {
+    fn create_immutable_binding(&mut self, name: String, strict: bool) -> Result<(), EnvironmentError> {
         if self.declerative_record.has_binding(&name) {
-            // TODO: change to exception
-            panic!("Binding already exists!");
+            return Err(already_declared(&name));
         }
         self.declerative_record
             .create_immutable_binding(name.clone(), strict)
     }
 
-    fn initialize_binding(&mut self, name: String, value: Value) {
+    fn initialize_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError> {
         if self.declerative_record.has_binding(&name) {
             // TODO: assert binding is in the object environment record
             return self
@@ -129,10 +125,10 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
                 .initialize_binding(name.clone(), value);
         }
 
-        panic!("Should not initialized binding without creating first.");
+        Err(EnvironmentError::new(ErrorKind::ReferenceError, &format!("{} is not defined", name)))
     }
 
-    fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) {
+    fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) -> Result<(), EnvironmentError> {
         if self.declerative_record.has_binding(&name) {
             return self
                 .declerative_record
@@ -141,7 +137,7 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         self.object_record.set_mutable_binding(name, value, strict)
     }
 
-    fn get_binding_value(&self, name: String, strict: bool) -> Value {
+    fn get_binding_value(&self, name: String, strict: bool) -> Result<Value, EnvironmentError> {
         if self.declerative_record.has_binding(&name) {
             return self.declerative_record.get_binding_value(name, strict);
         }
@@ -171,6 +167,10 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         true
     }
 
+    fn get_this_binding(&self) -> Result<Value, EnvironmentError> {
+        Ok(self.global_this_binding.clone())
+    }
+
     fn has_super_binding(&self) -> bool {
         false
     }
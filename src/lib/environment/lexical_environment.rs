@@ -10,7 +10,9 @@ use crate::environment::declerative_environment_record::DeclerativeEnvironmentRe
 use crate::environment::environment_record_trait::EnvironmentRecordTrait;
 use crate::environment::function_environment_record::{BindingStatus, FunctionEnvironmentRecord};
 use crate::environment::global_environment_record::GlobalEnvironmentRecord;
+use crate::environment::module_environment_record::{ImportBindings, ModuleEnvironmentRecord};
 use crate::environment::object_environment_record::ObjectEnvironmentRecord;
+use crate::js::error::ErrorKind;
 use crate::js::value::{Value, ValueData};
 use gc::{Gc, GcCell};
 use std::collections::hash_map::HashMap;
@@ -30,24 +32,33 @@ pub enum EnvironmentType {
     Function,
     Global,
     Object,
+    Module,
 }
 
 pub struct LexicalEnvironment {
     environment_stack: VecDeque<Environment>,
 }
 
-/// An error that occurred during lexing or compiling of the source input.
+/// An error from using a binding in a way that isn't allowed, such as assigning to a constant.
+/// The interpreter throws it as an error object of the given kind.
 #[derive(Debug, Clone)]
 pub struct EnvironmentError {
+    kind: ErrorKind,
     details: String,
 }
 
 impl EnvironmentError {
-    pub fn new(msg: &str) -> EnvironmentError {
+    pub fn new(kind: ErrorKind, msg: &str) -> EnvironmentError {
         EnvironmentError {
+            kind,
             details: msg.to_string(),
         }
     }
+
+    /// The kind of error object to throw for this error
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
 }
 
 impl fmt::Display for EnvironmentError {
@@ -68,8 +79,7 @@ impl error::Error for EnvironmentError {
 }
 
 impl LexicalEnvironment {
-    pub fn new(global: Value) -> LexicalEnvironment {
-        let global_env = new_global_environment(global.clone(), global.clone());
+    pub fn new(global_env: Environment) -> LexicalEnvironment {
         let mut lexical_env = LexicalEnvironment {
             environment_stack: VecDeque::new(),
         };
@@ -79,9 +89,13 @@ impl LexicalEnvironment {
         lexical_env
     }
 
+    /// Push a new environment onto the stack.
+    /// Environments which don't already have an outer environment are linked to the current one
     pub fn push(&mut self, env: Environment) {
-        let current_env: Environment = self.get_current_environment().clone();
-        env.borrow_mut().set_outer_environment(current_env);
+        if env.borrow().get_outer_environment().is_none() {
+            let current_env: Environment = self.get_current_environment().clone();
+            env.borrow_mut().set_outer_environment(current_env);
+        }
         self.environment_stack.push_back(env);
     }
 
@@ -89,32 +103,94 @@ impl LexicalEnvironment {
         self.environment_stack.pop_back();
     }
 
+    /// The global environment of the realm the current code is running in, which is the outermost environment
+    /// of the current one. Functions from other realms close over their own realm's global environment
+    pub fn get_global_environment(&self) -> Environment {
+        let mut env = self.get_current_environment_ref().clone();
+        loop {
+            let outer = env.borrow().get_outer_environment();
+            match outer {
+                Some(outer) => env = outer,
+                None => return env,
+            }
+        }
+    }
+
     pub fn get_global_object(&self) -> Option<Value> {
-        let global = &self.environment_stack[0];
-        global.borrow().get_global_object()
+        self.get_current_environment_ref().borrow().get_global_object()
     }
 
-    pub fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    pub fn create_mutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
         self.get_current_environment()
             .borrow_mut()
             .create_mutable_binding(name, deletion)
     }
 
-    pub fn create_immutable_binding(&mut self, name: String, deletion: bool) {
+    pub fn create_immutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
         self.get_current_environment()
             .borrow_mut()
             .create_immutable_binding(name, deletion)
     }
 
 
-    pub fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) {
+    pub fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) -> Result<(), EnvironmentError> {
         let env = self.get_current_environment();
-        env.borrow_mut().set_mutable_binding(name, value, strict);
+        env.borrow_mut().set_mutable_binding(name, value, strict)
+    }
+
+    /// Assign to an existing binding, searching outwards from the current environment.
+    /// If no environment has the binding, it is created on the global environment
+    pub fn assign_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError> {
+        let mut env: Option<Environment> = Some(self.get_current_environment().clone());
+        while let Some(current) = env {
+            if current.borrow().has_binding(&name) {
+                return current.borrow_mut().set_mutable_binding(name, value, false);
+            }
+            env = current.borrow().get_outer_environment();
+        }
+        let global = self.get_global_environment();
+        global.borrow_mut().create_mutable_binding(name.clone(), true)?;
+        let initialized = global.borrow_mut().initialize_binding(name, value);
+        initialized
     }
 
-    pub fn initialize_binding(&mut self, name: String, value: Value) {
+    pub fn initialize_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError> {
         let env = self.get_current_environment();
-        env.borrow_mut().initialize_binding(name, value);
+        env.borrow_mut().initialize_binding(name, value)
+    }
+
+    /// The environment `var` declarations are bound in: the nearest function, module or global environment,
+    /// skipping the declarative environments made for `eval` code
+    /// https://tc39.es/ecma262/#table-additional-state-components-for-ecmascript-code-execution-contexts
+    pub fn get_variable_environment(&self) -> Environment {
+        let mut env = self.get_current_environment_ref().clone();
+        loop {
+            let outer = match env.borrow().get_environment_type() {
+                EnvironmentType::Declerative | EnvironmentType::Object => env.borrow().get_outer_environment(),
+                _ => None,
+            };
+            match outer {
+                Some(outer) => env = outer,
+                None => return env,
+            }
+        }
+    }
+
+    /// Bind a `var` in the variable environment, or assign to it if it was already declared.
+    /// Without a value, an existing binding keeps its value and a new one starts as undefined
+    pub fn declare_var(&mut self, name: String, value: Option<Value>) -> Result<(), EnvironmentError> {
+        let env = self.get_variable_environment();
+        if env.borrow().has_binding(&name) {
+            return match value {
+                Some(value) => env.borrow_mut().set_mutable_binding(name, value, false),
+                None => Ok(()),
+            };
+        }
+        env.borrow_mut().create_mutable_binding(name.clone(), false)?;
+        let initialized = env
+            .borrow_mut()
+            .initialize_binding(name, value.unwrap_or_else(|| Gc::new(ValueData::Undefined)));
+        initialized
     }
 
     /// get_current_environment_ref is used when you only need to borrow the environment
@@ -132,7 +208,38 @@ impl LexicalEnvironment {
         self.environment_stack.back_mut().unwrap()
     }
 
-    pub fn get_binding_value(&mut self, name: String) -> Value {
+    /// The `this` value of the nearest environment which has one, searching outwards from the current environment
+    /// https://tc39.es/ecma262/#sec-getthisenvironment
+    pub fn get_this_binding(&self) -> Result<Value, EnvironmentError> {
+        let mut env: Option<Environment> = Some(self.get_current_environment_ref().clone());
+        while let Some(current) = env {
+            if current.borrow().has_this_binding() {
+                return current.borrow().get_this_binding();
+            }
+            env = current.borrow().get_outer_environment();
+        }
+        Ok(Gc::new(ValueData::Undefined))
+    }
+
+    /// Returns true if the current environment has a binding for the name, not looking at outer environments
+    pub fn has_own_binding(&self, name: &str) -> bool {
+        self.get_current_environment_ref().borrow().has_binding(&name.to_string())
+    }
+
+    /// Returns true if the name resolves to a binding in this environment or one of its outer environments
+    pub fn has_binding(&self, name: &str) -> bool {
+        let name = name.to_string();
+        let mut env: Option<Environment> = Some(self.get_current_environment_ref().clone());
+        while let Some(current) = env {
+            if current.borrow().has_binding(&name) {
+                return true;
+            }
+            env = current.borrow().get_outer_environment();
+        }
+        false
+    }
+
+    pub fn get_binding_value(&mut self, name: String) -> Result<Value, EnvironmentError> {
         let env: Environment = self.get_current_environment().clone();
         let borrowed_env = env.borrow();
         let result = borrowed_env.has_binding(&name);
@@ -151,7 +258,7 @@ impl LexicalEnvironment {
             }
         }
 
-        Gc::new(ValueData::Undefined)
+        Ok(Gc::new(ValueData::Undefined))
     }
 }
 
@@ -166,34 +273,53 @@ pub fn new_declerative_environment(env: Option<Environment>) -> Environment {
     Gc::new(GcCell::new(boxed_env))
 }
 
+/// Make the environment for a call to `f`, bound to the `this` value it was called with.
+/// Arrow functions have no `this` of their own, so they pass `None` and `this` is looked up in the outer environment
 pub fn new_function_environment(
     f: Value,
+    this: Option<Value>,
     new_target: Value,
     outer: Option<Environment>,
 ) -> Environment {
     debug_assert!(f.is_function());
-    debug_assert!(new_target.is_object() || new_target.is_undefined());
-    Gc::new(GcCell::new(Box::new(FunctionEnvironmentRecord {
+    debug_assert!(new_target.is_object() || new_target.is_function() || new_target.is_undefined());
+    let mut env = FunctionEnvironmentRecord {
         env_rec: HashMap::new(),
         function_object: f.clone(),
-        this_binding_status: BindingStatus::Uninitialized, // hardcoding to unitialized for now until short functions are properly supported
+        this_binding_status: BindingStatus::Lexical,
         home_object: Gc::new(ValueData::Undefined),
         new_target: new_target,
         outer_env: outer, // this will come from Environment set as a private property of F - https://tc39.github.io/ecma262/#sec-ecmascript-function-objects
         this_value: Gc::new(ValueData::Undefined), // TODO: this_value should start as an Option as its not always there to begin with
-    })))
+    };
+    if let Some(this) = this {
+        env.this_binding_status = BindingStatus::Uninitialized;
+        env.bind_this_value(this).expect("a new function environment can bind `this`");
+    }
+    Gc::new(GcCell::new(Box::new(env)))
+}
+
+/// Make the environment for a module, along with the table of import bindings to fill in when the module is linked
+pub fn new_module_environment(outer: Option<Environment>) -> (Environment, ImportBindings) {
+    let import_bindings: ImportBindings = Gc::new(GcCell::new(HashMap::new()));
+    let env: Environment = Gc::new(GcCell::new(Box::new(ModuleEnvironmentRecord {
+        declerative_record: DeclerativeEnvironmentRecord {
+            env_rec: HashMap::new(),
+            outer_env: outer,
+        },
+        import_bindings: import_bindings.clone(),
+    })));
+    (env, import_bindings)
 }
 
-pub fn new_object_environment(object: Value, environment: Option<Environment>) -> Environment {
+/// Make an environment whose bindings are the properties of an object.
+/// `with` statements make them with `with_environment` set, so unqualified calls get the object as `this`
+/// https://tc39.es/ecma262/#sec-newobjectenvironment
+pub fn new_object_environment(object: Value, with_environment: bool, environment: Option<Environment>) -> Environment {
     Gc::new(GcCell::new(Box::new(ObjectEnvironmentRecord {
         bindings: object,
         outer_env: environment,
-        /// Object Environment Records created for with statements (13.11)
-        /// can provide their binding object as an implicit this value for use in function calls.
-        /// The capability is controlled by a withEnvironment Boolean value that is associated
-        /// with each object Environment Record. By default, the value of withEnvironment is false
-        /// for any object Environment Record.
-        with_environment: false,
+        with_environment,
     })))
 }
 
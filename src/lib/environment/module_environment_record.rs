@@ -0,0 +1,108 @@
+//! # Module Environment Records
+//!
+//! A module Environment Record is a declarative Environment Record that is used to represent
+//! the outer scope of an ECMAScript Module.
+//! In additional to normal mutable and immutable bindings, module Environment Records also provide
+//! immutable import bindings which provide indirect access to a target binding that exists in another Environment Record.
+//! More info:  https://tc39.github.io/ecma262/#sec-module-environment-records
+
+use crate::environment::declerative_environment_record::DeclerativeEnvironmentRecord;
+use crate::environment::environment_record_trait::EnvironmentRecordTrait;
+use crate::environment::lexical_environment::{Environment, EnvironmentError, EnvironmentType};
+use crate::js::error::ErrorKind;
+use crate::js::value::{Value, ValueData};
+use gc::{Gc, GcCell};
+use std::collections::hash_map::HashMap;
+
+/// An indirect binding to a name in another module's environment
+#[derive(Trace, Finalize, Clone)]
+pub struct ImportBinding {
+    /// The environment of the module which exports the binding
+    pub module: Environment,
+    /// The name of the binding in that environment
+    pub name: String,
+}
+
+/// The import bindings of a module.
+/// They are shared with the module record, as imports can only be bound once every module in a cycle has an environment.
+pub type ImportBindings = Gc<GcCell<HashMap<String, ImportBinding>>>;
+
+/// https://tc39.github.io/ecma262/#sec-module-environment-records
+#[derive(Trace, Finalize, Clone)]
+pub struct ModuleEnvironmentRecord {
+    pub declerative_record: DeclerativeEnvironmentRecord,
+    pub import_bindings: ImportBindings,
+}
+
+impl EnvironmentRecordTrait for ModuleEnvironmentRecord {
+    fn has_binding(&self, name: &String) -> bool {
+        self.import_bindings.borrow().contains_key(name) || self.declerative_record.has_binding(name)
+    }
+
+    fn create_mutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
+        self.declerative_record.create_mutable_binding(name, deletion)
+    }
+
+    fn create_immutable_binding(&mut self, name: String, strict: bool) -> Result<(), EnvironmentError> {
+        self.declerative_record.create_immutable_binding(name, strict)
+    }
+
+    fn initialize_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError> {
+        self.declerative_record.initialize_binding(name, value)
+    }
+
+    fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) -> Result<(), EnvironmentError> {
+        if self.import_bindings.borrow().contains_key(&name) {
+            return Err(EnvironmentError::new(ErrorKind::TypeError, "Assignment to constant variable."));
+        }
+        self.declerative_record.set_mutable_binding(name, value, strict)
+    }
+
+    /// Import bindings are live: they read the current value from the exporting module's environment
+    fn get_binding_value(&self, name: String, strict: bool) -> Result<Value, EnvironmentError> {
+        let import = self.import_bindings.borrow().get(&name).cloned();
+        match import {
+            Some(import) => import.module.borrow().get_binding_value(import.name.clone(), true),
+            None => self.declerative_record.get_binding_value(name, strict),
+        }
+    }
+
+    /// Module Environment Records are only used within strict code,
+    /// so deleting a binding is never allowed
+    fn delete_binding(&mut self, _name: String) -> bool {
+        false
+    }
+
+    /// Module Environment Records provide a `this` binding, which is always undefined
+    fn has_this_binding(&self) -> bool {
+        true
+    }
+
+    fn get_this_binding(&self) -> Result<Value, EnvironmentError> {
+        Ok(Gc::new(ValueData::Undefined))
+    }
+
+    fn has_super_binding(&self) -> bool {
+        false
+    }
+
+    fn with_base_object(&self) -> Value {
+        self.declerative_record.with_base_object()
+    }
+
+    fn get_outer_environment(&self) -> Option<Environment> {
+        self.declerative_record.get_outer_environment()
+    }
+
+    fn set_outer_environment(&mut self, env: Environment) {
+        self.declerative_record.set_outer_environment(env)
+    }
+
+    fn get_environment_type(&self) -> EnvironmentType {
+        EnvironmentType::Module
+    }
+
+    fn get_global_object(&self) -> Option<Value> {
+        self.declerative_record.get_global_object()
+    }
+}
@@ -7,8 +7,10 @@
 //! More info:  [Object Records](https://tc39.github.io/ecma262/#sec-object-environment-records)
 
 use crate::environment::environment_record_trait::EnvironmentRecordTrait;
-use crate::environment::lexical_environment::{Environment, EnvironmentType};
+use crate::environment::lexical_environment::{Environment, EnvironmentError, EnvironmentType};
+use crate::js::error::ErrorKind;
 use crate::js::object::Property;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
 use crate::js::value::{Value, ValueData};
 use gc::Gc;
 
@@ -28,11 +30,13 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
             return true;
         }
 
-        // TODO: implement unscopables
-        true
+        // `with` statements don't see the properties named in the object's `Symbol.unscopables`.
+        // The interpreter checks this itself for objects with getters or proxies, see `Interpreter::resolve_binding`
+        let unscopables = self.bindings.get_field(Symbol::well_known(WellKnownSymbol::Unscopables));
+        !(unscopables.is_object() && unscopables.get_field(name.to_string()).is_true())
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    fn create_mutable_binding(&mut self, name: String, deletion: bool) -> Result<(), EnvironmentError> {
         // TODO: could save time here and not bother generating a new undefined object,
         // only for it to be replace with the real value later. We could just add the name to a Vector instead
         let bindings = &mut self.bindings;
@@ -42,39 +46,37 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         prop.writable = true;
         prop.configurable = deletion;
         bindings.set_prop(name, prop);
+        Ok(())
     }
 
-    fn create_immutable_binding(&mut self, _name: String, _strict: bool) {
+    fn create_immutable_binding(&mut self, _name: String, _strict: bool) -> Result<(), EnvironmentError> {
         unimplemented!()
     }
 
-    fn initialize_binding(&mut self, name: String, value: Value) {
+    fn initialize_binding(&mut self, name: String, value: Value) -> Result<(), EnvironmentError> {
         // We should never need to check if a binding has been created,
         // As all calls to create_mutable_binding are followed by initialized binding
         // The below is just a check.
         debug_assert!(self.has_binding(&name));
-        return self.set_mutable_binding(name, value, false);
+        self.set_mutable_binding(name, value, false)
     }
 
-    fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) {
-        debug_assert!(value.is_object() || value.is_function());
-
+    fn set_mutable_binding(&mut self, name: String, value: Value, strict: bool) -> Result<(), EnvironmentError> {
         let bindings = &mut self.bindings;
         bindings.update_prop(name, Some(value.clone()), None, None, Some(strict));
+        Ok(())
     }
 
-    fn get_binding_value(&self, name: String, strict: bool) -> Value {
+    fn get_binding_value(&self, name: String, strict: bool) -> Result<Value, EnvironmentError> {
         if self.bindings.has_field(name.clone()) {
-            return self.bindings.get_field(name);
+            return Ok(self.bindings.get_field(name));
         }
 
         if !strict {
-            return Gc::new(ValueData::Undefined);
+            return Ok(Gc::new(ValueData::Undefined));
         }
 
-        // TODO: throw error here
-        // Error handling not implemented yet
-        Gc::new(ValueData::Undefined)
+        Err(EnvironmentError::new(ErrorKind::ReferenceError, &format!("{} is not defined", name)))
     }
 
     fn delete_binding(&mut self, name: String) -> bool {
@@ -86,6 +88,10 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         false
     }
 
+    fn get_this_binding(&self) -> Result<Value, EnvironmentError> {
+        Ok(Gc::new(ValueData::Undefined))
+    }
+
     fn has_super_binding(&self) -> bool {
         false
     }
@@ -112,7 +118,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     }
 
     fn get_environment_type(&self) -> EnvironmentType {
-        return EnvironmentType::Function;
+        EnvironmentType::Object
     }
 
     fn get_global_object(&self) -> Option<Value> {
@@ -0,0 +1,228 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::exec::Interpreter;
+use crate::js::value::{Value, ValueData};
+use gc::Gc;
+
+/// A callback registered with `setTimeout` or `setInterval`
+pub struct Timer {
+    /// The function to call when the timer fires
+    pub callback: Value,
+    /// The extra arguments passed to `setTimeout`/`setInterval`
+    pub args: Vec<Value>,
+    /// The repeat delay in milliseconds, if this timer was made by `setInterval`
+    pub interval: Option<u64>,
+}
+
+/// The timers waiting to fire, ordered by due time and then by creation order
+pub struct TimerQueue {
+    /// The current time of the event loop, in milliseconds since it started
+    now: u64,
+    /// The id given to the next timer, ids start at 1 so they are always truthy
+    next_id: u64,
+    /// Increases every time a timer is scheduled, so timers due at the same time fire in order
+    next_seq: u64,
+    /// (due time, sequence number, timer id)
+    heap: BinaryHeap<Reverse<(u64, u64, u64)>>,
+    /// Timers that have not been cleared, by id
+    timers: HashMap<u64, Timer>,
+}
+
+impl TimerQueue {
+    /// Make an empty timer queue starting at time 0
+    pub fn new() -> Self {
+        TimerQueue {
+            now: 0,
+            next_id: 1,
+            next_seq: 0,
+            heap: BinaryHeap::new(),
+            timers: HashMap::new(),
+        }
+    }
+
+    /// The current time in milliseconds
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Add a timer which fires after `delay` milliseconds, returning its id.
+    /// Like browsers and Node, delays below 1ms are clamped to 1ms.
+    pub fn add(&mut self, timer: Timer, delay: u64) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.timers.insert(id, timer);
+        self.schedule(id, delay);
+        id
+    }
+
+    /// Remove a timer so it doesn't fire, unknown ids are ignored
+    pub fn clear(&mut self, id: u64) {
+        self.timers.remove(&id);
+    }
+
+    /// Returns true if no timers are waiting to fire
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// The due time of the next live timer
+    pub fn next_due(&mut self) -> Option<u64> {
+        self.discard_cleared();
+        self.heap.peek().map(|Reverse((due, _, _))| *due)
+    }
+
+    fn schedule(&mut self, id: u64, delay: u64) {
+        let due = self.now + delay.max(1);
+        self.heap.push(Reverse((due, self.next_seq, id)));
+        self.next_seq += 1;
+    }
+
+    /// Drop heap entries for timers which have been cleared
+    fn discard_cleared(&mut self) {
+        while let Some(Reverse((_, _, id))) = self.heap.peek() {
+            if self.timers.contains_key(id) {
+                break;
+            }
+            self.heap.pop();
+        }
+    }
+
+    /// Take the next timer which is due at or before `now`, rescheduling it if it is an interval
+    fn pop_due(&mut self) -> Option<(Value, Vec<Value>)> {
+        let due = self.next_due()?;
+        if due > self.now {
+            return None;
+        }
+        let Reverse((_, _, id)) = self.heap.pop()?;
+        let timer = self.timers.get(&id)?;
+        let task = (timer.callback.clone(), timer.args.clone());
+        match timer.interval {
+            Some(interval) => self.schedule(id, interval),
+            None => {
+                self.timers.remove(&id);
+            }
+        }
+        Some(task)
+    }
+}
+
+impl Default for TimerQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where the event loop gets the time from
+pub enum Clock {
+    /// Real time, measured from when the event loop was made
+    System(Instant),
+    /// Time which only moves when the event loop is advanced, for deterministic tests
+    Fake,
+}
+
+/// A host event loop which runs timer callbacks (macrotasks) in order,
+/// draining the job queue (microtasks) after the script and after every callback
+pub struct EventLoop {
+    interpreter: Interpreter,
+    clock: Clock,
+}
+
+impl EventLoop {
+    /// Make an event loop driven by the system clock
+    pub fn new(interpreter: Interpreter) -> Self {
+        EventLoop {
+            interpreter,
+            clock: Clock::System(Instant::now()),
+        }
+    }
+
+    /// Make an event loop with a fake clock, which only moves forward through `advance`
+    /// or when `run` skips ahead to the next timer
+    pub fn with_fake_clock(interpreter: Interpreter) -> Self {
+        EventLoop {
+            interpreter,
+            clock: Clock::Fake,
+        }
+    }
+
+    /// The interpreter driven by this event loop, with the time of its timers brought up to date
+    pub fn interpreter(&mut self) -> &mut Interpreter {
+        self.sync_clock();
+        &mut self.interpreter
+    }
+
+    /// The current time of the event loop in milliseconds
+    pub fn now(&mut self) -> u64 {
+        self.sync_clock();
+        self.interpreter.timers().now()
+    }
+
+    /// Returns true if there are pending jobs or timers
+    pub fn has_pending_work(&mut self) -> bool {
+        self.interpreter.has_pending_jobs() || !self.interpreter.timers().is_empty()
+    }
+
+    /// Move time forward by `ms` milliseconds, running every timer which becomes due on the way.
+    /// With the system clock this sleeps for the given time.
+    pub fn advance(&mut self, ms: u64) -> Result<(), Value> {
+        self.interpreter.run_jobs()?;
+        let target = self.now() + ms;
+        while let Some(due) = self.interpreter.timers().next_due() {
+            if due > target {
+                break;
+            }
+            self.wait_until(due);
+            self.run_due_timers()?;
+        }
+        self.wait_until(target);
+        Ok(())
+    }
+
+    /// Run until no jobs or timers remain.
+    /// With the fake clock, time skips straight to the next timer instead of waiting.
+    pub fn run(&mut self) -> Result<(), Value> {
+        self.interpreter.run_jobs()?;
+        while let Some(due) = self.interpreter.timers().next_due() {
+            self.wait_until(due);
+            self.run_due_timers()?;
+        }
+        Ok(())
+    }
+
+    /// Run each timer that is due, draining the job queue after each one
+    fn run_due_timers(&mut self) -> Result<(), Value> {
+        while let Some((callback, args)) = self.interpreter.timers().pop_due() {
+            let undefined = Gc::new(ValueData::Undefined);
+            self.interpreter.call(&callback, &undefined, args)?;
+            self.interpreter.run_jobs()?;
+        }
+        Ok(())
+    }
+
+    /// Wait until the event loop time reaches `time`, sleeping if the system clock is used
+    fn wait_until(&mut self, time: u64) {
+        match self.clock {
+            Clock::System(start) => {
+                let elapsed = start.elapsed().as_millis() as u64;
+                if time > elapsed {
+                    thread::sleep(Duration::from_millis(time - elapsed));
+                }
+                self.sync_clock();
+            }
+            Clock::Fake => {
+                let timers = self.interpreter.timers();
+                timers.now = timers.now.max(time);
+            }
+        }
+    }
+
+    /// Update the timer queue's time from the system clock
+    fn sync_clock(&mut self) {
+        if let Clock::System(start) = self.clock {
+            self.interpreter.timers().now = start.elapsed().as_millis() as u64;
+        }
+    }
+}
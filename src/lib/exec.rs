@@ -1,13 +1,33 @@
 use gc::{Gc, GcCell};
-use crate::environment::lexical_environment::{new_function_environment, LexicalEnvironment};
-use std::collections::HashMap;
+use crate::environment::lexical_environment::{
+    new_declerative_environment, new_function_environment, new_object_environment, EnvironmentError,
+    LexicalEnvironment,
+};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use crate::syntax::ast::constant::Const;
 use crate::syntax::ast::expr::{Expr, ExprDef};
 use crate::syntax::ast::op::{BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp};
-use crate::js::function::{Function, RegularFunction};
-use crate::js::object::{INSTANCE_PROTOTYPE, PROTOTYPE};
-use crate::js::value::{from_value, to_value, ResultValue, Value, ValueData};
-use crate::js::{array, console, function, json, math, object, string};
+use crate::syntax::ast::pos::Position;
+use crate::js::function::{Function, NativeFunctionData, RegularFunction};
+use crate::js::object::{Property, PropertyDescriptor, PropertyKey, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::console::{Console, ConsoleSink, StdioSink};
+use crate::js::date::{Clock, SystemClock};
+use crate::js::math::{RandomSource, SystemRandom};
+use crate::js::value::{from_value, strict_equals, to_value, ResultValue, Value, ValueData};
+use crate::js::string::JsString;
+use crate::js::promise;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::error::{self, ErrorKind, StackFrame};
+use crate::js::{array, bigint, function, object, proxy, regexp, typed_array};
+use crate::async_function::{self, AsyncContext, AsyncStep, Continuation, Continuations, Segment, Suspension};
+use crate::event_loop::TimerQueue;
+use crate::environment::lexical_environment::Environment;
+use crate::module::{self, MemoryModuleLoader, ModuleLoader, ModuleMap};
+use crate::realm::Realm;
+use crate::syntax::ast::module::ExportDecl;
+use crate::syntax::lexer::Lexer;
+use crate::syntax::parser::Parser;
 
 
 /// An execution engine
@@ -21,36 +41,1269 @@ pub trait Executor {
 /// A Javascript intepreter
 pub struct Interpreter {
     environment: LexicalEnvironment,
+    /// Jobs (microtasks) waiting to be run, in the order they were enqueued
+    jobs: VecDeque<Job>,
+    /// Timers (macrotasks) waiting for the host event loop to run them
+    timers: TimerQueue,
+    /// The async function and module bodies waiting for the values they awaited
+    continuations: Continuations,
+    /// Modules which have been imported, and the loader used to find them
+    modules: ModuleMap,
+    /// The frames of the functions being run, outermost first, with the top level of the script at the bottom
+    call_stack: Vec<StackFrame>,
+    /// Where dates get the current time and the local time zone from
+    clock: Box<dyn Clock>,
+    /// Where `Math.random` gets its numbers from
+    random_source: Box<dyn RandomSource>,
+    /// Where the console writes to, and its groups, counters and timers
+    console: Console,
+    /// If false, `eval` and `Function` throw instead of running code made from strings
+    dynamic_code_allowed: bool,
+    /// The realms made for this interpreter, whose built-in functions run in their own realm when called
+    realms: Vec<Realm>,
+}
+
+/// The file name scripts are shown with in stack traces, as they aren't loaded from a file
+pub const SCRIPT_FILE_NAME: &str = "<anonymous>";
+
+/// Returns true if the expression makes a function without a name, which takes the name of the binding or property it is assigned to
+/// https://tc39.es/ecma262/#sec-isanonymousfunctiondefinition
+fn is_anonymous_function_definition(expr: &Expr) -> bool {
+    matches!(
+        expr.def,
+        ExprDef::FunctionDeclExpr(None, _, _, _)
+            | ExprDef::AsyncFunctionDeclExpr(None, _, _, _)
+            | ExprDef::ArrowFunctionDeclExpr(_, _, _)
+            | ExprDef::AsyncArrowFunctionDeclExpr(_, _, _)
+    )
+}
+
+/// A pending job, such as a promise reaction
+/// https://tc39.es/ecma262/#sec-jobs
+pub struct Job {
+    /// The function to call when the job runs
+    pub func: Value,
+    /// The arguments to call the function with
+    pub args: Vec<Value>,
 }
 
 impl Interpreter {
+    /// Get the global object
+    pub fn get_global_object(&self) -> Value {
+        self.environment.get_global_object().unwrap()
+    }
+
+    /// Make an error object of one of the native error types, to be thrown
+    pub fn new_error<M: AsRef<str>>(&self, kind: ErrorKind, message: M) -> Value {
+        let error = error::new_error(&self.get_global_object(), kind, message.as_ref());
+        error::capture_stack(&error, &self.call_stack());
+        error
+    }
+
+    /// Make a `TypeError` to be thrown
+    pub fn type_error<M: AsRef<str>>(&self, message: M) -> Value {
+        self.new_error(ErrorKind::TypeError, message)
+    }
+
+    /// Make a `RangeError` to be thrown
+    pub fn range_error<M: AsRef<str>>(&self, message: M) -> Value {
+        self.new_error(ErrorKind::RangeError, message)
+    }
+
+    /// Make a `ReferenceError` to be thrown
+    pub fn reference_error<M: AsRef<str>>(&self, message: M) -> Value {
+        self.new_error(ErrorKind::ReferenceError, message)
+    }
+
+    /// Make a `SyntaxError` to be thrown
+    pub fn syntax_error<M: AsRef<str>>(&self, message: M) -> Value {
+        self.new_error(ErrorKind::SyntaxError, message)
+    }
+
+    /// Make the error to be thrown for using a binding in a way that isn't allowed, like redeclaring it
+    pub fn environment_error(&self, err: EnvironmentError) -> Value {
+        self.new_error(err.kind(), err.to_string())
+    }
+
+    /// Get the frames of the call stack, innermost first
+    pub fn call_stack(&self) -> Vec<StackFrame> {
+        self.call_stack.iter().rev().cloned().collect()
+    }
+
+    /// Start a new frame on the call stack, for a function call or the top level of a module
+    pub fn push_frame(&mut self, frame: StackFrame) {
+        self.call_stack.push(frame);
+    }
+
+    /// Leave the innermost frame of the call stack
+    pub fn pop_frame(&mut self) {
+        self.call_stack.pop();
+    }
+
+    /// Record where the innermost frame is in its source, if the expression being run has a position
+    fn set_position(&mut self, pos: &Option<Position>) {
+        if let (Some(pos), Some(frame)) = (pos, self.call_stack.last_mut()) {
+            frame.line_number = pos.line_number;
+            frame.column_number = pos.column_number;
+        }
+    }
+
+    /// Get the global environment
+    pub fn get_global_environment(&self) -> Environment {
+        self.environment.get_global_environment()
+    }
+
+    /// The realm of the code being run, whose global object and built-in objects it uses
+    /// https://tc39.es/ecma262/#current-realm
+    pub fn current_realm(&self) -> Realm {
+        Realm::from_global_environment(self.get_global_environment())
+    }
+
+    /// Create a realm with its own global object and built-in objects, which scripts can be run in with `run_in_realm`
+    pub fn create_realm(&mut self) -> Realm {
+        let realm = Realm::new();
+        self.realms.push(realm.clone());
+        realm
+    }
+
+    /// The realm a built-in function belongs to, if it was made for this interpreter
+    fn builtin_realm(&self, f: &Value) -> Option<Realm> {
+        let global = f.get_private_field_slice(function::FUNCTION_REALM);
+        if !global.is_object() {
+            return None;
+        }
+        self.realms.iter().find(|realm| Gc::ptr_eq(&realm.global_object(), &global)).cloned()
+    }
+
+    /// The realm a function was created in, or the current realm for functions made by the engine
+    /// https://tc39.es/ecma262/#sec-getfunctionrealm
+    pub fn function_realm(&self, f: &Value) -> Realm {
+        if proxy::is_proxy(f) {
+            // A revoked proxy has no target left to take the realm from
+            let target = f.get_private_field_slice(proxy::PROXY_TARGET);
+            return if target.is_null() { self.current_realm() } else { self.function_realm(&target) };
+        }
+        if let ValueData::Function(ref inner) = **f {
+            match *inner.borrow() {
+                Function::RegularFunc(RegularFunction {
+                    environment: Some(ref env),
+                    ..
+                }) => {
+                    let mut env = env.clone();
+                    loop {
+                        let outer = env.borrow().get_outer_environment();
+                        match outer {
+                            Some(outer) => env = outer,
+                            None => return Realm::from_global_environment(env),
+                        }
+                    }
+                }
+                Function::NativeFunc(_) => {
+                    if let Some(realm) = self.builtin_realm(f) {
+                        return realm;
+                    }
+                }
+                Function::BoundFunc(ref data) => return self.function_realm(&data.target),
+                _ => (),
+            }
+        }
+        self.current_realm()
+    }
+
+    /// Run an expression at the top level of a realm, in place of the realm the interpreter was running.
+    /// Values can be passed between realms through their global objects
+    pub fn run_in_realm(&mut self, realm: &Realm, expr: &Expr) -> ResultValue {
+        if !self.realms.contains(realm) {
+            self.realms.push(realm.clone());
+        }
+        self.with_realm(realm, |interpreter| interpreter.run(expr))
+    }
+
+    /// Run a closure with the global environment of a realm as the only environment on the stack
+    fn with_realm<F: FnOnce(&mut Interpreter) -> ResultValue>(&mut self, realm: &Realm, f: F) -> ResultValue {
+        let environment = std::mem::replace(&mut self.environment, LexicalEnvironment::new(realm.global_environment()));
+        let result = f(self);
+        self.environment = environment;
+        result
+    }
+
+    /// Call a built-in function, switching to its realm if that isn't the current one, so the objects it
+    /// makes come from its own realm
+    fn call_native(&mut self, f: &Value, func: NativeFunctionData, this: Value, args: Vec<Value>) -> ResultValue {
+        let global = f.get_private_field_slice(function::FUNCTION_REALM);
+        if !global.is_object() || Gc::ptr_eq(&global, &self.get_global_object()) {
+            return func(this, f.clone(), args, self);
+        }
+        match self.builtin_realm(f) {
+            Some(realm) => self.with_realm(&realm, |interpreter| func(this, f.clone(), args, interpreter)),
+            None => func(this, f.clone(), args, self),
+        }
+    }
+
+    /// Find the environment a name is bound in, searching outwards from the current environment, or `None` if it
+    /// isn't bound anywhere. The objects of `with` statements are searched with their internal methods, so getters
+    /// and proxies work, and the names in their `Symbol.unscopables` are skipped
+    /// https://tc39.es/ecma262/#sec-getidentifierreference
+    fn resolve_binding(&mut self, name: &str) -> Result<Option<Environment>, Value> {
+        let mut env = Some(self.environment.get_current_environment_ref().clone());
+        while let Some(current) = env {
+            let base = current.borrow().with_base_object();
+            let found = if base.is_undefined() {
+                current.borrow().has_binding(&name.to_string())
+            } else {
+                self.has_unscoped_property(&base, name)?
+            };
+            if found {
+                return Ok(Some(current));
+            }
+            env = current.borrow().get_outer_environment();
+        }
+        Ok(None)
+    }
+
+    /// Returns true if the object of a `with` statement has a property, and doesn't list it in its `Symbol.unscopables`
+    /// https://tc39.es/ecma262/#sec-object-environment-records-hasbinding-n
+    fn has_unscoped_property(&mut self, obj: &Value, name: &str) -> Result<bool, Value> {
+        if !self.has_property(obj, &PropertyKey::from(name.to_string()))? {
+            return Ok(false);
+        }
+        let unscopables = self.get_field(obj, Symbol::well_known(WellKnownSymbol::Unscopables))?;
+        if unscopables.is_object() || unscopables.is_function() {
+            return Ok(!self.get_field(&unscopables, name.to_string())?.is_true());
+        }
+        Ok(true)
+    }
+
+    /// Assign to a name, setting the property if it resolves to the object of a `with` statement,
+    /// or creating a global binding if it isn't bound anywhere
+    fn assign_binding(&mut self, name: &str, value: Value) -> Result<(), Value> {
+        let result = match self.resolve_binding(name)? {
+            Some(env) => {
+                let base = env.borrow().with_base_object();
+                if !base.is_undefined() {
+                    self.set_field(&base, name.to_string(), value)?;
+                    return Ok(());
+                }
+                let result = env.borrow_mut().set_mutable_binding(name.to_string(), value, false);
+                result
+            }
+            None => self.environment.assign_binding(name.to_string(), value),
+        };
+        result.map_err(|err| self.environment_error(err))
+    }
+
+    /// Bind the parameters of a function call in the current environment, where missing arguments are undefined.
+    /// A parameter name can repeat outside of strict code, and the last argument for it wins
+    /// https://tc39.es/ecma262/#sec-functiondeclarationinstantiation
+    fn bind_parameters(&mut self, names: &[String], args: &[Value]) -> Result<(), EnvironmentError> {
+        for (i, name) in names.iter().enumerate() {
+            let arg = match args.get(i) {
+                Some(arg) => arg.clone(),
+                None => Gc::new(ValueData::Undefined),
+            };
+            if self.environment.has_own_binding(name) {
+                self.environment.set_mutable_binding(name.clone(), arg, false)?;
+            } else {
+                self.environment.create_mutable_binding(name.clone(), false)?;
+                self.environment.initialize_binding(name.clone(), arg)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if a name resolves to a property of the object of a `with` statement
+    fn resolves_to_with_object(&mut self, name: &str) -> Result<bool, Value> {
+        Ok(match self.resolve_binding(name)? {
+            Some(env) => !env.borrow().with_base_object().is_undefined(),
+            None => false,
+        })
+    }
+
+    /// Get the value of a name from the environment it was resolved to
+    fn get_binding_value(&mut self, env: &Environment, name: &str) -> ResultValue {
+        let base = env.borrow().with_base_object();
+        if base.is_undefined() {
+            let value = env.borrow().get_binding_value(name.to_string(), false);
+            value.map_err(|err| self.environment_error(err))
+        } else {
+            self.get_field(&base, name.to_string())
+        }
+    }
+
+    /// Run an expression with `env` as the current environment
+    pub fn run_in_environment(&mut self, env: Environment, expr: &Expr) -> ResultValue {
+        self.environment.push(env);
+        let result = self.run(expr);
+        self.environment.pop();
+        result
+    }
+
+    /// The modules loaded by this interpreter
+    pub fn modules(&mut self) -> &mut ModuleMap {
+        &mut self.modules
+    }
+
+    /// The clock and time zone used by dates
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Set the clock and time zone used by dates, such as a `FixedClock` to make them reproducible
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// The source of the numbers `Math.random` returns
+    pub fn random_source(&mut self) -> &mut dyn RandomSource {
+        self.random_source.as_mut()
+    }
+
+    /// Set where `Math.random` gets its numbers from, such as a `SeededRandom` to make runs reproducible
+    pub fn set_random_source(&mut self, source: Box<dyn RandomSource>) {
+        self.random_source = source;
+    }
+
+    /// The state of the console
+    pub fn console(&mut self) -> &mut Console {
+        &mut self.console
+    }
+
+    /// Set where the console writes to, such as a `MemorySink` to capture the output
+    pub fn set_console_sink(&mut self, sink: Box<dyn ConsoleSink>) {
+        self.console.set_sink(sink);
+    }
+
+    /// Returns true if scripts may run code made from strings with `eval` and `Function`
+    pub fn dynamic_code_allowed(&self) -> bool {
+        self.dynamic_code_allowed
+    }
+
+    /// Allow or forbid running code made from strings, like a content security policy without `unsafe-eval`.
+    /// When forbidden, `eval` and `Function` throw an `EvalError`
+    pub fn set_dynamic_code_allowed(&mut self, allowed: bool) {
+        self.dynamic_code_allowed = allowed;
+    }
+
+    /// Parse code made from a string, throwing a `SyntaxError` if it doesn't parse,
+    /// or an `EvalError` if the embedder has disallowed dynamic code
+    /// https://tc39.es/ecma262/#sec-hostensurecancompilestrings
+    fn parse_dynamic_code(&self, source: &str) -> Result<Expr, Value> {
+        if !self.dynamic_code_allowed {
+            return Err(self.new_error(ErrorKind::EvalError, "Code generation from strings disallowed for this context"));
+        }
+        // The lexer needs a character after the last token, which eval code like `"1 + 2"` doesn't have
+        let padded = format!("{}\n", source);
+        let mut lexer = Lexer::new(&padded);
+        lexer.lex().map_err(|err| self.syntax_error(err.to_string()))?;
+        Parser::new(lexer.tokens)
+            .with_source(source)
+            .parse_all()
+            .map_err(|err| self.syntax_error(format!("{:?}", err)))
+    }
+
+    /// Run the code given to `eval`. Direct eval (calling `eval` by that name) sees the caller's bindings and
+    /// declares its `var`s in the calling function, while indirect eval runs in the global scope.
+    /// `let` and `const` declarations stay inside the eval code either way
+    /// https://tc39.es/ecma262/#sec-performeval
+    pub fn perform_eval(&mut self, source: &Value, direct: bool) -> ResultValue {
+        let source = match **source {
+            ValueData::String(ref source) => source.to_string(),
+            _ => return Ok(source.clone()),
+        };
+        let body = self.parse_dynamic_code(&source)?;
+        if let ExprDef::BlockExpr(ref exprs) = body.def {
+            if exprs.is_empty() {
+                return Ok(Gc::new(ValueData::Undefined));
+            }
+        }
+        let outer = if direct {
+            self.environment.get_current_environment_ref().clone()
+        } else {
+            self.get_global_environment()
+        };
+        self.run_in_environment(new_declerative_environment(Some(outer)), &body)
+    }
+
+    /// Make a function from the source of its parameters and body, closing over the global scope
+    /// https://tc39.es/ecma262/#sec-createdynamicfunction
+    pub fn create_dynamic_function(&mut self, params: &str, body: &str) -> ResultValue {
+        let source = format!("(function ({}\n) {{\n{}\n}})", params, body);
+        let expr = self.parse_dynamic_code(&source)?;
+        // The parameters and body are parsed together, so check they didn't close the function early
+        let def = match expr.def {
+            ExprDef::BlockExpr(ref exprs) if exprs.len() == 1 => match exprs[0].def {
+                ExprDef::FunctionDeclExpr(None, _, _, _) => exprs[0].def.clone(),
+                _ => return Err(self.syntax_error("Unexpected token in function source")),
+            },
+            _ => return Err(self.syntax_error("Unexpected token in function source")),
+        };
+        let global_env = self.get_global_environment();
+        self.environment.push(new_declerative_environment(Some(global_env)));
+        let result = self.run(&Expr::new(def));
+        self.environment.pop();
+        let func = result?;
+        function::set_function_name(&func, "anonymous");
+        if let ValueData::Function(ref inner) = *func {
+            if let Function::RegularFunc(ref mut data) = *inner.borrow_mut() {
+                data.source = format!("function anonymous({}\n) {{\n{}\n}}", params, body);
+            }
+        }
+        Ok(func)
+    }
+
+    /// Set the loader used to resolve and load modules
+    pub fn set_module_loader(&mut self, loader: Box<dyn ModuleLoader>) {
+        self.modules.set_loader(loader);
+    }
+
+    /// Import a module and everything it depends on, returning a promise of its namespace object.
+    /// The promise is settled by running jobs, or the event loop if the module awaits timers
+    pub fn import_module(&mut self, specifier: &str) -> ResultValue {
+        module::import_module(self, specifier, None)
+    }
+
+    /// Get the prototype object of a primitive, whose properties primitives have, or `None` for objects.
+    /// This is the current realm's original prototype, so it still works if scripts replace `String` or `Number`
+    /// https://tc39.es/ecma262/#sec-toobject
+    fn primitive_prototype(&self, value: &Value) -> Option<Value> {
+        let prototype = match **value {
+            ValueData::String(_) => "String.prototype",
+            ValueData::Symbol(_) => "Symbol.prototype",
+            ValueData::BigInt(_) => "BigInt.prototype",
+            ValueData::Number(_) | ValueData::Integer(_) => "Number.prototype",
+            ValueData::Boolean(_) => "Boolean.prototype",
+            _ => return None,
+        };
+        Some(self.current_realm().intrinsic(prototype))
+    }
+
+    /// Get a field of a value, running the getter if it is an accessor property
+    pub fn get_field<K: Into<PropertyKey>>(&mut self, obj: &Value, field: K) -> ResultValue {
+        // Strings, Symbols, BigInts, numbers and booleans are primitives, so their properties come from their prototype objects
+        let field = field.into();
+        let holder = match **obj {
+            ValueData::String(_) if obj.get_prop(field.clone()).is_some() => obj.clone(),
+            _ => self.primitive_prototype(obj).unwrap_or_else(|| obj.clone()),
+        };
+        self.get(&holder, &field, obj)
+    }
+
+    /// Get a property for a member expression like `obj.field`, where `obj` can't be `null` or `undefined`
+    fn get_member<K: Into<PropertyKey>>(&mut self, obj: &Value, field: K) -> ResultValue {
+        let field = field.into();
+        if obj.is_null_or_undefined() {
+            return Err(self.type_error(format!("Cannot read properties of {} (reading '{}')", obj, field)));
+        }
+        self.get_field(obj, field)
+    }
+
+    /// Set a field of a value, running the setter if it is an accessor property
+    /// Like an assignment outside of strict mode, nothing happens if the property is read-only or the object
+    /// isn't extensible
+    /// Setting the `length` of an array to something which isn't a valid length is a `RangeError`
+    pub fn set_field<K: Into<PropertyKey>>(&mut self, obj: &Value, field: K, val: Value) -> ResultValue {
+        let field = field.into();
+        if obj.is_array() && field == "length" && array::valid_length(&val).is_none() {
+            return Err(self.range_error("Invalid array length"));
+        }
+        if field == INSTANCE_PROTOTYPE {
+            if (val.is_object() || val.is_function() || val.is_null()) && (obj.is_object() || obj.is_function()) {
+                self.set_prototype_of(obj, val.clone())?;
+            }
+            return Ok(val);
+        }
+        let holder = self.primitive_prototype(obj).unwrap_or_else(|| obj.clone());
+        self.set(&holder, &field, val.clone(), obj)?;
+        Ok(val)
+    }
+
+    /// Get the prototype of an object, which is `null` if it has none
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-getprototypeof
+    pub fn get_prototype_of(&mut self, obj: &Value) -> ResultValue {
+        if proxy::is_proxy(obj) {
+            return proxy::get_prototype_of(obj, self);
+        }
+        let proto = obj.get_field_slice(INSTANCE_PROTOTYPE);
+        Ok(if proto.is_object() || proto.is_function() {
+            proto
+        } else {
+            Gc::new(ValueData::Null)
+        })
+    }
+
+    /// Set the prototype of an object, returning false if it can't be changed
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-setprototypeof-v
+    pub fn set_prototype_of(&mut self, obj: &Value, proto: Value) -> Result<bool, Value> {
+        if proxy::is_proxy(obj) {
+            return proxy::set_prototype_of(obj, proto, self);
+        }
+        Ok(object::ordinary_set_prototype_of(obj, proto))
+    }
+
+    /// Returns true if properties can be added to the object
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-isextensible
+    pub fn is_extensible(&mut self, obj: &Value) -> Result<bool, Value> {
+        if proxy::is_proxy(obj) {
+            return proxy::is_extensible(obj, self);
+        }
+        Ok(obj.is_extensible())
+    }
+
+    /// Stop properties from being added to the object, returning false if it refused
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-preventextensions
+    pub fn prevent_extensions(&mut self, obj: &Value) -> Result<bool, Value> {
+        if proxy::is_proxy(obj) {
+            return proxy::prevent_extensions(obj, self);
+        }
+        obj.prevent_extensions();
+        Ok(true)
+    }
+
+    /// Get an own property of an object, without looking at its prototype chain
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-getownproperty-p
+    pub fn get_own_property(&mut self, obj: &Value, key: &PropertyKey) -> Result<Option<Property>, Value> {
+        if proxy::is_proxy(obj) {
+            return proxy::get_own_property(obj, key, self);
+        }
+        Ok(obj.get_own_prop(key.clone()))
+    }
+
+    /// Define or change an own property of an object, returning false if it can't be defined
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-defineownproperty-p-desc
+    pub fn define_own_property(&mut self, obj: &Value, key: PropertyKey, desc: PropertyDescriptor) -> Result<bool, Value> {
+        if proxy::is_proxy(obj) {
+            return proxy::define_own_property(obj, key, desc, self);
+        }
+        object::define_own_property(obj, key, desc, self)
+    }
+
+    /// Returns true if the object or its prototype chain has the property
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-hasproperty-p
+    pub fn has_property(&mut self, obj: &Value, key: &PropertyKey) -> Result<bool, Value> {
+        let mut current = obj.clone();
+        loop {
+            if proxy::is_proxy(&current) {
+                return proxy::has_property(&current, key, self);
+            }
+            if current.get_own_prop(key.clone()).is_some() {
+                return Ok(true);
+            }
+            // Typed arrays don't look up their prototype chain for numeric keys
+            if typed_array::element_index(&current, key).is_some() {
+                return Ok(false);
+            }
+            let proto = current.get_field_slice(INSTANCE_PROTOTYPE);
+            if !proto.is_object() && !proto.is_function() {
+                return Ok(false);
+            }
+            current = proto;
+        }
+    }
+
+    /// Get the value of a property of an object or its prototype chain, calling a getter with `receiver` as `this`
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-get-p-receiver
+    pub fn get(&mut self, obj: &Value, key: &PropertyKey, receiver: &Value) -> ResultValue {
+        let mut current = obj.clone();
+        loop {
+            if proxy::is_proxy(&current) {
+                return proxy::get(&current, key, receiver, self);
+            }
+            if *key == INSTANCE_PROTOTYPE {
+                return Ok(current.get_field_slice(INSTANCE_PROTOTYPE));
+            }
+            match current.get_own_prop(key.clone()) {
+                Some(ref prop) if prop.get.is_function() => return self.call(&prop.get, receiver, Vec::new()),
+                Some(prop) => return Ok(prop.value.clone()),
+                None if typed_array::element_index(&current, key).is_some() => {
+                    return Ok(Gc::new(ValueData::Undefined))
+                }
+                None => (),
+            }
+            let proto = current.get_field_slice(INSTANCE_PROTOTYPE);
+            if !proto.is_object() && !proto.is_function() {
+                return Ok(Gc::new(ValueData::Undefined));
+            }
+            current = proto;
+        }
+    }
+
+    /// Set a property of an object, calling a setter with `receiver` as `this`, or defining the property on
+    /// `receiver`. Returns false if the property is read-only or can't be added.
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-set-p-v-receiver
+    pub fn set(&mut self, obj: &Value, key: &PropertyKey, val: Value, receiver: &Value) -> Result<bool, Value> {
+        let mut current = obj.clone();
+        let own = loop {
+            if proxy::is_proxy(&current) {
+                return proxy::set(&current, key, val, receiver, self);
+            }
+            // Storing an element of a typed array converts the value first, which can run script
+            if let Some(index) = typed_array::element_index(&current, key) {
+                if Gc::ptr_eq(&current, receiver) {
+                    typed_array::set_element(&current, index, &val, self)?;
+                    return Ok(true);
+                }
+                if current.get_own_prop(key.clone()).is_none() {
+                    return Ok(true);
+                }
+            }
+            if let Some(prop) = current.get_own_prop(key.clone()) {
+                break prop;
+            }
+            let proto = current.get_field_slice(INSTANCE_PROTOTYPE);
+            if !proto.is_object() && !proto.is_function() {
+                break Property::data(Gc::new(ValueData::Undefined));
+            }
+            current = proto;
+        };
+        if own.is_accessor() {
+            if !own.set.is_function() {
+                return Ok(false);
+            }
+            self.call(&own.set, receiver, vec![val])?;
+            return Ok(true);
+        }
+        if !own.writable || (!receiver.is_object() && !receiver.is_function()) {
+            return Ok(false);
+        }
+        match self.get_own_property(receiver, key)? {
+            Some(ref existing) if existing.is_accessor() || !existing.writable => Ok(false),
+            Some(_) => {
+                let desc = PropertyDescriptor {
+                    value: Some(val),
+                    ..PropertyDescriptor::default()
+                };
+                self.define_own_property(receiver, key.clone(), desc)
+            }
+            None => {
+                let desc = PropertyDescriptor {
+                    value: Some(val),
+                    writable: Some(true),
+                    enumerable: Some(true),
+                    configurable: Some(true),
+                    ..PropertyDescriptor::default()
+                };
+                self.define_own_property(receiver, key.clone(), desc)
+            }
+        }
+    }
+
+    /// Remove an own property of an object, returning false if it isn't configurable
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-delete-p
+    pub fn delete_property(&mut self, obj: &Value, key: &PropertyKey) -> Result<bool, Value> {
+        if proxy::is_proxy(obj) {
+            return proxy::delete_property(obj, key, self);
+        }
+        // The elements of typed arrays can't be removed
+        if typed_array::element_index(obj, key).is_some() {
+            return Ok(obj.get_own_prop(key.clone()).is_none());
+        }
+        match obj.get_own_prop(key.clone()) {
+            Some(ref prop) if !prop.configurable => Ok(false),
+            Some(_) => {
+                obj.remove_prop(key.clone());
+                Ok(true)
+            }
+            None => Ok(true),
+        }
+    }
+
+    /// Get the keys of the object's own properties
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-ownpropertykeys
+    pub fn own_property_keys(&mut self, obj: &Value) -> Result<Vec<PropertyKey>, Value> {
+        if proxy::is_proxy(obj) {
+            return proxy::own_property_keys(obj, self);
+        }
+        Ok(obj.own_keys())
+    }
+
+    /// Convert a value to an object, wrapping primitives in an object made from their constructor's prototype
+    /// https://tc39.es/ecma262/#sec-toobject
+    pub fn to_object(&mut self, value: &Value) -> ResultValue {
+        let proto = match **value {
+            ValueData::Undefined | ValueData::Null => {
+                return Err(self.type_error(format!("Cannot convert {} to object", value)))
+            }
+            ValueData::Object(_, _) | ValueData::Function(_) | ValueData::Buffer(_) => return Ok(value.clone()),
+            _ => self.primitive_prototype(value).expect("primitives have a prototype"),
+        };
+        let obj = ValueData::new_obj_from_prototype(proto);
+        obj.set_private_field_slice("PrimitiveValue", value.clone());
+        Ok(obj)
+    }
+
+    /// Convert a value to a string, calling `toString` on objects
+    /// https://tc39.es/ecma262/#sec-tostring
+    pub fn to_string(&mut self, value: &Value) -> Result<String, Value> {
+        if value.is_symbol() {
+            return Err(self.type_error("Cannot convert a Symbol value to a string"));
+        }
+        let primitive = self.to_primitive(value, "string")?;
+        if primitive.is_symbol() {
+            return Err(self.type_error("Cannot convert a Symbol value to a string"));
+        }
+        Ok(primitive.to_string())
+    }
+
+    /// Convert a value to a string like `to_string`, keeping the UTF-16 code units of strings as they are
+    pub fn to_js_string(&mut self, value: &Value) -> Result<JsString, Value> {
+        if value.is_symbol() {
+            return Err(self.type_error("Cannot convert a Symbol value to a string"));
+        }
+        let primitive = self.to_primitive(value, "string")?;
+        if primitive.is_symbol() {
+            return Err(self.type_error("Cannot convert a Symbol value to a string"));
+        }
+        Ok(primitive.to_js_string())
+    }
+
+    /// Convert a value to a number, calling `valueOf` on objects
+    /// https://tc39.es/ecma262/#sec-tonumber
+    pub fn to_number(&mut self, value: &Value) -> Result<f64, Value> {
+        let primitive = self.to_primitive(value, "number")?;
+        match *primitive {
+            ValueData::Symbol(_) => Err(self.type_error("Cannot convert a Symbol value to a number")),
+            ValueData::BigInt(_) => Err(self.type_error("Cannot convert a BigInt value to a number")),
+            _ => Ok(primitive.to_num()),
+        }
+    }
+
+    /// Convert a value to a primitive, using its `[Symbol.toPrimitive]`, `valueOf` or `toString` method
+    /// if it is an object. The hint is "default", "number" or "string".
+    /// https://tc39.es/ecma262/#sec-toprimitive
+    pub fn to_primitive(&mut self, value: &Value, hint: &str) -> ResultValue {
+        if !value.is_object() {
+            return Ok(value.clone());
+        }
+        let exotic = self.get_field(value, Symbol::well_known(WellKnownSymbol::ToPrimitive))?;
+        if exotic.is_function() {
+            let result = self.call(&exotic, value, vec![to_value(hint)])?;
+            if result.is_object() {
+                return Err(self.type_error("Cannot convert object to primitive value"));
+            }
+            return Ok(result);
+        }
+        self.ordinary_to_primitive(value, hint)
+    }
+
+    /// Convert an object to a primitive with its `valueOf` and `toString` methods, trying `toString` first
+    /// if the hint is "string"
+    /// https://tc39.es/ecma262/#sec-ordinarytoprimitive
+    pub fn ordinary_to_primitive(&mut self, value: &Value, hint: &str) -> ResultValue {
+        let methods = if hint == "string" {
+            ["toString", "valueOf"]
+        } else {
+            ["valueOf", "toString"]
+        };
+        for name in methods.iter() {
+            let method = self.get_field(value, *name)?;
+            if method.is_function() {
+                let result = self.call(&method, value, Vec::new())?;
+                if !result.is_object() {
+                    return Ok(result);
+                }
+            }
+        }
+        Err(self.type_error("Cannot convert object to primitive value"))
+    }
+
+    /// Check if `target` is in the prototype chain of `value`, or ask `target[Symbol.hasInstance]`
+    /// https://tc39.es/ecma262/#sec-instanceofoperator
+    pub fn instance_of(&mut self, value: &Value, target: &Value) -> Result<bool, Value> {
+        if !target.is_object() && !target.is_function() {
+            return Err(self.type_error("Right-hand side of 'instanceof' is not an object"));
+        }
+        let has_instance = self.get_field(target, Symbol::well_known(WellKnownSymbol::HasInstance))?;
+        if has_instance.is_function() {
+            return Ok(self.call(&has_instance, target, vec![value.clone()])?.is_true());
+        }
+        if !target.is_function() {
+            return Err(self.type_error("Right-hand side of 'instanceof' is not callable"));
+        }
+        // OrdinaryHasInstance
+        if !value.is_object() && !value.is_function() {
+            return Ok(false);
+        }
+        let prototype = self.get_field(target, PROTOTYPE)?;
+        if !prototype.is_object() && !prototype.is_function() {
+            return Err(self.type_error("Function has non-object prototype in instanceof check"));
+        }
+        let mut current = self.get_prototype_of(value)?;
+        while current.is_object() || current.is_function() {
+            if Gc::ptr_eq(&current, &prototype) {
+                return Ok(true);
+            }
+            current = self.get_prototype_of(&current)?;
+        }
+        Ok(false)
+    }
+
+    /// Call a function value with the given `this` value and arguments
+    pub fn call(&mut self, f: &Value, this: &Value, args: Vec<Value>) -> ResultValue {
+        match **f {
+            ValueData::Function(ref inner_func) => match inner_func.clone().into_inner() {
+                Function::NativeFunc(ref ntv) => self.call_native(f, ntv.data, this.clone(), args),
+                Function::RegularFunc(ref data) => {
+                    // New target is only needed for constructors, just pass undefined
+                    let undefined = Gc::new(ValueData::Undefined);
+                    self.call_regular_function(f, data, this.clone(), undefined, args)
+                }
+                Function::BoundFunc(ref data) => {
+                    let mut bound_args = data.args.clone();
+                    bound_args.extend(args);
+                    self.call(&data.target, &data.this, bound_args)
+                }
+            },
+            _ => Err(self.type_error(format!("{} is not a function", f))),
+        }
+    }
+
+    /// Call a function as a constructor, like `new f(...args)`
+    /// https://tc39.es/ecma262/#sec-construct
+    pub fn construct(&mut self, f: &Value, args: Vec<Value>) -> ResultValue {
+        self.construct_with_new_target(f, args, f)
+    }
+
+    /// Call a function as a constructor, where the new object's prototype comes from `new_target`, like
+    /// `Reflect.construct(f, args, new_target)`
+    pub fn construct_with_new_target(&mut self, f: &Value, args: Vec<Value>, new_target: &Value) -> ResultValue {
+        if proxy::is_proxy(f) {
+            return proxy::construct(f, args, new_target, self);
+        }
+        let inner_func = match **f {
+            ValueData::Function(ref inner_func) => inner_func.clone().into_inner(),
+            _ => return Ok(Gc::new(ValueData::Undefined)),
+        };
+        match inner_func {
+            Function::NativeFunc(ref ntv) => {
+                let func_data = ntv.data;
+                let this = self.ordinary_create_from_constructor(new_target)?;
+                self.call_native(f, func_data, this, args)
+            }
+            Function::RegularFunc(ref data) => {
+                // The new object is the result, unless the constructor returns another object
+                let this = self.ordinary_create_from_constructor(new_target)?;
+                let result = self.call_regular_function(f, data, this.clone(), new_target.clone(), args)?;
+                Ok(if result.is_object() { result } else { this })
+            }
+            // Bound functions ignore their bound `this`, and construct their target
+            Function::BoundFunc(ref data) => {
+                let mut bound_args = data.args.clone();
+                bound_args.extend(args);
+                let new_target = if Gc::ptr_eq(f, new_target) { &data.target } else { new_target };
+                self.construct_with_new_target(&data.target, bound_args, new_target)
+            }
+        }
+    }
+
+    /// Create a blank object, then set its __proto__ property to the [Constructor].prototype, or
+    /// `Object.prototype` of the constructor's realm if that isn't an object
+    /// https://tc39.es/ecma262/#sec-ordinarycreatefromconstructor
+    fn ordinary_create_from_constructor(&mut self, constructor: &Value) -> ResultValue {
+        let mut proto = self.get_field(constructor, PROTOTYPE)?;
+        if !proto.is_object() && !proto.is_function() {
+            proto = self.function_realm(constructor).intrinsic("Object.prototype");
+        }
+        Ok(ValueData::new_obj_from_prototype(proto))
+    }
+
+    /// Run the body of a regular function in a new function environment.
+    /// Async functions return a promise straight away, which is settled once their body has run
+    fn call_regular_function(
+        &mut self,
+        f: &Value,
+        data: &RegularFunction,
+        this: Value,
+        new_target: Value,
+        args: Vec<Value>,
+    ) -> ResultValue {
+        // Functions which aren't strict get the global object for a missing `this`, and primitives are wrapped in objects
+        // https://tc39.es/ecma262/#sec-ordinarycallbindthis
+        let this = if data.is_arrow {
+            None
+        } else if this.is_null_or_undefined() {
+            Some(self.get_global_object())
+        } else {
+            Some(self.to_object(&this)?)
+        };
+        let name = f.get_field_slice("name");
+        let mut frame = StackFrame::new(None, &data.file_name);
+        if name.is_string() && !name.to_string().is_empty() {
+            frame.function_name = Some(name.to_string());
+        }
+        frame.is_constructor = !new_target.is_undefined();
+        self.push_frame(frame.clone());
+        self.environment.push(new_function_environment(
+            f.clone(),
+            this,
+            new_target,
+            data.environment.clone(),
+        ));
+        if let Err(err) = self.bind_parameters(&data.args, &args) {
+            self.environment.pop();
+            self.pop_frame();
+            return Err(self.environment_error(err));
+        }
+        if data.is_async {
+            let environment = self.environment.get_current_environment_ref().clone();
+            self.environment.pop();
+            self.pop_frame();
+            let context = AsyncContext {
+                capability: promise::new_promise_capability(self),
+                frame,
+                module: None,
+            };
+            return self.run_async_body(environment, &data.expr, context);
+        }
+        let result = self.run(&data.expr);
+        self.environment.pop();
+        self.pop_frame();
+        result
+    }
+
+    /// Create a function object closing over the current environment, from a function or arrow function expression
+    fn make_function(&mut self, def: &ExprDef) -> Value {
+        let (name, args, expr, source, is_async, is_arrow) = match *def {
+            ExprDef::FunctionDeclExpr(ref name, ref args, ref expr, ref source) => (name.clone(), args, expr, source, false, false),
+            ExprDef::AsyncFunctionDeclExpr(ref name, ref args, ref expr, ref source) => (name.clone(), args, expr, source, true, false),
+            ExprDef::ArrowFunctionDeclExpr(ref args, ref expr, ref source) => (None, args, expr, source, false, true),
+            ExprDef::AsyncArrowFunctionDeclExpr(ref args, ref expr, ref source) => (None, args, expr, source, true, true),
+            _ => panic!("make_function called with {:?}", def),
+        };
+        // Async function bodies are rewritten so they can be suspended at each `await`
+        let expr = if is_async { async_function::normalize(expr) } else { *expr.clone() };
+        let mut function = RegularFunction::new(
+            expr,
+            args.to_vec(),
+            Some(self.environment.get_current_environment_ref().clone()),
+        );
+        function.is_async = is_async;
+        function.is_arrow = is_arrow;
+        function.source = source.clone();
+        function.file_name = self.call_stack.last().map_or_else(String::new, |frame| frame.file_name.clone());
+        let val = Gc::new(ValueData::Function(GcCell::new(Function::RegularFunc(function))));
+        let function_prototype = self.get_global_object().get_field_slice("Function").get_field_slice(PROTOTYPE);
+        val.set_field_slice(INSTANCE_PROTOTYPE, function_prototype);
+        function::set_function_length(&val, args.len());
+        function::set_function_name(&val, name.as_deref().unwrap_or(""));
+        val
+    }
+
+    /// The timers registered by `setTimeout` and `setInterval`
+    pub fn timers(&mut self) -> &mut TimerQueue {
+        &mut self.timers
+    }
+
+    /// Add a job to the end of the job queue
+    pub fn enqueue_job(&mut self, func: Value, args: Vec<Value>) {
+        self.jobs.push_back(Job { func, args });
+    }
+
+    /// Returns true if there are jobs waiting to be run
+    pub fn has_pending_jobs(&self) -> bool {
+        !self.jobs.is_empty()
+    }
+
+    /// Run the next job in the queue, returning `None` if the queue is empty
+    pub fn run_next_job(&mut self) -> Option<ResultValue> {
+        let job = self.jobs.pop_front()?;
+        let undefined = Gc::new(ValueData::Undefined);
+        Some(self.call(&job.func, &undefined, job.args))
+    }
+
+    /// Drain the job queue, including any jobs enqueued while running it.
+    /// If a job throws, the error is returned and the remaining jobs stay queued.
+    pub fn run_jobs(&mut self) -> Result<(), Value> {
+        while let Some(result) = self.run_next_job() {
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Run the body of an async function or module in `environment`, returning the promise settled with its
+    /// completion. The body runs until its first `await`, and the rest of it runs from promise jobs
+    pub fn run_async_body(&mut self, environment: Environment, body: &Expr, context: AsyncContext) -> ResultValue {
+        let promise = context.capability.get_field_slice("promise");
+        self.enter_async(&context);
+        self.environment.push(environment);
+        let step = self.run_async(body);
+        self.environment.pop();
+        self.leave_async(&context);
+        self.settle_async(step, context)?;
+        Ok(promise)
+    }
+
+    /// Run the body of a module once `awaited` has been fulfilled, as if the body started with `await awaited;`.
+    /// The promise returned is rejected without running the body if `awaited` is rejected
+    pub fn run_async_body_after(
+        &mut self,
+        awaited: Value,
+        environment: Environment,
+        body: &Expr,
+        context: AsyncContext,
+    ) -> ResultValue {
+        let promise = context.capability.get_field_slice("promise");
+        let suspension = Suspension {
+            value: awaited,
+            binding: None,
+            environment: environment.clone(),
+            segments: vec![Segment { environment, code: body.clone() }],
+        };
+        self.settle_async(Ok(AsyncStep::Await(suspension)), context)?;
+        Ok(promise)
+    }
+
+    /// Carry on running a suspended body once the value it awaited has settled, with the value it was fulfilled
+    /// with or the reason it was rejected
+    pub fn resume_async(&mut self, id: u64, result: Result<Value, Value>) -> Result<(), Value> {
+        let continuation = match self.continuations.take(id) {
+            Some(continuation) => continuation,
+            None => return Ok(()),
+        };
+        let Continuation { binding, environment, segments, context } = continuation;
+        self.enter_async(&context);
+        let step = self.resume_segments(result, binding, environment, segments);
+        self.leave_async(&context);
+        self.settle_async(step, context)
+    }
+
+    /// Put back the call stack frame of a body, and the module it is the top level of
+    fn enter_async(&mut self, context: &AsyncContext) {
+        self.push_frame(context.frame.clone());
+        if let Some(ref module) = context.module {
+            self.modules.push_evaluating(module);
+        }
+    }
+
+    /// Leave the call stack frame of a body, and the module it is the top level of
+    fn leave_async(&mut self, context: &AsyncContext) {
+        if context.module.is_some() {
+            self.modules.pop_evaluating();
+        }
+        self.pop_frame();
+    }
+
+    /// Assign the awaited value and run the rest of a suspended body, until it finishes or reaches another `await`
+    fn resume_segments(
+        &mut self,
+        result: Result<Value, Value>,
+        binding: Option<String>,
+        environment: Environment,
+        segments: Vec<Segment>,
+    ) -> Result<AsyncStep, Value> {
+        // A rejection is thrown from the `await`
+        let value = result?;
+        let mut completion = value.clone();
+        if let Some(name) = binding {
+            self.environment.push(environment);
+            let declared = self.environment.declare_var(name, Some(value));
+            self.environment.pop();
+            declared.map_err(|err| self.environment_error(err))?;
+            completion = Gc::new(ValueData::Undefined);
+        }
+        let mut segments = segments.into_iter();
+        while let Some(segment) = segments.next() {
+            self.environment.push(segment.environment);
+            let step = self.run_async(&segment.code);
+            self.environment.pop();
+            match step? {
+                AsyncStep::Done(value) => completion = value,
+                AsyncStep::Await(mut suspension) => {
+                    suspension.segments.extend(segments);
+                    return Ok(AsyncStep::Await(suspension));
+                }
+            }
+        }
+        Ok(AsyncStep::Done(completion))
+    }
+
+    /// Settle the promise of a body which has finished, or wait for the value it awaited with the rest of the body
+    /// kept as a continuation. Module bodies are fulfilled with undefined rather than their completion value
+    /// https://tc39.es/ecma262/#await
+    fn settle_async(&mut self, step: Result<AsyncStep, Value>, context: AsyncContext) -> Result<(), Value> {
+        let undefined = Gc::new(ValueData::Undefined);
+        let (resolver, value) = match step {
+            Ok(AsyncStep::Done(value)) => {
+                let value = if context.module.is_some() { undefined.clone() } else { value };
+                (context.capability.get_field_slice("resolve"), value)
+            }
+            Ok(AsyncStep::Await(suspension)) => {
+                let Suspension { value, binding, environment, segments } = suspension;
+                match promise::promise_resolve(value, self) {
+                    Ok(promise) => {
+                        let id = self.continuations.insert(Continuation { binding, environment, segments, context });
+                        let on_fulfilled = async_function::resume_function(async_function::resume_fulfilled, id);
+                        let on_rejected = async_function::resume_function(async_function::resume_rejected, id);
+                        promise::perform_promise_then(&promise, on_fulfilled, on_rejected, undefined, self);
+                        return Ok(());
+                    }
+                    Err(reason) => (context.capability.get_field_slice("reject"), reason),
+                }
+            }
+            Err(reason) => (context.capability.get_field_slice("reject"), reason),
+        };
+        self.call(&resolver, &undefined, vec![value])?;
+        Ok(())
+    }
+
+    /// Run part of the body of an async function or module, stopping at the first `await` it reaches.
+    /// The body must have been rewritten by `async_function::normalize`, so each `await` is a statement of its own
+    /// and only blocks, `if`, `while`, `with` and `switch` statements have them inside
+    fn run_async(&mut self, expr: &Expr) -> Result<AsyncStep, Value> {
+        if !async_function::contains_await(expr) {
+            return self.run(expr).map(AsyncStep::Done);
+        }
+        self.set_position(&expr.pos);
+        match expr.def {
+            ExprDef::AwaitExpr(ref value) => {
+                let value = self.run(value)?;
+                Ok(self.suspend(value, None))
+            }
+            ExprDef::VarDeclExpr(ref vars) => match vars.first() {
+                Some((name, Some(Expr { def: ExprDef::AwaitExpr(ref value), .. }))) => {
+                    let value = self.run(value)?;
+                    Ok(self.suspend(value, Some(name.clone())))
+                }
+                _ => self.run(expr).map(AsyncStep::Done),
+            },
+            ExprDef::BlockExpr(ref statements) => {
+                let mut result = to_value(None::<()>);
+                for (i, statement) in statements.iter().enumerate() {
+                    match self.run_async(statement)? {
+                        AsyncStep::Done(value) => result = value,
+                        AsyncStep::Await(mut suspension) => {
+                            let rest = &statements[i + 1..];
+                            if !rest.is_empty() {
+                                let code = Expr::new(ExprDef::BlockExpr(rest.to_vec()));
+                                suspension.segments.push(self.segment(code));
+                            }
+                            return Ok(AsyncStep::Await(suspension));
+                        }
+                    }
+                }
+                Ok(AsyncStep::Done(result))
+            }
+            ExprDef::IfExpr(ref cond, ref then, ref otherwise) => {
+                if self.run(cond)?.is_true() {
+                    self.run_async(then)
+                } else {
+                    match *otherwise {
+                        Some(ref otherwise) => self.run_async(otherwise),
+                        None => Ok(AsyncStep::Done(Gc::new(ValueData::Undefined))),
+                    }
+                }
+            }
+            ExprDef::WhileLoopExpr(ref cond, ref body) => {
+                let mut result = Gc::new(ValueData::Undefined);
+                while self.run(cond)?.is_true() {
+                    match self.run_async(body)? {
+                        AsyncStep::Done(value) => result = value,
+                        // Once the rest of the body has run, the loop carries on from its condition
+                        AsyncStep::Await(mut suspension) => {
+                            suspension.segments.push(self.segment(expr.clone()));
+                            return Ok(AsyncStep::Await(suspension));
+                        }
+                    }
+                }
+                Ok(AsyncStep::Done(result))
+            }
+            ExprDef::WithExpr(ref obj, ref body) => {
+                let body_env = self.with_environment(obj)?;
+                self.environment.push(body_env);
+                let step = self.run_async(body);
+                self.environment.pop();
+                step
+            }
+            ExprDef::SwitchExpr(ref value_e, ref cases, ref default) => {
+                let value = self.run(value_e)?;
+                let mut result = Gc::new(ValueData::Null);
+                let mut matched = false;
+                for (i, (test, block)) in cases.iter().enumerate() {
+                    if value == self.run(test)? {
+                        matched = true;
+                        match self.run_async(&Expr::new(ExprDef::BlockExpr(block.clone())))? {
+                            AsyncStep::Done(value) => result = value,
+                            // The cases after this one are still checked, but the default isn't run as a case matched
+                            AsyncStep::Await(mut suspension) => {
+                                let rest = cases[i + 1..].to_vec();
+                                if !rest.is_empty() {
+                                    let code = Expr::new(ExprDef::SwitchExpr(value_e.clone(), rest, None));
+                                    suspension.segments.push(self.segment(code));
+                                }
+                                return Ok(AsyncStep::Await(suspension));
+                            }
+                        }
+                    }
+                }
+                match *default {
+                    Some(ref default) if !matched => self.run_async(default),
+                    _ => Ok(AsyncStep::Done(result)),
+                }
+            }
+            _ => self.run(expr).map(AsyncStep::Done),
+        }
+    }
+
+    /// Stop at an `await` in the current environment, with none of the rest of the body gathered yet
+    fn suspend(&self, value: Value, binding: Option<String>) -> AsyncStep {
+        AsyncStep::Await(Suspension {
+            value,
+            binding,
+            environment: self.environment.get_current_environment_ref().clone(),
+            segments: Vec::new(),
+        })
+    }
+
+    /// Keep code to be run later in the current environment
+    fn segment(&self, code: Expr) -> Segment {
+        Segment {
+            environment: self.environment.get_current_environment_ref().clone(),
+            code,
+        }
+    }
+
+    /// Make the environment the body of a `with` statement runs in, whose outer environment has the properties of
+    /// the object as its bindings
+    fn with_environment(&mut self, obj: &Expr) -> Result<Environment, Value> {
+        let obj = self.run(obj)?;
+        let obj = self.to_object(&obj)?;
+        let outer = self.environment.get_current_environment_ref().clone();
+        let object_env = new_object_environment(obj, true, Some(outer));
+        // `let` and `const` in the body are scoped to it, rather than becoming properties of the object
+        Ok(new_declerative_environment(Some(object_env)))
+    }
 }
 
 impl Executor for Interpreter {
     fn new() -> Interpreter {
-        let global = ValueData::new_obj(None);
-        object::init(&global);
-        console::init(&global);
-        math::init(&global);
-        array::init(&global);
-        function::init(&global);
-        json::init(&global);
-        string::init(&global);
-
+        let realm = Realm::new();
         Interpreter {
-            environment: LexicalEnvironment::new(global.clone()),
+            environment: LexicalEnvironment::new(realm.global_environment()),
+            jobs: VecDeque::new(),
+            timers: TimerQueue::new(),
+            continuations: Continuations::new(),
+            modules: ModuleMap::new(Box::new(MemoryModuleLoader::new())),
+            call_stack: vec![StackFrame::new(None, SCRIPT_FILE_NAME)],
+            clock: Box::new(SystemClock),
+            random_source: Box::new(SystemRandom),
+            console: Console::new(Box::new(StdioSink)),
+            dynamic_code_allowed: true,
+            realms: vec![realm],
         }
     }
 
     fn run(&mut self, expr: &Expr) -> ResultValue {
+        self.set_position(&expr.pos);
         match expr.def {
             ExprDef::ConstExpr(Const::Null) => Ok(to_value(None::<()>)),
             ExprDef::ConstExpr(Const::Undefined) => Ok(Gc::new(ValueData::Undefined)),
             ExprDef::ConstExpr(Const::Num(num)) => Ok(to_value(num)),
             ExprDef::ConstExpr(Const::Int(num)) => Ok(to_value(num)),
+            ExprDef::ConstExpr(Const::BigInt(ref num)) => Ok(Gc::new(ValueData::BigInt(num.clone()))),
             ExprDef::ConstExpr(Const::String(ref str)) => Ok(to_value(str.to_owned())),
             ExprDef::ConstExpr(Const::Bool(val)) => Ok(to_value(val)),
-            ExprDef::ConstExpr(Const::RegExp(_, _, _)) => Ok(to_value(None::<()>)),
+            ExprDef::ConstExpr(Const::RegExp(ref body, ref flags)) => {
+                regexp::regexp_create(body, flags, self)
+            }
             ExprDef::BlockExpr(ref es) => {
                 let mut obj = to_value(None::<()>);
                 for e in es.iter() {
@@ -61,33 +1314,46 @@ impl Executor for Interpreter {
                 }
                 Ok(obj)
             }
-            ExprDef::LocalExpr(ref name) => {
-                let val = self.environment.get_binding_value(name.to_string());
-                Ok(val)
-            }
+            ExprDef::LocalExpr(ref name) => match self.resolve_binding(name)? {
+                Some(env) => self.get_binding_value(&env, name),
+                None => Err(self.reference_error(format!("{} is not defined", name))),
+            },
+            ExprDef::ThisExpr => self.environment.get_this_binding().map_err(|err| self.environment_error(err)),
             ExprDef::GetConstFieldExpr(ref obj, ref field) => {
                 let val_obj = self.run(obj)?;
-                Ok((&val_obj).get_field(field.clone()))
+                self.set_position(&expr.pos);
+                self.get_member(&val_obj, field.clone())
             }
             ExprDef::GetFieldExpr(ref obj, ref field) => {
                 let val_obj = self.run(obj)?;
                 let val_field = self.run(field)?;
-                Ok((&val_obj).get_field((&val_field).to_string()))
+                self.set_position(&expr.pos);
+                self.get_member(&val_obj, val_field.to_property_key())
             }
             ExprDef::CallExpr(ref callee, ref args) => {
                 let (this, func) = match callee.def {
                     ExprDef::GetConstFieldExpr(ref obj, ref field) => {
                         let obj = self.run(obj)?;
-                        (obj.clone(), (&obj).get_field(field.clone()))
+                        self.set_position(&expr.pos);
+                        let func = self.get_member(&obj, field.clone())?;
+                        (obj, func)
                     }
                     ExprDef::GetFieldExpr(ref obj, ref field) => {
                         let obj = self.run(obj)?;
                         let field = self.run(field)?;
-                        (
-                            obj.clone(),
-                            (&obj).get_field((&field).to_string()),
-                        )
+                        self.set_position(&expr.pos);
+                        let func = self.get_member(&obj, field.to_property_key())?;
+                        (obj, func)
                     }
+                    // Functions found on the object of a `with` statement are called with it as `this`
+                    ExprDef::LocalExpr(ref name) => match self.resolve_binding(name)? {
+                        Some(env) => {
+                            let base = env.borrow().with_base_object();
+                            let this = if base.is_undefined() { self.get_global_object() } else { base };
+                            (this, self.get_binding_value(&env, name)?)
+                        }
+                        None => return Err(self.reference_error(format!("{} is not defined", name))),
+                    },
                     _ => (
                         self.environment.get_global_object().unwrap(),
                         self.run(&callee.clone())?,
@@ -97,35 +1363,19 @@ impl Executor for Interpreter {
                 for arg in args.iter() {
                     v_args.push(self.run(arg)?);
                 }
-                match *func {
-                    ValueData::Function(ref inner_func) => match *inner_func.borrow() {
-                        Function::NativeFunc(ref ntv) => {
-                            let func = ntv.data;
-                            func(this, self.run(callee)?, v_args)
-                        }
-                        Function::RegularFunc(ref data) => {
-                            let env = &mut self.environment;
-                            // New target (second argument) is only needed for constructors, just pass undefined
-                            let undefined = Gc::new(ValueData::Undefined);
-                            env.push(new_function_environment(
-                                func.clone(),
-                                undefined,
-                                Some(env.get_current_environment_ref().clone()),
-                            ));
-                            for i in 0..data.args.len() {
-                                let name = data.args.get(i).unwrap();
-                                let expr = v_args.get(i).unwrap();
-                                self.environment.create_mutable_binding(name.clone(), false);
-                                self.environment
-                                    .initialize_binding(name.clone(), expr.to_owned());
-                            }
-                            let result = self.run(&data.expr);
-                            self.environment.pop();
-                            result
-                        }
-                    },
-                    _ => Err(Gc::new(ValueData::Undefined)),
+                self.set_position(&expr.pos);
+                // Calling the real `eval` by its name is a direct eval, which runs in this scope
+                if let ExprDef::LocalExpr(ref name) = callee.def {
+                    if name == "eval" && func.has_private_field("IsEval") {
+                        let source = v_args.into_iter().next().unwrap_or_else(|| Gc::new(ValueData::Undefined));
+                        return self.perform_eval(&source, true);
+                    }
                 }
+                self.call(&func, &this, v_args)
+            }
+            ExprDef::WithExpr(ref obj, ref body) => {
+                let body_env = self.with_environment(obj)?;
+                self.run_in_environment(body_env, body)
             }
             ExprDef::WhileLoopExpr(ref cond, ref expr) => {
                 let mut result = Gc::new(ValueData::Undefined);
@@ -176,48 +1426,107 @@ impl Executor for Interpreter {
                 let global_val = &self.environment.get_global_object().unwrap();
                 let obj = ValueData::new_obj(Some(global_val));
                 for (key, val) in map.iter() {
-                   (&obj).set_field(key.clone(), self.run(val)?);
+                    let val_obj = self.run(val)?;
+                    if is_anonymous_function_definition(val) {
+                        function::set_function_name(&val_obj, key);
+                    }
+                    obj.set_field(key.clone(), val_obj);
                 }
                 Ok(obj)
             }
             ExprDef::ArrayDeclExpr(ref arr) => {
-                let global_val = &self.environment.get_global_object().unwrap();
-                let arr_map = ValueData::new_obj(Some(global_val));
-                let mut index: i32 = 0;
+                let mut values = Vec::with_capacity(arr.len());
                 for val in arr.iter() {
-                    let val = self.run(val)?;
-                    (&arr_map).set_field(index.to_string(), val);
-                    index += 1;
-                }
-                (&arr_map).set_field_slice(
-                    INSTANCE_PROTOTYPE,
-                    (&(self.environment
-                        .get_binding_value("Array".to_string())))
-                        .get_field_slice(PROTOTYPE),
-                );
-                (&arr_map).set_field_slice("length", to_value(index));
-                Ok(arr_map)
-            }
-            ExprDef::FunctionDeclExpr(ref name, ref args, ref expr) => {
-                let function =
-                    Function::RegularFunc(RegularFunction::new(*expr.clone(), args.clone()));
-                let val = Gc::new(ValueData::Function(GcCell::new(function)));
-                if name.is_some() {
+                    values.push(self.run(val)?);
+                }
+                Ok(array::new_array(&self.get_global_object(), values))
+            }
+            ExprDef::FunctionDeclExpr(ref name, _, _, _) => {
+                let val = self.make_function(&expr.def);
+                // Functions declared with `function` are constructors, so they get a prototype object for their instances
+                let global = self.get_global_object();
+                let prototype = ValueData::new_obj(Some(&global));
+                let mut constructor = Property::data(val.clone());
+                constructor.enumerable = false;
+                prototype.set_prop_slice("constructor", constructor);
+                let mut prototype = Property::new(prototype);
+                prototype.writable = true;
+                val.set_prop_slice(PROTOTYPE, prototype);
+                if let Some(ref name) = *name {
                     self.environment
-                    .create_mutable_binding(name.clone().unwrap(), false);
-                self.environment
-                    .initialize_binding(name.clone().unwrap(), val.clone())
+                        .declare_var(name.clone(), Some(val.clone()))
+                        .map_err(|err| self.environment_error(err))?;
                 }
                 Ok(val)
             }
-            ExprDef::ArrowFunctionDeclExpr(ref args, ref expr) => {
-                let function =
-                    Function::RegularFunc(RegularFunction::new(*expr.clone(), args.clone()));
-                Ok(Gc::new(ValueData::Function(GcCell::new(function))))
+            ExprDef::AsyncFunctionDeclExpr(ref name, _, _, _) => {
+                let val = self.make_function(&expr.def);
+                if let Some(ref name) = *name {
+                    self.environment
+                        .declare_var(name.clone(), Some(val.clone()))
+                        .map_err(|err| self.environment_error(err))?;
+                }
+                Ok(val)
+            }
+            // Imports are bound when the module is linked
+            ExprDef::ImportDeclExpr(_, _) => Ok(Gc::new(ValueData::Undefined)),
+            ExprDef::ExportDeclExpr(ref decl) => {
+                match *decl {
+                    ExportDecl::Decl(ref decl) => {
+                        self.run(decl)?;
+                    }
+                    ExportDecl::Default(ref expr) => {
+                        let val = self.run(expr)?;
+                        match expr.def {
+                            ExprDef::FunctionDeclExpr(Some(_), _, _, _)
+                            | ExprDef::AsyncFunctionDeclExpr(Some(_), _, _, _) => (),
+                            _ => {
+                                let name = module::DEFAULT_EXPORT_BINDING.to_string();
+                                self.environment
+                                    .create_immutable_binding(name.clone(), true)
+                                    .and_then(|()| self.environment.initialize_binding(name, val))
+                                    .map_err(|err| self.environment_error(err))?;
+                            }
+                        }
+                    }
+                    ExportDecl::Named(_, _) | ExportDecl::All(_, _) => (),
+                }
+                Ok(Gc::new(ValueData::Undefined))
+            }
+            ExprDef::ImportCallExpr(ref specifier) => {
+                let specifier = self.run(specifier)?.to_string();
+                let referrer = self.modules.current_module();
+                match module::import_module(self, &specifier, referrer.as_deref()) {
+                    Ok(namespace) => Ok(namespace),
+                    // Errors loading or linking the module reject the promise, rather than being thrown
+                    Err(err) => {
+                        let capability = promise::new_promise_capability(self);
+                        let undefined = Gc::new(ValueData::Undefined);
+                        self.call(&capability.get_field_slice("reject"), &undefined, vec![err])?;
+                        Ok(capability.get_field_slice("promise"))
+                    }
+                }
+            }
+            ExprDef::ArrowFunctionDeclExpr(_, _, _) | ExprDef::AsyncArrowFunctionDeclExpr(_, _, _) => {
+                Ok(self.make_function(&expr.def))
+            }
+            // Async function and module bodies are run by `run_async`, which stops at their `await`s
+            ExprDef::AwaitExpr(_) => {
+                Err(self.syntax_error("await is only valid in async functions and the top level bodies of modules"))
             }
             ExprDef::BinOpExpr(BinOp::Num(ref op), ref a, ref b) => {
                 let v_r_a = self.run(a)?;
                 let v_r_b = self.run(b)?;
+                let hint = if *op == NumOp::Add { "default" } else { "number" };
+                let v_r_a = self.to_primitive(&v_r_a, hint)?;
+                let v_r_b = self.to_primitive(&v_r_b, hint)?;
+                if v_r_a.is_symbol() || v_r_b.is_symbol() {
+                    return Err(self.type_error("Cannot convert a Symbol value"));
+                }
+                let concat = *op == NumOp::Add && (v_r_a.is_string() || v_r_b.is_string());
+                if !concat && (v_r_a.is_bigint() || v_r_b.is_bigint()) {
+                    return bigint::num_op(op, &v_r_a, &v_r_b, self);
+                }
                 let v_a = (*v_r_a).clone();
                 let v_b = (*v_r_b).clone();
                 Ok(Gc::new(match *op {
@@ -230,6 +1539,22 @@ impl Executor for Interpreter {
             }
             ExprDef::UnaryOpExpr(ref op, ref a) => {
                 let v_r_a = self.run(a)?;
+                let v_r_a = match *op {
+                    UnaryOp::Minus | UnaryOp::Plus => self.to_primitive(&v_r_a, "number")?,
+                    _ => v_r_a,
+                };
+                if v_r_a.is_symbol() && *op != UnaryOp::Not {
+                    return Err(self.type_error("Cannot convert a Symbol value to a number"));
+                }
+                if let ValueData::BigInt(ref num) = *v_r_a {
+                    match *op {
+                        UnaryOp::Minus => return Ok(bigint::negate(num)),
+                        UnaryOp::Plus => {
+                            return Err(self.type_error("Cannot convert a BigInt value to a number"))
+                        }
+                        _ => (),
+                    }
+                }
                 let v_a = (*v_r_a).clone();
                 Ok(match *op {
                     UnaryOp::Minus => to_value(-v_a.to_num()),
@@ -241,6 +1566,11 @@ impl Executor for Interpreter {
             ExprDef::BinOpExpr(BinOp::Bit(ref op), ref a, ref b) => {
                 let v_r_a = self.run(a)?;
                 let v_r_b = self.run(b)?;
+                let v_r_a = self.to_primitive(&v_r_a, "number")?;
+                let v_r_b = self.to_primitive(&v_r_b, "number")?;
+                if v_r_a.is_bigint() || v_r_b.is_bigint() {
+                    return bigint::bit_op(op, &v_r_a, &v_r_b, self);
+                }
                 let v_a = (*v_r_a).clone();
                 let v_b = (*v_r_b).clone();
                 Ok(Gc::new(match *op {
@@ -251,24 +1581,62 @@ impl Executor for Interpreter {
                     BitOp::Shr => v_a >> v_b,
                 }))
             }
+            ExprDef::BinOpExpr(BinOp::Comp(CompOp::InstanceOf), ref a, ref b) => {
+                let v_a = self.run(a)?;
+                let v_b = self.run(b)?;
+                Ok(to_value(self.instance_of(&v_a, &v_b)?))
+            }
             ExprDef::BinOpExpr(BinOp::Comp(ref op), ref a, ref b) => {
-                let v_r_a = self.run(a)?;
-                let v_r_b = self.run(b)?;
+                let mut v_r_a = self.run(a)?;
+                let mut v_r_b = self.run(b)?;
+                match *op {
+                    CompOp::GreaterThan
+                    | CompOp::GreaterThanOrEqual
+                    | CompOp::LessThan
+                    | CompOp::LessThanOrEqual => {
+                        v_r_a = self.to_primitive(&v_r_a, "number")?;
+                        v_r_b = self.to_primitive(&v_r_b, "number")?;
+                    }
+                    _ => (),
+                }
+                if v_r_a.is_bigint() || v_r_b.is_bigint() {
+                    let ordering = bigint::compare(&v_r_a, &v_r_b);
+                    let strict_equal = v_r_a.is_bigint() && v_r_b.is_bigint() && v_r_a == v_r_b;
+                    return Ok(to_value(match *op {
+                        CompOp::Equal => v_r_a == v_r_b,
+                        CompOp::NotEqual => v_r_a != v_r_b,
+                        CompOp::StrictEqual => strict_equal,
+                        CompOp::StrictNotEqual => !strict_equal,
+                        CompOp::GreaterThan => ordering == Some(Ordering::Greater),
+                        CompOp::GreaterThanOrEqual => {
+                            ordering == Some(Ordering::Greater) || ordering == Some(Ordering::Equal)
+                        }
+                        CompOp::LessThan => ordering == Some(Ordering::Less),
+                        CompOp::LessThanOrEqual => {
+                            ordering == Some(Ordering::Less) || ordering == Some(Ordering::Equal)
+                        }
+                        CompOp::InstanceOf => unreachable!(),
+                    }));
+                }
                 let v_a = &v_r_a;
                 let v_b = &v_r_b;
+                // Objects and functions are only equal to themselves
+                let is_reference = |v: &Value| v.is_object() || v.is_function();
+                let loose_equal = if is_reference(v_a) && is_reference(v_b) {
+                    Gc::ptr_eq(v_a, v_b)
+                } else {
+                    v_a == v_b
+                };
                 Ok(to_value(match *op {
-                    CompOp::Equal if v_a.is_object() => v_r_a == v_r_b,
-                    CompOp::Equal => v_a == v_b,
-                    CompOp::NotEqual if v_a.is_object() => v_r_a != v_r_b,
-                    CompOp::NotEqual => v_a != v_b,
-                    CompOp::StrictEqual if v_a.is_object() => v_r_a == v_r_b,
-                    CompOp::StrictEqual => v_a == v_b,
-                    CompOp::StrictNotEqual if v_a.is_object() => v_r_a != v_r_b,
-                    CompOp::StrictNotEqual => v_a != v_b,
+                    CompOp::Equal => loose_equal,
+                    CompOp::NotEqual => !loose_equal,
+                    CompOp::StrictEqual => strict_equals(v_a, v_b),
+                    CompOp::StrictNotEqual => !strict_equals(v_a, v_b),
                     CompOp::GreaterThan => v_a.to_num() > v_b.to_num(),
                     CompOp::GreaterThanOrEqual => v_a.to_num() >= v_b.to_num(),
                     CompOp::LessThan => v_a.to_num() < v_b.to_num(),
                     CompOp::LessThanOrEqual => v_a.to_num() <= v_b.to_num(),
+                    CompOp::InstanceOf => unreachable!(),
                 }))
             }
             ExprDef::BinOpExpr(BinOp::Log(ref op), ref a, ref b) => {
@@ -285,41 +1653,8 @@ impl Executor for Interpreter {
                 for arg in args.iter() {
                     v_args.push(self.run(arg)?);
                 }
-                let this = Gc::new(ValueData::Object(
-                    GcCell::new(HashMap::new()),
-                    GcCell::new(HashMap::new()),
-                ));
-                // Create a blank object, then set its __proto__ property to the [Constructor].prototype
-                (&this)
-                    .set_field_slice(INSTANCE_PROTOTYPE, (&func).get_field_slice(PROTOTYPE));
-                match *func {
-                    ValueData::Function(ref inner_func) => match inner_func.clone().into_inner() {
-                        Function::NativeFunc(ref ntv) => {
-                            let func = ntv.data;
-                            func(this, self.run(callee)?, v_args)
-                        }
-                        Function::RegularFunc(ref data) => {
-                            // Create new scope
-                            let env = &mut self.environment;
-                            env.push(new_function_environment(
-                                func.clone(),
-                                this.clone(),
-                                Some(env.get_current_environment_ref().clone()),
-                            ));
-
-                            for i in 0..data.args.len() {
-                                let name = data.args.get(i).unwrap();
-                                let expr = v_args.get(i).unwrap();
-                                env.create_mutable_binding(name.clone(), false);
-                                env.initialize_binding(name.clone(), expr.to_owned());
-                            }
-                            let result = self.run(&data.expr);
-                            self.environment.pop();
-                            result
-                        }
-                    },
-                    _ => Ok(Gc::new(ValueData::Undefined)),
-                }
+                self.set_position(&expr.pos);
+                self.construct(&func, v_args)
             }
             ExprDef::ReturnExpr(ref ret) => match *ret {
                 Some(ref v) => self.run(v),
@@ -330,13 +1665,16 @@ impl Executor for Interpreter {
                 let val = self.run(val_e)?;
                 match ref_e.def {
                     ExprDef::LocalExpr(ref name) => {
-                        self.environment.create_mutable_binding(name.clone(), false);
-                        self.environment
-                            .initialize_binding(name.clone(), val.clone());
+                        self.assign_binding(name, val.clone())?;
                     }
                     ExprDef::GetConstFieldExpr(ref obj, ref field) => {
                         let val_obj = self.run(obj)?;
-                        (&val_obj).set_field(field.clone(), val.clone());
+                        self.set_field(&val_obj, field.clone(), val.clone())?;
+                    }
+                    ExprDef::GetFieldExpr(ref obj, ref field) => {
+                        let val_obj = self.run(obj)?;
+                        let val_field = self.run(field)?;
+                        self.set_field(&val_obj, val_field.to_property_key(), val.clone())?;
                     }
                     _ => (),
                 }
@@ -346,47 +1684,87 @@ impl Executor for Interpreter {
                 for var in vars.iter() {
                     let (name, value) = var.clone();
                     let val = match value {
-                        Some(v) => self.run(&v)?,
-                        None => Gc::new(ValueData::Null),
+                        Some(ref v) => {
+                            let val = self.run(v)?;
+                            if is_anonymous_function_definition(v) {
+                                function::set_function_name(&val, &name);
+                            }
+                            Some(val)
+                        }
+                        None => None,
+                    };
+                    // `var` goes in the enclosing function, even from inside `eval` code or a `with` statement,
+                    // but its value is assigned to the name as it resolves here, which may be a `with` object's property
+                    let val = match val {
+                        Some(val) if self.resolves_to_with_object(&name)? => {
+                            self.assign_binding(&name, val)?;
+                            None
+                        }
+                        val => val,
                     };
-                    self.environment.create_mutable_binding(name.clone(), false);
-                    self.environment.initialize_binding(name, val);
+                    self.environment
+                        .declare_var(name, val)
+                        .map_err(|err| self.environment_error(err))?;
                 }
                 Ok(Gc::new(ValueData::Undefined))
             }
             ExprDef::LetDeclExpr(ref vars) => {
                 for var in vars.iter() {
                     let (name, value) = var.clone();
+                    if self.environment.has_own_binding(&name) {
+                        return Err(self.syntax_error(format!("Identifier '{}' has already been declared", name)));
+                    }
                     let val = match value {
-                        Some(v) => r#try!(self.run(&v)),
+                        Some(ref v) => r#try!(self.run(v)),
                         None => Gc::new(ValueData::Null),
                     };
-                    self.environment.create_mutable_binding(name.clone(), false);
-                    self.environment.initialize_binding(name, val);
+                    if value.as_ref().is_some_and(is_anonymous_function_definition) {
+                        function::set_function_name(&val, &name);
+                    }
+                    self.environment
+                        .create_mutable_binding(name.clone(), false)
+                        .and_then(|()| self.environment.initialize_binding(name, val))
+                        .map_err(|err| self.environment_error(err))?;
                 }
                 Ok(Gc::new(ValueData::Undefined))
             }
             ExprDef::ConstDeclExpr(ref vars) => {
                 for var in vars.iter() {
                     let (name, value) = var.clone();
+                    if self.environment.has_own_binding(&name) {
+                        return Err(self.syntax_error(format!("Identifier '{}' has already been declared", name)));
+                    }
                     let val = match value {
-                        Some(v) => r#try!(self.run(&v)),
+                        Some(ref v) => r#try!(self.run(v)),
                         None => Gc::new(ValueData::Null),
                     };
+                    if value.as_ref().is_some_and(is_anonymous_function_definition) {
+                        function::set_function_name(&val, &name);
+                    }
                     self.environment
-                        .create_immutable_binding(name.clone(), false);
-                    self.environment.initialize_binding(name, val);
+                        .create_immutable_binding(name.clone(), true)
+                        .and_then(|()| self.environment.initialize_binding(name, val))
+                        .map_err(|err| self.environment_error(err))?;
                 }
                 Ok(Gc::new(ValueData::Undefined))
             }
             ExprDef::TypeOfExpr(ref val_e) => {
-                let val = self.run(val_e)?;
+                // `typeof` an unresolvable name is "undefined" rather than a ReferenceError
+                let val = match val_e.def {
+                    ExprDef::LocalExpr(ref name) => match self.resolve_binding(name)? {
+                        Some(env) => self.get_binding_value(&env, name)?,
+                        None => Gc::new(ValueData::Undefined),
+                    },
+                    _ => self.run(val_e)?,
+                };
                 Ok(to_value(match *val {
                     ValueData::Undefined => "undefined",
-                    ValueData::Null | ValueData::Object(_, _) => "object",
+                    ValueData::Null | ValueData::Object(_, _) | ValueData::Buffer(_) => "object",
                     ValueData::Boolean(_) => "boolean",
                     ValueData::Number(_) | ValueData::Integer(_) => "number",
                     ValueData::String(_) => "string",
+                    ValueData::Symbol(_) => "symbol",
+                    ValueData::BigInt(_) => "bigint",
                     ValueData::Function(_) => "function",
                 }))
             }
@@ -1,19 +1,1087 @@
+/// The Array Object
+/// https://tc39.es/ecma262/#sec-array-objects
 use gc::Gc;
+use std::cmp::Ordering;
+use crate::exec::Interpreter;
+use crate::js::conversion::{relative_index, to_integer_or_infinity, to_length};
 use crate::js::function::NativeFunctionData;
-use crate::js::value::{to_value, ResultValue, Value, ValueData};
+use crate::js::iterator::{self, ArrayIterationKind};
+use crate::js::promise;
+use crate::js::string::JsString;
+use crate::js::proxy;
+use crate::js::object::{delete_property_or_throw, set_or_throw, ObjectData, Property, PropertyKey, PROTOTYPE};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, same_value_zero, strict_equals, to_value, ResultValue, Value, ValueData};
 
-/// Create a new array
-pub fn make_array(this: Value, _: Value, _: Vec<Value>) -> ResultValue {
-    let this_ptr = this.clone();
-    this_ptr.set_field_slice("length", to_value(0i32));
+/// The private field marking an object as an Array exotic object
+pub static IS_ARRAY: &str = "IsArray";
+
+/// The methods listed in `Array.prototype[Symbol.unscopables]`
+/// https://tc39.es/ecma262/#sec-array.prototype-%symbol.unscopables%
+const UNSCOPABLES: [&str; 16] = [
+    "at", "copyWithin", "entries", "fill", "find", "findIndex", "findLast", "findLastIndex", "flat", "flatMap",
+    "includes", "keys", "toReversed", "toSorted", "toSpliced", "values",
+];
+
+/// Get the array index a property key refers to, if it is the canonical form of an integer below 2^32 - 1
+/// https://tc39.es/ecma262/#array-index
+pub fn array_index(key: &PropertyKey) -> Option<u32> {
+    match *key {
+        PropertyKey::String(ref key) => match key.parse::<u32>() {
+            Ok(index) if index != u32::MAX && index.to_string() == *key => Some(index),
+            _ => None,
+        },
+        PropertyKey::Symbol(_) => None,
+    }
+}
+
+/// Get the value as an array length, if it is an integer which fits
+pub fn valid_length(value: &Value) -> Option<u32> {
+    let num = value.to_num();
+    if num >= 0.0 && num.trunc() == num && num <= f64::from(u32::MAX) {
+        Some(num as u32)
+    } else {
+        None
+    }
+}
+
+/// Make the value of a `length` property
+fn length_value(length: f64) -> Value {
+    if length <= f64::from(i32::MAX) {
+        to_value(length as i32)
+    } else {
+        to_value(length)
+    }
+}
+
+/// Set the `length` property of an array, which is writable but not enumerable or configurable
+fn store_length(obj: &mut ObjectData, length: f64) {
+    let length_key = PropertyKey::from("length");
+    match obj.get_mut(&length_key) {
+        Some(prop) => prop.value = length_value(length),
+        None => {
+            let mut prop = Property::new(length_value(length));
+            prop.writable = true;
+            obj.insert(length_key, prop);
+        }
+    }
+}
+
+/// Delete the elements at or past a new length, which stops at the last element that isn't configurable, and
+/// return the length which is left
+/// https://tc39.es/ecma262/#sec-arraysetlength
+fn truncate(obj: &mut ObjectData, new_length: u32) -> u32 {
+    let length = obj
+        .iter()
+        .filter(|(_, prop)| !prop.configurable)
+        .filter_map(|(key, _)| array_index(key))
+        .filter(|index| *index >= new_length)
+        .map(|index| index + 1)
+        .max()
+        .unwrap_or(new_length);
+    obj.retain(|key, _| array_index(key).is_none_or(|index| index < length));
+    length
+}
+
+/// Set a property of an array, growing `length` when writing past the end and deleting elements when `length` shrinks
+/// https://tc39.es/ecma262/#sec-array-exotic-objects-defineownproperty-p-desc
+pub fn array_set(obj: &mut ObjectData, field: PropertyKey, val: Value) {
+    let length_key = PropertyKey::from("length");
+    if field == length_key {
+        if let Some(new_length) = valid_length(&val) {
+            let length = truncate(obj, new_length);
+            store_length(obj, f64::from(length));
+            return;
+        }
+    } else if let Some(index) = array_index(&field) {
+        let length = obj
+            .get(&length_key)
+            .map_or(0.0, |prop| prop.value.to_num());
+        if f64::from(index) >= length {
+            store_length(obj, f64::from(index) + 1.0);
+        }
+    }
+    obj.set_value(field, val);
+}
+
+/// Create a new array object holding the given values
+pub fn new_array(global: &Value, values: Vec<Value>) -> Value {
+    let proto = global.get_field_slice("Array").get_field_slice(PROTOTYPE);
+    let array = ValueData::new_obj_from_prototype(proto);
+    array.set_private_field_slice(IS_ARRAY, to_value(true));
+    array.set_field_slice("length", to_value(0i32));
+    for (index, value) in values.into_iter().enumerate() {
+        array.set_field(index.to_string(), value);
+    }
+    array
+}
+
+/// Get the callback argument of an iteration method, which must be a function
+pub(crate) fn callback_arg(args: &[Value], interpreter: &Interpreter) -> ResultValue {
+    let callback = arg(args, 0);
+    if callback.is_function() {
+        Ok(callback)
+    } else {
+        Err(interpreter.type_error(format!("{} is not a function", callback)))
+    }
+}
+
+/// Get the length of an array-like object
+/// https://tc39.es/ecma262/#sec-lengthofarraylike
+pub fn length_of(obj: &Value, interpreter: &mut Interpreter) -> Result<f64, Value> {
+    let length = interpreter.get_field(obj, "length")?;
+    to_length(&length, interpreter)
+}
+
+/// Set the length of an array-like object, throwing if it is read-only
+fn set_length(obj: &Value, length: f64, interpreter: &mut Interpreter) -> Result<(), Value> {
+    set_or_throw(obj, PropertyKey::from("length"), length_value(length), interpreter)
+}
+
+/// Get the element at an index
+fn get_index(obj: &Value, index: f64, interpreter: &mut Interpreter) -> ResultValue {
+    interpreter.get_field(obj, index.to_string())
+}
+
+/// Set the element at an index, throwing if it is read-only or can't be added
+fn set_index(obj: &Value, index: f64, value: Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    set_or_throw(obj, PropertyKey::from(index.to_string()), value, interpreter)
+}
+
+/// Returns true if the object, or its prototype chain, has an element at the index
+fn has_index(obj: &Value, index: f64, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    interpreter.has_property(obj, &PropertyKey::from(index.to_string()))
+}
+
+/// Remove the element at an index, leaving a hole, throwing if it isn't configurable
+fn delete_index(obj: &Value, index: f64, interpreter: &mut Interpreter) -> Result<(), Value> {
+    delete_property_or_throw(obj, PropertyKey::from(index.to_string()), interpreter)
+}
+
+/// Call a callback with `(element, index, array)`
+fn call_with_element(
+    callback: &Value,
+    this_arg: &Value,
+    element: Value,
+    index: f64,
+    obj: &Value,
+    interpreter: &mut Interpreter,
+) -> ResultValue {
+    interpreter.call(callback, this_arg, vec![element, length_value(index), obj.clone()])
+}
+
+/// Create a new array, which is the same as `new Array(...args)` or `Array(...args)`
+/// https://tc39.es/ecma262/#sec-array-constructor
+pub fn make_array(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    if args.len() == 1 && args[0].get_type() == "number" {
+        let length = match valid_length(&args[0]) {
+            Some(length) => length,
+            None => return Err(interpreter.range_error("Invalid array length")),
+        };
+        let array = new_array(&global, Vec::new());
+        array.set_field_slice("length", length_value(f64::from(length)));
+        return Ok(array);
+    }
+    Ok(new_array(&global, args))
+}
+
+/// Returns true if the value is an array
+pub fn is_array(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(is_array_value(&arg(&args, 0), interpreter)?))
+}
+
+/// Returns true if the value is an array, or a proxy for an array
+/// https://tc39.es/ecma262/#sec-isarray
+pub fn is_array_value(value: &Value, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    if !proxy::is_proxy(value) {
+        return Ok(value.is_array());
+    }
+    let target = value.get_private_field_slice(proxy::PROXY_TARGET);
+    if target.is_null() {
+        return Err(interpreter.type_error("Cannot perform 'IsArray' on a proxy that has been revoked"));
+    }
+    is_array_value(&target, interpreter)
+}
+
+/// Create an array from the arguments
+pub fn of(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(new_array(&interpreter.get_global_object(), args))
+}
+
+/// Create an array from an iterable or array-like object, optionally mapping each value
+/// https://tc39.es/ecma262/#sec-array.from
+pub fn from(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let items = arg(&args, 0);
+    let map_fn = arg(&args, 1);
+    let this_arg = arg(&args, 2);
+    if !map_fn.is_undefined() && !map_fn.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", map_fn)));
+    }
+    if items.is_null_or_undefined() {
+        return Err(interpreter.type_error(format!("{} is not iterable", items)));
+    }
+    let iterator_method = interpreter.get_field(&items, Symbol::well_known(WellKnownSymbol::Iterator))?;
+    let values = if iterator_method.is_null_or_undefined() {
+        let length = length_of(&items, interpreter)?;
+        let mut values = Vec::new();
+        let mut index = 0.0;
+        while index < length {
+            values.push(get_index(&items, index, interpreter)?);
+            index += 1.0;
+        }
+        values
+    } else {
+        iterator::iterable_to_list(&items, interpreter)?
+    };
+    let values = if map_fn.is_function() {
+        let mut mapped = Vec::with_capacity(values.len());
+        for (index, value) in values.into_iter().enumerate() {
+            mapped.push(interpreter.call(&map_fn, &this_arg, vec![value, to_value(index as i32)])?);
+        }
+        mapped
+    } else {
+        values
+    };
+    Ok(new_array(&interpreter.get_global_object(), values))
+}
+
+/// Create an array from an async iterable, iterable or array-like value, returning a promise for it
+/// https://tc39.es/proposal-array-from-async/#sec-array.fromAsync
+pub fn from_async(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let capability = promise::new_promise_capability(interpreter);
+    if let Err(reason) = start_from_async(&args, &capability, interpreter) {
+        let undefined = Gc::new(ValueData::Undefined);
+        interpreter.call(&capability.get_field_slice("reject"), &undefined, vec![reason])?;
+    }
+    Ok(capability.get_field_slice("promise"))
+}
+
+/// Set up the state of an `Array.fromAsync` call in a shared record, then fetch its first value
+fn start_from_async(args: &[Value], capability: &Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    let items = arg(args, 0);
+    let map_fn = arg(args, 1);
+    if !map_fn.is_undefined() && !map_fn.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", map_fn)));
+    }
+    if items.is_null_or_undefined() {
+        return Err(interpreter.type_error(format!("{} is not iterable", items)));
+    }
+    let record = ValueData::new_obj(None);
+    record.set_field_slice("values", new_array(&interpreter.get_global_object(), Vec::new()));
+    record.set_field_slice("index", to_value(0i32));
+    record.set_field_slice("mapFn", map_fn);
+    record.set_field_slice("thisArg", arg(args, 2));
+    record.set_field_slice("capability", capability.clone());
+    let async_method = interpreter.get_field(&items, Symbol::well_known(WellKnownSymbol::AsyncIterator))?;
+    if !async_method.is_null_or_undefined() {
+        if !async_method.is_function() {
+            return Err(interpreter.type_error(format!("{} is not a function", async_method)));
+        }
+        let iterator = interpreter.call(&async_method, &items, Vec::new())?;
+        if !iterator.is_object() {
+            return Err(interpreter.type_error("iterator is not an object"));
+        }
+        record.set_field_slice("iterator", iterator);
+        record.set_field_slice("async", to_value(true));
+    } else if interpreter.get_field(&items, Symbol::well_known(WellKnownSymbol::Iterator))?.is_null_or_undefined() {
+        let length = length_of(&items, interpreter)?;
+        record.set_field_slice("arrayLike", items);
+        record.set_field_slice("length", to_value(length));
+    } else {
+        record.set_field_slice("iterator", iterator::get_iterator(&items, interpreter)?);
+    }
+    from_async_step(&record, interpreter)
+}
+
+/// Fetch the next value for `Array.fromAsync`, resolving its promise once there are none left
+fn from_async_step(record: &Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    let array_like = record.get_field_slice("arrayLike");
+    if !array_like.is_undefined() {
+        let index = record.get_field_slice("index").to_num();
+        if index >= record.get_field_slice("length").to_num() {
+            return from_async_finish(record, interpreter);
+        }
+        let value = get_index(&array_like, index, interpreter)?;
+        return from_async_await(record, value, from_async_value_fulfilled, false, interpreter);
+    }
+    let iterator = record.get_field_slice("iterator");
+    let next = interpreter.get_field(&iterator, "next")?;
+    let result = interpreter.call(&next, &iterator, Vec::new())?;
+    if record.get_field_slice("async").is_true() {
+        // Async iterators hand back a promise for each result, and a rejected one leaves nothing to close
+        from_async_await(record, result, from_async_result_fulfilled, false, interpreter)
+    } else {
+        from_async_result(record, result, interpreter)
+    }
+}
+
+/// Handle an iterator result for `Array.fromAsync`, waiting for the values of sync iterators as they come
+fn from_async_result(record: &Value, result: Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    if !result.is_object() {
+        return Err(interpreter.type_error("iterator result is not an object"));
+    }
+    if interpreter.get_field(&result, "done")?.is_true() {
+        return from_async_finish(record, interpreter);
+    }
+    let value = interpreter.get_field(&result, "value")?;
+    if record.get_field_slice("async").is_true() {
+        from_async_value(record, value, interpreter)
+    } else {
+        from_async_await(record, value, from_async_value_fulfilled, true, interpreter)
+    }
+}
+
+/// Run the mapping function of `Array.fromAsync` on the next value, waiting for what it returns
+fn from_async_value(record: &Value, value: Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    let map_fn = record.get_field_slice("mapFn");
+    if !map_fn.is_function() {
+        return from_async_store(record, value, interpreter);
+    }
+    let index = record.get_field_slice("index");
+    match interpreter.call(&map_fn, &record.get_field_slice("thisArg"), vec![value, index]) {
+        Ok(mapped) => from_async_await(record, mapped, from_async_mapped_fulfilled, true, interpreter),
+        Err(reason) => {
+            close_from_async(record, interpreter);
+            Err(reason)
+        }
+    }
+}
+
+/// Add the next value to the array being built by `Array.fromAsync`, then move on to the one after it
+fn from_async_store(record: &Value, value: Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    let index = record.get_field_slice("index").to_num();
+    set_index(&record.get_field_slice("values"), index, value, interpreter)?;
+    record.set_field_slice("index", to_value(index + 1.0));
+    from_async_step(record, interpreter)
+}
+
+/// Resolve the promise of `Array.fromAsync` with the finished array
+fn from_async_finish(record: &Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    let values = record.get_field_slice("values");
+    set_length(&values, record.get_field_slice("index").to_num(), interpreter)?;
+    let resolve = record.get_field_slice("capability").get_field_slice("resolve");
+    interpreter.call(&resolve, &Gc::new(ValueData::Undefined), vec![values])?;
+    Ok(())
+}
+
+/// Wait for a value on behalf of `Array.fromAsync`, continuing with the given handler once it is fulfilled
+fn from_async_await(
+    record: &Value,
+    value: Value,
+    on_fulfilled: NativeFunctionData,
+    close_on_reject: bool,
+    interpreter: &mut Interpreter,
+) -> Result<(), Value> {
+    let awaited = promise::promise_resolve(value, interpreter)?;
+    let on_rejected = if close_on_reject {
+        promise::make_closure(from_async_rejected, vec![("[[Record]]", record.clone())])
+    } else {
+        record.get_field_slice("capability").get_field_slice("reject")
+    };
+    let on_fulfilled = promise::make_closure(on_fulfilled, vec![("[[Record]]", record.clone())]);
+    promise::perform_promise_then(&awaited, on_fulfilled, on_rejected, Gc::new(ValueData::Undefined), interpreter);
+    Ok(())
+}
+
+/// Reject the promise of `Array.fromAsync` if a step failed
+fn settle_from_async(record: &Value, outcome: Result<(), Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let undefined = Gc::new(ValueData::Undefined);
+    if let Err(reason) = outcome {
+        let reject = record.get_field_slice("capability").get_field_slice("reject");
+        interpreter.call(&reject, &undefined, vec![reason])?;
+    }
+    Ok(undefined)
+}
+
+/// Close the iterator of `Array.fromAsync` after an error, ignoring anything its `return` method does
+fn close_from_async(record: &Value, interpreter: &mut Interpreter) {
+    let iterator = record.get_field_slice("iterator");
+    if !iterator.is_object() {
+        return;
+    }
+    if let Ok(method) = interpreter.get_field(&iterator, "return") {
+        if method.is_function() {
+            let _ = interpreter.call(&method, &iterator, Vec::new());
+        }
+    }
+}
+
+/// Continue `Array.fromAsync` once the result of an async iterator has settled
+fn from_async_result_fulfilled(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let record = f.get_field_slice("[[Record]]");
+    let outcome = from_async_result(&record, arg(&args, 0), interpreter);
+    settle_from_async(&record, outcome, interpreter)
+}
+
+/// Continue `Array.fromAsync` once the next value has settled
+fn from_async_value_fulfilled(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let record = f.get_field_slice("[[Record]]");
+    let outcome = from_async_value(&record, arg(&args, 0), interpreter);
+    settle_from_async(&record, outcome, interpreter)
+}
+
+/// Continue `Array.fromAsync` once the mapped value has settled
+fn from_async_mapped_fulfilled(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let record = f.get_field_slice("[[Record]]");
+    let outcome = from_async_store(&record, arg(&args, 0), interpreter);
+    settle_from_async(&record, outcome, interpreter)
+}
+
+/// Close the iterator of `Array.fromAsync` and reject its promise when a value it waited for was rejected
+fn from_async_rejected(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let record = f.get_field_slice("[[Record]]");
+    close_from_async(&record, interpreter);
+    settle_from_async(&record, Err(arg(&args, 0)), interpreter)
+}
+
+/// Add values to the end of the array, returning the new length
+/// https://tc39.es/ecma262/#sec-array.prototype.push
+pub fn push(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut length = length_of(&this, interpreter)?;
+    for value in args {
+        set_index(&this, length, value, interpreter)?;
+        length += 1.0;
+    }
+    set_length(&this, length, interpreter)?;
+    Ok(length_value(length))
+}
+
+/// Remove the last value of the array and return it
+/// https://tc39.es/ecma262/#sec-array.prototype.pop
+pub fn pop(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    if length == 0.0 {
+        set_length(&this, 0.0, interpreter)?;
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    let value = get_index(&this, length - 1.0, interpreter)?;
+    delete_index(&this, length - 1.0, interpreter)?;
+    set_length(&this, length - 1.0, interpreter)?;
+    Ok(value)
+}
+
+/// Remove the first value of the array and return it
+/// https://tc39.es/ecma262/#sec-array.prototype.shift
+pub fn shift(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    if length == 0.0 {
+        set_length(&this, 0.0, interpreter)?;
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    let first = get_index(&this, 0.0, interpreter)?;
+    let mut index = 1.0;
+    while index < length {
+        if has_index(&this, index, interpreter)? {
+            let value = get_index(&this, index, interpreter)?;
+            set_index(&this, index - 1.0, value, interpreter)?;
+        } else {
+            delete_index(&this, index - 1.0, interpreter)?;
+        }
+        index += 1.0;
+    }
+    delete_index(&this, length - 1.0, interpreter)?;
+    set_length(&this, length - 1.0, interpreter)?;
+    Ok(first)
+}
+
+/// Add values to the start of the array, returning the new length
+/// https://tc39.es/ecma262/#sec-array.prototype.unshift
+pub fn unshift(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let count = args.len() as f64;
+    if count > 0.0 {
+        let mut index = length;
+        while index > 0.0 {
+            index -= 1.0;
+            if has_index(&this, index, interpreter)? {
+                let value = get_index(&this, index, interpreter)?;
+                set_index(&this, index + count, value, interpreter)?;
+            } else {
+                delete_index(&this, index + count, interpreter)?;
+            }
+        }
+        for (index, value) in args.into_iter().enumerate() {
+            set_index(&this, index as f64, value, interpreter)?;
+        }
+    }
+    set_length(&this, length + count, interpreter)?;
+    Ok(length_value(length + count))
+}
+
+/// Copy part of the array into a new array
+/// https://tc39.es/ecma262/#sec-array.prototype.slice
+pub fn slice(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let start = relative_index(&arg(&args, 0), length as usize, 0, interpreter)? as f64;
+    let end = relative_index(&arg(&args, 1), length as usize, length as usize, interpreter)? as f64;
+    let result = new_array(&interpreter.get_global_object(), Vec::new());
+    let mut index = start;
+    while index < end {
+        if has_index(&this, index, interpreter)? {
+            let value = get_index(&this, index, interpreter)?;
+            set_index(&result, index - start, value, interpreter)?;
+        }
+        index += 1.0;
+    }
+    set_length(&result, (end - start).max(0.0), interpreter)?;
+    Ok(result)
+}
+
+/// Remove values from the array and insert new ones in their place, returning the removed values
+/// https://tc39.es/ecma262/#sec-array.prototype.splice
+pub fn splice(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let start = relative_index(&arg(&args, 0), length as usize, 0, interpreter)? as f64;
+    let delete_count = match args.len() {
+        0 => 0.0,
+        1 => length - start,
+        _ => to_integer_or_infinity(&args[1], interpreter)?.max(0.0).min(length - start),
+    };
+    let items: Vec<Value> = args.into_iter().skip(2).collect();
+    let item_count = items.len() as f64;
+
+    let removed = new_array(&interpreter.get_global_object(), Vec::new());
+    let mut index = 0.0;
+    while index < delete_count {
+        if has_index(&this, start + index, interpreter)? {
+            let value = get_index(&this, start + index, interpreter)?;
+            set_index(&removed, index, value, interpreter)?;
+        }
+        index += 1.0;
+    }
+    set_length(&removed, delete_count, interpreter)?;
+
+    // Move the values after the removed ones to make room for the new items
+    let move_value = |from: f64, to: f64, interpreter: &mut Interpreter| -> Result<(), Value> {
+        if has_index(&this, from, interpreter)? {
+            let value = get_index(&this, from, interpreter)?;
+            set_index(&this, to, value, interpreter)
+        } else {
+            delete_index(&this, to, interpreter)
+        }
+    };
+    if item_count < delete_count {
+        let mut index = start;
+        while index < length - delete_count {
+            move_value(index + delete_count, index + item_count, interpreter)?;
+            index += 1.0;
+        }
+        let mut index = length;
+        while index > length - delete_count + item_count {
+            delete_index(&this, index - 1.0, interpreter)?;
+            index -= 1.0;
+        }
+    } else if item_count > delete_count {
+        let mut index = length - delete_count;
+        while index > start {
+            move_value(index + delete_count - 1.0, index + item_count - 1.0, interpreter)?;
+            index -= 1.0;
+        }
+    }
+    for (index, item) in items.into_iter().enumerate() {
+        set_index(&this, start + index as f64, item, interpreter)?;
+    }
+    set_length(&this, length - delete_count + item_count, interpreter)?;
+    Ok(removed)
+}
+
+/// Join the array and the arguments into a new array, spreading any arguments which are arrays
+/// https://tc39.es/ecma262/#sec-array.prototype.concat
+pub fn concat(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let result = new_array(&interpreter.get_global_object(), Vec::new());
+    let mut length = 0.0;
+    for item in Some(this).into_iter().chain(args) {
+        if item.is_array() {
+            let item_length = length_of(&item, interpreter)?;
+            let mut index = 0.0;
+            while index < item_length {
+                if has_index(&item, index, interpreter)? {
+                    let value = get_index(&item, index, interpreter)?;
+                    set_index(&result, length, value, interpreter)?;
+                }
+                length += 1.0;
+                index += 1.0;
+            }
+        } else {
+            set_index(&result, length, item, interpreter)?;
+            length += 1.0;
+        }
+    }
+    set_length(&result, length, interpreter)?;
+    Ok(result)
+}
+
+/// Join the values of the array into a string, with a separator which defaults to a comma
+/// https://tc39.es/ecma262/#sec-array.prototype.join
+pub fn join(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let separator = match args.first() {
+        Some(separator) if !separator.is_undefined() => interpreter.to_js_string(separator)?,
+        _ => JsString::from(","),
+    };
+    let mut result = Vec::new();
+    let mut index = 0.0;
+    while index < length {
+        if index > 0.0 {
+            result.extend_from_slice(&separator);
+        }
+        let value = get_index(&this, index, interpreter)?;
+        if !value.is_null_or_undefined() {
+            result.extend_from_slice(&interpreter.to_js_string(&value)?);
+        }
+        index += 1.0;
+    }
+    Ok(to_value(JsString::from_units(result)))
+}
+
+/// Join the values of the array with commas
+/// https://tc39.es/ecma262/#sec-array.prototype.tostring
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    join(this, Gc::new(ValueData::Undefined), Vec::new(), interpreter)
+}
+
+/// Reverse the array in place
+/// https://tc39.es/ecma262/#sec-array.prototype.reverse
+pub fn reverse(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let middle = (length / 2.0).floor();
+    let mut lower = 0.0;
+    while lower < middle {
+        let upper = length - lower - 1.0;
+        let lower_value = if has_index(&this, lower, interpreter)? {
+            Some(get_index(&this, lower, interpreter)?)
+        } else {
+            None
+        };
+        let upper_value = if has_index(&this, upper, interpreter)? {
+            Some(get_index(&this, upper, interpreter)?)
+        } else {
+            None
+        };
+        match upper_value {
+            Some(value) => set_index(&this, lower, value, interpreter)?,
+            None => delete_index(&this, lower, interpreter)?,
+        }
+        match lower_value {
+            Some(value) => set_index(&this, upper, value, interpreter)?,
+            None => delete_index(&this, upper, interpreter)?,
+        }
+        lower += 1.0;
+    }
+    Ok(this)
+}
+
+/// Compare two values for `sort`, where undefined values are sorted to the end
+/// https://tc39.es/ecma262/#sec-sortcompare
+fn sort_compare(a: &Value, b: &Value, compare_fn: &Value, interpreter: &mut Interpreter) -> Result<Ordering, Value> {
+    match (a.is_undefined(), b.is_undefined()) {
+        (true, true) => return Ok(Ordering::Equal),
+        (true, false) => return Ok(Ordering::Greater),
+        (false, true) => return Ok(Ordering::Less),
+        (false, false) => (),
+    }
+    if compare_fn.is_function() {
+        let undefined = Gc::new(ValueData::Undefined);
+        let result = interpreter.call(compare_fn, &undefined, vec![a.clone(), b.clone()])?;
+        let result = interpreter.to_number(&result)?;
+        return Ok(if result < 0.0 {
+            Ordering::Less
+        } else if result > 0.0 {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        });
+    }
+    let a = interpreter.to_string(a)?;
+    let b = interpreter.to_string(b)?;
+    // Strings are compared by UTF-16 code units
+    Ok(a.encode_utf16().cmp(b.encode_utf16()))
+}
+
+/// A stable merge sort which stops at the first error thrown by the comparison.
+/// The comparison is user code, so it can't be trusted to be consistent.
+fn merge_sort(values: Vec<Value>, compare_fn: &Value, interpreter: &mut Interpreter) -> Result<Vec<Value>, Value> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+    let mut left = values;
+    let right = left.split_off(left.len() / 2);
+    let left = merge_sort(left, compare_fn, interpreter)?;
+    let right = merge_sort(right, compare_fn, interpreter)?;
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        if sort_compare(a, b, compare_fn, interpreter)? == Ordering::Greater {
+            merged.push(right.next().unwrap());
+        } else {
+            merged.push(left.next().unwrap());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
+/// Sort the array in place, with holes moved to the end
+/// https://tc39.es/ecma262/#sec-array.prototype.sort
+pub fn sort(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let compare_fn = arg(&args, 0);
+    if !compare_fn.is_undefined() && !compare_fn.is_function() {
+        return Err(interpreter.type_error("The comparison function must be either a function or undefined"));
+    }
+    let length = length_of(&this, interpreter)?;
+    let mut values = Vec::new();
+    let mut index = 0.0;
+    while index < length {
+        if has_index(&this, index, interpreter)? {
+            values.push(get_index(&this, index, interpreter)?);
+        }
+        index += 1.0;
+    }
+    let sorted = merge_sort(values, &compare_fn, interpreter)?;
+    let count = sorted.len() as f64;
+    for (index, value) in sorted.into_iter().enumerate() {
+        set_index(&this, index as f64, value, interpreter)?;
+    }
+    let mut index = count;
+    while index < length {
+        delete_index(&this, index, interpreter)?;
+        index += 1.0;
+    }
+    Ok(this)
+}
+
+/// Find the first index of a value, using strict equality
+/// https://tc39.es/ecma262/#sec-array.prototype.indexof
+pub fn index_of(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let search = arg(&args, 0);
+    let mut index = relative_index(&arg(&args, 1), length as usize, 0, interpreter)? as f64;
+    while index < length {
+        if has_index(&this, index, interpreter)? && strict_equals(&get_index(&this, index, interpreter)?, &search) {
+            return Ok(length_value(index));
+        }
+        index += 1.0;
+    }
+    Ok(to_value(-1))
+}
+
+/// Returns true if the array contains the value, where `NaN` is found by `NaN`
+/// https://tc39.es/ecma262/#sec-array.prototype.includes
+pub fn includes(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let search = arg(&args, 0);
+    let mut index = relative_index(&arg(&args, 1), length as usize, 0, interpreter)? as f64;
+    while index < length {
+        if same_value_zero(&get_index(&this, index, interpreter)?, &search) {
+            return Ok(to_value(true));
+        }
+        index += 1.0;
+    }
+    Ok(to_value(false))
+}
+
+/// Find the first value and its index where the predicate returns a truthy value
+fn find_entry(this: &Value, args: &[Value], interpreter: &mut Interpreter) -> Result<Option<(f64, Value)>, Value> {
+    let length = length_of(this, interpreter)?;
+    let predicate = callback_arg(args, interpreter)?;
+    let this_arg = arg(args, 1);
+    let mut index = 0.0;
+    while index < length {
+        let value = get_index(this, index, interpreter)?;
+        if call_with_element(&predicate, &this_arg, value.clone(), index, this, interpreter)?.is_true() {
+            return Ok(Some((index, value)));
+        }
+        index += 1.0;
+    }
+    Ok(None)
+}
+
+/// Find the first value where the predicate returns a truthy value
+/// https://tc39.es/ecma262/#sec-array.prototype.find
+pub fn find(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(match find_entry(&this, &args, interpreter)? {
+        Some((_, value)) => value,
+        None => Gc::new(ValueData::Undefined),
+    })
+}
+
+/// Find the index of the first value where the predicate returns a truthy value
+/// https://tc39.es/ecma262/#sec-array.prototype.findindex
+pub fn find_index(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(match find_entry(&this, &args, interpreter)? {
+        Some((index, _)) => length_value(index),
+        None => to_value(-1),
+    })
+}
+
+/// Call the callback on every element which isn't a hole, stopping early if it returns `Some`
+fn for_each_element<F>(this: &Value, args: &[Value], interpreter: &mut Interpreter, mut f: F) -> Result<(), Value>
+where
+    F: FnMut(f64, Value, Value, &mut Interpreter) -> Result<bool, Value>,
+{
+    let length = length_of(this, interpreter)?;
+    let callback = callback_arg(args, interpreter)?;
+    let this_arg = arg(args, 1);
+    let mut index = 0.0;
+    while index < length {
+        if has_index(this, index, interpreter)? {
+            let value = get_index(this, index, interpreter)?;
+            let result = call_with_element(&callback, &this_arg, value.clone(), index, this, interpreter)?;
+            if !f(index, value, result, interpreter)? {
+                break;
+            }
+        }
+        index += 1.0;
+    }
+    Ok(())
+}
+
+/// Call the callback on every element
+/// https://tc39.es/ecma262/#sec-array.prototype.foreach
+pub fn for_each(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    for_each_element(&this, &args, interpreter, |_, _, _, _| Ok(true))?;
     Ok(Gc::new(ValueData::Undefined))
 }
+
+/// Make a new array from the results of calling the callback on every element
+/// https://tc39.es/ecma262/#sec-array.prototype.map
+pub fn map(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let result = new_array(&interpreter.get_global_object(), Vec::new());
+    set_length(&result, length, interpreter)?;
+    for_each_element(&this, &args, interpreter, |index, _, mapped, interpreter| {
+        set_index(&result, index, mapped, interpreter)?;
+        Ok(true)
+    })?;
+    Ok(result)
+}
+
+/// Make a new array of the elements where the callback returns a truthy value
+/// https://tc39.es/ecma262/#sec-array.prototype.filter
+pub fn filter(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut values = Vec::new();
+    for_each_element(&this, &args, interpreter, |_, value, selected, _| {
+        if selected.is_true() {
+            values.push(value);
+        }
+        Ok(true)
+    })?;
+    Ok(new_array(&interpreter.get_global_object(), values))
+}
+
+/// Returns true if the callback returns a truthy value for any element
+/// https://tc39.es/ecma262/#sec-array.prototype.some
+pub fn some(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut found = false;
+    for_each_element(&this, &args, interpreter, |_, _, result, _| {
+        found = result.is_true();
+        Ok(!found)
+    })?;
+    Ok(to_value(found))
+}
+
+/// Returns true if the callback returns a truthy value for every element
+/// https://tc39.es/ecma262/#sec-array.prototype.every
+pub fn every(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut all = true;
+    for_each_element(&this, &args, interpreter, |_, _, result, _| {
+        all = result.is_true();
+        Ok(all)
+    })?;
+    Ok(to_value(all))
+}
+
+/// Shared implementation of `reduce` and `reduceRight`
+fn reduce_elements(this: &Value, args: &[Value], from_right: bool, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(this, interpreter)?;
+    let callback = callback_arg(args, interpreter)?;
+    let indices: Vec<f64> = if from_right {
+        (0..length as u64).rev().map(|index| index as f64).collect()
+    } else {
+        (0..length as u64).map(|index| index as f64).collect()
+    };
+    let mut indices = indices.into_iter();
+    let mut accumulator = if args.len() >= 2 {
+        args[1].clone()
+    } else {
+        loop {
+            match indices.next() {
+                Some(index) if has_index(this, index, interpreter)? => break get_index(this, index, interpreter)?,
+                Some(_) => (),
+                None => return Err(interpreter.type_error("Reduce of empty array with no initial value")),
+            }
+        }
+    };
+    let undefined = Gc::new(ValueData::Undefined);
+    for index in indices {
+        if !has_index(this, index, interpreter)? {
+            continue;
+        }
+        let value = get_index(this, index, interpreter)?;
+        accumulator = interpreter.call(
+            &callback,
+            &undefined,
+            vec![accumulator, value, length_value(index), this.clone()],
+        )?;
+    }
+    Ok(accumulator)
+}
+
+/// Combine the elements from left to right with the callback
+/// https://tc39.es/ecma262/#sec-array.prototype.reduce
+pub fn reduce(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    reduce_elements(&this, &args, false, interpreter)
+}
+
+/// Combine the elements from right to left with the callback
+/// https://tc39.es/ecma262/#sec-array.prototype.reduceright
+pub fn reduce_right(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    reduce_elements(&this, &args, true, interpreter)
+}
+
+/// Append the elements of `source` to `target`, flattening arrays up to the given depth
+/// https://tc39.es/ecma262/#sec-flattenintoarray
+fn flatten_into_array(
+    target: &Value,
+    source: &Value,
+    mut target_index: f64,
+    depth: f64,
+    mapper: Option<(&Value, &Value)>,
+    interpreter: &mut Interpreter,
+) -> Result<f64, Value> {
+    let length = length_of(source, interpreter)?;
+    let mut index = 0.0;
+    while index < length {
+        if has_index(source, index, interpreter)? {
+            let mut element = get_index(source, index, interpreter)?;
+            if let Some((mapper, this_arg)) = mapper {
+                element = call_with_element(mapper, this_arg, element, index, source, interpreter)?;
+            }
+            if depth > 0.0 && element.is_array() {
+                target_index = flatten_into_array(target, &element, target_index, depth - 1.0, None, interpreter)?;
+            } else {
+                set_index(target, target_index, element, interpreter)?;
+                target_index += 1.0;
+            }
+        }
+        index += 1.0;
+    }
+    Ok(target_index)
+}
+
+/// Make a new array with nested arrays flattened, to a depth of 1 by default
+/// https://tc39.es/ecma262/#sec-array.prototype.flat
+pub fn flat(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let depth = match args.first() {
+        Some(depth) if !depth.is_undefined() => to_integer_or_infinity(depth, interpreter)?,
+        _ => 1.0,
+    };
+    let result = new_array(&interpreter.get_global_object(), Vec::new());
+    flatten_into_array(&result, &this, 0.0, depth, None, interpreter)?;
+    Ok(result)
+}
+
+/// Map each element with the callback, then flatten the results by one level
+/// https://tc39.es/ecma262/#sec-array.prototype.flatmap
+pub fn flat_map(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mapper = callback_arg(&args, interpreter)?;
+    let this_arg = arg(&args, 1);
+    let result = new_array(&interpreter.get_global_object(), Vec::new());
+    flatten_into_array(&result, &this, 0.0, 1.0, Some((&mapper, &this_arg)), interpreter)?;
+    Ok(result)
+}
+
+/// Set every element from `start` to `end` to the value
+/// https://tc39.es/ecma262/#sec-array.prototype.fill
+pub fn fill(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let value = arg(&args, 0);
+    let mut index = relative_index(&arg(&args, 1), length as usize, 0, interpreter)? as f64;
+    let end = relative_index(&arg(&args, 2), length as usize, length as usize, interpreter)? as f64;
+    while index < end {
+        set_index(&this, index, value.clone(), interpreter)?;
+        index += 1.0;
+    }
+    Ok(this)
+}
+
+/// Get the element at an index, where negative indices count back from the end
+/// https://tc39.es/ecma262/#sec-array.prototype.at
+pub fn at(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let length = length_of(&this, interpreter)?;
+    let relative = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    let index = if relative < 0.0 { length + relative } else { relative };
+    if index < 0.0 || index >= length {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    get_index(&this, index, interpreter)
+}
+
+/// Make an array iterator method, which carries the kind of iterator it creates
+fn iterator_method(kind: ArrayIterationKind, iterator_prototype: &Value) -> Value {
+    let method = to_value(iterator::create_array_iterator as NativeFunctionData);
+    method.set_private_field_slice(iterator::ARRAY_ITERATOR_PROTOTYPE, iterator_prototype.clone());
+    method.set_private_field_slice(iterator::ITERATION_KIND, to_value(kind.name()));
+    method
+}
+
 /// Create a new `Array` object
-pub fn _create() -> Value {
+pub fn _create(global: &Value) -> Value {
     let array = to_value(make_array as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("push", to_value(push as NativeFunctionData));
+    prototype.set_field_slice("pop", to_value(pop as NativeFunctionData));
+    prototype.set_field_slice("shift", to_value(shift as NativeFunctionData));
+    prototype.set_field_slice("unshift", to_value(unshift as NativeFunctionData));
+    prototype.set_field_slice("slice", to_value(slice as NativeFunctionData));
+    prototype.set_field_slice("splice", to_value(splice as NativeFunctionData));
+    prototype.set_field_slice("concat", to_value(concat as NativeFunctionData));
+    prototype.set_field_slice("join", to_value(join as NativeFunctionData));
+    prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    prototype.set_field_slice("reverse", to_value(reverse as NativeFunctionData));
+    prototype.set_field_slice("sort", to_value(sort as NativeFunctionData));
+    prototype.set_field_slice("indexOf", to_value(index_of as NativeFunctionData));
+    prototype.set_field_slice("includes", to_value(includes as NativeFunctionData));
+    prototype.set_field_slice("find", to_value(find as NativeFunctionData));
+    prototype.set_field_slice("findIndex", to_value(find_index as NativeFunctionData));
+    prototype.set_field_slice("map", to_value(map as NativeFunctionData));
+    prototype.set_field_slice("filter", to_value(filter as NativeFunctionData));
+    prototype.set_field_slice("reduce", to_value(reduce as NativeFunctionData));
+    prototype.set_field_slice("reduceRight", to_value(reduce_right as NativeFunctionData));
+    prototype.set_field_slice("forEach", to_value(for_each as NativeFunctionData));
+    prototype.set_field_slice("some", to_value(some as NativeFunctionData));
+    prototype.set_field_slice("every", to_value(every as NativeFunctionData));
+    prototype.set_field_slice("flat", to_value(flat as NativeFunctionData));
+    prototype.set_field_slice("flatMap", to_value(flat_map as NativeFunctionData));
+    prototype.set_field_slice("fill", to_value(fill as NativeFunctionData));
+    prototype.set_field_slice("at", to_value(at as NativeFunctionData));
+    array.set_field_slice("isArray", to_value(is_array as NativeFunctionData));
+    array.set_field_slice("of", to_value(of as NativeFunctionData));
+    array.set_field_slice("from", to_value(from as NativeFunctionData));
+    array.set_field_slice("fromAsync", to_value(from_async as NativeFunctionData));
+    let iterator_prototype = iterator::create_array_iterator_prototype(global);
+    let values = iterator_method(ArrayIterationKind::Values, &iterator_prototype);
+    prototype.set_field_slice("keys", iterator_method(ArrayIterationKind::Keys, &iterator_prototype));
+    prototype.set_field_slice("entries", iterator_method(ArrayIterationKind::Entries, &iterator_prototype));
+    prototype.set_field_slice("values", values.clone());
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::Iterator), values);
+    // The methods added since ES5, which `with (array)` statements in older scripts don't see
+    let unscopables = ValueData::new_obj(None);
+    for name in UNSCOPABLES.iter() {
+        unscopables.set_field_slice(name, to_value(true));
+    }
+    let mut unscopables = Property::new(unscopables);
+    unscopables.configurable = true;
+    prototype.set_prop(Symbol::well_known(WellKnownSymbol::Unscopables), unscopables);
+    array.set_field_slice("length", to_value(1i32));
+    array.set_field_slice(PROTOTYPE, prototype);
     array
 }
+
 /// Initialise the global object with the `Array` object
 pub fn init(global: &Value) {
-    global.set_field_slice("Array", _create());
-}
\ No newline at end of file
+    global.set_field_slice("Array", _create(global));
+}
@@ -0,0 +1,257 @@
+/// The global `ArrayBuffer` object, and the blocks of bytes which buffers share with their views and the embedder
+/// https://tc39.es/ecma262/#sec-arraybuffer-objects
+use gc::{Gc, GcCell, GcCellRef, GcCellRefMut};
+use crate::exec::Interpreter;
+use crate::js::conversion::{relative_index, to_index};
+use crate::js::function::NativeFunctionData;
+use crate::js::object::{Property, PROTOTYPE};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+
+/// The private field holding the bytes of an array buffer, the [[ArrayBufferData]] internal slot
+pub static ARRAY_BUFFER_DATA: &str = "ArrayBufferData";
+
+/// The private field holding the largest length a resizable array buffer can grow to, the
+/// [[ArrayBufferMaxByteLength]] internal slot. Buffers with a fixed length don't have it.
+pub static ARRAY_BUFFER_MAX_BYTE_LENGTH: &str = "ArrayBufferMaxByteLength";
+
+/// The private field holding the array buffer a typed array or `DataView` looks at, the [[ViewedArrayBuffer]]
+/// internal slot
+pub static VIEWED_ARRAY_BUFFER: &str = "ViewedArrayBuffer";
+
+/// The private field holding where the view of a typed array or `DataView` starts in its buffer, the
+/// [[ByteOffset]] internal slot
+pub static BYTE_OFFSET: &str = "ByteOffset";
+
+/// The largest number of bytes a buffer is allowed to hold
+const MAX_BYTE_LENGTH: usize = i32::MAX as usize;
+
+/// A block of bytes which can be shared between scripts and the embedder without copying.
+/// Clones refer to the same bytes, so the embedder can keep a clone of the bytes it gives a script to see what
+/// the script writes into them, or the other way round.
+#[derive(Trace, Finalize, Clone, Debug)]
+pub struct ByteBuffer(Gc<GcCell<Vec<u8>>>);
+
+impl ByteBuffer {
+    /// Make a block of bytes from a vector, which is moved rather than copied
+    pub fn new(bytes: Vec<u8>) -> ByteBuffer {
+        ByteBuffer(Gc::new(GcCell::new(bytes)))
+    }
+
+    /// Borrow the bytes to read them
+    pub fn borrow(&self) -> GcCellRef<'_, Vec<u8>> {
+        self.0.borrow()
+    }
+
+    /// Borrow the bytes to change them
+    pub fn borrow_mut(&self) -> GcCellRefMut<'_, Vec<u8>> {
+        self.0.borrow_mut()
+    }
+
+    /// Get the number of bytes
+    pub fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    /// Returns true if there are no bytes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if both refer to the same bytes
+    pub fn ptr_eq(&self, other: &ByteBuffer) -> bool {
+        Gc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl From<Vec<u8>> for ByteBuffer {
+    fn from(bytes: Vec<u8>) -> ByteBuffer {
+        ByteBuffer::new(bytes)
+    }
+}
+
+/// Get the bytes of an array buffer, which are shared rather than copied, or `None` if the value isn't one
+pub fn array_buffer_data(value: &Value) -> Option<ByteBuffer> {
+    match *value.get_private_field_slice(ARRAY_BUFFER_DATA) {
+        ValueData::Buffer(ref data) => Some(data.clone()),
+        _ => None,
+    }
+}
+
+/// Returns true if the value is an array buffer
+pub fn is_array_buffer(value: &Value) -> bool {
+    value.is_object() && array_buffer_data(value).is_some()
+}
+
+/// Returns true if the value is a typed array or a `DataView`
+pub fn is_view(value: &Value) -> bool {
+    value.is_object() && value.has_private_field(VIEWED_ARRAY_BUFFER)
+}
+
+/// Get the largest length an array buffer can grow to, or `None` if its length is fixed
+pub fn max_byte_length(buffer: &Value) -> Option<usize> {
+    let max = buffer.get_private_field_slice(ARRAY_BUFFER_MAX_BYTE_LENGTH);
+    if max.is_undefined() {
+        None
+    } else {
+        Some(max.to_num() as usize)
+    }
+}
+
+/// Make an `ArrayBuffer` holding the given bytes, which are shared rather than copied, so an embedder can keep a
+/// clone of `data` to pass bytes to and from scripts
+pub fn create_array_buffer(global: &Value, data: ByteBuffer) -> Value {
+    let proto = global.get_field_slice("ArrayBuffer").get_field_slice(PROTOTYPE);
+    let buffer = ValueData::new_obj_from_prototype(proto);
+    buffer.set_private_field_slice(ARRAY_BUFFER_DATA, Gc::new(ValueData::Buffer(data)));
+    buffer
+}
+
+/// Make a new `ArrayBuffer` of zeroed bytes, or throw if it is too large to allocate
+/// https://tc39.es/ecma262/#sec-allocatearraybuffer
+pub fn allocate_array_buffer(byte_length: usize, interpreter: &Interpreter) -> ResultValue {
+    if byte_length > MAX_BYTE_LENGTH {
+        return Err(interpreter.range_error("Array buffer allocation failed"));
+    }
+    Ok(create_array_buffer(&interpreter.get_global_object(), ByteBuffer::new(vec![0; byte_length])))
+}
+
+/// Get the bytes of the array buffer `this`, or throw if it isn't one
+fn this_array_buffer_data(this: &Value, method: &str, interpreter: &Interpreter) -> Result<ByteBuffer, Value> {
+    match array_buffer_data(this) {
+        Some(data) if this.is_object() => Ok(data),
+        _ => Err(interpreter.type_error(format!(
+            "ArrayBuffer.prototype.{} requires that 'this' be an ArrayBuffer",
+            method
+        ))),
+    }
+}
+
+/// Create a new array buffer of `length` zeroed bytes, which is resizable up to `maxByteLength` if the options
+/// have it
+/// https://tc39.es/ecma262/#sec-arraybuffer-length
+pub fn make_array_buffer(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error("Constructor ArrayBuffer requires 'new'"));
+    }
+    let byte_length = to_index(&arg(&args, 0), "Invalid array buffer length", interpreter)?;
+    let options = arg(&args, 1);
+    let max = if options.is_object() {
+        let max = interpreter.get_field(&options, "maxByteLength")?;
+        if max.is_undefined() {
+            None
+        } else {
+            Some(to_index(&max, "Invalid array buffer max length", interpreter)?)
+        }
+    } else {
+        None
+    };
+    if let Some(max) = max {
+        if byte_length > max {
+            return Err(interpreter.range_error("Invalid array buffer max length"));
+        }
+        if max > MAX_BYTE_LENGTH {
+            return Err(interpreter.range_error("Array buffer allocation failed"));
+        }
+        this.set_private_field_slice(ARRAY_BUFFER_MAX_BYTE_LENGTH, to_value(max as f64));
+    }
+    if byte_length > MAX_BYTE_LENGTH {
+        return Err(interpreter.range_error("Array buffer allocation failed"));
+    }
+    let data = ByteBuffer::new(vec![0; byte_length]);
+    this.set_private_field_slice(ARRAY_BUFFER_DATA, Gc::new(ValueData::Buffer(data)));
+    Ok(this)
+}
+
+/// `ArrayBuffer.isView(arg)`, which is true for typed arrays and `DataView`s
+/// https://tc39.es/ecma262/#sec-arraybuffer.isview
+pub fn is_view_method(_: Value, _: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(is_view(&arg(&args, 0))))
+}
+
+/// `get ArrayBuffer.prototype.byteLength`
+/// https://tc39.es/ecma262/#sec-get-arraybuffer.prototype.bytelength
+pub fn get_byte_length(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let data = this_array_buffer_data(&this, "byteLength", interpreter)?;
+    Ok(to_value(data.len() as f64))
+}
+
+/// `get ArrayBuffer.prototype.maxByteLength`, which is the byte length of a buffer which isn't resizable
+/// https://tc39.es/ecma262/#sec-get-arraybuffer.prototype.maxbytelength
+pub fn get_max_byte_length(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let data = this_array_buffer_data(&this, "maxByteLength", interpreter)?;
+    Ok(to_value(max_byte_length(&this).unwrap_or_else(|| data.len()) as f64))
+}
+
+/// `get ArrayBuffer.prototype.resizable`
+/// https://tc39.es/ecma262/#sec-get-arraybuffer.prototype.resizable
+pub fn get_resizable(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    this_array_buffer_data(&this, "resizable", interpreter)?;
+    Ok(to_value(max_byte_length(&this).is_some()))
+}
+
+/// `ArrayBuffer.prototype.resize(newLength)`, which changes the length of a resizable buffer in place, zeroing
+/// any new bytes
+/// https://tc39.es/ecma262/#sec-arraybuffer.prototype.resize
+pub fn resize(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let data = this_array_buffer_data(&this, "resize", interpreter)?;
+    let max = match max_byte_length(&this) {
+        Some(max) => max,
+        None => {
+            return Err(interpreter.type_error(
+                "ArrayBuffer.prototype.resize requires that 'this' be a resizable ArrayBuffer",
+            ))
+        }
+    };
+    let message = "ArrayBuffer.prototype.resize: Invalid length parameter";
+    let new_length = to_index(&arg(&args, 0), message, interpreter)?;
+    if new_length > max {
+        return Err(interpreter.range_error(message));
+    }
+    data.borrow_mut().resize(new_length, 0);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// `ArrayBuffer.prototype.slice(start, end)`, which copies the bytes from `start` up to `end` into a new buffer
+/// https://tc39.es/ecma262/#sec-arraybuffer.prototype.slice
+pub fn slice(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let data = this_array_buffer_data(&this, "slice", interpreter)?;
+    let length = data.len();
+    let first = relative_index(&arg(&args, 0), length, 0, interpreter)?;
+    let last = relative_index(&arg(&args, 1), length, length, interpreter)?;
+    // Converting the arguments may have shrunk the buffer
+    let bytes = data.borrow();
+    let last = last.min(bytes.len());
+    let copied = if first < last { bytes[first..last].to_vec() } else { Vec::new() };
+    Ok(create_array_buffer(&interpreter.get_global_object(), ByteBuffer::new(copied)))
+}
+
+/// Make an accessor property with a getter
+pub fn getter_property(getter: NativeFunctionData) -> Property {
+    let mut prop = Property::new(Gc::new(ValueData::Undefined));
+    prop.configurable = true;
+    prop.get = to_value(getter);
+    prop
+}
+
+/// Create a new `ArrayBuffer` object
+pub fn _create(global: &Value) -> Value {
+    let array_buffer = to_value(make_array_buffer as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_prop_slice("byteLength", getter_property(get_byte_length));
+    prototype.set_prop_slice("maxByteLength", getter_property(get_max_byte_length));
+    prototype.set_prop_slice("resizable", getter_property(get_resizable));
+    prototype.set_field_slice("resize", to_value(resize as NativeFunctionData));
+    prototype.set_field_slice("slice", to_value(slice as NativeFunctionData));
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("ArrayBuffer"));
+    array_buffer.set_field_slice("isView", to_value(is_view_method as NativeFunctionData));
+    array_buffer.set_field_slice("length", to_value(1i32));
+    prototype.set_field_slice("constructor", array_buffer.clone());
+    array_buffer.set_field_slice(PROTOTYPE, prototype);
+    array_buffer
+}
+
+/// Initialise the global object with the `ArrayBuffer` object
+pub fn init(global: &Value) {
+    global.set_field_slice("ArrayBuffer", _create(global));
+}
@@ -0,0 +1,366 @@
+/// The BigInt Object
+/// https://tc39.es/ecma262/#sec-bigint-objects
+use gc::{Finalize, Gc, Trace};
+use num_bigint::BigInt as RawBigInt;
+use num_traits::{Num, One, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use crate::exec::Interpreter;
+use crate::js::conversion::to_index;
+use crate::js::function::NativeFunctionData;
+use crate::js::object::{INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+use crate::syntax::ast::op::{BitOp, NumOp};
+
+/// An arbitrary-precision integer value
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BigInt(RawBigInt);
+
+impl Finalize for BigInt {}
+
+unsafe impl Trace for BigInt {
+    gc::unsafe_empty_trace!();
+}
+
+impl BigInt {
+    /// Make a BigInt from a number, if it is an integer
+    /// https://tc39.es/ecma262/#sec-numbertobigint
+    pub fn from_f64(num: f64) -> Option<BigInt> {
+        if num.is_finite() && num.trunc() == num {
+            // Integers beyond 2^53 are exact in an f64, so formatting without a fraction keeps every digit
+            format!("{:.0}", num).parse::<RawBigInt>().ok().map(BigInt)
+        } else {
+            None
+        }
+    }
+
+    /// Parse the digits of a BigInt literal, without the `n` suffix
+    pub fn from_str_radix(digits: &str, radix: u32) -> Option<BigInt> {
+        RawBigInt::from_str_radix(digits, radix).ok().map(BigInt)
+    }
+
+    /// Parse a string as a BigInt, which may be surrounded by whitespace and use a `0x`, `0o` or `0b` prefix.
+    /// Returns `None` if the string isn't an integer.
+    /// https://tc39.es/ecma262/#sec-stringtobigint
+    pub fn parse(string: &str) -> Option<BigInt> {
+        let string = string.trim();
+        if string.is_empty() {
+            return Some(BigInt(RawBigInt::zero()));
+        }
+        let (radix, digits) = match string.get(..2) {
+            Some("0x") | Some("0X") => (16, &string[2..]),
+            Some("0o") | Some("0O") => (8, &string[2..]),
+            Some("0b") | Some("0B") => (2, &string[2..]),
+            _ => (10, string),
+        };
+        // Signs are only allowed on decimal strings
+        let signed = digits.starts_with('+') || digits.starts_with('-');
+        if digits.is_empty() || (signed && radix != 10) || (signed && digits.len() == 1) {
+            return None;
+        }
+        BigInt::from_str_radix(digits.trim_start_matches('+'), radix)
+    }
+
+    /// Convert to the nearest number
+    pub fn to_f64(&self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// Returns true if the value is `0n`
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Format the value in the given radix, which must be between 2 and 36
+    pub fn to_string_radix(&self, radix: u32) -> String {
+        self.0.to_str_radix(radix)
+    }
+
+    /// Wrap the value to a signed integer of `bits` bits
+    /// https://tc39.es/ecma262/#sec-bigint.asintn
+    pub fn as_int_n(&self, bits: u64) -> BigInt {
+        if bits == 0 {
+            return BigInt(RawBigInt::zero());
+        }
+        let unsigned = self.as_uint_n(bits).0;
+        let half = RawBigInt::one() << (bits - 1) as usize;
+        if unsigned >= half {
+            BigInt(unsigned - (half << 1))
+        } else {
+            BigInt(unsigned)
+        }
+    }
+
+    /// Wrap the value to an unsigned integer of `bits` bits
+    /// https://tc39.es/ecma262/#sec-bigint.asuintn
+    pub fn as_uint_n(&self, bits: u64) -> BigInt {
+        let modulus = RawBigInt::one() << bits as usize;
+        let remainder = &self.0 % &modulus;
+        if remainder.is_negative() {
+            BigInt(remainder + modulus)
+        } else {
+            BigInt(remainder)
+        }
+    }
+
+    /// Wrap the value to a signed 64-bit integer, which is how `BigInt64Array` stores it
+    pub fn to_i64(&self) -> i64 {
+        self.as_int_n(64).0.to_i64().unwrap()
+    }
+
+    /// Wrap the value to an unsigned 64-bit integer, which is how `BigUint64Array` stores it
+    pub fn to_u64(&self) -> u64 {
+        self.as_uint_n(64).0.to_u64().unwrap()
+    }
+
+    /// Compare with a number, returning `None` if the number is NaN
+    pub fn compare_f64(&self, num: f64) -> Option<Ordering> {
+        if num.is_nan() {
+            return None;
+        }
+        if num.is_infinite() {
+            return Some(if num > 0.0 { Ordering::Less } else { Ordering::Greater });
+        }
+        let whole = BigInt::from_f64(num.trunc()).unwrap();
+        match self.cmp(&whole) {
+            // The number may have a fraction which makes it larger or smaller than its integer part
+            Ordering::Equal if num > num.trunc() => Some(Ordering::Less),
+            Ordering::Equal if num < num.trunc() => Some(Ordering::Greater),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(num: i64) -> BigInt {
+        BigInt(RawBigInt::from(num))
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(num: u64) -> BigInt {
+        BigInt(RawBigInt::from(num))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The error thrown when a BigInt and another type are used together in an operator
+fn mixed_types_error(interpreter: &Interpreter) -> Value {
+    interpreter.type_error("Cannot mix BigInt and other types, use explicit conversions")
+}
+
+/// Get both operands as BigInts, or throw if either isn't one
+fn both_bigints(a: &Value, b: &Value, interpreter: &Interpreter) -> Result<(BigInt, BigInt), Value> {
+    match (&**a, &**b) {
+        (ValueData::BigInt(ref a), ValueData::BigInt(ref b)) => Ok((a.clone(), b.clone())),
+        _ => Err(mixed_types_error(interpreter)),
+    }
+}
+
+/// Get the shift amount of a shift operator, which is too large if it doesn't fit in memory
+fn shift_amount(shift: &BigInt, interpreter: &Interpreter) -> Result<usize, Value> {
+    shift
+        .0
+        .abs()
+        .to_usize()
+        .ok_or_else(|| interpreter.range_error("Maximum BigInt size exceeded"))
+}
+
+/// Apply a numeric operator to two operands, at least one of which is a BigInt
+/// https://tc39.es/ecma262/#sec-numeric-types-bigint
+pub fn num_op(op: &NumOp, a: &Value, b: &Value, interpreter: &Interpreter) -> ResultValue {
+    let (BigInt(a), BigInt(b)) = both_bigints(a, b, interpreter)?;
+    let result = match *op {
+        NumOp::Add => a + b,
+        NumOp::Sub => a - b,
+        NumOp::Mul => a * b,
+        NumOp::Div | NumOp::Mod if b.is_zero() => {
+            return Err(interpreter.range_error("Division by zero"));
+        }
+        // Division truncates towards zero, and the remainder takes the sign of the dividend
+        NumOp::Div => a / b,
+        NumOp::Mod => a % b,
+    };
+    Ok(Gc::new(ValueData::BigInt(BigInt(result))))
+}
+
+/// Apply a bitwise operator to two operands, at least one of which is a BigInt.
+/// BigInts act as if they are infinitely sign-extended two's complement integers.
+pub fn bit_op(op: &BitOp, a: &Value, b: &Value, interpreter: &Interpreter) -> ResultValue {
+    let (BigInt(a), b) = both_bigints(a, b, interpreter)?;
+    let result = match *op {
+        BitOp::And => a & b.0,
+        BitOp::Or => a | b.0,
+        BitOp::Xor => a ^ b.0,
+        BitOp::Shl if b.0.is_negative() => a >> shift_amount(&b, interpreter)?,
+        BitOp::Shl => a << shift_amount(&b, interpreter)?,
+        BitOp::Shr if b.0.is_negative() => a << shift_amount(&b, interpreter)?,
+        BitOp::Shr => a >> shift_amount(&b, interpreter)?,
+    };
+    Ok(Gc::new(ValueData::BigInt(BigInt(result))))
+}
+
+/// Negate a BigInt
+pub fn negate(value: &BigInt) -> Value {
+    Gc::new(ValueData::BigInt(BigInt(-value.0.clone())))
+}
+
+/// Compare two primitives for the relational operators, where at least one is a BigInt.
+/// Returns `None` if they can't be compared, such as a string which isn't an integer.
+/// https://tc39.es/ecma262/#sec-islessthan
+pub fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (&**a, &**b) {
+        (ValueData::BigInt(ref a), ValueData::BigInt(ref b)) => Some(a.cmp(b)),
+        (ValueData::BigInt(ref a), ValueData::String(ref b)) => Some(a.cmp(&BigInt::parse(&b.to_string())?)),
+        (ValueData::String(ref a), ValueData::BigInt(ref b)) => Some(BigInt::parse(&a.to_string())?.cmp(b)),
+        (ValueData::BigInt(ref a), _) => a.compare_f64(b.to_num()),
+        (_, ValueData::BigInt(ref b)) => b.compare_f64(a.to_num()).map(Ordering::reverse),
+        _ => None,
+    }
+}
+
+/// Convert a value to a BigInt, where numbers must be converted explicitly with `BigInt()`
+/// https://tc39.es/ecma262/#sec-tobigint
+pub fn to_bigint(value: &Value, interpreter: &mut Interpreter) -> Result<BigInt, Value> {
+    let prim = interpreter.to_primitive(value, "number")?;
+    match *prim {
+        ValueData::BigInt(ref bigint) => Ok(bigint.clone()),
+        ValueData::Boolean(b) => Ok(BigInt(if b { RawBigInt::one() } else { RawBigInt::zero() })),
+        ValueData::String(ref string) => BigInt::parse(&string.to_string()).ok_or_else(|| {
+            interpreter.syntax_error(format!("Cannot convert {} to a BigInt", string))
+        }),
+        _ => Err(interpreter.type_error(format!("Cannot convert {} to a BigInt", prim))),
+    }
+}
+
+/// Get the BigInt value from `this`, which must be a BigInt
+/// https://tc39.es/ecma262/#sec-thisbigintvalue
+fn this_bigint_value(this: &Value, interpreter: &Interpreter) -> Result<BigInt, Value> {
+    match **this {
+        ValueData::BigInt(ref bigint) => Ok(bigint.clone()),
+        _ => Err(interpreter.type_error("BigInt.prototype method called on a value which is not a BigInt")),
+    }
+}
+
+/// Convert a value to a BigInt, `BigInt` can only be called as a function
+/// https://tc39.es/ecma262/#sec-bigint-constructor-number-value
+pub fn call_bigint(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    // `new BigInt()` calls this with a new object made from BigInt.prototype
+    if this.is_object() {
+        let proto = this.get_field_slice(INSTANCE_PROTOTYPE);
+        if Gc::ptr_eq(&proto, &f.get_field_slice(PROTOTYPE)) {
+            return Err(interpreter.type_error("BigInt is not a constructor"));
+        }
+    }
+    let value = match args.first() {
+        Some(value) => value.clone(),
+        None => Gc::new(ValueData::Undefined),
+    };
+    let prim = interpreter.to_primitive(&value, "number")?;
+    let bigint = match *prim {
+        ValueData::Number(_) | ValueData::Integer(_) => {
+            BigInt::from_f64(prim.to_num()).ok_or_else(|| {
+                interpreter.range_error(format!(
+                    "The number {} cannot be converted to a BigInt because it is not an integer",
+                    prim
+                ))
+            })?
+        }
+        _ => to_bigint(&prim, interpreter)?,
+    };
+    Ok(Gc::new(ValueData::BigInt(bigint)))
+}
+
+/// Get the bit count and BigInt arguments of `BigInt.asIntN` and `BigInt.asUintN`
+fn as_n_args(args: &[Value], interpreter: &mut Interpreter) -> Result<(u64, BigInt), Value> {
+    let bits = to_index(&arg(args, 0), "Invalid value: not (convertible to) a safe integer", interpreter)?;
+    let bigint = match args.get(1) {
+        Some(bigint) => to_bigint(bigint, interpreter)?,
+        None => to_bigint(&Gc::new(ValueData::Undefined), interpreter)?,
+    };
+    Ok((bits as u64, bigint))
+}
+
+/// Wrap a BigInt to a signed integer with the given number of bits
+pub fn as_int_n(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (bits, bigint) = as_n_args(&args, interpreter)?;
+    Ok(Gc::new(ValueData::BigInt(bigint.as_int_n(bits))))
+}
+
+/// Wrap a BigInt to an unsigned integer with the given number of bits
+pub fn as_uint_n(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (bits, bigint) = as_n_args(&args, interpreter)?;
+    Ok(Gc::new(ValueData::BigInt(bigint.as_uint_n(bits))))
+}
+
+/// Format the BigInt in the radix given by the first argument, or base 10
+/// https://tc39.es/ecma262/#sec-bigint.prototype.tostring
+pub fn to_string(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let bigint = this_bigint_value(&this, interpreter)?;
+    let radix = match args.first() {
+        Some(radix) if !radix.is_undefined() => interpreter.to_number(radix)?,
+        _ => 10.0,
+    };
+    if !(2.0..=36.0).contains(&radix) {
+        return Err(interpreter.range_error("toString() radix must be between 2 and 36"));
+    }
+    Ok(to_value(bigint.to_string_radix(radix as u32)))
+}
+
+/// Get the BigInt itself
+pub fn value_of(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(Gc::new(ValueData::BigInt(this_bigint_value(&this, interpreter)?)))
+}
+
+/// Create a new `BigInt` object
+pub fn _create(global: &Value) -> Value {
+    let bigint = to_value(call_bigint as NativeFunctionData);
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    proto.set_field_slice("toLocaleString", to_value(to_string as NativeFunctionData));
+    proto.set_field_slice("valueOf", to_value(value_of as NativeFunctionData));
+    proto.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("BigInt"));
+    proto.set_field_slice("constructor", bigint.clone());
+    bigint.set_field_slice(PROTOTYPE, proto);
+    bigint.set_field_slice("length", to_value(1i32));
+    bigint.set_field_slice("asIntN", to_value(as_int_n as NativeFunctionData));
+    bigint.set_field_slice("asUintN", to_value(as_uint_n as NativeFunctionData));
+    bigint
+}
+
+/// Initialise the global object with the `BigInt` object
+pub fn init(global: &Value) {
+    global.set_field_slice("BigInt", _create(global));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_parse() {
+        assert_eq!(BigInt::parse(" 42 ").unwrap().to_string(), "42");
+        assert_eq!(BigInt::parse("-42").unwrap().to_string(), "-42");
+        assert_eq!(BigInt::parse("0xff").unwrap().to_string(), "255");
+        assert_eq!(BigInt::parse("").unwrap().to_string(), "0");
+        assert!(BigInt::parse("1.5").is_none());
+        assert!(BigInt::parse("-0x1").is_none());
+        assert!(BigInt::parse("-").is_none());
+    }
+
+    #[test]
+    fn check_as_n() {
+        let max = BigInt::parse("255").unwrap();
+        assert_eq!(max.as_int_n(8).to_string(), "-1");
+        assert_eq!(max.as_uint_n(8).to_string(), "255");
+        let negative = BigInt::parse("-1").unwrap();
+        assert_eq!(negative.as_uint_n(64).to_string(), "18446744073709551615");
+        assert_eq!(negative.as_int_n(0).to_string(), "0");
+    }
+}
@@ -1,19 +1,67 @@
+/// The Boolean Object
+/// https://tc39.es/ecma262/#sec-boolean-objects
 use gc::Gc;
-use super::value::{Value, ValueData, ResultValue, to_value};
+use crate::exec::Interpreter;
+use crate::js::function::NativeFunctionData;
+use crate::js::object::{INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
 
+/// Get the boolean `this` refers to, which is a boolean or a `Boolean` object
+/// https://tc39.es/ecma262/#sec-thisbooleanvalue
+fn this_boolean_value(this: &Value, method: &str, interpreter: &Interpreter) -> Result<bool, Value> {
+    let primitive = if this.is_object() {
+        this.get_private_field_slice("PrimitiveValue")
+    } else {
+        this.clone()
+    };
+    match *primitive {
+        ValueData::Boolean(b) => Ok(b),
+        _ => Err(interpreter.type_error(format!(
+            "Boolean.prototype.{} requires that 'this' be a Boolean",
+            method
+        ))),
+    }
+}
+
+/// Convert a value to a boolean, or create a `Boolean` object with `new`
+/// https://tc39.es/ecma262/#sec-boolean-constructor-boolean-value
+pub fn make_boolean(this: Value, f: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    let value = args.first().is_some_and(|value| value.is_true());
+    // `new Boolean()` calls this with a new object made from Boolean.prototype, which gets wrapped around the boolean
+    if this.is_object()
+        && this.get_private_field_slice("PrimitiveValue").is_undefined()
+        && Gc::ptr_eq(&this.get_field_slice(INSTANCE_PROTOTYPE), &f.get_field_slice(PROTOTYPE))
+    {
+        this.set_private_field_slice("PrimitiveValue", to_value(value));
+        return Ok(this);
+    }
+    Ok(to_value(value))
+}
 
-/// 创建一个boolean
-pub fn make_boolean(_: Vec<Value>, _: Value, _: Value, this: Value) -> ResultValue {
-    Ok(Gc::new(ValueData::Undefined))
+/// Get `"true"` or `"false"`
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_boolean_value(&this, "toString", interpreter)?.to_string()))
 }
 
-/// 创建一个Boolean
-pub fn _create(global: Value) -> Value {
-    let boolean = to_value(make_boolean);
+/// Get the boolean itself
+pub fn value_of(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_boolean_value(&this, "valueOf", interpreter)?))
+}
+
+/// Create a new `Boolean` object
+pub fn _create(global: &Value) -> Value {
+    let boolean = to_value(make_boolean as NativeFunctionData);
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_private_field_slice("PrimitiveValue", to_value(false));
+    proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    proto.set_field_slice("valueOf", to_value(value_of as NativeFunctionData));
+    proto.set_field_slice("constructor", boolean.clone());
+    boolean.set_field_slice(PROTOTYPE, proto);
+    boolean.set_field_slice("length", to_value(1i32));
     boolean
 }
 
+/// Initialise the global object with the `Boolean` object
 pub fn init(global: &Value) {
-    let global_ptr = global.borrow();
-    global_ptr.set_field_slice("Boolean", _create(global));
-}
\ No newline at end of file
+    global.set_field_slice("Boolean", _create(global));
+}
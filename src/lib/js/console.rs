@@ -1,78 +1,971 @@
-use crate::js::function::NativeFunctionData;
-use crate::js::object::INSTANCE_PROTOTYPE;
-use crate::js::value::{from_value, to_value, ResultValue, Value, ValueData};
-use chrono::Local;
+/// The global `console` object, which formats values and writes them to a sink the embedder can replace
+/// https://console.spec.whatwg.org/
 use gc::Gc;
-use std::fmt::Write;
-use std::iter::FromIterator;
+use crate::exec::Interpreter;
+use crate::js::array_buffer;
+use crate::js::date::{self, DATE_VALUE};
+use crate::js::error;
+use crate::js::function::{Function, NativeFunctionData};
+use crate::js::map::{self, MAP_DATA};
+use crate::js::number;
+use crate::js::object::{PropertyKey, INSTANCE_PROTOTYPE};
+use crate::js::proxy::{self, PROXY_TARGET};
+use crate::js::regexp;
+use crate::js::set::SET_DATA;
+use crate::js::typed_array::TypedArray;
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+use crate::js::weak::{WEAK_MAP_DATA, WEAK_SET_DATA};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::Write;
 
-/// Print a javascript value to the standard output stream
-/// https://console.spec.whatwg.org/#logger
-pub fn log(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let args: Vec<String> = FromIterator::from_iter(args.iter().map(|x| {
-        // Welcome to console.log! The output here is what the developer sees, so its best matching through value types and stringifying to the correct output
-        // The input is a vector of Values, we generate a vector of strings then pass them to println!
-        return match *x.clone() {
-            // We don't want to print private (compiler) or prototype properties
-            ValueData::Object(ref v, _) => {
-                // Create empty formatted string to start writing to
-                // TODO: once constructor is set on objects, we can do specific output for Strings, Numbers etc
-                let mut s = String::new();
-                write!(s, "{}", "{").unwrap();
-                match v.borrow().iter().last() {
-                    Some((last_key, _)) => {
-                        for (key, val) in v.borrow().iter() {
-                            // Don't print prototype properties
-                            if key == INSTANCE_PROTOTYPE {
-                                continue;
-                            }
-                            write!(s, "{}: {}", key, val.value.clone()).unwrap();
-                            if key != last_key {
-                                write!(s, "{}", ", ").unwrap();
-                            }
-                        }
+/// How deep nested objects are shown before they are abbreviated to `[Object]`
+const DEFAULT_DEPTH: usize = 2;
+
+/// How many elements of an array are shown before the rest are summarised
+const MAX_ARRAY_LENGTH: usize = 100;
+
+/// How long a line can be before an object is shown over several lines
+const BREAK_LENGTH: usize = 80;
+
+/// The kind of message being written, which decides the stream it goes to
+/// https://console.spec.whatwg.org/#loglevel-severity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogLevel {
+    /// `console.log`, `console.dir`, `console.table` and group labels
+    Log,
+    /// `console.info`, counters and timers
+    Info,
+    /// `console.debug`
+    Debug,
+    /// `console.warn`, and warnings about labels which are missing or already used
+    Warn,
+    /// `console.error` and failed assertions
+    Error,
+    /// `console.trace`
+    Trace,
+}
+
+impl LogLevel {
+    /// Returns true if messages of this level go to the standard error stream
+    pub fn is_error(self) -> bool {
+        matches!(self, LogLevel::Warn | LogLevel::Error | LogLevel::Trace)
+    }
+}
+
+/// Where console messages are written.
+/// The interpreter writes to the standard streams unless the embedder gives it another sink, such as a
+/// `MemorySink` to read the output back.
+pub trait ConsoleSink {
+    /// Write a message, which is already formatted and indented for the current group and has no trailing newline
+    fn write(&mut self, level: LogLevel, message: &str);
+}
+
+/// Write messages to standard output, or standard error for warnings and errors
+pub struct StdioSink;
+
+impl ConsoleSink for StdioSink {
+    fn write(&mut self, level: LogLevel, message: &str) {
+        // There is nowhere to report a broken stream, so the message is dropped like Node does
+        if level.is_error() {
+            let _ = writeln!(std::io::stderr(), "{}", message);
+        } else {
+            let _ = writeln!(std::io::stdout(), "{}", message);
+        }
+    }
+}
+
+/// Keep messages in memory.
+/// Clones share their messages, so the embedder can keep one to read back what scripts wrote to the other.
+#[derive(Clone, Default)]
+pub struct MemorySink {
+    messages: std::rc::Rc<std::cell::RefCell<Vec<(LogLevel, String)>>>,
+}
+
+impl MemorySink {
+    /// Make a sink with no messages
+    pub fn new() -> MemorySink {
+        MemorySink::default()
+    }
+
+    /// The messages written so far, oldest first
+    pub fn messages(&self) -> Vec<(LogLevel, String)> {
+        self.messages.borrow().clone()
+    }
+
+    /// The messages written so far as lines of text, like they would show in a terminal
+    pub fn output(&self) -> String {
+        let messages = self.messages.borrow();
+        messages.iter().map(|(_, message)| message.as_str()).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Forget the messages written so far
+    pub fn clear(&self) {
+        self.messages.borrow_mut().clear();
+    }
+}
+
+impl ConsoleSink for MemorySink {
+    fn write(&mut self, level: LogLevel, message: &str) {
+        self.messages.borrow_mut().push((level, message.to_string()));
+    }
+}
+
+/// The state of the console, kept by the interpreter: where messages go, and the groups, counters and timers
+/// https://console.spec.whatwg.org/#console-namespace
+pub struct Console {
+    /// Where messages are written
+    sink: Box<dyn ConsoleSink>,
+    /// How many groups are open, each indenting messages by two more spaces
+    group_depth: usize,
+    /// The count map, from each label to the times it has been counted
+    counts: HashMap<String, u32>,
+    /// The timer table, from each label to the time it was started in milliseconds
+    timers: HashMap<String, f64>,
+}
+
+impl Console {
+    /// Make a console which writes to a sink
+    pub fn new(sink: Box<dyn ConsoleSink>) -> Console {
+        Console {
+            sink,
+            group_depth: 0,
+            counts: HashMap::new(),
+            timers: HashMap::new(),
+        }
+    }
+
+    /// Replace the sink messages are written to
+    pub fn set_sink(&mut self, sink: Box<dyn ConsoleSink>) {
+        self.sink = sink;
+    }
+
+    /// Write a message to the sink, indenting each of its lines for the open groups
+    /// https://console.spec.whatwg.org/#printer
+    pub fn print(&mut self, level: LogLevel, message: &str) {
+        if self.group_depth == 0 {
+            self.sink.write(level, message);
+        } else {
+            let indent = " ".repeat(self.group_depth * 2);
+            let message: Vec<String> = message.lines().map(|line| format!("{}{}", indent, line)).collect();
+            self.sink.write(level, &message.join("\n"));
+        }
+    }
+}
+
+/// Returns true if the key can be shown without quotes, like `a` in `{ a: 1 }`
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == '$' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        }
+        _ => false,
+    }
+}
+
+/// Quote a string with single quotes, like strings inside objects are shown
+fn quote(string: &str) -> String {
+    let mut quoted = String::from("'");
+    for c in string.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\x{:02X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Show a number, keeping the sign of negative zero
+fn format_number(num: f64) -> String {
+    if num == 0.0 && num.is_sign_negative() {
+        "-0".to_string()
+    } else {
+        number::number_to_string(num)
+    }
+}
+
+/// Show a primitive value, or `None` for objects and functions
+fn format_primitive(value: &ValueData, nested: bool) -> Option<String> {
+    Some(match *value {
+        ValueData::String(ref string) if nested => quote(&string.to_string()),
+        ValueData::String(ref string) => string.to_string(),
+        ValueData::Number(num) => format_number(num),
+        ValueData::BigInt(ref num) => format!("{}n", num),
+        ValueData::Object(_, _) | ValueData::Function(_) => return None,
+        _ => value.to_string(),
+    })
+}
+
+/// The name of an object's constructor, read without running any code, or `None` if it has a null prototype
+fn constructor_name(value: &Value) -> Option<String> {
+    let mut proto = value.get_field_slice(INSTANCE_PROTOTYPE);
+    if !proto.is_object() && !proto.is_function() {
+        return None;
+    }
+    while proto.is_object() || proto.is_function() {
+        if let Some(prop) = proto.get_own_prop("constructor") {
+            if let Some(name) = prop.value.get_own_prop("name") {
+                if let ValueData::String(ref name) = *name.value {
+                    if !name.is_empty() {
+                        return Some(name.to_string());
                     }
-                    None => (),
                 }
-                write!(s, "{}", "}").unwrap();
-                s
             }
+        }
+        proto = proto.get_field_slice(INSTANCE_PROTOTYPE);
+    }
+    Some("Object".to_string())
+}
+
+/// The prefix showing which kind of object an object is, like `Foo ` or `Map(2) `, with the brackets around its entries
+struct Shape {
+    prefix: String,
+    braces: (&'static str, &'static str),
+    /// What the object is shown as past the depth limit
+    abbreviation: String,
+}
 
-            _ => from_value::<String>(x.clone()).unwrap(),
+/// Shows values the way `util.inspect` in Node does, like `{ a: 1, b: [ 'x' ] }`.
+/// Properties are read without running getters or proxy traps.
+pub struct Inspector {
+    /// How deep nested objects are shown, or `None` to show them all
+    depth: Option<usize>,
+    /// The objects being shown, outermost first, to find cycles
+    stack: Vec<Value>,
+    /// The objects which turned out to contain themselves, numbered in the order the cycles were found
+    circular: Vec<Value>,
+}
+
+impl Inspector {
+    /// Make an inspector which shows nested objects up to a depth, or all of them if it is `None`
+    pub fn new(depth: Option<usize>) -> Inspector {
+        Inspector {
+            depth,
+            stack: Vec::new(),
+            circular: Vec::new(),
+        }
+    }
+
+    /// Show a value, where strings are quoted
+    pub fn inspect(&mut self, value: &Value) -> String {
+        self.format_value(value, 0)
+    }
+
+    /// Show a value nested `level` objects deep
+    fn format_value(&mut self, value: &Value, level: usize) -> String {
+        if let Some(string) = format_primitive(value, true) {
+            return string;
+        }
+        // Proxies are shown as their target
+        let mut value = value.clone();
+        while proxy::is_proxy(&value) {
+            value = value.get_private_field_slice(PROXY_TARGET);
+            if !value.is_object() && !value.is_function() {
+                return "<Revoked Proxy>".to_string();
+            }
+        }
+        if let Some(position) = self.stack.iter().position(|other| Gc::ptr_eq(other, &value)) {
+            let target = self.stack[position].clone();
+            let index = match self.circular.iter().position(|other| Gc::ptr_eq(other, &target)) {
+                Some(index) => index,
+                None => {
+                    self.circular.push(target);
+                    self.circular.len() - 1
+                }
+            };
+            return format!("[Circular *{}]", index + 1);
+        }
+        self.stack.push(value.clone());
+        let output = self.format_object(&value, level);
+        self.stack.pop();
+        match self.circular.iter().position(|other| Gc::ptr_eq(other, &value)) {
+            Some(index) => format!("<ref *{}> {}", index + 1, output),
+            None => output,
+        }
+    }
+
+    /// Show an object or function
+    fn format_object(&mut self, value: &Value, level: usize) -> String {
+        let mut entries = Vec::new();
+        let mut skip_indices = false;
+        // `lastIndex` isn't enumerable in other engines
+        let mut skip_keys = Vec::new();
+        let (base, shape) = if let ValueData::Function(ref func) = **value {
+            let name = match value.get_own_prop("name") {
+                Some(ref prop) if prop.value.is_string() && !prop.value.to_string().is_empty() => {
+                    format!(": {}", prop.value)
+                }
+                _ => " (anonymous)".to_string(),
+            };
+            let kind = match *func.borrow() {
+                Function::RegularFunc(ref rf) if rf.is_async => "AsyncFunction",
+                _ => "Function",
+            };
+            (Some(format!("[{}{}]", kind, name)), None)
+        } else if value.is_array() {
+            skip_indices = true;
+            let length = value.get_field_slice("length").to_num() as usize;
+            let shape = Shape {
+                prefix: String::new(),
+                braces: ("[", "]"),
+                abbreviation: "[Array]".to_string(),
+            };
+            if self.past_depth(level) {
+                return shape.abbreviation;
+            }
+            let mut holes = 0;
+            for index in 0..length.min(MAX_ARRAY_LENGTH) {
+                match value.get_own_prop(index.to_string()) {
+                    Some(prop) => {
+                        if holes > 0 {
+                            entries.push(empty_items(holes));
+                            holes = 0;
+                        }
+                        entries.push(self.format_property_value(&prop, level));
+                    }
+                    None => holes += 1,
+                }
+            }
+            if holes > 0 {
+                entries.push(empty_items(holes));
+            }
+            if length > MAX_ARRAY_LENGTH {
+                entries.push(more_items(length - MAX_ARRAY_LENGTH));
+            }
+            (None, Some(shape))
+        } else if let Some(typed_array) = TypedArray::from_value(value) {
+            skip_indices = true;
+            let values = typed_array.values();
+            let shape = Shape {
+                prefix: format!("{}({}) ", typed_array.kind.name(), values.len()),
+                braces: ("[", "]"),
+                abbreviation: format!("[{}]", typed_array.kind.name()),
+            };
+            if self.past_depth(level) {
+                return shape.abbreviation;
+            }
+            for element in values.iter().take(MAX_ARRAY_LENGTH) {
+                entries.push(self.format_value(element, level + 1));
+            }
+            if values.len() > MAX_ARRAY_LENGTH {
+                entries.push(more_items(values.len() - MAX_ARRAY_LENGTH));
+            }
+            (None, Some(shape))
+        } else if value.has_private_field(MAP_DATA) || value.has_private_field(SET_DATA) {
+            let is_map = value.has_private_field(MAP_DATA);
+            let data = value.get_private_field_slice(if is_map { MAP_DATA } else { SET_DATA });
+            let kind = if is_map { "Map" } else { "Set" };
+            let shape = Shape {
+                prefix: format!("{}({}) ", kind, map::entries_size(&data)),
+                braces: ("{", "}"),
+                abbreviation: format!("[{}]", kind),
+            };
+            if self.past_depth(level) {
+                return shape.abbreviation;
+            }
+            for position in 0..map::entries_length(&data) {
+                if let Some((key, entry)) = map::entry_at(&data, position) {
+                    let key = self.format_value(&key, level + 1);
+                    entries.push(if is_map {
+                        format!("{} => {}", key, self.format_value(&entry, level + 1))
+                    } else {
+                        key
+                    });
+                }
+            }
+            (None, Some(shape))
+        } else if value.has_private_field(WEAK_MAP_DATA) || value.has_private_field(WEAK_SET_DATA) {
+            let kind = if value.has_private_field(WEAK_MAP_DATA) { "WeakMap" } else { "WeakSet" };
+            return format!("{} {{ <items unknown> }}", kind);
+        } else if value.has_private_field("PromiseState") {
+            let shape = Shape {
+                prefix: "Promise ".to_string(),
+                braces: ("{", "}"),
+                abbreviation: "[Promise]".to_string(),
+            };
+            if self.past_depth(level) {
+                return shape.abbreviation;
+            }
+            let result = value.get_private_field_slice("PromiseResult");
+            entries.push(match value.get_private_field_slice("PromiseState").to_string().as_str() {
+                "pending" => "<pending>".to_string(),
+                "rejected" => format!("<rejected> {}", self.format_value(&result, level + 1)),
+                _ => self.format_value(&result, level + 1),
+            });
+            (None, Some(shape))
+        } else if date::is_date_object(value) {
+            let time = value.get_private_field_slice(DATE_VALUE).to_num();
+            let base = if time.is_nan() { "Invalid Date".to_string() } else { date::iso_string(time) };
+            (Some(base), None)
+        } else if regexp::is_regexp_object(value) {
+            skip_keys.push("lastIndex");
+            (Some(regexp::regexp_to_string(value)), None)
+        } else if error::is_error_object(value) {
+            let base = match *value.get_field_slice("stack") {
+                ValueData::String(ref stack) => stack.to_string(),
+                _ => error::error_message(value),
+            };
+            (Some(base), None)
+        } else if array_buffer::is_array_buffer(value) {
+            let (bytes, length): (Vec<String>, usize) = match array_buffer::array_buffer_data(value) {
+                Some(data) => (data.borrow().iter().take(50).map(|byte| format!("{:02x}", byte)).collect(), data.len()),
+                None => (Vec::new(), 0),
+            };
+            entries.push(format!("[Uint8Contents]: <{}>", bytes.join(" ")));
+            entries.push(format!("byteLength: {}", length));
+            let shape = Shape {
+                prefix: "ArrayBuffer ".to_string(),
+                braces: ("{", "}"),
+                abbreviation: "[ArrayBuffer]".to_string(),
+            };
+            (None, Some(shape))
+        } else {
+            let primitive = value.get_private_field_slice("PrimitiveValue");
+            match format_primitive(&primitive, true) {
+                Some(string) if !primitive.is_undefined() => {
+                    let kind = match *primitive {
+                        ValueData::String(_) => "String",
+                        ValueData::Boolean(_) => "Boolean",
+                        ValueData::BigInt(_) => "BigInt",
+                        ValueData::Symbol(_) => "Symbol",
+                        _ => "Number",
+                    };
+                    skip_indices = primitive.is_string();
+                    (Some(format!("[{}: {}]", kind, string)), None)
+                }
+                _ => {
+                    let name = constructor_name(value);
+                    let prefix = match name {
+                        Some(ref name) if name == "Object" => String::new(),
+                        Some(ref name) => format!("{} ", name),
+                        None => "[Object: null prototype] ".to_string(),
+                    };
+                    let shape = Shape {
+                        prefix,
+                        braces: ("{", "}"),
+                        abbreviation: format!("[{}]", name.unwrap_or_else(|| "Object: null prototype".to_string())),
+                    };
+                    (None, Some(shape))
+                }
+            }
         };
+        // Objects with a special form only need braces if they have properties
+        let shape = match shape {
+            Some(shape) => shape,
+            None => {
+                if self.past_depth(level) && has_enumerable_keys(value) {
+                    return format!("[{}]", base.unwrap_or_default().trim_matches(|c| c == '[' || c == ']'));
+                }
+                Shape {
+                    prefix: String::new(),
+                    braces: ("{", "}"),
+                    abbreviation: String::new(),
+                }
+            }
+        };
+        if base.is_none() && self.past_depth(level) {
+            return shape.abbreviation;
+        }
+        for key in value.own_keys() {
+            if skip_indices && (crate::js::array::array_index(&key).is_some() || key == "length") {
+                continue;
+            }
+            if skip_keys.iter().any(|skip| key == *skip) {
+                continue;
+            }
+            let prop = match value.get_own_prop(key.clone()) {
+                Some(prop) if prop.enumerable => prop,
+                _ => continue,
+            };
+            let key = match key {
+                PropertyKey::Symbol(ref symbol) => format!("[{}]", symbol),
+                PropertyKey::String(ref key) if is_identifier(key) => key.clone(),
+                PropertyKey::String(ref key) => quote(key),
+            };
+            entries.push(format!("{}: {}", key, self.format_property_value(&prop, level)));
+        }
+        match base {
+            Some(base) if entries.is_empty() => base,
+            Some(base) => reduce_to_single_string(&format!("{} ", base), ("{", "}"), &entries, level),
+            None if entries.is_empty() => format!("{}{}{}", shape.prefix, shape.braces.0, shape.braces.1),
+            None => reduce_to_single_string(&shape.prefix, shape.braces, &entries, level),
+        }
+    }
+
+    /// Show the value of a property, or which accessors it has
+    fn format_property_value(&mut self, prop: &crate::js::object::Property, level: usize) -> String {
+        match (prop.get.is_undefined(), prop.set.is_undefined()) {
+            (false, false) => "[Getter/Setter]".to_string(),
+            (false, true) => "[Getter]".to_string(),
+            (true, false) => "[Setter]".to_string(),
+            (true, true) => self.format_value(&prop.value, level + 1),
+        }
+    }
 
-        // from_value::<String>(x.clone()).unwrap()
-    }));
+    /// Returns true if objects at this level are too deep to show
+    fn past_depth(&self, level: usize) -> bool {
+        match self.depth {
+            Some(depth) => level > depth,
+            None => false,
+        }
+    }
+}
+
+/// Returns true if the object has any enumerable own properties
+fn has_enumerable_keys(value: &Value) -> bool {
+    value
+        .own_keys()
+        .into_iter()
+        .any(|key| value.get_own_prop(key).is_some_and(|prop| prop.enumerable))
+}
+
+/// Show a run of holes in an array
+fn empty_items(count: usize) -> String {
+    format!("<{} empty item{}>", count, if count == 1 { "" } else { "s" })
+}
+
+/// Show how many elements of a long array were left out
+fn more_items(count: usize) -> String {
+    format!("... {} more item{}", count, if count == 1 { "" } else { "s" })
+}
 
-    println!(
-        "{}: {}",
-        Local::now().format("%X").to_string(),
-        args.join(" ")
-    );
+/// Join the entries of an object on one line if they fit, or put each on its own line
+fn reduce_to_single_string(prefix: &str, braces: (&str, &str), entries: &[String], level: usize) -> String {
+    let total: usize = entries.iter().map(|entry| entry.chars().count()).sum::<usize>() + entries.len();
+    let start = prefix.chars().count() + braces.0.len() + level * 2 + 10;
+    if total + start <= BREAK_LENGTH && !entries.iter().any(|entry| entry.contains('\n')) {
+        return format!("{}{} {} {}", prefix, braces.0, entries.join(", "), braces.1);
+    }
+    let indent = "  ".repeat(level);
+    let separator = format!(",\n{}  ", indent);
+    format!("{}{}\n{}  {}\n{}{}", prefix, braces.0, indent, entries.join(&separator), indent, braces.1)
+}
+
+/// Show a value like `util.inspect`, nested objects up to a depth, or all of them if it is `None`
+pub fn inspect(value: &Value, depth: Option<usize>) -> String {
+    Inspector::new(depth).inspect(value)
+}
+
+/// Show a value as an argument of a console method, where strings aren't quoted
+fn format_arg(value: &Value) -> String {
+    match format_primitive(value, false) {
+        Some(string) => string,
+        None => inspect(value, Some(DEFAULT_DEPTH)),
+    }
+}
+
+/// Format the arguments of a console method, replacing the format specifiers in the first one if it is a string
+/// https://console.spec.whatwg.org/#formatter
+pub fn format_args(args: &[Value]) -> String {
+    let mut output = String::new();
+    let mut rest = 0;
+    if let Some(ValueData::String(ref format)) = args.first().map(|arg| &**arg) {
+        rest = 1;
+        let format = format.to_string();
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            let specifier = match chars.peek() {
+                Some(&next) if c == '%' => next,
+                _ => {
+                    output.push(c);
+                    continue;
+                }
+            };
+            if specifier == '%' {
+                chars.next();
+                output.push('%');
+                continue;
+            }
+            let arg = match args.get(rest) {
+                Some(arg) if "sdifoOc".contains(specifier) => arg,
+                _ => {
+                    output.push(c);
+                    continue;
+                }
+            };
+            chars.next();
+            rest += 1;
+            match specifier {
+                's' => output.push_str(&match format_primitive(arg, false) {
+                    Some(string) => string,
+                    None => inspect(arg, Some(0)),
+                }),
+                'd' | 'i' | 'f' => {
+                    if let ValueData::BigInt(ref num) = **arg {
+                        output.push_str(&format!("{}n", num));
+                    } else {
+                        let num = if arg.is_symbol() { f64::NAN } else { arg.to_num() };
+                        output.push_str(&format_number(if specifier == 'i' { num.trunc() } else { num }));
+                    }
+                }
+                'o' | 'O' => output.push_str(&inspect(arg, Some(DEFAULT_DEPTH))),
+                // Styles can't be shown in a terminal, so `%c` just uses up its argument
+                _ => (),
+            }
+        }
+    }
+    for arg in &args[rest.min(args.len())..] {
+        if !output.is_empty() || rest > 0 {
+            output.push(' ');
+        }
+        output.push_str(&format_arg(arg));
+    }
+    output
+}
+
+/// Get the label argument of a counter or timer method, which is `default` if it isn't given
+fn label(args: &[Value], interpreter: &mut Interpreter) -> Result<String, Value> {
+    match args.first() {
+        Some(label) if !label.is_undefined() => interpreter.to_string(label),
+        _ => Ok("default".to_string()),
+    }
+}
+
+/// Show how long a timer has been running, like `12.345ms` or `1.500s`
+fn format_duration(ms: f64) -> String {
+    if ms >= 1000.0 {
+        format!("{:.3}s", ms / 1000.0)
+    } else {
+        format!("{:.3}ms", ms)
+    }
+}
+
+/// Write the formatted arguments at a log level
+/// https://console.spec.whatwg.org/#logger
+fn logger(level: LogLevel, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let message = format_args(args);
+    interpreter.console().print(level, &message);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Print a message to the standard output stream
+/// https://console.spec.whatwg.org/#log
+pub fn log(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    logger(LogLevel::Log, &args, interpreter)
+}
+
+/// Print an informative message to the standard output stream
+/// https://console.spec.whatwg.org/#info
+pub fn info(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    logger(LogLevel::Info, &args, interpreter)
+}
+
+/// Print a debugging message to the standard output stream
+/// https://console.spec.whatwg.org/#debug
+pub fn debug(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    logger(LogLevel::Debug, &args, interpreter)
+}
+
+/// Print a warning to the standard error stream
+/// https://console.spec.whatwg.org/#warn
+pub fn warn(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    logger(LogLevel::Warn, &args, interpreter)
+}
+
+/// Print an error to the standard error stream
+/// https://console.spec.whatwg.org/#error
+pub fn error(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    logger(LogLevel::Error, &args, interpreter)
+}
+
+/// Print a message and the call stack to the standard error stream
+/// https://console.spec.whatwg.org/#trace
+pub fn trace(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut message = format!("Trace: {}", format_args(&args)).trim_end().to_string();
+    for frame in interpreter.call_stack() {
+        message.push_str(&format!("\n    at {}", frame));
+    }
+    interpreter.console().print(LogLevel::Trace, &message);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Print a value like `util.inspect`, with strings quoted and the depth taken from the `depth` option
+/// https://console.spec.whatwg.org/#dir
+pub fn dir(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let value = args.first().cloned().unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let depth = match args.get(1) {
+        Some(options) if options.is_object() => match *options.get_field_slice("depth") {
+            ValueData::Undefined => Some(DEFAULT_DEPTH),
+            ValueData::Null => None,
+            _ => {
+                let depth = interpreter.to_number(&options.get_field_slice("depth"))?;
+                if depth.is_infinite() { None } else { Some(depth.max(0.0) as usize) }
+            }
+        },
+        _ => Some(DEFAULT_DEPTH),
+    };
+    let message = inspect(&value, depth);
+    interpreter.console().print(LogLevel::Log, &message);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Show a cell of a table, where objects with more than two properties are abbreviated
+fn format_cell(value: &Value) -> String {
+    let depth = if value.is_object() && !value.is_array() && has_more_keys_than(value, 2) { None } else { Some(0) };
+    match depth {
+        None => format!("[{}]", constructor_name(value).unwrap_or_else(|| "Object".to_string())),
+        Some(depth) => Inspector::new(Some(depth)).format_value(value, 1),
+    }
+}
+
+/// Returns true if the object has more enumerable own properties than a count
+fn has_more_keys_than(value: &Value, count: usize) -> bool {
+    let keys = value.own_keys();
+    keys.into_iter()
+        .filter(|key| value.get_own_prop(key.clone()).is_some_and(|prop| prop.enumerable))
+        .count()
+        > count
+}
+
+/// The enumerable own keys of an object, as they are shown as the columns or rows of a table
+fn table_keys(value: &Value) -> Vec<(String, Value)> {
+    let mut keys = Vec::new();
+    let mut value = value.clone();
+    while proxy::is_proxy(&value) {
+        value = value.get_private_field_slice(PROXY_TARGET);
+    }
+    for key in value.own_keys() {
+        if value.is_array() && key == "length" {
+            continue;
+        }
+        if let Some(prop) = value.get_own_prop(key.clone()) {
+            if prop.enumerable {
+                keys.push((key.to_string(), prop.value.clone()));
+            }
+        }
+    }
+    keys
+}
+
+/// Draw a table with a header row, centring each cell in its column
+fn render_table(head: &[String], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = head
+        .iter()
+        .enumerate()
+        .map(|(column, title)| {
+            let widest = rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0);
+            widest.max(title.chars().count()) + 2
+        })
+        .collect();
+    let render_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| {
+                let needed = width - cell.chars().count();
+                format!("{}{}{}", " ".repeat(needed / 2), cell, " ".repeat(needed - needed / 2))
+            })
+            .collect();
+        format!("│{}│", cells.join("│"))
+    };
+    let line = |left: &str, middle: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+        format!("{}{}{}", left, segments.join(middle), right)
+    };
+    let mut lines = vec![line("┌", "┬", "┐"), render_row(head), line("├", "┼", "┤")];
+    lines.extend(rows.iter().map(|row| render_row(row)));
+    lines.push(line("└", "┴", "┘"));
+    lines.join("\n")
+}
+
+/// Print the properties of an object, or the elements of an array, as a table with a row for each one.
+/// The rows' own properties are the columns, which can be limited to a list of names.
+pub fn table(this: Value, callee: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let data = match args.first() {
+        Some(data) if data.is_object() => data.clone(),
+        _ => return log(this, callee, args, interpreter),
+    };
+    let filter: Option<Vec<String>> = match args.get(1) {
+        Some(columns) if columns.is_array() => Some(table_keys(columns).into_iter().map(|(_, column)| column.to_string()).collect()),
+        _ => None,
+    };
+    let mut columns: Vec<String> = Vec::new();
+    let mut has_values = false;
+    let rows = table_keys(&data);
+    for (_, row) in rows.iter() {
+        if row.is_object() {
+            for (key, _) in table_keys(row) {
+                if !columns.contains(&key) {
+                    columns.push(key);
+                }
+            }
+        } else {
+            has_values = true;
+        }
+    }
+    if let Some(filter) = filter {
+        columns = filter;
+    }
+    let mut head = vec!["(index)".to_string()];
+    head.extend(columns.iter().cloned());
+    if has_values {
+        head.push("Values".to_string());
+    }
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(index, row)| {
+            let mut cells = vec![index.clone()];
+            let fields = if row.is_object() { table_keys(row) } else { Vec::new() };
+            for column in columns.iter() {
+                cells.push(match fields.iter().find(|(key, _)| key == column) {
+                    Some((_, value)) => format_cell(value),
+                    None => String::new(),
+                });
+            }
+            if has_values {
+                cells.push(if row.is_object() { String::new() } else { format_cell(row) });
+            }
+            cells
+        })
+        .collect();
+    let message = render_table(&head, &body);
+    interpreter.console().print(LogLevel::Log, &message);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Print the arguments as the label of a group, and indent the messages after it until the group ends
+/// https://console.spec.whatwg.org/#group
+pub fn group(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !args.is_empty() {
+        logger(LogLevel::Log, &args, interpreter)?;
+    }
+    interpreter.console().group_depth += 1;
     Ok(Gc::new(ValueData::Undefined))
 }
-/// Print a javascript value to the standard error stream
-pub fn error(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let args: Vec<String> = FromIterator::from_iter(
-        args.iter()
-            .map(|x| from_value::<String>(x.clone()).unwrap()),
-    );
-    println!(
-        "{}: {}",
-        Local::now().format("%X").to_string(),
-        args.join(" ")
-    );
+
+/// End the innermost group
+/// https://console.spec.whatwg.org/#groupend
+pub fn group_end(_: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let console = interpreter.console();
+    console.group_depth = console.group_depth.saturating_sub(1);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Start a timer with a label
+/// https://console.spec.whatwg.org/#time
+pub fn time(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let label = label(&args, interpreter)?;
+    let now = interpreter.clock().now();
+    let console = interpreter.console();
+    match console.timers.entry(label) {
+        Entry::Occupied(entry) => {
+            let message = format!("Warning: Label '{}' already exists for console.time()", entry.key());
+            console.print(LogLevel::Warn, &message);
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(now);
+        }
+    }
     Ok(Gc::new(ValueData::Undefined))
 }
+
+/// Print how long a timer has been running, and the rest of the arguments, or warn if there is no such timer
+fn print_timer(method: &str, args: &[Value], stop: bool, interpreter: &mut Interpreter) -> ResultValue {
+    let label = label(args, interpreter)?;
+    let now = interpreter.clock().now();
+    let data = if args.len() > 1 { format!(" {}", format_args(&args[1..])) } else { String::new() };
+    let console = interpreter.console();
+    match console.timers.get(&label).cloned() {
+        Some(start) => {
+            if stop {
+                console.timers.remove(&label);
+            }
+            let message = format!("{}: {}{}", label, format_duration(now - start), data);
+            console.print(LogLevel::Info, &message);
+        }
+        None => {
+            let message = format!("Warning: No such label '{}' for console.{}()", label, method);
+            console.print(LogLevel::Warn, &message);
+        }
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Print how long a timer has been running, and the rest of the arguments
+/// https://console.spec.whatwg.org/#timelog
+pub fn time_log(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    print_timer("timeLog", &args, false, interpreter)
+}
+
+/// Print how long a timer ran for, and stop it
+/// https://console.spec.whatwg.org/#timeend
+pub fn time_end(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    print_timer("timeEnd", &args[..args.len().min(1)], true, interpreter)
+}
+
+/// Print how many times this has been called with a label
+/// https://console.spec.whatwg.org/#count
+pub fn count(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let label = label(&args, interpreter)?;
+    let console = interpreter.console();
+    let count = console.counts.entry(label.clone()).or_insert(0);
+    *count += 1;
+    let message = format!("{}: {}", label, count);
+    console.print(LogLevel::Info, &message);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Set the count of a label back to zero
+/// https://console.spec.whatwg.org/#countreset
+pub fn count_reset(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let label = label(&args, interpreter)?;
+    let console = interpreter.console();
+    match console.counts.get_mut(&label) {
+        Some(count) => *count = 0,
+        None => {
+            let message = format!("Warning: Count for '{}' does not exist", label);
+            console.print(LogLevel::Warn, &message);
+        }
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Print an error with the rest of the arguments if the first argument is falsy
+/// https://console.spec.whatwg.org/#assert
+pub fn assert(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if args.first().is_some_and(|condition| condition.is_true()) {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    let mut data: Vec<Value> = args.into_iter().skip(1).collect();
+    match data.first().map(|first| (**first).clone()) {
+        Some(ValueData::String(ref message)) => data[0] = to_value(format!("Assertion failed: {}", message)),
+        _ => data.insert(0, to_value("Assertion failed")),
+    }
+    logger(LogLevel::Error, &data, interpreter)
+}
+
 /// Create a new `console` object
 pub fn _create(global: &Value) -> Value {
     let console = ValueData::new_obj(Some(global));
     console.set_field_slice("log", to_value(log as NativeFunctionData));
+    console.set_field_slice("info", to_value(info as NativeFunctionData));
+    console.set_field_slice("debug", to_value(debug as NativeFunctionData));
+    console.set_field_slice("warn", to_value(warn as NativeFunctionData));
     console.set_field_slice("error", to_value(error as NativeFunctionData));
     console.set_field_slice("exception", to_value(error as NativeFunctionData));
+    console.set_field_slice("trace", to_value(trace as NativeFunctionData));
+    console.set_field_slice("dir", to_value(dir as NativeFunctionData));
+    console.set_field_slice("table", to_value(table as NativeFunctionData));
+    console.set_field_slice("group", to_value(group as NativeFunctionData));
+    console.set_field_slice("groupCollapsed", to_value(group as NativeFunctionData));
+    console.set_field_slice("groupEnd", to_value(group_end as NativeFunctionData));
+    console.set_field_slice("time", to_value(time as NativeFunctionData));
+    console.set_field_slice("timeLog", to_value(time_log as NativeFunctionData));
+    console.set_field_slice("timeEnd", to_value(time_end as NativeFunctionData));
+    console.set_field_slice("count", to_value(count as NativeFunctionData));
+    console.set_field_slice("countReset", to_value(count_reset as NativeFunctionData));
+    console.set_field_slice("assert", to_value(assert as NativeFunctionData));
     console
 }
+
 /// Initialise the global object with the `console` object
 pub fn init(global: &Value) {
     global.set_field_slice("console", _create(global));
-}
\ No newline at end of file
+}
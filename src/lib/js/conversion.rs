@@ -0,0 +1,56 @@
+use crate::exec::Interpreter;
+use crate::js::value::Value;
+
+/// The largest integer which numbers hold exactly, 2^53 - 1, and so the largest length or index
+/// https://tc39.es/ecma262/#sec-number.max_safe_integer
+pub const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Get the argument at an index as a number, which is `NaN` if it wasn't passed
+pub fn number_arg(args: &[Value], index: usize, interpreter: &mut Interpreter) -> Result<f64, Value> {
+    match args.get(index) {
+        Some(value) => interpreter.to_number(value),
+        None => Ok(f64::NAN),
+    }
+}
+
+/// Convert a value to an integer, where `NaN` is 0 and infinities are kept
+/// https://tc39.es/ecma262/#sec-tointegerorinfinity
+pub fn to_integer_or_infinity(value: &Value, interpreter: &mut Interpreter) -> Result<f64, Value> {
+    let num = interpreter.to_number(value)?;
+    Ok(if num.is_nan() { 0.0 } else { num.trunc() + 0.0 })
+}
+
+/// Convert a value to an integer which is a valid length, clamped to `0..=MAX_SAFE_INTEGER`
+/// https://tc39.es/ecma262/#sec-tolength
+pub fn to_length(value: &Value, interpreter: &mut Interpreter) -> Result<f64, Value> {
+    Ok(to_integer_or_infinity(value, interpreter)?.clamp(0.0, MAX_SAFE_INTEGER))
+}
+
+/// Convert a value to an integer which can be used as a length or an offset, or throw a `RangeError` with the
+/// message if it is negative or too large. Undefined is 0.
+/// https://tc39.es/ecma262/#sec-toindex
+pub fn to_index(value: &Value, message: &str, interpreter: &mut Interpreter) -> Result<usize, Value> {
+    if value.is_undefined() {
+        return Ok(0);
+    }
+    let integer = to_integer_or_infinity(value, interpreter)?;
+    if !(0.0..=MAX_SAFE_INTEGER).contains(&integer) {
+        return Err(interpreter.range_error(message));
+    }
+    Ok(integer as usize)
+}
+
+/// Resolve a relative index argument such as the `start` of `slice`, where negative values count back from the end
+/// and the result is clamped to `0..=length`. Undefined is `default`.
+pub fn relative_index(value: &Value, length: usize, default: usize, interpreter: &mut Interpreter) -> Result<usize, Value> {
+    if value.is_undefined() {
+        return Ok(default);
+    }
+    let relative = to_integer_or_infinity(value, interpreter)?;
+    let length = length as f64;
+    Ok(if relative < 0.0 {
+        (length + relative).max(0.0) as usize
+    } else {
+        relative.min(length) as usize
+    })
+}
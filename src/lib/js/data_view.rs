@@ -0,0 +1,308 @@
+/// The global `DataView` object, which reads and writes numbers of any type and byte order anywhere in a buffer
+/// https://tc39.es/ecma262/#sec-dataview-objects
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::array_buffer::{self, getter_property, ByteBuffer, BYTE_OFFSET, VIEWED_ARRAY_BUFFER};
+use crate::js::conversion::to_index;
+use crate::js::function::NativeFunctionData;
+use crate::js::object::PROTOTYPE;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::typed_array::{self, ElementType};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+
+/// The private field holding the number of bytes a `DataView` looks at, the [[ByteLength]] internal slot.
+/// Views which track the length of a resizable buffer don't have it.
+pub static BYTE_LENGTH: &str = "ByteLength";
+
+/// The internal slots of a `DataView`, which say which bytes of which buffer it looks at
+pub struct DataView {
+    /// The `ArrayBuffer` the view looks at
+    pub buffer: Value,
+    /// The bytes of the buffer
+    pub data: ByteBuffer,
+    /// Where the view starts in the buffer
+    pub byte_offset: usize,
+    /// The number of bytes, or `None` if the view tracks the length of a resizable buffer
+    pub byte_length: Option<usize>,
+}
+
+impl DataView {
+    /// Get the internal slots of a `DataView`, or `None` if the value isn't one
+    pub fn from_value(value: &Value) -> Option<DataView> {
+        // Typed arrays have a byte offset too
+        if !array_buffer::is_view(value) || typed_array::is_typed_array(value) {
+            return None;
+        }
+        let buffer = value.get_private_field_slice(VIEWED_ARRAY_BUFFER);
+        let data = array_buffer::array_buffer_data(&buffer)?;
+        let byte_length = value.get_private_field_slice(BYTE_LENGTH);
+        Some(DataView {
+            buffer,
+            data,
+            byte_offset: value.get_private_field_slice(BYTE_OFFSET).to_num() as usize,
+            byte_length: if byte_length.is_undefined() { None } else { Some(byte_length.to_num() as usize) },
+        })
+    }
+
+    /// Get the number of bytes, or `None` if the buffer has shrunk so that the view is out of its bounds
+    /// https://tc39.es/ecma262/#sec-getviewbytelength
+    pub fn byte_length(&self) -> Option<usize> {
+        let buffer_length = self.data.len();
+        match self.byte_length {
+            None if self.byte_offset > buffer_length => None,
+            None => Some(buffer_length - self.byte_offset),
+            Some(length) if self.byte_offset + length > buffer_length => None,
+            Some(length) => Some(length),
+        }
+    }
+}
+
+/// Get the internal slots of the `DataView` `this`, or throw if it isn't one
+fn this_data_view(this: &Value, method: &str, interpreter: &Interpreter) -> Result<DataView, Value> {
+    DataView::from_value(this).ok_or_else(|| {
+        interpreter.type_error(format!("DataView.prototype.{} requires that 'this' be a DataView", method))
+    })
+}
+
+/// Get the number of bytes of a view, or throw if the view is out of the bounds of its buffer
+fn view_byte_length(view: &DataView, method: &str, interpreter: &Interpreter) -> Result<usize, Value> {
+    view.byte_length().ok_or_else(|| {
+        interpreter.type_error(format!("Cannot perform DataView.prototype.{} on a detached or out of bounds DataView", method))
+    })
+}
+
+/// Create a new `DataView` looking at the bytes of a buffer from `byteOffset`, for `byteLength` bytes or up to
+/// the end of the buffer
+/// https://tc39.es/ecma262/#sec-dataview-buffer-byteoffset-bytelength
+pub fn make_data_view(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error("Constructor DataView requires 'new'"));
+    }
+    let buffer = arg(&args, 0);
+    if !array_buffer::is_array_buffer(&buffer) {
+        return Err(interpreter.type_error("First argument to DataView constructor must be an ArrayBuffer"));
+    }
+    let offset_arg = arg(&args, 1);
+    let message = format!("Start offset {} is outside the bounds of the buffer", offset_arg);
+    let offset = to_index(&offset_arg, &message, interpreter)?;
+    let length_arg = arg(&args, 2);
+    let view_length = if length_arg.is_undefined() {
+        None
+    } else {
+        Some(to_index(&length_arg, &format!("Invalid DataView length {}", length_arg), interpreter)?)
+    };
+    // Converting the arguments may have resized the buffer
+    let buffer_length = array_buffer::array_buffer_data(&buffer).map_or(0, |data| data.len());
+    if offset > buffer_length {
+        return Err(interpreter.range_error(format!("Start offset {} is outside the bounds of the buffer", offset)));
+    }
+    let byte_length = match view_length {
+        // Views of resizable buffers without a length track the length of the buffer
+        None if array_buffer::max_byte_length(&buffer).is_some() => None,
+        None => Some(buffer_length - offset),
+        Some(length) if offset + length > buffer_length => {
+            return Err(interpreter.range_error(format!("Invalid DataView length {}", length)));
+        }
+        Some(length) => Some(length),
+    };
+    this.set_private_field_slice(VIEWED_ARRAY_BUFFER, buffer);
+    this.set_private_field_slice(BYTE_OFFSET, to_value(offset as f64));
+    if let Some(byte_length) = byte_length {
+        this.set_private_field_slice(BYTE_LENGTH, to_value(byte_length as f64));
+    }
+    Ok(this)
+}
+
+/// `get DataView.prototype.buffer`
+/// https://tc39.es/ecma262/#sec-get-dataview.prototype.buffer
+pub fn get_buffer(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(this_data_view(&this, "buffer", interpreter)?.buffer)
+}
+
+/// `get DataView.prototype.byteLength`, which throws if the view is out of the bounds of its buffer
+/// https://tc39.es/ecma262/#sec-get-dataview.prototype.bytelength
+pub fn get_byte_length(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let view = this_data_view(&this, "byteLength", interpreter)?;
+    Ok(to_value(view_byte_length(&view, "byteLength", interpreter)? as f64))
+}
+
+/// `get DataView.prototype.byteOffset`, which throws if the view is out of the bounds of its buffer
+/// https://tc39.es/ecma262/#sec-get-dataview.prototype.byteoffset
+pub fn get_byte_offset(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let view = this_data_view(&this, "byteOffset", interpreter)?;
+    view_byte_length(&view, "byteOffset", interpreter)?;
+    Ok(to_value(view.byte_offset as f64))
+}
+
+/// Read an element of the given type at `byteOffset` in the view, in little-endian order if `littleEndian` is
+/// truthy and big-endian order otherwise
+/// https://tc39.es/ecma262/#sec-getviewvalue
+fn get_view_value(this: &Value, args: &[Value], kind: ElementType, method: &str, interpreter: &mut Interpreter) -> ResultValue {
+    let view = this_data_view(this, method, interpreter)?;
+    let index = to_index(&arg(args, 0), "Offset is outside the bounds of the DataView", interpreter)?;
+    let little_endian = arg(args, 1).is_true();
+    let view_size = view_byte_length(&view, method, interpreter)?;
+    if index + kind.size() > view_size {
+        return Err(interpreter.range_error("Offset is outside the bounds of the DataView"));
+    }
+    let start = view.byte_offset + index;
+    let bytes = view.data.borrow();
+    Ok(kind.decode(&bytes[start..], little_endian))
+}
+
+/// Write a value as an element of the given type at `byteOffset` in the view, in little-endian order if
+/// `littleEndian` is truthy and big-endian order otherwise
+/// https://tc39.es/ecma262/#sec-setviewvalue
+fn set_view_value(this: &Value, args: &[Value], kind: ElementType, method: &str, interpreter: &mut Interpreter) -> ResultValue {
+    let view = this_data_view(this, method, interpreter)?;
+    let index = to_index(&arg(args, 0), "Offset is outside the bounds of the DataView", interpreter)?;
+    let value = kind.to_numeric(&arg(args, 1), interpreter)?;
+    let little_endian = arg(args, 2).is_true();
+    let view_size = view_byte_length(&view, method, interpreter)?;
+    if index + kind.size() > view_size {
+        return Err(interpreter.range_error("Offset is outside the bounds of the DataView"));
+    }
+    let start = view.byte_offset + index;
+    let bytes = kind.encode(&value, little_endian);
+    view.data.borrow_mut()[start..start + bytes.len()].copy_from_slice(&bytes);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// `DataView.prototype.getInt8(byteOffset)`
+pub fn get_int8(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Int8, "getInt8", interpreter)
+}
+
+/// `DataView.prototype.getUint8(byteOffset)`
+pub fn get_uint8(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Uint8, "getUint8", interpreter)
+}
+
+/// `DataView.prototype.getInt16(byteOffset, littleEndian)`
+pub fn get_int16(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Int16, "getInt16", interpreter)
+}
+
+/// `DataView.prototype.getUint16(byteOffset, littleEndian)`
+pub fn get_uint16(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Uint16, "getUint16", interpreter)
+}
+
+/// `DataView.prototype.getInt32(byteOffset, littleEndian)`
+pub fn get_int32(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Int32, "getInt32", interpreter)
+}
+
+/// `DataView.prototype.getUint32(byteOffset, littleEndian)`
+pub fn get_uint32(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Uint32, "getUint32", interpreter)
+}
+
+/// `DataView.prototype.getFloat32(byteOffset, littleEndian)`
+pub fn get_float32(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Float32, "getFloat32", interpreter)
+}
+
+/// `DataView.prototype.getFloat64(byteOffset, littleEndian)`
+pub fn get_float64(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::Float64, "getFloat64", interpreter)
+}
+
+/// `DataView.prototype.getBigInt64(byteOffset, littleEndian)`
+pub fn get_big_int64(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::BigInt64, "getBigInt64", interpreter)
+}
+
+/// `DataView.prototype.getBigUint64(byteOffset, littleEndian)`
+pub fn get_big_uint64(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_view_value(&this, &args, ElementType::BigUint64, "getBigUint64", interpreter)
+}
+
+/// `DataView.prototype.setInt8(byteOffset, value)`
+pub fn set_int8(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Int8, "setInt8", interpreter)
+}
+
+/// `DataView.prototype.setUint8(byteOffset, value)`
+pub fn set_uint8(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Uint8, "setUint8", interpreter)
+}
+
+/// `DataView.prototype.setInt16(byteOffset, value, littleEndian)`
+pub fn set_int16(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Int16, "setInt16", interpreter)
+}
+
+/// `DataView.prototype.setUint16(byteOffset, value, littleEndian)`
+pub fn set_uint16(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Uint16, "setUint16", interpreter)
+}
+
+/// `DataView.prototype.setInt32(byteOffset, value, littleEndian)`
+pub fn set_int32(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Int32, "setInt32", interpreter)
+}
+
+/// `DataView.prototype.setUint32(byteOffset, value, littleEndian)`
+pub fn set_uint32(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Uint32, "setUint32", interpreter)
+}
+
+/// `DataView.prototype.setFloat32(byteOffset, value, littleEndian)`
+pub fn set_float32(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Float32, "setFloat32", interpreter)
+}
+
+/// `DataView.prototype.setFloat64(byteOffset, value, littleEndian)`
+pub fn set_float64(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::Float64, "setFloat64", interpreter)
+}
+
+/// `DataView.prototype.setBigInt64(byteOffset, value, littleEndian)`
+pub fn set_big_int64(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::BigInt64, "setBigInt64", interpreter)
+}
+
+/// `DataView.prototype.setBigUint64(byteOffset, value, littleEndian)`
+pub fn set_big_uint64(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_view_value(&this, &args, ElementType::BigUint64, "setBigUint64", interpreter)
+}
+
+/// Create a new `DataView` object
+pub fn _create(global: &Value) -> Value {
+    let data_view = to_value(make_data_view as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_prop_slice("buffer", getter_property(get_buffer));
+    prototype.set_prop_slice("byteLength", getter_property(get_byte_length));
+    prototype.set_prop_slice("byteOffset", getter_property(get_byte_offset));
+    prototype.set_field_slice("getInt8", to_value(get_int8 as NativeFunctionData));
+    prototype.set_field_slice("getUint8", to_value(get_uint8 as NativeFunctionData));
+    prototype.set_field_slice("getInt16", to_value(get_int16 as NativeFunctionData));
+    prototype.set_field_slice("getUint16", to_value(get_uint16 as NativeFunctionData));
+    prototype.set_field_slice("getInt32", to_value(get_int32 as NativeFunctionData));
+    prototype.set_field_slice("getUint32", to_value(get_uint32 as NativeFunctionData));
+    prototype.set_field_slice("getFloat32", to_value(get_float32 as NativeFunctionData));
+    prototype.set_field_slice("getFloat64", to_value(get_float64 as NativeFunctionData));
+    prototype.set_field_slice("getBigInt64", to_value(get_big_int64 as NativeFunctionData));
+    prototype.set_field_slice("getBigUint64", to_value(get_big_uint64 as NativeFunctionData));
+    prototype.set_field_slice("setInt8", to_value(set_int8 as NativeFunctionData));
+    prototype.set_field_slice("setUint8", to_value(set_uint8 as NativeFunctionData));
+    prototype.set_field_slice("setInt16", to_value(set_int16 as NativeFunctionData));
+    prototype.set_field_slice("setUint16", to_value(set_uint16 as NativeFunctionData));
+    prototype.set_field_slice("setInt32", to_value(set_int32 as NativeFunctionData));
+    prototype.set_field_slice("setUint32", to_value(set_uint32 as NativeFunctionData));
+    prototype.set_field_slice("setFloat32", to_value(set_float32 as NativeFunctionData));
+    prototype.set_field_slice("setFloat64", to_value(set_float64 as NativeFunctionData));
+    prototype.set_field_slice("setBigInt64", to_value(set_big_int64 as NativeFunctionData));
+    prototype.set_field_slice("setBigUint64", to_value(set_big_uint64 as NativeFunctionData));
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("DataView"));
+    data_view.set_field_slice("length", to_value(1i32));
+    prototype.set_field_slice("constructor", data_view.clone());
+    data_view.set_field_slice(PROTOTYPE, prototype);
+    data_view
+}
+
+/// Initialise the global object with the `DataView` object
+pub fn init(global: &Value) {
+    global.set_field_slice("DataView", _create(global));
+}
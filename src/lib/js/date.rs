@@ -0,0 +1,1000 @@
+/// The global `Date` object, and the clock and time zone dates are made with
+/// https://tc39.es/ecma262/#sec-date-objects
+///
+/// Time values are kept as the spec describes them: milliseconds since the epoch in UTC, as an `f64` which is
+/// `NaN` for an invalid date. The fields of a time value are worked out with the spec's own arithmetic, which covers
+/// the whole range of ±100,000,000 days, and `chrono` provides the system clock, the local time zone and RFC 2822
+/// parsing.
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::function::NativeFunctionData;
+use crate::js::object::PROTOTYPE;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+
+/// The private field holding the time value of a date, the [[DateValue]] internal slot
+pub static DATE_VALUE: &str = "DateValue";
+
+const MS_PER_SECOND: f64 = 1000.0;
+const MS_PER_MINUTE: f64 = 60_000.0;
+const MS_PER_HOUR: f64 = 3_600_000.0;
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// The furthest a time value can be from the epoch, which is 100,000,000 days
+/// https://tc39.es/ecma262/#sec-time-values-and-time-range
+const MAX_TIME: f64 = 8.64e15;
+
+const DAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTH_NAMES: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Where dates get the current time and the local time zone from.
+/// The interpreter uses the system clock unless the embedder gives it another one, such as a `FixedClock` for
+/// reproducible results.
+pub trait Clock {
+    /// The current time, in milliseconds since the epoch
+    fn now(&self) -> f64;
+
+    /// How far local time is ahead of UTC at a time, in milliseconds
+    fn offset(&self, time: f64) -> f64;
+
+    /// The name of the local time zone at a time, which `Date.prototype.toString` shows after the offset
+    fn time_zone_name(&self, _time: f64) -> Option<String> {
+        None
+    }
+}
+
+/// The clock and time zone of the system
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> f64 {
+        Utc::now().timestamp_millis() as f64
+    }
+
+    fn offset(&self, time: f64) -> f64 {
+        // Times chrono can't represent use the offset in effect now
+        let time = NaiveDateTime::from_timestamp_millis(time as i64).unwrap_or_else(|| Utc::now().naive_utc());
+        f64::from(Local.offset_from_utc_datetime(&time).local_minus_utc()) * MS_PER_SECOND
+    }
+}
+
+/// A clock which is stopped at one time, in a time zone with a fixed offset from UTC
+pub struct FixedClock {
+    now: f64,
+    offset: f64,
+    name: Option<String>,
+}
+
+impl FixedClock {
+    /// Make a clock stopped at a time in milliseconds since the epoch, with local time the given number of
+    /// minutes ahead of UTC
+    pub fn new(now: f64, offset_minutes: i32) -> FixedClock {
+        FixedClock {
+            now,
+            offset: f64::from(offset_minutes) * MS_PER_MINUTE,
+            name: None,
+        }
+    }
+
+    /// Give the time zone a name, like `Coordinated Universal Time`
+    pub fn with_name(mut self, name: &str) -> FixedClock {
+        self.name = Some(name.to_string());
+        self
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> f64 {
+        self.now
+    }
+
+    fn offset(&self, _: f64) -> f64 {
+        self.offset
+    }
+
+    fn time_zone_name(&self, _: f64) -> Option<String> {
+        self.name.clone()
+    }
+}
+
+/// The number of the day a time value falls on, counting from the epoch
+/// https://tc39.es/ecma262/#sec-day
+fn day(time: f64) -> f64 {
+    (time / MS_PER_DAY).floor()
+}
+
+/// The remainder of a division with the sign of the divisor, which is never -0
+fn modulo(x: f64, y: f64) -> f64 {
+    x.rem_euclid(y) + 0.0
+}
+
+/// https://tc39.es/ecma262/#sec-timewithinday
+fn time_within_day(time: f64) -> f64 {
+    modulo(time, MS_PER_DAY)
+}
+
+/// https://tc39.es/ecma262/#sec-daysinyear
+fn days_in_year(year: f64) -> f64 {
+    if year % 4.0 != 0.0 || (year % 100.0 == 0.0 && year % 400.0 != 0.0) {
+        365.0
+    } else {
+        366.0
+    }
+}
+
+/// The number of the first day of a year
+/// https://tc39.es/ecma262/#sec-dayfromyear
+fn day_from_year(year: f64) -> f64 {
+    365.0 * (year - 1970.0) + ((year - 1969.0) / 4.0).floor() - ((year - 1901.0) / 100.0).floor()
+        + ((year - 1601.0) / 400.0).floor()
+}
+
+/// https://tc39.es/ecma262/#sec-timefromyear
+fn time_from_year(year: f64) -> f64 {
+    MS_PER_DAY * day_from_year(year)
+}
+
+/// https://tc39.es/ecma262/#sec-yearfromtime
+fn year_from_time(time: f64) -> f64 {
+    let mut year = (time / (MS_PER_DAY * 365.2425)).floor() + 1970.0;
+    while time_from_year(year) > time {
+        year -= 1.0;
+    }
+    while time_from_year(year + 1.0) <= time {
+        year += 1.0;
+    }
+    year
+}
+
+/// The number of days before the first day of each month, and of the next year
+fn month_starts(year: f64) -> [f64; 13] {
+    let leap = days_in_year(year) - 365.0;
+    [
+        0.0,
+        31.0,
+        59.0 + leap,
+        90.0 + leap,
+        120.0 + leap,
+        151.0 + leap,
+        181.0 + leap,
+        212.0 + leap,
+        243.0 + leap,
+        273.0 + leap,
+        304.0 + leap,
+        334.0 + leap,
+        365.0 + leap,
+    ]
+}
+
+/// https://tc39.es/ecma262/#sec-month-number
+fn month_from_time(time: f64) -> f64 {
+    let year = year_from_time(time);
+    let day_within_year = day(time) - day_from_year(year);
+    let starts = month_starts(year);
+    (0..12).rev().find(|&month| day_within_year >= starts[month]).unwrap_or(0) as f64
+}
+
+/// https://tc39.es/ecma262/#sec-date-number
+fn date_from_time(time: f64) -> f64 {
+    let year = year_from_time(time);
+    let day_within_year = day(time) - day_from_year(year);
+    day_within_year - month_starts(year)[month_from_time(time) as usize] + 1.0
+}
+
+/// https://tc39.es/ecma262/#sec-week-day
+fn week_day(time: f64) -> f64 {
+    modulo(day(time) + 4.0, 7.0)
+}
+
+/// https://tc39.es/ecma262/#sec-hours-minutes-second-and-milliseconds
+fn hour_from_time(time: f64) -> f64 {
+    modulo((time / MS_PER_HOUR).floor(), 24.0)
+}
+
+fn min_from_time(time: f64) -> f64 {
+    modulo((time / MS_PER_MINUTE).floor(), 60.0)
+}
+
+fn sec_from_time(time: f64) -> f64 {
+    modulo((time / MS_PER_SECOND).floor(), 60.0)
+}
+
+fn ms_from_time(time: f64) -> f64 {
+    modulo(time, MS_PER_SECOND)
+}
+
+/// https://tc39.es/ecma262/#sec-maketime
+fn make_time(hour: f64, min: f64, sec: f64, ms: f64) -> f64 {
+    if !(hour.is_finite() && min.is_finite() && sec.is_finite() && ms.is_finite()) {
+        return f64::NAN;
+    }
+    hour.trunc() * MS_PER_HOUR + min.trunc() * MS_PER_MINUTE + sec.trunc() * MS_PER_SECOND + ms.trunc()
+}
+
+/// https://tc39.es/ecma262/#sec-makeday
+fn make_day(year: f64, month: f64, date: f64) -> f64 {
+    if !(year.is_finite() && month.is_finite() && date.is_finite()) {
+        return f64::NAN;
+    }
+    let (month, date) = (month.trunc(), date.trunc());
+    let year = year.trunc() + (month / 12.0).floor();
+    // Years this far out are past the range of time values, however many days are added
+    if year.abs() > 400_000.0 {
+        return f64::NAN;
+    }
+    let month = month.rem_euclid(12.0);
+    day_from_year(year) + month_starts(year)[month as usize] + date - 1.0
+}
+
+/// https://tc39.es/ecma262/#sec-makedate
+fn make_date(day: f64, time: f64) -> f64 {
+    let date = day * MS_PER_DAY + time;
+    if date.is_finite() {
+        date
+    } else {
+        f64::NAN
+    }
+}
+
+/// https://tc39.es/ecma262/#sec-timeclip
+fn time_clip(time: f64) -> f64 {
+    if !time.is_finite() || time.abs() > MAX_TIME {
+        return f64::NAN;
+    }
+    // Adding 0 turns -0 into +0
+    time.trunc() + 0.0
+}
+
+/// Convert a time value from UTC to local time
+/// https://tc39.es/ecma262/#sec-localtime
+fn local_time(time: f64, clock: &dyn Clock) -> f64 {
+    time + clock.offset(time)
+}
+
+/// Convert a time value from local time to UTC
+/// https://tc39.es/ecma262/#sec-utc-t
+fn utc(time: f64, clock: &dyn Clock) -> f64 {
+    if !time.is_finite() {
+        return f64::NAN;
+    }
+    time - clock.offset(time - clock.offset(time))
+}
+
+/// Returns true if the value is a date object
+pub fn is_date_object(value: &Value) -> bool {
+    value.is_object() && value.get_private_field_slice(DATE_VALUE).get_type() == "number"
+}
+
+/// Get the time value of the date `this`, or throw if it isn't a date
+/// https://tc39.es/ecma262/#sec-thistimevalue
+fn this_time_value(this: &Value, interpreter: &Interpreter) -> Result<f64, Value> {
+    if !is_date_object(this) {
+        return Err(interpreter.type_error("this is not a Date object."));
+    }
+    Ok(this.get_private_field_slice(DATE_VALUE).to_num())
+}
+
+/// Format a year with at least four digits, and six with a sign outside of 0 to 9999 when `extended`
+fn format_year(year: f64, extended: bool) -> String {
+    if extended && !(0.0..=9999.0).contains(&year) {
+        format!("{}{:06}", if year < 0.0 { "-" } else { "+" }, year.abs())
+    } else if year < 0.0 {
+        format!("-{:06}", -year)
+    } else {
+        format!("{:04}", year)
+    }
+}
+
+/// Format the date of a time value like `Tue Jan 02 2024`
+/// https://tc39.es/ecma262/#sec-datestring
+fn date_string(time: f64) -> String {
+    format!(
+        "{} {} {:02} {}",
+        DAY_NAMES[week_day(time) as usize],
+        MONTH_NAMES[month_from_time(time) as usize],
+        date_from_time(time),
+        format_year(year_from_time(time), false)
+    )
+}
+
+/// Format the time of a time value like `10:00:00`
+/// https://tc39.es/ecma262/#sec-timestring
+fn time_string(time: f64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        hour_from_time(time),
+        min_from_time(time),
+        sec_from_time(time)
+    )
+}
+
+/// Format the local time zone at a UTC time value like `GMT+0100 (Central European Standard Time)`
+/// https://tc39.es/ecma262/#sec-timezoneestring
+fn time_zone_string(time: f64, clock: &dyn Clock) -> String {
+    let offset = clock.offset(time);
+    let minutes = (offset.abs() / MS_PER_MINUTE).floor();
+    let sign = if offset < 0.0 { "-" } else { "+" };
+    let zone = format!("GMT{}{:02}{:02}", sign, (minutes / 60.0).floor(), minutes % 60.0);
+    match clock.time_zone_name(time) {
+        Some(name) => format!("{} ({})", zone, name),
+        None => zone,
+    }
+}
+
+/// Format a UTC time value like `Tue Jan 02 2024 10:00:00 GMT+0100`, in local time
+/// https://tc39.es/ecma262/#sec-todatestring
+pub fn to_date_string(time: f64, clock: &dyn Clock) -> String {
+    if time.is_nan() {
+        return "Invalid Date".to_string();
+    }
+    let local = local_time(time, clock);
+    format!("{} {} {}", date_string(local), time_string(local), time_zone_string(time, clock))
+}
+
+/// Format a UTC time value in the format of the ISO 8601 calendar date extended format, like
+/// `2024-01-02T09:00:00.000Z`
+/// https://tc39.es/ecma262/#sec-date-time-string-format
+pub fn iso_string(time: f64) -> String {
+    format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        format_year(year_from_time(time), true),
+        month_from_time(time) + 1.0,
+        date_from_time(time),
+        hour_from_time(time),
+        min_from_time(time),
+        sec_from_time(time),
+        ms_from_time(time)
+    )
+}
+
+/// Format the date of a local time value like `en-US` does, like `1/2/2024`
+fn locale_date_string(time: f64) -> String {
+    format!("{}/{}/{}", month_from_time(time) + 1.0, date_from_time(time), year_from_time(time))
+}
+
+/// Format the time of a local time value like `en-US` does, like `10:00:00 AM`
+fn locale_time_string(time: f64) -> String {
+    let hour = hour_from_time(time);
+    let twelve_hour = if hour % 12.0 == 0.0 { 12.0 } else { hour % 12.0 };
+    format!(
+        "{}:{:02}:{:02} {}",
+        twelve_hour,
+        min_from_time(time),
+        sec_from_time(time),
+        if hour < 12.0 { "AM" } else { "PM" }
+    )
+}
+
+/// Read `count` digits from the characters of a date string
+fn read_digits(chars: &[char], position: &mut usize, count: usize) -> Option<f64> {
+    let digits = chars.get(*position..*position + count)?;
+    if !digits.iter().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    *position += count;
+    Some(digits.iter().fold(0.0, |num, ch| num * 10.0 + f64::from(ch.to_digit(10).unwrap())))
+}
+
+/// Returns true and moves past the character if it is next
+fn read_char(chars: &[char], position: &mut usize, expected: char) -> bool {
+    if chars.get(*position) == Some(&expected) {
+        *position += 1;
+        true
+    } else {
+        false
+    }
+}
+
+/// Parse the date time string format, a simplification of ISO 8601 like `2024-01-02T10:00:00.000+01:00`.
+/// Forms with only a date are UTC, and forms with a time but no offset are local time.
+/// https://tc39.es/ecma262/#sec-date-time-string-format
+fn parse_iso(string: &str, clock: &dyn Clock) -> Option<f64> {
+    let chars: Vec<char> = string.chars().collect();
+    let mut position = 0;
+    let year = match chars.first() {
+        Some(&sign) if sign == '+' || sign == '-' => {
+            position += 1;
+            let year = read_digits(&chars, &mut position, 6)?;
+            // -000000 is not allowed, as 0 has no sign
+            if sign == '-' && year == 0.0 {
+                return None;
+            }
+            if sign == '-' { -year } else { year }
+        }
+        _ => read_digits(&chars, &mut position, 4)?,
+    };
+    let mut month = 1.0;
+    let mut date = 1.0;
+    if read_char(&chars, &mut position, '-') {
+        month = read_digits(&chars, &mut position, 2)?;
+        if read_char(&chars, &mut position, '-') {
+            date = read_digits(&chars, &mut position, 2)?;
+        }
+    }
+    let (mut hour, mut min, mut sec, mut ms) = (0.0, 0.0, 0.0, 0.0);
+    let has_time = read_char(&chars, &mut position, 'T');
+    let mut offset = None;
+    if has_time {
+        hour = read_digits(&chars, &mut position, 2)?;
+        if !read_char(&chars, &mut position, ':') {
+            return None;
+        }
+        min = read_digits(&chars, &mut position, 2)?;
+        if read_char(&chars, &mut position, ':') {
+            sec = read_digits(&chars, &mut position, 2)?;
+            if read_char(&chars, &mut position, '.') {
+                // Any number of digits can follow, but only the milliseconds are kept
+                let start = position;
+                while chars.get(position).is_some_and(|ch| ch.is_ascii_digit()) {
+                    position += 1;
+                }
+                if position == start {
+                    return None;
+                }
+                let fraction: String = chars[start..position].iter().take(3).collect();
+                ms = format!("{:0<3}", fraction).parse::<f64>().ok()?;
+            }
+        }
+        if read_char(&chars, &mut position, 'Z') {
+            offset = Some(0.0);
+        } else if let Some(&sign) = chars.get(position).filter(|&&ch| ch == '+' || ch == '-') {
+            position += 1;
+            let offset_hours = read_digits(&chars, &mut position, 2)?;
+            if !read_char(&chars, &mut position, ':') {
+                return None;
+            }
+            let offset_minutes = read_digits(&chars, &mut position, 2)?;
+            let minutes = offset_hours * 60.0 + offset_minutes;
+            offset = Some(if sign == '-' { -minutes } else { minutes } * MS_PER_MINUTE);
+        }
+    }
+    if position != chars.len() {
+        return None;
+    }
+    let in_range = (1.0..=12.0).contains(&month)
+        && date >= 1.0
+        && date <= month_starts(year)[month as usize] - month_starts(year)[month as usize - 1]
+        && (hour < 24.0 || (hour == 24.0 && min == 0.0 && sec == 0.0 && ms == 0.0))
+        && min < 60.0
+        && sec < 60.0;
+    if !in_range {
+        return None;
+    }
+    let time = make_date(make_day(year, month - 1.0, date), make_time(hour, min, sec, ms));
+    Some(match offset {
+        Some(offset) => time - offset,
+        None if has_time => utc(time, clock),
+        None => time,
+    })
+}
+
+/// Parse a date string, which is the date time string format, RFC 2822, or what `toString` and `toUTCString`
+/// give. Anything else is an invalid date.
+/// https://tc39.es/ecma262/#sec-date.parse
+pub fn parse_date(string: &str, clock: &dyn Clock) -> f64 {
+    let string = string.trim();
+    if let Some(time) = parse_iso(string, clock) {
+        return time_clip(time);
+    }
+    if let Ok(date) = DateTime::parse_from_rfc2822(string) {
+        return time_clip(date.timestamp_millis() as f64);
+    }
+    // The format of `toString`, without the name of the time zone
+    let without_name = match string.find(" (") {
+        Some(index) if string.ends_with(')') => &string[..index],
+        _ => string,
+    };
+    match DateTime::parse_from_str(without_name, "%a %b %d %Y %H:%M:%S GMT%z") {
+        Ok(date) => time_clip(date.timestamp_millis() as f64),
+        Err(_) => f64::NAN,
+    }
+}
+
+/// Work out a time value from the arguments `year, month[, date[, hours[, minutes[, seconds[, ms]]]]]`, which is
+/// in the time zone the arguments are in
+fn time_from_arguments(args: &[Value], interpreter: &mut Interpreter) -> Result<f64, Value> {
+    let mut fields = [f64::NAN, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    for (field, arg) in fields.iter_mut().zip(args.iter()) {
+        *field = interpreter.to_number(arg)?;
+    }
+    let [year, month, date, hours, minutes, seconds, ms] = fields;
+    // Years 0 to 99 are in the 1900s
+    let year = if !year.is_nan() && (0.0..=99.0).contains(&year.trunc()) {
+        1900.0 + year.trunc()
+    } else {
+        year
+    };
+    Ok(make_date(make_day(year, month, date), make_time(hours, minutes, seconds, ms)))
+}
+
+/// Create a new date. Called as a function, it gives the current time as a string.
+/// https://tc39.es/ecma262/#sec-date-constructor
+pub fn make_date_object(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        let now = interpreter.clock().now();
+        return Ok(to_value(to_date_string(now, interpreter.clock())));
+    }
+    let time = match args.len() {
+        0 => interpreter.clock().now(),
+        1 if is_date_object(&args[0]) => this_time_value(&args[0], interpreter)?,
+        1 => {
+            let value = interpreter.to_primitive(&args[0], "default")?;
+            if value.is_string() {
+                parse_date(&value.to_string(), interpreter.clock())
+            } else {
+                interpreter.to_number(&value)?
+            }
+        }
+        _ => {
+            let time = time_from_arguments(&args, interpreter)?;
+            utc(time, interpreter.clock())
+        }
+    };
+    this.set_private_field_slice(DATE_VALUE, to_value(time_clip(time)));
+    Ok(this)
+}
+
+/// `Date.now()`
+/// https://tc39.es/ecma262/#sec-date.now
+pub fn now(_: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(interpreter.clock().now()))
+}
+
+/// `Date.parse(string)`
+/// https://tc39.es/ecma262/#sec-date.parse
+pub fn parse(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = match args.first() {
+        Some(string) => interpreter.to_string(string)?,
+        None => "undefined".to_string(),
+    };
+    Ok(to_value(parse_date(&string, interpreter.clock())))
+}
+
+/// `Date.UTC(year[, month[, date[, hours[, minutes[, seconds[, ms]]]]]])`
+/// https://tc39.es/ecma262/#sec-date.utc
+pub fn utc_time(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(time_clip(time_from_arguments(&args, interpreter)?)))
+}
+
+/// Get a field of the time value of `this` in local time, or NaN if the date is invalid
+fn get_local(this: &Value, interpreter: &Interpreter, field: fn(f64) -> f64) -> ResultValue {
+    let time = this_time_value(this, interpreter)?;
+    if time.is_nan() {
+        return Ok(to_value(f64::NAN));
+    }
+    Ok(to_value(field(local_time(time, interpreter.clock()))))
+}
+
+/// Get a field of the time value of `this` in UTC, or NaN if the date is invalid
+fn get_utc(this: &Value, interpreter: &Interpreter, field: fn(f64) -> f64) -> ResultValue {
+    let time = this_time_value(this, interpreter)?;
+    if time.is_nan() {
+        return Ok(to_value(f64::NAN));
+    }
+    Ok(to_value(field(time)))
+}
+
+/// `Date.prototype.getTime()`, which is also `Date.prototype.valueOf`
+/// https://tc39.es/ecma262/#sec-date.prototype.gettime
+pub fn get_time(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_time_value(&this, interpreter)?))
+}
+
+/// `Date.prototype.getFullYear()`
+pub fn get_full_year(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, year_from_time)
+}
+
+/// `Date.prototype.getYear()`, which is the full year minus 1900
+/// https://tc39.es/ecma262/#sec-date.prototype.getyear
+pub fn get_year(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, |time| year_from_time(time) - 1900.0)
+}
+
+/// `Date.prototype.getMonth()`
+pub fn get_month(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, month_from_time)
+}
+
+/// `Date.prototype.getDate()`
+pub fn get_date(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, date_from_time)
+}
+
+/// `Date.prototype.getDay()`
+pub fn get_day(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, week_day)
+}
+
+/// `Date.prototype.getHours()`
+pub fn get_hours(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, hour_from_time)
+}
+
+/// `Date.prototype.getMinutes()`
+pub fn get_minutes(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, min_from_time)
+}
+
+/// `Date.prototype.getSeconds()`
+pub fn get_seconds(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, sec_from_time)
+}
+
+/// `Date.prototype.getMilliseconds()`
+pub fn get_milliseconds(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_local(&this, interpreter, ms_from_time)
+}
+
+/// `Date.prototype.getUTCFullYear()`
+pub fn get_utc_full_year(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, year_from_time)
+}
+
+/// `Date.prototype.getUTCMonth()`
+pub fn get_utc_month(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, month_from_time)
+}
+
+/// `Date.prototype.getUTCDate()`
+pub fn get_utc_date(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, date_from_time)
+}
+
+/// `Date.prototype.getUTCDay()`
+pub fn get_utc_day(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, week_day)
+}
+
+/// `Date.prototype.getUTCHours()`
+pub fn get_utc_hours(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, hour_from_time)
+}
+
+/// `Date.prototype.getUTCMinutes()`
+pub fn get_utc_minutes(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, min_from_time)
+}
+
+/// `Date.prototype.getUTCSeconds()`
+pub fn get_utc_seconds(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, sec_from_time)
+}
+
+/// `Date.prototype.getUTCMilliseconds()`
+pub fn get_utc_milliseconds(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    get_utc(&this, interpreter, ms_from_time)
+}
+
+/// `Date.prototype.getTimezoneOffset()`, which is how many minutes UTC is ahead of local time
+/// https://tc39.es/ecma262/#sec-date.prototype.gettimezoneoffset
+pub fn get_timezone_offset(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let time = this_time_value(&this, interpreter)?;
+    if time.is_nan() {
+        return Ok(to_value(f64::NAN));
+    }
+    Ok(to_value((time - local_time(time, interpreter.clock())) / MS_PER_MINUTE + 0.0))
+}
+
+/// The fields of a time value which the setters change, in the order of their arguments
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Field {
+    FullYear,
+    Month,
+    Date,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+}
+
+/// Set the time value of `this` to a new one
+fn set_time_value(this: &Value, time: f64) -> ResultValue {
+    let time = time_clip(time);
+    this.set_private_field_slice(DATE_VALUE, to_value(time));
+    Ok(to_value(time))
+}
+
+/// Change some of the fields of the time value of `this`, starting at `first` and taking up to `count` of them
+/// from the arguments, in local time or UTC.
+/// An invalid date stays invalid, except that setting the full year starts again from +0.
+/// https://tc39.es/ecma262/#sec-date.prototype.sethours
+fn set_fields(this: &Value, args: &[Value], first: Field, count: usize, local: bool, interpreter: &mut Interpreter) -> ResultValue {
+    let time = this_time_value(this, interpreter)?;
+    let time = if time.is_nan() && first == Field::FullYear { 0.0 } else { time };
+    let time = if local { local_time(time, interpreter.clock()) } else { time };
+    let mut fields = [
+        year_from_time(time),
+        month_from_time(time),
+        date_from_time(time),
+        hour_from_time(time),
+        min_from_time(time),
+        sec_from_time(time),
+        ms_from_time(time),
+    ];
+    let first = first as usize;
+    fields[first] = f64::NAN;
+    for (index, arg) in args.iter().take(count).enumerate() {
+        fields[first + index] = interpreter.to_number(arg)?;
+    }
+    if time.is_nan() {
+        return Ok(to_value(f64::NAN));
+    }
+    let [year, month, date, hours, minutes, seconds, ms] = fields;
+    let new_time = make_date(make_day(year, month, date), make_time(hours, minutes, seconds, ms));
+    let new_time = if local { utc(new_time, interpreter.clock()) } else { new_time };
+    set_time_value(this, new_time)
+}
+
+/// `Date.prototype.setTime(time)`
+/// https://tc39.es/ecma262/#sec-date.prototype.settime
+pub fn set_time(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    this_time_value(&this, interpreter)?;
+    let time = match args.first() {
+        Some(time) => interpreter.to_number(time)?,
+        None => f64::NAN,
+    };
+    set_time_value(&this, time)
+}
+
+/// `Date.prototype.setFullYear(year[, month[, date]])`
+pub fn set_full_year(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::FullYear, 3, true, interpreter)
+}
+
+/// `Date.prototype.setYear(year)`, where years 0 to 99 are in the 1900s
+/// https://tc39.es/ecma262/#sec-date.prototype.setyear
+pub fn set_year(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let time = this_time_value(&this, interpreter)?;
+    let time = if time.is_nan() { 0.0 } else { local_time(time, interpreter.clock()) };
+    let year = match args.first() {
+        Some(year) => interpreter.to_number(year)?,
+        None => f64::NAN,
+    };
+    if year.is_nan() {
+        return set_time_value(&this, f64::NAN);
+    }
+    let year = if (0.0..=99.0).contains(&year.trunc()) { 1900.0 + year.trunc() } else { year };
+    let day = make_day(year, month_from_time(time), date_from_time(time));
+    let new_time = utc(make_date(day, time_within_day(time)), interpreter.clock());
+    set_time_value(&this, new_time)
+}
+
+/// `Date.prototype.setMonth(month[, date])`
+pub fn set_month(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Month, 2, true, interpreter)
+}
+
+/// `Date.prototype.setDate(date)`
+pub fn set_date(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Date, 1, true, interpreter)
+}
+
+/// `Date.prototype.setHours(hour[, min[, sec[, ms]]])`
+pub fn set_hours(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Hours, 4, true, interpreter)
+}
+
+/// `Date.prototype.setMinutes(min[, sec[, ms]])`
+pub fn set_minutes(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Minutes, 3, true, interpreter)
+}
+
+/// `Date.prototype.setSeconds(sec[, ms])`
+pub fn set_seconds(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Seconds, 2, true, interpreter)
+}
+
+/// `Date.prototype.setMilliseconds(ms)`
+pub fn set_milliseconds(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Milliseconds, 1, true, interpreter)
+}
+
+/// `Date.prototype.setUTCFullYear(year[, month[, date]])`
+pub fn set_utc_full_year(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::FullYear, 3, false, interpreter)
+}
+
+/// `Date.prototype.setUTCMonth(month[, date])`
+pub fn set_utc_month(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Month, 2, false, interpreter)
+}
+
+/// `Date.prototype.setUTCDate(date)`
+pub fn set_utc_date(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Date, 1, false, interpreter)
+}
+
+/// `Date.prototype.setUTCHours(hour[, min[, sec[, ms]]])`
+pub fn set_utc_hours(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Hours, 4, false, interpreter)
+}
+
+/// `Date.prototype.setUTCMinutes(min[, sec[, ms]])`
+pub fn set_utc_minutes(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Minutes, 3, false, interpreter)
+}
+
+/// `Date.prototype.setUTCSeconds(sec[, ms])`
+pub fn set_utc_seconds(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Seconds, 2, false, interpreter)
+}
+
+/// `Date.prototype.setUTCMilliseconds(ms)`
+pub fn set_utc_milliseconds(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    set_fields(&this, &args, Field::Milliseconds, 1, false, interpreter)
+}
+
+/// `Date.prototype.toISOString()`
+/// https://tc39.es/ecma262/#sec-date.prototype.toisostring
+pub fn to_iso_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let time = this_time_value(&this, interpreter)?;
+    if time.is_nan() {
+        return Err(interpreter.range_error("Invalid time value"));
+    }
+    Ok(to_value(iso_string(time)))
+}
+
+/// `Date.prototype.toJSON(key)`, which is null for an invalid date
+/// https://tc39.es/ecma262/#sec-date.prototype.tojson
+pub fn to_json(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let time = interpreter.to_primitive(&this, "number")?;
+    if time.get_type() == "number" && !time.to_num().is_finite() {
+        return Ok(Gc::new(ValueData::Null));
+    }
+    let to_iso_string = interpreter.get_field(&this, "toISOString")?;
+    interpreter.call(&to_iso_string, &this, Vec::new())
+}
+
+/// `Date.prototype.toString()`
+/// https://tc39.es/ecma262/#sec-date.prototype.tostring
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let time = this_time_value(&this, interpreter)?;
+    Ok(to_value(to_date_string(time, interpreter.clock())))
+}
+
+/// Format the time value of `this` in local time, or give `Invalid Date`
+fn format_local(this: &Value, interpreter: &Interpreter, format: fn(f64, &dyn Clock) -> String) -> ResultValue {
+    let time = this_time_value(this, interpreter)?;
+    if time.is_nan() {
+        return Ok(to_value("Invalid Date"));
+    }
+    Ok(to_value(format(time, interpreter.clock())))
+}
+
+/// `Date.prototype.toDateString()`, like `Tue Jan 02 2024`
+/// https://tc39.es/ecma262/#sec-date.prototype.todatestring
+pub fn to_date_string_method(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    format_local(&this, interpreter, |time, clock| date_string(local_time(time, clock)))
+}
+
+/// `Date.prototype.toTimeString()`, like `10:00:00 GMT+0100`
+/// https://tc39.es/ecma262/#sec-date.prototype.totimestring
+pub fn to_time_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    format_local(&this, interpreter, |time, clock| {
+        format!("{} {}", time_string(local_time(time, clock)), time_zone_string(time, clock))
+    })
+}
+
+/// `Date.prototype.toUTCString()`, like `Tue, 02 Jan 2024 09:00:00 GMT`
+/// https://tc39.es/ecma262/#sec-date.prototype.toutcstring
+pub fn to_utc_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    format_local(&this, interpreter, |time, _| {
+        format!(
+            "{}, {:02} {} {} {} GMT",
+            DAY_NAMES[week_day(time) as usize],
+            date_from_time(time),
+            MONTH_NAMES[month_from_time(time) as usize],
+            format_year(year_from_time(time), false),
+            time_string(time)
+        )
+    })
+}
+
+/// `Date.prototype.toLocaleString()`, which is formatted like the `en-US` locale, like `1/2/2024, 10:00:00 AM`
+/// https://tc39.es/ecma262/#sec-date.prototype.tolocalestring
+pub fn to_locale_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    format_local(&this, interpreter, |time, clock| {
+        let local = local_time(time, clock);
+        format!("{}, {}", locale_date_string(local), locale_time_string(local))
+    })
+}
+
+/// `Date.prototype.toLocaleDateString()`, like `1/2/2024`
+/// https://tc39.es/ecma262/#sec-date.prototype.tolocaledatestring
+pub fn to_locale_date_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    format_local(&this, interpreter, |time, clock| locale_date_string(local_time(time, clock)))
+}
+
+/// `Date.prototype.toLocaleTimeString()`, like `10:00:00 AM`
+/// https://tc39.es/ecma262/#sec-date.prototype.tolocaletimestring
+pub fn to_locale_time_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    format_local(&this, interpreter, |time, clock| locale_time_string(local_time(time, clock)))
+}
+
+/// `Date.prototype[Symbol.toPrimitive](hint)`, which makes dates strings unless a number is asked for
+/// https://tc39.es/ecma262/#sec-date.prototype-@@toprimitive
+pub fn to_primitive(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("Date.prototype[Symbol.toPrimitive] called on non-object"));
+    }
+    let hint = match args.first() {
+        Some(hint) if hint.is_string() => hint.to_string(),
+        _ => String::new(),
+    };
+    match hint.as_str() {
+        "string" | "default" => interpreter.ordinary_to_primitive(&this, "string"),
+        "number" => interpreter.ordinary_to_primitive(&this, "number"),
+        _ => Err(interpreter.type_error("Invalid hint")),
+    }
+}
+
+/// Create a new `Date` object
+pub fn _create(global: &Value) -> Value {
+    let date = to_value(make_date_object as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("getTime", to_value(get_time as NativeFunctionData));
+    prototype.set_field_slice("valueOf", to_value(get_time as NativeFunctionData));
+    prototype.set_field_slice("getFullYear", to_value(get_full_year as NativeFunctionData));
+    prototype.set_field_slice("getYear", to_value(get_year as NativeFunctionData));
+    prototype.set_field_slice("getMonth", to_value(get_month as NativeFunctionData));
+    prototype.set_field_slice("getDate", to_value(get_date as NativeFunctionData));
+    prototype.set_field_slice("getDay", to_value(get_day as NativeFunctionData));
+    prototype.set_field_slice("getHours", to_value(get_hours as NativeFunctionData));
+    prototype.set_field_slice("getMinutes", to_value(get_minutes as NativeFunctionData));
+    prototype.set_field_slice("getSeconds", to_value(get_seconds as NativeFunctionData));
+    prototype.set_field_slice("getMilliseconds", to_value(get_milliseconds as NativeFunctionData));
+    prototype.set_field_slice("getUTCFullYear", to_value(get_utc_full_year as NativeFunctionData));
+    prototype.set_field_slice("getUTCMonth", to_value(get_utc_month as NativeFunctionData));
+    prototype.set_field_slice("getUTCDate", to_value(get_utc_date as NativeFunctionData));
+    prototype.set_field_slice("getUTCDay", to_value(get_utc_day as NativeFunctionData));
+    prototype.set_field_slice("getUTCHours", to_value(get_utc_hours as NativeFunctionData));
+    prototype.set_field_slice("getUTCMinutes", to_value(get_utc_minutes as NativeFunctionData));
+    prototype.set_field_slice("getUTCSeconds", to_value(get_utc_seconds as NativeFunctionData));
+    prototype.set_field_slice("getUTCMilliseconds", to_value(get_utc_milliseconds as NativeFunctionData));
+    prototype.set_field_slice("getTimezoneOffset", to_value(get_timezone_offset as NativeFunctionData));
+    prototype.set_field_slice("setTime", to_value(set_time as NativeFunctionData));
+    prototype.set_field_slice("setFullYear", to_value(set_full_year as NativeFunctionData));
+    prototype.set_field_slice("setYear", to_value(set_year as NativeFunctionData));
+    prototype.set_field_slice("setMonth", to_value(set_month as NativeFunctionData));
+    prototype.set_field_slice("setDate", to_value(set_date as NativeFunctionData));
+    prototype.set_field_slice("setHours", to_value(set_hours as NativeFunctionData));
+    prototype.set_field_slice("setMinutes", to_value(set_minutes as NativeFunctionData));
+    prototype.set_field_slice("setSeconds", to_value(set_seconds as NativeFunctionData));
+    prototype.set_field_slice("setMilliseconds", to_value(set_milliseconds as NativeFunctionData));
+    prototype.set_field_slice("setUTCFullYear", to_value(set_utc_full_year as NativeFunctionData));
+    prototype.set_field_slice("setUTCMonth", to_value(set_utc_month as NativeFunctionData));
+    prototype.set_field_slice("setUTCDate", to_value(set_utc_date as NativeFunctionData));
+    prototype.set_field_slice("setUTCHours", to_value(set_utc_hours as NativeFunctionData));
+    prototype.set_field_slice("setUTCMinutes", to_value(set_utc_minutes as NativeFunctionData));
+    prototype.set_field_slice("setUTCSeconds", to_value(set_utc_seconds as NativeFunctionData));
+    prototype.set_field_slice("setUTCMilliseconds", to_value(set_utc_milliseconds as NativeFunctionData));
+    prototype.set_field_slice("toISOString", to_value(to_iso_string as NativeFunctionData));
+    prototype.set_field_slice("toJSON", to_value(to_json as NativeFunctionData));
+    prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    prototype.set_field_slice("toDateString", to_value(to_date_string_method as NativeFunctionData));
+    prototype.set_field_slice("toTimeString", to_value(to_time_string as NativeFunctionData));
+    prototype.set_field_slice("toUTCString", to_value(to_utc_string as NativeFunctionData));
+    prototype.set_field_slice("toLocaleString", to_value(to_locale_string as NativeFunctionData));
+    prototype.set_field_slice("toLocaleDateString", to_value(to_locale_date_string as NativeFunctionData));
+    prototype.set_field_slice("toLocaleTimeString", to_value(to_locale_time_string as NativeFunctionData));
+    // `toGMTString` is the same function as `toUTCString`
+    prototype.set_field_slice("toGMTString", prototype.get_field_slice("toUTCString"));
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::ToPrimitive),
+        to_value(to_primitive as NativeFunctionData),
+    );
+    prototype.set_field_slice("constructor", date.clone());
+    date.set_field_slice("now", to_value(now as NativeFunctionData));
+    date.set_field_slice("parse", to_value(parse as NativeFunctionData));
+    date.set_field_slice("UTC", to_value(utc_time as NativeFunctionData));
+    date.set_field_slice("length", to_value(7i32));
+    date.set_field_slice(PROTOTYPE, prototype);
+    date
+}
+
+/// Initialise the global object with the `Date` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Date", _create(global));
+}
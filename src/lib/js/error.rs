@@ -1,32 +1,299 @@
+/// The Error objects, which are thrown when something goes wrong
+/// https://tc39.es/ecma262/#sec-error-objects
 use gc::Gc;
+use std::fmt::{self, Display, Formatter};
+use crate::exec::Interpreter;
+use crate::js::array;
 use crate::js::function::NativeFunctionData;
-use crate::js::object::PROTOTYPE;
+use crate::js::iterator;
+use crate::js::object::{Property, INSTANCE_PROTOTYPE, PROTOTYPE};
 use crate::js::value::{to_value, ResultValue, Value, ValueData};
 
-/// Create a new error
-pub fn make_error(this: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    if args.len() >= 1 {
-        this.set_field_slice("message", to_value(args.get(0).unwrap().to_string()));
+/// The private field marking an object as an error, the [[ErrorData]] internal slot
+pub static ERROR_DATA: &str = "ErrorData";
+
+/// The private field holding the frames of the call stack when an error was made
+pub static STACK_FRAMES: &str = "StackFrames";
+
+/// The most frames an error's `stack` shows, like V8's default `Error.stackTraceLimit`
+const STACK_TRACE_LIMIT: usize = 10;
+
+/// A frame of the call stack, which is a call to a function or the top level of a script or module
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackFrame {
+    /// The name of the function, which is `None` at the top level or for an anonymous function
+    pub function_name: Option<String>,
+    /// The script or module the code being run comes from
+    pub file_name: String,
+    /// The line of the code being run in this frame, starting at 1
+    pub line_number: u64,
+    /// The column of the code being run in this frame, starting at 1
+    pub column_number: u64,
+    /// If the function was called with `new`
+    pub is_constructor: bool,
+}
+
+impl StackFrame {
+    /// Make a frame which starts at the beginning of a file
+    pub fn new(function_name: Option<String>, file_name: &str) -> StackFrame {
+        StackFrame {
+            function_name,
+            file_name: file_name.to_string(),
+            line_number: 1,
+            column_number: 1,
+            is_constructor: false,
+        }
+    }
+}
+
+impl Display for StackFrame {
+    /// Show the frame like a line of a V8 stack trace, without the leading `at`
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let location = format!("{}:{}:{}", self.file_name, self.line_number, self.column_number);
+        match self.function_name {
+            Some(ref name) if self.is_constructor => write!(f, "new {} ({})", name, location),
+            Some(ref name) => write!(f, "{} ({})", name, location),
+            None => write!(f, "{}", location),
+        }
     }
-    Ok(Gc::new(ValueData::Undefined))
 }
+
+/// The kinds of error the engine throws, which are the native error types
+/// https://tc39.es/ecma262/#sec-native-error-types-used-in-this-standard
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// A plain `Error`
+    Error,
+    /// Not used by the engine any more, but kept for compatibility
+    EvalError,
+    /// A value is outside the range of allowed values
+    RangeError,
+    /// A reference couldn't be resolved
+    ReferenceError,
+    /// Code couldn't be parsed
+    SyntaxError,
+    /// An operation was used on a value of the wrong type
+    TypeError,
+    /// A URI couldn't be encoded or decoded
+    URIError,
+}
+
+impl ErrorKind {
+    /// The name of the error type, which is also the name of its constructor on the global object
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Error => "Error",
+            ErrorKind::EvalError => "EvalError",
+            ErrorKind::RangeError => "RangeError",
+            ErrorKind::ReferenceError => "ReferenceError",
+            ErrorKind::SyntaxError => "SyntaxError",
+            ErrorKind::TypeError => "TypeError",
+            ErrorKind::URIError => "URIError",
+        }
+    }
+}
+
+/// The native error types which are subclasses of `Error`
+const NATIVE_ERRORS: [ErrorKind; 6] = [
+    ErrorKind::EvalError,
+    ErrorKind::RangeError,
+    ErrorKind::ReferenceError,
+    ErrorKind::SyntaxError,
+    ErrorKind::TypeError,
+    ErrorKind::URIError,
+];
+
+/// Returns true if the value is an error object
+pub fn is_error_object(value: &ValueData) -> bool {
+    value.is_object() && !value.get_private_field_slice(ERROR_DATA).is_undefined()
+}
+
+/// Make a property which is writable and configurable, but not enumerable, like those error objects are made with
+fn hidden_property(value: Value) -> Property {
+    let mut prop = Property::new(value);
+    prop.writable = true;
+    prop.configurable = true;
+    prop
+}
+
+/// Make an error object with a prototype and message
+fn create_error(prototype: Value, message: &str) -> Value {
+    let error = ValueData::new_obj_from_prototype(prototype);
+    error.set_private_field_slice(ERROR_DATA, to_value(true));
+    if !message.is_empty() {
+        error.set_prop_slice("message", hidden_property(to_value(message)));
+    }
+    error
+}
+
+/// Give an error the frames of the call stack, innermost first, and a `stack` property showing them
+pub fn capture_stack(error: &Value, frames: &[StackFrame]) {
+    let mut stack = error_message(error);
+    let records = ValueData::new_obj(None);
+    let frames = &frames[..frames.len().min(STACK_TRACE_LIMIT)];
+    for (index, frame) in frames.iter().enumerate() {
+        stack.push_str(&format!("\n    at {}", frame));
+        let record = ValueData::new_obj(None);
+        let name = match frame.function_name {
+            Some(ref name) => to_value(name.clone()),
+            None => Gc::new(ValueData::Null),
+        };
+        record.set_field_slice("functionName", name);
+        record.set_field_slice("fileName", to_value(frame.file_name.clone()));
+        record.set_field_slice("lineNumber", to_value(frame.line_number as f64));
+        record.set_field_slice("columnNumber", to_value(frame.column_number as f64));
+        record.set_field_slice("isConstructor", to_value(frame.is_constructor));
+        records.set_field_slice(&index.to_string(), record);
+    }
+    records.set_field_slice("length", to_value(frames.len() as i32));
+    error.set_private_field_slice(STACK_FRAMES, records);
+    error.set_prop_slice("stack", hidden_property(to_value(stack)));
+}
+
+/// Get the frames of the call stack when an error was made, innermost first.
+/// Values which aren't error objects have no frames.
+pub fn stack_frames(error: &Value) -> Vec<StackFrame> {
+    let records = error.get_private_field_slice(STACK_FRAMES);
+    if records.is_undefined() {
+        return Vec::new();
+    }
+    let length = records.get_field_slice("length").to_int();
+    (0..length)
+        .map(|index| {
+            let record = records.get_field_slice(&index.to_string());
+            let name = record.get_field_slice("functionName");
+            StackFrame {
+                function_name: if name.is_null() { None } else { Some(name.to_string()) },
+                file_name: record.get_field_slice("fileName").to_string(),
+                line_number: record.get_field_slice("lineNumber").to_num() as u64,
+                column_number: record.get_field_slice("columnNumber").to_num() as u64,
+                is_constructor: record.get_field_slice("isConstructor").is_true(),
+            }
+        })
+        .collect()
+}
+
+/// Make an error object of one of the native error types, for the engine to throw
+pub fn new_error(global: &Value, kind: ErrorKind, message: &str) -> Value {
+    let prototype = global.get_field_slice(kind.name()).get_field_slice(PROTOTYPE);
+    create_error(prototype, message)
+}
+
+/// Make an error object from the arguments given to an error constructor, starting at `message`
+/// https://tc39.es/ecma262/#sec-error-message
+fn construct_error(callee: &Value, args: &[Value], message: usize, interpreter: &mut Interpreter) -> ResultValue {
+    let error = create_error(callee.get_field_slice(PROTOTYPE), "");
+    match args.get(message) {
+        Some(message) if !message.is_undefined() => {
+            let message = interpreter.to_string(message)?;
+            error.set_prop_slice("message", hidden_property(to_value(message)));
+        }
+        _ => (),
+    }
+    // The options object can give the error a cause
+    // https://tc39.es/ecma262/#sec-installerrorcause
+    if let Some(options) = args.get(message + 1) {
+        if options.is_object() && options.has_field("cause") {
+            let cause = interpreter.get_field(options, "cause")?;
+            error.set_prop_slice("cause", hidden_property(cause));
+        }
+    }
+    capture_stack(&error, &interpreter.call_stack());
+    Ok(error)
+}
+
+/// Create a new error, whether it is called as a constructor or not
+/// The callee's `prototype` is used, so this is shared by `Error` and the native error types
+/// https://tc39.es/ecma262/#sec-error-message
+pub fn make_error(_: Value, callee: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    construct_error(&callee, &args, 0, interpreter)
+}
+
+/// Create an error which holds several other errors, like the ones which made `Promise.any` reject
+/// https://tc39.es/ecma262/#sec-aggregate-error
+pub fn make_aggregate_error(_: Value, callee: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let error = construct_error(&callee, &args, 1, interpreter)?;
+    let errors = match args.first() {
+        Some(errors) => iterator::iterable_to_list(errors, interpreter)?,
+        None => return Err(interpreter.type_error("undefined is not iterable")),
+    };
+    let errors = array::new_array(&interpreter.get_global_object(), errors);
+    error.set_prop_slice("errors", hidden_property(errors));
+    Ok(error)
+}
+
 /// Get the string representation of the error
-pub fn to_string(this: Value, _: Value, _: Vec<Value>) -> ResultValue {
-    let name = this.get_field_slice("name");
-    let message = this.get_field_slice("message");
-    Ok(to_value(format!("{}: {}", name, message).to_string()))
+/// https://tc39.es/ecma262/#sec-error.prototype.tostring
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() && !this.is_function() {
+        return Err(interpreter.type_error("Error.prototype.toString called on non-object"));
+    }
+    let name = interpreter.get_field(&this, "name")?;
+    let name = if name.is_undefined() { "Error".to_string() } else { interpreter.to_string(&name)? };
+    let message = interpreter.get_field(&this, "message")?;
+    let message = if message.is_undefined() { String::new() } else { interpreter.to_string(&message)? };
+    Ok(to_value(join_name_and_message(name, message)))
+}
+
+/// Join the name and message of an error like `TypeError: message`, leaving out the colon if either is empty
+fn join_name_and_message(name: String, message: String) -> String {
+    if name.is_empty() {
+        message
+    } else if message.is_empty() {
+        name
+    } else {
+        format!("{}: {}", name, message)
+    }
 }
+
+/// Get the name and message of an error for printing it, reading its properties without running any code
+pub fn error_message(error: &ValueData) -> String {
+    let name = error.get_field_slice("name");
+    let message = error.get_field_slice("message");
+    let name = if name.is_undefined() { "Error".to_string() } else { name.to_string() };
+    let message = if message.is_undefined() { String::new() } else { message.to_string() };
+    join_name_and_message(name, message)
+}
+
+/// Make the prototype of an error type, with its `name` and an empty `message`
+fn create_prototype(prototype: Value, name: &str, constructor: &Value) -> Value {
+    let prototype = ValueData::new_obj_from_prototype(prototype);
+    prototype.set_field_slice("name", to_value(name));
+    prototype.set_field_slice("message", to_value(""));
+    prototype.set_field_slice("constructor", constructor.clone());
+    prototype
+}
+
 /// Create a new `Error` object
 pub fn _create(global: &Value) -> Value {
-    let prototype = ValueData::new_obj(Some(global));
-    prototype.set_field_slice("message", to_value(""));
-    prototype.set_field_slice("name", to_value("Error"));
-    prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    let object_prototype = global.get_field_slice("Object").get_field_slice(PROTOTYPE);
     let error = to_value(make_error as NativeFunctionData);
+    let prototype = create_prototype(object_prototype, "Error", &error);
+    prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    error.set_field_slice("length", to_value(1i32));
     error.set_field_slice(PROTOTYPE, prototype);
     error
 }
-/// Initialise the global object with the `Error` object
+
+/// Create one of the native error types, which inherit from `Error`
+/// https://tc39.es/ecma262/#sec-nativeerror-object-structure
+fn create_native_error(error: &Value, name: &str, function: NativeFunctionData) -> Value {
+    let native_error = to_value(function);
+    let prototype = create_prototype(error.get_field_slice(PROTOTYPE), name, &native_error);
+    native_error.set_field_slice(INSTANCE_PROTOTYPE, error.clone());
+    native_error.set_field_slice("length", to_value(1i32));
+    native_error.set_field_slice(PROTOTYPE, prototype);
+    native_error
+}
+
+/// Initialise the global object with `Error`, the native error types and `AggregateError`
 pub fn init(global: &Value) {
-    global.set_field_slice("Error", _create(global));
-}
\ No newline at end of file
+    let error = _create(global);
+    for kind in NATIVE_ERRORS.iter() {
+        global.set_field_slice(kind.name(), create_native_error(&error, kind.name(), make_error));
+    }
+    let aggregate_error = create_native_error(&error, "AggregateError", make_aggregate_error);
+    aggregate_error.set_field_slice("length", to_value(2i32));
+    global.set_field_slice("AggregateError", aggregate_error);
+    global.set_field_slice("Error", error);
+}
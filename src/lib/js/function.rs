@@ -1,10 +1,12 @@
-use gc::Gc;
-use std::collections::HashMap;
+use gc::{Gc, GcCell};
+use std::fmt::{self, Debug, Formatter};
+use crate::environment::lexical_environment::Environment;
 use crate::syntax::ast::expr::Expr;
-use crate::js::object::{ObjectData, Property};
+use crate::exec::Interpreter;
+use crate::js::object::{ObjectData, Property, PropertyKey, INSTANCE_PROTOTYPE, PROTOTYPE};
 use crate::js::value::{to_value, ResultValue, Value, ValueData};
 
-pub type NativeFunctionData = fn(Value, Value, Vec<Value>) -> ResultValue;
+pub type NativeFunctionData = fn(Value, Value, Vec<Value>, &mut Interpreter) -> ResultValue;
 /// A Javascript function
 /// A member of the Object type that may be invoked as a subroutine
 /// https://tc39.github.io/ecma262/#sec-terms-and-definitions-function
@@ -17,61 +19,354 @@ pub enum Function {
     NativeFunc(NativeFunction),
     /// A regular javascript function
     RegularFunc(RegularFunction),
+    /// A function made by `Function.prototype.bind`
+    BoundFunc(BoundFunction),
+}
+
+impl Function {
+    /// The fields associated with the function
+    pub fn object(&self) -> &ObjectData {
+        match *self {
+            Function::NativeFunc(ref func) => &func.object,
+            Function::RegularFunc(ref func) => &func.object,
+            Function::BoundFunc(ref func) => &func.object,
+        }
+    }
+
+    /// The fields associated with the function, to change them
+    pub fn object_mut(&mut self) -> &mut ObjectData {
+        match *self {
+            Function::NativeFunc(ref mut func) => &mut func.object,
+            Function::RegularFunc(ref mut func) => &mut func.object,
+            Function::BoundFunc(ref mut func) => &mut func.object,
+        }
+    }
+
+    /// The internal slots of the function, which scripts can't see
+    pub fn private(&self) -> &ObjectData {
+        match *self {
+            Function::NativeFunc(ref func) => &func.private,
+            Function::RegularFunc(ref func) => &func.private,
+            Function::BoundFunc(ref func) => &func.private,
+        }
+    }
+
+    /// The internal slots of the function, to change them
+    pub fn private_mut(&mut self) -> &mut ObjectData {
+        match *self {
+            Function::NativeFunc(ref mut func) => &mut func.private,
+            Function::RegularFunc(ref mut func) => &mut func.private,
+            Function::BoundFunc(ref mut func) => &mut func.private,
+        }
+    }
 }
 
 /// Represents a regular javascript function in memory
-#[derive(Trace, Finalize, Debug, Clone)]
+#[derive(Trace, Finalize, Clone)]
 pub struct RegularFunction {
     /// The fields associated with the function
     pub object: ObjectData,
+    /// The internal slots of the function, which scripts can't see
+    pub private: ObjectData,
     /// This function's expression
     pub expr: Expr,
     /// The argument names of the function
     pub args: Vec<String>,
+    /// The environment the function was created in, which its body runs inside of
+    /// https://tc39.es/ecma262/#sec-ecmascript-function-objects
+    pub environment: Option<Environment>,
+    /// If the function was declared with `async`, calling it returns a promise
+    pub is_async: bool,
+    /// Arrow functions don't have their own `this`, they use the one from where they were created
+    pub is_arrow: bool,
+    /// The source text of the function, which `toString` returns
+    pub source: String,
+    /// The script or module the function was written in, shown in stack traces
+    pub file_name: String,
 }
 
 impl RegularFunction {
     /// Make a new regular function
-    pub fn new(expr: Expr, args: Vec<String>) -> RegularFunction {
-        let mut obj = HashMap::new();
+    pub fn new(expr: Expr, args: Vec<String>, environment: Option<Environment>) -> RegularFunction {
+        let mut obj = ObjectData::new();
         obj.insert(
-            "arguments".to_string(),
+            "arguments".into(),
             Property::new(Gc::new(ValueData::Integer(args.len() as i32))),
         );
         RegularFunction {
             object: obj,
+            private: ObjectData::new(),
             expr: expr,
             args: args,
+            environment: environment,
+            is_async: false,
+            is_arrow: false,
+            source: String::new(),
+            file_name: String::new(),
         }
     }
 }
 
+impl Debug for RegularFunction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("RegularFunction")
+            .field("object", &self.object)
+            .field("expr", &self.expr)
+            .field("args", &self.args)
+            .field("is_async", &self.is_async)
+            .field("is_arrow", &self.is_arrow)
+            .finish()
+    }
+}
+
 #[derive(Trace, Finalize, Debug, Clone)]
 /// Represents a native javascript function in memory
 pub struct NativeFunction {
     /// The fields associated with the function
     pub object: ObjectData,
+    /// The internal slots of the function, which scripts can't see
+    pub private: ObjectData,
     /// The callable function data
+    #[unsafe_ignore_trace]
     pub data: NativeFunctionData,
 }
 impl NativeFunction {
     /// Make a new native function with the given function data
     pub fn new(data: NativeFunctionData) -> NativeFunction {
-        let obj = HashMap::new();
+        let obj = ObjectData::new();
         NativeFunction {
             object: obj,
+            private: ObjectData::new(),
             data: data,
         }
     }
 }
 
+/// A function which calls another function with a fixed `this` value and leading arguments
+/// https://tc39.es/ecma262/#sec-bound-function-exotic-objects
+#[derive(Trace, Finalize, Debug, Clone)]
+pub struct BoundFunction {
+    /// The fields associated with the function
+    pub object: ObjectData,
+    /// The internal slots of the function, which scripts can't see
+    pub private: ObjectData,
+    /// The function which is called, the [[BoundTargetFunction]] internal slot
+    pub target: Value,
+    /// The `this` value the target is called with, the [[BoundThis]] internal slot
+    pub this: Value,
+    /// The arguments passed before the ones the bound function is called with, the [[BoundArguments]] internal slot
+    pub args: Vec<Value>,
+}
+
+/// Make a property holding the `name` or `length` of a function, which is read-only but can be redefined
+fn function_property(value: Value) -> Property {
+    let mut prop = Property::new(value);
+    prop.configurable = true;
+    prop
+}
+
+/// Set the `name` of a function
+/// https://tc39.es/ecma262/#sec-setfunctionname
+pub fn set_function_name(func: &Value, name: &str) {
+    func.set_prop_slice("name", function_property(to_value(name)));
+}
+
+/// Set the `length` of a function, the number of arguments it expects
+/// https://tc39.es/ecma262/#sec-setfunctionlength
+pub fn set_function_length(func: &Value, length: usize) {
+    func.set_prop_slice("length", function_property(to_value(length as i32)));
+}
+
+/// Get the `this` value of a function call, making sure it is a function
+fn this_function(this: &Value, method: &str, interpreter: &Interpreter) -> ResultValue {
+    if this.is_function() {
+        Ok(this.clone())
+    } else {
+        Err(interpreter.type_error(format!(
+            "Function.prototype.{} called on {}, which is not a function",
+            method, this
+        )))
+    }
+}
+
+/// The `Function.prototype` object is itself a function, which accepts any arguments and returns undefined
+fn empty(_: Value, _: Value, _: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Call the function with a `this` value and arguments
+/// https://tc39.es/ecma262/#sec-function.prototype.call
+pub fn call(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let func = this_function(&this, "call", interpreter)?;
+    let mut args = args.into_iter();
+    let this_arg = args.next().unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    interpreter.call(&func, &this_arg, args.collect())
+}
+
+/// Make a list of values from an array-like object
+/// https://tc39.es/ecma262/#sec-createlistfromarraylike
+pub fn create_list_from_array_like(obj: &Value, interpreter: &mut Interpreter) -> Result<Vec<Value>, Value> {
+    if !obj.is_object() && !obj.is_function() {
+        return Err(interpreter.type_error("CreateListFromArrayLike called on non-object"));
+    }
+    let length = interpreter.get_field(obj, "length")?;
+    let length = interpreter.to_number(&length)?;
+    let length = if length.is_nan() || length <= 0.0 { 0.0 } else { length.trunc() };
+    let mut list = Vec::new();
+    let mut index = 0.0;
+    while index < length {
+        list.push(interpreter.get_field(obj, index.to_string())?);
+        index += 1.0;
+    }
+    Ok(list)
+}
+
+/// Call the function with a `this` value, and the arguments in an array
+/// https://tc39.es/ecma262/#sec-function.prototype.apply
+pub fn apply(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let func = this_function(&this, "apply", interpreter)?;
+    let this_arg = args.first().cloned().unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let arg_list = match args.get(1) {
+        Some(list) if !list.is_null_or_undefined() => create_list_from_array_like(list, interpreter)?,
+        _ => Vec::new(),
+    };
+    interpreter.call(&func, &this_arg, arg_list)
+}
+
+/// Make a function which calls this one with a fixed `this` value, and the rest of the arguments before any it is called with
+/// https://tc39.es/ecma262/#sec-function.prototype.bind
+pub fn bind(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = this_function(&this, "bind", interpreter)?;
+    let mut args = args.into_iter();
+    let bound_this = args.next().unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    let bound_args: Vec<Value> = args.collect();
+    let length = match target.get_own_prop("length") {
+        Some(_) => {
+            let length = interpreter.get_field(&target, "length")?;
+            match *length {
+                ValueData::Number(_) | ValueData::Integer(_) => {
+                    let length = length.to_num();
+                    if length == f64::INFINITY {
+                        length
+                    } else if length.is_nan() || length <= 0.0 {
+                        0.0
+                    } else {
+                        (length.trunc() - bound_args.len() as f64).max(0.0)
+                    }
+                }
+                _ => 0.0,
+            }
+        }
+        None => 0.0,
+    };
+    let name = interpreter.get_field(&target, "name")?;
+    let name = if name.is_string() { name.to_string() } else { String::new() };
+    let mut object = ObjectData::new();
+    object.insert(INSTANCE_PROTOTYPE.into(), Property::data(target.get_field_slice(INSTANCE_PROTOTYPE)));
+    let bound = Gc::new(ValueData::Function(GcCell::new(Function::BoundFunc(BoundFunction {
+        object,
+        private: ObjectData::new(),
+        target,
+        this: bound_this,
+        args: bound_args,
+    }))));
+    bound.set_prop_slice("length", function_property(to_value(length)));
+    set_function_name(&bound, &format!("bound {}", name));
+    Ok(bound)
+}
+
+/// Get the source text of a function, or a placeholder for functions which aren't written in Javascript
+/// https://tc39.es/ecma262/#sec-function.prototype.tostring
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let func = this_function(&this, "toString", interpreter)?;
+    let source = match *func {
+        ValueData::Function(ref inner) => match *inner.borrow() {
+            Function::RegularFunc(ref data) if !data.source.is_empty() => Some(data.source.clone()),
+            Function::RegularFunc(_) => Some(func.to_string()),
+            _ => None,
+        },
+        _ => None,
+    };
+    Ok(to_value(source.unwrap_or_else(|| {
+        let name = func.get_field_slice("name");
+        let name = if name.is_string() { name.to_string() } else { String::new() };
+        format!("function {}() {{ [native code] }}", name)
+    })))
+}
+
+/// Make a function from strings, where the last argument is the body and any others are the parameters
+/// https://tc39.es/ecma262/#sec-function-p1-p2-pn-body
+pub fn make_function(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut strings = Vec::with_capacity(args.len());
+    for arg in args.iter() {
+        strings.push(interpreter.to_string(arg)?);
+    }
+    let body = strings.pop().unwrap_or_default();
+    interpreter.create_dynamic_function(&strings.join(","), &body)
+}
+
 /// Create a new `Function` object
-pub fn _create() -> Value {
-    let function: ObjectData = HashMap::new();
-    to_value(function)
+pub fn _create(global: &Value) -> Value {
+    let function = to_value(make_function as NativeFunctionData);
+    function.set_field_slice("length", to_value(1i32));
+    let prototype = to_value(empty as NativeFunctionData);
+    set_function_name(&prototype, "");
+    prototype.set_field_slice(INSTANCE_PROTOTYPE, global.get_field_slice("Object").get_field_slice(PROTOTYPE));
+    prototype.set_field_slice("call", to_value(call as NativeFunctionData));
+    prototype.set_field_slice("apply", to_value(apply as NativeFunctionData));
+    prototype.set_field_slice("bind", to_value(bind as NativeFunctionData));
+    prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    prototype.set_field_slice("constructor", function.clone());
+    function.set_field_slice(PROTOTYPE, prototype);
+    function
 }
+
 /// Initialise the global object with the `Function` object
 pub fn init(global: &Value) {
     let global_ptr = global;
-    global_ptr.set_field_slice("Function", _create());
-}
\ No newline at end of file
+    global_ptr.set_field_slice("Function", _create(global));
+}
+
+/// The internal slot of a built-in function holding the global object of the realm it belongs to
+pub static FUNCTION_REALM: &str = "Realm";
+
+/// Give every built-in function reachable from the global object `Function.prototype` as its prototype,
+/// the name of the property it was found on if it doesn't have a name already, and the realm it belongs to
+pub fn init_builtin_functions(global: &Value) {
+    let function_prototype = global.get_field_slice("Function").get_field_slice(PROTOTYPE);
+    let mut seen: Vec<Value> = Vec::new();
+    let mut pending = vec![global.clone()];
+    while let Some(obj) = pending.pop() {
+        if seen.iter().any(|other| Gc::ptr_eq(other, &obj)) {
+            continue;
+        }
+        for key in obj.own_keys() {
+            let value = match obj.get_own_prop(key.clone()) {
+                Some(prop) => prop.value.clone(),
+                None => continue,
+            };
+            if value.is_function() {
+                if value.get_own_prop("name").is_none() {
+                    let name = match key {
+                        PropertyKey::String(ref name) => name.clone(),
+                        PropertyKey::Symbol(ref symbol) => format!("[{}]", symbol.description().unwrap_or("")),
+                    };
+                    set_function_name(&value, &name);
+                }
+                if value.get_field_slice(INSTANCE_PROTOTYPE).is_undefined() && !Gc::ptr_eq(&value, &function_prototype) {
+                    value.set_field_slice(INSTANCE_PROTOTYPE, function_prototype.clone());
+                }
+                value.set_private_field_slice(FUNCTION_REALM, global.clone());
+            }
+            if value.is_object() || value.is_function() {
+                pending.push(value);
+            }
+        }
+        // Some builtins, like `%TypedArray%`, are only reachable through the prototype chain
+        let proto = obj.get_field_slice(INSTANCE_PROTOTYPE);
+        if proto.is_object() || proto.is_function() {
+            pending.push(proto);
+        }
+        seen.push(obj);
+    }
+}
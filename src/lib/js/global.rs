@@ -0,0 +1,222 @@
+/// The value properties and function properties of the global object
+/// https://tc39.es/ecma262/#sec-global-object
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::conversion::number_arg;
+use crate::js::error::ErrorKind;
+use crate::js::function::NativeFunctionData;
+use crate::js::object::Property;
+use crate::js::string::is_trimmable;
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+
+/// Characters which are never escaped by `encodeURI` or `encodeURIComponent`
+/// https://tc39.es/ecma262/#prod-uriUnescaped
+const URI_UNESCAPED: &str = "-_.!~*'()";
+
+/// Characters with a special meaning in URIs, which `encodeURI` and `decodeURI` leave alone
+/// https://tc39.es/ecma262/#prod-uriReserved
+const URI_RESERVED: &str = ";/?:@&=+$,#";
+
+/// Get the argument at an index as a string, where a missing argument is `"undefined"`
+fn string_arg(args: &[Value], index: usize, interpreter: &mut Interpreter) -> Result<String, Value> {
+    match args.get(index) {
+        Some(value) => interpreter.to_string(value),
+        None => Ok("undefined".to_string()),
+    }
+}
+
+/// Convert a number to a 32-bit integer, wrapping around like `x | 0`
+/// https://tc39.es/ecma262/#sec-toint32
+fn to_int32(num: f64) -> i32 {
+    if !num.is_finite() {
+        return 0;
+    }
+    num.trunc().rem_euclid(4_294_967_296.0) as u32 as i32
+}
+
+/// Parse the digits at the start of a string as an integer in a radix, which is 10 by default,
+/// or 16 if the string starts with `0x`
+/// https://tc39.es/ecma262/#sec-parseint-string-radix
+pub fn parse_int(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = string_arg(&args, 0, interpreter)?;
+    let radix = match args.get(1) {
+        Some(value) => to_int32(interpreter.to_number(value)?),
+        None => 0,
+    };
+    let mut string = string.trim_start_matches(is_trimmable);
+    let sign = if string.starts_with('-') { -1.0 } else { 1.0 };
+    if string.starts_with('-') || string.starts_with('+') {
+        string = &string[1..];
+    }
+    // A radix of 0 means 10, unless the string has a hexadecimal prefix
+    let (mut radix, strip_prefix) = match radix {
+        0 => (10, true),
+        2..=36 => (radix as u32, radix == 16),
+        _ => return Ok(to_value(f64::NAN)),
+    };
+    if strip_prefix && (string.starts_with("0x") || string.starts_with("0X")) {
+        string = &string[2..];
+        radix = 16;
+    }
+    let length = string.chars().take_while(|c| c.is_digit(radix)).count();
+    if length == 0 {
+        return Ok(to_value(f64::NAN));
+    }
+    let digits = &string[..length];
+    let value = if radix == 10 {
+        // Rust parses decimal strings with correct rounding, even past 20 digits
+        digits.parse().unwrap_or(f64::NAN)
+    } else {
+        digits
+            .chars()
+            .fold(0.0, |value, c| value * f64::from(radix) + f64::from(c.to_digit(radix).unwrap()))
+    };
+    Ok(to_value(sign * value))
+}
+
+/// Returns true if the argument is `NaN` after converting it to a number
+/// https://tc39.es/ecma262/#sec-isnan-number
+pub fn is_nan(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(&args, 0, interpreter)?.is_nan()))
+}
+
+/// Returns true if the argument is a finite number after converting it to a number
+/// https://tc39.es/ecma262/#sec-isfinite-number
+pub fn is_finite(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_arg(&args, 0, interpreter)?.is_finite()))
+}
+
+/// Escape every character of a string outside the unescaped set as `%XX` UTF-8 bytes
+/// https://tc39.es/ecma262/#sec-encode
+fn encode(string: &str, unescaped: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    for c in string.chars() {
+        if c.is_ascii_alphanumeric() || URI_UNESCAPED.contains(c) || unescaped.contains(c) {
+            result.push(c);
+        } else {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                result.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    result
+}
+
+/// Read the byte written as `%XX` at an index
+fn escaped_byte(bytes: &[u8], index: usize) -> Option<u8> {
+    if bytes.get(index) != Some(&b'%') {
+        return None;
+    }
+    let hex = std::str::from_utf8(bytes.get(index + 1..index + 3)?).ok()?;
+    u8::from_str_radix(hex, 16).ok()
+}
+
+/// Replace the `%XX` escapes in a string with the UTF-8 characters they encode, apart from
+/// characters in the reserved set which stay escaped
+/// https://tc39.es/ecma262/#sec-decode
+fn decode(string: &str, reserved: &str, interpreter: &Interpreter) -> Result<String, Value> {
+    let malformed = || interpreter.new_error(ErrorKind::URIError, "URI malformed");
+    let bytes = string.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] != b'%' {
+            result.push(bytes[index]);
+            index += 1;
+            continue;
+        }
+        let lead = escaped_byte(bytes, index).ok_or_else(malformed)?;
+        if lead < 0x80 {
+            if reserved.contains(char::from(lead)) {
+                result.extend_from_slice(&bytes[index..index + 3]);
+            } else {
+                result.push(lead);
+            }
+            index += 3;
+            continue;
+        }
+        // The number of leading 1 bits is the length of the UTF-8 sequence
+        let length = lead.leading_ones() as usize;
+        if !(2..=4).contains(&length) {
+            return Err(malformed());
+        }
+        let mut sequence = vec![lead];
+        for continuation in 1..length {
+            let byte = escaped_byte(bytes, index + continuation * 3).ok_or_else(malformed)?;
+            sequence.push(byte);
+        }
+        // Overlong encodings, surrogates and code points past U+10FFFF are not valid UTF-8
+        let decoded = std::str::from_utf8(&sequence).map_err(|_| malformed())?;
+        result.extend_from_slice(decoded.as_bytes());
+        index += length * 3;
+    }
+    Ok(String::from_utf8(result).expect("decoded URI is UTF-8"))
+}
+
+/// Escape a URI, leaving the characters which separate its parts
+/// https://tc39.es/ecma262/#sec-encodeuri-uri
+pub fn encode_uri(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(encode(&string_arg(&args, 0, interpreter)?, URI_RESERVED)))
+}
+
+/// Escape a part of a URI, including characters which separate the parts
+/// https://tc39.es/ecma262/#sec-encodeuricomponent-uricomponent
+pub fn encode_uri_component(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(encode(&string_arg(&args, 0, interpreter)?, "")))
+}
+
+/// Unescape a URI, except for escapes of the characters which separate its parts
+/// https://tc39.es/ecma262/#sec-decodeuri-encodeduri
+pub fn decode_uri(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(decode(&string_arg(&args, 0, interpreter)?, URI_RESERVED, interpreter)?))
+}
+
+/// Unescape a part of a URI
+/// https://tc39.es/ecma262/#sec-decodeuricomponent-encodeduricomponent
+pub fn decode_uri_component(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(decode(&string_arg(&args, 0, interpreter)?, "", interpreter)?))
+}
+
+/// Run a string as a script in the global scope, or return any other value unchanged.
+/// This is an indirect eval, as calls to `eval` by name are handled by the interpreter
+/// https://tc39.es/ecma262/#sec-eval-x
+pub fn eval(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let source = args.first().cloned().unwrap_or_else(|| Gc::new(ValueData::Undefined));
+    interpreter.perform_eval(&source, false)
+}
+
+/// Initialise the global object with its value properties and functions.
+/// This runs after `Number` is set up, so `parseFloat` and `parseInt` are shared with it.
+pub fn init(global: &Value) {
+    // `NaN`, `Infinity` and `undefined` can't be changed or deleted
+    let constant = |value: Value| Property {
+        configurable: false,
+        enumerable: false,
+        writable: false,
+        value,
+        get: Gc::new(ValueData::Undefined),
+        set: Gc::new(ValueData::Undefined),
+    };
+    global.set_prop_slice("NaN", constant(to_value(f64::NAN)));
+    global.set_prop_slice("Infinity", constant(to_value(f64::INFINITY)));
+    global.set_prop_slice("undefined", constant(Gc::new(ValueData::Undefined)));
+    global.set_field_slice("globalThis", global.clone());
+
+    let number = global.get_field_slice("Number");
+    let parse_int = to_value(parse_int as NativeFunctionData);
+    parse_int.set_field_slice("length", to_value(2i32));
+    number.set_field_slice("parseInt", parse_int.clone());
+    global.set_field_slice("parseInt", parse_int);
+    global.set_field_slice("parseFloat", number.get_field_slice("parseFloat"));
+    let eval = to_value(eval as NativeFunctionData);
+    eval.set_field_slice("length", to_value(1i32));
+    eval.set_private_field_slice("IsEval", to_value(true));
+    global.set_field_slice("eval", eval);
+    global.set_field_slice("isNaN", to_value(is_nan as NativeFunctionData));
+    global.set_field_slice("isFinite", to_value(is_finite as NativeFunctionData));
+    global.set_field_slice("encodeURI", to_value(encode_uri as NativeFunctionData));
+    global.set_field_slice("encodeURIComponent", to_value(encode_uri_component as NativeFunctionData));
+    global.set_field_slice("decodeURI", to_value(decode_uri as NativeFunctionData));
+    global.set_field_slice("decodeURIComponent", to_value(decode_uri_component as NativeFunctionData));
+}
@@ -0,0 +1,157 @@
+/// Iterators and the iteration protocol
+/// https://tc39.es/ecma262/#sec-iteration
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::array;
+use crate::js::function::NativeFunctionData;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+
+/// The private field of an iterator method, holding the prototype of the iterators it creates
+pub static ARRAY_ITERATOR_PROTOTYPE: &str = "ArrayIteratorPrototype";
+
+/// The private field of an iterator method, holding the kind of iteration its iterators do
+pub static ITERATION_KIND: &str = "IterationKind";
+
+/// Make an object like `{ value, done }`, as returned by an iterator's `next` method
+/// https://tc39.es/ecma262/#sec-createiterresultobject
+pub fn create_iter_result_object(global: &Value, value: Value, done: bool) -> Value {
+    let result = ValueData::new_obj(Some(global));
+    result.set_field_slice("value", value);
+    result.set_field_slice("done", to_value(done));
+    result
+}
+
+/// Get an iterator from a value by calling its `[Symbol.iterator]` method
+/// https://tc39.es/ecma262/#sec-getiterator
+pub fn get_iterator(iterable: &Value, interpreter: &mut Interpreter) -> ResultValue {
+    let method = interpreter.get_field(iterable, Symbol::well_known(WellKnownSymbol::Iterator))?;
+    if !method.is_function() {
+        return Err(interpreter.type_error("value is not iterable"));
+    }
+    let iterator = interpreter.call(&method, iterable, Vec::new())?;
+    if !iterator.is_object() {
+        return Err(interpreter.type_error("iterator is not an object"));
+    }
+    Ok(iterator)
+}
+
+/// Call the iterator's `next` method, returning the next value or `None` once it is done
+/// https://tc39.es/ecma262/#sec-iteratorstep
+pub fn iterator_step(iterator: &Value, interpreter: &mut Interpreter) -> Result<Option<Value>, Value> {
+    let next = interpreter.get_field(iterator, "next")?;
+    let result = interpreter.call(&next, iterator, Vec::new())?;
+    if !result.is_object() {
+        return Err(interpreter.type_error("iterator result is not an object"));
+    }
+    if interpreter.get_field(&result, "done")?.is_true() {
+        return Ok(None);
+    }
+    Ok(Some(interpreter.get_field(&result, "value")?))
+}
+
+/// Collect every value produced by an iterable
+/// https://tc39.es/ecma262/#sec-iterabletolist
+pub fn iterable_to_list(iterable: &Value, interpreter: &mut Interpreter) -> Result<Vec<Value>, Value> {
+    let iterator = get_iterator(iterable, interpreter)?;
+    let mut values = Vec::new();
+    while let Some(value) = iterator_step(&iterator, interpreter)? {
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Iterators are iterable, returning themselves
+pub fn return_this(this: Value, _: Value, _: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(this)
+}
+
+/// What an array iterator produces for each index
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArrayIterationKind {
+    /// The index
+    Keys,
+    /// The value at the index
+    Values,
+    /// An array of `[index, value]`
+    Entries,
+}
+
+impl ArrayIterationKind {
+    /// The name the kind is stored under
+    pub fn name(self) -> &'static str {
+        match self {
+            ArrayIterationKind::Keys => "keys",
+            ArrayIterationKind::Values => "values",
+            ArrayIterationKind::Entries => "entries",
+        }
+    }
+
+    /// Get the kind from its name, defaulting to values
+    pub fn from_name(name: &str) -> ArrayIterationKind {
+        match name {
+            "keys" => ArrayIterationKind::Keys,
+            "entries" => ArrayIterationKind::Entries,
+            _ => ArrayIterationKind::Values,
+        }
+    }
+}
+
+/// Create a new iterator over an array-like object.
+/// The callee carries the shared prototype of array iterators, and optionally the kind of iteration.
+pub fn create_array_iterator(this: Value, f: Value, _: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    let iterator = ValueData::new_obj_from_prototype(f.get_private_field_slice(ARRAY_ITERATOR_PROTOTYPE));
+    let kind = f.get_private_field_slice(ITERATION_KIND);
+    let kind = if kind.is_undefined() {
+        ArrayIterationKind::Values
+    } else {
+        ArrayIterationKind::from_name(&kind.to_string())
+    };
+    iterator.set_private_field_slice("IteratedObject", this);
+    iterator.set_private_field_slice("NextIndex", to_value(0i32));
+    iterator.set_private_field_slice("ArrayIterationKind", to_value(kind.name()));
+    Ok(iterator)
+}
+
+/// Get the next value of an array iterator
+/// https://tc39.es/ecma262/#sec-%arrayiteratorprototype%.next
+pub fn array_iterator_next(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    let array = this.get_private_field_slice("IteratedObject");
+    if array.is_undefined() {
+        return Ok(create_iter_result_object(&global, Gc::new(ValueData::Undefined), true));
+    }
+    let index = this.get_private_field_slice("NextIndex").to_int();
+    let length = interpreter.get_field(&array, "length")?.to_int();
+    if index >= length {
+        // An exhausted iterator stays exhausted, even if the array grows
+        this.set_private_field_slice("IteratedObject", Gc::new(ValueData::Undefined));
+        return Ok(create_iter_result_object(&global, Gc::new(ValueData::Undefined), true));
+    }
+    this.set_private_field_slice("NextIndex", to_value(index + 1));
+    let kind = ArrayIterationKind::from_name(&this.get_private_field_slice("ArrayIterationKind").to_string());
+    let value = match kind {
+        ArrayIterationKind::Keys => to_value(index),
+        ArrayIterationKind::Values => interpreter.get_field(&array, index.to_string())?,
+        ArrayIterationKind::Entries => {
+            let value = interpreter.get_field(&array, index.to_string())?;
+            array::new_array(&global, vec![to_value(index), value])
+        }
+    };
+    Ok(create_iter_result_object(&global, value, false))
+}
+
+/// Create the `%ArrayIteratorPrototype%` object
+pub fn create_array_iterator_prototype(global: &Value) -> Value {
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("next", to_value(array_iterator_next as NativeFunctionData));
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::Iterator),
+        to_value(return_this as NativeFunctionData),
+    );
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::ToStringTag),
+        to_value("Array Iterator"),
+    );
+    prototype
+}
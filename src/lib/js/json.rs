@@ -1,22 +1,489 @@
 /// The JSON Object
 /// https://tc39.github.io/ecma262/#sec-json-object
-use serde_json::{self, to_string_pretty, Value as JSONValue};
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::array;
 use crate::js::function::NativeFunctionData;
-use crate::js::value::{to_value, ResultValue, Value, ValueData};
+use crate::js::object::{PropertyDescriptor, PropertyKey};
+use crate::js::string::{self, JsString};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
 
-/// Parse a JSON string into a Javascript object
+/// A parser for JSON text, which makes Javascript values directly so objects keep the order of their keys
+/// https://tc39.es/ecma262/#sec-json.parse
+struct JsonParser {
+    chars: Vec<char>,
+    pos: usize,
+    global: Value,
+}
+
+impl JsonParser {
+    /// Skip the whitespace JSON allows between tokens
+    fn skip_whitespace(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.chars.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    /// The error for the character at the current position, or for the end of the text
+    fn unexpected(&self) -> String {
+        match self.chars.get(self.pos) {
+            Some(ch) => format!("Unexpected token {} in JSON at position {}", ch, self.pos),
+            None => "Unexpected end of JSON input".to_string(),
+        }
+    }
+
+    /// Consume the character if it is next, or fail
+    fn expect(&mut self, ch: char) -> Result<(), String> {
+        if self.chars.get(self.pos) != Some(&ch) {
+            return Err(self.unexpected());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Consume a literal like `true`, or fail at the first character which doesn't match
+    fn expect_word(&mut self, word: &str) -> Result<(), String> {
+        for ch in word.chars() {
+            self.expect(ch)?;
+        }
+        Ok(())
+    }
+
+    /// Parse a whole JSON text, which is a single value
+    fn parse_text(&mut self) -> Result<Value, String> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.pos < self.chars.len() {
+            return Err(self.unexpected());
+        }
+        Ok(value)
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => Ok(to_value(self.parse_string()?)),
+            Some('t') => self.expect_word("true").map(|_| to_value(true)),
+            Some('f') => self.expect_word("false").map(|_| to_value(false)),
+            Some('n') => self.expect_word("null").map(|_| Gc::new(ValueData::Null)),
+            Some('-') | Some('0'..='9') => self.parse_number(),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let obj = ValueData::new_obj(Some(&self.global));
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&'}') {
+            self.pos += 1;
+            return Ok(obj);
+        }
+        loop {
+            self.skip_whitespace();
+            if self.chars.get(self.pos) != Some(&'"') {
+                return Err(self.unexpected());
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.parse_value()?;
+            // Later members with the same key replace earlier ones
+            obj.set_field(key.to_string(), value);
+            self.skip_whitespace();
+            match self.chars.get(self.pos) {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(obj);
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&']') {
+            self.pos += 1;
+            return Ok(array::new_array(&self.global, values));
+        }
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.chars.get(self.pos) {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(array::new_array(&self.global, values));
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    /// Parse a string, where escaped lone surrogates are kept as they are
+    fn parse_string(&mut self) -> Result<JsString, String> {
+        self.expect('"')?;
+        let mut units: Vec<u16> = Vec::new();
+        loop {
+            let ch = match self.chars.get(self.pos) {
+                Some(&ch) => ch,
+                None => return Err(format!("Unterminated string in JSON at position {}", self.pos)),
+            };
+            match ch {
+                '"' => {
+                    self.pos += 1;
+                    return Ok(JsString::from_units(units));
+                }
+                '\\' => {
+                    self.pos += 1;
+                    let escaped = match self.chars.get(self.pos) {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            let digits: String = self.chars.iter().skip(self.pos + 1).take(4).collect();
+                            match u16::from_str_radix(&digits, 16) {
+                                Ok(unit) if digits.len() == 4 && digits.chars().all(|ch| ch.is_ascii_hexdigit()) => {
+                                    units.push(unit);
+                                    self.pos += 5;
+                                    continue;
+                                }
+                                _ => {
+                                    return Err(format!("Bad Unicode escape in JSON at position {}", self.pos - 1));
+                                }
+                            }
+                        }
+                        _ => return Err(format!("Bad escaped character in JSON at position {}", self.pos)),
+                    };
+                    units.push(escaped as u16);
+                    self.pos += 1;
+                }
+                _ if (ch as u32) < 0x20 => {
+                    return Err(format!("Bad control character in string literal in JSON at position {}", self.pos));
+                }
+                _ => {
+                    let mut buffer = [0; 2];
+                    units.extend_from_slice(ch.encode_utf16(&mut buffer));
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        let digits = |parser: &mut JsonParser| -> Result<(), String> {
+            if !parser.chars.get(parser.pos).is_some_and(|ch| ch.is_ascii_digit()) {
+                return Err(parser.unexpected());
+            }
+            while parser.chars.get(parser.pos).is_some_and(|ch| ch.is_ascii_digit()) {
+                parser.pos += 1;
+            }
+            Ok(())
+        };
+        if self.chars.get(self.pos) == Some(&'-') {
+            self.pos += 1;
+        }
+        // Numbers can't have leading zeros
+        if self.chars.get(self.pos) == Some(&'0') {
+            self.pos += 1;
+        } else {
+            digits(self)?;
+        }
+        if self.chars.get(self.pos) == Some(&'.') {
+            self.pos += 1;
+            digits(self)?;
+        }
+        if let Some('e') | Some('E') = self.chars.get(self.pos) {
+            self.pos += 1;
+            if let Some('+') | Some('-') = self.chars.get(self.pos) {
+                self.pos += 1;
+            }
+            digits(self)?;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        Ok(to_value(text.parse::<f64>().unwrap_or(f64::NAN)))
+    }
+}
+
+/// Parse a JSON string into a Javascript value, then pass every value through the reviver if there is one,
+/// from the innermost values out
 /// https://tc39.github.io/ecma262/#sec-json.parse
-pub fn parse(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    match serde_json::from_str::<JSONValue>(&args.get(0).unwrap().clone().to_string()) {
-        Ok(json) => Ok(to_value(json)),
-        Err(err) => Err(to_value(err.to_string())),
+pub fn parse(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let text = interpreter.to_string(&arg(&args, 0))?;
+    let global = interpreter.get_global_object();
+    let mut parser = JsonParser {
+        chars: text.chars().collect(),
+        pos: 0,
+        global: global.clone(),
+    };
+    let unfiltered = match parser.parse_text() {
+        Ok(value) => value,
+        Err(message) => return Err(interpreter.syntax_error(message)),
+    };
+    let reviver = arg(&args, 1);
+    if !reviver.is_function() {
+        return Ok(unfiltered);
     }
+    let root = ValueData::new_obj(Some(&global));
+    root.set_field_slice("", unfiltered);
+    internalize_json_property(&root, "".into(), &reviver, interpreter)
 }
-/// Process a Javascript object into a JSON string
-pub fn stringify(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let obj = args.get(0).unwrap();
-    let json = obj.to_json();
-    Ok(to_value(to_string_pretty(&json).unwrap()))
+
+/// Pass the properties of a parsed value through the reviver, then the value itself
+/// https://tc39.es/ecma262/#sec-internalizejsonproperty
+fn internalize_json_property(holder: &Value, name: PropertyKey, reviver: &Value, interpreter: &mut Interpreter) -> ResultValue {
+    let value = interpreter.get_field(holder, name.clone())?;
+    if value.is_object() {
+        let keys = if array::is_array_value(&value, interpreter)? {
+            let length = array::length_of(&value, interpreter)?;
+            (0..length as u64).map(|index| PropertyKey::from(index.to_string())).collect()
+        } else {
+            enumerable_own_keys(&value, interpreter)?
+        };
+        for key in keys {
+            let element = internalize_json_property(&value, key.clone(), reviver, interpreter)?;
+            if element.is_undefined() {
+                interpreter.delete_property(&value, &key)?;
+            } else {
+                let desc = PropertyDescriptor {
+                    value: Some(element),
+                    writable: Some(true),
+                    enumerable: Some(true),
+                    configurable: Some(true),
+                    ..PropertyDescriptor::default()
+                };
+                interpreter.define_own_property(&value, key, desc)?;
+            }
+        }
+    }
+    let name = to_value(name.to_string());
+    interpreter.call(reviver, holder, vec![name, value])
+}
+
+/// Get the string keys of the enumerable own properties of an object
+fn enumerable_own_keys(obj: &Value, interpreter: &mut Interpreter) -> Result<Vec<PropertyKey>, Value> {
+    let mut keys = Vec::new();
+    for key in interpreter.own_property_keys(obj)? {
+        if let PropertyKey::Symbol(_) = key {
+            continue;
+        }
+        if interpreter.get_own_property(obj, &key)?.is_some_and(|prop| prop.enumerable) {
+            keys.push(key);
+        }
+    }
+    Ok(keys)
+}
+
+/// Quote a string for JSON, escaping quotes, backslashes, control characters and lone surrogates
+/// https://tc39.es/ecma262/#sec-quotejsonstring
+fn quote_json_string(string: &[u16]) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for ch in char::decode_utf16(string.iter().cloned()) {
+        let ch = match ch {
+            Ok(ch) => ch,
+            Err(error) => {
+                quoted.push_str(&format!("\\u{:04x}", error.unpaired_surrogate()));
+                continue;
+            }
+        };
+        match ch {
+            '\u{8}' => quoted.push_str("\\b"),
+            '\t' => quoted.push_str("\\t"),
+            '\n' => quoted.push_str("\\n"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\r' => quoted.push_str("\\r"),
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            _ if (ch as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", ch as u32)),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The state of a call to `JSON.stringify`
+/// https://tc39.es/ecma262/#sec-json.stringify
+struct Serializer {
+    replacer_function: Option<Value>,
+    property_list: Option<Vec<PropertyKey>>,
+    gap: String,
+    indent: String,
+    /// The objects being serialized, to find cycles
+    stack: Vec<Value>,
+}
+
+impl Serializer {
+    /// Serialize a property of `holder`, or get `None` if it isn't serialized, like `undefined` and functions
+    /// https://tc39.es/ecma262/#sec-serializejsonproperty
+    fn serialize_property(&mut self, key: PropertyKey, holder: &Value, interpreter: &mut Interpreter) -> Result<Option<String>, Value> {
+        let mut value = interpreter.get_field(holder, key.clone())?;
+        if value.is_object() || value.is_function() || value.is_bigint() {
+            let to_json = interpreter.get_field(&value, "toJSON")?;
+            if to_json.is_function() {
+                value = interpreter.call(&to_json, &value, vec![to_value(key.to_string())])?;
+            }
+        }
+        if let Some(ref replacer) = self.replacer_function {
+            value = interpreter.call(replacer, holder, vec![to_value(key.to_string()), value])?;
+        }
+        // Number, String, Boolean and BigInt objects are serialized like their primitives
+        if value.is_object() {
+            let primitive = value.get_private_field_slice("PrimitiveValue");
+            value = match *primitive {
+                ValueData::Number(_) | ValueData::Integer(_) => to_value(interpreter.to_number(&value)?),
+                ValueData::String(_) => to_value(interpreter.to_string(&value)?),
+                ValueData::Boolean(_) | ValueData::BigInt(_) => primitive.clone(),
+                _ => value,
+            };
+        }
+        Ok(Some(match *value {
+            ValueData::Null => "null".to_string(),
+            ValueData::Boolean(boolean) => boolean.to_string(),
+            ValueData::String(ref string) => quote_json_string(string),
+            ValueData::Number(num) if !num.is_finite() => "null".to_string(),
+            ValueData::Number(_) | ValueData::Integer(_) => value.to_string(),
+            ValueData::BigInt(_) => return Err(interpreter.type_error("Do not know how to serialize a BigInt")),
+            ValueData::Object(_, _) if array::is_array_value(&value, interpreter)? => self.serialize_array(&value, interpreter)?,
+            ValueData::Object(_, _) => self.serialize_object(&value, interpreter)?,
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Start serializing an object or array, throwing if it is already being serialized, and get the indent to
+    /// go back to
+    fn enter(&mut self, value: &Value, interpreter: &Interpreter) -> Result<String, Value> {
+        if self.stack.iter().any(|other| Gc::ptr_eq(other, value)) {
+            return Err(interpreter.type_error("Converting circular structure to JSON"));
+        }
+        self.stack.push(value.clone());
+        let stepback = self.indent.clone();
+        self.indent.push_str(&self.gap);
+        Ok(stepback)
+    }
+
+    /// Finish serializing an object or array, joining its serialized members between the brackets
+    fn leave(&mut self, open: char, close: char, partial: Vec<String>, stepback: String) -> String {
+        let result = if partial.is_empty() {
+            format!("{}{}", open, close)
+        } else if self.gap.is_empty() {
+            format!("{}{}{}", open, partial.join(","), close)
+        } else {
+            let separator = format!(",\n{}", self.indent);
+            format!("{}\n{}{}\n{}{}", open, self.indent, partial.join(&separator), stepback, close)
+        };
+        self.stack.pop();
+        self.indent = stepback;
+        result
+    }
+
+    /// https://tc39.es/ecma262/#sec-serializejsonobject
+    fn serialize_object(&mut self, value: &Value, interpreter: &mut Interpreter) -> Result<String, Value> {
+        let stepback = self.enter(value, interpreter)?;
+        let keys = match self.property_list {
+            Some(ref list) => list.clone(),
+            None => enumerable_own_keys(value, interpreter)?,
+        };
+        let mut partial = Vec::new();
+        for key in keys {
+            if let Some(member) = self.serialize_property(key.clone(), value, interpreter)? {
+                let colon = if self.gap.is_empty() { ":" } else { ": " };
+                partial.push(format!("{}{}{}", quote_json_string(&string::to_utf16(&key.to_string())), colon, member));
+            }
+        }
+        Ok(self.leave('{', '}', partial, stepback))
+    }
+
+    /// https://tc39.es/ecma262/#sec-serializejsonarray
+    fn serialize_array(&mut self, value: &Value, interpreter: &mut Interpreter) -> Result<String, Value> {
+        let stepback = self.enter(value, interpreter)?;
+        let length = array::length_of(value, interpreter)?;
+        let mut partial = Vec::new();
+        for index in 0..length as u64 {
+            let element = self.serialize_property(index.to_string().into(), value, interpreter)?;
+            partial.push(element.unwrap_or_else(|| "null".to_string()));
+        }
+        Ok(self.leave('[', ']', partial, stepback))
+    }
+}
+
+/// Process a Javascript value into a JSON string, using a replacer function or a list of the keys to include,
+/// indented with a number of spaces or a string
+/// https://tc39.github.io/ecma262/#sec-json.stringify
+pub fn stringify(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let replacer = arg(&args, 1);
+    let mut replacer_function = None;
+    let mut property_list = None;
+    if replacer.is_function() {
+        replacer_function = Some(replacer);
+    } else if array::is_array_value(&replacer, interpreter)? {
+        let mut list: Vec<PropertyKey> = Vec::new();
+        let length = array::length_of(&replacer, interpreter)?;
+        for index in 0..length as u64 {
+            let value = interpreter.get_field(&replacer, index.to_string())?;
+            let item = match *value {
+                ValueData::String(ref string) => Some(string.to_string()),
+                ValueData::Number(_) | ValueData::Integer(_) => Some(value.to_string()),
+                ValueData::Object(_, _) => match *value.get_private_field_slice("PrimitiveValue") {
+                    ValueData::String(_) | ValueData::Number(_) | ValueData::Integer(_) => Some(interpreter.to_string(&value)?),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(item) = item {
+                let key = PropertyKey::from(item);
+                if !list.contains(&key) {
+                    list.push(key);
+                }
+            }
+        }
+        property_list = Some(list);
+    }
+    let mut space = arg(&args, 2);
+    if space.is_object() {
+        space = match *space.get_private_field_slice("PrimitiveValue") {
+            ValueData::Number(_) | ValueData::Integer(_) => to_value(interpreter.to_number(&space)?),
+            ValueData::String(_) => to_value(interpreter.to_string(&space)?),
+            _ => space,
+        };
+    }
+    // The indent is at most 10 characters
+    let gap = match *space {
+        ValueData::Number(_) | ValueData::Integer(_) => {
+            let count = space.to_num();
+            let count = if count.is_nan() { 0.0 } else { count.trunc().clamp(0.0, 10.0) };
+            " ".repeat(count as usize)
+        }
+        ValueData::String(ref string) => string.to_string().chars().take(10).collect(),
+        _ => String::new(),
+    };
+    let wrapper = ValueData::new_obj(Some(&interpreter.get_global_object()));
+    wrapper.set_field_slice("", arg(&args, 0));
+    let mut serializer = Serializer {
+        replacer_function,
+        property_list,
+        gap,
+        indent: String::new(),
+        stack: Vec::new(),
+    };
+    Ok(match serializer.serialize_property("".into(), &wrapper, interpreter)? {
+        Some(json) => to_value(json),
+        None => Gc::new(ValueData::Undefined),
+    })
 }
 
 /// Create a new `JSON` object
@@ -24,10 +491,11 @@ pub fn _create(global: &Value) -> Value {
     let object = ValueData::new_obj(Some(global));
     object.set_field_slice("stringify", to_value(stringify as NativeFunctionData));
     object.set_field_slice("parse", to_value(parse as NativeFunctionData));
+    object.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("JSON"));
     object
 }
 
 /// Initialise the global object with the `JSON` object
 pub fn init(global: &Value) {
     global.set_field_slice("JSON", _create(global));
-}
\ No newline at end of file
+}
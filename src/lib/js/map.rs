@@ -0,0 +1,346 @@
+/// The global `Map` object, and the ordered entries which maps and sets keep their contents in
+/// https://tc39.es/ecma262/#sec-map-objects
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::array;
+use crate::js::function::NativeFunctionData;
+use crate::js::iterator::{self, ArrayIterationKind};
+use crate::js::object::{Property, PropertyKey, PROTOTYPE};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+
+/// The private field holding the entries of a map, the [[MapData]] internal slot
+pub static MAP_DATA: &str = "MapData";
+
+/// The private field of an iterator method holding the prototype of the iterators it makes
+pub static ITERATOR_PROTOTYPE: &str = "IteratorPrototype";
+
+/// Make the entries of a map or set.
+/// The entries are a list in the order they were added, where a deleted entry leaves a hole so that
+/// iterators part way through keep their place, with an index from each key to its position in the list.
+pub fn new_entries() -> Value {
+    let entries = ValueData::new_obj(None);
+    entries.set_field_slice("length", to_value(0i32));
+    entries.set_private_field_slice("Index", ValueData::new_obj(None));
+    entries.set_private_field_slice("Size", to_value(0i32));
+    entries
+}
+
+/// The key an entry is indexed by, which is the same for keys which are equal by SameValueZero.
+/// Objects are indexed by their address, which can't be reused while the entry keeps them alive.
+/// https://tc39.es/ecma262/#sec-samevaluezero
+fn index_key(key: &Value) -> PropertyKey {
+    match **key {
+        ValueData::Symbol(ref symbol) => PropertyKey::Symbol(symbol.clone()),
+        ValueData::Object(_, _) | ValueData::Function(_) | ValueData::Buffer(_) => {
+            PropertyKey::String(format!("object:{:p}", &**key))
+        }
+        ValueData::Number(_) | ValueData::Integer(_) => {
+            let num = key.to_num();
+            PropertyKey::String(format!("number:{}", if num == 0.0 { 0.0 } else { num }))
+        }
+        ValueData::String(ref string) => PropertyKey::String(format!("string:{}", string)),
+        ValueData::BigInt(ref num) => PropertyKey::String(format!("bigint:{}", num)),
+        ValueData::Boolean(boolean) => PropertyKey::String(format!("boolean:{}", boolean)),
+        ValueData::Null => PropertyKey::String("null".to_string()),
+        ValueData::Undefined => PropertyKey::String("undefined".to_string()),
+    }
+}
+
+/// Find the position of the entry with the key
+fn find_entry(entries: &Value, key: &Value) -> Option<i32> {
+    let position = entries.get_private_field_slice("Index").get_field(index_key(key));
+    if position.is_undefined() {
+        None
+    } else {
+        Some(position.to_int())
+    }
+}
+
+/// Get the number of positions in the list of entries, including the holes left by deleted entries
+pub fn entries_length(entries: &Value) -> i32 {
+    entries.get_field_slice("length").to_int()
+}
+
+/// Get the number of entries
+pub fn entries_size(entries: &Value) -> i32 {
+    entries.get_private_field_slice("Size").to_int()
+}
+
+/// Get the key and value of the entry at a position, or `None` if it was deleted
+pub fn entry_at(entries: &Value, position: i32) -> Option<(Value, Value)> {
+    let entry = entries.get_field(position.to_string());
+    if entry.is_object() {
+        Some((entry.get_field_slice("key"), entry.get_field_slice("value")))
+    } else {
+        None
+    }
+}
+
+/// Get the value of the entry with the key
+pub fn entries_get(entries: &Value, key: &Value) -> Option<Value> {
+    find_entry(entries, key).and_then(|position| entry_at(entries, position)).map(|(_, value)| value)
+}
+
+/// Returns true if there is an entry with the key
+pub fn entries_has(entries: &Value, key: &Value) -> bool {
+    find_entry(entries, key).is_some()
+}
+
+/// Set the value of the entry with the key, adding an entry at the end if there isn't one
+pub fn entries_set(entries: &Value, key: Value, value: Value) {
+    if let Some(position) = find_entry(entries, &key) {
+        entries.get_field(position.to_string()).set_field_slice("value", value);
+        return;
+    }
+    // -0 is stored as +0, so the key a map gives back is the same for both
+    let key = if key.get_type() == "number" && key.to_num() == 0.0 { to_value(0i32) } else { key };
+    let position = entries_length(entries);
+    let entry = ValueData::new_obj(None);
+    entry.set_field_slice("key", key.clone());
+    entry.set_field_slice("value", value);
+    entries.set_field(position.to_string(), entry);
+    entries.set_field_slice("length", to_value(position + 1));
+    entries.get_private_field_slice("Index").set_field(index_key(&key), to_value(position));
+    entries.set_private_field_slice("Size", to_value(entries_size(entries) + 1));
+}
+
+/// Delete the entry with the key, returning true if there was one
+pub fn entries_delete(entries: &Value, key: &Value) -> bool {
+    match find_entry(entries, key) {
+        Some(position) => {
+            entries.get_private_field_slice("Index").remove_prop(index_key(key));
+            entries.set_field(position.to_string(), Gc::new(ValueData::Null));
+            entries.set_private_field_slice("Size", to_value(entries_size(entries) - 1));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Delete every entry. The positions aren't reused, so iterators carry on with entries added afterwards.
+pub fn entries_clear(entries: &Value) {
+    for position in 0..entries_length(entries) {
+        entries.set_field(position.to_string(), Gc::new(ValueData::Null));
+    }
+    entries.set_private_field_slice("Index", ValueData::new_obj(None));
+    entries.set_private_field_slice("Size", to_value(0i32));
+}
+
+/// Call the callback with the value, key and collection of every entry, including ones added while iterating
+/// https://tc39.es/ecma262/#sec-map.prototype.foreach
+pub fn entries_for_each(entries: &Value, collection: &Value, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let callback = arg(args, 0);
+    if !callback.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", callback)));
+    }
+    let this_arg = arg(args, 1);
+    let mut position = 0;
+    while position < entries_length(entries) {
+        if let Some((key, value)) = entry_at(entries, position) {
+            interpreter.call(&callback, &this_arg, vec![value, key, collection.clone()])?;
+        }
+        position += 1;
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Make an iterator over the entries, with the prototype carried by the iterator method
+pub fn create_entries_iterator(entries: &Value, kind: ArrayIterationKind, method: &Value) -> Value {
+    let iterator = ValueData::new_obj_from_prototype(method.get_private_field_slice(ITERATOR_PROTOTYPE));
+    iterator.set_private_field_slice("IteratedEntries", entries.clone());
+    iterator.set_private_field_slice("NextIndex", to_value(0i32));
+    iterator.set_private_field_slice("IterationKind", to_value(kind.name()));
+    iterator
+}
+
+/// Get the next entry of a map or set iterator
+/// https://tc39.es/ecma262/#sec-%mapiteratorprototype%.next
+pub fn entries_iterator_next(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    let entries = this.get_private_field_slice("IteratedEntries");
+    if entries.is_undefined() {
+        return Ok(iterator::create_iter_result_object(&global, Gc::new(ValueData::Undefined), true));
+    }
+    let mut position = this.get_private_field_slice("NextIndex").to_int();
+    while position < entries_length(&entries) {
+        let entry = entry_at(&entries, position);
+        position += 1;
+        if let Some((key, value)) = entry {
+            this.set_private_field_slice("NextIndex", to_value(position));
+            let kind = ArrayIterationKind::from_name(&this.get_private_field_slice("IterationKind").to_string());
+            let result = match kind {
+                ArrayIterationKind::Keys => key,
+                ArrayIterationKind::Values => value,
+                ArrayIterationKind::Entries => array::new_array(&global, vec![key, value]),
+            };
+            return Ok(iterator::create_iter_result_object(&global, result, false));
+        }
+    }
+    // An exhausted iterator stays exhausted, even if entries are added
+    this.set_private_field_slice("IteratedEntries", Gc::new(ValueData::Undefined));
+    Ok(iterator::create_iter_result_object(&global, Gc::new(ValueData::Undefined), true))
+}
+
+/// Create the prototype of map or set iterators, like `%MapIteratorPrototype%`
+pub fn create_iterator_prototype(global: &Value, tag: &str) -> Value {
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("next", to_value(entries_iterator_next as NativeFunctionData));
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::Iterator),
+        to_value(iterator::return_this as NativeFunctionData),
+    );
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value(tag));
+    prototype
+}
+
+/// Make an iterator method, which carries the prototype of the iterators it makes
+pub fn iterator_method(method: NativeFunctionData, iterator_prototype: &Value) -> Value {
+    let method = to_value(method);
+    method.set_private_field_slice(ITERATOR_PROTOTYPE, iterator_prototype.clone());
+    method
+}
+
+/// Make the `size` accessor of a map or set
+pub fn size_property(getter: NativeFunctionData) -> Property {
+    let mut size = Property::new(Gc::new(ValueData::Undefined));
+    size.configurable = true;
+    size.get = to_value(getter);
+    size
+}
+
+/// Get the entries of the map `this`, or throw if it isn't a map
+fn this_map_data(this: &Value, method: &str, interpreter: &Interpreter) -> ResultValue {
+    let entries = this.get_private_field_slice(MAP_DATA);
+    if entries.is_undefined() {
+        return Err(interpreter.type_error(format!("Map.prototype.{} requires that 'this' be a Map", method)));
+    }
+    Ok(entries)
+}
+
+/// Create a new map, adding the `[key, value]` entries of an iterable with the map's `set` method
+/// https://tc39.es/ecma262/#sec-map-iterable
+pub fn make_map(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error("Constructor Map requires 'new'"));
+    }
+    this.set_private_field_slice(MAP_DATA, new_entries());
+    let iterable = arg(&args, 0);
+    if iterable.is_null_or_undefined() {
+        return Ok(this);
+    }
+    let adder = interpreter.get_field(&this, "set")?;
+    if !adder.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", adder)));
+    }
+    for entry in iterator::iterable_to_list(&iterable, interpreter)? {
+        if !entry.is_object() {
+            return Err(interpreter.type_error(format!("Iterator value {} is not an entry object", entry)));
+        }
+        let key = interpreter.get_field(&entry, "0")?;
+        let value = interpreter.get_field(&entry, "1")?;
+        interpreter.call(&adder, &this, vec![key, value])?;
+    }
+    Ok(this)
+}
+
+/// `Map.prototype.get(key)`
+/// https://tc39.es/ecma262/#sec-map.prototype.get
+pub fn get(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "get", interpreter)?;
+    Ok(entries_get(&entries, &arg(&args, 0)).unwrap_or_else(|| Gc::new(ValueData::Undefined)))
+}
+
+/// `Map.prototype.set(key, value)`
+/// https://tc39.es/ecma262/#sec-map.prototype.set
+pub fn set(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "set", interpreter)?;
+    entries_set(&entries, arg(&args, 0), arg(&args, 1));
+    Ok(this)
+}
+
+/// `Map.prototype.has(key)`
+/// https://tc39.es/ecma262/#sec-map.prototype.has
+pub fn has(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "has", interpreter)?;
+    Ok(to_value(entries_has(&entries, &arg(&args, 0))))
+}
+
+/// `Map.prototype.delete(key)`
+/// https://tc39.es/ecma262/#sec-map.prototype.delete
+pub fn delete(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "delete", interpreter)?;
+    Ok(to_value(entries_delete(&entries, &arg(&args, 0))))
+}
+
+/// `Map.prototype.clear()`
+/// https://tc39.es/ecma262/#sec-map.prototype.clear
+pub fn clear(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "clear", interpreter)?;
+    entries_clear(&entries);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// `get Map.prototype.size`
+/// https://tc39.es/ecma262/#sec-get-map.prototype.size
+pub fn get_size(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "size", interpreter)?;
+    Ok(to_value(entries_size(&entries)))
+}
+
+/// `Map.prototype.forEach(callback, thisArg)`
+/// https://tc39.es/ecma262/#sec-map.prototype.foreach
+pub fn for_each(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "forEach", interpreter)?;
+    entries_for_each(&entries, &this, &args, interpreter)
+}
+
+/// `Map.prototype.keys()`
+/// https://tc39.es/ecma262/#sec-map.prototype.keys
+pub fn keys(this: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "keys", interpreter)?;
+    Ok(create_entries_iterator(&entries, ArrayIterationKind::Keys, &f))
+}
+
+/// `Map.prototype.values()`
+/// https://tc39.es/ecma262/#sec-map.prototype.values
+pub fn values(this: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "values", interpreter)?;
+    Ok(create_entries_iterator(&entries, ArrayIterationKind::Values, &f))
+}
+
+/// `Map.prototype.entries()`, which is also `Map.prototype[Symbol.iterator]`
+/// https://tc39.es/ecma262/#sec-map.prototype.entries
+pub fn entries(this: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_map_data(&this, "entries", interpreter)?;
+    Ok(create_entries_iterator(&entries, ArrayIterationKind::Entries, &f))
+}
+
+/// Create a new `Map` object
+pub fn _create(global: &Value) -> Value {
+    let map = to_value(make_map as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("get", to_value(get as NativeFunctionData));
+    prototype.set_field_slice("set", to_value(set as NativeFunctionData));
+    prototype.set_field_slice("has", to_value(has as NativeFunctionData));
+    prototype.set_field_slice("delete", to_value(delete as NativeFunctionData));
+    prototype.set_field_slice("clear", to_value(clear as NativeFunctionData));
+    prototype.set_field_slice("forEach", to_value(for_each as NativeFunctionData));
+    prototype.set_prop_slice("size", size_property(get_size));
+    let iterator_prototype = create_iterator_prototype(global, "Map Iterator");
+    let entries = iterator_method(entries, &iterator_prototype);
+    prototype.set_field_slice("keys", iterator_method(keys, &iterator_prototype));
+    prototype.set_field_slice("values", iterator_method(values, &iterator_prototype));
+    prototype.set_field_slice("entries", entries.clone());
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::Iterator), entries);
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("Map"));
+    map.set_field_slice("length", to_value(0i32));
+    prototype.set_field_slice("constructor", map.clone());
+    map.set_field_slice(PROTOTYPE, prototype);
+    map
+}
+
+/// Initialise the global object with the `Map` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Map", _create(global));
+}
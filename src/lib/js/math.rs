@@ -1,223 +1,431 @@
+/// The global `Math` object, and the source of the numbers `Math.random` returns
+/// https://tc39.es/ecma262/#sec-math-object
 use rand::random;
 use std::f64;
-use crate::js::function::NativeFunctionData;
-use crate::js::value::{from_value, to_value, ResultValue, Value, ValueData};
+use crate::exec::Interpreter;
+use crate::js::conversion::number_arg;
+use crate::js::function::{self, NativeFunctionData};
+use crate::js::object::Property;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+
+/// Where `Math.random` gets its numbers from.
+/// The interpreter uses the system's generator unless the embedder gives it another source, such as a
+/// `SeededRandom` to make runs reproducible.
+pub trait RandomSource {
+    /// The next number, which is at least 0 and less than 1
+    fn next_f64(&mut self) -> f64;
+}
+
+/// The random number generator of the system, which is seeded differently every run
+pub struct SystemRandom;
+
+impl RandomSource for SystemRandom {
+    fn next_f64(&mut self) -> f64 {
+        random::<f64>()
+    }
+}
+
+/// A xorshift128+ generator, the algorithm V8 uses, which makes the same numbers for the same seed
+pub struct SeededRandom {
+    state: [u64; 2],
+}
+
+impl SeededRandom {
+    /// Make a generator from a seed
+    pub fn new(seed: u64) -> SeededRandom {
+        // The seed is spread over the state with splitmix64, so that similar seeds give unrelated numbers
+        let mut seed = seed;
+        let mut split_mix = || {
+            seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        SeededRandom {
+            state: [split_mix(), split_mix()],
+        }
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_f64(&mut self) -> f64 {
+        let [mut s1, s0] = self.state;
+        s1 ^= s1 << 23;
+        s1 ^= s1 >> 17;
+        s1 ^= s0;
+        s1 ^= s0 >> 26;
+        self.state = [s0, s1];
+        // The top 53 bits make a number in [0, 1) with every representable step equally likely
+        (s0.wrapping_add(s1) >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Get all of the arguments as numbers, converting every one before any are used
+fn number_args(args: &[Value], interpreter: &mut Interpreter) -> Result<Vec<f64>, Value> {
+    args.iter().map(|arg| interpreter.to_number(arg)).collect()
+}
+
+/// Convert a number to an unsigned 32-bit integer, wrapping around like `x >>> 0`
+/// https://tc39.es/ecma262/#sec-touint32
+fn to_uint32(num: f64) -> u32 {
+    if !num.is_finite() {
+        return 0;
+    }
+    num.trunc().rem_euclid(4_294_967_296.0) as u32
+}
+
+/// Run a function of one number on the first argument
+fn unary(args: &[Value], interpreter: &mut Interpreter, f: fn(f64) -> f64) -> ResultValue {
+    Ok(to_value(f(number_arg(args, 0, interpreter)?)))
+}
 
 /// Get the absolute value of a number
-pub fn abs(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .abs()
-    } else {
-        f64::NAN
-    }))
-}
-/// Get the arccos of a number
-pub fn acos(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .acos()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.abs
+pub fn abs(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::abs)
+}
+
+/// Get the arccosine of a number
+/// https://tc39.es/ecma262/#sec-math.acos
+pub fn acos(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::acos)
 }
+
+/// Get the inverse hyperbolic cosine of a number
+/// https://tc39.es/ecma262/#sec-math.acosh
+pub fn acosh(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::acosh)
+}
+
 /// Get the arcsine of a number
-pub fn asin(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .asin()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.asin
+pub fn asin(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::asin)
 }
+
+/// Get the inverse hyperbolic sine of a number
+/// https://tc39.es/ecma262/#sec-math.asinh
+pub fn asinh(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, |x| if x == 0.0 { x } else { x.asinh() })
+}
+
 /// Get the arctangent of a number
-pub fn atan(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .atan()
-    } else {
-        f64::NAN
-    }))
-}
-/// Get the arctangent of a numbers
-pub fn atan2(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .atan2(args.get(1).unwrap().to_num())
-    } else {
-        f64::NAN
-    }))
-}
-/// Get the cubic root of a number
-pub fn cbrt(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .cbrt()
-    } else {
-        f64::NAN
-    }))
-}
-/// Get lowest integer above a number
-pub fn ceil(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .ceil()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.atan
+pub fn atan(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::atan)
+}
+
+/// Get the inverse hyperbolic tangent of a number
+/// https://tc39.es/ecma262/#sec-math.atanh
+pub fn atanh(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, |x| if x == 0.0 { x } else { x.atanh() })
+}
+
+/// Get the angle from the x axis to the point `(x, y)`, where y is the first argument
+/// https://tc39.es/ecma262/#sec-math.atan2
+pub fn atan2(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let y = number_arg(&args, 0, interpreter)?;
+    let x = number_arg(&args, 1, interpreter)?;
+    Ok(to_value(y.atan2(x)))
+}
+
+/// Get the cube root of a number
+/// https://tc39.es/ecma262/#sec-math.cbrt
+pub fn cbrt(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::cbrt)
 }
+
+/// Get the lowest integer above a number
+/// https://tc39.es/ecma262/#sec-math.ceil
+pub fn ceil(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::ceil)
+}
+
+/// Count the leading zero bits of a number as an unsigned 32-bit integer
+/// https://tc39.es/ecma262/#sec-math.clz32
+pub fn clz32(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let num = to_uint32(number_arg(&args, 0, interpreter)?);
+    Ok(to_value(num.leading_zeros() as i32))
+}
+
 /// Get the cosine of a number
-pub fn cos(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .cos()
-    } else {
-        f64::NAN
-    }))
-}
-/// Get the power to raise the natural logarithm to get the number
-pub fn exp(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .exp()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.cos
+pub fn cos(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::cos)
+}
+
+/// Get the hyperbolic cosine of a number
+/// https://tc39.es/ecma262/#sec-math.cosh
+pub fn cosh(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::cosh)
+}
+
+/// Raise e to the power of a number
+/// https://tc39.es/ecma262/#sec-math.exp
+pub fn exp(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::exp)
+}
+
+/// Raise e to the power of a number and subtract 1, which is accurate even when the number is close to 0
+/// https://tc39.es/ecma262/#sec-math.expm1
+pub fn expm1(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::exp_m1)
 }
+
 /// Get the highest integer below a number
-pub fn floor(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .floor()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.floor
+pub fn floor(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::floor)
+}
+
+/// Round a number to the nearest single precision float
+/// https://tc39.es/ecma262/#sec-math.fround
+pub fn fround(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, |x| x as f32 as f64)
+}
+
+/// Get the square root of the sum of the squares of the arguments
+/// https://tc39.es/ecma262/#sec-math.hypot
+pub fn hypot(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let nums = number_args(&args, interpreter)?;
+    // An infinite argument wins even over NaN
+    if nums.iter().any(|num| num.is_infinite()) {
+        return Ok(to_value(f64::INFINITY));
+    }
+    if nums.iter().any(|num| num.is_nan()) {
+        return Ok(to_value(f64::NAN));
+    }
+    // Scaling by the largest argument stops the squares from overflowing
+    let largest = nums.iter().fold(0.0, |largest: f64, num| largest.max(num.abs()));
+    if largest == 0.0 {
+        return Ok(to_value(0.0));
+    }
+    // Kahan summation keeps the rounding errors of the squares from adding up
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+    for num in nums {
+        let scaled = num.abs() / largest;
+        let summand = scaled * scaled - compensation;
+        let preliminary = sum + summand;
+        compensation = (preliminary - sum) - summand;
+        sum = preliminary;
+    }
+    Ok(to_value(sum.sqrt() * largest))
 }
+
+/// Multiply two numbers as 32-bit integers, wrapping around like C does
+/// https://tc39.es/ecma262/#sec-math.imul
+pub fn imul(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let a = to_uint32(number_arg(&args, 0, interpreter)?);
+    let b = to_uint32(number_arg(&args, 1, interpreter)?);
+    Ok(to_value(a.wrapping_mul(b) as i32))
+}
+
 /// Get the natural logarithm of a number
-pub fn log(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .log(f64::consts::E)
-    } else {
-        f64::NAN
-    }))
-}
-/// Get the maximum of several numbers
-pub fn max(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
+/// https://tc39.es/ecma262/#sec-math.log
+pub fn log(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::ln)
+}
+
+/// Get the natural logarithm of 1 plus a number, which is accurate even when the number is close to 0
+/// https://tc39.es/ecma262/#sec-math.log1p
+pub fn log1p(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::ln_1p)
+}
+
+/// Get the base 10 logarithm of a number
+/// https://tc39.es/ecma262/#sec-math.log10
+pub fn log10(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::log10)
+}
+
+/// Get the base 2 logarithm of a number
+/// https://tc39.es/ecma262/#sec-math.log2
+pub fn log2(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::log2)
+}
+
+/// Get the largest of the arguments, which is -Infinity if there are none, or NaN if any of them are.
+/// +0 is larger than -0.
+/// https://tc39.es/ecma262/#sec-math.max
+pub fn max(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
     let mut max = f64::NEG_INFINITY;
-    for arg in args.iter() {
-        let num = arg.to_num();
-        max = max.max(num);
+    for num in number_args(&args, interpreter)? {
+        if num.is_nan() {
+            return Ok(to_value(f64::NAN));
+        }
+        if num > max || (num == 0.0 && max == 0.0 && max.is_sign_negative()) {
+            max = num;
+        }
     }
     Ok(to_value(max))
 }
-/// Get the minimum of several numbers
-pub fn min(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let mut max = f64::INFINITY;
-    for arg in args.iter() {
-        let num = arg.to_num();
-        max = max.min(num);
+
+/// Get the smallest of the arguments, which is Infinity if there are none, or NaN if any of them are.
+/// -0 is smaller than +0.
+/// https://tc39.es/ecma262/#sec-math.min
+pub fn min(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut min = f64::INFINITY;
+    for num in number_args(&args, interpreter)? {
+        if num.is_nan() {
+            return Ok(to_value(f64::NAN));
+        }
+        if num < min || (num == 0.0 && min == 0.0 && num.is_sign_negative()) {
+            min = num;
+        }
     }
-    Ok(to_value(max))
+    Ok(to_value(min))
+}
+
+/// Raise a number to a power, like the `**` operator
+/// https://tc39.es/ecma262/#sec-numeric-types-number-exponentiate
+pub fn exponentiate(base: f64, exponent: f64) -> f64 {
+    // IEEE 754 says 1 to any power is 1, but ECMAScript makes these NaN
+    if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+        return f64::NAN;
+    }
+    base.powf(exponent)
 }
+
 /// Raise a number to a power
-pub fn pow(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 2 {
-        let num: f64 = from_value(args.get(0).unwrap().clone()).unwrap();
-        let power: f64 = from_value(args.get(1).unwrap().clone()).unwrap();
-        num.powf(power)
-    } else {
-        f64::NAN
-    }))
-}
-/// Generate a random floating-point number between 0 and 1
-pub fn _random(_: Value, _: Value, _args: Vec<Value>) -> ResultValue {
-    Ok(to_value(random::<f64>()))
-}
-/// Round a number to the nearest integer
-pub fn round(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .round()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.pow
+pub fn pow(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let base = number_arg(&args, 0, interpreter)?;
+    let exponent = number_arg(&args, 1, interpreter)?;
+    Ok(to_value(exponentiate(base, exponent)))
+}
+
+/// Get a random number which is at least 0 and less than 1, from the interpreter's random source
+/// https://tc39.es/ecma262/#sec-math.random
+pub fn random_number(_: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(interpreter.random_source().next_f64()))
+}
+
+/// Round a number to the nearest integer, where halves round up, towards +Infinity
+/// https://tc39.es/ecma262/#sec-math.round
+pub fn round(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, |x| {
+        if !x.is_finite() || x.fract() == 0.0 {
+            return x;
+        }
+        // Numbers between -0.5 and -0 round to -0
+        if (-0.5..0.0).contains(&x) {
+            return -0.0;
+        }
+        // Adding 0.5 and flooring would round 0.49999999999999994 up, as the sum rounds to 1
+        let floor = x.floor();
+        if x - floor >= 0.5 { floor + 1.0 } else { floor }
+    })
+}
+
+/// Get the sign of a number, which is 1, -1, or the number itself if it is a zero or NaN
+/// https://tc39.es/ecma262/#sec-math.sign
+pub fn sign(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, |x| if x.is_nan() || x == 0.0 { x } else { x.signum() })
 }
+
 /// Get the sine of a number
-pub fn sin(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .sin()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.sin
+pub fn sin(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::sin)
+}
+
+/// Get the hyperbolic sine of a number
+/// https://tc39.es/ecma262/#sec-math.sinh
+pub fn sinh(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::sinh)
 }
+
 /// Get the square root of a number
-pub fn sqrt(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .sqrt()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.sqrt
+pub fn sqrt(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::sqrt)
 }
+
 /// Get the tangent of a number
-pub fn tan(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    Ok(to_value(if args.len() >= 1 {
-        from_value::<f64>(args.get(0).unwrap().clone())
-            .unwrap()
-            .tan()
-    } else {
-        f64::NAN
-    }))
+/// https://tc39.es/ecma262/#sec-math.tan
+pub fn tan(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::tan)
+}
+
+/// Get the hyperbolic tangent of a number
+/// https://tc39.es/ecma262/#sec-math.tanh
+pub fn tanh(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::tanh)
+}
+
+/// Remove the fractional part of a number, rounding towards zero
+/// https://tc39.es/ecma262/#sec-math.trunc
+pub fn trunc(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    unary(&args, interpreter, f64::trunc)
 }
+
 /// Create a new `Math` object
 pub fn _create(global: &Value) -> Value {
     let math = ValueData::new_obj(Some(global));
-    math.set_field_slice("E", to_value(f64::consts::E));
-    math.set_field_slice("LN2", to_value(f64::consts::LN_2));
-    math.set_field_slice("LN10", to_value(f64::consts::LN_10));
-    math.set_field_slice("LOG2E", to_value(f64::consts::LOG2_E));
-    math.set_field_slice("LOG10E", to_value(f64::consts::LOG10_E));
-    math.set_field_slice("SQRT1_2", to_value(0.5f64.sqrt()));
-    math.set_field_slice("SQRT2", to_value(f64::consts::SQRT_2));
-    math.set_field_slice("PI", to_value(f64::consts::PI));
-    math.set_field_slice("abs", to_value(abs as NativeFunctionData));
-    math.set_field_slice("acos", to_value(acos as NativeFunctionData));
-    math.set_field_slice("asin", to_value(asin as NativeFunctionData));
-    math.set_field_slice("atan", to_value(atan as NativeFunctionData));
-    math.set_field_slice("atan2", to_value(atan2 as NativeFunctionData));
-    math.set_field_slice("cbrt", to_value(cbrt as NativeFunctionData));
-    math.set_field_slice("ceil", to_value(ceil as NativeFunctionData));
-    math.set_field_slice("cos", to_value(cos as NativeFunctionData));
-    math.set_field_slice("exp", to_value(exp as NativeFunctionData));
-    math.set_field_slice("floor", to_value(floor as NativeFunctionData));
-    math.set_field_slice("log", to_value(log as NativeFunctionData));
-    math.set_field_slice("max", to_value(max as NativeFunctionData));
-    math.set_field_slice("min", to_value(min as NativeFunctionData));
-    math.set_field_slice("pow", to_value(pow as NativeFunctionData));
-    math.set_field_slice("random", to_value(_random as NativeFunctionData));
-    math.set_field_slice("round", to_value(round as NativeFunctionData));
-    math.set_field_slice("sin", to_value(sin as NativeFunctionData));
-    math.set_field_slice("sqrt", to_value(sqrt as NativeFunctionData));
-    math.set_field_slice("tan", to_value(tan as NativeFunctionData));
+    // The constants can't be changed or deleted
+    let constants = [
+        ("E", f64::consts::E),
+        ("LN10", f64::consts::LN_10),
+        ("LN2", f64::consts::LN_2),
+        ("LOG10E", f64::consts::LOG10_E),
+        ("LOG2E", f64::consts::LOG2_E),
+        ("PI", f64::consts::PI),
+        ("SQRT1_2", f64::consts::FRAC_1_SQRT_2),
+        ("SQRT2", f64::consts::SQRT_2),
+    ];
+    for &(name, value) in constants.iter() {
+        math.set_prop_slice(name, Property::new(to_value(value)));
+    }
+    let functions: [(&str, NativeFunctionData, usize); 35] = [
+        ("abs", abs, 1),
+        ("acos", acos, 1),
+        ("acosh", acosh, 1),
+        ("asin", asin, 1),
+        ("asinh", asinh, 1),
+        ("atan", atan, 1),
+        ("atanh", atanh, 1),
+        ("atan2", atan2, 2),
+        ("cbrt", cbrt, 1),
+        ("ceil", ceil, 1),
+        ("clz32", clz32, 1),
+        ("cos", cos, 1),
+        ("cosh", cosh, 1),
+        ("exp", exp, 1),
+        ("expm1", expm1, 1),
+        ("floor", floor, 1),
+        ("fround", fround, 1),
+        ("hypot", hypot, 2),
+        ("imul", imul, 2),
+        ("log", log, 1),
+        ("log1p", log1p, 1),
+        ("log10", log10, 1),
+        ("log2", log2, 1),
+        ("max", max, 2),
+        ("min", min, 2),
+        ("pow", pow, 2),
+        ("random", random_number, 0),
+        ("round", round, 1),
+        ("sign", sign, 1),
+        ("sin", sin, 1),
+        ("sinh", sinh, 1),
+        ("sqrt", sqrt, 1),
+        ("tan", tan, 1),
+        ("tanh", tanh, 1),
+        ("trunc", trunc, 1),
+    ];
+    for &(name, native, length) in functions.iter() {
+        let func = to_value(native);
+        function::set_function_length(&func, length);
+        math.set_field_slice(name, func);
+    }
+    math.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("Math"));
     math
 }
+
 /// Initialise the `Math` object on the global object
 pub fn init(global: &Value) {
     global.set_field_slice("Math", _create(global));
-}
\ No newline at end of file
+}
@@ -1,11 +1,51 @@
 pub mod function;
+/// Iterators and the iteration protocol
+pub mod iterator;
 /// The global `Object` object
 pub mod object;
 /// Javascript values, utility methods and conversion between Javascript values and Rust values
 pub mod value;
 pub mod array;
+/// The global `ArrayBuffer` object and the bytes buffers share with embedders
+pub mod array_buffer;
+/// The global `BigInt` object and BigInt values
+pub mod bigint;
+/// The global `Boolean` object
+pub mod boolean;
+/// The global `console` object, and the sinks its output is written to
 pub mod console;
+/// Conversions of argument values shared by the built-in objects
+pub mod conversion;
+/// The global `DataView` object
+pub mod data_view;
+/// The global `Date` object, and the clock and time zone dates are made with
+pub mod date;
 pub mod json;
+/// The global `Map` object, and the entries maps and sets keep their contents in
+pub mod map;
 pub mod error;
+/// The value properties and function properties of the global object
+pub mod global;
+/// The global `Math` object, and the sources of random numbers it can use
 pub mod math;
-pub mod string;
\ No newline at end of file
+/// The global `Number` object and the formatting of numbers
+pub mod number;
+pub mod string;
+/// The global `Promise` object and promise jobs
+pub mod promise;
+/// The global `Proxy` object and the internal methods of proxies
+pub mod proxy;
+/// The global `Reflect` object
+pub mod reflect;
+/// The global `RegExp` object and regular expression objects
+pub mod regexp;
+/// The global `Set` object
+pub mod set;
+/// The global `Symbol` object and symbol values
+pub mod symbol;
+/// The global timer functions and `queueMicrotask`
+pub mod timers;
+/// The global typed array objects and `%TypedArray%`
+pub mod typed_array;
+/// The global `WeakMap` and `WeakSet` objects
+pub mod weak;
@@ -0,0 +1,559 @@
+/// The Number Object
+/// https://tc39.es/ecma262/#sec-number-objects
+use gc::Gc;
+use num_bigint::BigUint;
+use crate::exec::Interpreter;
+use crate::js::conversion::{to_integer_or_infinity, MAX_SAFE_INTEGER};
+use crate::js::function::NativeFunctionData;
+use crate::js::object::{INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::string::is_trimmable;
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+
+/// Split a positive, finite number into its shortest round-trip decimal digits and exponent,
+/// so that the number is `d.ddd × 10^exponent`
+fn shortest_digits(x: f64) -> (String, i32) {
+    // Rust formats floats with the shortest digits which parse back to the same value
+    let formatted = format!("{:e}", x);
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+    let digits = mantissa.replace('.', "");
+    (digits, exponent[1..].parse().unwrap())
+}
+
+/// Split a positive, finite number into the exact decimal digits of its binary value and the position
+/// of its decimal point, so that the number is `0.ddd × 10^point`
+fn exact_digits(x: f64) -> (String, i32) {
+    let bits = x.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7FF) as i32;
+    let fraction = bits & 0x000F_FFFF_FFFF_FFFF;
+    // The value is mantissa × 2^exponent
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    let mantissa = BigUint::from(mantissa);
+    if exponent >= 0 {
+        let digits = (mantissa << exponent as usize).to_string();
+        let point = digits.len() as i32;
+        return (digits, point);
+    }
+    // m / 2^k is m × 5^k / 10^k
+    let digits = (mantissa * BigUint::from(5u32).pow((-exponent) as u32)).to_string();
+    let point = digits.len() as i32 + exponent;
+    (digits.trim_end_matches('0').to_string(), point)
+}
+
+/// Keep the first `keep` digits, rounding half up on the digits which are dropped.
+/// Returns true as well if rounding carried into a new leading digit.
+fn round_digits(digits: &str, keep: usize) -> (String, bool) {
+    let mut kept: Vec<u8> = digits.bytes().take(keep).collect();
+    kept.resize(keep, b'0');
+    if digits.len() <= keep || digits.as_bytes()[keep] < b'5' {
+        return (String::from_utf8(kept).unwrap(), false);
+    }
+    for digit in kept.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return (String::from_utf8(kept).unwrap(), false);
+        }
+    }
+    kept.insert(0, b'1');
+    (String::from_utf8(kept).unwrap(), true)
+}
+
+/// Round a positive, finite number to `precision` significant digits,
+/// returning the digits and the exponent of the first digit
+fn to_precision_digits(x: f64, precision: usize) -> (String, i32) {
+    if x == 0.0 {
+        return ("0".repeat(precision), 0);
+    }
+    let (digits, point) = exact_digits(x);
+    let (mut rounded, carried) = round_digits(&digits, precision);
+    if carried {
+        rounded.truncate(precision);
+        return (rounded, point);
+    }
+    (rounded, point - 1)
+}
+
+/// Format an exponent as it appears after the `e` of a number
+fn exponent_string(exponent: i32) -> String {
+    if exponent < 0 {
+        format!("-{}", -exponent)
+    } else {
+        format!("+{}", exponent)
+    }
+}
+
+/// Format digits and an exponent in exponential notation, like `1.5e+21`
+fn exponential(digits: &str, exponent: i32) -> String {
+    if digits.len() == 1 {
+        format!("{}e{}", digits, exponent_string(exponent))
+    } else {
+        format!("{}.{}e{}", &digits[..1], &digits[1..], exponent_string(exponent))
+    }
+}
+
+/// Format a number as Javascript does when converting it to a string
+/// https://tc39.es/ecma262/#sec-numeric-types-number-tostring
+pub fn number_to_string(x: f64) -> String {
+    if x.is_nan() {
+        return "NaN".to_string();
+    }
+    if x == 0.0 {
+        return "0".to_string();
+    }
+    if x < 0.0 {
+        return format!("-{}", number_to_string(-x));
+    }
+    if x.is_infinite() {
+        return "Infinity".to_string();
+    }
+    let (digits, exponent) = shortest_digits(x);
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        exponential(&digits, exponent)
+    }
+}
+
+/// Format a number in a radix other than 10, with as many fraction digits as are needed to tell it apart
+/// from its neighbouring numbers. This follows V8's `DoubleToRadixCString`.
+pub fn number_to_string_radix(x: f64, radix: u32) -> String {
+    if radix == 10 || !x.is_finite() || x == 0.0 {
+        return number_to_string(x);
+    }
+    if x < 0.0 {
+        return format!("-{}", number_to_string_radix(-x, radix));
+    }
+    let digit_char = |digit: u32| std::char::from_digit(digit, radix).unwrap();
+    let radix_f = f64::from(radix);
+    let mut integer = x.trunc();
+    let mut fraction = x - integer;
+    // Half the distance to the next number, which is how precise the digits need to be
+    let next = f64::from_bits(x.to_bits() + 1);
+    let mut delta = (0.5 * (next - x)).max(f64::from_bits(1));
+
+    let mut fraction_digits: Vec<u32> = Vec::new();
+    if fraction >= delta {
+        loop {
+            fraction *= radix_f;
+            delta *= radix_f;
+            let digit = fraction.trunc() as u32;
+            fraction_digits.push(digit);
+            fraction -= f64::from(digit);
+            let past_half = fraction > 0.5 || (fraction == 0.5 && digit & 1 == 1);
+            if past_half && fraction + delta > 1.0 {
+                // Round up, carrying into earlier digits and possibly the integer part
+                loop {
+                    match fraction_digits.pop() {
+                        None => {
+                            integer += 1.0;
+                            break;
+                        }
+                        Some(digit) if digit + 1 < radix => {
+                            fraction_digits.push(digit + 1);
+                            break;
+                        }
+                        Some(_) => (),
+                    }
+                }
+                break;
+            }
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    let mut integer_digits = Vec::new();
+    // Digits below the precision of the number are zeros
+    while integer / radix_f >= MAX_SAFE_INTEGER + 1.0 {
+        integer /= radix_f;
+        integer_digits.push('0');
+    }
+    loop {
+        let remainder = integer % radix_f;
+        integer_digits.push(digit_char(remainder as u32));
+        integer = (integer - remainder) / radix_f;
+        if integer <= 0.0 {
+            break;
+        }
+    }
+    let mut result: String = integer_digits.into_iter().rev().collect();
+    if !fraction_digits.is_empty() {
+        result.push('.');
+        result.extend(fraction_digits.into_iter().map(digit_char));
+    }
+    result
+}
+
+/// Parse a string as a number, where the whole string must be a number apart from surrounding white space
+/// https://tc39.es/ecma262/#sec-stringtonumber
+pub fn string_to_number(string: &str) -> f64 {
+    let string = string.trim_matches(is_trimmable);
+    if string.is_empty() {
+        return 0.0;
+    }
+    let radix = match string.get(..2) {
+        Some("0x") | Some("0X") => 16,
+        Some("0o") | Some("0O") => 8,
+        Some("0b") | Some("0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        let digits = &string[2..];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return f64::NAN;
+        }
+        return digits
+            .chars()
+            .fold(0.0, |value, c| value * f64::from(radix) + f64::from(c.to_digit(radix).unwrap()));
+    }
+    match decimal_prefix(string) {
+        Some(length) if length == string.len() => parse_decimal(string),
+        _ => f64::NAN,
+    }
+}
+
+/// Get the length of the longest prefix of the string which is a decimal number, like `-1.5e3` or `Infinity`
+/// https://tc39.es/ecma262/#prod-StrDecimalLiteral
+fn decimal_prefix(string: &str) -> Option<usize> {
+    let bytes = string.as_bytes();
+    let mut index = 0;
+    if index < bytes.len() && (bytes[index] == b'+' || bytes[index] == b'-') {
+        index += 1;
+    }
+    if string[index..].starts_with("Infinity") {
+        return Some(index + "Infinity".len());
+    }
+    let count_digits = |from: usize| bytes[from..].iter().take_while(|b| b.is_ascii_digit()).count();
+    let integer_digits = count_digits(index);
+    index += integer_digits;
+    let mut fraction_digits = 0;
+    if index < bytes.len() && bytes[index] == b'.' {
+        fraction_digits = count_digits(index + 1);
+        if integer_digits > 0 || fraction_digits > 0 {
+            index += 1 + fraction_digits;
+        }
+    }
+    if integer_digits == 0 && fraction_digits == 0 {
+        return None;
+    }
+    if index < bytes.len() && (bytes[index] == b'e' || bytes[index] == b'E') {
+        let mut exponent_index = index + 1;
+        if exponent_index < bytes.len() && (bytes[exponent_index] == b'+' || bytes[exponent_index] == b'-') {
+            exponent_index += 1;
+        }
+        let exponent_digits = count_digits(exponent_index);
+        if exponent_digits > 0 {
+            index = exponent_index + exponent_digits;
+        }
+    }
+    Some(index)
+}
+
+/// Parse a string matched by `decimal_prefix`
+fn parse_decimal(string: &str) -> f64 {
+    match string.trim_start_matches('+') {
+        "Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        // Rust parses decimal strings with correct rounding
+        decimal => decimal.parse().unwrap_or(f64::NAN),
+    }
+}
+
+/// Get the number `this` refers to, which is a number or a `Number` object
+/// https://tc39.es/ecma262/#sec-thisnumbervalue
+fn this_number_value(this: &Value, method: &str, interpreter: &Interpreter) -> Result<f64, Value> {
+    let primitive = if this.is_object() {
+        this.get_private_field_slice("PrimitiveValue")
+    } else {
+        this.clone()
+    };
+    match *primitive {
+        ValueData::Number(num) => Ok(num),
+        ValueData::Integer(num) => Ok(f64::from(num)),
+        _ => Err(interpreter.type_error(format!(
+            "Number.prototype.{} requires that 'this' be a Number",
+            method
+        ))),
+    }
+}
+
+/// Convert a value to a number, or create a `Number` object with `new`
+/// https://tc39.es/ecma262/#sec-number-constructor-number-value
+pub fn make_number(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let num = match args.first() {
+        Some(value) => {
+            let prim = interpreter.to_primitive(value, "number")?;
+            match *prim {
+                ValueData::Symbol(_) => {
+                    return Err(interpreter.type_error("Cannot convert a Symbol value to a number"))
+                }
+                _ => prim.to_num(),
+            }
+        }
+        None => 0.0,
+    };
+    // `new Number()` calls this with a new object made from Number.prototype, which gets wrapped around the number
+    if this.is_object()
+        && this.get_private_field_slice("PrimitiveValue").is_undefined()
+        && Gc::ptr_eq(&this.get_field_slice(INSTANCE_PROTOTYPE), &f.get_field_slice(PROTOTYPE))
+    {
+        this.set_private_field_slice("PrimitiveValue", to_value(num));
+        return Ok(this);
+    }
+    Ok(to_value(num))
+}
+
+/// Format the number in the radix given by the first argument, or base 10
+/// https://tc39.es/ecma262/#sec-number.prototype.tostring
+pub fn to_string(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let num = this_number_value(&this, "toString", interpreter)?;
+    let radix = match args.first() {
+        Some(radix) if !radix.is_undefined() => to_integer_or_infinity(&arg(&args, 0), interpreter)?,
+        _ => 10.0,
+    };
+    if !(2.0..=36.0).contains(&radix) {
+        return Err(interpreter.range_error("toString() radix must be between 2 and 36"));
+    }
+    Ok(to_value(number_to_string_radix(num, radix as u32)))
+}
+
+/// Format the number, which would use the conventions of a locale if there were locale support
+pub fn to_locale_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_to_string(this_number_value(&this, "toLocaleString", interpreter)?)))
+}
+
+/// Get the number itself
+pub fn value_of(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_number_value(&this, "valueOf", interpreter)?))
+}
+
+/// Format the number with a fixed number of digits after the decimal point
+/// https://tc39.es/ecma262/#sec-number.prototype.tofixed
+pub fn to_fixed(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let num = this_number_value(&this, "toFixed", interpreter)?;
+    let fraction_digits = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    if !(0.0..=100.0).contains(&fraction_digits) {
+        return Err(interpreter.range_error("toFixed() digits argument must be between 0 and 100"));
+    }
+    if !num.is_finite() || num.abs() >= 1e21 {
+        return Ok(to_value(number_to_string(num)));
+    }
+    let fraction_digits = fraction_digits as usize;
+    let sign = if num < 0.0 { "-" } else { "" };
+    let x = num.abs();
+    // n is the integer closest to x × 10^f, as digits
+    let mut n = if x == 0.0 {
+        "0".to_string()
+    } else {
+        let (digits, point) = exact_digits(x);
+        let keep = point + fraction_digits as i32;
+        if keep < 0 {
+            "0".to_string()
+        } else if keep as usize >= digits.len() {
+            format!("{}{}", digits, "0".repeat(keep as usize - digits.len()))
+        } else {
+            let (rounded, _) = round_digits(&digits, keep as usize);
+            if rounded.is_empty() { "0".to_string() } else { rounded }
+        }
+    };
+    if fraction_digits == 0 {
+        return Ok(to_value(format!("{}{}", sign, n)));
+    }
+    if n.len() <= fraction_digits {
+        n = format!("{}{}", "0".repeat(fraction_digits + 1 - n.len()), n);
+    }
+    let (integer, fraction) = n.split_at(n.len() - fraction_digits);
+    Ok(to_value(format!("{}{}.{}", sign, integer, fraction)))
+}
+
+/// Format the number in exponential notation, with a number of digits after the decimal point
+/// or as many as are needed to represent it
+/// https://tc39.es/ecma262/#sec-number.prototype.toexponential
+pub fn to_exponential(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let num = this_number_value(&this, "toExponential", interpreter)?;
+    let fraction_digits = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    if !num.is_finite() {
+        return Ok(to_value(number_to_string(num)));
+    }
+    if !(0.0..=100.0).contains(&fraction_digits) {
+        return Err(interpreter.range_error("toExponential() argument must be between 0 and 100"));
+    }
+    let sign = if num < 0.0 { "-" } else { "" };
+    let x = num.abs();
+    let (digits, exponent) = match args.first() {
+        Some(value) if !value.is_undefined() => to_precision_digits(x, fraction_digits as usize + 1),
+        _ if x == 0.0 => ("0".to_string(), 0),
+        _ => shortest_digits(x),
+    };
+    Ok(to_value(format!("{}{}", sign, exponential(&digits, exponent))))
+}
+
+/// Format the number with a number of significant digits
+/// https://tc39.es/ecma262/#sec-number.prototype.toprecision
+pub fn to_precision(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let num = this_number_value(&this, "toPrecision", interpreter)?;
+    match args.first() {
+        Some(value) if !value.is_undefined() => (),
+        _ => return Ok(to_value(number_to_string(num))),
+    }
+    let precision = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    if !num.is_finite() {
+        return Ok(to_value(number_to_string(num)));
+    }
+    if !(1.0..=100.0).contains(&precision) {
+        return Err(interpreter.range_error("toPrecision() argument must be between 1 and 100"));
+    }
+    let precision = precision as usize;
+    let sign = if num < 0.0 { "-" } else { "" };
+    let (digits, exponent) = to_precision_digits(num.abs(), precision);
+    let formatted = if exponent < -6 || exponent >= precision as i32 {
+        exponential(&digits, exponent)
+    } else if exponent >= 0 {
+        let integer_digits = exponent as usize + 1;
+        if integer_digits == precision {
+            digits
+        } else {
+            format!("{}.{}", &digits[..integer_digits], &digits[integer_digits..])
+        }
+    } else {
+        format!("0.{}{}", "0".repeat((-exponent - 1) as usize), digits)
+    };
+    Ok(to_value(format!("{}{}", sign, formatted)))
+}
+
+/// Get the first argument as a number without converting it, or None if it isn't a number
+fn number_value_arg(args: &[Value]) -> Option<f64> {
+    match args.first().map(|value| &**value) {
+        Some(ValueData::Number(num)) => Some(*num),
+        Some(ValueData::Integer(num)) => Some(f64::from(*num)),
+        _ => None,
+    }
+}
+
+/// Returns true if the argument is a finite number
+/// https://tc39.es/ecma262/#sec-number.isfinite
+pub fn is_finite(_: Value, _: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_value_arg(&args).is_some_and(f64::is_finite)))
+}
+
+/// Returns true if the argument is `NaN`
+/// https://tc39.es/ecma262/#sec-number.isnan
+pub fn is_nan(_: Value, _: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_value_arg(&args).is_some_and(f64::is_nan)))
+}
+
+/// Returns true if the argument is a number which is an integer
+/// https://tc39.es/ecma262/#sec-number.isinteger
+pub fn is_integer(_: Value, _: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_value_arg(&args).is_some_and(|num| num.is_finite() && num.trunc() == num)))
+}
+
+/// Returns true if the argument is an integer which can be represented exactly
+/// https://tc39.es/ecma262/#sec-number.issafeinteger
+pub fn is_safe_integer(_: Value, _: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(number_value_arg(&args).is_some_and(|num| {
+        num.is_finite() && num.trunc() == num && num.abs() <= MAX_SAFE_INTEGER
+    })))
+}
+
+/// Parse the longest prefix of a string which is a decimal number, ignoring leading white space
+/// https://tc39.es/ecma262/#sec-parsefloat-string
+pub fn parse_float(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = match args.first() {
+        Some(value) => interpreter.to_string(value)?,
+        None => "undefined".to_string(),
+    };
+    let string = string.trim_start_matches(is_trimmable);
+    Ok(to_value(match decimal_prefix(string) {
+        Some(length) => parse_decimal(&string[..length]),
+        None => f64::NAN,
+    }))
+}
+
+/// Create a new `Number` object
+pub fn _create(global: &Value) -> Value {
+    let number = to_value(make_number as NativeFunctionData);
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_private_field_slice("PrimitiveValue", to_value(0.0));
+    proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    proto.set_field_slice("toLocaleString", to_value(to_locale_string as NativeFunctionData));
+    proto.set_field_slice("valueOf", to_value(value_of as NativeFunctionData));
+    proto.set_field_slice("toFixed", to_value(to_fixed as NativeFunctionData));
+    proto.set_field_slice("toExponential", to_value(to_exponential as NativeFunctionData));
+    proto.set_field_slice("toPrecision", to_value(to_precision as NativeFunctionData));
+    proto.set_field_slice("constructor", number.clone());
+    number.set_field_slice(PROTOTYPE, proto);
+    number.set_field_slice("length", to_value(1i32));
+    number.set_field_slice("MAX_SAFE_INTEGER", to_value(MAX_SAFE_INTEGER));
+    number.set_field_slice("MIN_SAFE_INTEGER", to_value(-MAX_SAFE_INTEGER));
+    number.set_field_slice("MAX_VALUE", to_value(f64::MAX));
+    number.set_field_slice("MIN_VALUE", to_value(f64::from_bits(1)));
+    number.set_field_slice("EPSILON", to_value(f64::EPSILON));
+    number.set_field_slice("POSITIVE_INFINITY", to_value(f64::INFINITY));
+    number.set_field_slice("NEGATIVE_INFINITY", to_value(f64::NEG_INFINITY));
+    number.set_field_slice("NaN", to_value(f64::NAN));
+    number.set_field_slice("isFinite", to_value(is_finite as NativeFunctionData));
+    number.set_field_slice("isNaN", to_value(is_nan as NativeFunctionData));
+    number.set_field_slice("isInteger", to_value(is_integer as NativeFunctionData));
+    number.set_field_slice("isSafeInteger", to_value(is_safe_integer as NativeFunctionData));
+    number.set_field_slice("parseFloat", to_value(parse_float as NativeFunctionData));
+    number
+}
+
+/// Initialise the global object with the `Number` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Number", _create(global));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_number_to_string() {
+        assert_eq!(number_to_string(1e21), "1e+21");
+        assert_eq!(number_to_string(1e20), "100000000000000000000");
+        assert_eq!(number_to_string(0.000001), "0.000001");
+        assert_eq!(number_to_string(1e-7), "1e-7");
+        assert_eq!(number_to_string(-0.0), "0");
+        assert_eq!(number_to_string(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(number_to_string(123.456), "123.456");
+        assert_eq!(number_to_string(1.5e300), "1.5e+300");
+        assert_eq!(number_to_string(5e-324), "5e-324");
+        assert_eq!(number_to_string(f64::NEG_INFINITY), "-Infinity");
+    }
+
+    #[test]
+    fn check_number_to_string_radix() {
+        assert_eq!(number_to_string_radix(255.0, 16), "ff");
+        assert_eq!(number_to_string_radix(-255.0, 2), "-11111111");
+        assert_eq!(number_to_string_radix(0.5, 2), "0.1");
+        assert_eq!(number_to_string_radix(0.1, 2), "0.0001100110011001100110011001100110011001100110011001101");
+        assert_eq!(number_to_string_radix(35.5, 36), "z.i");
+    }
+
+    #[test]
+    fn check_string_to_number() {
+        assert_eq!(string_to_number("  12  "), 12.0);
+        assert_eq!(string_to_number(""), 0.0);
+        assert_eq!(string_to_number("0x1F"), 31.0);
+        assert_eq!(string_to_number("0b101"), 5.0);
+        assert_eq!(string_to_number("-Infinity"), f64::NEG_INFINITY);
+        assert_eq!(string_to_number(".5e1"), 5.0);
+        assert!(string_to_number("inf").is_nan());
+        assert!(string_to_number("12px").is_nan());
+        assert!(string_to_number("-0x1").is_nan());
+    }
+}
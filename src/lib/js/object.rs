@@ -1,12 +1,201 @@
 use gc::Gc;
 use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::iter::FromIterator;
+use crate::exec::Interpreter;
 use crate::js::function::NativeFunctionData;
-use crate::js::value::{from_value, to_value, FromValue, ResultValue, ToValue, Value, ValueData};
+use crate::js::{array, date, error, iterator, proxy, regexp, typed_array};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, same_value, to_value, ResultValue, ToValue, Value, ValueData};
 
 pub static PROTOTYPE: &'static str = "prototype";
 pub static INSTANCE_PROTOTYPE: &'static str = "__proto__";
 
-pub type ObjectData = HashMap<String, Property>;
+/// The properties of an object, which are kept in the order they were added in
+/// https://tc39.es/ecma262/#sec-ordinaryownpropertykeys
+#[derive(Trace, Finalize, Clone, Debug)]
+pub struct ObjectData {
+    /// The properties in the order they were added
+    entries: Vec<(PropertyKey, Property)>,
+    /// The position of each property in `entries`
+    positions: HashMap<PropertyKey, usize>,
+    /// If new properties can be added to the object, this is the [[Extensible]] internal slot
+    pub extensible: bool,
+}
+
+impl ObjectData {
+    /// Make a new extensible object with no properties
+    pub fn new() -> ObjectData {
+        ObjectData {
+            entries: Vec::new(),
+            positions: HashMap::new(),
+            extensible: true,
+        }
+    }
+
+    /// Get a property
+    pub fn get(&self, key: &PropertyKey) -> Option<&Property> {
+        self.positions.get(key).map(|&position| &self.entries[position].1)
+    }
+
+    /// Get a property to change it in place
+    pub fn get_mut(&mut self, key: &PropertyKey) -> Option<&mut Property> {
+        match self.positions.get(key) {
+            Some(&position) => Some(&mut self.entries[position].1),
+            None => None,
+        }
+    }
+
+    /// Returns true if there is a property with the key
+    pub fn contains_key(&self, key: &PropertyKey) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Set a property, which keeps its position if it already exists, returning the old property
+    pub fn insert(&mut self, key: PropertyKey, prop: Property) -> Option<Property> {
+        match self.positions.get(&key) {
+            Some(&position) => Some(std::mem::replace(&mut self.entries[position].1, prop)),
+            None => {
+                self.positions.insert(key.clone(), self.entries.len());
+                self.entries.push((key, prop));
+                None
+            }
+        }
+    }
+
+    /// Set the value of a property, keeping its attributes if it is a data property, or adding a data property
+    /// which is writable, enumerable and configurable
+    pub fn set_value(&mut self, key: PropertyKey, value: Value) {
+        match self.get_mut(&key) {
+            Some(prop) if !prop.is_accessor() => prop.value = value,
+            _ => {
+                self.insert(key, Property::data(value));
+            }
+        }
+    }
+
+    /// Remove a property, returning it
+    pub fn remove(&mut self, key: &PropertyKey) -> Option<Property> {
+        let position = self.positions.remove(key)?;
+        let (_, prop) = self.entries.remove(position);
+        for (key, _) in &self.entries[position..] {
+            *self.positions.get_mut(key).unwrap() -= 1;
+        }
+        Some(prop)
+    }
+
+    /// Keep only the properties where the predicate returns true
+    pub fn retain<F: FnMut(&PropertyKey, &Property) -> bool>(&mut self, mut keep: F) {
+        self.entries.retain(|(key, prop)| keep(key, prop));
+        self.positions = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(position, (key, _))| (key.clone(), position))
+            .collect();
+    }
+
+    /// Iterate over the properties in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&PropertyKey, &Property)> {
+        self.entries.iter().map(|(key, prop)| (key, prop))
+    }
+
+    /// Iterate over the properties in the order they were added, to change them in place
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&PropertyKey, &mut Property)> {
+        self.entries.iter_mut().map(|(key, prop)| (&*key, prop))
+    }
+
+    /// Get the number of properties
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no properties
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for ObjectData {
+    fn default() -> ObjectData {
+        ObjectData::new()
+    }
+}
+
+impl FromIterator<(PropertyKey, Property)> for ObjectData {
+    fn from_iter<I: IntoIterator<Item = (PropertyKey, Property)>>(iter: I) -> ObjectData {
+        let mut data = ObjectData::new();
+        for (key, prop) in iter {
+            data.insert(key, prop);
+        }
+        data
+    }
+}
+
+/// The key of a property, which is either a string or a symbol
+/// https://tc39.es/ecma262/#sec-object-type
+#[derive(Trace, Finalize, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PropertyKey {
+    String(String),
+    Symbol(Symbol),
+}
+
+impl From<String> for PropertyKey {
+    fn from(key: String) -> PropertyKey {
+        PropertyKey::String(key)
+    }
+}
+
+impl<'a> From<&'a str> for PropertyKey {
+    fn from(key: &'a str) -> PropertyKey {
+        PropertyKey::String(key.to_string())
+    }
+}
+
+impl<'a> From<&'a String> for PropertyKey {
+    fn from(key: &'a String) -> PropertyKey {
+        PropertyKey::String(key.clone())
+    }
+}
+
+impl From<Symbol> for PropertyKey {
+    fn from(key: Symbol) -> PropertyKey {
+        PropertyKey::Symbol(key)
+    }
+}
+
+impl PartialEq<str> for PropertyKey {
+    fn eq(&self, other: &str) -> bool {
+        match *self {
+            PropertyKey::String(ref key) => key == other,
+            PropertyKey::Symbol(_) => false,
+        }
+    }
+}
+
+impl<'a> PartialEq<&'a str> for PropertyKey {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == *other
+    }
+}
+
+impl ToValue for PropertyKey {
+    fn to_value(&self) -> Value {
+        match *self {
+            PropertyKey::String(ref key) => to_value(key.clone()),
+            PropertyKey::Symbol(ref symbol) => Gc::new(ValueData::Symbol(symbol.clone())),
+        }
+    }
+}
+
+impl Display for PropertyKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PropertyKey::String(ref key) => write!(f, "{}", key),
+            PropertyKey::Symbol(ref key) => write!(f, "[{}]", key),
+        }
+    }
+}
 
 /// A Javascript Property AKA The Property Descriptor
 /// [[SPEC] - The Property Descriptor Specification Type](https://tc39.github.io/ecma262/#sec-property-descriptor-specification-type)   
@@ -39,6 +228,20 @@ impl Property {
             set: Gc::new(ValueData::Undefined),
         }
     }
+
+    /// Make a new data property which is writable, enumerable and configurable, like the ones made by assignment
+    pub fn data(value: Value) -> Property {
+        let mut prop = Property::new(value);
+        prop.configurable = true;
+        prop.enumerable = true;
+        prop.writable = true;
+        prop
+    }
+
+    /// Returns true if this is an accessor property, which has a getter or a setter instead of a value
+    pub fn is_accessor(&self) -> bool {
+        !self.get.is_undefined() || !self.set.is_undefined()
+    }
 }
 
 impl ToValue for Property {
@@ -54,90 +257,856 @@ impl ToValue for Property {
     }
 }
 
-impl FromValue for Property {
-    fn from_value(v: Value) -> Result<Property, &'static str> {
-        Ok(Property {
-            configurable: from_value(v.get_field_slice("configurable")).unwrap(),
-            enumerable: from_value(v.get_field_slice("enumerable")).unwrap(),
-            writable: from_value(v.get_field_slice("writable")).unwrap(),
-            value: v.get_field_slice("value"),
-            get: v.get_field_slice("get"),
-            set: v.get_field_slice("set"),
-        })
+/// A property descriptor, which is a partial `Property` where the missing fields are `None`
+/// https://tc39.es/ecma262/#sec-property-descriptor-specification-type
+#[derive(Clone, Default)]
+pub struct PropertyDescriptor {
+    pub value: Option<Value>,
+    pub writable: Option<bool>,
+    pub get: Option<Value>,
+    pub set: Option<Value>,
+    pub enumerable: Option<bool>,
+    pub configurable: Option<bool>,
+}
+
+impl PropertyDescriptor {
+    /// Returns true if the descriptor has a getter or setter field
+    /// https://tc39.es/ecma262/#sec-isaccessordescriptor
+    pub fn is_accessor(&self) -> bool {
+        self.get.is_some() || self.set.is_some()
+    }
+
+    /// Returns true if the descriptor has a value or writable field
+    /// https://tc39.es/ecma262/#sec-isdatadescriptor
+    pub fn is_data(&self) -> bool {
+        self.value.is_some() || self.writable.is_some()
+    }
+
+    /// Make a property from the descriptor, where the missing fields are `false` or `undefined`
+    /// https://tc39.es/ecma262/#sec-completepropertydescriptor
+    pub fn complete(self) -> Property {
+        let mut prop = Property::new(self.value.unwrap_or_else(|| Gc::new(ValueData::Undefined)));
+        prop.writable = self.writable.unwrap_or(false);
+        prop.get = self.get.unwrap_or_else(|| Gc::new(ValueData::Undefined));
+        prop.set = self.set.unwrap_or_else(|| Gc::new(ValueData::Undefined));
+        prop.enumerable = self.enumerable.unwrap_or(false);
+        prop.configurable = self.configurable.unwrap_or(false);
+        prop
+    }
+}
+
+/// Read a property descriptor from an object like `{ value: 1, writable: true }`
+/// https://tc39.es/ecma262/#sec-topropertydescriptor
+pub fn to_property_descriptor(obj: &Value, interpreter: &mut Interpreter) -> Result<PropertyDescriptor, Value> {
+    if !obj.is_object() && !obj.is_function() {
+        return Err(interpreter.type_error(format!("Property description must be an object: {}", obj)));
+    }
+    let mut field = |name: &str| -> Result<Option<Value>, Value> {
+        if interpreter.has_property(obj, &name.into())? {
+            Ok(Some(interpreter.get_field(obj, name)?))
+        } else {
+            Ok(None)
+        }
+    };
+    let desc = PropertyDescriptor {
+        enumerable: field("enumerable")?.map(|value| value.is_true()),
+        configurable: field("configurable")?.map(|value| value.is_true()),
+        value: field("value")?,
+        writable: field("writable")?.map(|value| value.is_true()),
+        get: field("get")?,
+        set: field("set")?,
+    };
+    for (name, accessor) in [("Getter", &desc.get), ("Setter", &desc.set)] {
+        if let Some(ref accessor) = *accessor {
+            if !accessor.is_function() && !accessor.is_undefined() {
+                return Err(interpreter.type_error(format!("{} must be a function: {}", name, accessor)));
+            }
+        }
+    }
+    if desc.is_accessor() && desc.is_data() {
+        return Err(interpreter.type_error(
+            "Invalid property descriptor. Cannot both specify accessors and a value or writable attribute",
+        ));
+    }
+    Ok(desc)
+}
+
+/// Make an object like `{ value: 1, writable: true, enumerable: true, configurable: true }` describing a property
+/// https://tc39.es/ecma262/#sec-frompropertydescriptor
+pub fn from_property(prop: &Property, global: &Value) -> Value {
+    let desc = ValueData::new_obj(Some(global));
+    if prop.is_accessor() {
+        desc.set_field_slice("get", prop.get.clone());
+        desc.set_field_slice("set", prop.set.clone());
+    } else {
+        desc.set_field_slice("value", prop.value.clone());
+        desc.set_field_slice("writable", to_value(prop.writable));
+    }
+    desc.set_field_slice("enumerable", to_value(prop.enumerable));
+    desc.set_field_slice("configurable", to_value(prop.configurable));
+    desc
+}
+
+/// Make an object describing a property descriptor, with only the fields the descriptor has
+/// https://tc39.es/ecma262/#sec-frompropertydescriptor
+pub fn from_property_descriptor(desc: &PropertyDescriptor, global: &Value) -> Value {
+    let obj = ValueData::new_obj(Some(global));
+    if let Some(ref value) = desc.value {
+        obj.set_field_slice("value", value.clone());
+    }
+    if let Some(writable) = desc.writable {
+        obj.set_field_slice("writable", to_value(writable));
+    }
+    if let Some(ref get) = desc.get {
+        obj.set_field_slice("get", get.clone());
+    }
+    if let Some(ref set) = desc.set {
+        obj.set_field_slice("set", set.clone());
+    }
+    if let Some(enumerable) = desc.enumerable {
+        obj.set_field_slice("enumerable", to_value(enumerable));
+    }
+    if let Some(configurable) = desc.configurable {
+        obj.set_field_slice("configurable", to_value(configurable));
+    }
+    obj
+}
+
+/// Convert a value to a property key, where objects are converted to strings
+/// https://tc39.es/ecma262/#sec-topropertykey
+pub fn to_property_key(value: &Value, interpreter: &mut Interpreter) -> Result<PropertyKey, Value> {
+    let key = interpreter.to_primitive(value, "string")?;
+    Ok(key.to_property_key())
+}
+
+/// Define or change an own property of an object which isn't a proxy, returning false if the current property
+/// or the object doesn't allow it. Use `Interpreter::define_own_property` for any object.
+/// Arrays keep their `length` in step with their elements
+/// https://tc39.es/ecma262/#sec-array-exotic-objects-defineownproperty-p-desc
+pub fn define_own_property(obj: &Value, key: PropertyKey, desc: PropertyDescriptor, interpreter: &Interpreter) -> Result<bool, Value> {
+    if let Some(index) = typed_array::element_index(obj, &key) {
+        return Ok(typed_array::define_element(obj, index, &desc));
+    }
+    if !obj.is_array() {
+        return Ok(validate_and_apply_property_descriptor(obj, key, desc));
+    }
+    let length = obj.get_own_prop("length").expect("arrays have a length");
+    if key == "length" {
+        let new_length = match desc.value {
+            Some(ref value) => match array::valid_length(value) {
+                Some(new_length) => new_length,
+                None => return Err(interpreter.range_error("Invalid array length")),
+            },
+            None => return Ok(validate_and_apply_property_descriptor(obj, key, desc)),
+        };
+        let old_length = length.value.to_num();
+        if !validate_and_apply_property_descriptor(obj, key, desc) {
+            return Ok(false);
+        }
+        if f64::from(new_length) < old_length {
+            // Setting the length deletes the elements past the end, and fails if one of them can't be deleted
+            obj.set_field_slice("length", to_value(f64::from(new_length)));
+            return Ok(obj.get_field_slice("length").to_num() == f64::from(new_length));
+        }
+        return Ok(true);
+    }
+    if let Some(index) = array::array_index(&key) {
+        if f64::from(index) >= length.value.to_num() {
+            if !length.writable || !validate_and_apply_property_descriptor(obj, key, desc) {
+                return Ok(false);
+            }
+            obj.set_field_slice("length", to_value(f64::from(index) + 1.0));
+            return Ok(true);
+        }
     }
+    Ok(validate_and_apply_property_descriptor(obj, key, desc))
 }
 
-/// Create a new object
-pub fn make_object(_: Value, _: Value, _args: Vec<Value>) -> ResultValue {
-    Ok(Gc::new(ValueData::Undefined))
+/// Define or change an own property of an ordinary object, where the missing fields of the descriptor keep
+/// their current values, or default to `false` and `undefined` for a new property
+/// https://tc39.es/ecma262/#sec-validateandapplypropertydescriptor
+fn validate_and_apply_property_descriptor(obj: &Value, key: PropertyKey, desc: PropertyDescriptor) -> bool {
+    let current = obj.get_own_prop(key.clone());
+    if !is_compatible_property_descriptor(obj.is_extensible(), &desc, current.as_ref()) {
+        return false;
+    }
+    let mut prop = match current {
+        Some(current) => current,
+        None => {
+            obj.set_prop(key, desc.complete());
+            return true;
+        }
+    };
+    // Changing between a data property and an accessor property keeps only the enumerable and configurable fields
+    if desc.is_accessor() && !prop.is_accessor() {
+        prop.value = Gc::new(ValueData::Undefined);
+        prop.writable = false;
+    } else if desc.is_data() && prop.is_accessor() {
+        prop.get = Gc::new(ValueData::Undefined);
+        prop.set = Gc::new(ValueData::Undefined);
+    }
+    if let Some(value) = desc.value {
+        prop.value = value;
+    }
+    if let Some(writable) = desc.writable {
+        prop.writable = writable;
+    }
+    if let Some(get) = desc.get {
+        prop.get = get;
+    }
+    if let Some(set) = desc.set {
+        prop.set = set;
+    }
+    if let Some(enumerable) = desc.enumerable {
+        prop.enumerable = enumerable;
+    }
+    if let Some(configurable) = desc.configurable {
+        prop.configurable = configurable;
+    }
+    obj.set_prop(key, prop);
+    true
+}
+
+/// Returns true if a property can be defined with the descriptor, given the current property if there is one
+/// and whether the object is extensible, without changing anything
+/// A property which isn't configurable can only have its value changed, and only if it is writable
+/// https://tc39.es/ecma262/#sec-iscompatiblepropertydescriptor
+pub fn is_compatible_property_descriptor(extensible: bool, desc: &PropertyDescriptor, current: Option<&Property>) -> bool {
+    let current = match current {
+        Some(current) => current,
+        None => return extensible,
+    };
+    if current.configurable {
+        return true;
+    }
+    let changes = |field: &Option<Value>, value: &Value| field.as_ref().is_some_and(|field| !same_value(field, value));
+    if desc.configurable == Some(true) || desc.enumerable.is_some_and(|enumerable| enumerable != current.enumerable) {
+        return false;
+    }
+    if (desc.is_accessor() && !current.is_accessor()) || (desc.is_data() && current.is_accessor()) {
+        return false;
+    }
+    if current.is_accessor() && (changes(&desc.get, &current.get) || changes(&desc.set, &current.set)) {
+        return false;
+    }
+    if !current.is_accessor() && !current.writable && (desc.writable == Some(true) || changes(&desc.value, &current.value)) {
+        return false;
+    }
+    true
+}
+
+/// Define a property like `Object.defineProperty`, throwing if it can't be defined
+/// https://tc39.es/ecma262/#sec-definepropertyorthrow
+pub fn define_property_or_throw(obj: &Value, key: PropertyKey, desc: PropertyDescriptor, interpreter: &mut Interpreter) -> Result<(), Value> {
+    if interpreter.define_own_property(obj, key.clone(), desc)? {
+        Ok(())
+    } else {
+        Err(interpreter.type_error(format!("Cannot redefine property: {}", key)))
+    }
+}
+
+/// Set a property like an assignment in strict mode, throwing if it is read-only or can't be added
+/// https://tc39.es/ecma262/#sec-set-o-p-v-throw
+pub fn set_or_throw(obj: &Value, key: PropertyKey, value: Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    if interpreter.set(obj, &key, value, obj)? {
+        Ok(())
+    } else {
+        Err(interpreter.type_error(format!("Cannot assign to read only property '{}' of object", key)))
+    }
+}
+
+/// Remove a property like the `delete` operator in strict mode, throwing if it isn't configurable
+/// https://tc39.es/ecma262/#sec-deletepropertyorthrow
+pub fn delete_property_or_throw(obj: &Value, key: PropertyKey, interpreter: &mut Interpreter) -> Result<(), Value> {
+    if interpreter.delete_property(obj, &key)? {
+        Ok(())
+    } else {
+        Err(interpreter.type_error(format!("Cannot delete property '{}' of object", key)))
+    }
+}
+
+/// Add a data property which is writable, enumerable and configurable, like in an object literal
+/// https://tc39.es/ecma262/#sec-createdataproperty
+pub fn create_data_property(obj: &Value, key: PropertyKey, value: Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    define_property_or_throw(
+        obj,
+        key,
+        PropertyDescriptor {
+            value: Some(value),
+            writable: Some(true),
+            enumerable: Some(true),
+            configurable: Some(true),
+            ..PropertyDescriptor::default()
+        },
+        interpreter,
+    )
+}
+
+/// Get the argument at an index, which must be an object
+fn object_arg(args: &[Value], index: usize, method: &str, interpreter: &Interpreter) -> ResultValue {
+    let obj = arg(args, index);
+    if obj.is_object() || obj.is_function() {
+        Ok(obj)
+    } else {
+        Err(interpreter.type_error(format!("Object.{} called on non-object", method)))
+    }
+}
+
+/// Get the argument at an index, which can be anything but `null` or `undefined`
+fn coercible_arg(args: &[Value], index: usize, interpreter: &Interpreter) -> ResultValue {
+    let value = arg(args, index);
+    if value.is_null_or_undefined() {
+        Err(interpreter.type_error(format!("Cannot convert {} to object", value)))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Convert a value to an object, or make a new object if it is `null` or `undefined`
+/// https://tc39.es/ecma262/#sec-object-value
+pub fn make_object(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let value = arg(&args, 0);
+    if value.is_null_or_undefined() {
+        Ok(ValueData::new_obj(Some(&interpreter.get_global_object())))
+    } else {
+        interpreter.to_object(&value)
+    }
 }
 
 /// Get the prototype of an object
-pub fn get_proto_of(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let obj = args.get(0).unwrap();
-    Ok(obj.get_field_slice(INSTANCE_PROTOTYPE))
+/// https://tc39.es/ecma262/#sec-object.getprototypeof
+pub fn get_proto_of(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = interpreter.to_object(&arg(&args, 0))?;
+    interpreter.get_prototype_of(&obj)
 }
 
 /// Set the prototype of an object
-pub fn set_proto_of(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let obj = args.get(0).unwrap().clone();
-    let proto = args.get(1).unwrap().clone();
+/// https://tc39.es/ecma262/#sec-object.setprototypeof
+pub fn set_proto_of(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = coercible_arg(&args, 0, interpreter)?;
+    let proto = arg(&args, 1);
+    if !proto.is_object() && !proto.is_function() && !proto.is_null() {
+        return Err(interpreter.type_error(format!("Object prototype may only be an Object or null: {}", proto)));
+    }
+    if !obj.is_object() && !obj.is_function() {
+        return Ok(obj);
+    }
+    if interpreter.set_prototype_of(&obj, proto)? {
+        return Ok(obj);
+    }
+    Err(interpreter.type_error(if proxy::is_proxy(&obj) {
+        "'setPrototypeOf' on proxy: trap returned falsish"
+    } else if !obj.is_extensible() {
+        "Cannot set the prototype of an object which is not extensible"
+    } else {
+        // The prototype chain can't loop back to the object
+        "Cyclic __proto__ value"
+    }))
+}
+
+/// Set the prototype of an object which isn't a proxy, returning false if it isn't extensible or the
+/// prototype chain would loop back to the object. Use `Interpreter::set_prototype_of` for any object.
+/// https://tc39.es/ecma262/#sec-ordinarysetprototypeof
+pub fn ordinary_set_prototype_of(obj: &Value, proto: Value) -> bool {
+    let current = obj.get_field_slice(INSTANCE_PROTOTYPE);
+    if same_value(&current, &proto) || (current.is_null_or_undefined() && proto.is_null()) {
+        return true;
+    }
+    if !obj.is_extensible() {
+        return false;
+    }
+    let mut link = proto.clone();
+    while link.is_object() || link.is_function() {
+        if Gc::ptr_eq(&link, obj) {
+            return false;
+        }
+        // Proxies can't be followed without running their traps
+        if proxy::is_proxy(&link) {
+            break;
+        }
+        link = link.get_field_slice(INSTANCE_PROTOTYPE);
+    }
     obj.set_field_slice(INSTANCE_PROTOTYPE, proto);
+    true
+}
+
+/// Define a property in an object from a descriptor
+/// https://tc39.es/ecma262/#sec-object.defineproperty
+pub fn define_prop(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = object_arg(&args, 0, "defineProperty", interpreter)?;
+    let key = to_property_key(&arg(&args, 1), interpreter)?;
+    let desc = to_property_descriptor(&arg(&args, 2), interpreter)?;
+    define_property_or_throw(&obj, key, desc, interpreter)?;
+    Ok(obj)
+}
+
+/// Define the properties of an object from an object of descriptors
+/// https://tc39.es/ecma262/#sec-objectdefineproperties
+fn object_define_properties(obj: &Value, props: &Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    let props = interpreter.to_object(props)?;
+    // Every descriptor is read before any property is defined
+    let mut descriptors = Vec::new();
+    for key in interpreter.own_property_keys(&props)? {
+        if interpreter.get_own_property(&props, &key)?.is_some_and(|prop| prop.enumerable) {
+            let desc = interpreter.get_field(&props, key.clone())?;
+            descriptors.push((key, to_property_descriptor(&desc, interpreter)?));
+        }
+    }
+    for (key, desc) in descriptors {
+        define_property_or_throw(obj, key, desc, interpreter)?;
+    }
+    Ok(())
+}
+
+/// Define properties in an object from an object of descriptors
+/// https://tc39.es/ecma262/#sec-object.defineproperties
+pub fn define_properties(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = object_arg(&args, 0, "defineProperties", interpreter)?;
+    object_define_properties(&obj, &arg(&args, 1), interpreter)?;
     Ok(obj)
 }
 
-/// Define a property in an object
-pub fn define_prop(_: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let obj = args.get(0).unwrap();
-    let prop = from_value::<String>(args.get(1).unwrap().clone()).unwrap();
-    let desc = from_value::<Property>(args.get(2).unwrap().clone()).unwrap();
-    obj.set_prop(prop, desc);
-    Ok(Gc::new(ValueData::Undefined))
+/// Create an object with the given prototype, and properties from an object of descriptors
+/// https://tc39.es/ecma262/#sec-object.create
+pub fn create(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let proto = arg(&args, 0);
+    if !proto.is_object() && !proto.is_function() && !proto.is_null() {
+        return Err(interpreter.type_error(format!("Object prototype may only be an Object or null: {}", proto)));
+    }
+    let obj = ValueData::new_obj_from_prototype(proto);
+    let props = arg(&args, 1);
+    if !props.is_undefined() {
+        object_define_properties(&obj, &props, interpreter)?;
+    }
+    Ok(obj)
 }
 
-/// To string
-pub fn to_string(this: Value, _: Value, _: Vec<Value>) -> ResultValue {
-    Ok(to_value(this.to_string()))
+/// Get the descriptor of an own property, or undefined if there isn't one
+/// https://tc39.es/ecma262/#sec-object.getownpropertydescriptor
+pub fn get_own_property_descriptor(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = interpreter.to_object(&coercible_arg(&args, 0, interpreter)?)?;
+    let key = to_property_key(&arg(&args, 1), interpreter)?;
+    Ok(match interpreter.get_own_property(&obj, &key)? {
+        Some(prop) => from_property(&prop, &interpreter.get_global_object()),
+        None => Gc::new(ValueData::Undefined),
+    })
 }
 
-/// Check if it has a property
-pub fn has_own_prop(this: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    let prop = if args.len() == 0 {
-        None
-    } else {
-        from_value::<String>(args.get(0).unwrap().clone()).ok()
-    };
+/// Get an object of the descriptors of every own property
+/// https://tc39.es/ecma262/#sec-object.getownpropertydescriptors
+pub fn get_own_property_descriptors(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = interpreter.to_object(&coercible_arg(&args, 0, interpreter)?)?;
+    let global = interpreter.get_global_object();
+    let descriptors = ValueData::new_obj(Some(&global));
+    for key in interpreter.own_property_keys(&obj)? {
+        if let Some(prop) = interpreter.get_own_property(&obj, &key)? {
+            create_data_property(&descriptors, key, from_property(&prop, &global), interpreter)?;
+        }
+    }
+    Ok(descriptors)
+}
+
+/// Get the own string keys of an object, including the ones which aren't enumerable
+/// https://tc39.es/ecma262/#sec-object.getownpropertynames
+pub fn get_own_property_names(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = interpreter.to_object(&coercible_arg(&args, 0, interpreter)?)?;
+    let names = interpreter
+        .own_property_keys(&obj)?
+        .into_iter()
+        .filter_map(|key| match key {
+            PropertyKey::String(ref name) => Some(to_value(name.clone())),
+            PropertyKey::Symbol(_) => None,
+        })
+        .collect();
+    Ok(array::new_array(&interpreter.get_global_object(), names))
+}
+
+/// Get the own symbol keys of an object
+/// https://tc39.es/ecma262/#sec-object.getownpropertysymbols
+pub fn get_own_property_symbols(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = interpreter.to_object(&coercible_arg(&args, 0, interpreter)?)?;
+    let symbols = interpreter
+        .own_property_keys(&obj)?
+        .into_iter()
+        .filter_map(|key| match key {
+            PropertyKey::Symbol(ref symbol) => Some(Gc::new(ValueData::Symbol(symbol.clone()))),
+            PropertyKey::String(_) => None,
+        })
+        .collect();
+    Ok(array::new_array(&interpreter.get_global_object(), symbols))
+}
+
+/// Which parts of an object's enumerable properties to list
+/// https://tc39.es/ecma262/#sec-enumerableownproperties
+#[derive(Clone, Copy, PartialEq)]
+enum EnumerationKind {
+    Keys,
+    Values,
+    Entries,
+}
+
+/// List the enumerable own properties of an object with string keys
+/// https://tc39.es/ecma262/#sec-enumerableownproperties
+fn enumerable_own_properties(args: &[Value], kind: EnumerationKind, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = interpreter.to_object(&coercible_arg(args, 0, interpreter)?)?;
+    let global = interpreter.get_global_object();
+    let mut list = Vec::new();
+    for key in interpreter.own_property_keys(&obj)? {
+        let name = match key {
+            PropertyKey::String(ref name) => name.clone(),
+            PropertyKey::Symbol(_) => continue,
+        };
+        // A getter may have deleted the property or made it non-enumerable
+        if !interpreter.get_own_property(&obj, &key)?.is_some_and(|prop| prop.enumerable) {
+            continue;
+        }
+        list.push(match kind {
+            EnumerationKind::Keys => to_value(name),
+            EnumerationKind::Values => interpreter.get_field(&obj, key)?,
+            EnumerationKind::Entries => {
+                let value = interpreter.get_field(&obj, key)?;
+                array::new_array(&global, vec![to_value(name), value])
+            }
+        });
+    }
+    Ok(array::new_array(&global, list))
+}
+
+/// Get the enumerable own string keys of an object
+/// https://tc39.es/ecma262/#sec-object.keys
+pub fn keys(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    enumerable_own_properties(&args, EnumerationKind::Keys, interpreter)
+}
+
+/// Get the values of the enumerable own properties of an object
+/// https://tc39.es/ecma262/#sec-object.values
+pub fn values(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    enumerable_own_properties(&args, EnumerationKind::Values, interpreter)
+}
+
+/// Get `[key, value]` pairs for the enumerable own properties of an object
+/// https://tc39.es/ecma262/#sec-object.entries
+pub fn entries(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    enumerable_own_properties(&args, EnumerationKind::Entries, interpreter)
+}
+
+/// Make an object from an iterable of `[key, value]` pairs
+/// https://tc39.es/ecma262/#sec-object.fromentries
+pub fn from_entries(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let iterable = coercible_arg(&args, 0, interpreter)?;
+    let obj = ValueData::new_obj(Some(&interpreter.get_global_object()));
+    for entry in iterator::iterable_to_list(&iterable, interpreter)? {
+        if !entry.is_object() && !entry.is_function() {
+            return Err(interpreter.type_error(format!("Iterator value {} is not an entry object", entry)));
+        }
+        let key = interpreter.get_field(&entry, "0")?;
+        let value = interpreter.get_field(&entry, "1")?;
+        let key = to_property_key(&key, interpreter)?;
+        create_data_property(&obj, key, value, interpreter)?;
+    }
+    Ok(obj)
+}
+
+/// Copy the enumerable own properties of the sources onto the target
+/// https://tc39.es/ecma262/#sec-object.assign
+pub fn assign(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = interpreter.to_object(&arg(&args, 0))?;
+    for source in args.iter().skip(1) {
+        if source.is_null_or_undefined() {
+            continue;
+        }
+        let source = interpreter.to_object(source)?;
+        for key in interpreter.own_property_keys(&source)? {
+            if interpreter.get_own_property(&source, &key)?.is_some_and(|prop| prop.enumerable) {
+                let value = interpreter.get_field(&source, key.clone())?;
+                interpreter.set_field(&target, key, value)?;
+            }
+        }
+    }
+    Ok(target)
+}
+
+/// Which integrity level to set or check on an object
+/// https://tc39.es/ecma262/#sec-setintegritylevel
+#[derive(Clone, Copy, PartialEq)]
+enum IntegrityLevel {
+    /// No properties can be added or removed
+    Sealed,
+    /// No properties can be added, removed or changed
+    Frozen,
+}
+
+/// Stop an object from being extended, and make its properties non-configurable, and also read-only if frozen
+/// https://tc39.es/ecma262/#sec-setintegritylevel
+fn set_integrity_level(obj: &Value, level: IntegrityLevel, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    if !interpreter.prevent_extensions(obj)? {
+        return Ok(false);
+    }
+    for key in interpreter.own_property_keys(obj)? {
+        let is_accessor = interpreter.get_own_property(obj, &key)?.is_some_and(|prop| prop.is_accessor());
+        let desc = PropertyDescriptor {
+            configurable: Some(false),
+            writable: if level == IntegrityLevel::Frozen && !is_accessor { Some(false) } else { None },
+            ..PropertyDescriptor::default()
+        };
+        define_property_or_throw(obj, key, desc, interpreter)?;
+    }
+    Ok(true)
+}
+
+/// Returns true if an object is not extensible, and its properties are non-configurable, and also read-only
+/// if checking for frozen
+/// https://tc39.es/ecma262/#sec-testintegritylevel
+fn test_integrity_level(obj: &Value, level: IntegrityLevel, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    if interpreter.is_extensible(obj)? {
+        return Ok(false);
+    }
+    for key in interpreter.own_property_keys(obj)? {
+        if let Some(prop) = interpreter.get_own_property(obj, &key)? {
+            if prop.configurable || (level == IntegrityLevel::Frozen && !prop.is_accessor() && prop.writable) {
+                return Ok(false);
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Stop properties from being added to, removed from or changed in an object
+/// https://tc39.es/ecma262/#sec-object.freeze
+pub fn freeze(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = arg(&args, 0);
+    if (obj.is_object() || obj.is_function()) && !set_integrity_level(&obj, IntegrityLevel::Frozen, interpreter)? {
+        return Err(interpreter.type_error("Cannot freeze"));
+    }
+    Ok(obj)
+}
+
+/// Returns true if an object is frozen, which all primitives are
+/// https://tc39.es/ecma262/#sec-object.isfrozen
+pub fn is_frozen(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = arg(&args, 0);
     Ok(to_value(
-        prop.is_some() && this.get_prop(prop.unwrap()).is_some(),
+        !(obj.is_object() || obj.is_function()) || test_integrity_level(&obj, IntegrityLevel::Frozen, interpreter)?,
     ))
 }
 
+/// Stop properties from being added to or removed from an object
+/// https://tc39.es/ecma262/#sec-object.seal
+pub fn seal(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = arg(&args, 0);
+    if (obj.is_object() || obj.is_function()) && !set_integrity_level(&obj, IntegrityLevel::Sealed, interpreter)? {
+        return Err(interpreter.type_error("Cannot seal"));
+    }
+    Ok(obj)
+}
+
+/// Returns true if an object is sealed, which all primitives are
+/// https://tc39.es/ecma262/#sec-object.issealed
+pub fn is_sealed(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = arg(&args, 0);
+    Ok(to_value(
+        !(obj.is_object() || obj.is_function()) || test_integrity_level(&obj, IntegrityLevel::Sealed, interpreter)?,
+    ))
+}
+
+/// Stop properties from being added to an object
+/// https://tc39.es/ecma262/#sec-object.preventextensions
+pub fn prevent_extensions(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = arg(&args, 0);
+    if (obj.is_object() || obj.is_function()) && !interpreter.prevent_extensions(&obj)? {
+        return Err(interpreter.type_error("'preventExtensions' on proxy: trap returned falsish"));
+    }
+    Ok(obj)
+}
+
+/// Returns true if properties can be added to an object, which is never true for primitives
+/// https://tc39.es/ecma262/#sec-object.isextensible
+pub fn is_extensible(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = arg(&args, 0);
+    Ok(to_value((obj.is_object() || obj.is_function()) && interpreter.is_extensible(&obj)?))
+}
+
+/// Returns true if two values are the same, where `NaN` is the same as itself and `0` is not the same as `-0`
+/// https://tc39.es/ecma262/#sec-object.is
+pub fn is(_: Value, _: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(to_value(same_value(&arg(&args, 0), &arg(&args, 1))))
+}
+
+/// Returns true if an object has an own property with the key
+/// https://tc39.es/ecma262/#sec-object.hasown
+pub fn has_own(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let obj = interpreter.to_object(&coercible_arg(&args, 0, interpreter)?)?;
+    let key = to_property_key(&arg(&args, 1), interpreter)?;
+    Ok(to_value(interpreter.get_own_property(&obj, &key)?.is_some()))
+}
+
+/// Group the values of an iterable into arrays on a null-prototype object, by the keys the callback returns
+/// https://tc39.es/ecma262/#sec-object.groupby
+pub fn group_by(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let items = coercible_arg(&args, 0, interpreter)?;
+    let callback = arg(&args, 1);
+    if !callback.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", callback)));
+    }
+    let mut groups: Vec<(PropertyKey, Vec<Value>)> = Vec::new();
+    for (index, value) in iterator::iterable_to_list(&items, interpreter)?.into_iter().enumerate() {
+        let key = interpreter.call(&callback, &Gc::new(ValueData::Undefined), vec![value.clone(), to_value(index)])?;
+        let key = to_property_key(&key, interpreter)?;
+        match groups.iter_mut().find(|(group, _)| *group == key) {
+            Some((_, members)) => members.push(value),
+            None => groups.push((key, vec![value])),
+        }
+    }
+    let global = interpreter.get_global_object();
+    let obj = ValueData::new_obj_from_prototype(Gc::new(ValueData::Null));
+    for (key, members) in groups {
+        create_data_property(&obj, key, array::new_array(&global, members), interpreter)?;
+    }
+    Ok(obj)
+}
+
+/// Get a string like `[object Tag]`, using `this[Symbol.toStringTag]` as the tag if it is a string
+/// https://tc39.es/ecma262/#sec-object.prototype.tostring
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let builtin_tag = match *this {
+        ValueData::Undefined => return Ok(to_value("[object Undefined]")),
+        ValueData::Null => return Ok(to_value("[object Null]")),
+        ValueData::Function(_) => "Function",
+        ValueData::Boolean(_) => "Boolean",
+        ValueData::Number(_) | ValueData::Integer(_) => "Number",
+        ValueData::String(_) => "String",
+        _ if array::is_array_value(&this, interpreter)? => "Array",
+        _ if regexp::is_regexp_object(&this) => "RegExp",
+        _ if error::is_error_object(&this) => "Error",
+        _ if date::is_date_object(&this) => "Date",
+        // Wrapper objects for primitives have the tag of their primitive
+        _ => match *this.get_private_field_slice("PrimitiveValue") {
+            ValueData::Number(_) | ValueData::Integer(_) => "Number",
+            ValueData::Boolean(_) => "Boolean",
+            ValueData::String(_) => "String",
+            _ => "Object",
+        },
+    };
+    let tag = interpreter.get_field(&this, Symbol::well_known(WellKnownSymbol::ToStringTag))?;
+    Ok(to_value(match *tag {
+        ValueData::String(ref tag) => format!("[object {}]", tag),
+        _ => format!("[object {}]", builtin_tag),
+    }))
+}
+
+/// Returns true if the object has an own property with the key
+/// https://tc39.es/ecma262/#sec-object.prototype.hasownproperty
+pub fn has_own_prop(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let key = to_property_key(&arg(&args, 0), interpreter)?;
+    let obj = interpreter.to_object(&this)?;
+    Ok(to_value(interpreter.get_own_property(&obj, &key)?.is_some()))
+}
+
+/// Returns true if the object has an own enumerable property with the key
+/// https://tc39.es/ecma262/#sec-object.prototype.propertyisenumerable
+pub fn property_is_enumerable(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let key = to_property_key(&arg(&args, 0), interpreter)?;
+    let obj = interpreter.to_object(&this)?;
+    Ok(to_value(interpreter.get_own_property(&obj, &key)?.is_some_and(|prop| prop.enumerable)))
+}
+
+/// Returns true if the object is in the prototype chain of the argument
+/// https://tc39.es/ecma262/#sec-object.prototype.isprototypeof
+pub fn is_prototype_of(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let value = arg(&args, 0);
+    if !value.is_object() && !value.is_function() {
+        return Ok(to_value(false));
+    }
+    let obj = interpreter.to_object(&this)?;
+    let mut link = interpreter.get_prototype_of(&value)?;
+    while link.is_object() || link.is_function() {
+        if Gc::ptr_eq(&link, &obj) {
+            return Ok(to_value(true));
+        }
+        link = interpreter.get_prototype_of(&link)?;
+    }
+    Ok(to_value(false))
+}
+
+/// Get the object itself
+/// https://tc39.es/ecma262/#sec-object.prototype.valueof
+pub fn value_of(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    interpreter.to_object(&this)
+}
+
 /// Create a new `Object` object
 pub fn _create(global: &Value) -> Value {
     let object = to_value(make_object as NativeFunctionData);
     let prototype = ValueData::new_obj(Some(global));
-    prototype.set_field_slice(
-        "hasOwnProperty",
-        to_value(has_own_prop as NativeFunctionData),
-    );
+    prototype.set_field_slice("hasOwnProperty", to_value(has_own_prop as NativeFunctionData));
+    prototype.set_field_slice("propertyIsEnumerable", to_value(property_is_enumerable as NativeFunctionData));
+    prototype.set_field_slice("isPrototypeOf", to_value(is_prototype_of as NativeFunctionData));
     prototype.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    prototype.set_field_slice("valueOf", to_value(value_of as NativeFunctionData));
     object.set_field_slice("length", to_value(1i32));
     object.set_field_slice(PROTOTYPE, prototype);
+    object.set_field_slice("setPrototypeOf", to_value(set_proto_of as NativeFunctionData));
+    object.set_field_slice("getPrototypeOf", to_value(get_proto_of as NativeFunctionData));
+    object.set_field_slice("defineProperty", to_value(define_prop as NativeFunctionData));
+    object.set_field_slice("defineProperties", to_value(define_properties as NativeFunctionData));
+    object.set_field_slice("create", to_value(create as NativeFunctionData));
     object.set_field_slice(
-        "setPrototypeOf",
-        to_value(set_proto_of as NativeFunctionData),
+        "getOwnPropertyDescriptor",
+        to_value(get_own_property_descriptor as NativeFunctionData),
     );
     object.set_field_slice(
-        "getPrototypeOf",
-        to_value(get_proto_of as NativeFunctionData),
-    );
-    object.set_field_slice(
-        "defineProperty",
-        to_value(define_prop as NativeFunctionData),
+        "getOwnPropertyDescriptors",
+        to_value(get_own_property_descriptors as NativeFunctionData),
     );
+    object.set_field_slice("getOwnPropertyNames", to_value(get_own_property_names as NativeFunctionData));
+    object.set_field_slice("getOwnPropertySymbols", to_value(get_own_property_symbols as NativeFunctionData));
+    object.set_field_slice("keys", to_value(keys as NativeFunctionData));
+    object.set_field_slice("values", to_value(values as NativeFunctionData));
+    object.set_field_slice("entries", to_value(entries as NativeFunctionData));
+    object.set_field_slice("fromEntries", to_value(from_entries as NativeFunctionData));
+    object.set_field_slice("assign", to_value(assign as NativeFunctionData));
+    object.set_field_slice("freeze", to_value(freeze as NativeFunctionData));
+    object.set_field_slice("isFrozen", to_value(is_frozen as NativeFunctionData));
+    object.set_field_slice("seal", to_value(seal as NativeFunctionData));
+    object.set_field_slice("isSealed", to_value(is_sealed as NativeFunctionData));
+    object.set_field_slice("preventExtensions", to_value(prevent_extensions as NativeFunctionData));
+    object.set_field_slice("isExtensible", to_value(is_extensible as NativeFunctionData));
+    object.set_field_slice("is", to_value(is as NativeFunctionData));
+    object.set_field_slice("hasOwn", to_value(has_own as NativeFunctionData));
+    object.set_field_slice("groupBy", to_value(group_by as NativeFunctionData));
     object
 }
 
+/// Make every property of the built-in objects reachable from the global object non-enumerable, as the
+/// specification defines them, since they are added by assignment while the global object is set up
+pub fn hide_builtin_properties(global: &Value) {
+    let mut seen: Vec<Value> = Vec::new();
+    let mut pending = vec![global.clone()];
+    while let Some(obj) = pending.pop() {
+        if seen.iter().any(|other| Gc::ptr_eq(other, &obj)) {
+            continue;
+        }
+        for key in obj.own_keys() {
+            if let Some(mut prop) = obj.get_own_prop(key.clone()) {
+                // The entries of `Array.prototype[Symbol.unscopables]` are plain data, which stay enumerable
+                let unscopables = key == PropertyKey::from(Symbol::well_known(WellKnownSymbol::Unscopables));
+                for value in [&prop.value, &prop.get, &prop.set] {
+                    if (value.is_object() || value.is_function()) && !unscopables {
+                        pending.push(value.clone());
+                    }
+                }
+                prop.enumerable = false;
+                obj.set_prop(key, prop);
+            }
+        }
+        let proto = obj.get_field_slice(INSTANCE_PROTOTYPE);
+        if proto.is_object() || proto.is_function() {
+            pending.push(proto);
+        }
+        seen.push(obj);
+    }
+}
+
 /// Initialise the `Object` object on the global object
 pub fn init(global: &Value) {
     global.set_field_slice("Object", _create(global));
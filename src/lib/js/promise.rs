@@ -0,0 +1,564 @@
+/// The Promise Object
+/// https://tc39.es/ecma262/#sec-promise-objects
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::function::NativeFunctionData;
+use crate::js::object::PROTOTYPE;
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+use crate::js::array;
+use crate::js::iterator;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+
+/// The state of a promise, along with its result once it has settled
+/// https://tc39.es/ecma262/#sec-properties-of-promise-instances
+pub enum PromiseState {
+    /// The promise is waiting to be resolved or rejected
+    Pending,
+    /// The promise was fulfilled with the given value
+    Fulfilled(Value),
+    /// The promise was rejected with the given reason
+    Rejected(Value),
+}
+
+/// Returns true if the value is a promise object
+pub fn is_promise(value: &Value) -> bool {
+    value.get_private_field_slice("PromiseState").is_string()
+}
+
+/// Get the current state of a promise
+pub fn promise_state(promise: &Value) -> PromiseState {
+    let result = promise.get_private_field_slice("PromiseResult");
+    match promise.get_private_field_slice("PromiseState").to_string().as_str() {
+        "fulfilled" => PromiseState::Fulfilled(result),
+        "rejected" => PromiseState::Rejected(result),
+        _ => PromiseState::Pending,
+    }
+}
+
+/// Create a native function carrying internal slots, which it can read back from the function object it is called with
+pub fn make_closure(func: NativeFunctionData, slots: Vec<(&str, Value)>) -> Value {
+    let closure = to_value(func);
+    for (name, value) in slots {
+        closure.set_field_slice(name, value);
+    }
+    closure
+}
+
+/// Create an empty list to hold internal records
+fn new_list() -> Value {
+    let list = ValueData::new_obj(None);
+    list.set_field_slice("length", to_value(0i32));
+    list
+}
+
+/// Append a value to an internal list
+fn list_push(list: &Value, item: Value) {
+    let length = list.get_field_slice("length").to_int();
+    list.set_field(length.to_string(), item);
+    list.set_field_slice("length", to_value(length + 1));
+}
+
+/// Get the items of an array-like value
+fn list_items(list: &Value) -> Vec<Value> {
+    let length = list.get_field_slice("length").to_int();
+    (0..length).map(|i| list.get_field(i.to_string())).collect()
+}
+
+/// Create a new pending promise object inheriting from `Promise.prototype`
+fn new_promise(interpreter: &mut Interpreter) -> Value {
+    let proto = interpreter
+        .get_global_object()
+        .get_field_slice("Promise")
+        .get_field_slice(PROTOTYPE);
+    let promise = ValueData::new_obj_from_prototype(proto);
+    init_promise(&promise);
+    promise
+}
+
+/// Set up the internal slots of a pending promise
+fn init_promise(promise: &Value) {
+    promise.set_private_field_slice("PromiseState", to_value("pending"));
+    promise.set_private_field_slice("PromiseResult", Gc::new(ValueData::Undefined));
+    promise.set_private_field_slice("PromiseReactions", new_list());
+}
+
+/// Create a promise capability: a new pending promise, along with the `resolve` and `reject` functions which settle it
+/// https://tc39.es/ecma262/#sec-newpromisecapability
+pub fn new_promise_capability(interpreter: &mut Interpreter) -> Value {
+    let promise = new_promise(interpreter);
+    let (resolve, reject) = create_resolving_functions(&promise);
+    let capability = ValueData::new_obj(None);
+    capability.set_field_slice("promise", promise);
+    capability.set_field_slice("resolve", resolve);
+    capability.set_field_slice("reject", reject);
+    capability
+}
+
+/// Create the `resolve` and `reject` functions for a promise, which share an "already resolved" flag
+/// https://tc39.es/ecma262/#sec-createresolvingfunctions
+fn create_resolving_functions(promise: &Value) -> (Value, Value) {
+    let already_resolved = ValueData::new_obj(None);
+    already_resolved.set_field_slice("value", to_value(false));
+    let resolve = make_closure(
+        resolve_function as NativeFunctionData,
+        vec![
+            ("[[Promise]]", promise.clone()),
+            ("[[AlreadyResolved]]", already_resolved.clone()),
+        ],
+    );
+    let reject = make_closure(
+        reject_function as NativeFunctionData,
+        vec![
+            ("[[Promise]]", promise.clone()),
+            ("[[AlreadyResolved]]", already_resolved),
+        ],
+    );
+    (resolve, reject)
+}
+
+/// Check and set the "already resolved" flag shared by a pair of resolving functions
+fn take_already_resolved(f: &Value) -> bool {
+    let already_resolved = f.get_field_slice("[[AlreadyResolved]]");
+    if already_resolved.get_field_slice("value").is_true() {
+        return true;
+    }
+    already_resolved.set_field_slice("value", to_value(true));
+    false
+}
+
+/// A promise resolve function
+/// https://tc39.es/ecma262/#sec-promise-resolve-functions
+pub fn resolve_function(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let undefined = Gc::new(ValueData::Undefined);
+    if take_already_resolved(&f) {
+        return Ok(undefined);
+    }
+    let promise = f.get_field_slice("[[Promise]]");
+    let resolution = arg(&args, 0);
+    if Gc::ptr_eq(&resolution, &promise) {
+        let reason = interpreter.type_error("Chaining cycle detected for promise");
+        reject_promise(&promise, reason, interpreter);
+        return Ok(undefined);
+    }
+    if !resolution.is_object() && !resolution.is_function() {
+        fulfill_promise(&promise, resolution, interpreter);
+        return Ok(undefined);
+    }
+    let then = resolution.get_field_slice("then");
+    if !then.is_function() {
+        fulfill_promise(&promise, resolution, interpreter);
+        return Ok(undefined);
+    }
+    interpreter.enqueue_job(
+        to_value(resolve_thenable_job as NativeFunctionData),
+        vec![promise, resolution, then],
+    );
+    Ok(undefined)
+}
+
+/// A promise reject function
+/// https://tc39.es/ecma262/#sec-promise-reject-functions
+pub fn reject_function(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !take_already_resolved(&f) {
+        let promise = f.get_field_slice("[[Promise]]");
+        reject_promise(&promise, arg(&args, 0), interpreter);
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Settle a pending promise and schedule its reactions
+/// https://tc39.es/ecma262/#sec-triggerpromisereactions
+fn settle_promise(promise: &Value, state: &str, result: Value, interpreter: &mut Interpreter) {
+    let reactions = promise.get_private_field_slice("PromiseReactions");
+    promise.set_private_field_slice("PromiseState", to_value(state));
+    promise.set_private_field_slice("PromiseResult", result.clone());
+    promise.set_private_field_slice("PromiseReactions", new_list());
+    for reaction in list_items(&reactions) {
+        enqueue_reaction_job(reaction, state, result.clone(), interpreter);
+    }
+}
+
+/// https://tc39.es/ecma262/#sec-fulfillpromise
+fn fulfill_promise(promise: &Value, value: Value, interpreter: &mut Interpreter) {
+    settle_promise(promise, "fulfilled", value, interpreter)
+}
+
+/// https://tc39.es/ecma262/#sec-rejectpromise
+fn reject_promise(promise: &Value, reason: Value, interpreter: &mut Interpreter) {
+    settle_promise(promise, "rejected", reason, interpreter)
+}
+
+/// Queue a job to run the handler of a reaction for the given outcome
+fn enqueue_reaction_job(reaction: Value, state: &str, argument: Value, interpreter: &mut Interpreter) {
+    interpreter.enqueue_job(
+        to_value(reaction_job as NativeFunctionData),
+        vec![reaction, to_value(state), argument],
+    );
+}
+
+/// Run the handler of a promise reaction, then settle the derived promise with its outcome
+/// https://tc39.es/ecma262/#sec-newpromisereactionjob
+pub fn reaction_job(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let reaction = arg(&args, 0);
+    let fulfilled = arg(&args, 1).to_string() == "fulfilled";
+    let argument = arg(&args, 2);
+    let undefined = Gc::new(ValueData::Undefined);
+    let handler = reaction.get_field_slice(if fulfilled { "onFulfilled" } else { "onRejected" });
+    let outcome = if handler.is_function() {
+        interpreter.call(&handler, &undefined, vec![argument])
+    } else if fulfilled {
+        Ok(argument)
+    } else {
+        Err(argument)
+    };
+    // Reactions registered by `await` have no promise of their own to settle
+    let capability = reaction.get_field_slice("capability");
+    if capability.is_undefined() {
+        return outcome;
+    }
+    let (resolver, value) = match outcome {
+        Ok(value) => (capability.get_field_slice("resolve"), value),
+        Err(reason) => (capability.get_field_slice("reject"), reason),
+    };
+    interpreter.call(&resolver, &undefined, vec![value])
+}
+
+/// Resolve a promise with a thenable by calling its `then` method
+/// https://tc39.es/ecma262/#sec-newpromiseresolvethenablejob
+pub fn resolve_thenable_job(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let promise = arg(&args, 0);
+    let thenable = arg(&args, 1);
+    let then = arg(&args, 2);
+    let (resolve, reject) = create_resolving_functions(&promise);
+    match interpreter.call(&then, &thenable, vec![resolve, reject.clone()]) {
+        Ok(value) => Ok(value),
+        Err(reason) => {
+            let undefined = Gc::new(ValueData::Undefined);
+            interpreter.call(&reject, &undefined, vec![reason])
+        }
+    }
+}
+
+/// Register reactions on a promise, to be run once it settles
+/// https://tc39.es/ecma262/#sec-performpromisethen
+pub fn perform_promise_then(
+    promise: &Value,
+    on_fulfilled: Value,
+    on_rejected: Value,
+    capability: Value,
+    interpreter: &mut Interpreter,
+) {
+    let reaction = ValueData::new_obj(None);
+    reaction.set_field_slice("capability", capability);
+    reaction.set_field_slice("onFulfilled", on_fulfilled);
+    reaction.set_field_slice("onRejected", on_rejected);
+    match promise_state(promise) {
+        PromiseState::Pending => {
+            list_push(&promise.get_private_field_slice("PromiseReactions"), reaction)
+        }
+        PromiseState::Fulfilled(value) => {
+            enqueue_reaction_job(reaction, "fulfilled", value, interpreter)
+        }
+        PromiseState::Rejected(reason) => {
+            enqueue_reaction_job(reaction, "rejected", reason, interpreter)
+        }
+    }
+}
+
+/// Convert a value to a promise, returning it unchanged if it is already one
+/// https://tc39.es/ecma262/#sec-promise-resolve
+pub fn promise_resolve(value: Value, interpreter: &mut Interpreter) -> ResultValue {
+    if is_promise(&value) {
+        return Ok(value);
+    }
+    let capability = new_promise_capability(interpreter);
+    let undefined = Gc::new(ValueData::Undefined);
+    interpreter.call(&capability.get_field_slice("resolve"), &undefined, vec![value])?;
+    Ok(capability.get_field_slice("promise"))
+}
+
+/// Create a new promise, running the executor with its resolving functions
+/// https://tc39.es/ecma262/#sec-promise-executor
+pub fn make_promise(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error("Promise constructor cannot be invoked without 'new'"));
+    }
+    let executor = arg(&args, 0);
+    if !executor.is_function() {
+        return Err(interpreter.type_error("Promise resolver is not a function"));
+    }
+    init_promise(&this);
+    let (resolve, reject) = create_resolving_functions(&this);
+    let undefined = Gc::new(ValueData::Undefined);
+    if let Err(reason) = interpreter.call(&executor, &undefined, vec![resolve, reject.clone()]) {
+        interpreter.call(&reject, &undefined, vec![reason])?;
+    }
+    Ok(this)
+}
+
+/// `Promise.prototype.then(onFulfilled, onRejected)`
+/// https://tc39.es/ecma262/#sec-promise.prototype.then
+pub fn then(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !is_promise(&this) {
+        return Err(interpreter.type_error("Promise.prototype.then called on a non-promise"));
+    }
+    let capability = new_promise_capability(interpreter);
+    perform_promise_then(
+        &this,
+        arg(&args, 0),
+        arg(&args, 1),
+        capability.clone(),
+        interpreter,
+    );
+    Ok(capability.get_field_slice("promise"))
+}
+
+/// Call the `then` method of a value with the given handlers
+fn invoke_then(value: &Value, on_fulfilled: Value, on_rejected: Value, interpreter: &mut Interpreter) -> ResultValue {
+    let then = value.get_field_slice("then");
+    interpreter.call(&then, value, vec![on_fulfilled, on_rejected])
+}
+
+/// `Promise.prototype.catch(onRejected)`
+/// https://tc39.es/ecma262/#sec-promise.prototype.catch
+pub fn catch(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    invoke_then(&this, Gc::new(ValueData::Undefined), arg(&args, 0), interpreter)
+}
+
+/// `Promise.prototype.finally(onFinally)`
+/// https://tc39.es/ecma262/#sec-promise.prototype.finally
+pub fn finally(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let on_finally = arg(&args, 0);
+    if !on_finally.is_function() {
+        return invoke_then(&this, on_finally.clone(), on_finally, interpreter);
+    }
+    let then_finally = make_closure(
+        then_finally as NativeFunctionData,
+        vec![("[[OnFinally]]", on_finally.clone())],
+    );
+    let catch_finally = make_closure(
+        catch_finally as NativeFunctionData,
+        vec![("[[OnFinally]]", on_finally)],
+    );
+    invoke_then(&this, then_finally, catch_finally, interpreter)
+}
+
+/// Run the `finally` callback, then pass the original value through
+fn then_finally(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let undefined = Gc::new(ValueData::Undefined);
+    let result = interpreter.call(&f.get_field_slice("[[OnFinally]]"), &undefined, Vec::new())?;
+    let promise = promise_resolve(result, interpreter)?;
+    let value_thunk = make_closure(
+        return_value as NativeFunctionData,
+        vec![("[[Value]]", arg(&args, 0))],
+    );
+    invoke_then(&promise, value_thunk, undefined, interpreter)
+}
+
+/// Run the `finally` callback, then rethrow the original reason
+fn catch_finally(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let undefined = Gc::new(ValueData::Undefined);
+    let result = interpreter.call(&f.get_field_slice("[[OnFinally]]"), &undefined, Vec::new())?;
+    let promise = promise_resolve(result, interpreter)?;
+    let thrower = make_closure(
+        throw_reason as NativeFunctionData,
+        vec![("[[Reason]]", arg(&args, 0))],
+    );
+    invoke_then(&promise, thrower, undefined, interpreter)
+}
+
+/// Return the value captured by the closure
+fn return_value(_: Value, f: Value, _: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(f.get_field_slice("[[Value]]"))
+}
+
+/// Throw the reason captured by the closure
+fn throw_reason(_: Value, f: Value, _: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Err(f.get_field_slice("[[Reason]]"))
+}
+
+/// `Promise.resolve(value)`
+/// https://tc39.es/ecma262/#sec-promise.resolve
+pub fn resolve(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    promise_resolve(arg(&args, 0), interpreter)
+}
+
+/// `Promise.reject(reason)`
+/// https://tc39.es/ecma262/#sec-promise.reject
+pub fn reject(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let capability = new_promise_capability(interpreter);
+    let undefined = Gc::new(ValueData::Undefined);
+    interpreter.call(&capability.get_field_slice("reject"), &undefined, vec![arg(&args, 0)])?;
+    Ok(capability.get_field_slice("promise"))
+}
+
+/// The different ways of combining promises, for `Promise.all`, `allSettled`, `race` and `any`
+#[derive(Clone, Copy, PartialEq)]
+enum Combinator {
+    All,
+    AllSettled,
+    Race,
+    Any,
+}
+
+/// Shared implementation of the promise combinators
+/// https://tc39.es/ecma262/#sec-promise.all
+fn combine(combinator: Combinator, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let capability = new_promise_capability(interpreter);
+    let promise = capability.get_field_slice("promise");
+    let resolve = capability.get_field_slice("resolve");
+    let reject = capability.get_field_slice("reject");
+    let undefined = Gc::new(ValueData::Undefined);
+    let items = match iterator::iterable_to_list(&arg(&args, 0), interpreter) {
+        Ok(items) => items,
+        Err(reason) => {
+            interpreter.call(&reject, &undefined, vec![reason])?;
+            return Ok(promise);
+        }
+    };
+    // Results are collected into a shared record, so the element closures can fill them in as they settle
+    let record = ValueData::new_obj(None);
+    record.set_field_slice("values", new_list());
+    record.set_field_slice("remaining", to_value(items.len() as i32 + 1));
+    for (index, item) in items.into_iter().enumerate() {
+        let next = promise_resolve(item, interpreter)?;
+        let element = |func: NativeFunctionData| {
+            let already_called = ValueData::new_obj(None);
+            already_called.set_field_slice("value", to_value(false));
+            make_closure(
+                func,
+                vec![
+                    ("[[Index]]", to_value(index as i32)),
+                    ("[[Record]]", record.clone()),
+                    ("[[Capability]]", capability.clone()),
+                    ("[[AlreadyCalled]]", already_called),
+                ],
+            )
+        };
+        let (on_fulfilled, on_rejected) = match combinator {
+            Combinator::All => (element(all_resolve_element as NativeFunctionData), reject.clone()),
+            Combinator::AllSettled => (
+                element(all_settled_resolve_element as NativeFunctionData),
+                element(all_settled_reject_element as NativeFunctionData),
+            ),
+            Combinator::Race => (resolve.clone(), reject.clone()),
+            Combinator::Any => (resolve.clone(), element(any_reject_element as NativeFunctionData)),
+        };
+        invoke_then(&next, on_fulfilled, on_rejected, interpreter)?;
+    }
+    if combinator != Combinator::Race {
+        finish_element(&record, &capability, combinator, interpreter)?;
+    }
+    Ok(promise)
+}
+
+/// Record the outcome of one element of a combinator, settling the combined promise once every element is done
+fn store_element(f: &Value, value: Value, combinator: Combinator, interpreter: &mut Interpreter) -> ResultValue {
+    let already_called = f.get_field_slice("[[AlreadyCalled]]");
+    if already_called.get_field_slice("value").is_true() {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    already_called.set_field_slice("value", to_value(true));
+    let record = f.get_field_slice("[[Record]]");
+    let index = f.get_field_slice("[[Index]]").to_int();
+    let values = record.get_field_slice("values");
+    values.set_field(index.to_string(), value);
+    if index >= values.get_field_slice("length").to_int() {
+        values.set_field_slice("length", to_value(index + 1));
+    }
+    finish_element(&record, &f.get_field_slice("[[Capability]]"), combinator, interpreter)
+}
+
+/// Count down the remaining elements of a combinator, settling the combined promise when none are left
+fn finish_element(record: &Value, capability: &Value, combinator: Combinator, interpreter: &mut Interpreter) -> ResultValue {
+    let remaining = record.get_field_slice("remaining").to_int() - 1;
+    record.set_field_slice("remaining", to_value(remaining));
+    let undefined = Gc::new(ValueData::Undefined);
+    if remaining > 0 {
+        return Ok(undefined);
+    }
+    let global = interpreter.get_global_object();
+    let values = array::new_array(&global, list_items(&record.get_field_slice("values")));
+    if combinator == Combinator::Any {
+        let aggregate_error = global.get_field_slice("AggregateError");
+        let error = interpreter.construct(&aggregate_error, vec![values, to_value("All promises were rejected")])?;
+        interpreter.call(&capability.get_field_slice("reject"), &undefined, vec![error])
+    } else {
+        interpreter.call(&capability.get_field_slice("resolve"), &undefined, vec![values])
+    }
+}
+
+/// Store the value of a fulfilled element for `Promise.all`
+fn all_resolve_element(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    store_element(&f, arg(&args, 0), Combinator::All, interpreter)
+}
+
+/// Store a settlement record for a fulfilled element for `Promise.allSettled`
+fn all_settled_resolve_element(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    let outcome = ValueData::new_obj(Some(&global));
+    outcome.set_field_slice("status", to_value("fulfilled"));
+    outcome.set_field_slice("value", arg(&args, 0));
+    store_element(&f, outcome, Combinator::AllSettled, interpreter)
+}
+
+/// Store a settlement record for a rejected element for `Promise.allSettled`
+fn all_settled_reject_element(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    let outcome = ValueData::new_obj(Some(&global));
+    outcome.set_field_slice("status", to_value("rejected"));
+    outcome.set_field_slice("reason", arg(&args, 0));
+    store_element(&f, outcome, Combinator::AllSettled, interpreter)
+}
+
+/// Store the reason of a rejected element for `Promise.any`
+fn any_reject_element(_: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    store_element(&f, arg(&args, 0), Combinator::Any, interpreter)
+}
+
+/// `Promise.all(iterable)`
+/// https://tc39.es/ecma262/#sec-promise.all
+pub fn all(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    combine(Combinator::All, args, interpreter)
+}
+
+/// `Promise.allSettled(iterable)`
+/// https://tc39.es/ecma262/#sec-promise.allsettled
+pub fn all_settled(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    combine(Combinator::AllSettled, args, interpreter)
+}
+
+/// `Promise.race(iterable)`
+/// https://tc39.es/ecma262/#sec-promise.race
+pub fn race(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    combine(Combinator::Race, args, interpreter)
+}
+
+/// `Promise.any(iterable)`
+/// https://tc39.es/ecma262/#sec-promise.any
+pub fn any(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    combine(Combinator::Any, args, interpreter)
+}
+
+/// Create a new `Promise` object
+pub fn _create(global: &Value) -> Value {
+    let promise = to_value(make_promise as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("then", to_value(then as NativeFunctionData));
+    prototype.set_field_slice("catch", to_value(catch as NativeFunctionData));
+    prototype.set_field_slice("finally", to_value(finally as NativeFunctionData));
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("Promise"));
+    promise.set_field_slice(PROTOTYPE, prototype);
+    promise.set_field_slice("resolve", to_value(resolve as NativeFunctionData));
+    promise.set_field_slice("reject", to_value(reject as NativeFunctionData));
+    promise.set_field_slice("all", to_value(all as NativeFunctionData));
+    promise.set_field_slice("allSettled", to_value(all_settled as NativeFunctionData));
+    promise.set_field_slice("race", to_value(race as NativeFunctionData));
+    promise.set_field_slice("any", to_value(any as NativeFunctionData));
+    promise
+}
+
+/// Initialise the global object with the `Promise` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Promise", _create(global));
+}
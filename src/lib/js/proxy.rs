@@ -0,0 +1,548 @@
+/// The global `Proxy` object, and the internal methods of proxy objects, which run the traps of their handler
+/// and check that the results are consistent with the proxy's target
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::array;
+use crate::js::function::{self, NativeFunctionData};
+use crate::js::object::{self, Property, PropertyDescriptor, PropertyKey};
+use crate::js::value::{arg, same_value, to_value, ResultValue, ToValue, Value, ValueData};
+
+/// The internal slot holding the target of a proxy, which is `null` once the proxy has been revoked
+pub static PROXY_TARGET: &str = "ProxyTarget";
+/// The internal slot holding the handler of a proxy, which is `null` once the proxy has been revoked
+pub static PROXY_HANDLER: &str = "ProxyHandler";
+/// The internal slot of a revoke function holding the proxy it revokes
+static REVOCABLE_PROXY: &str = "RevocableProxy";
+
+/// Returns true if the value is a proxy, even if it has been revoked
+pub fn is_proxy(value: &Value) -> bool {
+    value.has_private_field(PROXY_HANDLER)
+}
+
+/// Returns true if the value is an object or a function
+fn is_object(value: &Value) -> bool {
+    value.is_object() || value.is_function()
+}
+
+/// The target and handler of a proxy, and the handler's trap for an internal method if it has one
+struct Trap {
+    target: Value,
+    handler: Value,
+    method: Option<Value>,
+}
+
+impl Trap {
+    /// Call the trap with the handler as `this`
+    fn call(&self, method: &Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+        interpreter.call(method, &self.handler, args)
+    }
+}
+
+/// Get the trap of a proxy's handler, which is `None` if the handler doesn't have it, throwing if the proxy
+/// has been revoked or the trap isn't a function
+/// https://tc39.es/ecma262/#sec-getmethod
+fn get_trap(proxy: &Value, name: &str, interpreter: &mut Interpreter) -> Result<Trap, Value> {
+    let handler = proxy.get_private_field_slice(PROXY_HANDLER);
+    if handler.is_null() {
+        return Err(interpreter.type_error(format!("Cannot perform '{}' on a proxy that has been revoked", name)));
+    }
+    let target = proxy.get_private_field_slice(PROXY_TARGET);
+    let method = interpreter.get_field(&handler, name)?;
+    if method.is_null_or_undefined() {
+        return Ok(Trap { target, handler, method: None });
+    }
+    if !method.is_function() {
+        return Err(interpreter.type_error(format!("'{}' on proxy: trap is not a function", name)));
+    }
+    Ok(Trap {
+        target,
+        handler,
+        method: Some(method),
+    })
+}
+
+/// Fill in the missing fields of a descriptor with `false` and `undefined`
+/// https://tc39.es/ecma262/#sec-completepropertydescriptor
+fn complete_descriptor(desc: &PropertyDescriptor) -> PropertyDescriptor {
+    let undefined = || Gc::new(ValueData::Undefined);
+    let mut desc = desc.clone();
+    if desc.is_accessor() {
+        desc.get.get_or_insert_with(undefined);
+        desc.set.get_or_insert_with(undefined);
+    } else {
+        desc.value.get_or_insert_with(undefined);
+        desc.writable.get_or_insert(false);
+    }
+    desc.enumerable.get_or_insert(false);
+    desc.configurable.get_or_insert(false);
+    desc
+}
+
+/// `[[GetPrototypeOf]]`, which runs the `getPrototypeOf` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-getprototypeof
+pub fn get_prototype_of(proxy: &Value, interpreter: &mut Interpreter) -> ResultValue {
+    let trap = get_trap(proxy, "getPrototypeOf", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.get_prototype_of(&trap.target),
+    };
+    let proto = trap.call(&method, vec![trap.target.clone()], interpreter)?;
+    if !is_object(&proto) && !proto.is_null() {
+        return Err(interpreter.type_error("'getPrototypeOf' on proxy: trap returned neither object nor null"));
+    }
+    if interpreter.is_extensible(&trap.target)? {
+        return Ok(proto);
+    }
+    // A target which isn't extensible has a fixed prototype
+    let target_proto = interpreter.get_prototype_of(&trap.target)?;
+    if !same_value(&proto, &target_proto) {
+        return Err(interpreter.type_error(
+            "'getPrototypeOf' on proxy: proxy target is non-extensible but the trap did not return its actual prototype",
+        ));
+    }
+    Ok(proto)
+}
+
+/// `[[SetPrototypeOf]]`, which runs the `setPrototypeOf` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-setprototypeof-v
+pub fn set_prototype_of(proxy: &Value, proto: Value, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    let trap = get_trap(proxy, "setPrototypeOf", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.set_prototype_of(&trap.target, proto),
+    };
+    if !trap.call(&method, vec![trap.target.clone(), proto.clone()], interpreter)?.is_true() {
+        return Ok(false);
+    }
+    if interpreter.is_extensible(&trap.target)? {
+        return Ok(true);
+    }
+    let target_proto = interpreter.get_prototype_of(&trap.target)?;
+    if !same_value(&proto, &target_proto) {
+        return Err(interpreter.type_error(
+            "'setPrototypeOf' on proxy: trap returned truish for setting a new prototype on the non-extensible proxy target",
+        ));
+    }
+    Ok(true)
+}
+
+/// `[[IsExtensible]]`, which runs the `isExtensible` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-isextensible
+pub fn is_extensible(proxy: &Value, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    let trap = get_trap(proxy, "isExtensible", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.is_extensible(&trap.target),
+    };
+    let result = trap.call(&method, vec![trap.target.clone()], interpreter)?.is_true();
+    let target_result = interpreter.is_extensible(&trap.target)?;
+    if result != target_result {
+        return Err(interpreter.type_error(format!(
+            "'isExtensible' on proxy: trap result does not reflect extensibility of proxy target (which is '{}')",
+            target_result
+        )));
+    }
+    Ok(result)
+}
+
+/// `[[PreventExtensions]]`, which runs the `preventExtensions` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-preventextensions
+pub fn prevent_extensions(proxy: &Value, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    let trap = get_trap(proxy, "preventExtensions", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.prevent_extensions(&trap.target),
+    };
+    let result = trap.call(&method, vec![trap.target.clone()], interpreter)?.is_true();
+    if result && interpreter.is_extensible(&trap.target)? {
+        return Err(interpreter.type_error(
+            "'preventExtensions' on proxy: trap returned truish but the proxy target is extensible",
+        ));
+    }
+    Ok(result)
+}
+
+/// `[[GetOwnProperty]]`, which runs the `getOwnPropertyDescriptor` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-getownproperty-p
+pub fn get_own_property(proxy: &Value, key: &PropertyKey, interpreter: &mut Interpreter) -> Result<Option<Property>, Value> {
+    let trap = get_trap(proxy, "getOwnPropertyDescriptor", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.get_own_property(&trap.target, key),
+    };
+    let result = trap.call(&method, vec![trap.target.clone(), key.to_value()], interpreter)?;
+    if !is_object(&result) && !result.is_undefined() {
+        return Err(interpreter.type_error(format!(
+            "'getOwnPropertyDescriptor' on proxy: trap returned neither object nor undefined for property '{}'",
+            key
+        )));
+    }
+    let target_desc = interpreter.get_own_property(&trap.target, key)?;
+    if result.is_undefined() {
+        // A property can only be hidden if the target could remove it
+        return match target_desc {
+            None => Ok(None),
+            Some(ref target_desc) if !target_desc.configurable => Err(interpreter.type_error(format!(
+                "'getOwnPropertyDescriptor' on proxy: trap returned undefined for property '{}' which is non-configurable in the proxy target",
+                key
+            ))),
+            Some(_) if !interpreter.is_extensible(&trap.target)? => Err(interpreter.type_error(format!(
+                "'getOwnPropertyDescriptor' on proxy: trap returned undefined for property '{}' which exists in the non-extensible proxy target",
+                key
+            ))),
+            Some(_) => Ok(None),
+        };
+    }
+    let extensible = interpreter.is_extensible(&trap.target)?;
+    let desc = object::to_property_descriptor(&result, interpreter)?;
+    let desc = complete_descriptor(&desc);
+    if !object::is_compatible_property_descriptor(extensible, &desc, target_desc.as_ref()) {
+        return Err(interpreter.type_error(format!(
+            "'getOwnPropertyDescriptor' on proxy: trap returned descriptor for property '{}' that is incompatible with the existing property in the proxy target",
+            key
+        )));
+    }
+    // A property can only be reported as non-configurable if it is non-configurable on the target
+    if desc.configurable == Some(false) {
+        match target_desc {
+            Some(ref target_desc) if !target_desc.configurable => {
+                if desc.writable == Some(false) && !target_desc.is_accessor() && target_desc.writable {
+                    return Err(interpreter.type_error(format!(
+                        "'getOwnPropertyDescriptor' on proxy: trap reported non-configurable and non-writable for property '{}' which is writable in the proxy target",
+                        key
+                    )));
+                }
+            }
+            _ => {
+                return Err(interpreter.type_error(format!(
+                    "'getOwnPropertyDescriptor' on proxy: trap reported non-configurability for property '{}' which is either non-existent or configurable in the proxy target",
+                    key
+                )))
+            }
+        }
+    }
+    Ok(Some(desc.complete()))
+}
+
+/// `[[DefineOwnProperty]]`, which runs the `defineProperty` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-defineownproperty-p-desc
+pub fn define_own_property(
+    proxy: &Value,
+    key: PropertyKey,
+    desc: PropertyDescriptor,
+    interpreter: &mut Interpreter,
+) -> Result<bool, Value> {
+    let trap = get_trap(proxy, "defineProperty", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.define_own_property(&trap.target, key, desc),
+    };
+    let desc_obj = object::from_property_descriptor(&desc, &interpreter.get_global_object());
+    let args = vec![trap.target.clone(), key.to_value(), desc_obj];
+    if !trap.call(&method, args, interpreter)?.is_true() {
+        return Ok(false);
+    }
+    let target_desc = interpreter.get_own_property(&trap.target, &key)?;
+    let extensible = interpreter.is_extensible(&trap.target)?;
+    let setting_non_configurable = desc.configurable == Some(false);
+    match target_desc {
+        None if !extensible => Err(interpreter.type_error(format!(
+            "'defineProperty' on proxy: trap returned truish for adding property '{}'  to the non-extensible proxy target",
+            key
+        ))),
+        Some(ref target_desc) if !object::is_compatible_property_descriptor(extensible, &desc, Some(target_desc)) => {
+            Err(interpreter.type_error(format!(
+                "'defineProperty' on proxy: trap returned truish for adding property '{}'  that is incompatible with the existing property in the proxy target",
+                key
+            )))
+        }
+        Some(ref target_desc) if setting_non_configurable && !target_desc.configurable => {
+            if !target_desc.is_accessor() && target_desc.writable && desc.writable == Some(false) {
+                return Err(interpreter.type_error(format!(
+                    "'defineProperty' on proxy: trap returned truish for defining non-configurable property '{}' which cannot be non-writable, unless there exists a corresponding non-configurable, non-writable own property of the target object.",
+                    key
+                )));
+            }
+            Ok(true)
+        }
+        _ if setting_non_configurable => Err(interpreter.type_error(format!(
+            "'defineProperty' on proxy: trap returned truish for defining non-configurable property '{}' which is either non-existent or configurable in the proxy target",
+            key
+        ))),
+        _ => Ok(true),
+    }
+}
+
+/// `[[HasProperty]]`, which runs the `has` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-hasproperty-p
+pub fn has_property(proxy: &Value, key: &PropertyKey, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    let trap = get_trap(proxy, "has", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.has_property(&trap.target, key),
+    };
+    if trap.call(&method, vec![trap.target.clone(), key.to_value()], interpreter)?.is_true() {
+        return Ok(true);
+    }
+    // A property can only be hidden if the target could remove it
+    if let Some(target_desc) = interpreter.get_own_property(&trap.target, key)? {
+        if !target_desc.configurable {
+            return Err(interpreter.type_error(format!(
+                "'has' on proxy: trap returned falsish for property '{}' which exists in the proxy target as non-configurable",
+                key
+            )));
+        }
+        if !interpreter.is_extensible(&trap.target)? {
+            return Err(interpreter.type_error(format!(
+                "'has' on proxy: trap returned falsish for property '{}' but the proxy target is not extensible",
+                key
+            )));
+        }
+    }
+    Ok(false)
+}
+
+/// `[[Get]]`, which runs the `get` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-get-p-receiver
+pub fn get(proxy: &Value, key: &PropertyKey, receiver: &Value, interpreter: &mut Interpreter) -> ResultValue {
+    let trap = get_trap(proxy, "get", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.get(&trap.target, key, receiver),
+    };
+    let args = vec![trap.target.clone(), key.to_value(), receiver.clone()];
+    let value = trap.call(&method, args, interpreter)?;
+    // Properties of the target which can't change must be reported as they are
+    match interpreter.get_own_property(&trap.target, key)? {
+        Some(ref target_desc) if !target_desc.configurable => {
+            if !target_desc.is_accessor() && !target_desc.writable && !same_value(&value, &target_desc.value) {
+                return Err(interpreter.type_error(format!(
+                    "'get' on proxy: property '{}' is a read-only and non-configurable data property on the proxy target but the proxy did not return its actual value (expected '{}' but got '{}')",
+                    key, target_desc.value, value
+                )));
+            }
+            if target_desc.is_accessor() && target_desc.get.is_undefined() && !value.is_undefined() {
+                return Err(interpreter.type_error(format!(
+                    "'get' on proxy: property '{}' is a non-configurable accessor property on the proxy target and does not have a getter function, but the trap did not return 'undefined' (got '{}')",
+                    key, value
+                )));
+            }
+            Ok(value)
+        }
+        _ => Ok(value),
+    }
+}
+
+/// `[[Set]]`, which runs the `set` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-set-p-v-receiver
+pub fn set(proxy: &Value, key: &PropertyKey, val: Value, receiver: &Value, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    let trap = get_trap(proxy, "set", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.set(&trap.target, key, val, receiver),
+    };
+    let args = vec![trap.target.clone(), key.to_value(), val.clone(), receiver.clone()];
+    if !trap.call(&method, args, interpreter)?.is_true() {
+        return Ok(false);
+    }
+    // Properties of the target which can't change can't be reported as set to something else
+    if let Some(target_desc) = interpreter.get_own_property(&trap.target, key)? {
+        if !target_desc.configurable {
+            if !target_desc.is_accessor() && !target_desc.writable && !same_value(&val, &target_desc.value) {
+                return Err(interpreter.type_error(format!(
+                    "'set' on proxy: trap returned truish for property '{}' which exists in the proxy target as a non-configurable and non-writable data property with a different value",
+                    key
+                )));
+            }
+            if target_desc.is_accessor() && target_desc.set.is_undefined() {
+                return Err(interpreter.type_error(format!(
+                    "'set' on proxy: trap returned truish for property '{}' which exists in the proxy target as a non-configurable and non-writable accessor property without a setter",
+                    key
+                )));
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// `[[Delete]]`, which runs the `deleteProperty` trap
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-delete-p
+pub fn delete_property(proxy: &Value, key: &PropertyKey, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    let trap = get_trap(proxy, "deleteProperty", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.delete_property(&trap.target, key),
+    };
+    if !trap.call(&method, vec![trap.target.clone(), key.to_value()], interpreter)?.is_true() {
+        return Ok(false);
+    }
+    let target_desc = match interpreter.get_own_property(&trap.target, key)? {
+        Some(target_desc) => target_desc,
+        None => return Ok(true),
+    };
+    if !target_desc.configurable {
+        return Err(interpreter.type_error(format!(
+            "'deleteProperty' on proxy: trap returned truish for property '{}' which is non-configurable in the proxy target",
+            key
+        )));
+    }
+    if !interpreter.is_extensible(&trap.target)? {
+        return Err(interpreter.type_error(format!(
+            "'deleteProperty' on proxy: trap returned truish for property '{}' but the proxy target is non-extensible",
+            key
+        )));
+    }
+    Ok(true)
+}
+
+/// `[[OwnPropertyKeys]]`, which runs the `ownKeys` trap
+/// The result must include every property of the target which can't be removed, and exactly the target's
+/// properties if the target isn't extensible
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-ownpropertykeys
+pub fn own_property_keys(proxy: &Value, interpreter: &mut Interpreter) -> Result<Vec<PropertyKey>, Value> {
+    let trap = get_trap(proxy, "ownKeys", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.own_property_keys(&trap.target),
+    };
+    let result = trap.call(&method, vec![trap.target.clone()], interpreter)?;
+    let mut keys: Vec<PropertyKey> = Vec::new();
+    for value in function::create_list_from_array_like(&result, interpreter)? {
+        if !value.is_string() && !value.is_symbol() {
+            return Err(interpreter.type_error(format!("{} is not a valid property name", value)));
+        }
+        let key = value.to_property_key();
+        if keys.contains(&key) {
+            return Err(interpreter.type_error("'ownKeys' on proxy: trap returned duplicate entries"));
+        }
+        keys.push(key);
+    }
+    let extensible = interpreter.is_extensible(&trap.target)?;
+    let mut configurable_keys = Vec::new();
+    let mut non_configurable_keys = Vec::new();
+    for key in interpreter.own_property_keys(&trap.target)? {
+        match interpreter.get_own_property(&trap.target, &key)? {
+            Some(ref prop) if !prop.configurable => non_configurable_keys.push(key),
+            _ => configurable_keys.push(key),
+        }
+    }
+    if extensible && non_configurable_keys.is_empty() {
+        return Ok(keys);
+    }
+    let mut unchecked = keys.clone();
+    let mut check = |key: PropertyKey, interpreter: &mut Interpreter| match unchecked.iter().position(|other| *other == key) {
+        Some(position) => {
+            unchecked.remove(position);
+            Ok(())
+        }
+        None => Err(interpreter.type_error(format!("'ownKeys' on proxy: trap result did not include '{}'", key))),
+    };
+    for key in non_configurable_keys {
+        check(key, interpreter)?;
+    }
+    if extensible {
+        return Ok(keys);
+    }
+    for key in configurable_keys {
+        check(key, interpreter)?;
+    }
+    if !unchecked.is_empty() {
+        return Err(interpreter.type_error("'ownKeys' on proxy: trap returned extra keys but proxy target is non-extensible"));
+    }
+    Ok(keys)
+}
+
+/// `[[Call]]` of a proxy for a function, which runs the `apply` trap with the arguments in an array
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-call-thisargument-argumentslist
+pub fn call_proxy(this: Value, proxy: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let trap = get_trap(&proxy, "apply", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.call(&trap.target, &this, args),
+    };
+    let args = array::new_array(&interpreter.get_global_object(), args);
+    trap.call(&method, vec![trap.target.clone(), this, args], interpreter)
+}
+
+/// `[[Construct]]`, which runs the `construct` trap with the arguments in an array
+/// https://tc39.es/ecma262/#sec-proxy-object-internal-methods-and-internal-slots-construct-argumentslist-newtarget
+pub fn construct(proxy: &Value, args: Vec<Value>, new_target: &Value, interpreter: &mut Interpreter) -> ResultValue {
+    if !proxy.is_function() {
+        return Err(interpreter.type_error("proxy is not a constructor"));
+    }
+    let trap = get_trap(proxy, "construct", interpreter)?;
+    let method = match trap.method {
+        Some(ref method) => method.clone(),
+        None => return interpreter.construct_with_new_target(&trap.target, args, new_target),
+    };
+    let args = array::new_array(&interpreter.get_global_object(), args);
+    let result = trap.call(&method, vec![trap.target.clone(), args, new_target.clone()], interpreter)?;
+    if !is_object(&result) {
+        return Err(interpreter.type_error(format!("'construct' on proxy: trap returned non-object ('{}')", result)));
+    }
+    Ok(result)
+}
+
+/// Create a proxy for a target, which is callable if the target is
+/// https://tc39.es/ecma262/#sec-proxycreate
+pub fn proxy_create(target: &Value, handler: &Value, interpreter: &mut Interpreter) -> ResultValue {
+    if !is_object(target) || !is_object(handler) {
+        return Err(interpreter.type_error("Cannot create proxy with a non-object as target or handler"));
+    }
+    let proxy = if target.is_function() {
+        to_value(call_proxy as NativeFunctionData)
+    } else {
+        ValueData::new_obj(None)
+    };
+    proxy.set_private_field_slice(PROXY_TARGET, target.clone());
+    proxy.set_private_field_slice(PROXY_HANDLER, handler.clone());
+    Ok(proxy)
+}
+
+/// Create a new proxy
+/// https://tc39.es/ecma262/#sec-proxy-target-handler
+pub fn make_proxy(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error("Constructor Proxy requires 'new'"));
+    }
+    proxy_create(&arg(&args, 0), &arg(&args, 1), interpreter)
+}
+
+/// `Proxy.revocable(target, handler)`, which makes a proxy along with a function which revokes it
+/// https://tc39.es/ecma262/#sec-proxy.revocable
+pub fn revocable(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let proxy = proxy_create(&arg(&args, 0), &arg(&args, 1), interpreter)?;
+    let revoke = to_value(revoke_proxy as NativeFunctionData);
+    revoke.set_private_field_slice(REVOCABLE_PROXY, proxy.clone());
+    revoke.set_field_slice("length", to_value(0i32));
+    let result = ValueData::new_obj(Some(&interpreter.get_global_object()));
+    result.set_field_slice("proxy", proxy);
+    result.set_field_slice("revoke", revoke);
+    Ok(result)
+}
+
+/// Revoke the proxy of a revoke function, after which every operation on the proxy throws
+/// https://tc39.es/ecma262/#sec-proxy-revocation-functions
+fn revoke_proxy(_: Value, f: Value, _: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    let proxy = f.get_private_field_slice(REVOCABLE_PROXY);
+    if is_proxy(&proxy) {
+        f.set_private_field_slice(REVOCABLE_PROXY, Gc::new(ValueData::Null));
+        proxy.set_private_field_slice(PROXY_TARGET, Gc::new(ValueData::Null));
+        proxy.set_private_field_slice(PROXY_HANDLER, Gc::new(ValueData::Null));
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Create a new `Proxy` object, which has no `prototype` property
+pub fn _create(_: &Value) -> Value {
+    let proxy = to_value(make_proxy as NativeFunctionData);
+    proxy.set_field_slice("revocable", to_value(revocable as NativeFunctionData));
+    proxy.set_field_slice("length", to_value(2i32));
+    proxy
+}
+
+/// Initialise the global object with the `Proxy` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Proxy", _create(global));
+}
@@ -0,0 +1,184 @@
+/// The global `Reflect` object, whose functions run the internal methods of objects
+/// https://tc39.es/ecma262/#sec-reflect-object
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::array;
+use crate::js::function::{self, NativeFunctionData};
+use crate::js::object;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, ToValue, Value, ValueData};
+
+/// Get the target argument, which must be an object
+fn target_arg(args: &[Value], name: &str, interpreter: &mut Interpreter) -> Result<Value, Value> {
+    let target = arg(args, 0);
+    if !target.is_object() && !target.is_function() {
+        return Err(interpreter.type_error(format!("Reflect.{} called on non-object", name)));
+    }
+    Ok(target)
+}
+
+/// Call a function with a `this` value and the arguments in an array-like object
+/// https://tc39.es/ecma262/#sec-reflect.apply
+pub fn apply(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = arg(&args, 0);
+    if !target.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", target)));
+    }
+    let arg_list = function::create_list_from_array_like(&arg(&args, 2), interpreter)?;
+    interpreter.call(&target, &arg(&args, 1), arg_list)
+}
+
+/// Construct an object with a constructor and the arguments in an array-like object, where the prototype comes
+/// from `newTarget`, which is the constructor if it isn't given
+/// https://tc39.es/ecma262/#sec-reflect.construct
+pub fn construct(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = arg(&args, 0);
+    if !target.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a constructor", target)));
+    }
+    let new_target = match args.get(2) {
+        Some(new_target) => new_target.clone(),
+        None => target.clone(),
+    };
+    if !new_target.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a constructor", new_target)));
+    }
+    let arg_list = function::create_list_from_array_like(&arg(&args, 1), interpreter)?;
+    interpreter.construct_with_new_target(&target, arg_list, &new_target)
+}
+
+/// Define a property, returning false instead of throwing if it can't be defined
+/// https://tc39.es/ecma262/#sec-reflect.defineproperty
+pub fn define_property(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "defineProperty", interpreter)?;
+    let key = object::to_property_key(&arg(&args, 1), interpreter)?;
+    let attributes = arg(&args, 2);
+    if !attributes.is_object() && !attributes.is_function() {
+        return Err(interpreter.type_error(format!("Property description must be an object: {}", attributes)));
+    }
+    let desc = object::to_property_descriptor(&attributes, interpreter)?;
+    Ok(to_value(interpreter.define_own_property(&target, key, desc)?))
+}
+
+/// Remove a property, returning false if it can't be removed
+/// https://tc39.es/ecma262/#sec-reflect.deleteproperty
+pub fn delete_property(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "deleteProperty", interpreter)?;
+    let key = object::to_property_key(&arg(&args, 1), interpreter)?;
+    Ok(to_value(interpreter.delete_property(&target, &key)?))
+}
+
+/// Get the value of a property, calling a getter with `receiver` as `this`, which is the target if it isn't given
+/// https://tc39.es/ecma262/#sec-reflect.get
+pub fn get(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "get", interpreter)?;
+    let key = object::to_property_key(&arg(&args, 1), interpreter)?;
+    let receiver = match args.get(2) {
+        Some(receiver) => receiver.clone(),
+        None => target.clone(),
+    };
+    interpreter.get(&target, &key, &receiver)
+}
+
+/// Get the descriptor of an own property, or undefined if there isn't one
+/// https://tc39.es/ecma262/#sec-reflect.getownpropertydescriptor
+pub fn get_own_property_descriptor(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "getOwnPropertyDescriptor", interpreter)?;
+    let key = object::to_property_key(&arg(&args, 1), interpreter)?;
+    Ok(match interpreter.get_own_property(&target, &key)? {
+        Some(prop) => object::from_property(&prop, &interpreter.get_global_object()),
+        None => Gc::new(ValueData::Undefined),
+    })
+}
+
+/// Get the prototype of an object
+/// https://tc39.es/ecma262/#sec-reflect.getprototypeof
+pub fn get_prototype_of(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "getPrototypeOf", interpreter)?;
+    interpreter.get_prototype_of(&target)
+}
+
+/// Returns true if the object or its prototype chain has the property, like the `in` operator
+/// https://tc39.es/ecma262/#sec-reflect.has
+pub fn has(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "has", interpreter)?;
+    let key = object::to_property_key(&arg(&args, 1), interpreter)?;
+    Ok(to_value(interpreter.has_property(&target, &key)?))
+}
+
+/// Returns true if properties can be added to the object
+/// https://tc39.es/ecma262/#sec-reflect.isextensible
+pub fn is_extensible(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "isExtensible", interpreter)?;
+    Ok(to_value(interpreter.is_extensible(&target)?))
+}
+
+/// Get the keys of an object's own properties, including symbols and keys which aren't enumerable
+/// https://tc39.es/ecma262/#sec-reflect.ownkeys
+pub fn own_keys(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "ownKeys", interpreter)?;
+    let keys = interpreter
+        .own_property_keys(&target)?
+        .iter()
+        .map(|key| key.to_value())
+        .collect();
+    Ok(array::new_array(&interpreter.get_global_object(), keys))
+}
+
+/// Stop properties from being added to an object, returning false if it refused
+/// https://tc39.es/ecma262/#sec-reflect.preventextensions
+pub fn prevent_extensions(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "preventExtensions", interpreter)?;
+    Ok(to_value(interpreter.prevent_extensions(&target)?))
+}
+
+/// Set a property, returning false instead of failing silently if it is read-only
+/// https://tc39.es/ecma262/#sec-reflect.set
+pub fn set(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "set", interpreter)?;
+    let key = object::to_property_key(&arg(&args, 1), interpreter)?;
+    let receiver = match args.get(3) {
+        Some(receiver) => receiver.clone(),
+        None => target.clone(),
+    };
+    Ok(to_value(interpreter.set(&target, &key, arg(&args, 2), &receiver)?))
+}
+
+/// Set the prototype of an object, returning false if it can't be changed
+/// https://tc39.es/ecma262/#sec-reflect.setprototypeof
+pub fn set_prototype_of(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = target_arg(&args, "setPrototypeOf", interpreter)?;
+    let proto = arg(&args, 1);
+    if !proto.is_object() && !proto.is_function() && !proto.is_null() {
+        return Err(interpreter.type_error(format!("Object prototype may only be an Object or null: {}", proto)));
+    }
+    Ok(to_value(interpreter.set_prototype_of(&target, proto)?))
+}
+
+/// Create a new `Reflect` object
+pub fn _create(global: &Value) -> Value {
+    let reflect = ValueData::new_obj(Some(global));
+    reflect.set_field_slice("apply", to_value(apply as NativeFunctionData));
+    reflect.set_field_slice("construct", to_value(construct as NativeFunctionData));
+    reflect.set_field_slice("defineProperty", to_value(define_property as NativeFunctionData));
+    reflect.set_field_slice("deleteProperty", to_value(delete_property as NativeFunctionData));
+    reflect.set_field_slice("get", to_value(get as NativeFunctionData));
+    reflect.set_field_slice(
+        "getOwnPropertyDescriptor",
+        to_value(get_own_property_descriptor as NativeFunctionData),
+    );
+    reflect.set_field_slice("getPrototypeOf", to_value(get_prototype_of as NativeFunctionData));
+    reflect.set_field_slice("has", to_value(has as NativeFunctionData));
+    reflect.set_field_slice("isExtensible", to_value(is_extensible as NativeFunctionData));
+    reflect.set_field_slice("ownKeys", to_value(own_keys as NativeFunctionData));
+    reflect.set_field_slice("preventExtensions", to_value(prevent_extensions as NativeFunctionData));
+    reflect.set_field_slice("set", to_value(set as NativeFunctionData));
+    reflect.set_field_slice("setPrototypeOf", to_value(set_prototype_of as NativeFunctionData));
+    reflect.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("Reflect"));
+    reflect
+}
+
+/// Initialise the global object with the `Reflect` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Reflect", _create(global));
+}
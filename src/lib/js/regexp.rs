@@ -0,0 +1,715 @@
+use gc::Gc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::exec::Interpreter;
+use crate::js::array;
+use crate::js::conversion::to_length;
+use crate::js::function::NativeFunctionData;
+use crate::js::iterator;
+use crate::js::object::{Property, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::string::{from_utf16, get_substitution};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+use crate::regex::matcher::Captures;
+use crate::regex::Regex;
+
+/// The private field holding the pattern text of a RegExp object, which only RegExp objects have
+const ORIGINAL_SOURCE: &str = "OriginalSource";
+/// The private field holding the flags of a RegExp object
+const ORIGINAL_FLAGS: &str = "OriginalFlags";
+
+thread_local! {
+    /// Compiled patterns, shared by every RegExp object with the same source and flags
+    static COMPILED: RefCell<HashMap<(String, String), Rc<Regex>>> = RefCell::new(HashMap::new());
+}
+
+/// Compile a pattern, or get it from the cache if it has been compiled before.
+/// Invalid patterns are a `SyntaxError`.
+fn compile(source: &str, flags: &str, interpreter: &Interpreter) -> Result<Rc<Regex>, Value> {
+    let key = (source.to_string(), flags.to_string());
+    if let Some(regex) = COMPILED.with(|compiled| compiled.borrow().get(&key).cloned()) {
+        return Ok(regex);
+    }
+    let regex = Rc::new(Regex::new(source, flags).map_err(|message| interpreter.syntax_error(message))?);
+    COMPILED.with(|compiled| compiled.borrow_mut().insert(key, regex.clone()));
+    Ok(regex)
+}
+
+/// Returns true if the value is a RegExp object
+pub fn is_regexp_object(value: &Value) -> bool {
+    value.is_object() && !value.get_private_field_slice(ORIGINAL_SOURCE).is_undefined()
+}
+
+/// Show a RegExp object like its literal, such as `/a+/g`, reading its slots without running any code
+pub fn regexp_to_string(value: &Value) -> String {
+    format!(
+        "/{}/{}",
+        value.get_private_field_slice(ORIGINAL_SOURCE),
+        value.get_private_field_slice(ORIGINAL_FLAGS)
+    )
+}
+
+/// Returns true if the value should be treated as a regular expression by string methods,
+/// which can be changed with `Symbol.match`
+/// https://tc39.es/ecma262/#sec-isregexp
+pub fn is_regexp(value: &Value, interpreter: &mut Interpreter) -> Result<bool, Value> {
+    if !value.is_object() {
+        return Ok(false);
+    }
+    let matcher = interpreter.get_field(value, Symbol::well_known(WellKnownSymbol::Match))?;
+    if !matcher.is_undefined() {
+        return Ok(matcher.is_true());
+    }
+    Ok(is_regexp_object(value))
+}
+
+/// Get the compiled pattern of a RegExp object
+fn this_regex(this: &Value, method: &str, interpreter: &Interpreter) -> Result<Rc<Regex>, Value> {
+    if !is_regexp_object(this) {
+        return Err(interpreter.type_error(format!(
+            "RegExp.prototype.{} requires that 'this' be a RegExp object",
+            method
+        )));
+    }
+    let source = this.get_private_field_slice(ORIGINAL_SOURCE).to_string();
+    let flags = this.get_private_field_slice(ORIGINAL_FLAGS).to_string();
+    compile(&source, &flags, interpreter)
+}
+
+/// Get `RegExp.prototype`
+fn regexp_prototype(interpreter: &Interpreter) -> Value {
+    interpreter
+        .get_global_object()
+        .get_field_slice("RegExp")
+        .get_field_slice(PROTOTYPE)
+}
+
+/// Make `obj` a RegExp object with the given pattern and flags
+/// https://tc39.es/ecma262/#sec-regexpinitialize
+fn regexp_initialize(obj: &Value, source: &str, flags: &str, interpreter: &Interpreter) -> ResultValue {
+    compile(source, flags, interpreter)?;
+    obj.set_private_field_slice(ORIGINAL_SOURCE, to_value(source.to_string()));
+    obj.set_private_field_slice(ORIGINAL_FLAGS, to_value(flags.to_string()));
+    obj.set_field_slice("lastIndex", to_value(0i32));
+    Ok(obj.clone())
+}
+
+/// Create a new RegExp object, which is how regular expression literals are evaluated
+/// https://tc39.es/ecma262/#sec-regexpcreate
+pub fn regexp_create(source: &str, flags: &str, interpreter: &Interpreter) -> ResultValue {
+    let proto = regexp_prototype(interpreter);
+    regexp_initialize(&ValueData::new_obj_from_prototype(proto), source, flags, interpreter)
+}
+
+/// Get the index after `index`, which skips over a whole surrogate pair in unicode mode
+/// https://tc39.es/ecma262/#sec-advancestringindex
+fn advance_string_index(units: &[u16], index: usize, unicode: bool) -> usize {
+    if unicode
+        && index + 1 < units.len()
+        && (0xD800..0xDC00).contains(&units[index])
+        && (0xDC00..0xE000).contains(&units[index + 1])
+    {
+        return index + 2;
+    }
+    index + 1
+}
+
+/// Set the `lastIndex` of a regular expression
+fn set_last_index(rx: &Value, index: usize, interpreter: &mut Interpreter) -> Result<(), Value> {
+    interpreter.set_field(rx, "lastIndex", to_value(index as f64))?;
+    Ok(())
+}
+
+/// Get the flags of a regular expression as a string, through its `flags` property
+fn flags_of(rx: &Value, interpreter: &mut Interpreter) -> Result<String, Value> {
+    let flags = interpreter.get_field(rx, "flags")?;
+    interpreter.to_string(&flags)
+}
+
+/// Create a RegExp object, or call RegExp to convert a pattern to a RegExp object
+/// https://tc39.es/ecma262/#sec-regexp-pattern-flags
+pub fn make_regexp(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let pattern = arg(&args, 0);
+    let flags = arg(&args, 1);
+    // `new RegExp()` calls this with a new object made from RegExp.prototype, which becomes the RegExp object
+    let constructing = this.is_object()
+        && !is_regexp_object(&this)
+        && Gc::ptr_eq(&this.get_field_slice(INSTANCE_PROTOTYPE), &f.get_field_slice(PROTOTYPE));
+    let pattern_is_regexp = is_regexp(&pattern, interpreter)?;
+    if !constructing && pattern_is_regexp && flags.is_undefined() {
+        // Calling RegExp on a regular expression returns it unchanged
+        let constructor = interpreter.get_field(&pattern, "constructor")?;
+        if Gc::ptr_eq(&constructor, &f) {
+            return Ok(pattern);
+        }
+    }
+    let (source, flags) = if is_regexp_object(&pattern) {
+        let flags = if flags.is_undefined() {
+            pattern.get_private_field_slice(ORIGINAL_FLAGS).to_string()
+        } else {
+            interpreter.to_string(&flags)?
+        };
+        (pattern.get_private_field_slice(ORIGINAL_SOURCE).to_string(), flags)
+    } else if pattern_is_regexp {
+        let source = interpreter.get_field(&pattern, "source")?;
+        let flags = if flags.is_undefined() {
+            interpreter.get_field(&pattern, "flags")?
+        } else {
+            flags
+        };
+        (interpreter.to_string(&source)?, interpreter.to_string(&flags)?)
+    } else {
+        let source = if pattern.is_undefined() { String::new() } else { interpreter.to_string(&pattern)? };
+        let flags = if flags.is_undefined() { String::new() } else { interpreter.to_string(&flags)? };
+        (source, flags)
+    };
+    if constructing {
+        regexp_initialize(&this, &source, &flags, interpreter)
+    } else {
+        regexp_create(&source, &flags, interpreter)
+    }
+}
+
+/// Run the pattern against a string, from `lastIndex` if the expression is global or sticky
+/// https://tc39.es/ecma262/#sec-regexpbuiltinexec
+fn builtin_exec(rx: &Value, string: &[u16], interpreter: &mut Interpreter) -> ResultValue {
+    let regex = this_regex(rx, "exec", interpreter)?;
+    let last_index = interpreter.get_field(rx, "lastIndex")?;
+    let global = regex.flags.global;
+    let sticky = regex.flags.sticky;
+    let start = if global || sticky { to_length(&last_index, interpreter)? as usize } else { 0 };
+    let captures = if start > string.len() {
+        Ok(None)
+    } else if sticky {
+        regex.match_at(string, start)
+    } else {
+        regex.find_from(string, start)
+    };
+    let captures = captures.map_err(|message| interpreter.range_error(message))?;
+    let captures = match captures {
+        Some(captures) => captures,
+        None => {
+            if global || sticky {
+                set_last_index(rx, 0, interpreter)?;
+            }
+            return Ok(Gc::new(ValueData::Null));
+        }
+    };
+    let (index, end) = captures[0].unwrap();
+    if global || sticky {
+        set_last_index(rx, end, interpreter)?;
+    }
+    Ok(make_match_result(&interpreter.get_global_object(), &regex, string, &captures, index))
+}
+
+/// Create the array returned by `exec`, with the matched string and each capture
+fn make_match_result(global: &Value, regex: &Regex, string: &[u16], captures: &Captures, index: usize) -> Value {
+    let undefined = || Gc::new(ValueData::Undefined);
+    let values = captures
+        .iter()
+        .map(|capture| match *capture {
+            Some((start, end)) => to_value(from_utf16(&string[start..end])),
+            None => undefined(),
+        })
+        .collect();
+    let result = array::new_array(global, values);
+    result.set_field_slice("index", to_value(index as i32));
+    result.set_field_slice("input", to_value(from_utf16(string)));
+    // Named groups are collected into an object without a prototype
+    let groups = if regex.group_names.is_empty() {
+        undefined()
+    } else {
+        let groups = ValueData::new_obj(None);
+        for (name, group) in regex.group_names.iter() {
+            groups.set_field_slice(name, result.get_field(group.to_string()));
+        }
+        groups
+    };
+    result.set_field_slice("groups", groups.clone());
+    if regex.flags.has_indices {
+        let pairs = captures
+            .iter()
+            .map(|capture| match *capture {
+                Some((start, end)) => {
+                    array::new_array(global, vec![to_value(start as i32), to_value(end as i32)])
+                }
+                None => undefined(),
+            })
+            .collect();
+        let indices = array::new_array(global, pairs);
+        let index_groups = if groups.is_undefined() {
+            undefined()
+        } else {
+            let index_groups = ValueData::new_obj(None);
+            for (name, group) in regex.group_names.iter() {
+                index_groups.set_field_slice(name, indices.get_field(group.to_string()));
+            }
+            index_groups
+        };
+        indices.set_field_slice("groups", index_groups);
+        result.set_field_slice("indices", indices);
+    }
+    result
+}
+
+/// Run a regular expression through its `exec` method, which may have been replaced
+/// https://tc39.es/ecma262/#sec-regexpexec
+fn regexp_exec(rx: &Value, string: &[u16], interpreter: &mut Interpreter) -> ResultValue {
+    let exec = interpreter.get_field(rx, "exec")?;
+    if exec.is_function() {
+        let result = interpreter.call(&exec, rx, vec![to_value(from_utf16(string))])?;
+        if !result.is_object() && !result.is_null() {
+            return Err(interpreter.type_error("The result of exec must be an object or null"));
+        }
+        return Ok(result);
+    }
+    builtin_exec(rx, string, interpreter)
+}
+
+/// Match the pattern against a string, returning the match or null
+/// https://tc39.es/ecma262/#sec-regexp.prototype.exec
+pub fn exec(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    this_regex(&this, "exec", interpreter)?;
+    let string = interpreter.to_js_string(&arg(&args, 0))?;
+    builtin_exec(&this, &string, interpreter)
+}
+
+/// Returns true if the pattern matches the string
+/// https://tc39.es/ecma262/#sec-regexp.prototype.test
+pub fn test(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype.test called on a non-object"));
+    }
+    let string = interpreter.to_js_string(&arg(&args, 0))?;
+    let result = regexp_exec(&this, &string, interpreter)?;
+    Ok(to_value(!result.is_null()))
+}
+
+/// Get the expression as a string like `/a+/g`
+/// https://tc39.es/ecma262/#sec-regexp.prototype.tostring
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype.toString called on a non-object"));
+    }
+    let source = interpreter.get_field(&this, "source")?;
+    let source = interpreter.to_string(&source)?;
+    let flags = flags_of(&this, interpreter)?;
+    Ok(to_value(format!("/{}/{}", source, flags)))
+}
+
+/// Get the pattern text, escaped so it could be written as a regular expression literal
+/// https://tc39.es/ecma262/#sec-get-regexp.prototype.source
+pub fn get_source(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !is_regexp_object(&this) {
+        if this.is_object() && Gc::ptr_eq(&this, &regexp_prototype(interpreter)) {
+            return Ok(to_value("(?:)"));
+        }
+        return Err(interpreter.type_error("RegExp.prototype.source getter called on a non-RegExp object"));
+    }
+    let source = this.get_private_field_slice(ORIGINAL_SOURCE).to_string();
+    if source.is_empty() {
+        return Ok(to_value("(?:)"));
+    }
+    // https://tc39.es/ecma262/#sec-escaperegexppattern
+    let mut escaped = String::with_capacity(source.len());
+    let mut in_class = false;
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                escaped.push(c);
+                if let Some(next) = chars.next() {
+                    escaped.push(next);
+                }
+                continue;
+            }
+            '/' if !in_class => escaped.push_str("\\/"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            _ => {
+                match c {
+                    '[' => in_class = true,
+                    ']' => in_class = false,
+                    _ => (),
+                }
+                escaped.push(c);
+            }
+        }
+    }
+    Ok(to_value(escaped))
+}
+
+/// Get one flag of a regular expression.
+/// The getter for each flag carries the flag's letter.
+pub fn get_flag(this: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let flag = f.get_private_field_slice("Flag").to_string();
+    if !is_regexp_object(&this) {
+        if this.is_object() && Gc::ptr_eq(&this, &regexp_prototype(interpreter)) {
+            return Ok(Gc::new(ValueData::Undefined));
+        }
+        return Err(interpreter.type_error("RegExp flag getter called on a non-RegExp object"));
+    }
+    Ok(to_value(this.get_private_field_slice(ORIGINAL_FLAGS).to_string().contains(&flag)))
+}
+
+/// Get the flags of a regular expression, in their canonical order
+/// https://tc39.es/ecma262/#sec-get-regexp.prototype.flags
+pub fn get_flags(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype.flags getter called on a non-object"));
+    }
+    let mut flags = String::new();
+    for &(name, flag) in FLAG_PROPERTIES.iter() {
+        if interpreter.get_field(&this, name)?.is_true() {
+            flags.push(flag);
+        }
+    }
+    Ok(to_value(flags))
+}
+
+/// The property for each flag, in the order `flags` lists them
+const FLAG_PROPERTIES: [(&str, char); 7] = [
+    ("hasIndices", 'd'),
+    ("global", 'g'),
+    ("ignoreCase", 'i'),
+    ("multiline", 'm'),
+    ("dotAll", 's'),
+    ("unicode", 'u'),
+    ("sticky", 'y'),
+];
+
+/// Get the first match, or every match if the expression is global
+/// https://tc39.es/ecma262/#sec-regexp.prototype-@@match
+pub fn symbol_match(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype[Symbol.match] called on a non-object"));
+    }
+    let string = interpreter.to_js_string(&arg(&args, 0))?;
+    let flags = flags_of(&this, interpreter)?;
+    if !flags.contains('g') {
+        return regexp_exec(&this, &string, interpreter);
+    }
+    let unicode = flags.contains('u');
+    set_last_index(&this, 0, interpreter)?;
+    let mut matches = Vec::new();
+    loop {
+        let result = regexp_exec(&this, &string, interpreter)?;
+        if result.is_null() {
+            break;
+        }
+        let matched = interpreter.get_field(&result, "0")?;
+        let matched = interpreter.to_js_string(&matched)?;
+        if matched.is_empty() {
+            // An empty match would be found again at the same place, so move past it
+            let last_index = interpreter.get_field(&this, "lastIndex")?;
+            let last_index = to_length(&last_index, interpreter)? as usize;
+            set_last_index(&this, advance_string_index(&string, last_index, unicode), interpreter)?;
+        }
+        matches.push(to_value(matched));
+    }
+    if matches.is_empty() {
+        return Ok(Gc::new(ValueData::Null));
+    }
+    Ok(array::new_array(&interpreter.get_global_object(), matches))
+}
+
+/// Get an iterator over every match of the expression.
+/// The callee carries the shared prototype of RegExp string iterators.
+/// https://tc39.es/ecma262/#sec-regexp-prototype-matchall
+pub fn symbol_match_all(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype[Symbol.matchAll] called on a non-object"));
+    }
+    let string = interpreter.to_js_string(&arg(&args, 0))?;
+    let flags = flags_of(&this, interpreter)?;
+    // The matches come from a copy, so iterating doesn't change the expression's `lastIndex`
+    let source = interpreter.get_field(&this, "source")?;
+    let source = match *this.get_private_field_slice(ORIGINAL_SOURCE) {
+        ValueData::String(ref source) => source.to_string(),
+        _ => interpreter.to_string(&source)?,
+    };
+    let matcher = regexp_create(&source, &flags, interpreter)?;
+    let last_index = interpreter.get_field(&this, "lastIndex")?;
+    let last_index = to_length(&last_index, interpreter)? as usize;
+    set_last_index(&matcher, last_index, interpreter)?;
+
+    let iterator = ValueData::new_obj_from_prototype(f.get_private_field_slice("RegExpStringIteratorPrototype"));
+    iterator.set_private_field_slice("IteratingRegExp", matcher);
+    iterator.set_private_field_slice("IteratedString", to_value(string));
+    iterator.set_private_field_slice("Global", to_value(flags.contains('g')));
+    iterator.set_private_field_slice("Unicode", to_value(flags.contains('u')));
+    iterator.set_private_field_slice("Done", to_value(false));
+    Ok(iterator)
+}
+
+/// Get the next match of a RegExp string iterator
+/// https://tc39.es/ecma262/#sec-%regexpstringiteratorprototype%.next
+pub fn regexp_string_iterator_next(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    let matcher = this.get_private_field_slice("IteratingRegExp");
+    if matcher.is_undefined() {
+        return Err(interpreter.type_error("next called on an object which isn't a RegExp String Iterator"));
+    }
+    if this.get_private_field_slice("Done").is_true() {
+        return Ok(iterator::create_iter_result_object(&global, Gc::new(ValueData::Undefined), true));
+    }
+    let string = this.get_private_field_slice("IteratedString").to_js_string();
+    let result = regexp_exec(&matcher, &string, interpreter)?;
+    if result.is_null() {
+        this.set_private_field_slice("Done", to_value(true));
+        return Ok(iterator::create_iter_result_object(&global, Gc::new(ValueData::Undefined), true));
+    }
+    if !this.get_private_field_slice("Global").is_true() {
+        this.set_private_field_slice("Done", to_value(true));
+        return Ok(iterator::create_iter_result_object(&global, result, false));
+    }
+    let matched = interpreter.get_field(&result, "0")?;
+    if interpreter.to_string(&matched)?.is_empty() {
+        let last_index = interpreter.get_field(&matcher, "lastIndex")?;
+        let last_index = to_length(&last_index, interpreter)? as usize;
+        let unicode = this.get_private_field_slice("Unicode").is_true();
+        set_last_index(&matcher, advance_string_index(&string, last_index, unicode), interpreter)?;
+    }
+    Ok(iterator::create_iter_result_object(&global, result, false))
+}
+
+/// Get the index of the first match, or -1
+/// https://tc39.es/ecma262/#sec-regexp.prototype-@@search
+pub fn symbol_search(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype[Symbol.search] called on a non-object"));
+    }
+    let string = interpreter.to_js_string(&arg(&args, 0))?;
+    // Searching always starts from the beginning, and leaves `lastIndex` as it was
+    let previous_last_index = interpreter.get_field(&this, "lastIndex")?;
+    interpreter.set_field(&this, "lastIndex", to_value(0i32))?;
+    let result = regexp_exec(&this, &string, interpreter)?;
+    interpreter.set_field(&this, "lastIndex", previous_last_index)?;
+    if result.is_null() {
+        return Ok(to_value(-1));
+    }
+    interpreter.get_field(&result, "index")
+}
+
+/// Replace the first match, or every match if the expression is global,
+/// with a replacement string or the result of calling a function
+/// https://tc39.es/ecma262/#sec-regexp.prototype-@@replace
+pub fn symbol_replace(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype[Symbol.replace] called on a non-object"));
+    }
+    let string = interpreter.to_js_string(&arg(&args, 0))?;
+    let replace_value = arg(&args, 1);
+    let replacement = if replace_value.is_function() {
+        None
+    } else {
+        Some(interpreter.to_js_string(&replace_value)?)
+    };
+    let flags = flags_of(&this, interpreter)?;
+    let global = flags.contains('g');
+    let unicode = flags.contains('u');
+    if global {
+        set_last_index(&this, 0, interpreter)?;
+    }
+
+    let mut results = Vec::new();
+    loop {
+        let result = regexp_exec(&this, &string, interpreter)?;
+        if result.is_null() {
+            break;
+        }
+        results.push(result.clone());
+        if !global {
+            break;
+        }
+        let matched = interpreter.get_field(&result, "0")?;
+        if interpreter.to_string(&matched)?.is_empty() {
+            let last_index = interpreter.get_field(&this, "lastIndex")?;
+            let last_index = to_length(&last_index, interpreter)? as usize;
+            set_last_index(&this, advance_string_index(&string, last_index, unicode), interpreter)?;
+        }
+    }
+
+    let mut accumulated = Vec::with_capacity(string.len());
+    let mut next_source_position = 0;
+    for result in results {
+        let length = interpreter.get_field(&result, "length")?;
+        let capture_count = (to_length(&length, interpreter)? as usize).saturating_sub(1);
+        let matched = interpreter.get_field(&result, "0")?;
+        let matched = interpreter.to_js_string(&matched)?;
+        let position = interpreter.get_field(&result, "index")?;
+        let position = interpreter.to_number(&position)?;
+        let position = if position.is_nan() { 0 } else { position.max(0.0).min(string.len() as f64) as usize };
+        let mut captures = Vec::with_capacity(capture_count);
+        for index in 1..=capture_count {
+            let capture = interpreter.get_field(&result, index.to_string())?;
+            captures.push(if capture.is_undefined() {
+                capture
+            } else {
+                to_value(interpreter.to_js_string(&capture)?)
+            });
+        }
+        let named_captures = interpreter.get_field(&result, "groups")?;
+        let replaced = match replacement {
+            Some(ref replacement) => {
+                get_substitution(&matched, &string, position, &captures, &named_captures, replacement)
+            }
+            None => {
+                let mut replacer_args = vec![to_value(from_utf16(&matched))];
+                replacer_args.extend(captures);
+                replacer_args.push(to_value(position as i32));
+                replacer_args.push(to_value(from_utf16(&string)));
+                if !named_captures.is_undefined() {
+                    replacer_args.push(named_captures);
+                }
+                let undefined = Gc::new(ValueData::Undefined);
+                let replaced = interpreter.call(&replace_value, &undefined, replacer_args)?;
+                interpreter.to_js_string(&replaced)?.to_vec()
+            }
+        };
+        // Matches which overlap an earlier match are skipped
+        if position >= next_source_position {
+            accumulated.extend_from_slice(&string[next_source_position..position]);
+            accumulated.extend(replaced);
+            next_source_position = position + matched.len();
+        }
+    }
+    if next_source_position < string.len() {
+        accumulated.extend_from_slice(&string[next_source_position..]);
+    }
+    Ok(to_value(from_utf16(&accumulated)))
+}
+
+/// Split a string at each match of the expression, including the captures of each match
+/// https://tc39.es/ecma262/#sec-regexp.prototype-@@split
+pub fn symbol_split(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() {
+        return Err(interpreter.type_error("RegExp.prototype[Symbol.split] called on a non-object"));
+    }
+    let global = interpreter.get_global_object();
+    let string = interpreter.to_js_string(&arg(&args, 0))?;
+    let flags = flags_of(&this, interpreter)?;
+    let unicode = flags.contains('u');
+    // The splitter is a sticky copy of the expression, so it only matches where it is told to
+    let source = interpreter.get_field(&this, "source")?;
+    let source = match *this.get_private_field_slice(ORIGINAL_SOURCE) {
+        ValueData::String(ref source) => source.to_string(),
+        _ => interpreter.to_string(&source)?,
+    };
+    let splitter_flags = if flags.contains('y') { flags } else { format!("{}y", flags) };
+    let splitter = regexp_create(&source, &splitter_flags, interpreter)?;
+
+    let limit = match args.get(1) {
+        Some(limit) if !limit.is_undefined() => {
+            let limit = interpreter.to_number(limit)?;
+            if limit.is_finite() { (limit.trunc() as i64 as u32) as usize } else { 0 }
+        }
+        _ => u32::MAX as usize,
+    };
+    let mut parts = Vec::new();
+    if limit == 0 {
+        return Ok(array::new_array(&global, parts));
+    }
+    if string.is_empty() {
+        if regexp_exec(&splitter, &string, interpreter)?.is_null() {
+            parts.push(to_value(String::new()));
+        }
+        return Ok(array::new_array(&global, parts));
+    }
+    let mut p = 0;
+    let mut q = 0;
+    while q < string.len() {
+        set_last_index(&splitter, q, interpreter)?;
+        let result = regexp_exec(&splitter, &string, interpreter)?;
+        if result.is_null() {
+            q = advance_string_index(&string, q, unicode);
+            continue;
+        }
+        let end = interpreter.get_field(&splitter, "lastIndex")?;
+        let end = (to_length(&end, interpreter)? as usize).min(string.len());
+        if end == p {
+            q = advance_string_index(&string, q, unicode);
+            continue;
+        }
+        parts.push(to_value(from_utf16(&string[p..q])));
+        if parts.len() >= limit {
+            return Ok(array::new_array(&global, parts));
+        }
+        p = end;
+        let length = interpreter.get_field(&result, "length")?;
+        let capture_count = (to_length(&length, interpreter)? as usize).saturating_sub(1);
+        for index in 1..=capture_count {
+            parts.push(interpreter.get_field(&result, index.to_string())?);
+            if parts.len() >= limit {
+                return Ok(array::new_array(&global, parts));
+            }
+        }
+        q = p;
+    }
+    parts.push(to_value(from_utf16(&string[p..])));
+    Ok(array::new_array(&global, parts))
+}
+
+/// Define a getter on the prototype
+fn define_getter(proto: &Value, name: &str, getter: Value) {
+    proto.set_prop_slice(
+        name,
+        Property {
+            configurable: true,
+            enumerable: false,
+            writable: false,
+            value: Gc::new(ValueData::Undefined),
+            get: getter,
+            set: Gc::new(ValueData::Undefined),
+        },
+    );
+}
+
+/// Create the `%RegExpStringIteratorPrototype%` object
+fn create_regexp_string_iterator_prototype(global: &Value) -> Value {
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("next", to_value(regexp_string_iterator_next as NativeFunctionData));
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::Iterator),
+        to_value(iterator::return_this as NativeFunctionData),
+    );
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::ToStringTag),
+        to_value("RegExp String Iterator"),
+    );
+    prototype
+}
+
+/// Create a new `RegExp` object
+pub fn _create(global: &Value) -> Value {
+    let regexp = to_value(make_regexp as NativeFunctionData);
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("exec", to_value(exec as NativeFunctionData));
+    proto.set_field_slice("test", to_value(test as NativeFunctionData));
+    proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    define_getter(&proto, "source", to_value(get_source as NativeFunctionData));
+    define_getter(&proto, "flags", to_value(get_flags as NativeFunctionData));
+    for &(name, flag) in FLAG_PROPERTIES.iter() {
+        let getter = to_value(get_flag as NativeFunctionData);
+        getter.set_private_field_slice("Flag", to_value(flag.to_string()));
+        define_getter(&proto, name, getter);
+    }
+    proto.set_field(Symbol::well_known(WellKnownSymbol::Match), to_value(symbol_match as NativeFunctionData));
+    let match_all = to_value(symbol_match_all as NativeFunctionData);
+    match_all.set_private_field_slice("RegExpStringIteratorPrototype", create_regexp_string_iterator_prototype(global));
+    proto.set_field(Symbol::well_known(WellKnownSymbol::MatchAll), match_all);
+    proto.set_field(Symbol::well_known(WellKnownSymbol::Replace), to_value(symbol_replace as NativeFunctionData));
+    proto.set_field(Symbol::well_known(WellKnownSymbol::Search), to_value(symbol_search as NativeFunctionData));
+    proto.set_field(Symbol::well_known(WellKnownSymbol::Split), to_value(symbol_split as NativeFunctionData));
+    proto.set_field_slice("constructor", regexp.clone());
+    regexp.set_field_slice(PROTOTYPE, proto);
+    regexp
+}
+
+/// Initialise the `RegExp` object on the global object
+pub fn init(global: &Value) {
+    global.set_field_slice("RegExp", _create(global));
+}
@@ -0,0 +1,133 @@
+/// The global `Set` object
+/// https://tc39.es/ecma262/#sec-set-objects
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::function::NativeFunctionData;
+use crate::js::iterator::{self, ArrayIterationKind};
+use crate::js::map::{self, create_entries_iterator, iterator_method};
+use crate::js::object::PROTOTYPE;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+
+/// The private field holding the entries of a set, the [[SetData]] internal slot.
+/// A set keeps its values as the entries of a map, where each value is its own key.
+pub static SET_DATA: &str = "SetData";
+
+/// Get the entries of the set `this`, or throw if it isn't a set
+fn this_set_data(this: &Value, method: &str, interpreter: &Interpreter) -> ResultValue {
+    let entries = this.get_private_field_slice(SET_DATA);
+    if entries.is_undefined() {
+        return Err(interpreter.type_error(format!("Set.prototype.{} requires that 'this' be a Set", method)));
+    }
+    Ok(entries)
+}
+
+/// Create a new set, adding the values of an iterable with the set's `add` method
+/// https://tc39.es/ecma262/#sec-set-iterable
+pub fn make_set(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error("Constructor Set requires 'new'"));
+    }
+    this.set_private_field_slice(SET_DATA, map::new_entries());
+    let iterable = arg(&args, 0);
+    if iterable.is_null_or_undefined() {
+        return Ok(this);
+    }
+    let adder = interpreter.get_field(&this, "add")?;
+    if !adder.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", adder)));
+    }
+    for value in iterator::iterable_to_list(&iterable, interpreter)? {
+        interpreter.call(&adder, &this, vec![value])?;
+    }
+    Ok(this)
+}
+
+/// `Set.prototype.add(value)`
+/// https://tc39.es/ecma262/#sec-set.prototype.add
+pub fn add(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "add", interpreter)?;
+    let value = arg(&args, 0);
+    if !map::entries_has(&entries, &value) {
+        map::entries_set(&entries, value.clone(), value);
+    }
+    Ok(this)
+}
+
+/// `Set.prototype.has(value)`
+/// https://tc39.es/ecma262/#sec-set.prototype.has
+pub fn has(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "has", interpreter)?;
+    Ok(to_value(map::entries_has(&entries, &arg(&args, 0))))
+}
+
+/// `Set.prototype.delete(value)`
+/// https://tc39.es/ecma262/#sec-set.prototype.delete
+pub fn delete(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "delete", interpreter)?;
+    Ok(to_value(map::entries_delete(&entries, &arg(&args, 0))))
+}
+
+/// `Set.prototype.clear()`
+/// https://tc39.es/ecma262/#sec-set.prototype.clear
+pub fn clear(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "clear", interpreter)?;
+    map::entries_clear(&entries);
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// `get Set.prototype.size`
+/// https://tc39.es/ecma262/#sec-get-set.prototype.size
+pub fn get_size(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "size", interpreter)?;
+    Ok(to_value(map::entries_size(&entries)))
+}
+
+/// `Set.prototype.forEach(callback, thisArg)`, which passes each value as both the value and the key
+/// https://tc39.es/ecma262/#sec-set.prototype.foreach
+pub fn for_each(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "forEach", interpreter)?;
+    map::entries_for_each(&entries, &this, &args, interpreter)
+}
+
+/// `Set.prototype.values()`, which is also `Set.prototype.keys` and `Set.prototype[Symbol.iterator]`
+/// https://tc39.es/ecma262/#sec-set.prototype.values
+pub fn values(this: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "values", interpreter)?;
+    Ok(create_entries_iterator(&entries, ArrayIterationKind::Values, &f))
+}
+
+/// `Set.prototype.entries()`, which gives each value as `[value, value]`
+/// https://tc39.es/ecma262/#sec-set.prototype.entries
+pub fn entries(this: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let entries = this_set_data(&this, "entries", interpreter)?;
+    Ok(create_entries_iterator(&entries, ArrayIterationKind::Entries, &f))
+}
+
+/// Create a new `Set` object
+pub fn _create(global: &Value) -> Value {
+    let set = to_value(make_set as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("add", to_value(add as NativeFunctionData));
+    prototype.set_field_slice("has", to_value(has as NativeFunctionData));
+    prototype.set_field_slice("delete", to_value(delete as NativeFunctionData));
+    prototype.set_field_slice("clear", to_value(clear as NativeFunctionData));
+    prototype.set_field_slice("forEach", to_value(for_each as NativeFunctionData));
+    prototype.set_prop_slice("size", map::size_property(get_size));
+    let iterator_prototype = map::create_iterator_prototype(global, "Set Iterator");
+    let values = iterator_method(values, &iterator_prototype);
+    prototype.set_field_slice("entries", iterator_method(entries, &iterator_prototype));
+    prototype.set_field_slice("keys", values.clone());
+    prototype.set_field_slice("values", values.clone());
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::Iterator), values);
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("Set"));
+    set.set_field_slice("length", to_value(0i32));
+    prototype.set_field_slice("constructor", set.clone());
+    set.set_field_slice(PROTOTYPE, prototype);
+    set
+}
+
+/// Initialise the global object with the `Set` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Set", _create(global));
+}
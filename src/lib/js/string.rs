@@ -1,50 +1,919 @@
 use gc::Gc;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Deref;
+use unicode_normalization::UnicodeNormalization;
+use crate::exec::Interpreter;
+use crate::js::conversion::{relative_index, to_integer_or_infinity};
+use crate::js::array;
 use crate::js::function::NativeFunctionData;
-use crate::js::object::{Property, PROTOTYPE};
-use crate::js::value::{from_value, to_value, ResultValue, Value, ValueData};
-use std::f64::NAN; // Not a Number
+use crate::js::iterator;
+use crate::js::object::{Property, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::regexp;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+
+/// A string value, stored as the UTF-16 code units Javascript sees so that lone surrogates are kept intact
+/// https://tc39.es/ecma262/#sec-ecmascript-language-types-string-type
+#[derive(Trace, Finalize, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JsString(Vec<u16>);
+
+impl JsString {
+    /// Make a string from UTF-16 code units
+    pub fn from_units(units: Vec<u16>) -> JsString {
+        JsString(units)
+    }
+
+    /// Join two strings
+    pub fn concat(&self, other: &JsString) -> JsString {
+        JsString([&self.0[..], &other.0[..]].concat())
+    }
+}
+
+impl Deref for JsString {
+    type Target = [u16];
+    fn deref(&self) -> &[u16] {
+        &self.0
+    }
+}
+
+impl<'s> From<&'s str> for JsString {
+    fn from(string: &'s str) -> JsString {
+        JsString(to_utf16(string))
+    }
+}
+
+impl From<String> for JsString {
+    fn from(string: String) -> JsString {
+        JsString::from(string.as_str())
+    }
+}
+
+impl<'s> PartialEq<&'s str> for JsString {
+    fn eq(&self, other: &&'s str) -> bool {
+        self.0.iter().cloned().eq(other.encode_utf16())
+    }
+}
+
+/// Rust strings can't hold lone surrogates, so they are shown as U+FFFD
+impl fmt::Display for JsString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in char::decode_utf16(self.0.iter().cloned()) {
+            write!(f, "{}", c.unwrap_or(char::REPLACEMENT_CHARACTER))?;
+        }
+        Ok(())
+    }
+}
+
+/// Get the UTF-16 code units of a Rust string
+pub fn to_utf16(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
+
+/// Make a string from UTF-16 code units
+pub fn from_utf16(units: &[u16]) -> JsString {
+    JsString::from_units(units.to_vec())
+}
+
+/// Get the code unit at an index of a string, as a string
+pub fn code_unit_at(string: &JsString, index: usize) -> Option<JsString> {
+    string.get(index).map(|unit| from_utf16(&[*unit]))
+}
+
+/// Apply a mapping to each run of valid code points in a string, keeping lone surrogates as they are
+fn map_well_formed<F: Fn(&str) -> String>(string: &JsString, map: F) -> JsString {
+    let mut units = Vec::with_capacity(string.len());
+    let mut run = String::new();
+    for c in char::decode_utf16(string.iter().cloned()) {
+        match c {
+            Ok(c) => run.push(c),
+            Err(error) => {
+                units.extend(map(&run).encode_utf16());
+                run.clear();
+                units.push(error.unpaired_surrogate());
+            }
+        }
+    }
+    units.extend(map(&run).encode_utf16());
+    JsString::from_units(units)
+}
+
+/// Get the code points of a string, where lone surrogates stand for themselves
+fn code_points(string: &JsString) -> impl Iterator<Item = u32> + '_ {
+    char::decode_utf16(string.iter().cloned()).map(|c| match c {
+        Ok(c) => c as u32,
+        Err(error) => u32::from(error.unpaired_surrogate()),
+    })
+}
 
 /// https://searchfox.org/mozilla-central/source/js/src/vm/StringObject.h#19
-pub fn make_string(this: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    // If we're constructing a string, we should set the initial length
-    // To do this we need to convert the string back to a Rust String, then get the .len()
-    // let a: String = from_value(args[0].clone()).unwrap();
-    // this.set_field_slice("length", to_value(a.len() as i32));
+pub fn make_string(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let value = match args.first() {
+        // `String(symbol)` is the only way to convert a symbol to a string without an error
+        Some(value) if value.is_symbol() => to_value(value.to_string()),
+        Some(value) => to_value(interpreter.to_js_string(value)?),
+        None => to_value(String::new()),
+    };
+    // `new String()` calls this with a new object made from String.prototype, which gets wrapped around the string
+    if this.is_object()
+        && this.get_private_field_slice("PrimitiveValue").is_undefined()
+        && Gc::ptr_eq(&this.get_field_slice(INSTANCE_PROTOTYPE), &f.get_field_slice(PROTOTYPE))
+    {
+        this.set_private_field_slice("PrimitiveValue", value);
+        return Ok(this);
+    }
+    Ok(value)
+}
+
+/// Get the string `this` refers to, converting it to a string if needed
+/// https://tc39.es/ecma262/#sec-requireobjectcoercible
+fn this_string(this: &Value, method: &str, interpreter: &mut Interpreter) -> Result<JsString, Value> {
+    match **this {
+        ValueData::String(ref string) => Ok(string.clone()),
+        ValueData::Null | ValueData::Undefined => Err(interpreter.type_error(format!(
+            "String.prototype.{} called on null or undefined",
+            method
+        ))),
+        _ => {
+            let primitive = this.get_private_field_slice("PrimitiveValue");
+            if let ValueData::String(ref string) = *primitive {
+                return Ok(string.clone());
+            }
+            interpreter.to_js_string(this)
+        }
+    }
+}
+
+/// Get the string value of a string primitive or `String` object
+/// https://tc39.es/ecma262/#sec-thisstringvalue
+fn this_string_value(this: &Value, interpreter: &Interpreter) -> Result<JsString, Value> {
+    let primitive = match **this {
+        ValueData::String(_) => this.clone(),
+        _ => this.get_private_field_slice("PrimitiveValue"),
+    };
+    match *primitive {
+        ValueData::String(ref string) => Ok(string.clone()),
+        _ => Err(interpreter.type_error("String.prototype.toString requires that 'this' be a String")),
+    }
+}
+
+/// Convert an argument to a string
+fn string_arg(args: &[Value], index: usize, interpreter: &mut Interpreter) -> Result<JsString, Value> {
+    interpreter.to_js_string(&arg(args, index))
+}
+
+/// Clamp an index argument to be within the string
+fn clamp_index(value: &Value, length: usize, default: usize, interpreter: &mut Interpreter) -> Result<usize, Value> {
+    if value.is_undefined() {
+        return Ok(default);
+    }
+    Ok(to_integer_or_infinity(value, interpreter)?.max(0.0).min(length as f64) as usize)
+}
+
+/// Find the first occurrence of `needle` in `haystack` at or after `from`
+fn find_units(haystack: &[u16], needle: &[u16], from: usize) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    (from..=haystack.len() - needle.len()).find(|&index| haystack[index..].starts_with(needle))
+}
+
+/// Find the last occurrence of `needle` in `haystack` at or before `from`
+fn rfind_units(haystack: &[u16], needle: &[u16], from: usize) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    let last = from.min(haystack.len() - needle.len());
+    (0..=last).rev().find(|&index| haystack[index..].starts_with(needle))
+}
 
-    this.set_private_field_slice("PrimitiveValue", args[0].clone());
-    Ok(this)
+/// Call the method for a well-known symbol on a pattern argument, like `separator[Symbol.split]`,
+/// returning None if the pattern is null, undefined or doesn't have the method
+fn call_pattern_method(
+    pattern: &Value,
+    symbol: WellKnownSymbol,
+    args: Vec<Value>,
+    interpreter: &mut Interpreter,
+) -> Result<Option<Value>, Value> {
+    if pattern.is_null_or_undefined() {
+        return Ok(None);
+    }
+    let method = interpreter.get_field(pattern, Symbol::well_known(symbol))?;
+    if method.is_null_or_undefined() {
+        return Ok(None);
+    }
+    interpreter.call(&method, pattern, args).map(Some)
+}
+
+/// Throw a TypeError if the argument is a regular expression, for methods which only take strings
+fn reject_regexp(value: &Value, method: &str, interpreter: &mut Interpreter) -> Result<(), Value> {
+    if regexp::is_regexp(value, interpreter)? {
+        return Err(interpreter.type_error(format!(
+            "First argument to String.prototype.{} must not be a regular expression",
+            method
+        )));
+    }
+    Ok(())
+}
+
+/// Returns true if the character is white space or a line terminator, which `trim` removes
+/// https://tc39.es/ecma262/#sec-white-space
+pub fn is_trimmable(c: char) -> bool {
+    (c.is_whitespace() && c != '\u{0085}') || c == '\u{FEFF}'
+}
+
+/// Remove white space from the start and/or end of a string, which is never made of surrogates
+/// https://tc39.es/ecma262/#sec-trimstring
+fn trim_string(string: &JsString, start: bool, end: bool) -> JsString {
+    let trimmable = |unit: &u16| char::from_u32(u32::from(*unit)).is_some_and(is_trimmable);
+    let mut units = &string[..];
+    if start {
+        let leading = units.iter().take_while(|unit| trimmable(unit)).count();
+        units = &units[leading..];
+    }
+    if end {
+        let trailing = units.iter().rev().take_while(|unit| trimmable(unit)).count();
+        units = &units[..units.len() - trailing];
+    }
+    from_utf16(units)
 }
+
 /// Get a string's length
-pub fn get_string_length(this: Value, _: Value, _: Vec<Value>) -> ResultValue {
-    let this_str: String =
-        from_value(this.get_private_field(String::from("PrimitiveValue"))).unwrap();
-    Ok(to_value::<i32>(this_str.len() as i32))
+pub fn get_string_length(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "length", interpreter)?;
+    Ok(to_value(string.len() as i32))
 }
+
 /// Get the string value to a primitive string
-pub fn to_string(this: Value, _: Value, _: Vec<Value>) -> ResultValue {
-    // Get String from String Object and send it back as a new value
-    let primitive_val = this.get_private_field(String::from("PrimitiveValue"));
-    Ok(to_value(format!("{}", primitive_val).to_string()))
-}
-pub fn char_at(this: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    //          ^^ represents instance       ^^ represents arguments (we only care about the first one in this case)
-    // First we get it the actual string a private field stored on the object only the engine has access to.
-    // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String =
-        from_value(this.get_private_field(String::from("PrimitiveValue"))).unwrap();
-    let pos = from_value(args[0].clone()).unwrap();
-
-    // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-    // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-    let length = primitive_val.chars().count();
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_string_value(&this, interpreter)?))
+}
 
+/// Get the UTF-16 code unit at an index, as a string
+/// https://tc39.es/ecma262/#sec-string.prototype.charat
+pub fn char_at(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "charAt", interpreter)?;
+    let pos = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
     // We should return an empty string is pos is out of range
-    if pos >= length || pos < 0 as usize {
-        return Ok(to_value::<String>(String::new()));
+    if pos < 0.0 {
+        return Ok(to_value(String::new()));
     }
+    Ok(to_value(code_unit_at(&string, pos as usize).unwrap_or_default()))
+}
 
-    Ok(to_value::<char>(primitive_val.chars().nth(pos).unwrap()))
+/// Get the UTF-16 code unit at an index, as a number
+/// https://tc39.github.io/ecma262/#sec-string.prototype.charcodeat
+pub fn char_code_at(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "charCodeAt", interpreter)?;
+    let pos = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    // If there is no element at that index, the result is NaN
+    if pos < 0.0 {
+        return Ok(to_value(f64::NAN));
+    }
+    Ok(match string.get(pos as usize) {
+        Some(&unit) => to_value(i32::from(unit)),
+        None => to_value(f64::NAN),
+    })
 }
+
+/// Get the code point starting at an index, combining surrogate pairs
+/// https://tc39.es/ecma262/#sec-string.prototype.codepointat
+pub fn code_point_at(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "codePointAt", interpreter)?;
+    let pos = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    if pos < 0.0 || pos >= units.len() as f64 {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    let pos = pos as usize;
+    let first = units[pos];
+    let code_point = match units.get(pos + 1) {
+        Some(&second) if (0xD800..0xDC00).contains(&first) && (0xDC00..0xE000).contains(&second) => {
+            0x10000 + ((u32::from(first) - 0xD800) << 10) + (u32::from(second) - 0xDC00)
+        }
+        _ => u32::from(first),
+    };
+    Ok(to_value(code_point as i32))
+}
+
+/// Get the code unit at an index, where negative indices count back from the end
+/// https://tc39.es/ecma262/#sec-string.prototype.at
+pub fn at(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "at", interpreter)?;
+    let length = string.len() as f64;
+    let relative = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    let index = if relative < 0.0 { length + relative } else { relative };
+    if index < 0.0 || index >= length {
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    Ok(to_value(code_unit_at(&string, index as usize).unwrap_or_default()))
+}
+
+/// Join the string with the arguments
+/// https://tc39.es/ecma262/#sec-string.prototype.concat
+pub fn concat(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut units = this_string(&this, "concat", interpreter)?.to_vec();
+    for value in args.iter() {
+        units.extend_from_slice(&interpreter.to_js_string(value)?);
+    }
+    Ok(to_value(JsString::from_units(units)))
+}
+
+/// Get part of the string, where negative indices count back from the end
+/// https://tc39.es/ecma262/#sec-string.prototype.slice
+pub fn slice(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "slice", interpreter)?;
+    let start = relative_index(&arg(&args, 0), units.len(), 0, interpreter)?;
+    let end = relative_index(&arg(&args, 1), units.len(), units.len(), interpreter)?;
+    if start >= end {
+        return Ok(to_value(String::new()));
+    }
+    Ok(to_value(from_utf16(&units[start..end])))
+}
+
+/// Get part of the string between two indices, in either order
+/// https://tc39.es/ecma262/#sec-string.prototype.substring
+pub fn substring(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "substring", interpreter)?;
+    let start = clamp_index(&arg(&args, 0), units.len(), 0, interpreter)?;
+    let end = clamp_index(&arg(&args, 1), units.len(), units.len(), interpreter)?;
+    Ok(to_value(from_utf16(&units[start.min(end)..start.max(end)])))
+}
+
+/// Get part of the string from a start index with a length
+/// https://tc39.es/ecma262/#sec-string.prototype.substr
+pub fn substr(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "substr", interpreter)?;
+    let start = relative_index(&arg(&args, 0), units.len(), 0, interpreter)?;
+    let length = match args.get(1) {
+        Some(length) if !length.is_undefined() => to_integer_or_infinity(length, interpreter)?,
+        _ => units.len() as f64,
+    };
+    let end = (start as f64 + length.max(0.0)).min(units.len() as f64) as usize;
+    if start >= end {
+        return Ok(to_value(String::new()));
+    }
+    Ok(to_value(from_utf16(&units[start..end])))
+}
+
+/// Find the first index of a substring, or -1
+/// https://tc39.es/ecma262/#sec-string.prototype.indexof
+pub fn index_of(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "indexOf", interpreter)?;
+    let search = string_arg(&args, 0, interpreter)?;
+    let from = clamp_index(&arg(&args, 1), units.len(), 0, interpreter)?;
+    Ok(match find_units(&units, &search, from) {
+        Some(index) => to_value(index as i32),
+        None => to_value(-1),
+    })
+}
+
+/// Find the last index of a substring at or before a position, or -1
+/// https://tc39.es/ecma262/#sec-string.prototype.lastindexof
+pub fn last_index_of(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "lastIndexOf", interpreter)?;
+    let search = string_arg(&args, 0, interpreter)?;
+    let position = interpreter.to_number(&arg(&args, 1))?;
+    let from = if position.is_nan() {
+        units.len()
+    } else {
+        position.max(0.0).min(units.len() as f64) as usize
+    };
+    Ok(match rfind_units(&units, &search, from) {
+        Some(index) => to_value(index as i32),
+        None => to_value(-1),
+    })
+}
+
+/// Returns true if the string contains the substring at or after a position
+/// https://tc39.es/ecma262/#sec-string.prototype.includes
+pub fn includes(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "includes", interpreter)?;
+    reject_regexp(&arg(&args, 0), "includes", interpreter)?;
+    let search = string_arg(&args, 0, interpreter)?;
+    let from = clamp_index(&arg(&args, 1), units.len(), 0, interpreter)?;
+    Ok(to_value(find_units(&units, &search, from).is_some()))
+}
+
+/// Returns true if the string starts with the substring at a position
+/// https://tc39.es/ecma262/#sec-string.prototype.startswith
+pub fn starts_with(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "startsWith", interpreter)?;
+    reject_regexp(&arg(&args, 0), "startsWith", interpreter)?;
+    let search = string_arg(&args, 0, interpreter)?;
+    let start = clamp_index(&arg(&args, 1), units.len(), 0, interpreter)?;
+    Ok(to_value(units[start..].starts_with(&search)))
+}
+
+/// Returns true if the string ends with the substring before a position
+/// https://tc39.es/ecma262/#sec-string.prototype.endswith
+pub fn ends_with(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let units = this_string(&this, "endsWith", interpreter)?;
+    reject_regexp(&arg(&args, 0), "endsWith", interpreter)?;
+    let search = string_arg(&args, 0, interpreter)?;
+    let end = clamp_index(&arg(&args, 1), units.len(), units.len(), interpreter)?;
+    Ok(to_value(units[..end].ends_with(&search)))
+}
+
+/// Split the string into an array of substrings
+/// https://tc39.es/ecma262/#sec-string.prototype.split
+pub fn split(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    if this.is_null_or_undefined() {
+        this_string(&this, "split", interpreter)?;
+    }
+    let split_args = vec![this.clone(), arg(&args, 1)];
+    if let Some(parts) = call_pattern_method(&arg(&args, 0), WellKnownSymbol::Split, split_args, interpreter)? {
+        return Ok(parts);
+    }
+    let units = this_string(&this, "split", interpreter)?;
+    let limit = match args.get(1) {
+        Some(limit) if !limit.is_undefined() => {
+            let limit = interpreter.to_number(limit)?;
+            if limit.is_finite() { (limit.trunc() as i64 as u32) as usize } else { 0 }
+        }
+        _ => u32::MAX as usize,
+    };
+    let separator = arg(&args, 0);
+    if limit == 0 {
+        return Ok(array::new_array(&global, Vec::new()));
+    }
+    if separator.is_undefined() {
+        return Ok(array::new_array(&global, vec![to_value(units)]));
+    }
+    let separator = interpreter.to_js_string(&separator)?;
+    let mut parts = Vec::new();
+    if separator.is_empty() {
+        for unit in units.iter().take(limit) {
+            parts.push(to_value(from_utf16(&[*unit])));
+        }
+        return Ok(array::new_array(&global, parts));
+    }
+    let mut start = 0;
+    while let Some(index) = find_units(&units, &separator, start) {
+        parts.push(to_value(from_utf16(&units[start..index])));
+        if parts.len() >= limit {
+            return Ok(array::new_array(&global, parts));
+        }
+        start = index + separator.len();
+    }
+    parts.push(to_value(from_utf16(&units[start..])));
+    Ok(array::new_array(&global, parts))
+}
+
+/// Remove white space from both ends of the string
+/// https://tc39.es/ecma262/#sec-string.prototype.trim
+pub fn trim(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "trim", interpreter)?;
+    Ok(to_value(trim_string(&string, true, true)))
+}
+
+/// Remove white space from the start of the string
+/// https://tc39.es/ecma262/#sec-string.prototype.trimstart
+pub fn trim_start(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "trimStart", interpreter)?;
+    Ok(to_value(trim_string(&string, true, false)))
+}
+
+/// Remove white space from the end of the string
+/// https://tc39.es/ecma262/#sec-string.prototype.trimend
+pub fn trim_end(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "trimEnd", interpreter)?;
+    Ok(to_value(trim_string(&string, false, true)))
+}
+
+/// Pad the string to a length by repeating the fill string, which defaults to a space
+/// https://tc39.es/ecma262/#sec-stringpad
+fn pad(this: &Value, args: &[Value], at_start: bool, interpreter: &mut Interpreter) -> ResultValue {
+    let method = if at_start { "padStart" } else { "padEnd" };
+    let units = this_string(this, method, interpreter)?;
+    let max_length = to_integer_or_infinity(&arg(args, 0), interpreter)?;
+    if max_length <= units.len() as f64 {
+        return Ok(to_value(units));
+    }
+    let fill = match args.get(1) {
+        Some(fill) if !fill.is_undefined() => interpreter.to_js_string(fill)?,
+        _ => JsString::from(" "),
+    };
+    if fill.is_empty() {
+        return Ok(to_value(units));
+    }
+    let padding: Vec<u16> = fill
+        .iter()
+        .cycle()
+        .take(max_length as usize - units.len())
+        .cloned()
+        .collect();
+    let padded = if at_start {
+        [&padding[..], &units[..]].concat()
+    } else {
+        [&units[..], &padding[..]].concat()
+    };
+    Ok(to_value(from_utf16(&padded)))
+}
+
+/// Pad the start of the string to a length
+/// https://tc39.es/ecma262/#sec-string.prototype.padstart
+pub fn pad_start(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    pad(&this, &args, true, interpreter)
+}
+
+/// Pad the end of the string to a length
+/// https://tc39.es/ecma262/#sec-string.prototype.padend
+pub fn pad_end(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    pad(&this, &args, false, interpreter)
+}
+
+/// Repeat the string a number of times
+/// https://tc39.es/ecma262/#sec-string.prototype.repeat
+pub fn repeat(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "repeat", interpreter)?;
+    let count = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    if count < 0.0 || count.is_infinite() {
+        return Err(interpreter.range_error(format!("Invalid count value: {}", to_value(count))));
+    }
+    Ok(to_value(JsString::from_units(string.repeat(count as usize))))
+}
+
+/// Convert the string to upper case
+/// https://tc39.es/ecma262/#sec-string.prototype.touppercase
+pub fn to_upper_case(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "toUpperCase", interpreter)?;
+    Ok(to_value(map_well_formed(&string, str::to_uppercase)))
+}
+
+/// Convert the string to lower case
+/// https://tc39.es/ecma262/#sec-string.prototype.tolowercase
+pub fn to_lower_case(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "toLowerCase", interpreter)?;
+    Ok(to_value(map_well_formed(&string, str::to_lowercase)))
+}
+
+/// Expand the `$` patterns of a replacement string.
+/// `named_captures` is the `groups` object of a regular expression match, or undefined.
+/// https://tc39.es/ecma262/#sec-getsubstitution
+pub fn get_substitution(
+    matched: &[u16],
+    string: &[u16],
+    position: usize,
+    captures: &[Value],
+    named_captures: &Value,
+    replacement: &[u16],
+) -> Vec<u16> {
+    let dollar = u16::from(b'$');
+    let mut result = Vec::with_capacity(replacement.len());
+    let mut index = 0;
+    while index < replacement.len() {
+        let unit = replacement[index];
+        let next = replacement.get(index + 1).cloned();
+        if unit != dollar || next.is_none() {
+            result.push(unit);
+            index += 1;
+            continue;
+        }
+        let next = next.unwrap();
+        match next {
+            _ if next == dollar => result.push(dollar),
+            _ if next == u16::from(b'&') => result.extend_from_slice(matched),
+            _ if next == u16::from(b'`') => result.extend_from_slice(&string[..position]),
+            _ if next == u16::from(b'\'') => {
+                let end = (position + matched.len()).min(string.len());
+                result.extend_from_slice(&string[end..]);
+            }
+            _ if (u16::from(b'0')..=u16::from(b'9')).contains(&next) => {
+                // `$n` and `$nn` refer to capture groups, where a two digit group is preferred if it exists
+                let digit = |unit: Option<u16>| match unit {
+                    Some(unit) if (u16::from(b'0')..=u16::from(b'9')).contains(&unit) => Some((unit - u16::from(b'0')) as usize),
+                    _ => None,
+                };
+                let first = digit(Some(next)).unwrap();
+                let (group, length) = match digit(replacement.get(index + 2).cloned()) {
+                    Some(second) if first * 10 + second >= 1 && first * 10 + second <= captures.len() => {
+                        (first * 10 + second, 3)
+                    }
+                    _ => (first, 2),
+                };
+                if group >= 1 && group <= captures.len() {
+                    let capture = &captures[group - 1];
+                    if !capture.is_undefined() {
+                        result.extend_from_slice(&capture.to_js_string());
+                    }
+                    index += length;
+                    continue;
+                }
+                result.push(dollar);
+                result.push(next);
+            }
+            _ if next == u16::from(b'<') && !named_captures.is_undefined() => {
+                // `$<name>` refers to a named group, and is left as it is if there is no `>`
+                let close = replacement[index + 2..].iter().position(|&unit| unit == u16::from(b'>'));
+                match close {
+                    Some(length) => {
+                        let name = from_utf16(&replacement[index + 2..index + 2 + length]);
+                        let capture = named_captures.get_field(name.to_string());
+                        if !capture.is_undefined() {
+                            result.extend_from_slice(&capture.to_js_string());
+                        }
+                        index += length + 3;
+                        continue;
+                    }
+                    None => {
+                        result.push(dollar);
+                        result.push(next);
+                    }
+                }
+            }
+            _ => {
+                result.push(dollar);
+                result.push(next);
+            }
+        }
+        index += 2;
+    }
+    result
+}
+
+/// Shared implementation of `replace` and `replaceAll` with a string pattern
+fn replace_string(this: &Value, args: &[Value], all: bool, interpreter: &mut Interpreter) -> ResultValue {
+    let method = if all { "replaceAll" } else { "replace" };
+    if this.is_null_or_undefined() {
+        this_string(this, method, interpreter)?;
+    }
+    let pattern = arg(args, 0);
+    if all && regexp::is_regexp(&pattern, interpreter)? {
+        let flags = interpreter.get_field(&pattern, "flags")?;
+        if !interpreter.to_string(&flags)?.contains('g') {
+            return Err(interpreter.type_error("replaceAll must be called with a global RegExp"));
+        }
+    }
+    let replace_args = vec![this.clone(), arg(args, 1)];
+    if let Some(replaced) = call_pattern_method(&pattern, WellKnownSymbol::Replace, replace_args, interpreter)? {
+        return Ok(replaced);
+    }
+    let units = this_string(this, method, interpreter)?;
+    let pattern = string_arg(args, 0, interpreter)?;
+    let replace_value = arg(args, 1);
+    let replacement = if replace_value.is_function() {
+        None
+    } else {
+        Some(interpreter.to_js_string(&replace_value)?)
+    };
+
+    let mut positions = Vec::new();
+    let mut from = 0;
+    while let Some(position) = find_units(&units, &pattern, from) {
+        positions.push(position);
+        if !all {
+            break;
+        }
+        // An empty pattern matches between every code unit
+        from = position + pattern.len().max(1);
+        if from > units.len() {
+            break;
+        }
+    }
+
+    let mut result = Vec::with_capacity(units.len());
+    let mut end_of_last_match = 0;
+    for position in positions {
+        result.extend_from_slice(&units[end_of_last_match..position]);
+        match replacement {
+            Some(ref replacement) => {
+                let undefined = Gc::new(ValueData::Undefined);
+                result.extend(get_substitution(&pattern, &units, position, &[], &undefined, replacement));
+            }
+            None => {
+                let undefined = Gc::new(ValueData::Undefined);
+                let args = vec![
+                    to_value(from_utf16(&pattern)),
+                    to_value(position as i32),
+                    to_value(from_utf16(&units)),
+                ];
+                let replaced = interpreter.call(&replace_value, &undefined, args)?;
+                result.extend_from_slice(&interpreter.to_js_string(&replaced)?);
+            }
+        }
+        end_of_last_match = position + pattern.len();
+    }
+    result.extend_from_slice(&units[end_of_last_match..]);
+    Ok(to_value(from_utf16(&result)))
+}
+
+/// Replace the first occurrence of a pattern with a replacement string, or the result of calling a function
+/// https://tc39.es/ecma262/#sec-string.prototype.replace
+pub fn replace(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    replace_string(&this, &args, false, interpreter)
+}
+
+/// Replace every occurrence of a pattern with a replacement string, or the result of calling a function
+/// https://tc39.es/ecma262/#sec-string.prototype.replaceall
+pub fn replace_all(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    replace_string(&this, &args, true, interpreter)
+}
+
+/// Match the string against a regular expression, where other values are converted to one
+/// https://tc39.es/ecma262/#sec-string.prototype.match
+pub fn match_regexp(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if this.is_null_or_undefined() {
+        this_string(&this, "match", interpreter)?;
+    }
+    let regexp = arg(&args, 0);
+    if let Some(result) = call_pattern_method(&regexp, WellKnownSymbol::Match, vec![this.clone()], interpreter)? {
+        return Ok(result);
+    }
+    let string = this_string(&this, "match", interpreter)?;
+    let source = if regexp.is_undefined() { String::new() } else { interpreter.to_string(&regexp)? };
+    let rx = regexp::regexp_create(&source, "", interpreter)?;
+    let matcher = interpreter.get_field(&rx, Symbol::well_known(WellKnownSymbol::Match))?;
+    interpreter.call(&matcher, &rx, vec![to_value(string)])
+}
+
+/// Get an iterator over every match of a global regular expression, where other values are converted to one
+/// https://tc39.es/ecma262/#sec-string.prototype.matchall
+pub fn match_all(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if this.is_null_or_undefined() {
+        this_string(&this, "matchAll", interpreter)?;
+    }
+    let regexp = arg(&args, 0);
+    if regexp::is_regexp(&regexp, interpreter)? {
+        let flags = interpreter.get_field(&regexp, "flags")?;
+        if !interpreter.to_string(&flags)?.contains('g') {
+            return Err(interpreter.type_error("matchAll must be called with a global RegExp"));
+        }
+    }
+    if let Some(result) = call_pattern_method(&regexp, WellKnownSymbol::MatchAll, vec![this.clone()], interpreter)? {
+        return Ok(result);
+    }
+    let string = this_string(&this, "matchAll", interpreter)?;
+    let source = if regexp.is_undefined() { String::new() } else { interpreter.to_string(&regexp)? };
+    let rx = regexp::regexp_create(&source, "g", interpreter)?;
+    let matcher = interpreter.get_field(&rx, Symbol::well_known(WellKnownSymbol::MatchAll))?;
+    interpreter.call(&matcher, &rx, vec![to_value(string)])
+}
+
+/// Get the index of the first match of a regular expression, where other values are converted to one
+/// https://tc39.es/ecma262/#sec-string.prototype.search
+pub fn search(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if this.is_null_or_undefined() {
+        this_string(&this, "search", interpreter)?;
+    }
+    let regexp = arg(&args, 0);
+    if let Some(result) = call_pattern_method(&regexp, WellKnownSymbol::Search, vec![this.clone()], interpreter)? {
+        return Ok(result);
+    }
+    let string = this_string(&this, "search", interpreter)?;
+    let source = if regexp.is_undefined() { String::new() } else { interpreter.to_string(&regexp)? };
+    let rx = regexp::regexp_create(&source, "", interpreter)?;
+    let searcher = interpreter.get_field(&rx, Symbol::well_known(WellKnownSymbol::Search))?;
+    interpreter.call(&searcher, &rx, vec![to_value(string)])
+}
+
+/// Get the Unicode normalization form of the string, which defaults to NFC
+/// https://tc39.es/ecma262/#sec-string.prototype.normalize
+pub fn normalize(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "normalize", interpreter)?;
+    let form = match args.first() {
+        Some(form) if !form.is_undefined() => interpreter.to_string(form)?,
+        _ => "NFC".to_string(),
+    };
+    let normalized = match form.as_str() {
+        "NFC" => map_well_formed(&string, |run| run.nfc().collect()),
+        "NFD" => map_well_formed(&string, |run| run.nfd().collect()),
+        "NFKC" => map_well_formed(&string, |run| run.nfkc().collect()),
+        "NFKD" => map_well_formed(&string, |run| run.nfkd().collect()),
+        _ => {
+            return Err(interpreter.range_error(
+                "The normalization form should be one of NFC, NFD, NFKC, NFKD.",
+            ))
+        }
+    };
+    Ok(to_value(normalized))
+}
+
+/// Compare the string with another, returning a negative number, zero or a positive number.
+/// There is no locale support, so strings are compared by code point.
+/// https://tc39.es/ecma262/#sec-string.prototype.localecompare
+pub fn locale_compare(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "localeCompare", interpreter)?;
+    let that = string_arg(&args, 0, interpreter)?;
+    Ok(to_value(match code_points(&string).cmp(code_points(&that)) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }))
+}
+
+/// Create a new iterator over the code points of the string.
+/// The callee carries the shared prototype of string iterators.
+/// https://tc39.es/ecma262/#sec-string.prototype-@@iterator
+pub fn create_string_iterator(this: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let string = this_string(&this, "[Symbol.iterator]", interpreter)?;
+    let iterator = ValueData::new_obj_from_prototype(f.get_private_field_slice("StringIteratorPrototype"));
+    iterator.set_private_field_slice("IteratedString", to_value(string));
+    iterator.set_private_field_slice("NextIndex", to_value(0i32));
+    Ok(iterator)
+}
+
+/// Get the next code point of a string iterator
+/// https://tc39.es/ecma262/#sec-%stringiteratorprototype%.next
+pub fn string_iterator_next(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let global = interpreter.get_global_object();
+    let string = this.get_private_field_slice("IteratedString");
+    if string.is_undefined() {
+        return Ok(iterator::create_iter_result_object(&global, Gc::new(ValueData::Undefined), true));
+    }
+    let index = this.get_private_field_slice("NextIndex").to_int() as usize;
+    let string = string.to_js_string();
+    match string.get(index) {
+        Some(&first) => {
+            // A surrogate pair makes up one code point, while a lone surrogate is a code point of its own
+            let length = match string.get(index + 1) {
+                Some(&second) if (0xD800..0xDC00).contains(&first) && (0xDC00..0xE000).contains(&second) => 2,
+                _ => 1,
+            };
+            this.set_private_field_slice("NextIndex", to_value((index + length) as i32));
+            let code_point = from_utf16(&string[index..index + length]);
+            Ok(iterator::create_iter_result_object(&global, to_value(code_point), false))
+        }
+        None => {
+            this.set_private_field_slice("IteratedString", Gc::new(ValueData::Undefined));
+            Ok(iterator::create_iter_result_object(&global, Gc::new(ValueData::Undefined), true))
+        }
+    }
+}
+
+/// Make a string from UTF-16 code units
+/// https://tc39.es/ecma262/#sec-string.fromcharcode
+pub fn from_char_code(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut units = Vec::with_capacity(args.len());
+    for value in args.iter() {
+        let num = interpreter.to_number(value)?;
+        // ToUint16
+        let unit = if num.is_finite() { num.trunc() as i64 as u16 } else { 0 };
+        units.push(unit);
+    }
+    Ok(to_value(from_utf16(&units)))
+}
+
+/// Make a string from code points
+/// https://tc39.es/ecma262/#sec-string.fromcodepoint
+pub fn from_code_point(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let mut units = Vec::with_capacity(args.len());
+    for value in args.iter() {
+        let num = interpreter.to_number(value)?;
+        if num.trunc() != num || !(0.0..=1_114_111.0).contains(&num) {
+            return Err(interpreter.range_error(format!("Invalid code point {}", value)));
+        }
+        let code_point = num as u32;
+        if code_point >= 0x10000 {
+            let offset = code_point - 0x10000;
+            units.push(0xD800 + (offset >> 10) as u16);
+            units.push(0xDC00 + (offset & 0x3FF) as u16);
+        } else {
+            units.push(code_point as u16);
+        }
+    }
+    Ok(to_value(from_utf16(&units)))
+}
+
+/// Join the raw strings of a template with the substitutions between them
+/// https://tc39.es/ecma262/#sec-string.raw
+pub fn raw(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let template = arg(&args, 0);
+    if template.is_null_or_undefined() {
+        return Err(interpreter.type_error("Cannot convert undefined or null to object"));
+    }
+    let raw = interpreter.get_field(&template, "raw")?;
+    if raw.is_null_or_undefined() {
+        return Err(interpreter.type_error("Cannot convert undefined or null to object"));
+    }
+    let length = interpreter.get_field(&raw, "length")?.to_num();
+    let length = if length.is_nan() || length < 0.0 { 0 } else { length as usize };
+    let mut result = Vec::new();
+    for index in 0..length {
+        let segment = interpreter.get_field(&raw, index.to_string())?;
+        result.extend_from_slice(&interpreter.to_js_string(&segment)?);
+        if index + 1 < length && index + 1 < args.len() {
+            result.extend_from_slice(&interpreter.to_js_string(&args[index + 1])?);
+        }
+    }
+    Ok(to_value(JsString::from_units(result)))
+}
+
+/// Create the `%StringIteratorPrototype%` object
+fn create_string_iterator_prototype(global: &Value) -> Value {
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("next", to_value(string_iterator_next as NativeFunctionData));
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::Iterator),
+        to_value(iterator::return_this as NativeFunctionData),
+    );
+    prototype.set_field(
+        Symbol::well_known(WellKnownSymbol::ToStringTag),
+        to_value("String Iterator"),
+    );
+    prototype
+}
+
 /// Create a new `String` object
 pub fn _create(global: &Value) -> Value {
     let string = to_value(make_string as NativeFunctionData);
@@ -59,8 +928,45 @@ pub fn _create(global: &Value) -> Value {
     };
     proto.set_prop_slice("length", prop);
     proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    proto.set_field_slice("valueOf", to_value(to_string as NativeFunctionData));
     proto.set_field_slice("charAt", to_value(char_at as NativeFunctionData));
     proto.set_field_slice("charCodeAt", to_value(char_code_at as NativeFunctionData));
+    proto.set_field_slice("codePointAt", to_value(code_point_at as NativeFunctionData));
+    proto.set_field_slice("at", to_value(at as NativeFunctionData));
+    proto.set_field_slice("concat", to_value(concat as NativeFunctionData));
+    proto.set_field_slice("slice", to_value(slice as NativeFunctionData));
+    proto.set_field_slice("substring", to_value(substring as NativeFunctionData));
+    proto.set_field_slice("substr", to_value(substr as NativeFunctionData));
+    proto.set_field_slice("indexOf", to_value(index_of as NativeFunctionData));
+    proto.set_field_slice("lastIndexOf", to_value(last_index_of as NativeFunctionData));
+    proto.set_field_slice("includes", to_value(includes as NativeFunctionData));
+    proto.set_field_slice("startsWith", to_value(starts_with as NativeFunctionData));
+    proto.set_field_slice("endsWith", to_value(ends_with as NativeFunctionData));
+    proto.set_field_slice("split", to_value(split as NativeFunctionData));
+    proto.set_field_slice("trim", to_value(trim as NativeFunctionData));
+    proto.set_field_slice("trimStart", to_value(trim_start as NativeFunctionData));
+    proto.set_field_slice("trimEnd", to_value(trim_end as NativeFunctionData));
+    proto.set_field_slice("padStart", to_value(pad_start as NativeFunctionData));
+    proto.set_field_slice("padEnd", to_value(pad_end as NativeFunctionData));
+    proto.set_field_slice("repeat", to_value(repeat as NativeFunctionData));
+    proto.set_field_slice("toUpperCase", to_value(to_upper_case as NativeFunctionData));
+    proto.set_field_slice("toLowerCase", to_value(to_lower_case as NativeFunctionData));
+    proto.set_field_slice("toLocaleUpperCase", to_value(to_upper_case as NativeFunctionData));
+    proto.set_field_slice("toLocaleLowerCase", to_value(to_lower_case as NativeFunctionData));
+    proto.set_field_slice("replace", to_value(replace as NativeFunctionData));
+    proto.set_field_slice("replaceAll", to_value(replace_all as NativeFunctionData));
+    proto.set_field_slice("match", to_value(match_regexp as NativeFunctionData));
+    proto.set_field_slice("matchAll", to_value(match_all as NativeFunctionData));
+    proto.set_field_slice("search", to_value(search as NativeFunctionData));
+    proto.set_field_slice("normalize", to_value(normalize as NativeFunctionData));
+    proto.set_field_slice("localeCompare", to_value(locale_compare as NativeFunctionData));
+    let iterator = to_value(create_string_iterator as NativeFunctionData);
+    iterator.set_private_field_slice("StringIteratorPrototype", create_string_iterator_prototype(global));
+    proto.set_field(Symbol::well_known(WellKnownSymbol::Iterator), iterator);
+    string.set_field_slice("fromCharCode", to_value(from_char_code as NativeFunctionData));
+    string.set_field_slice("fromCodePoint", to_value(from_code_point as NativeFunctionData));
+    string.set_field_slice("raw", to_value(raw as NativeFunctionData));
+    proto.set_field_slice("constructor", string.clone());
     string.set_field_slice(PROTOTYPE, proto);
     string
 }
@@ -69,30 +975,6 @@ pub fn init(global: &Value) {
     global.set_field_slice("String", _create(global));
 }
 
-/// Get the character (UTF 16) of string
-/// https://tc39.github.io/ecma262/#sec-string.prototype.charcodeat
-pub fn char_code_at(this: Value, _: Value, args: Vec<Value>) -> ResultValue {
-    //              ^^ represents instance  ^^ represents arguments (we only care about the first one in this case)
-    // First we get it the actual string a private field stored on the object only the engine has access to.
-    // Then we convert it into a Rust String by wrapping it in from_value
-    let primitive_val: String =
-        from_value(this.get_private_field(String::from("PrimitiveValue"))).unwrap();
-
-    // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-    // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-    let length = primitive_val.chars().count();
-    let pos = from_value(args[0].clone()).unwrap();
-
-    if pos >= length || pos < 0 as usize {
-        return Ok(to_value(NAN));
-    }
-
-    let utf16_val = primitive_val.encode_utf16().nth(pos).unwrap();
-    // If there is no element at that index, the result is NaN
-    // TODO: We currently don't have NaN
-    Ok(to_value(utf16_val as f64))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,4 +985,24 @@ mod tests {
         assert_eq!(string_constructor.is_function(), true);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn check_utf16() {
+        assert_eq!(JsString::from("héllo").len(), 5);
+        assert_eq!(JsString::from("😀").len(), 2);
+        assert_eq!(code_unit_at(&JsString::from("héllo"), 1), Some(JsString::from("é")));
+        assert_eq!(code_unit_at(&JsString::from("a"), 1), None);
+    }
+
+    #[test]
+    fn check_substitution() {
+        let string = to_utf16("abc");
+        let undefined = Gc::new(ValueData::Undefined);
+        let substituted = get_substitution(&to_utf16("b"), &string, 1, &[], &undefined, &to_utf16("[$&|$`|$'|$$|$1]"));
+        assert_eq!(from_utf16(&substituted), "[b|a|c|$|$1]");
+        let captures = [to_value("x"), Gc::new(ValueData::Undefined)];
+        let groups = ValueData::new_obj(None);
+        groups.set_field_slice("name", to_value("y"));
+        let substituted = get_substitution(&to_utf16("b"), &string, 1, &captures, &groups, &to_utf16("$2$1$<name>$<none>$<"));
+        assert_eq!(from_utf16(&substituted), "xy$<");
+    }
+}
@@ -0,0 +1,234 @@
+/// The Symbol Object
+/// https://tc39.es/ecma262/#sec-symbol-objects
+use gc::Gc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::exec::Interpreter;
+use crate::js::function::NativeFunctionData;
+use crate::js::object::{Property, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+
+/// The ids below this are used by the well-known symbols, so they are the same in every realm
+static NEXT_SYMBOL_ID: AtomicU64 = AtomicU64::new(WellKnownSymbol::COUNT);
+
+thread_local! {
+    /// The GlobalSymbolRegistry, shared by every realm
+    /// https://tc39.es/ecma262/#sec-symbol.for
+    static REGISTRY: RefCell<HashMap<String, Symbol>> = RefCell::new(HashMap::new());
+}
+
+/// A symbol value, unique unless it comes from the global registry or is a well-known symbol
+#[derive(Trace, Finalize, Debug, Clone)]
+pub struct Symbol {
+    id: u64,
+    description: Option<String>,
+}
+
+impl Symbol {
+    /// Make a new unique symbol
+    pub fn new(description: Option<String>) -> Symbol {
+        Symbol {
+            id: NEXT_SYMBOL_ID.fetch_add(1, Ordering::Relaxed),
+            description,
+        }
+    }
+
+    /// Get one of the well-known symbols
+    pub fn well_known(symbol: WellKnownSymbol) -> Symbol {
+        Symbol {
+            id: symbol as u64,
+            description: Some(format!("Symbol.{}", symbol.name())),
+        }
+    }
+
+    /// The description the symbol was made with
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Symbol) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Symbol({})", self.description().unwrap_or(""))
+    }
+}
+
+/// The well-known symbols, which are used by the engine's algorithms
+/// https://tc39.es/ecma262/#sec-well-known-symbols
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WellKnownSymbol {
+    AsyncIterator,
+    HasInstance,
+    Iterator,
+    Match,
+    MatchAll,
+    Replace,
+    Search,
+    Split,
+    ToPrimitive,
+    ToStringTag,
+    Unscopables,
+}
+
+impl WellKnownSymbol {
+    const COUNT: u64 = 11;
+    const ALL: [WellKnownSymbol; 11] = [
+        WellKnownSymbol::AsyncIterator,
+        WellKnownSymbol::HasInstance,
+        WellKnownSymbol::Iterator,
+        WellKnownSymbol::Match,
+        WellKnownSymbol::MatchAll,
+        WellKnownSymbol::Replace,
+        WellKnownSymbol::Search,
+        WellKnownSymbol::Split,
+        WellKnownSymbol::ToPrimitive,
+        WellKnownSymbol::ToStringTag,
+        WellKnownSymbol::Unscopables,
+    ];
+
+    /// The name of the property of `Symbol` holding this symbol
+    pub fn name(self) -> &'static str {
+        match self {
+            WellKnownSymbol::AsyncIterator => "asyncIterator",
+            WellKnownSymbol::HasInstance => "hasInstance",
+            WellKnownSymbol::Iterator => "iterator",
+            WellKnownSymbol::Match => "match",
+            WellKnownSymbol::MatchAll => "matchAll",
+            WellKnownSymbol::Replace => "replace",
+            WellKnownSymbol::Search => "search",
+            WellKnownSymbol::Split => "split",
+            WellKnownSymbol::ToPrimitive => "toPrimitive",
+            WellKnownSymbol::ToStringTag => "toStringTag",
+            WellKnownSymbol::Unscopables => "unscopables",
+        }
+    }
+}
+
+/// Get the symbol value from `this`, which must be a symbol
+/// https://tc39.es/ecma262/#sec-thissymbolvalue
+fn this_symbol_value(this: &Value, interpreter: &Interpreter) -> Result<Symbol, Value> {
+    match **this {
+        ValueData::Symbol(ref symbol) => Ok(symbol.clone()),
+        _ => Err(interpreter.type_error("Symbol.prototype method called on a value which is not a symbol")),
+    }
+}
+
+/// Make a new symbol, `Symbol` can only be called as a function
+pub fn call_symbol(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    // `new Symbol()` calls this with a new object made from Symbol.prototype
+    if this.is_object() {
+        let proto = this.get_field_slice(INSTANCE_PROTOTYPE);
+        if Gc::ptr_eq(&proto, &f.get_field_slice(PROTOTYPE)) {
+            return Err(interpreter.type_error("Symbol is not a constructor"));
+        }
+    }
+    let description = match args.first() {
+        Some(desc) if !desc.is_undefined() => Some(desc.to_string()),
+        _ => None,
+    };
+    Ok(Gc::new(ValueData::Symbol(Symbol::new(description))))
+}
+
+/// Get the symbol in the registry with the given key, making it if it doesn't exist
+pub fn for_(_: Value, _: Value, args: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    let key = match args.first() {
+        Some(key) => key.to_string(),
+        None => "undefined".to_string(),
+    };
+    let symbol = REGISTRY.with(|registry| {
+        registry
+            .borrow_mut()
+            .entry(key.clone())
+            .or_insert_with(|| Symbol::new(Some(key)))
+            .clone()
+    });
+    Ok(Gc::new(ValueData::Symbol(symbol)))
+}
+
+/// Get the registry key of a symbol, or undefined if it isn't in the registry
+pub fn key_for(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = match args.first().map(|arg| &**arg) {
+        Some(ValueData::Symbol(ref symbol)) => symbol.clone(),
+        _ => return Err(interpreter.type_error("Symbol.keyFor argument is not a symbol")),
+    };
+    let key = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .iter()
+            .find(|(_, registered)| **registered == symbol)
+            .map(|(key, _)| key.clone())
+    });
+    Ok(match key {
+        Some(key) => to_value(key),
+        None => Gc::new(ValueData::Undefined),
+    })
+}
+
+/// Get a string like `Symbol(description)`
+pub fn to_string(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_symbol_value(&this, interpreter)?.to_string()))
+}
+
+/// Get the symbol itself
+pub fn value_of(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(Gc::new(ValueData::Symbol(this_symbol_value(&this, interpreter)?)))
+}
+
+/// The getter of `Symbol.prototype.description`
+pub fn get_description(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(match this_symbol_value(&this, interpreter)?.description() {
+        Some(description) => to_value(description),
+        None => Gc::new(ValueData::Undefined),
+    })
+}
+
+/// Create a new `Symbol` object
+pub fn _create(global: &Value) -> Value {
+    let symbol = to_value(call_symbol as NativeFunctionData);
+    let proto = ValueData::new_obj(Some(global));
+    proto.set_field_slice("toString", to_value(to_string as NativeFunctionData));
+    proto.set_field_slice("valueOf", to_value(value_of as NativeFunctionData));
+    let mut description = Property::new(Gc::new(ValueData::Undefined));
+    description.configurable = true;
+    description.get = to_value(get_description as NativeFunctionData);
+    proto.set_prop_slice("description", description);
+    proto.set_field(
+        Symbol::well_known(WellKnownSymbol::ToPrimitive),
+        to_value(value_of as NativeFunctionData),
+    );
+    proto.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("Symbol"));
+    proto.set_field_slice("constructor", symbol.clone());
+    symbol.set_field_slice(PROTOTYPE, proto);
+    symbol.set_field_slice("length", to_value(0i32));
+    symbol.set_field_slice("for", to_value(for_ as NativeFunctionData));
+    symbol.set_field_slice("keyFor", to_value(key_for as NativeFunctionData));
+    for well_known in WellKnownSymbol::ALL.iter() {
+        symbol.set_field_slice(
+            well_known.name(),
+            Gc::new(ValueData::Symbol(Symbol::well_known(*well_known))),
+        );
+    }
+    symbol
+}
+
+/// Initialise the global object with the `Symbol` object
+pub fn init(global: &Value) {
+    global.set_field_slice("Symbol", _create(global));
+}
@@ -0,0 +1,85 @@
+/// Timers and microtasks
+/// https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#timers
+use gc::Gc;
+use crate::event_loop::Timer;
+use crate::exec::Interpreter;
+use crate::js::function::NativeFunctionData;
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+
+/// Register a timer from the arguments `(callback, delay, ...args)`, returning its id
+fn add_timer(args: Vec<Value>, repeat: bool, interpreter: &mut Interpreter) -> ResultValue {
+    let mut args = args.into_iter();
+    let callback = match args.next() {
+        Some(ref f) if f.is_function() => f.clone(),
+        _ => return Err(interpreter.type_error("timer callback is not a function")),
+    };
+    let delay = match args.next() {
+        Some(delay) => interpreter.to_number(&delay)?,
+        None => 0.0,
+    };
+    // Negative, NaN and infinite delays all count as 0
+    let delay = if delay.is_finite() && delay > 0.0 {
+        delay as u64
+    } else {
+        0
+    };
+    let timer = Timer {
+        callback,
+        args: args.collect(),
+        interval: if repeat { Some(delay.max(1)) } else { None },
+    };
+    let id = interpreter.timers().add(timer, delay);
+    Ok(to_value(id as f64))
+}
+
+/// Cancel the timer with the id in the first argument
+fn remove_timer(args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    if let Some(id) = args.first() {
+        let id = interpreter.to_number(id)?;
+        if id >= 1.0 {
+            interpreter.timers().clear(id as u64);
+        }
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// Call a function once after a delay
+pub fn set_timeout(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    add_timer(args, false, interpreter)
+}
+
+/// Call a function repeatedly with a delay between each call
+pub fn set_interval(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    add_timer(args, true, interpreter)
+}
+
+/// Cancel a timer made with `setTimeout`
+pub fn clear_timeout(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    remove_timer(args, interpreter)
+}
+
+/// Cancel a timer made with `setInterval`
+pub fn clear_interval(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    remove_timer(args, interpreter)
+}
+
+/// Add a function to the job queue, to run once the current script or task has finished
+/// https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#microtask-queuing
+pub fn queue_microtask(_: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    match args.first() {
+        Some(f) if f.is_function() => {
+            interpreter.enqueue_job(f.clone(), Vec::new());
+            Ok(Gc::new(ValueData::Undefined))
+        }
+        _ => Err(interpreter.type_error("queueMicrotask callback is not a function")),
+    }
+}
+
+/// Initialise the global object with the timer functions
+pub fn init(global: &Value) {
+    global.set_field_slice("setTimeout", to_value(set_timeout as NativeFunctionData));
+    global.set_field_slice("setInterval", to_value(set_interval as NativeFunctionData));
+    global.set_field_slice("clearTimeout", to_value(clear_timeout as NativeFunctionData));
+    global.set_field_slice("clearInterval", to_value(clear_interval as NativeFunctionData));
+    global.set_field_slice("queueMicrotask", to_value(queue_microtask as NativeFunctionData));
+}
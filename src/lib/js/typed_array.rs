@@ -0,0 +1,1108 @@
+/// The global typed array objects like `Uint8Array`, and `%TypedArray%` which they all inherit from
+/// https://tc39.es/ecma262/#sec-typedarray-objects
+///
+/// Typed arrays are integer-indexed exotic objects: their elements aren't properties, but are read from and written
+/// to the bytes of their buffer in the format of their element type. `ValueData` sends numeric property keys of
+/// typed arrays here instead of looking them up, see `element_index`.
+use gc::Gc;
+use std::cmp::Ordering;
+use crate::exec::Interpreter;
+use crate::js::conversion::{relative_index, to_index, to_integer_or_infinity};
+use crate::js::array;
+use crate::js::array_buffer::{self, getter_property, ByteBuffer, BYTE_OFFSET, VIEWED_ARRAY_BUFFER};
+use crate::js::bigint::{self, BigInt};
+use crate::js::function::{set_function_name, NativeFunctionData};
+use crate::js::iterator::{self, ArrayIterationKind};
+use crate::js::number;
+use crate::js::object::{Property, PropertyDescriptor, PropertyKey, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, strict_equals, to_value, ResultValue, Value, ValueData};
+
+/// The private field holding the name of the constructor of a typed array, like `Uint8Array`, the
+/// [[TypedArrayName]] internal slot. Typed array constructors have it too, to know which kind they make.
+pub static TYPED_ARRAY_NAME: &str = "TypedArrayName";
+
+/// The private field holding the number of elements of a typed array, the [[ArrayLength]] internal slot.
+/// Typed arrays which track the length of a resizable buffer don't have it.
+pub static ARRAY_LENGTH: &str = "ArrayLength";
+
+/// The type of the elements of a typed array, which decides how they are stored in bytes
+/// https://tc39.es/ecma262/#table-the-typedarray-constructors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ElementType {
+    Int8,
+    Uint8,
+    Uint8Clamped,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+    BigInt64,
+    BigUint64,
+}
+
+/// Every element type, in the order their constructors are added to the global object
+pub const ELEMENT_TYPES: [ElementType; 11] = [
+    ElementType::Int8,
+    ElementType::Uint8,
+    ElementType::Uint8Clamped,
+    ElementType::Int16,
+    ElementType::Uint16,
+    ElementType::Int32,
+    ElementType::Uint32,
+    ElementType::Float32,
+    ElementType::Float64,
+    ElementType::BigInt64,
+    ElementType::BigUint64,
+];
+
+impl ElementType {
+    /// The number of bytes an element takes up
+    pub fn size(self) -> usize {
+        match self {
+            ElementType::Int8 | ElementType::Uint8 | ElementType::Uint8Clamped => 1,
+            ElementType::Int16 | ElementType::Uint16 => 2,
+            ElementType::Int32 | ElementType::Uint32 | ElementType::Float32 => 4,
+            ElementType::Float64 | ElementType::BigInt64 | ElementType::BigUint64 => 8,
+        }
+    }
+
+    /// The name of the constructor of typed arrays with this element type
+    pub fn name(self) -> &'static str {
+        match self {
+            ElementType::Int8 => "Int8Array",
+            ElementType::Uint8 => "Uint8Array",
+            ElementType::Uint8Clamped => "Uint8ClampedArray",
+            ElementType::Int16 => "Int16Array",
+            ElementType::Uint16 => "Uint16Array",
+            ElementType::Int32 => "Int32Array",
+            ElementType::Uint32 => "Uint32Array",
+            ElementType::Float32 => "Float32Array",
+            ElementType::Float64 => "Float64Array",
+            ElementType::BigInt64 => "BigInt64Array",
+            ElementType::BigUint64 => "BigUint64Array",
+        }
+    }
+
+    /// Get the element type from the name of its constructor
+    pub fn from_name(name: &str) -> Option<ElementType> {
+        ELEMENT_TYPES.iter().cloned().find(|kind| kind.name() == name)
+    }
+
+    /// Returns true if the elements are BigInts rather than numbers
+    pub fn is_bigint(self) -> bool {
+        self == ElementType::BigInt64 || self == ElementType::BigUint64
+    }
+
+    /// Convert a value to the kind of value the elements are, which is a BigInt or a number
+    pub fn to_numeric(self, value: &Value, interpreter: &mut Interpreter) -> ResultValue {
+        if self.is_bigint() {
+            Ok(Gc::new(ValueData::BigInt(bigint::to_bigint(value, interpreter)?)))
+        } else {
+            Ok(to_value(interpreter.to_number(value)?))
+        }
+    }
+
+    /// Convert a BigInt or a number to the bytes of an element
+    /// https://tc39.es/ecma262/#sec-numerictorawbytes
+    pub fn encode(self, value: &Value, little_endian: bool) -> Vec<u8> {
+        let mut bytes = match self {
+            ElementType::BigInt64 | ElementType::BigUint64 => match **value {
+                // Signed and unsigned integers of the same size wrap to the same bytes
+                ValueData::BigInt(ref bigint) => bigint.to_u64().to_le_bytes().to_vec(),
+                _ => vec![0; 8],
+            },
+            ElementType::Float32 => (value.to_num() as f32).to_le_bytes().to_vec(),
+            ElementType::Float64 => value.to_num().to_le_bytes().to_vec(),
+            ElementType::Uint8Clamped => {
+                let num = value.to_num();
+                let clamped = if num.is_nan() { 0.0 } else { num.clamp(0.0, 255.0).round_ties_even() };
+                vec![clamped as u8]
+            }
+            _ => {
+                let num = value.to_num();
+                let modulus = 2f64.powi(8 * self.size() as i32);
+                let wrapped = if num.is_finite() { num.trunc().rem_euclid(modulus) as u32 } else { 0 };
+                wrapped.to_le_bytes()[..self.size()].to_vec()
+            }
+        };
+        if !little_endian {
+            bytes.reverse();
+        }
+        bytes
+    }
+
+    /// Convert the bytes of an element to a BigInt or a number
+    /// https://tc39.es/ecma262/#sec-rawbytestonumeric
+    pub fn decode(self, bytes: &[u8], little_endian: bool) -> Value {
+        let mut ordered = [0; 8];
+        ordered[..self.size()].copy_from_slice(&bytes[..self.size()]);
+        if !little_endian {
+            ordered[..self.size()].reverse();
+        }
+        let [b0, b1, b2, b3, ..] = ordered;
+        match self {
+            ElementType::Int8 => to_value(i32::from(b0 as i8)),
+            ElementType::Uint8 | ElementType::Uint8Clamped => to_value(i32::from(b0)),
+            ElementType::Int16 => to_value(i32::from(i16::from_le_bytes([b0, b1]))),
+            ElementType::Uint16 => to_value(i32::from(u16::from_le_bytes([b0, b1]))),
+            ElementType::Int32 => to_value(i32::from_le_bytes([b0, b1, b2, b3])),
+            ElementType::Uint32 => to_value(f64::from(u32::from_le_bytes([b0, b1, b2, b3]))),
+            ElementType::Float32 => to_value(f64::from(f32::from_le_bytes([b0, b1, b2, b3]))),
+            ElementType::Float64 => to_value(f64::from_le_bytes(ordered)),
+            ElementType::BigInt64 => Gc::new(ValueData::BigInt(BigInt::from(i64::from_le_bytes(ordered)))),
+            ElementType::BigUint64 => Gc::new(ValueData::BigInt(BigInt::from(u64::from_le_bytes(ordered)))),
+        }
+    }
+}
+
+/// Typed arrays store their elements in the byte order of the platform
+const NATIVE_LITTLE_ENDIAN: bool = cfg!(target_endian = "little");
+
+/// The internal slots of a typed array, which say where its elements are in which bytes
+pub struct TypedArray {
+    /// The type of the elements
+    pub kind: ElementType,
+    /// The `ArrayBuffer` holding the elements
+    pub buffer: Value,
+    /// The bytes of the buffer
+    pub data: ByteBuffer,
+    /// Where the first element starts in the buffer
+    pub byte_offset: usize,
+    /// The number of elements, or `None` if the array tracks the length of a resizable buffer
+    pub array_length: Option<usize>,
+}
+
+impl TypedArray {
+    /// Get the internal slots of a typed array, or `None` if the value isn't one
+    pub fn from_value(value: &ValueData) -> Option<TypedArray> {
+        if !value.is_object() {
+            return None;
+        }
+        let kind = ElementType::from_name(&value.get_private_field_slice(TYPED_ARRAY_NAME).to_string())?;
+        let buffer = value.get_private_field_slice(VIEWED_ARRAY_BUFFER);
+        let data = array_buffer::array_buffer_data(&buffer)?;
+        let array_length = value.get_private_field_slice(ARRAY_LENGTH);
+        Some(TypedArray {
+            kind,
+            buffer,
+            data,
+            byte_offset: value.get_private_field_slice(BYTE_OFFSET).to_num() as usize,
+            array_length: if array_length.is_undefined() { None } else { Some(array_length.to_num() as usize) },
+        })
+    }
+
+    /// Get the number of elements, or `None` if the buffer has shrunk so that the view is out of its bounds
+    /// https://tc39.es/ecma262/#sec-istypedarrayoutofbounds
+    pub fn length(&self) -> Option<usize> {
+        let buffer_length = self.data.len();
+        if self.byte_offset > buffer_length {
+            return None;
+        }
+        match self.array_length {
+            None => Some((buffer_length - self.byte_offset) / self.kind.size()),
+            Some(length) if self.byte_offset + length * self.kind.size() > buffer_length => None,
+            Some(length) => Some(length),
+        }
+    }
+
+    /// Get the number of elements, which is 0 if the view is out of the bounds of its buffer
+    pub fn length_or_zero(&self) -> usize {
+        self.length().unwrap_or(0)
+    }
+
+    /// Returns true if the number is the index of an element
+    /// https://tc39.es/ecma262/#sec-isvalidintegerindex
+    pub fn is_valid_index(&self, index: f64) -> bool {
+        index.trunc() == index
+            && !(index == 0.0 && index.is_sign_negative())
+            && index >= 0.0
+            && index < self.length_or_zero() as f64
+    }
+
+    /// Get the element at an index, or `None` if there isn't one
+    pub fn get(&self, index: usize) -> Option<Value> {
+        if index >= self.length_or_zero() {
+            return None;
+        }
+        let start = self.byte_offset + index * self.kind.size();
+        Some(self.kind.decode(&self.data.borrow()[start..], NATIVE_LITTLE_ENDIAN))
+    }
+
+    /// Get the element at an index, or undefined if there isn't one
+    pub fn get_or_undefined(&self, index: usize) -> Value {
+        self.get(index).unwrap_or_else(|| Gc::new(ValueData::Undefined))
+    }
+
+    /// Set the element at an index to a value which has already been converted with `ElementType::to_numeric`,
+    /// doing nothing if there isn't an element at the index
+    pub fn set(&self, index: usize, numeric: &Value) {
+        if index >= self.length_or_zero() {
+            return;
+        }
+        let start = self.byte_offset + index * self.kind.size();
+        let bytes = self.kind.encode(numeric, NATIVE_LITTLE_ENDIAN);
+        self.data.borrow_mut()[start..start + bytes.len()].copy_from_slice(&bytes);
+    }
+
+    /// Get all of the elements
+    pub fn values(&self) -> Vec<Value> {
+        (0..self.length_or_zero()).filter_map(|index| self.get(index)).collect()
+    }
+}
+
+/// Returns true if the value is a typed array
+pub fn is_typed_array(value: &ValueData) -> bool {
+    value.is_object() && value.has_private_field(TYPED_ARRAY_NAME)
+}
+
+/// Get the number a string property key stands for, if it is the canonical form of a number, like `"1"`, `"-0"`
+/// or `"1.5"`. Typed arrays treat all of these as elements, even if they aren't valid indices.
+/// https://tc39.es/ecma262/#sec-canonicalnumericindexstring
+pub fn canonical_numeric_index(key: &PropertyKey) -> Option<f64> {
+    let key = match *key {
+        PropertyKey::String(ref key) => key,
+        PropertyKey::Symbol(_) => return None,
+    };
+    match key.parse::<u32>() {
+        Ok(index) if index.to_string() == *key => return Some(f64::from(index)),
+        _ => (),
+    }
+    // Every other canonical numeric string starts with a digit, `-`, or the `I` of `Infinity` or `N` of `NaN`
+    if !key.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == 'I' || c == 'N') {
+        return None;
+    }
+    if key == "-0" {
+        return Some(-0.0);
+    }
+    let num = number::string_to_number(key);
+    if number::number_to_string(num) == *key {
+        Some(num)
+    } else {
+        None
+    }
+}
+
+/// Get the number a property key stands for if the object is a typed array and the key is numeric, which means
+/// it is an element rather than an ordinary property
+pub fn element_index(obj: &ValueData, key: &PropertyKey) -> Option<f64> {
+    let index = canonical_numeric_index(key)?;
+    if is_typed_array(obj) {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+/// Get an element of a typed array as a property, which is writable, enumerable and configurable, or `None` if
+/// there isn't an element at the index
+/// https://tc39.es/ecma262/#sec-typedarray-getownproperty
+pub fn element_property(obj: &ValueData, index: f64) -> Option<Property> {
+    let typed_array = TypedArray::from_value(obj)?;
+    if !typed_array.is_valid_index(index) {
+        return None;
+    }
+    typed_array.get(index as usize).map(Property::data)
+}
+
+/// Set an element of a typed array, converting the value without running any script, as `ValueData::set_field`
+/// can't. `Interpreter::set_field` converts it with `ElementType::to_numeric` instead.
+pub fn store_element(obj: &ValueData, index: f64, value: &Value) {
+    let typed_array = match TypedArray::from_value(obj) {
+        Some(typed_array) => typed_array,
+        None => return,
+    };
+    if typed_array.kind.is_bigint() != value.is_bigint() || !typed_array.is_valid_index(index) {
+        return;
+    }
+    typed_array.set(index as usize, value);
+}
+
+/// Set an element of a typed array, converting the value to the type of the elements first
+/// https://tc39.es/ecma262/#sec-typedarraysetelement
+pub fn set_element(obj: &Value, index: f64, value: &Value, interpreter: &mut Interpreter) -> Result<(), Value> {
+    let typed_array = match TypedArray::from_value(obj) {
+        Some(typed_array) => typed_array,
+        None => return Ok(()),
+    };
+    let numeric = typed_array.kind.to_numeric(value, interpreter)?;
+    // Converting the value may have shrunk the buffer
+    if typed_array.is_valid_index(index) {
+        typed_array.set(index as usize, &numeric);
+    }
+    Ok(())
+}
+
+/// Define an element of a typed array with a property descriptor, which can only change its value
+/// https://tc39.es/ecma262/#sec-typedarray-defineownproperty
+pub fn define_element(obj: &Value, index: f64, desc: &PropertyDescriptor) -> bool {
+    let typed_array = match TypedArray::from_value(obj) {
+        Some(typed_array) => typed_array,
+        None => return false,
+    };
+    if !typed_array.is_valid_index(index)
+        || desc.configurable == Some(false)
+        || desc.enumerable == Some(false)
+        || desc.is_accessor()
+        || desc.writable == Some(false)
+    {
+        return false;
+    }
+    if let Some(ref value) = desc.value {
+        store_element(obj, index, value);
+    }
+    true
+}
+
+/// Get the internal slots of the typed array `this`, or throw if it isn't one
+fn this_typed_array(this: &Value, interpreter: &Interpreter) -> Result<TypedArray, Value> {
+    TypedArray::from_value(this).ok_or_else(|| interpreter.type_error("this is not a typed array."))
+}
+
+/// Get the internal slots and length of the typed array `this`, or throw if it isn't one or is out of the bounds
+/// of its buffer
+/// https://tc39.es/ecma262/#sec-validatetypedarray
+fn validate_typed_array(this: &Value, method: &str, interpreter: &Interpreter) -> Result<(TypedArray, usize), Value> {
+    let typed_array = this_typed_array(this, interpreter)?;
+    match typed_array.length() {
+        Some(length) => Ok((typed_array, length)),
+        None => Err(interpreter.type_error(format!(
+            "Cannot perform %TypedArray%.prototype.{} on an out of bounds TypedArray",
+            method
+        ))),
+    }
+}
+
+/// The error thrown when BigInt elements and number elements are mixed
+fn content_type_error(interpreter: &Interpreter) -> Value {
+    interpreter.type_error("Cannot mix BigInt and other types, use explicit conversions")
+}
+
+/// Give a typed array its buffer, where its elements start in the buffer, and how many there are
+fn initialize(obj: &Value, kind: ElementType, buffer: Value, byte_offset: usize, length: Option<usize>) {
+    obj.set_private_field_slice(TYPED_ARRAY_NAME, to_value(kind.name()));
+    obj.set_private_field_slice(VIEWED_ARRAY_BUFFER, buffer);
+    obj.set_private_field_slice(BYTE_OFFSET, to_value(byte_offset as f64));
+    if let Some(length) = length {
+        obj.set_private_field_slice(ARRAY_LENGTH, to_value(length as f64));
+    }
+}
+
+/// Give a typed array a new buffer big enough for `length` elements
+/// https://tc39.es/ecma262/#sec-allocatetypedarraybuffer
+fn allocate(obj: &Value, kind: ElementType, length: usize, interpreter: &Interpreter) -> Result<TypedArray, Value> {
+    let byte_length = length
+        .checked_mul(kind.size())
+        .ok_or_else(|| interpreter.range_error(format!("Invalid typed array length: {}", length)))?;
+    let buffer = array_buffer::allocate_array_buffer(byte_length, interpreter)?;
+    initialize(obj, kind, buffer, 0, Some(length));
+    Ok(TypedArray::from_value(obj).expect("the typed array was just initialized"))
+}
+
+/// Make a new typed array with the given element type and length, returning it with its internal slots
+/// https://tc39.es/ecma262/#sec-typedarraycreatesametype
+pub fn create_typed_array(kind: ElementType, length: usize, interpreter: &mut Interpreter) -> Result<(Value, TypedArray), Value> {
+    let constructor = interpreter.get_global_object().get_field_slice(kind.name());
+    let obj = interpreter.construct(&constructor, vec![to_value(length as f64)])?;
+    let typed_array = TypedArray::from_value(&obj).expect("typed array constructors make typed arrays");
+    Ok((obj, typed_array))
+}
+
+/// Make a new typed array with a constructor from a script, checking that it makes a typed array which is long
+/// enough
+/// https://tc39.es/ecma262/#sec-typedarraycreatefromconstructor
+fn create_from_constructor(constructor: &Value, length: usize, interpreter: &mut Interpreter) -> ResultValue {
+    if !constructor.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a constructor", constructor)));
+    }
+    let obj = interpreter.construct(constructor, vec![to_value(length as f64)])?;
+    let (_, new_length) = validate_typed_array(&obj, "constructor", interpreter)?;
+    if new_length < length {
+        return Err(interpreter.type_error("The constructor made a typed array which is too short"));
+    }
+    Ok(obj)
+}
+
+/// Get the values of an object given to a typed array constructor or `from`, with its iterator if it has one, or
+/// as an array-like object otherwise
+fn source_values(source: &Value, interpreter: &mut Interpreter) -> Result<Vec<Value>, Value> {
+    let using_iterator = interpreter.get_field(source, Symbol::well_known(WellKnownSymbol::Iterator))?;
+    if !using_iterator.is_null_or_undefined() {
+        if !using_iterator.is_function() {
+            return Err(interpreter.type_error(format!("{} is not a function", using_iterator)));
+        }
+        return iterator::iterable_to_list(source, interpreter);
+    }
+    let length = array::length_of(source, interpreter)? as usize;
+    let mut values = Vec::with_capacity(length);
+    for index in 0..length {
+        values.push(interpreter.get_field(source, index.to_string())?);
+    }
+    Ok(values)
+}
+
+/// `%TypedArray%`, which is only there to be inherited from, so it always throws
+/// https://tc39.es/ecma262/#sec-%typedarray%
+pub fn make_abstract_typed_array(_: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Err(interpreter.type_error("Abstract class TypedArray not directly constructable"))
+}
+
+/// Create a new typed array, of the kind the constructor carries, from a length, another typed array, an
+/// iterable or array-like object, or a view of an `ArrayBuffer` from `byteOffset` for `length` elements
+/// https://tc39.es/ecma262/#sec-typedarray
+pub fn make_typed_array(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let kind = ElementType::from_name(&f.get_private_field_slice(TYPED_ARRAY_NAME).to_string())
+        .expect("typed array constructors carry their element type");
+    if !this.is_object() || Gc::ptr_eq(&this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error(format!("Constructor {} requires 'new'", kind.name())));
+    }
+    let first = arg(&args, 0);
+    if !first.is_object() {
+        let length = to_index(&first, &format!("Invalid typed array length: {}", first), interpreter)?;
+        allocate(&this, kind, length, interpreter)?;
+    } else if let Some(source) = TypedArray::from_value(&first) {
+        // https://tc39.es/ecma262/#sec-initializetypedarrayfromtypedarray
+        let length = match source.length() {
+            Some(length) => length,
+            None => return Err(interpreter.type_error("Cannot construct a typed array from an out of bounds TypedArray")),
+        };
+        if source.kind.is_bigint() != kind.is_bigint() {
+            return Err(content_type_error(interpreter));
+        }
+        let target = allocate(&this, kind, length, interpreter)?;
+        for (index, value) in source.values().iter().enumerate() {
+            target.set(index, value);
+        }
+    } else if array_buffer::is_array_buffer(&first) {
+        // https://tc39.es/ecma262/#sec-initializetypedarrayfromarraybuffer
+        let size = kind.size();
+        let offset_arg = arg(&args, 1);
+        let offset = to_index(&offset_arg, &format!("Start offset {} is outside the bounds of the buffer", offset_arg), interpreter)?;
+        if !offset.is_multiple_of(size) {
+            return Err(interpreter.range_error(format!("start offset of {} should be a multiple of {}", kind.name(), size)));
+        }
+        let length_arg = arg(&args, 2);
+        let new_length = if length_arg.is_undefined() {
+            None
+        } else {
+            Some(to_index(&length_arg, &format!("Invalid typed array length: {}", length_arg), interpreter)?)
+        };
+        let buffer_length = array_buffer::array_buffer_data(&first).map_or(0, |data| data.len());
+        let length = match new_length {
+            // Views of resizable buffers without a length track the length of the buffer
+            None if array_buffer::max_byte_length(&first).is_some() => {
+                if offset > buffer_length {
+                    return Err(interpreter.range_error(format!("Start offset {} is outside the bounds of the buffer", offset)));
+                }
+                None
+            }
+            None => {
+                if !buffer_length.is_multiple_of(size) {
+                    return Err(interpreter.range_error(format!("byte length of {} should be a multiple of {}", kind.name(), size)));
+                }
+                if offset > buffer_length {
+                    return Err(interpreter.range_error(format!("Start offset {} is outside the bounds of the buffer", offset)));
+                }
+                Some((buffer_length - offset) / size)
+            }
+            Some(length) => {
+                if offset as f64 + length as f64 * size as f64 > buffer_length as f64 {
+                    return Err(interpreter.range_error(format!("Invalid typed array length: {}", length)));
+                }
+                Some(length)
+            }
+        };
+        initialize(&this, kind, first, offset, length);
+    } else {
+        // https://tc39.es/ecma262/#sec-initializetypedarrayfromlist
+        let values = source_values(&first, interpreter)?;
+        allocate(&this, kind, values.len(), interpreter)?;
+        for (index, value) in values.iter().enumerate() {
+            set_element(&this, index as f64, value, interpreter)?;
+        }
+    }
+    Ok(this)
+}
+
+/// `%TypedArray%.from(source, mapFn, thisArg)`, which makes a typed array with the constructor `this` from the
+/// values of an iterable or array-like object
+/// https://tc39.es/ecma262/#sec-%typedarray%.from
+pub fn from(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let map_fn = arg(&args, 1);
+    if !map_fn.is_undefined() && !map_fn.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", map_fn)));
+    }
+    let source = arg(&args, 0);
+    if source.is_null_or_undefined() {
+        return Err(interpreter.type_error(format!("Cannot convert {} to object", source)));
+    }
+    let source = interpreter.to_object(&source)?;
+    let values = source_values(&source, interpreter)?;
+    let target = create_from_constructor(&this, values.len(), interpreter)?;
+    let this_arg = arg(&args, 2);
+    for (index, value) in values.into_iter().enumerate() {
+        let value = if map_fn.is_function() {
+            interpreter.call(&map_fn, &this_arg, vec![value, to_value(index)])?
+        } else {
+            value
+        };
+        interpreter.set_field(&target, index.to_string(), value)?;
+    }
+    Ok(target)
+}
+
+/// `%TypedArray%.of(...items)`, which makes a typed array with the constructor `this` from the arguments
+/// https://tc39.es/ecma262/#sec-%typedarray%.of
+pub fn of(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = create_from_constructor(&this, args.len(), interpreter)?;
+    for (index, value) in args.into_iter().enumerate() {
+        interpreter.set_field(&target, index.to_string(), value)?;
+    }
+    Ok(target)
+}
+
+/// `get %TypedArray%.prototype.buffer`
+/// https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.buffer
+pub fn get_buffer(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(this_typed_array(&this, interpreter)?.buffer)
+}
+
+/// `get %TypedArray%.prototype.byteLength`, which is 0 if the array is out of the bounds of its buffer
+/// https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.bytelength
+pub fn get_byte_length(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let typed_array = this_typed_array(&this, interpreter)?;
+    Ok(to_value((typed_array.length_or_zero() * typed_array.kind.size()) as f64))
+}
+
+/// `get %TypedArray%.prototype.byteOffset`, which is 0 if the array is out of the bounds of its buffer
+/// https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.byteoffset
+pub fn get_byte_offset(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let typed_array = this_typed_array(&this, interpreter)?;
+    let offset = if typed_array.length().is_some() { typed_array.byte_offset } else { 0 };
+    Ok(to_value(offset as f64))
+}
+
+/// `get %TypedArray%.prototype.length`, which is 0 if the array is out of the bounds of its buffer
+/// https://tc39.es/ecma262/#sec-get-%typedarray%.prototype.length
+pub fn get_length(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(to_value(this_typed_array(&this, interpreter)?.length_or_zero() as f64))
+}
+
+/// `get %TypedArray%.prototype[Symbol.toStringTag]`, which is the name of the constructor, or undefined for
+/// values which aren't typed arrays
+/// https://tc39.es/ecma262/#sec-get-%typedarray%.prototype-@@tostringtag
+pub fn get_to_string_tag(this: Value, _: Value, _: Vec<Value>, _: &mut Interpreter) -> ResultValue {
+    Ok(match TypedArray::from_value(&this) {
+        Some(typed_array) => to_value(typed_array.kind.name()),
+        None => Gc::new(ValueData::Undefined),
+    })
+}
+
+/// `%TypedArray%.prototype.at(index)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.at
+pub fn at(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "at", interpreter)?;
+    array::at(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.copyWithin(target, start, end)`, which copies the elements from `start` up to `end`
+/// over the elements from `target`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.copywithin
+pub fn copy_within(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "copyWithin", interpreter)?;
+    let to = relative_index(&arg(&args, 0), length, 0, interpreter)?;
+    let from = relative_index(&arg(&args, 1), length, 0, interpreter)?;
+    let last = relative_index(&arg(&args, 2), length, length, interpreter)?;
+    // Converting the arguments may have shrunk the buffer
+    let length = typed_array.length_or_zero();
+    let count = last.saturating_sub(from).min(length.saturating_sub(from)).min(length.saturating_sub(to));
+    if count > 0 {
+        let size = typed_array.kind.size();
+        let start = typed_array.byte_offset + from * size;
+        let target = typed_array.byte_offset + to * size;
+        typed_array.data.borrow_mut().copy_within(start..start + count * size, target);
+    }
+    Ok(this)
+}
+
+/// `%TypedArray%.prototype.every(callback, thisArg)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.every
+pub fn every(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "every", interpreter)?;
+    array::every(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.fill(value, start, end)`, which converts the value once and stores it in each element
+/// from `start` up to `end`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.fill
+pub fn fill(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "fill", interpreter)?;
+    let value = typed_array.kind.to_numeric(&arg(&args, 0), interpreter)?;
+    let first = relative_index(&arg(&args, 1), length, 0, interpreter)?;
+    let last = relative_index(&arg(&args, 2), length, length, interpreter)?;
+    for index in first..last.min(typed_array.length_or_zero()) {
+        typed_array.set(index, &value);
+    }
+    Ok(this)
+}
+
+/// `%TypedArray%.prototype.filter(callback, thisArg)`, which makes a typed array of the same type from the
+/// elements the callback returns a truthy value for
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.filter
+pub fn filter(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "filter", interpreter)?;
+    let callback = array::callback_arg(&args, interpreter)?;
+    let this_arg = arg(&args, 1);
+    let mut kept = Vec::new();
+    for index in 0..length {
+        let value = typed_array.get_or_undefined(index);
+        let args = vec![value.clone(), to_value(index), this.clone()];
+        if interpreter.call(&callback, &this_arg, args)?.is_true() {
+            kept.push(value);
+        }
+    }
+    let (result_obj, result) = create_typed_array(typed_array.kind, kept.len(), interpreter)?;
+    for (index, value) in kept.iter().enumerate() {
+        result.set(index, value);
+    }
+    Ok(result_obj)
+}
+
+/// `%TypedArray%.prototype.find(predicate, thisArg)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.find
+pub fn find(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "find", interpreter)?;
+    array::find(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.findIndex(predicate, thisArg)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.findindex
+pub fn find_index(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "findIndex", interpreter)?;
+    array::find_index(this, f, args, interpreter)
+}
+
+/// Find the last element and its index where the predicate returns a truthy value
+fn find_last_entry(this: &Value, method: &str, args: &[Value], interpreter: &mut Interpreter) -> Result<Option<(usize, Value)>, Value> {
+    let (typed_array, length) = validate_typed_array(this, method, interpreter)?;
+    let predicate = array::callback_arg(args, interpreter)?;
+    let this_arg = arg(args, 1);
+    for index in (0..length).rev() {
+        let value = typed_array.get_or_undefined(index);
+        let args = vec![value.clone(), to_value(index), this.clone()];
+        if interpreter.call(&predicate, &this_arg, args)?.is_true() {
+            return Ok(Some((index, value)));
+        }
+    }
+    Ok(None)
+}
+
+/// `%TypedArray%.prototype.findLast(predicate, thisArg)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.findlast
+pub fn find_last(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(match find_last_entry(&this, "findLast", &args, interpreter)? {
+        Some((_, value)) => value,
+        None => Gc::new(ValueData::Undefined),
+    })
+}
+
+/// `%TypedArray%.prototype.findLastIndex(predicate, thisArg)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.findlastindex
+pub fn find_last_index(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    Ok(match find_last_entry(&this, "findLastIndex", &args, interpreter)? {
+        Some((index, _)) => to_value(index),
+        None => to_value(-1),
+    })
+}
+
+/// `%TypedArray%.prototype.forEach(callback, thisArg)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.foreach
+pub fn for_each(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "forEach", interpreter)?;
+    array::for_each(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.includes(searchElement, fromIndex)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.includes
+pub fn includes(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "includes", interpreter)?;
+    array::includes(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.indexOf(searchElement, fromIndex)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.indexof
+pub fn index_of(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "indexOf", interpreter)?;
+    array::index_of(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.join(separator)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.join
+pub fn join(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "join", interpreter)?;
+    array::join(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.lastIndexOf(searchElement, fromIndex)`, which searches backwards from `fromIndex`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.lastindexof
+pub fn last_index_of(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "lastIndexOf", interpreter)?;
+    if length == 0 {
+        return Ok(to_value(-1));
+    }
+    let from = if args.len() > 1 {
+        to_integer_or_infinity(&args[1], interpreter)?
+    } else {
+        length as f64 - 1.0
+    };
+    let from = if from < 0.0 { length as f64 + from } else { from.min(length as f64 - 1.0) };
+    if from < 0.0 {
+        return Ok(to_value(-1));
+    }
+    let search = arg(&args, 0);
+    for index in (0..=from as usize).rev() {
+        if let Some(value) = typed_array.get(index) {
+            if strict_equals(&value, &search) {
+                return Ok(to_value(index));
+            }
+        }
+    }
+    Ok(to_value(-1))
+}
+
+/// `%TypedArray%.prototype.map(callback, thisArg)`, which makes a typed array of the same type from what the
+/// callback returns for each element
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.map
+pub fn map(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "map", interpreter)?;
+    let callback = array::callback_arg(&args, interpreter)?;
+    let this_arg = arg(&args, 1);
+    let (result_obj, _) = create_typed_array(typed_array.kind, length, interpreter)?;
+    for index in 0..length {
+        let args = vec![typed_array.get_or_undefined(index), to_value(index), this.clone()];
+        let value = interpreter.call(&callback, &this_arg, args)?;
+        set_element(&result_obj, index as f64, &value, interpreter)?;
+    }
+    Ok(result_obj)
+}
+
+/// `%TypedArray%.prototype.reduce(callback, initialValue)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.reduce
+pub fn reduce(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "reduce", interpreter)?;
+    array::reduce(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.reduceRight(callback, initialValue)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.reduceright
+pub fn reduce_right(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "reduceRight", interpreter)?;
+    array::reduce_right(this, f, args, interpreter)
+}
+
+/// `%TypedArray%.prototype.reverse()`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.reverse
+pub fn reverse(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, _) = validate_typed_array(&this, "reverse", interpreter)?;
+    for (index, value) in typed_array.values().iter().rev().enumerate() {
+        typed_array.set(index, value);
+    }
+    Ok(this)
+}
+
+/// `%TypedArray%.prototype.set(source, offset)`, which copies the elements of a typed array, or the values of an
+/// array-like object, into this array from `offset`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.set
+pub fn set(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let target = this_typed_array(&this, interpreter)?;
+    let offset = to_integer_or_infinity(&arg(&args, 1), interpreter)?;
+    if offset < 0.0 {
+        return Err(interpreter.range_error("offset is out of bounds"));
+    }
+    let target_length = match target.length() {
+        Some(length) => length,
+        None => return Err(interpreter.type_error("Cannot perform %TypedArray%.prototype.set on an out of bounds TypedArray")),
+    };
+    let source = arg(&args, 0);
+    if let Some(source) = TypedArray::from_value(&source) {
+        // https://tc39.es/ecma262/#sec-settypedarrayfromtypedarray
+        let values = match source.length() {
+            Some(_) => source.values(),
+            None => return Err(interpreter.type_error("Cannot perform %TypedArray%.prototype.set on an out of bounds TypedArray")),
+        };
+        if source.kind.is_bigint() != target.kind.is_bigint() {
+            return Err(content_type_error(interpreter));
+        }
+        if values.len() as f64 + offset > target_length as f64 {
+            return Err(interpreter.range_error("offset is out of bounds"));
+        }
+        // The values were all read first, so it doesn't matter if the arrays share their buffer
+        for (index, value) in values.iter().enumerate() {
+            target.set(offset as usize + index, value);
+        }
+        return Ok(Gc::new(ValueData::Undefined));
+    }
+    // https://tc39.es/ecma262/#sec-settypedarrayfromarraylike
+    let source = interpreter.to_object(&source)?;
+    let source_length = array::length_of(&source, interpreter)? as usize;
+    if source_length as f64 + offset > target_length as f64 {
+        return Err(interpreter.range_error("offset is out of bounds"));
+    }
+    for index in 0..source_length {
+        let value = interpreter.get_field(&source, index.to_string())?;
+        set_element(&this, offset + index as f64, &value, interpreter)?;
+    }
+    Ok(Gc::new(ValueData::Undefined))
+}
+
+/// `%TypedArray%.prototype.slice(start, end)`, which copies the elements from `start` up to `end` into a new
+/// typed array of the same type
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.slice
+pub fn slice(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "slice", interpreter)?;
+    let first = relative_index(&arg(&args, 0), length, 0, interpreter)?;
+    let last = relative_index(&arg(&args, 1), length, length, interpreter)?;
+    let count = last.saturating_sub(first);
+    let (result_obj, result) = create_typed_array(typed_array.kind, count, interpreter)?;
+    // Converting the arguments may have shrunk the buffer
+    let last = last.min(typed_array.length_or_zero());
+    for index in first..last {
+        if let Some(value) = typed_array.get(index) {
+            result.set(index - first, &value);
+        }
+    }
+    Ok(result_obj)
+}
+
+/// `%TypedArray%.prototype.some(callback, thisArg)`
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.some
+pub fn some(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    validate_typed_array(&this, "some", interpreter)?;
+    array::some(this, f, args, interpreter)
+}
+
+/// Compare two elements for sorting, with a comparison function or by their numeric order, where `-0` is before
+/// `+0` and `NaN` is last
+/// https://tc39.es/ecma262/#sec-typedarray-objects
+fn compare_elements(a: &Value, b: &Value, compare_fn: &Value, interpreter: &mut Interpreter) -> Result<Ordering, Value> {
+    if compare_fn.is_function() {
+        let undefined = Gc::new(ValueData::Undefined);
+        let result = interpreter.call(compare_fn, &undefined, vec![a.clone(), b.clone()])?;
+        let result = interpreter.to_number(&result)?;
+        return Ok(result.partial_cmp(&0.0).unwrap_or(Ordering::Equal));
+    }
+    if let (ValueData::BigInt(ref x), ValueData::BigInt(ref y)) = (&**a, &**b) {
+        return Ok(x.cmp(y));
+    }
+    let (x, y) = (a.to_num(), b.to_num());
+    Ok(match (x.is_nan(), y.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) if x == 0.0 && y == 0.0 => y.is_sign_negative().cmp(&x.is_sign_negative()),
+        (false, false) => x.partial_cmp(&y).unwrap(),
+    })
+}
+
+/// Sort elements with a stable sort, stopping at the first error thrown by the comparison function
+fn sort_elements(mut values: Vec<Value>, compare_fn: &Value, interpreter: &mut Interpreter) -> Result<Vec<Value>, Value> {
+    let mut error = None;
+    values.sort_by(|a, b| {
+        if error.is_some() {
+            return Ordering::Equal;
+        }
+        compare_elements(a, b, compare_fn, interpreter).unwrap_or_else(|err| {
+            error = Some(err);
+            Ordering::Equal
+        })
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(values),
+    }
+}
+
+/// Get the comparison function argument of `sort` and `toSorted`, which must be a function or undefined
+fn compare_fn_arg(args: &[Value], interpreter: &Interpreter) -> ResultValue {
+    let compare_fn = arg(args, 0);
+    if !compare_fn.is_undefined() && !compare_fn.is_function() {
+        return Err(interpreter.type_error("The comparison function must be either a function or undefined"));
+    }
+    Ok(compare_fn)
+}
+
+/// `%TypedArray%.prototype.sort(compareFn)`, which sorts the elements in place
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.sort
+pub fn sort(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let compare_fn = compare_fn_arg(&args, interpreter)?;
+    let (typed_array, _) = validate_typed_array(&this, "sort", interpreter)?;
+    let sorted = sort_elements(typed_array.values(), &compare_fn, interpreter)?;
+    for (index, value) in sorted.iter().enumerate() {
+        typed_array.set(index, value);
+    }
+    Ok(this)
+}
+
+/// `%TypedArray%.prototype.subarray(start, end)`, which makes a typed array of the same type looking at the
+/// elements from `start` up to `end` in the same buffer
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.subarray
+pub fn subarray(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let typed_array = this_typed_array(&this, interpreter)?;
+    let length = typed_array.length_or_zero();
+    let first = relative_index(&arg(&args, 0), length, 0, interpreter)?;
+    let end = arg(&args, 1);
+    let byte_offset = to_value((typed_array.byte_offset + first * typed_array.kind.size()) as f64);
+    let mut view_args = vec![typed_array.buffer.clone(), byte_offset];
+    // A subarray of an array which tracks the length of its buffer tracks it too, unless it is given an end
+    if typed_array.array_length.is_some() || !end.is_undefined() {
+        let last = relative_index(&end, length, length, interpreter)?;
+        view_args.push(to_value(last.saturating_sub(first) as f64));
+    }
+    let constructor = interpreter.get_global_object().get_field_slice(typed_array.kind.name());
+    interpreter.construct(&constructor, view_args)
+}
+
+/// `%TypedArray%.prototype.toReversed()`, which copies the elements into a new typed array in reverse order
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.toreversed
+pub fn to_reversed(this: Value, _: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "toReversed", interpreter)?;
+    let (result_obj, result) = create_typed_array(typed_array.kind, length, interpreter)?;
+    for (index, value) in typed_array.values().iter().rev().enumerate() {
+        result.set(index, value);
+    }
+    Ok(result_obj)
+}
+
+/// `%TypedArray%.prototype.toSorted(compareFn)`, which copies the elements into a new typed array in sorted order
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.tosorted
+pub fn to_sorted(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let compare_fn = compare_fn_arg(&args, interpreter)?;
+    let (typed_array, length) = validate_typed_array(&this, "toSorted", interpreter)?;
+    let (result_obj, result) = create_typed_array(typed_array.kind, length, interpreter)?;
+    let sorted = sort_elements(typed_array.values(), &compare_fn, interpreter)?;
+    for (index, value) in sorted.iter().enumerate() {
+        result.set(index, value);
+    }
+    Ok(result_obj)
+}
+
+/// `%TypedArray%.prototype.with(index, value)`, which copies the elements into a new typed array with the
+/// element at `index` replaced
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.with
+pub fn with(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let (typed_array, length) = validate_typed_array(&this, "with", interpreter)?;
+    let relative = to_integer_or_infinity(&arg(&args, 0), interpreter)?;
+    let index = if relative >= 0.0 { relative } else { length as f64 + relative };
+    let value = typed_array.kind.to_numeric(&arg(&args, 1), interpreter)?;
+    if !typed_array.is_valid_index(index) {
+        return Err(interpreter.range_error("Invalid typed array index"));
+    }
+    let (result_obj, result) = create_typed_array(typed_array.kind, length, interpreter)?;
+    for (position, element) in typed_array.values().iter().enumerate() {
+        result.set(position, if position == index as usize { &value } else { element });
+    }
+    Ok(result_obj)
+}
+
+/// `%TypedArray%.prototype.values()`, `keys()` and `entries()`, which make an array iterator of the kind the
+/// method carries
+/// https://tc39.es/ecma262/#sec-%typedarray%.prototype.values
+pub fn create_iterator(this: Value, f: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let kind = ArrayIterationKind::from_name(&f.get_private_field_slice(iterator::ITERATION_KIND).to_string());
+    validate_typed_array(&this, kind.name(), interpreter)?;
+    iterator::create_array_iterator(this, f, args, interpreter)
+}
+
+/// Make an iterator method, which carries the kind of iterator it creates
+fn iterator_method(kind: ArrayIterationKind, iterator_prototype: &Value) -> Value {
+    let method = to_value(create_iterator as NativeFunctionData);
+    method.set_private_field_slice(iterator::ARRAY_ITERATOR_PROTOTYPE, iterator_prototype.clone());
+    method.set_private_field_slice(iterator::ITERATION_KIND, to_value(kind.name()));
+    method
+}
+
+/// Create the `%TypedArray%` object, which the typed array constructors inherit from
+pub fn create_abstract_typed_array(global: &Value) -> Value {
+    let typed_array = to_value(make_abstract_typed_array as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_prop_slice("buffer", getter_property(get_buffer));
+    prototype.set_prop_slice("byteLength", getter_property(get_byte_length));
+    prototype.set_prop_slice("byteOffset", getter_property(get_byte_offset));
+    prototype.set_prop_slice("length", getter_property(get_length));
+    prototype.set_prop(
+        Symbol::well_known(WellKnownSymbol::ToStringTag),
+        getter_property(get_to_string_tag),
+    );
+    prototype.set_field_slice("at", to_value(at as NativeFunctionData));
+    prototype.set_field_slice("copyWithin", to_value(copy_within as NativeFunctionData));
+    prototype.set_field_slice("every", to_value(every as NativeFunctionData));
+    prototype.set_field_slice("fill", to_value(fill as NativeFunctionData));
+    prototype.set_field_slice("filter", to_value(filter as NativeFunctionData));
+    prototype.set_field_slice("find", to_value(find as NativeFunctionData));
+    prototype.set_field_slice("findIndex", to_value(find_index as NativeFunctionData));
+    prototype.set_field_slice("findLast", to_value(find_last as NativeFunctionData));
+    prototype.set_field_slice("findLastIndex", to_value(find_last_index as NativeFunctionData));
+    prototype.set_field_slice("forEach", to_value(for_each as NativeFunctionData));
+    prototype.set_field_slice("includes", to_value(includes as NativeFunctionData));
+    prototype.set_field_slice("indexOf", to_value(index_of as NativeFunctionData));
+    prototype.set_field_slice("join", to_value(join as NativeFunctionData));
+    prototype.set_field_slice("lastIndexOf", to_value(last_index_of as NativeFunctionData));
+    prototype.set_field_slice("map", to_value(map as NativeFunctionData));
+    prototype.set_field_slice("reduce", to_value(reduce as NativeFunctionData));
+    prototype.set_field_slice("reduceRight", to_value(reduce_right as NativeFunctionData));
+    prototype.set_field_slice("reverse", to_value(reverse as NativeFunctionData));
+    prototype.set_field_slice("set", to_value(set as NativeFunctionData));
+    prototype.set_field_slice("slice", to_value(slice as NativeFunctionData));
+    prototype.set_field_slice("some", to_value(some as NativeFunctionData));
+    prototype.set_field_slice("sort", to_value(sort as NativeFunctionData));
+    prototype.set_field_slice("subarray", to_value(subarray as NativeFunctionData));
+    prototype.set_field_slice("toReversed", to_value(to_reversed as NativeFunctionData));
+    prototype.set_field_slice("toSorted", to_value(to_sorted as NativeFunctionData));
+    prototype.set_field_slice("with", to_value(with as NativeFunctionData));
+    // `toString` is the same function as `Array.prototype.toString`
+    let array_prototype = global.get_field_slice("Array").get_field_slice(PROTOTYPE);
+    prototype.set_field_slice("toString", array_prototype.get_field_slice("toString"));
+    // The iterators are array iterators, which share their prototype with the iterators of arrays
+    let iterator_prototype = array_prototype.get_field_slice("values").get_private_field_slice(iterator::ARRAY_ITERATOR_PROTOTYPE);
+    let values = iterator_method(ArrayIterationKind::Values, &iterator_prototype);
+    prototype.set_field_slice("keys", iterator_method(ArrayIterationKind::Keys, &iterator_prototype));
+    prototype.set_field_slice("entries", iterator_method(ArrayIterationKind::Entries, &iterator_prototype));
+    prototype.set_field_slice("values", values.clone());
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::Iterator), values);
+    typed_array.set_field_slice("from", to_value(from as NativeFunctionData));
+    typed_array.set_field_slice("of", to_value(of as NativeFunctionData));
+    typed_array.set_field_slice("length", to_value(0i32));
+    set_function_name(&typed_array, "TypedArray");
+    prototype.set_field_slice("constructor", typed_array.clone());
+    typed_array.set_field_slice(PROTOTYPE, prototype);
+    typed_array
+}
+
+/// Create the constructor of typed arrays with the given element type, which inherits from `%TypedArray%`
+pub fn create_typed_array_constructor(kind: ElementType, abstract_typed_array: &Value) -> Value {
+    let constructor = to_value(make_typed_array as NativeFunctionData);
+    constructor.set_private_field_slice(TYPED_ARRAY_NAME, to_value(kind.name()));
+    let prototype = ValueData::new_obj_from_prototype(abstract_typed_array.get_field_slice(PROTOTYPE));
+    let bytes_per_element = || {
+        let mut prop = Property::new(to_value(kind.size()));
+        prop.enumerable = false;
+        prop
+    };
+    prototype.set_prop_slice("BYTES_PER_ELEMENT", bytes_per_element());
+    constructor.set_prop_slice("BYTES_PER_ELEMENT", bytes_per_element());
+    constructor.set_field_slice("length", to_value(3i32));
+    constructor.set_field_slice(INSTANCE_PROTOTYPE, abstract_typed_array.clone());
+    prototype.set_field_slice("constructor", constructor.clone());
+    constructor.set_field_slice(PROTOTYPE, prototype);
+    constructor
+}
+
+/// Initialise the global object with the typed array constructors
+pub fn init(global: &Value) {
+    let abstract_typed_array = create_abstract_typed_array(global);
+    for kind in ELEMENT_TYPES.iter() {
+        global.set_field_slice(kind.name(), create_typed_array_constructor(*kind, &abstract_typed_array));
+    }
+}
@@ -2,17 +2,22 @@ use gc::{Gc, GcCell};
 use serde_json::map::Map;
 use serde_json::Number as JSONNumber;
 use serde_json::Value as JSONValue;
-use std::collections::HashMap;
-use std::f64::NAN;
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Display;
 use std::iter::FromIterator;
-use std::ops::Deref;
 use std::ops::DerefMut;
 use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Rem, Shl, Shr, Sub};
-use std::str::FromStr;
+use crate::js::array;
+use crate::js::array_buffer::ByteBuffer;
+use crate::js::bigint::BigInt;
+use crate::js::error;
 use crate::js::function::{Function, NativeFunction, NativeFunctionData};
-use crate::js::object::{ObjectData, Property, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::object::{ObjectData, Property, PropertyKey, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::number;
+use crate::js::string::{self, JsString};
+use crate::js::symbol::Symbol;
+use crate::js::typed_array;
 
 #[must_use]
 /// The result of a Javascript expression is represented like this so it can succeed (`Ok`) or fail (`Err`)
@@ -29,30 +34,37 @@ pub enum ValueData {
     Undefined,
     /// `boolean` - A `true` / `false` value, for if a certain criteria is met
     Boolean(bool),
-    /// `String` - A UTF-8 string, such as `"Hello, world"`
-    String(String),
+    /// `String` - A string of UTF-16 code units, such as `"Hello, world"`
+    String(JsString),
     /// `Number` - A 64-bit floating point number, such as `3.1415`
     Number(f64),
     /// `Number` - A 32-bit integer, such as `42`
     Integer(i32),
+    /// `BigInt` - An arbitrary-precision integer, such as `9007199254740993n`
+    BigInt(BigInt),
+    /// `Symbol` - A unique value which can be used as a property key
+    Symbol(Symbol),
     /// `Object` - An object, such as `Math`, represented by a binary tree of string keys to Javascript values
     /// The second object storage is optional for now
     Object(GcCell<ObjectData>, GcCell<ObjectData>),
     /// `Function` - A runnable block of code, such as `Math.sqrt`, which can take some variables and return a useful value or act upon an object
     Function(GcCell<Function>),
+    /// `Buffer` - The bytes behind an `ArrayBuffer`, which are kept in an internal slot of the buffer object and
+    /// are never seen by scripts directly
+    Buffer(ByteBuffer),
 }
 
 impl ValueData {
     /// Returns a new empty object
     pub fn new_obj(global: Option<&Value>) -> Value {
-        let mut obj: ObjectData = HashMap::new();
-        let private_obj: ObjectData = HashMap::new();
+        let mut obj: ObjectData = ObjectData::new();
+        let private_obj: ObjectData = ObjectData::new();
         if global.is_some() {
             let obj_proto = global
                 .unwrap()
                 .get_field_slice("Object")
                 .get_field_slice(PROTOTYPE);
-            obj.insert(INSTANCE_PROTOTYPE.to_string(), Property::new(obj_proto));
+            obj.insert(INSTANCE_PROTOTYPE.into(), Property::new(obj_proto));
         }
         Gc::new(ValueData::Object(
             GcCell::new(obj),
@@ -60,20 +72,121 @@ impl ValueData {
         ))
     }
 
+    /// Run a function on the own properties of an object or function, or get `None` for other values
+    fn with_props<R, F: FnOnce(&ObjectData) -> R>(&self, f: F) -> Option<R> {
+        match *self {
+            ValueData::Object(ref obj, _) => Some(f(&obj.borrow())),
+            ValueData::Function(ref func) => Some(f(func.borrow().object())),
+            _ => None,
+        }
+    }
+
+    /// Run a function which changes the own properties of an object or function, or get `None` for other values
+    fn with_props_mut<R, F: FnOnce(&mut ObjectData) -> R>(&self, f: F) -> Option<R> {
+        match *self {
+            ValueData::Object(ref obj, _) => Some(f(&mut obj.borrow_mut())),
+            ValueData::Function(ref func) => Some(f(func.borrow_mut().object_mut())),
+            _ => None,
+        }
+    }
+
+    /// Run a function on the internal slots of an object or function, or get `None` for other values
+    fn with_private_props<R, F: FnOnce(&ObjectData) -> R>(&self, f: F) -> Option<R> {
+        match *self {
+            ValueData::Object(_, ref obj) => Some(f(&obj.borrow())),
+            ValueData::Function(ref func) => Some(f(func.borrow().private())),
+            _ => None,
+        }
+    }
+
+    /// Run a function which changes the internal slots of an object or function, or get `None` for other values
+    fn with_private_props_mut<R, F: FnOnce(&mut ObjectData) -> R>(&self, f: F) -> Option<R> {
+        match *self {
+            ValueData::Object(_, ref obj) => Some(f(&mut obj.borrow_mut())),
+            ValueData::Function(ref func) => Some(f(func.borrow_mut().private_mut())),
+            _ => None,
+        }
+    }
+
+    /// Returns true if properties can be added to the object, which is the [[Extensible]] internal slot
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-isextensible
     pub fn is_extensible(&self) -> bool {
-        true
+        self.with_props(|obj| obj.extensible).unwrap_or(false)
+    }
+
+    /// Stop properties from being added to the object
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-preventextensions
+    pub fn prevent_extensions(&self) {
+        self.with_props_mut(|obj| obj.extensible = false);
+    }
+
+    /// Get an own property of the object, without looking at its prototype chain
+    /// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-getownproperty-p
+    pub fn get_own_prop<K: Into<PropertyKey>>(&self, field: K) -> Option<Property> {
+        let field = field.into();
+        if field == INSTANCE_PROTOTYPE {
+            return None;
+        }
+        // The elements of typed arrays live in their buffer, numeric keys never reach their ordinary properties
+        if let Some(index) = typed_array::element_index(self, &field) {
+            return typed_array::element_property(self, index);
+        }
+        // String objects have the length and code units of their string as properties
+        if let Some(string) = self.string_data() {
+            if let Some(prop) = to_value(string).get_prop(field.clone()) {
+                return Some(prop);
+            }
+        }
+        self.with_props(|obj| obj.get(&field).cloned()).flatten()
     }
 
-    pub fn remove_prop(&self, field: &String) {
+    /// Get the string of a string, or of a `String` object
+    fn string_data(&self) -> Option<JsString> {
         match *self {
-            ValueData::Object(ref obj, _) => obj.borrow_mut().deref_mut().remove(field),
-            // Accesing .object on borrow() seems to automatically dereference it, so we don't need the *
-            ValueData::Function(ref func) => match func.borrow_mut().deref_mut() {
-                Function::NativeFunc(ref mut func) => func.object.remove(field),
-                Function::RegularFunc(ref mut func) => func.object.remove(field),
+            ValueData::String(ref s) => Some(s.clone()),
+            ValueData::Object(_, _) => match *self.get_private_field_slice("PrimitiveValue") {
+                ValueData::String(ref s) => Some(s.clone()),
+                _ => None,
             },
             _ => None,
-        };
+        }
+    }
+
+    /// Get the keys of the object's own properties, which are array indices in ascending order, then the other
+    /// strings and then symbols in the order they were added
+    /// https://tc39.es/ecma262/#sec-ordinaryownpropertykeys
+    pub fn own_keys(&self) -> Vec<PropertyKey> {
+        let mut indices = Vec::new();
+        let mut strings = Vec::new();
+        if let Some(ref s) = self.string_data() {
+            let length = s.len();
+            indices.extend((0..length).map(|index| (index as u32, index.to_string().into())));
+            strings.push("length".into());
+        }
+        if let Some(typed_array) = typed_array::TypedArray::from_value(self) {
+            let length = typed_array.length_or_zero();
+            indices.extend((0..length).map(|index| (index as u32, index.to_string().into())));
+        }
+        let mut symbols = Vec::new();
+        self.with_props(|obj| {
+            for (key, _) in obj.iter() {
+                match *key {
+                    _ if *key == INSTANCE_PROTOTYPE => (),
+                    PropertyKey::Symbol(_) => symbols.push(key.clone()),
+                    _ => match array::array_index(key) {
+                        Some(index) => indices.push((index, key.clone())),
+                        None => strings.push(key.clone()),
+                    },
+                }
+            }
+        });
+        indices.sort_by_key(|&(index, _)| index);
+        indices.into_iter().map(|(_, key)| key).chain(strings).chain(symbols).collect()
+    }
+
+    pub fn remove_prop<K: Into<PropertyKey>>(&self, field: K) {
+        let field = &field.into();
+        self.with_props_mut(|obj| obj.remove(field));
     }
 
     pub fn update_prop(
@@ -87,10 +200,7 @@ impl ValueData {
         let obj: Option<ObjectData> = match self {
             ValueData::Object(ref obj, _) => Some(obj.borrow_mut().deref_mut().clone()),
             // Accesing .object on borrow() seems to automatically dereference it, so we don't need the *
-            ValueData::Function(ref func) => match func.borrow_mut().deref_mut() {
-                Function::NativeFunc(ref mut func) => Some(func.object.clone()),
-                Function::RegularFunc(ref mut func) => Some(func.object.clone()),
-            },
+            ValueData::Function(ref func) => Some(func.borrow().object().clone()),
             _ => None,
         };
 
@@ -100,22 +210,18 @@ impl ValueData {
 
         let mut hashmap = obj.unwrap();
         // Use value, or walk up the prototype chain
-        match hashmap.get_mut(&field) {
-            Some(ref mut prop) => {
-                prop.value = value.unwrap_or(prop.value.clone());
-                prop.enumerable = enumerable.unwrap_or(prop.enumerable);
-                prop.writable = writable.unwrap_or(prop.writable);
-                prop.configurable = configurable.unwrap_or(prop.configurable);
-            }
-            // Try again with next prop up the chain
-            None => (),
+        if let Some(prop) = hashmap.get_mut(&field.into()) {
+            prop.value = value.unwrap_or(prop.value.clone());
+            prop.enumerable = enumerable.unwrap_or(prop.enumerable);
+            prop.writable = writable.unwrap_or(prop.writable);
+            prop.configurable = configurable.unwrap_or(prop.configurable);
         }
     }
 
     pub fn new_obj_from_prototype(proto: Value) -> Value {
-        let mut obj: ObjectData = HashMap::new();
-        let private_obj: ObjectData = HashMap::new();
-        obj.insert(INSTANCE_PROTOTYPE.to_string(), Property::new(proto));
+        let mut obj: ObjectData = ObjectData::new();
+        let private_obj: ObjectData = ObjectData::new();
+        obj.insert(INSTANCE_PROTOTYPE.into(), Property::new(proto));
         Gc::new(ValueData::Object(
             GcCell::new(obj),
             GcCell::new(private_obj),
@@ -178,14 +284,49 @@ impl ValueData {
         }
     }
 
+    /// Returns true if the value is an Array exotic object
+    pub fn is_array(&self) -> bool {
+        match *self {
+            ValueData::Object(_, ref private) => private.borrow().contains_key(&array::IS_ARRAY.into()),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the value is a BigInt
+    pub fn is_bigint(&self) -> bool {
+        matches!(*self, ValueData::BigInt(_))
+    }
+
+    /// Returns true if the value is a symbol
+    pub fn is_symbol(&self) -> bool {
+        matches!(*self, ValueData::Symbol(_))
+    }
+
+    /// Converts the value to a property key, symbols are kept and anything else becomes a string
+    pub fn to_property_key(&self) -> PropertyKey {
+        match *self {
+            ValueData::Symbol(ref symbol) => PropertyKey::Symbol(symbol.clone()),
+            _ => PropertyKey::String(self.to_string()),
+        }
+    }
+
+    /// Converts a primitive value to a string, keeping the code units of strings as they are
+    pub fn to_js_string(&self) -> JsString {
+        match *self {
+            ValueData::String(ref s) => s.clone(),
+            _ => JsString::from(self.to_string()),
+        }
+    }
+
     /// Returns true if the value is true
     /// [toBoolean](https://tc39.github.io/ecma262/#sec-toboolean)
     pub fn is_true(&self) -> bool {
         match *self {
-            ValueData::Object(_, _) => true,
+            ValueData::Object(_, _) | ValueData::Function(_) | ValueData::Symbol(_) => true,
             ValueData::String(ref s) if !s.is_empty() => true,
-            ValueData::Number(n) if n >= 1.0 && n % 1.0 == 0.0 => true,
-            ValueData::Integer(n) if n > 1 => true,
+            ValueData::Number(n) if n != 0.0 && !n.is_nan() => true,
+            ValueData::Integer(n) if n != 0 => true,
+            ValueData::BigInt(ref n) => !n.is_zero(),
             ValueData::Boolean(v) => v,
             _ => false,
         }
@@ -194,15 +335,17 @@ impl ValueData {
     /// Converts the value into a 64-bit floating point number
     pub fn to_num(&self) -> f64 {
         match *self {
-            ValueData::Object(_, _) | ValueData::Undefined | ValueData::Function(_) => NAN,
-            ValueData::String(ref str) => match FromStr::from_str(str) {
-                Ok(num) => num,
-                Err(_) => NAN,
-            },
+            ValueData::Object(_, _)
+            | ValueData::Undefined
+            | ValueData::Function(_)
+            | ValueData::Buffer(_)
+            | ValueData::Symbol(_) => f64::NAN,
+            ValueData::String(ref str) => number::string_to_number(&str.to_string()),
             ValueData::Number(num) => num,
             ValueData::Boolean(true) => 1.0,
             ValueData::Boolean(false) | ValueData::Null => 0.0,
             ValueData::Integer(num) => num as f64,
+            ValueData::BigInt(ref num) => num.to_f64(),
         }
     }
 
@@ -213,12 +356,12 @@ impl ValueData {
             | ValueData::Undefined
             | ValueData::Null
             | ValueData::Boolean(false)
-            | ValueData::Function(_) => 0,
-            ValueData::String(ref str) => match FromStr::from_str(str) {
-                Ok(num) => num,
-                Err(_) => 0,
-            },
+            | ValueData::Function(_)
+            | ValueData::Buffer(_)
+            | ValueData::Symbol(_) => 0,
+            ValueData::String(ref str) => number::string_to_number(&str.to_string()) as i32,
             ValueData::Number(num) => num as i32,
+            ValueData::BigInt(ref num) => num.to_f64() as i32,
             ValueData::Boolean(true) => 1,
             ValueData::Integer(num) => num,
         }
@@ -226,91 +369,67 @@ impl ValueData {
 
     /// Resolve the property in the object
     /// Returns a copy of the Property
-    pub fn get_prop(&self, field: String) -> Option<Property> {
-        // handle length
+    pub fn get_prop<K: Into<PropertyKey>>(&self, field: K) -> Option<Property> {
+        // Strings have their length and code units as properties, their methods come from String.prototype
         // Spidermonkey has its own GetLengthProperty: https://searchfox.org/mozilla-central/source/js/src/vm/Interpreter-inl.h#154
-        // TODO: Maybe we need a GetLengthProperty for value types
         if let ValueData::String(ref s) = *self {
-            return Some(Property::new(to_value(s.len() as i32)));
+            let field = field.into();
+            if field == "length" {
+                return Some(Property::new(to_value(s.len() as i32)));
+            }
+            return array::array_index(&field)
+                .and_then(|index| string::code_unit_at(s, index as usize))
+                .map(|unit| {
+                    let mut prop = Property::new(to_value(unit));
+                    prop.enumerable = true;
+                    prop
+                });
         }
 
-        let obj: ObjectData = match *self {
-            ValueData::Object(ref obj, _) => {
-                let hash = obj.clone();
-                hash.into_inner()
-            }
-            // Accesing .object on borrow() seems to automatically dereference it, so we don't need the *
-            ValueData::Function(ref func) => match func.clone().into_inner() {
-                Function::NativeFunc(ref func) => func.object.clone(),
-                Function::RegularFunc(ref func) => func.object.clone(),
-            }
-            _ => return None,
-        };
-        match obj.get(&field) {
-            Some(val) => Some(val.clone()),
-            None => match obj.get(&INSTANCE_PROTOTYPE.to_string()) {
-                Some(prop) => prop.value.get_prop(field),
-                None => None,
-            },
+        let field = field.into();
+        // Typed arrays don't look up their prototype chain for numeric keys
+        if let Some(index) = typed_array::element_index(self, &field) {
+            return typed_array::element_property(self, index);
+        }
+        let (prop, proto) = self.with_props(|obj| {
+            (obj.get(&field).cloned(), obj.get(&INSTANCE_PROTOTYPE.into()).map(|prop| prop.value.clone()))
+        })?;
+        match prop {
+            Some(prop) => Some(prop),
+            None => proto?.get_prop(field),
         }
     }
 
     /// Resolve the property in the object
     /// Returns a copy of the Property
-    pub fn get_private_prop(&self, field: String) -> Option<Property> {
-        let obj: ObjectData = match *self {
-            ValueData::Object(_, ref obj) => {
-                let hash = obj.clone();
-                hash.into_inner()
-            }
-            _ => return None,
-        };
-
-        match obj.get(&field) {
-            Some(val) => Some(val.clone()),
-            None => match obj.get(&INSTANCE_PROTOTYPE.to_string()) {
-                Some(prop) => prop.value.get_prop(field),
-                None => None,
-            },
+    pub fn get_private_prop<K: Into<PropertyKey>>(&self, field: K) -> Option<Property> {
+        let field = field.into();
+        let (prop, proto) = self.with_private_props(|obj| {
+            (obj.get(&field).cloned(), obj.get(&INSTANCE_PROTOTYPE.into()).map(|prop| prop.value.clone()))
+        })?;
+        match prop {
+            Some(prop) => Some(prop),
+            None => proto?.get_prop(field),
         }
     }
 
-    pub fn get_private_field(&self, field: String) -> Value {
+    pub fn get_private_field<K: Into<PropertyKey>>(&self, field: K) -> Value {
         match self.get_private_prop(field) {
             Some(prop) => prop.value.clone(),
             None => Gc::new(ValueData::Undefined),
         }
     }
 
+    /// Get the private field in the value, or undefined if it doesn't exist
+    pub fn get_private_field_slice<'a>(&self, field: &'a str) -> Value {
+        self.get_private_field(field.to_string())
+    }
+
     /// Resolve the property in the object and get its value, or undefined if this is not an object or the field doesn't exist
-    pub fn get_field(&self, field: String) -> Value {
+    /// Getters of accessor properties need the interpreter to run, see `Interpreter::get_field`
+    pub fn get_field<K: Into<PropertyKey>>(&self, field: K) -> Value {
         match self.get_prop(field) {
-            Some(prop) => {
-                // If the Property has [[Get]] set to a function, we should run that and return the Value
-                let prop_getter = match *prop.get {
-                    ValueData::Function(ref v) => match *v.borrow() {
-                        Function::NativeFunc(ref ntv) => {
-                            let func = ntv.data;
-                            Some(
-                                func(
-                                    Gc::new(self.clone()),
-                                    Gc::new(self.clone()),
-                                    vec![Gc::new(self.clone())],
-                                )
-                                .unwrap(),
-                            )
-                        }
-                        _ => None,
-                    },
-                    _ => None,
-                };
-
-                // If the getter is populated, use that. If not use [[Value]] instead
-                match prop_getter {
-                    Some(val) => val,
-                    None => prop.value.clone(),
-                }
-            }
+            Some(prop) => prop.value.clone(),
             None => Gc::new(ValueData::Undefined),
         }
     }
@@ -320,7 +439,7 @@ impl ValueData {
         self.get_field(field.to_string())
     }
 
-    pub fn has_field(&self, field: String) -> bool {
+    pub fn has_field<K: Into<PropertyKey>>(&self, field: K) -> bool {
         match self.get_prop(field) {
             Some(_) => true,
             None => false,
@@ -328,40 +447,46 @@ impl ValueData {
     }
 
 
-    pub fn set_private_field(&self, field: String, val: Value) -> Value {
-        match *self {
-            ValueData::Object(_, ref obj) => {
-                obj.borrow_mut()
-                    .insert(field.clone(), Property::new(val.clone()));
-            }
-            _ => (),
-        }
+    pub fn set_private_field<K: Into<PropertyKey>>(&self, field: K, val: Value) -> Value {
+        self.with_private_props_mut(|obj| obj.insert(field.into(), Property::new(val.clone())));
         val
     }
 
+    /// Returns true if the value has the private field, even if it holds undefined
+    pub fn has_private_field<K: Into<PropertyKey>>(&self, field: K) -> bool {
+        let field = field.into();
+        self.with_private_props(|obj| obj.contains_key(&field)).unwrap_or(false)
+    }
+
+    /// Remove the private field from the value
+    pub fn remove_private_field<K: Into<PropertyKey>>(&self, field: K) {
+        let field = field.into();
+        self.with_private_props_mut(|obj| obj.remove(&field));
+    }
+
     /// Set the private field in the value
     pub fn set_private_field_slice<'a>(&self, field: &'a str, val: Value) -> Value {
         self.set_private_field(field.to_string(), val)
     }
 
-    /// Set the field in the value
-    pub fn set_field(&self, field: String, val: Value) -> Value {
+    /// Set the field in the value, keeping the attributes of an existing data property, or adding a
+    /// data property which is writable, enumerable and configurable
+    /// This doesn't check the attributes or run setters, see `Interpreter::set_field`
+    /// Arrays keep their `length` in step with their elements, see `array::array_set`, and typed arrays store
+    /// their elements in their buffer, see `typed_array::store_element`
+    pub fn set_field<K: Into<PropertyKey>>(&self, field: K, val: Value) -> Value {
+        let field = field.into();
+        if let Some(index) = typed_array::element_index(self, &field) {
+            typed_array::store_element(self, index, &val);
+            return val;
+        }
         match *self {
-            ValueData::Object(ref obj, _) => {
-                obj.borrow_mut()
-                    .insert(field.clone(), Property::new(val.clone()));
+            ValueData::Object(ref obj, _) if self.is_array() => {
+                array::array_set(&mut obj.borrow_mut(), field, val.clone());
             }
-            ValueData::Function(ref func) => {
-                match *func.borrow_mut().deref_mut() {
-                    Function::NativeFunc(ref mut f) => {
-                        f.object.insert(field.clone(), Property::new(val.clone()))
-                    }
-                    Function::RegularFunc(ref mut f) => {
-                        f.object.insert(field.clone(), Property::new(val.clone()))
-                    }
-                };
+            _ => {
+                self.with_props_mut(|obj| obj.set_value(field, val.clone()));
             }
-            _ => (),
         }
         val
     }
@@ -372,18 +497,14 @@ impl ValueData {
     }
 
     /// Set the property in the value
-    pub fn set_prop(&self, field: String, prop: Property) -> Property {
+    pub fn set_prop<K: Into<PropertyKey>>(&self, field: K, prop: Property) -> Property {
+        let field = field.into();
         match *self {
             ValueData::Object(ref obj, _) => {
                 obj.borrow_mut().insert(field.clone(), prop.clone());
             }
             ValueData::Function(ref func) => {
-                match *func.borrow_mut().deref_mut() {
-                    Function::NativeFunc(ref mut f) => f.object.insert(field.clone(), prop.clone()),
-                    Function::RegularFunc(ref mut f) => {
-                        f.object.insert(field.clone(), prop.clone())
-                    }
-                };
+                func.borrow_mut().object_mut().insert(field.clone(), prop.clone());
             }
             _ => (),
         }
@@ -399,29 +520,29 @@ impl ValueData {
     pub fn from_json(json: JSONValue) -> ValueData {
         match json {
             JSONValue::Number(v) => ValueData::Number(v.as_f64().unwrap()),
-            JSONValue::String(v) => ValueData::String(v),
+            JSONValue::String(v) => ValueData::String(JsString::from(v)),
             JSONValue::Bool(v) => ValueData::Boolean(v),
             JSONValue::Array(vs) => {
                 let mut i = 0;
-                let private_data: ObjectData = HashMap::new();
+                let private_data: ObjectData = ObjectData::new();
                 let mut data: ObjectData = FromIterator::from_iter(vs.iter().map(|json| {
                     i += 1;
                     (
-                        (i - 1).to_string().to_string(),
-                        Property::new(to_value(json.clone())),
+                        (i - 1).to_string().into(),
+                        Property::data(to_value(json.clone())),
                     )
                 }));
                 data.insert(
-                    "length".to_string(),
+                    "length".into(),
                     Property::new(to_value(vs.len() as i32)),
                 );
                 ValueData::Object(GcCell::new(data), GcCell::new(private_data))
             }
             JSONValue::Object(obj) => {
-                let private_data: ObjectData = HashMap::new();
+                let private_data: ObjectData = ObjectData::new();
                 let data: ObjectData = FromIterator::from_iter(
                     obj.iter()
-                        .map(|(key, json)| (key.clone(), Property::new(to_value(json.clone())))),
+                        .map(|(key, json)| (key.into(), Property::data(to_value(json.clone())))),
                 );
                 ValueData::Object(GcCell::new(data), GcCell::new(private_data))
             }
@@ -436,16 +557,21 @@ impl ValueData {
             ValueData::Object(ref obj, _) => {
                 let mut nobj = Map::new();
                 for (k, v) in obj.borrow().iter() {
-                    if k != INSTANCE_PROTOTYPE {
-                        nobj.insert(k.clone(), v.value.to_json());
+                    if let PropertyKey::String(ref k) = *k {
+                        if k != INSTANCE_PROTOTYPE {
+                            nobj.insert(k.clone(), v.value.to_json());
+                        }
                     }
                 }
                 JSONValue::Object(nobj)
             }
-            ValueData::String(ref str) => JSONValue::String(str.clone()),
-            ValueData::Number(num) => JSONValue::Number(JSONNumber::from_f64(num).unwrap()),
+            ValueData::String(ref str) => JSONValue::String(str.to_string()),
+            // JSON has no NaN or infinities
+            ValueData::Number(num) => JSONNumber::from_f64(num).map_or(JSONValue::Null, JSONValue::Number),
             ValueData::Integer(val) => JSONValue::Number(JSONNumber::from(val)),
-            ValueData::Function(_) => JSONValue::Null,
+            // JSON.stringify throws before converting a BigInt
+            ValueData::BigInt(ref val) => JSONValue::String(val.to_string()),
+            ValueData::Function(_) | ValueData::Buffer(_) | ValueData::Symbol(_) => JSONValue::Null,
         }
     }
 
@@ -457,6 +583,8 @@ impl ValueData {
             ValueData::Boolean(_) => "boolean",
             ValueData::Null => "null",
             ValueData::Undefined => "undefined",
+            ValueData::Symbol(_) => "symbol",
+            ValueData::BigInt(_) => "bigint",
             _ => "object",
         }
     }
@@ -469,16 +597,10 @@ impl Display for ValueData {
             ValueData::Undefined => write!(f, "undefined"),
             ValueData::Boolean(v) => write!(f, "{}", v),
             ValueData::String(ref v) => write!(f, "{}", v),
-            ValueData::Number(v) => write!(
-                f,
-                "{}",
-                match v {
-                    _ if v.is_nan() => "NaN".to_string(),
-                    _ if v.is_infinite() && v.is_sign_negative() => "-Infinity".to_string(),
-                    _ if v.is_infinite() => "Infinity".to_string(),
-                    _ => v.to_string(),
-                }
-            ),
+            ValueData::Number(v) => write!(f, "{}", number::number_to_string(v)),
+            ValueData::Object(_, _) if error::is_error_object(self) => {
+                write!(f, "{}", error::error_message(self))
+            }
             ValueData::Object(ref v, ref p) => {
                 write!(f, "{}", "{")?;
                 match v.borrow().iter().last() {
@@ -508,8 +630,13 @@ impl Display for ValueData {
                 write!(f, "{}", "}")
             }
             ValueData::Integer(v) => write!(f, "{}", v),
+            ValueData::Symbol(ref v) => write!(f, "{}", v),
+            ValueData::BigInt(ref v) => write!(f, "{}", v),
+            ValueData::Buffer(ref v) => write!(f, "[{} bytes]", v.len()),
             ValueData::Function(ref v) => match *v.borrow() {
-                Function::NativeFunc(_) => write!(f, "{}", "function() { [native code] }"),
+                Function::NativeFunc(_) | Function::BoundFunc(_) => {
+                    write!(f, "{}", "function() { [native code] }")
+                }
                 Function::RegularFunc(ref rf) => {
                     write!(f, "function({}){}", rf.args.join(", "), rf.expr)
                 }
@@ -524,8 +651,19 @@ impl PartialEq for ValueData {
             // TODO: fix this
             // _ if self.ptr.to_inner() == &other.ptr.to_inner() => true,
             _ if self.is_null_or_undefined() && other.is_null_or_undefined() => true,
+            (ValueData::Symbol(ref a), ValueData::Symbol(ref b)) => a == b,
+            (ValueData::Symbol(_), _) | (_, ValueData::Symbol(_)) => false,
+            (ValueData::BigInt(ref a), ValueData::BigInt(ref b)) => a == b,
+            (ValueData::BigInt(ref a), ValueData::String(ref b))
+            | (ValueData::String(ref b), ValueData::BigInt(ref a)) => BigInt::parse(&b.to_string()).as_ref() == Some(a),
+            (ValueData::BigInt(ref a), ref b) | (ref b, ValueData::BigInt(ref a))
+                if b.get_type() == "number" || b.get_type() == "boolean" =>
+            {
+                a.compare_f64(b.to_num()) == Some(Ordering::Equal)
+            }
+            (ValueData::BigInt(_), _) | (_, ValueData::BigInt(_)) => false,
             (ValueData::String(_), _) | (_, ValueData::String(_)) => {
-                self.to_string() == other.to_string()
+                self.to_js_string() == other.to_js_string()
             }
             (ValueData::Boolean(a), ValueData::Boolean(b)) if a == b => true,
             (ValueData::Number(a), ValueData::Number(b))
@@ -545,9 +683,8 @@ impl Add for ValueData {
     type Output = ValueData;
     fn add(self, other: ValueData) -> ValueData {
         return match (self.clone(), other.clone()) {
-            (ValueData::String(ref s), ref other) | (ref other, ValueData::String(ref s)) => {
-                ValueData::String(s.clone() + &other.to_string())
-            }
+            (ValueData::String(ref s), ref other) => ValueData::String(s.concat(&other.to_js_string())),
+            (ref other, ValueData::String(ref s)) => ValueData::String(other.to_js_string().concat(s)),
             (_, _) => ValueData::Number(self.to_num() + other.to_num()),
         };
     }
@@ -627,6 +764,12 @@ pub trait FromValue {
 }
 
 impl ToValue for String {
+    fn to_value(&self) -> Value {
+        Gc::new(ValueData::String(JsString::from(self.as_str())))
+    }
+}
+
+impl ToValue for JsString {
     fn to_value(&self) -> Value {
         Gc::new(ValueData::String(self.clone()))
     }
@@ -640,13 +783,13 @@ impl FromValue for String {
 
 impl<'s> ToValue for &'s str {
     fn to_value(&self) -> Value {
-        Gc::new(ValueData::String(String::from_str(*self).unwrap()))
+        Gc::new(ValueData::String(JsString::from(*self)))
     }
 }
 
 impl ToValue for char {
     fn to_value(&self) -> Value {
-        Gc::new(ValueData::String(self.to_string()))
+        Gc::new(ValueData::String(JsString::from(self.to_string())))
     }
 }
 impl FromValue for char {
@@ -690,10 +833,10 @@ impl FromValue for bool {
 
 impl<'s, T: ToValue> ToValue for &'s [T] {
     fn to_value(&self) -> Value {
-        let mut arr = HashMap::new();
+        let mut arr = ObjectData::new();
         let mut i = 0;
         for item in self.iter() {
-            arr.insert(i.to_string(), Property::new(item.to_value()));
+            arr.insert(i.to_string().into(), Property::data(item.to_value()));
             i += 1;
         }
         to_value(arr)
@@ -701,10 +844,10 @@ impl<'s, T: ToValue> ToValue for &'s [T] {
 }
 impl<T: ToValue> ToValue for Vec<T> {
     fn to_value(&self) -> Value {
-        let mut arr = HashMap::new();
+        let mut arr = ObjectData::new();
         let mut i = 0;
         for item in self.iter() {
-            arr.insert(i.to_string(), Property::new(item.to_value()));
+            arr.insert(i.to_string().into(), Property::data(item.to_value()));
             i += 1;
         }
         to_value(arr)
@@ -724,7 +867,7 @@ impl<T: FromValue> FromValue for Vec<T> {
 
 impl ToValue for ObjectData {
     fn to_value(&self) -> Value {
-        let private_obj: ObjectData = HashMap::new();
+        let private_obj: ObjectData = ObjectData::new();
         Gc::new(ValueData::Object(
             GcCell::new(self.clone()),
             GcCell::new(private_obj),
@@ -736,10 +879,7 @@ impl FromValue for ObjectData {
     fn from_value(v: Value) -> Result<ObjectData, &'static str> {
         match *v {
             ValueData::Object(ref obj, _) => Ok(obj.clone().into_inner()),
-            ValueData::Function(ref func) => Ok(match *func.borrow().deref() {
-                Function::NativeFunc(ref data) => data.object.clone(),
-                Function::RegularFunc(ref data) => data.object.clone(),
-            }),
+            ValueData::Function(ref func) => Ok(func.borrow().object().clone()),
             _ => Err("Value is not a valid object"),
         }
     }
@@ -815,6 +955,52 @@ pub fn to_value<A: ToValue>(v: A) -> Value {
     v.to_value()
 }
 
+/// Get the argument at the given index of a native function call, or undefined if it wasn't passed
+pub fn arg(args: &[Value], index: usize) -> Value {
+    match args.get(index) {
+        Some(value) => value.clone(),
+        None => Gc::new(ValueData::Undefined),
+    }
+}
+
+/// Compare two values without type coercion, where objects are equal only to themselves
+/// https://tc39.es/ecma262/#sec-isstrictlyequal
+pub fn strict_equals(a: &Value, b: &Value) -> bool {
+    if a.get_type() != b.get_type() {
+        return false;
+    }
+    match (&**a, &**b) {
+        (ValueData::Object(_, _), _) | (ValueData::Function(_), _) => Gc::ptr_eq(a, b),
+        (ValueData::Buffer(a), ValueData::Buffer(b)) => a.ptr_eq(b),
+        (ValueData::String(a), ValueData::String(b)) => a == b,
+        (ValueData::Boolean(a), ValueData::Boolean(b)) => a == b,
+        (ValueData::Symbol(a), ValueData::Symbol(b)) => a == b,
+        (ValueData::BigInt(a), ValueData::BigInt(b)) => a == b,
+        _ if a.get_type() == "number" => a.to_num() == b.to_num(),
+        // Null and undefined
+        _ => true,
+    }
+}
+
+/// Compare two values like `strict_equals`, except that `NaN` is equal to itself and `0` is different from `-0`
+/// https://tc39.es/ecma262/#sec-samevalue
+pub fn same_value(a: &Value, b: &Value) -> bool {
+    if a.get_type() == "number" && b.get_type() == "number" {
+        let (x, y) = (a.to_num(), b.to_num());
+        return (x.is_nan() && y.is_nan()) || (x == y && x.is_sign_negative() == y.is_sign_negative());
+    }
+    strict_equals(a, b)
+}
+
+/// Compare two values like `strict_equals`, except that `NaN` is equal to itself
+/// https://tc39.es/ecma262/#sec-samevaluezero
+pub fn same_value_zero(a: &Value, b: &Value) -> bool {
+    if a.get_type() == "number" && b.get_type() == "number" && a.to_num().is_nan() && b.to_num().is_nan() {
+        return true;
+    }
+    strict_equals(a, b)
+}
+
 
 impl ToValue for usize {
     fn to_value(&self) -> Value {
@@ -0,0 +1,183 @@
+/// The global `WeakMap` and `WeakSet` objects
+/// https://tc39.es/ecma262/#sec-weakmap-objects
+///
+/// The entries of a weak collection are kept on their keys rather than on the collection: each collection has a
+/// unique symbol, and an entry is a private field of the key under that symbol. The collection holds nothing the
+/// garbage collector would trace to a key, so a key which isn't reachable otherwise is collected with its entries,
+/// and a value is only kept alive while its key is.
+use gc::Gc;
+use crate::exec::Interpreter;
+use crate::js::function::NativeFunctionData;
+use crate::js::iterator;
+use crate::js::object::PROTOTYPE;
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{arg, to_value, ResultValue, Value, ValueData};
+
+/// The private field holding the symbol which the entries of a weak map are kept under on their keys
+pub static WEAK_MAP_DATA: &str = "WeakMapData";
+
+/// The private field holding the symbol which the values of a weak set are marked with
+pub static WEAK_SET_DATA: &str = "WeakSetData";
+
+/// Returns true if the value can be held weakly, which is any object
+/// https://tc39.es/ecma262/#sec-canbeheldweakly
+fn can_be_held_weakly(value: &Value) -> bool {
+    value.is_object() || value.is_function()
+}
+
+/// Get the symbol a weak collection keeps its entries under, or throw if `this` isn't that kind of collection
+fn this_weak_data(this: &Value, slot: &str, method: &str, interpreter: &Interpreter) -> Result<Symbol, Value> {
+    match *this.get_private_field_slice(slot) {
+        ValueData::Symbol(ref symbol) => Ok(symbol.clone()),
+        _ => {
+            let name = if slot == WEAK_MAP_DATA { "WeakMap" } else { "WeakSet" };
+            Err(interpreter.type_error(format!(
+                "{0}.prototype.{1} requires that 'this' be a {0}",
+                name, method
+            )))
+        }
+    }
+}
+
+/// Give a new weak collection its symbol, then add the entries of an iterable with the adder method
+fn init_weak_collection(this: &Value, slot: &str, adder: &str, args: &[Value], interpreter: &mut Interpreter) -> ResultValue {
+    let name = if slot == WEAK_MAP_DATA { "WeakMap" } else { "WeakSet" };
+    if !this.is_object() || Gc::ptr_eq(this, &interpreter.get_global_object()) {
+        return Err(interpreter.type_error(format!("Constructor {} requires 'new'", name)));
+    }
+    let symbol = Symbol::new(Some(name.to_string()));
+    this.set_private_field_slice(slot, Gc::new(ValueData::Symbol(symbol)));
+    let iterable = arg(args, 0);
+    if iterable.is_null_or_undefined() {
+        return Ok(this.clone());
+    }
+    let adder = interpreter.get_field(this, adder)?;
+    if !adder.is_function() {
+        return Err(interpreter.type_error(format!("{} is not a function", adder)));
+    }
+    for item in iterator::iterable_to_list(&iterable, interpreter)? {
+        let args = if slot == WEAK_MAP_DATA {
+            if !item.is_object() {
+                return Err(interpreter.type_error(format!("Iterator value {} is not an entry object", item)));
+            }
+            vec![interpreter.get_field(&item, "0")?, interpreter.get_field(&item, "1")?]
+        } else {
+            vec![item]
+        };
+        interpreter.call(&adder, this, args)?;
+    }
+    Ok(this.clone())
+}
+
+/// Create a new weak map, adding the `[key, value]` entries of an iterable with the map's `set` method
+/// https://tc39.es/ecma262/#sec-weakmap-iterable
+pub fn make_weak_map(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    init_weak_collection(&this, WEAK_MAP_DATA, "set", &args, interpreter)
+}
+
+/// `WeakMap.prototype.get(key)`
+/// https://tc39.es/ecma262/#sec-weakmap.prototype.get
+pub fn get(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = this_weak_data(&this, WEAK_MAP_DATA, "get", interpreter)?;
+    Ok(arg(&args, 0).get_private_field(symbol))
+}
+
+/// `WeakMap.prototype.set(key, value)`
+/// https://tc39.es/ecma262/#sec-weakmap.prototype.set
+pub fn set(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = this_weak_data(&this, WEAK_MAP_DATA, "set", interpreter)?;
+    let key = arg(&args, 0);
+    if !can_be_held_weakly(&key) {
+        return Err(interpreter.type_error(format!("Invalid value used as weak map key: {}", key)));
+    }
+    key.set_private_field(symbol, arg(&args, 1));
+    Ok(this)
+}
+
+/// `WeakMap.prototype.has(key)`
+/// https://tc39.es/ecma262/#sec-weakmap.prototype.has
+pub fn has(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = this_weak_data(&this, WEAK_MAP_DATA, "has", interpreter)?;
+    Ok(to_value(arg(&args, 0).has_private_field(symbol)))
+}
+
+/// `WeakMap.prototype.delete(key)`
+/// https://tc39.es/ecma262/#sec-weakmap.prototype.delete
+pub fn delete(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = this_weak_data(&this, WEAK_MAP_DATA, "delete", interpreter)?;
+    Ok(to_value(delete_entry(&arg(&args, 0), symbol)))
+}
+
+/// Remove the entry kept on a key, returning true if there was one
+fn delete_entry(key: &Value, symbol: Symbol) -> bool {
+    let existed = key.has_private_field(symbol.clone());
+    key.remove_private_field(symbol);
+    existed
+}
+
+/// Create a new weak set, adding the values of an iterable with the set's `add` method
+/// https://tc39.es/ecma262/#sec-weakset-iterable
+pub fn make_weak_set(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    init_weak_collection(&this, WEAK_SET_DATA, "add", &args, interpreter)
+}
+
+/// `WeakSet.prototype.add(value)`
+/// https://tc39.es/ecma262/#sec-weakset.prototype.add
+pub fn add(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = this_weak_data(&this, WEAK_SET_DATA, "add", interpreter)?;
+    let value = arg(&args, 0);
+    if !can_be_held_weakly(&value) {
+        return Err(interpreter.type_error(format!("Invalid value used in weak set: {}", value)));
+    }
+    value.set_private_field(symbol, to_value(true));
+    Ok(this)
+}
+
+/// `WeakSet.prototype.has(value)`
+/// https://tc39.es/ecma262/#sec-weakset.prototype.has
+pub fn set_has(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = this_weak_data(&this, WEAK_SET_DATA, "has", interpreter)?;
+    Ok(to_value(arg(&args, 0).has_private_field(symbol)))
+}
+
+/// `WeakSet.prototype.delete(value)`
+/// https://tc39.es/ecma262/#sec-weakset.prototype.delete
+pub fn set_delete(this: Value, _: Value, args: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let symbol = this_weak_data(&this, WEAK_SET_DATA, "delete", interpreter)?;
+    Ok(to_value(delete_entry(&arg(&args, 0), symbol)))
+}
+
+/// Create a new `WeakMap` object
+pub fn create_weak_map(global: &Value) -> Value {
+    let weak_map = to_value(make_weak_map as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("get", to_value(get as NativeFunctionData));
+    prototype.set_field_slice("set", to_value(set as NativeFunctionData));
+    prototype.set_field_slice("has", to_value(has as NativeFunctionData));
+    prototype.set_field_slice("delete", to_value(delete as NativeFunctionData));
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("WeakMap"));
+    weak_map.set_field_slice("length", to_value(0i32));
+    prototype.set_field_slice("constructor", weak_map.clone());
+    weak_map.set_field_slice(PROTOTYPE, prototype);
+    weak_map
+}
+
+/// Create a new `WeakSet` object
+pub fn create_weak_set(global: &Value) -> Value {
+    let weak_set = to_value(make_weak_set as NativeFunctionData);
+    let prototype = ValueData::new_obj(Some(global));
+    prototype.set_field_slice("add", to_value(add as NativeFunctionData));
+    prototype.set_field_slice("has", to_value(set_has as NativeFunctionData));
+    prototype.set_field_slice("delete", to_value(set_delete as NativeFunctionData));
+    prototype.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("WeakSet"));
+    weak_set.set_field_slice("length", to_value(0i32));
+    prototype.set_field_slice("constructor", weak_set.clone());
+    weak_set.set_field_slice(PROTOTYPE, prototype);
+    weak_set
+}
+
+/// Initialise the global object with the `WeakMap` and `WeakSet` objects
+pub fn init(global: &Value) {
+    global.set_field_slice("WeakMap", create_weak_map(global));
+    global.set_field_slice("WeakSet", create_weak_set(global));
+}
@@ -1,18 +1,29 @@
 extern crate chrono;
 extern crate gc;
+extern crate num_bigint;
+extern crate num_traits;
 extern crate rand;
 extern crate serde_json;
+extern crate unicode_normalization;
 
 #[macro_use]
 extern crate gc_derive;
 
+pub mod async_function;
 pub mod environment;
 pub mod syntax;
 pub mod exec;
 pub mod js;
 pub mod engine;
+pub mod event_loop;
+pub mod module;
+pub mod realm;
+pub mod regex;
 
+use crate::event_loop::EventLoop;
 use crate::exec::{Executor, Interpreter};
+use crate::js::promise::{self, PromiseState};
+use crate::module::FileModuleLoader;
 use crate::syntax::lexer::Lexer;
 use crate::syntax::parser::Parser;
 use wasm_bindgen::prelude::*;
@@ -31,14 +42,34 @@ pub fn exec(src: String) {
     let tokens = lexer.tokens;
 
     // Setup executor
-    let expr = Parser::new(tokens).parse_all().unwrap();
+    let expr = Parser::new(tokens).with_source(&src).parse_all().unwrap();
     // print!("{:#?}", expr);
 
-    let mut engine: Interpreter = Executor::new();
-    let result = engine.run(&expr);
+    // The event loop keeps running timers and jobs after the script has finished
+    let mut event_loop = EventLoop::new(Executor::new());
+    let result = event_loop.interpreter().run(&expr);
+    let result = result.and_then(|v| event_loop.run().map(|_| v));
     match result {
         Ok(v) => print!("{}", v),
-        Err(v) => print!("Error: {}", v),
+        Err(v) => print!("Uncaught {}", v),
+    }
+}
+
+/// Run a module from the file system, with imports resolved from the current directory
+pub fn exec_module(path: &str) {
+    let mut engine: Interpreter = Executor::new();
+    engine.set_module_loader(Box::new(FileModuleLoader::new(".")));
+    let mut event_loop = EventLoop::new(engine);
+    // The module has finished once its promise settles, which may be after timers it awaits have fired
+    let result = event_loop.interpreter().import_module(path);
+    let result = result.and_then(|promise| event_loop.run().map(|_| promise));
+    match result {
+        Ok(promise) => {
+            if let PromiseState::Rejected(v) = promise::promise_state(&promise) {
+                print!("Uncaught {}", v);
+            }
+        }
+        Err(v) => print!("Uncaught {}", v),
     }
 }
 
@@ -55,7 +86,7 @@ pub fn evaluate(src: &str) -> String {
     // Setup executor
     let expr: syntax::ast::expr::Expr;
 
-    match Parser::new(tokens).parse_all() {
+    match Parser::new(tokens).with_source(src).parse_all() {
         Ok(v) => {
             expr = v;
         }
@@ -66,7 +97,7 @@ pub fn evaluate(src: &str) -> String {
     }
 
     let mut engine: Interpreter = Executor::new();
-    let result = engine.run(&expr);
+    let result = engine.run(&expr).and_then(|v| engine.run_jobs().map(|_| v));
     match result {
         Ok(v) => v.to_string(),
         Err(_v) => String::from("Error"),
@@ -0,0 +1,631 @@
+//! # Modules
+//!
+//! https://tc39.es/ecma262/#sec-modules
+//!
+//! Modules are loaded through a `ModuleLoader`, so the embedder decides where their source comes from.
+//! Each module goes through three steps: it is loaded and parsed along with everything it imports,
+//! then linked, which binds its imports to the exporting modules' environments,
+//! then evaluated, after the modules it depends on.
+//! Cycles are allowed: a module which is already being linked or evaluated is skipped.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::async_function::{self, AsyncContext};
+use crate::environment::lexical_environment::{new_module_environment, Environment};
+use crate::environment::module_environment_record::{ImportBinding, ImportBindings};
+use crate::exec::Interpreter;
+use crate::js::array;
+use crate::js::error::{self, ErrorKind, StackFrame};
+use crate::js::function::NativeFunctionData;
+use crate::js::object::Property;
+use crate::js::promise::{self, PromiseState};
+use crate::js::symbol::{Symbol, WellKnownSymbol};
+use crate::js::value::{to_value, ResultValue, Value, ValueData};
+use crate::syntax::ast::expr::{Expr, ExprDef};
+use crate::syntax::ast::module::{ExportDecl, ImportSpecifier};
+use crate::syntax::lexer::Lexer;
+use crate::syntax::parser::Parser;
+use gc::Gc;
+
+/// The local name holding a module's default export when it is not a named declaration
+pub static DEFAULT_EXPORT_BINDING: &str = "*default*";
+
+/// Decides how module specifiers are resolved and where module source code comes from
+pub trait ModuleLoader {
+    /// Turn a specifier into the key of a module, relative to the key of the module importing it.
+    /// The same module must always resolve to the same key.
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String, String>;
+
+    /// Get the source code of a module from its key
+    fn load(&self, key: &str) -> Result<String, String>;
+}
+
+/// Resolve a `/` separated path against the directory of the referrer.
+/// Specifiers starting with `/` are taken from the root, anything else is relative to the referrer.
+pub fn resolve_path(specifier: &str, referrer: Option<&str>) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    if !specifier.starts_with('/') {
+        if let Some(referrer) = referrer {
+            parts.extend(referrer.split('/'));
+            // Drop the file name of the referrer
+            parts.pop();
+        }
+    }
+    for part in specifier.split('/') {
+        match part {
+            "" | "." => (),
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.retain(|part| !part.is_empty());
+    parts.join("/")
+}
+
+/// Loads modules from the file system, relative to a root directory
+pub struct FileModuleLoader {
+    root: PathBuf,
+}
+
+impl FileModuleLoader {
+    /// Make a loader which resolves specifiers from the given root directory
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        FileModuleLoader { root: root.into() }
+    }
+}
+
+impl ModuleLoader for FileModuleLoader {
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String, String> {
+        Ok(resolve_path(specifier, referrer))
+    }
+
+    fn load(&self, key: &str) -> Result<String, String> {
+        fs::read_to_string(self.root.join(key))
+            .map_err(|err| format!("Cannot load module '{}': {}", key, err))
+    }
+}
+
+/// Loads modules from source code held in memory, keyed by path
+#[derive(Default)]
+pub struct MemoryModuleLoader {
+    modules: HashMap<String, String>,
+}
+
+impl MemoryModuleLoader {
+    /// Make a loader with no modules
+    pub fn new() -> Self {
+        MemoryModuleLoader {
+            modules: HashMap::new(),
+        }
+    }
+
+    /// Add the source of a module, which can then be imported as `path`
+    pub fn insert(&mut self, path: &str, source: &str) {
+        self.modules.insert(resolve_path(path, None), source.to_string());
+    }
+}
+
+impl ModuleLoader for MemoryModuleLoader {
+    fn resolve(&self, specifier: &str, referrer: Option<&str>) -> Result<String, String> {
+        Ok(resolve_path(specifier, referrer))
+    }
+
+    fn load(&self, key: &str) -> Result<String, String> {
+        match self.modules.get(key) {
+            Some(source) => Ok(source.clone()),
+            None => Err(format!("Cannot find module '{}'", key)),
+        }
+    }
+}
+
+/// Where a module is up to
+/// https://tc39.es/ecma262/#sec-cyclic-module-records
+pub enum ModuleStatus {
+    Unlinked,
+    Linking,
+    Linked,
+    Evaluating,
+    /// Evaluation has started, and the module's evaluation promise settles once it has finished.
+    /// If evaluation threw, importing the module again rejects with the same error
+    Evaluated,
+}
+
+/// An import entry, binding `specifier` from the module with the key `from`
+struct ImportEntry {
+    specifier: ImportSpecifier,
+    from: String,
+}
+
+/// How a module exports a name which is not its own binding
+enum IndirectExport {
+    /// `export { imported as exported } from "..."`
+    Binding(String),
+    /// `export * as exported from "..."`
+    Namespace,
+}
+
+/// A Source Text Module Record
+/// https://tc39.es/ecma262/#sec-source-text-module-records
+pub struct Module {
+    /// The body of the module, rewritten so a top level `await` can suspend it
+    body: Expr,
+    /// The environment holding the module's top level bindings
+    environment: Environment,
+    /// The indirect bindings in `environment`, filled in when the module is linked
+    import_bindings: ImportBindings,
+    status: ModuleStatus,
+    /// The keys of the modules this module imports from, in source order
+    requested: Vec<String>,
+    imports: Vec<ImportEntry>,
+    /// (exported name, local binding name)
+    local_exports: Vec<(String, String)>,
+    /// (exported name, module key, how it is exported)
+    indirect_exports: Vec<(String, String, IndirectExport)>,
+    /// Modules with all their exports re-exported by `export * from "..."`
+    star_exports: Vec<String>,
+    /// The module namespace object, made the first time it is needed
+    namespace: Option<Value>,
+    /// The promise settled once the module and the modules it depends on have finished evaluating
+    evaluation: Option<Value>,
+}
+
+/// The modules an interpreter has loaded, and the loader used to find new ones
+pub struct ModuleMap {
+    loader: Box<dyn ModuleLoader>,
+    modules: HashMap<String, Module>,
+    /// The keys of the modules currently being evaluated, the last one is the innermost
+    evaluating: Vec<String>,
+}
+
+impl ModuleMap {
+    /// Make an empty module map which loads modules with the given loader
+    pub fn new(loader: Box<dyn ModuleLoader>) -> Self {
+        ModuleMap {
+            loader,
+            modules: HashMap::new(),
+            evaluating: Vec::new(),
+        }
+    }
+
+    /// Replace the module loader, modules which are already loaded are kept
+    pub fn set_loader(&mut self, loader: Box<dyn ModuleLoader>) {
+        self.loader = loader;
+    }
+
+    /// The key of the module being evaluated, used to resolve dynamic imports
+    pub fn current_module(&self) -> Option<String> {
+        self.evaluating.last().cloned()
+    }
+
+    /// Start or resume evaluating the body of a module
+    pub fn push_evaluating(&mut self, key: &str) {
+        self.evaluating.push(key.to_string());
+    }
+
+    /// Stop evaluating the body of the innermost module, until it is resumed or another one starts
+    pub fn pop_evaluating(&mut self) {
+        self.evaluating.pop();
+    }
+
+    fn get(&self, key: &str) -> &Module {
+        &self.modules[key]
+    }
+
+    fn get_mut(&mut self, key: &str) -> &mut Module {
+        self.modules.get_mut(key).unwrap()
+    }
+}
+
+/// Make an error value for a module which can't be found or parsed, with the frames of the call stack importing it
+fn module_error(global: &Value, frames: &[StackFrame], kind: ErrorKind, message: String) -> Value {
+    let error = error::new_error(global, kind, &message);
+    error::capture_stack(&error, frames);
+    error
+}
+
+/// Resolve, load and parse a module and everything it imports, returning its key
+pub fn load_module(
+    interpreter: &mut Interpreter,
+    specifier: &str,
+    referrer: Option<&str>,
+) -> Result<String, Value> {
+    let global = interpreter.get_global_object();
+    let frames = interpreter.call_stack();
+    let key = interpreter
+        .modules()
+        .loader
+        .resolve(specifier, referrer)
+        .map_err(|err| module_error(&global, &frames, ErrorKind::Error, err))?;
+    if interpreter.modules().modules.contains_key(&key) {
+        return Ok(key);
+    }
+    let source = interpreter
+        .modules()
+        .loader
+        .load(&key)
+        .map_err(|err| module_error(&global, &frames, ErrorKind::Error, err))?;
+    let mut lexer = Lexer::new(&source);
+    lexer
+        .lex()
+        .map_err(|err| module_error(&global, &frames, ErrorKind::SyntaxError, format!("{} in {}", err, key)))?;
+    let body = Parser::new(lexer.tokens)
+        .with_source(&source)
+        .parse_all()
+        .map_err(|err| module_error(&global, &frames, ErrorKind::SyntaxError, format!("{:?} in {}", err, key)))?;
+
+    let global_env = interpreter.get_global_environment();
+    let (environment, import_bindings) = new_module_environment(Some(global_env));
+    let mut module = Module {
+        body: async_function::normalize(&body),
+        environment,
+        import_bindings,
+        status: ModuleStatus::Unlinked,
+        requested: Vec::new(),
+        imports: Vec::new(),
+        local_exports: Vec::new(),
+        indirect_exports: Vec::new(),
+        star_exports: Vec::new(),
+        namespace: None,
+        evaluation: None,
+    };
+    let mut specifiers = Vec::new();
+    {
+        let loader = &interpreter.modules().loader;
+        let mut resolve = |specifier: &String| -> Result<String, Value> {
+            let from = loader
+                .resolve(specifier, Some(&key))
+                .map_err(|err| module_error(&global, &frames, ErrorKind::Error, err))?;
+            specifiers.push(specifier.clone());
+            Ok(from)
+        };
+        let statements = match body.def {
+            ExprDef::BlockExpr(ref statements) => statements.clone(),
+            _ => vec![body.clone()],
+        };
+        for statement in statements.iter() {
+            match statement.def {
+                ExprDef::ImportDeclExpr(ref import_specifiers, ref specifier) => {
+                    let from = resolve(specifier)?;
+                    for import in import_specifiers.iter() {
+                        module.imports.push(ImportEntry {
+                            specifier: import.clone(),
+                            from: from.clone(),
+                        });
+                    }
+                    module.requested.push(from);
+                }
+                ExprDef::ExportDeclExpr(ref decl) => match *decl {
+                    ExportDecl::Named(ref names, None) => {
+                        for (local, exported) in names.iter() {
+                            module.local_exports.push((exported.clone(), local.clone()));
+                        }
+                    }
+                    ExportDecl::Named(ref names, Some(ref specifier)) => {
+                        let from = resolve(specifier)?;
+                        for (imported, exported) in names.iter() {
+                            module.indirect_exports.push((
+                                exported.clone(),
+                                from.clone(),
+                                IndirectExport::Binding(imported.clone()),
+                            ));
+                        }
+                        module.requested.push(from);
+                    }
+                    ExportDecl::All(Some(ref exported), ref specifier) => {
+                        let from = resolve(specifier)?;
+                        module.indirect_exports.push((
+                            exported.clone(),
+                            from.clone(),
+                            IndirectExport::Namespace,
+                        ));
+                        module.requested.push(from);
+                    }
+                    ExportDecl::All(None, ref specifier) => {
+                        let from = resolve(specifier)?;
+                        module.star_exports.push(from.clone());
+                        module.requested.push(from);
+                    }
+                    ExportDecl::Decl(ref decl) => {
+                        for name in declared_names(decl) {
+                            module.local_exports.push((name.clone(), name));
+                        }
+                    }
+                    ExportDecl::Default(ref expr) => {
+                        let local = match expr.def {
+                            ExprDef::FunctionDeclExpr(Some(ref name), _, _, _)
+                            | ExprDef::AsyncFunctionDeclExpr(Some(ref name), _, _, _) => name.clone(),
+                            _ => DEFAULT_EXPORT_BINDING.to_string(),
+                        };
+                        module.local_exports.push(("default".to_string(), local));
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+    let requested = module.requested.clone();
+    interpreter.modules().modules.insert(key.clone(), module);
+    for (from, specifier) in requested.iter().zip(specifiers.iter()) {
+        // Specifiers were already resolved, so this only loads modules which aren't loaded yet
+        if !interpreter.modules().modules.contains_key(from) {
+            load_module(interpreter, specifier, Some(&key))?;
+        }
+    }
+    Ok(key)
+}
+
+/// The names bound by an exported declaration
+fn declared_names(decl: &Expr) -> Vec<String> {
+    match decl.def {
+        ExprDef::VarDeclExpr(ref vars)
+        | ExprDef::LetDeclExpr(ref vars)
+        | ExprDef::ConstDeclExpr(ref vars) => vars.iter().map(|(name, _)| name.clone()).collect(),
+        ExprDef::FunctionDeclExpr(Some(ref name), _, _, _)
+        | ExprDef::AsyncFunctionDeclExpr(Some(ref name), _, _, _) => vec![name.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// What an exported name refers to
+enum ResolvedBinding {
+    /// A binding in the environment of the module with this key
+    Binding(String, String),
+    /// The namespace object of the module with this key
+    Namespace(String),
+}
+
+/// Find the binding a module exports under `name`, following re-exports
+/// https://tc39.es/ecma262/#sec-resolveexport
+fn resolve_export(
+    interpreter: &mut Interpreter,
+    key: &str,
+    name: &str,
+    resolve_set: &mut Vec<(String, String)>,
+) -> Option<ResolvedBinding> {
+    // A circular import request
+    if resolve_set.iter().any(|(k, n)| k == key && n == name) {
+        return None;
+    }
+    resolve_set.push((key.to_string(), name.to_string()));
+    let (local, indirect, star_exports) = {
+        let module = interpreter.modules().get(key);
+        let local = module
+            .local_exports
+            .iter()
+            .find(|(exported, _)| exported == name)
+            .map(|(_, local)| local.clone());
+        let indirect = module
+            .indirect_exports
+            .iter()
+            .find(|(exported, _, _)| exported == name)
+            .map(|(_, from, export)| match *export {
+                IndirectExport::Binding(ref imported) => (from.clone(), Some(imported.clone())),
+                IndirectExport::Namespace => (from.clone(), None),
+            });
+        (local, indirect, module.star_exports.clone())
+    };
+    if let Some(local) = local {
+        return Some(ResolvedBinding::Binding(key.to_string(), local));
+    }
+    match indirect {
+        Some((from, Some(imported))) => return resolve_export(interpreter, &from, &imported, resolve_set),
+        Some((from, None)) => return Some(ResolvedBinding::Namespace(from)),
+        None => (),
+    }
+    // A default export cannot be provided by export *
+    if name == "default" {
+        return None;
+    }
+    let mut resolution: Option<ResolvedBinding> = None;
+    for from in star_exports.iter() {
+        match (resolve_export(interpreter, from, name, resolve_set), &resolution) {
+            (None, _) => (),
+            (Some(found), None) => resolution = Some(found),
+            // The same name from two different places is ambiguous, so it is not exported
+            (Some(ResolvedBinding::Binding(ref k1, ref n1)), Some(ResolvedBinding::Binding(ref k2, ref n2)))
+                if k1 == k2 && n1 == n2 => {}
+            (Some(ResolvedBinding::Namespace(ref k1)), Some(ResolvedBinding::Namespace(ref k2)))
+                if k1 == k2 => {}
+            _ => return None,
+        }
+    }
+    resolution
+}
+
+/// All the names a module exports
+/// https://tc39.es/ecma262/#sec-getexportednames
+fn exported_names(interpreter: &mut Interpreter, key: &str, visited: &mut Vec<String>) -> Vec<String> {
+    if visited.iter().any(|k| k == key) {
+        return Vec::new();
+    }
+    visited.push(key.to_string());
+    let (mut names, star_exports) = {
+        let module = interpreter.modules().get(key);
+        let mut names: Vec<String> = module.local_exports.iter().map(|(name, _)| name.clone()).collect();
+        names.extend(module.indirect_exports.iter().map(|(name, _, _)| name.clone()));
+        (names, module.star_exports.clone())
+    };
+    for from in star_exports.iter() {
+        for name in exported_names(interpreter, from, visited) {
+            if name != "default" && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Bind the imports of a module and the modules it depends on
+/// https://tc39.es/ecma262/#sec-moduledeclarationlinking
+pub fn link_module(interpreter: &mut Interpreter, key: &str) -> Result<(), Value> {
+    match interpreter.modules().get(key).status {
+        ModuleStatus::Unlinked => (),
+        _ => return Ok(()),
+    }
+    interpreter.modules().get_mut(key).status = ModuleStatus::Linking;
+    let requested = interpreter.modules().get(key).requested.clone();
+    for from in requested.iter() {
+        link_module(interpreter, from)?;
+    }
+    let imports: Vec<(ImportSpecifier, String)> = interpreter
+        .modules()
+        .get(key)
+        .imports
+        .iter()
+        .map(|entry| (entry.specifier.clone(), entry.from.clone()))
+        .collect();
+    for (specifier, from) in imports {
+        let local = specifier.local_name().to_string();
+        let resolution = match specifier {
+            ImportSpecifier::Namespace(_) => Some(ResolvedBinding::Namespace(from.clone())),
+            ImportSpecifier::Named(ref imported, _) => {
+                resolve_export(interpreter, &from, imported, &mut Vec::new())
+            }
+        };
+        match resolution {
+            Some(ResolvedBinding::Binding(module, name)) => {
+                let binding = ImportBinding {
+                    module: interpreter.modules().get(&module).environment.clone(),
+                    name,
+                };
+                let module = interpreter.modules().get(key);
+                module.import_bindings.borrow_mut().insert(local, binding);
+            }
+            Some(ResolvedBinding::Namespace(module)) => {
+                let namespace = get_namespace(interpreter, &module);
+                let env = interpreter.modules().get(key).environment.clone();
+                let bound = env.borrow_mut().create_immutable_binding(local.clone(), true);
+                let bound = bound.and_then(|()| env.borrow_mut().initialize_binding(local, namespace));
+                if let Err(err) = bound {
+                    interpreter.modules().get_mut(key).status = ModuleStatus::Unlinked;
+                    return Err(interpreter.environment_error(err));
+                }
+            }
+            None => {
+                interpreter.modules().get_mut(key).status = ModuleStatus::Unlinked;
+                let imported = match specifier {
+                    ImportSpecifier::Named(ref imported, _) => imported.clone(),
+                    ImportSpecifier::Namespace(_) => "*".to_string(),
+                };
+                let message = format!("The module '{}' does not provide an export named '{}'", from, imported);
+                return Err(interpreter.syntax_error(message));
+            }
+        }
+    }
+    interpreter.modules().get_mut(key).status = ModuleStatus::Linked;
+    Ok(())
+}
+
+/// Run a linked module after the modules it depends on, returning a promise settled once it has finished.
+/// A module with a top level `await` finishes later, from promise jobs, and so do the modules importing it.
+/// A module which is already being evaluated is part of a cycle, so an already fulfilled promise is returned for it.
+/// https://tc39.es/ecma262/#sec-moduleevaluation
+pub fn evaluate_module(interpreter: &mut Interpreter, key: &str) -> ResultValue {
+    match interpreter.modules().get(key).status {
+        ModuleStatus::Linked => (),
+        ModuleStatus::Evaluating => return promise::promise_resolve(Gc::new(ValueData::Undefined), interpreter),
+        _ => return Ok(interpreter.modules().get(key).evaluation.clone().unwrap()),
+    }
+    let capability = promise::new_promise_capability(interpreter);
+    let evaluation = capability.get_field_slice("promise");
+    {
+        let module = interpreter.modules().get_mut(key);
+        module.status = ModuleStatus::Evaluating;
+        module.evaluation = Some(evaluation.clone());
+    }
+    let requested = interpreter.modules().get(key).requested.clone();
+    let mut dependencies = Vec::new();
+    for from in requested.iter() {
+        dependencies.push(evaluate_module(interpreter, from)?);
+    }
+    let (environment, body) = {
+        let module = interpreter.modules().get(key);
+        (module.environment.clone(), module.body.clone())
+    };
+    let context = AsyncContext {
+        capability,
+        frame: StackFrame::new(None, key),
+        module: Some(key.to_string()),
+    };
+    let finished = dependencies
+        .iter()
+        .all(|dependency| matches!(promise::promise_state(dependency), PromiseState::Fulfilled(_)));
+    if finished {
+        interpreter.run_async_body(environment, &body, context)?;
+    } else {
+        // Wait for every dependency, the body is never run if one of them is rejected
+        let global = interpreter.get_global_object();
+        let dependencies = array::new_array(&global, dependencies);
+        let undefined = Gc::new(ValueData::Undefined);
+        let awaited = promise::all(undefined.clone(), undefined, vec![dependencies], interpreter)?;
+        interpreter.run_async_body_after(awaited, environment, &body, context)?;
+    }
+    interpreter.modules().get_mut(key).status = ModuleStatus::Evaluated;
+    Ok(evaluation)
+}
+
+/// Load, link and evaluate a module, returning a promise of its namespace object, which is fulfilled once the
+/// module has finished evaluating. Errors loading or linking the module are returned straight away
+pub fn import_module(interpreter: &mut Interpreter, specifier: &str, referrer: Option<&str>) -> ResultValue {
+    let key = load_module(interpreter, specifier, referrer)?;
+    link_module(interpreter, &key)?;
+    let evaluation = evaluate_module(interpreter, &key)?;
+    let on_fulfilled = to_value(evaluated_namespace as NativeFunctionData);
+    on_fulfilled.set_field_slice("Module", to_value(key));
+    let capability = promise::new_promise_capability(interpreter);
+    let undefined = Gc::new(ValueData::Undefined);
+    promise::perform_promise_then(&evaluation, on_fulfilled, undefined, capability.clone(), interpreter);
+    Ok(capability.get_field_slice("promise"))
+}
+
+/// Get the namespace object of a module once it has finished evaluating
+fn evaluated_namespace(_: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let key = f.get_field_slice("Module").to_string();
+    Ok(get_namespace(interpreter, &key))
+}
+
+/// Get the namespace object of a module, whose properties are getters for the live exported bindings
+/// https://tc39.es/ecma262/#sec-module-namespace-objects
+pub fn get_namespace(interpreter: &mut Interpreter, key: &str) -> Value {
+    if let Some(ref namespace) = interpreter.modules().get(key).namespace {
+        return namespace.clone();
+    }
+    let namespace = ValueData::new_obj(None);
+    let mut names = exported_names(interpreter, key, &mut Vec::new());
+    names.sort();
+    for name in names {
+        let getter = to_value(namespace_getter as NativeFunctionData);
+        getter.set_field_slice("Module", to_value(key.to_string()));
+        getter.set_field_slice("Name", to_value(name.clone()));
+        let mut prop = Property::new(Gc::new(ValueData::Undefined));
+        prop.enumerable = true;
+        prop.get = getter;
+        namespace.set_prop(name, prop);
+    }
+    namespace.set_field(Symbol::well_known(WellKnownSymbol::ToStringTag), to_value("Module"));
+    interpreter.modules().get_mut(key).namespace = Some(namespace.clone());
+    namespace
+}
+
+/// The getter of a namespace object property, reading the exported binding it is made for
+fn namespace_getter(_: Value, f: Value, _: Vec<Value>, interpreter: &mut Interpreter) -> ResultValue {
+    let key = f.get_field_slice("Module").to_string();
+    let name = f.get_field_slice("Name").to_string();
+    match resolve_export(interpreter, &key, &name, &mut Vec::new()) {
+        Some(ResolvedBinding::Binding(module, name)) => {
+            let env = interpreter.modules().get(&module).environment.clone();
+            let env = env.borrow();
+            if env.has_binding(&name) {
+                env.get_binding_value(name, true).map_err(|err| interpreter.environment_error(err))
+            } else {
+                Err(interpreter.reference_error(format!("{} is not initialized", name)))
+            }
+        }
+        Some(ResolvedBinding::Namespace(module)) => Ok(get_namespace(interpreter, &module)),
+        None => Ok(Gc::new(ValueData::Undefined)),
+    }
+}
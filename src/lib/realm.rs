@@ -0,0 +1,132 @@
+//! # Realms
+//!
+//! https://tc39.es/ecma262/#sec-code-realms
+//!
+//! A realm is a global object with its own set of built-in objects, like `Object.prototype` and `Array.prototype`,
+//! and the global environment scripts in it run in. An interpreter can have several realms, which share its
+//! job queue, timers and modules but none of their objects, like iframes on a web page.
+//! Code runs in the realm its function was created in, so objects passed between realms keep the prototypes
+//! of the realm they were made in: an array from another realm is not an `instanceof Array` here.
+
+use crate::environment::lexical_environment::{new_global_environment, Environment};
+use crate::js::object::{PropertyKey, INSTANCE_PROTOTYPE, PROTOTYPE};
+use crate::js::value::{Value, ValueData};
+use crate::js::{
+    array, array_buffer, bigint, boolean, console, data_view, date, error, function, global, json, map, math, number,
+    object, promise, proxy, reflect, regexp, set, string, symbol, timers, typed_array, weak,
+};
+use gc::Gc;
+
+/// A global object and global environment, with the intrinsic objects they started with
+#[derive(Clone)]
+pub struct Realm {
+    /// The global object, whose properties are the built-in objects
+    global_object: Value,
+    /// The environment at the top level of scripts run in this realm
+    global_env: Environment,
+}
+
+impl Realm {
+    /// Create a realm with a new global object and its own copies of the built-in objects
+    /// https://tc39.es/ecma262/#sec-initializehostdefinedrealm
+    pub fn new() -> Realm {
+        let global = ValueData::new_obj(None);
+        object::init(&global);
+        // The global object is an ordinary object, which inherits from this realm's `Object.prototype`
+        let object_prototype = global.get_field_slice("Object").get_field_slice(PROTOTYPE);
+        global.set_field_slice(INSTANCE_PROTOTYPE, object_prototype);
+        error::init(&global);
+        symbol::init(&global);
+        bigint::init(&global);
+        number::init(&global);
+        boolean::init(&global);
+        global::init(&global);
+        console::init(&global);
+        math::init(&global);
+        date::init(&global);
+        array::init(&global);
+        function::init(&global);
+        array_buffer::init(&global);
+        typed_array::init(&global);
+        data_view::init(&global);
+        json::init(&global);
+        string::init(&global);
+        regexp::init(&global);
+        promise::init(&global);
+        map::init(&global);
+        set::init(&global);
+        weak::init(&global);
+        proxy::init(&global);
+        reflect::init(&global);
+        timers::init(&global);
+        function::init_builtin_functions(&global);
+        object::hide_builtin_properties(&global);
+        record_intrinsics(&global);
+        Realm {
+            global_env: new_global_environment(global.clone(), global.clone()),
+            global_object: global,
+        }
+    }
+
+    /// Get the realm a global environment belongs to
+    pub fn from_global_environment(global_env: Environment) -> Realm {
+        let global_object = global_env
+            .borrow()
+            .get_global_object()
+            .expect("a global environment has a global object");
+        Realm {
+            global_object,
+            global_env,
+        }
+    }
+
+    /// The global object of the realm, which values can be passed to other realms through
+    pub fn global_object(&self) -> Value {
+        self.global_object.clone()
+    }
+
+    /// The global environment of the realm
+    pub fn global_environment(&self) -> Environment {
+        self.global_env.clone()
+    }
+
+    /// Get one of the built-in objects the realm was created with, by its path from the global object,
+    /// like `"Array.prototype"`. This is the original object even if scripts have replaced the global binding
+    /// https://tc39.es/ecma262/#sec-well-known-intrinsic-objects
+    pub fn intrinsic(&self, name: &str) -> Value {
+        self.global_object.get_private_field_slice(&format!("%{}%", name))
+    }
+}
+
+impl Default for Realm {
+    fn default() -> Self {
+        Realm::new()
+    }
+}
+
+/// Realms are the same if they have the same global object
+impl PartialEq for Realm {
+    fn eq(&self, other: &Realm) -> bool {
+        Gc::ptr_eq(&self.global_object, &other.global_object)
+    }
+}
+
+/// Keep the built-in objects, and the prototypes of built-in constructors, in private fields of the global object,
+/// named like `%Array%` and `%Array.prototype%`
+fn record_intrinsics(global: &Value) {
+    for key in global.own_keys() {
+        let name = match key {
+            PropertyKey::String(ref name) => name.clone(),
+            PropertyKey::Symbol(_) => continue,
+        };
+        let value = global.get_field(key);
+        if (!value.is_object() && !value.is_function()) || Gc::ptr_eq(&value, global) {
+            continue;
+        }
+        let prototype = value.get_field_slice(PROTOTYPE);
+        if value.is_function() && (prototype.is_object() || prototype.is_function()) {
+            global.set_private_field_slice(&format!("%{}.prototype%", name), prototype);
+        }
+        global.set_private_field_slice(&format!("%{}%", name), value);
+    }
+}
@@ -0,0 +1,353 @@
+//! Matching a parsed pattern against a string by backtracking
+//!
+//! https://tc39.es/ecma262/#sec-pattern-semantics
+//!
+//! Each node is matched with a continuation, which is the rest of the pattern.
+//! A node tries each way it can match in order, calling the continuation for each,
+//! and backtracks to its next option when the continuation fails.
+//!
+//! This recurses for each node matched, so a match gives up once it is `MAX_DEPTH` nodes deep
+//! rather than overflowing the stack. Quantifiers of characters and fixed sequences of them,
+//! like `a*`, `[^,]+` or `(?:\r\n)*`, loop instead, which keeps common patterns shallow however
+//! long the input is.
+
+use crate::regex::parser::{Class, Node};
+use crate::regex::Flags;
+
+/// The span of each capture group, in code units, where group 0 is the whole match
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+/// The most nodes a match can be nested in before it gives up
+pub const MAX_DEPTH: usize = 500;
+
+/// The state of a match in progress
+pub struct State {
+    pub captures: Captures,
+    /// The number of nodes being matched, each of which is a call on the stack
+    pub depth: usize,
+    /// Set when the match went deeper than `MAX_DEPTH`, after which nothing matches
+    pub too_deep: bool,
+}
+
+/// Matches nodes against an input string of UTF-16 code units
+pub struct Matcher<'a> {
+    pub input: &'a [u16],
+    pub flags: &'a Flags,
+}
+
+/// A continuation, which tries to match the rest of the pattern from a position
+type Continuation<'k> = dyn FnMut(usize, &mut State) -> bool + 'k;
+
+/// Returns true if the node matches a sequence of characters in only one way, without any captures,
+/// so it can be repeated without backtracking into an iteration
+fn is_simple(node: &Node) -> bool {
+    match *node {
+        Node::Char(_) | Node::Any | Node::Class(_) => true,
+        Node::Concat(ref nodes) => !nodes.is_empty() && nodes.iter().all(is_simple),
+        _ => false,
+    }
+}
+
+/// Returns true if the code unit ends a line
+fn is_line_terminator(c: u32) -> bool {
+    c == 0x0A || c == 0x0D || c == 0x2028 || c == 0x2029
+}
+
+/// Returns true if the code unit is matched by `\w`
+fn is_word_char(c: u32) -> bool {
+    c < 128 && ((c as u8).is_ascii_alphanumeric() || c == u32::from(b'_'))
+}
+
+/// Map a character to the form it is compared in when ignoring case
+/// https://tc39.es/ecma262/#sec-runtime-semantics-canonicalize-ch
+pub fn canonicalize(c: u32, unicode: bool) -> u32 {
+    let ch = match std::char::from_u32(c) {
+        Some(ch) => ch,
+        None => return c,
+    };
+    if unicode {
+        // Simple case folding, which is close to lower casing a single character
+        let mut lower = ch.to_lowercase();
+        return match (lower.next(), lower.next()) {
+            (Some(folded), None) => folded as u32,
+            _ => c,
+        };
+    }
+    let mut upper = ch.to_uppercase();
+    match (upper.next(), upper.next()) {
+        // Non-ASCII characters can't become ASCII characters
+        (Some(mapped), None) if !(c >= 128 && (mapped as u32) < 128) => mapped as u32,
+        _ => c,
+    }
+}
+
+impl<'a> Matcher<'a> {
+    /// Read the character at a position, which is a code point with the `u` flag, returning it and its length in code units
+    fn char_at(&self, pos: usize) -> Option<(u32, usize)> {
+        let first = u32::from(*self.input.get(pos)?);
+        if self.flags.unicode && (0xD800..0xDC00).contains(&first) {
+            if let Some(&second) = self.input.get(pos + 1) {
+                let second = u32::from(second);
+                if (0xDC00..0xE000).contains(&second) {
+                    return Some((0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00), 2));
+                }
+            }
+        }
+        Some((first, 1))
+    }
+
+    fn class_matches(&self, class: &Class, c: u32) -> bool {
+        let mut found = class.contains(c);
+        if !found && self.flags.ignore_case {
+            let canonical = canonicalize(c, self.flags.unicode);
+            found = class.ranges.iter().any(|&(start, end)| {
+                // Small ranges are checked exhaustively, as case mapping isn't monotonic
+                if end - start > 0x400 {
+                    return false;
+                }
+                (start..=end).any(|member| canonicalize(member, self.flags.unicode) == canonical)
+            });
+        }
+        found != class.negated
+    }
+
+    fn is_word_at(&self, pos: usize) -> bool {
+        self.input.get(pos).is_some_and(|&c| is_word_char(u32::from(c)))
+    }
+
+    /// Match a node which `is_simple`, returning the position after it
+    fn match_simple(&self, node: &Node, pos: usize) -> Option<usize> {
+        if let Node::Concat(ref nodes) = *node {
+            return nodes.iter().try_fold(pos, |pos, node| self.match_simple(node, pos));
+        }
+        let (c, length) = self.char_at(pos)?;
+        let matches = match *node {
+            Node::Char(expected) => {
+                let unicode = self.flags.unicode;
+                c == expected || (self.flags.ignore_case && canonicalize(c, unicode) == canonicalize(expected, unicode))
+            }
+            Node::Any => self.flags.dot_all || !is_line_terminator(c),
+            Node::Class(ref class) => self.class_matches(class, c),
+            _ => unreachable!("only simple nodes are matched without a continuation"),
+        };
+        if matches {
+            Some(pos + length)
+        } else {
+            None
+        }
+    }
+
+    /// Match a node at a position, then call the continuation with the position after it
+    pub fn match_node(&self, node: &Node, pos: usize, state: &mut State, k: &mut Continuation) -> bool {
+        if state.too_deep {
+            return false;
+        }
+        if state.depth == MAX_DEPTH {
+            state.too_deep = true;
+            return false;
+        }
+        state.depth += 1;
+        let matched = self.match_node_at_depth(node, pos, state, k);
+        state.depth -= 1;
+        matched
+    }
+
+    /// The larger cases have their own methods, which keeps the stack frame of each level of recursion small
+    fn match_node_at_depth(&self, node: &Node, pos: usize, state: &mut State, k: &mut Continuation) -> bool {
+        match *node {
+            Node::Empty => k(pos, state),
+            Node::Char(_) | Node::Any | Node::Class(_) => match self.match_simple(node, pos) {
+                Some(next) => k(next, state),
+                None => false,
+            },
+            Node::LineStart => {
+                let at_start = pos == 0
+                    || (self.flags.multiline && is_line_terminator(u32::from(self.input[pos - 1])));
+                at_start && k(pos, state)
+            }
+            Node::LineEnd => {
+                let at_end = pos == self.input.len()
+                    || (self.flags.multiline && is_line_terminator(u32::from(self.input[pos])));
+                at_end && k(pos, state)
+            }
+            Node::WordBoundary(negate) => {
+                let before = pos > 0 && self.is_word_at(pos - 1);
+                let at_boundary = before != self.is_word_at(pos);
+                at_boundary != negate && k(pos, state)
+            }
+            Node::Group(ref inner, index) => self.match_node(inner, pos, state, &mut |end, state: &mut State| {
+                let previous = state.captures[index];
+                state.captures[index] = Some((pos, end));
+                if k(end, state) {
+                    return true;
+                }
+                state.captures[index] = previous;
+                false
+            }),
+            Node::Concat(ref nodes) => self.match_sequence(nodes, pos, state, k),
+            Node::Alternate(ref alternatives) => alternatives
+                .iter()
+                .any(|alternative| self.match_node(alternative, pos, state, k)),
+            Node::Repeat { ref node, min, max, greedy, groups } => {
+                self.match_repeat(node, min, max, greedy, groups, 0, pos, state, k)
+            }
+            Node::BackReference(index) => self.match_back_reference(index, pos, state, k),
+            Node::NamedBackReference(_) => unreachable!("named backreferences are resolved by the parser"),
+            Node::Look { ref node, behind, negate } => self.match_look(node, behind, negate, pos, state, k),
+        }
+    }
+
+    /// Match the same characters as a capture group matched
+    fn match_back_reference(&self, index: usize, pos: usize, state: &mut State, k: &mut Continuation) -> bool {
+        match state.captures.get(index).cloned().unwrap_or(None) {
+            // A reference to a group which hasn't matched matches the empty string
+            None => k(pos, state),
+            Some((start, end)) => {
+                let length = end - start;
+                if pos + length > self.input.len() {
+                    return false;
+                }
+                let unicode = self.flags.unicode;
+                let matches = (0..length).all(|offset| {
+                    let a = u32::from(self.input[start + offset]);
+                    let b = u32::from(self.input[pos + offset]);
+                    a == b || (self.flags.ignore_case && canonicalize(a, unicode) == canonicalize(b, unicode))
+                });
+                matches && k(pos + length, state)
+            }
+        }
+    }
+
+    /// Match a lookahead or lookbehind assertion, which doesn't consume any characters
+    fn match_look(&self, node: &Node, behind: bool, negate: bool, pos: usize, state: &mut State, k: &mut Continuation) -> bool {
+        let saved = state.captures.clone();
+        let found = if behind {
+            // Try the closest start first, so the assertion matches as little as it can
+            (0..=pos)
+                .rev()
+                .any(|start| self.match_node(node, start, state, &mut |end, _: &mut State| end == pos))
+        } else {
+            self.match_node(node, pos, state, &mut |_, _: &mut State| true)
+        };
+        if negate {
+            // Captures in a negative assertion are always undefined afterwards
+            state.captures = saved;
+            return !found && k(pos, state);
+        }
+        if found && k(pos, state) {
+            return true;
+        }
+        state.captures = saved;
+        false
+    }
+
+    fn match_sequence(&self, nodes: &[Node], pos: usize, state: &mut State, k: &mut Continuation) -> bool {
+        match nodes.split_first() {
+            None => k(pos, state),
+            Some((first, rest)) => self.match_node(first, pos, state, &mut |next, state: &mut State| {
+                self.match_sequence(rest, next, state, k)
+            }),
+        }
+    }
+
+    /// Match a quantified node, where `count` is the number of iterations matched so far
+    /// https://tc39.es/ecma262/#sec-runtime-semantics-repeatmatcher-abstract-operation
+    #[allow(clippy::too_many_arguments)]
+    fn match_repeat(
+        &self,
+        node: &Node,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+        groups: (usize, usize),
+        count: u32,
+        pos: usize,
+        state: &mut State,
+        k: &mut Continuation,
+    ) -> bool {
+        if max == Some(count) {
+            return k(pos, state);
+        }
+        if is_simple(node) {
+            return self.match_simple_repeat(node, min, max, greedy, pos, state, k);
+        }
+        let iterate = |state: &mut State, k: &mut Continuation| -> bool {
+            // The captures inside the quantified node are cleared before each iteration
+            let (first_group, last_group) = groups;
+            let saved: Captures = state.captures[first_group..=last_group.max(first_group - 1)].to_vec();
+            for index in first_group..=last_group {
+                state.captures[index] = None;
+            }
+            let matched = self.match_node(node, pos, state, &mut |next, state: &mut State| {
+                // An iteration which matches nothing once the minimum is reached would loop forever
+                if next == pos && count >= min {
+                    return false;
+                }
+                self.match_repeat(node, min, max, greedy, groups, count.saturating_add(1), next, state, k)
+            });
+            if !matched {
+                state.captures.splice(first_group..first_group + saved.len(), saved);
+            }
+            matched
+        };
+        if count < min {
+            return iterate(state, k);
+        }
+        // A greedy quantifier tries another iteration before the rest of the pattern, a lazy one after it
+        if !greedy && k(pos, state) {
+            return true;
+        }
+        if iterate(state, k) {
+            return true;
+        }
+        greedy && k(pos, state)
+    }
+
+    /// Match a quantified node which `is_simple`, looping over the iterations instead of recursing.
+    /// A greedy quantifier keeps the position after each iteration, to backtrack through from the longest match.
+    #[allow(clippy::too_many_arguments)]
+    fn match_simple_repeat(
+        &self,
+        node: &Node,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+        pos: usize,
+        state: &mut State,
+        k: &mut Continuation,
+    ) -> bool {
+        let min = min as usize;
+        let max = max.map_or(usize::MAX, |max| max as usize);
+        if greedy {
+            let mut positions = vec![pos];
+            while positions.len() <= max {
+                match self.match_simple(node, positions[positions.len() - 1]) {
+                    Some(next) => positions.push(next),
+                    None => break,
+                }
+            }
+            while positions.len() > min {
+                let end = positions.pop().expect("there is a position for each iteration");
+                if k(end, state) {
+                    return true;
+                }
+            }
+            return false;
+        }
+        let mut end = pos;
+        let mut count = 0;
+        loop {
+            if count >= min && k(end, state) {
+                return true;
+            }
+            if count == max {
+                return false;
+            }
+            match self.match_simple(node, end) {
+                Some(next) => end = next,
+                None => return false,
+            }
+            count += 1;
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! # Regular expressions
+//!
+//! https://tc39.es/ecma262/#sec-regexp-regular-expression-objects
+//!
+//! A backtracking regular expression engine with ECMAScript semantics.
+//! Patterns are parsed into a tree of nodes, which is matched directly against
+//! strings of UTF-16 code units, so indices are the same as the indices of Javascript strings.
+
+pub mod matcher;
+pub mod parser;
+
+use self::matcher::{Captures, Matcher, State};
+use self::parser::{Node, Pattern};
+
+/// The flags of a regular expression
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Flags {
+    /// `d`, which adds the indices of each capture to match results
+    pub has_indices: bool,
+    /// `g`, which finds every match
+    pub global: bool,
+    /// `i`
+    pub ignore_case: bool,
+    /// `m`, where `^` and `$` match at line terminators
+    pub multiline: bool,
+    /// `s`, where `.` matches line terminators
+    pub dot_all: bool,
+    /// `u`, which matches code points instead of code units
+    pub unicode: bool,
+    /// `y`, which only matches at `lastIndex`
+    pub sticky: bool,
+}
+
+impl Flags {
+    /// Parse flags like `"gi"`, which may not repeat a flag
+    pub fn parse(flags: &str) -> Result<Flags, String> {
+        let mut result = Flags::default();
+        for flag in flags.chars() {
+            let field = match flag {
+                'd' => &mut result.has_indices,
+                'g' => &mut result.global,
+                'i' => &mut result.ignore_case,
+                'm' => &mut result.multiline,
+                's' => &mut result.dot_all,
+                'u' => &mut result.unicode,
+                'y' => &mut result.sticky,
+                _ => return Err(format!("Invalid flags supplied to RegExp constructor '{}'", flags)),
+            };
+            if *field {
+                return Err(format!("Invalid flags supplied to RegExp constructor '{}'", flags));
+            }
+            *field = true;
+        }
+        Ok(result)
+    }
+}
+
+impl std::fmt::Display for Flags {
+    /// Write the flags in their canonical order
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let flags = [
+            (self.has_indices, 'd'),
+            (self.global, 'g'),
+            (self.ignore_case, 'i'),
+            (self.multiline, 'm'),
+            (self.dot_all, 's'),
+            (self.unicode, 'u'),
+            (self.sticky, 'y'),
+        ];
+        for &(set, flag) in flags.iter() {
+            if set {
+                write!(f, "{}", flag)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A compiled regular expression
+#[derive(Debug)]
+pub struct Regex {
+    node: Node,
+    pub flags: Flags,
+    /// The number of capturing groups, not counting the whole match
+    pub capture_count: usize,
+    /// The names of named groups, with their indices, in the order they appear
+    pub group_names: Vec<(String, usize)>,
+}
+
+impl Regex {
+    /// Compile a pattern with flags, returning the message of a `SyntaxError` if either is invalid
+    pub fn new(pattern: &str, flags: &str) -> Result<Regex, String> {
+        let parsed_flags = Flags::parse(flags)?;
+        let Pattern { node, capture_count, group_names } = parser::parse(pattern, &parsed_flags)
+            .map_err(|message| format!("Invalid regular expression: /{}/{}: {}", pattern, flags, message))?;
+        Ok(Regex {
+            node,
+            flags: parsed_flags,
+            capture_count,
+            group_names,
+        })
+    }
+
+    /// Match the pattern starting exactly at `start`, returning the span of each capture group,
+    /// or the message of a `RangeError` if matching backtracks too deeply
+    pub fn match_at(&self, input: &[u16], start: usize) -> Result<Option<Captures>, String> {
+        if start > input.len() {
+            return Ok(None);
+        }
+        let matcher = Matcher { input, flags: &self.flags };
+        let mut state = State {
+            captures: vec![None; self.capture_count + 1],
+            depth: 0,
+            too_deep: false,
+        };
+        let mut end = None;
+        if matcher.match_node(&self.node, start, &mut state, &mut |pos, state: &mut State| {
+            // Giving up on a branch that went too deep can't leave a shorter match
+            if state.too_deep {
+                return false;
+            }
+            end = Some(pos);
+            true
+        }) {
+            state.captures[0] = Some((start, end.unwrap()));
+            return Ok(Some(state.captures));
+        }
+        if state.too_deep {
+            return Err("Maximum call stack size exceeded".to_string());
+        }
+        Ok(None)
+    }
+
+    /// Find the first match at or after `start`.
+    /// With the `u` flag, matches never start in the middle of a surrogate pair.
+    pub fn find_from(&self, input: &[u16], start: usize) -> Result<Option<Captures>, String> {
+        let mut pos = start;
+        while pos <= input.len() {
+            if let Some(captures) = self.match_at(input, pos)? {
+                return Ok(Some(captures));
+            }
+            let is_pair = self.flags.unicode
+                && pos + 1 < input.len()
+                && (0xD800..0xDC00).contains(&input[pos])
+                && (0xDC00..0xE000).contains(&input[pos + 1]);
+            pos += if is_pair { 2 } else { 1 };
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, flags: &str, input: &str) -> Option<Vec<Option<String>>> {
+        let units: Vec<u16> = input.encode_utf16().collect();
+        let regex = Regex::new(pattern, flags).unwrap();
+        regex.find_from(&units, 0).unwrap().map(|captures| {
+            captures
+                .into_iter()
+                .map(|span| span.map(|(start, end)| String::from_utf16_lossy(&units[start..end])))
+                .collect()
+        })
+    }
+
+    fn found(pattern: &str, flags: &str, input: &str) -> String {
+        match find(pattern, flags, input) {
+            Some(captures) => captures[0].clone().unwrap(),
+            None => "null".to_string(),
+        }
+    }
+
+    #[test]
+    fn check_flags() {
+        assert_eq!(Flags::parse("yg").unwrap().to_string(), "gy");
+        assert!(Flags::parse("gg").is_err());
+        assert!(Flags::parse("x").is_err());
+    }
+
+    #[test]
+    fn check_syntax_errors() {
+        assert!(Regex::new("(", "").is_err());
+        assert!(Regex::new("a**", "").is_err());
+        assert!(Regex::new("[b-a]", "").is_err());
+        assert!(Regex::new("\\k<x>(?<y>a)", "").is_err());
+        assert!(Regex::new("\\a", "u").is_err());
+        assert!(Regex::new("\\a", "").is_ok());
+        assert!(Regex::new("{", "").is_ok());
+    }
+
+    #[test]
+    fn check_quantifiers() {
+        assert_eq!(found("a+", "", "baaac"), "aaa");
+        assert_eq!(found("a+?", "", "baaac"), "a");
+        assert_eq!(found("a{2,3}", "", "aaaa"), "aaa");
+        assert_eq!(found("(a*)*b", "", "aaab"), "aaab");
+        assert_eq!(found("(?:)*", "", "a"), "");
+        assert_eq!(found("(?:ab)*ab", "", "ababab"), "ababab");
+        assert_eq!(found("(?:ab)+?c", "", "ababc"), "ababc");
+        assert_eq!(found("a{2,3}?", "", "aaaa"), "aa");
+        assert_eq!(found("x[a-c]{2}", "", "xab xabc"), "xab");
+        assert_eq!(found("^.{2}$", "u", "😀😀"), "😀😀");
+        assert_eq!(find("(a|b)*c", "", "abc").unwrap()[1], Some("b".to_string()));
+        assert_eq!(find("(z)((a+)?(b+)?(c))*", "", "zaacbbbcac").unwrap()[4], None);
+    }
+
+    #[test]
+    fn check_assertions() {
+        assert_eq!(found("^b", "m", "a\nb"), "b");
+        assert_eq!(found("^b", "", "a\nb"), "null");
+        assert_eq!(found("\\bfoo\\b", "", "a foo b"), "foo");
+        assert_eq!(found("a(?=b)", "", "acab"), "a");
+        assert_eq!(found("a(?!b)", "", "abac"), "a");
+        assert_eq!(found("(?<=\\$)\\d+", "", "cost: $42"), "42");
+        assert_eq!(found("(?<!\\$)\\b\\d+", "", "$4 and 5"), "5");
+    }
+
+    #[test]
+    fn check_backreferences() {
+        assert_eq!(found("(a)\\1", "", "aa"), "aa");
+        assert_eq!(found("(?<q>['\"]).*?\\k<q>", "", "say 'hi'"), "'hi'");
+        assert_eq!(found("(a)\\1", "i", "aA"), "aA");
+    }
+
+    #[test]
+    fn check_classes_and_case() {
+        assert_eq!(found("[a-c]+", "", "xxbcay"), "bca");
+        assert_eq!(found("[^a-c]+", "", "abxyc"), "xy");
+        assert_eq!(found("\\d+", "", "ab123"), "123");
+        assert_eq!(found("[\\w-]+", "", "!a-b!"), "a-b");
+        assert_eq!(found("HELLO", "i", "say hello"), "hello");
+        assert_eq!(found("a.c", "", "a\nc"), "null");
+        assert_eq!(found("a.c", "s", "a\nc"), "a\nc");
+    }
+
+    #[test]
+    fn check_unicode() {
+        assert_eq!(found("^.$", "", "😀"), "null");
+        assert_eq!(found("^.$", "u", "😀"), "😀");
+        assert_eq!(found("\\u{1F600}", "u", "😀"), "😀");
+        assert_eq!(found("\\p{Lu}+", "u", "abCDe"), "CD");
+    }
+}
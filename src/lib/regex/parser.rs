@@ -0,0 +1,776 @@
+//! Parsing regular expression patterns into a tree of nodes
+//!
+//! https://tc39.es/ecma262/#sec-patterns
+//!
+//! Without the `u` flag, the pattern is read as UTF-16 code units and the lenient
+//! syntax of Annex B is allowed, e.g. `\a` is `a` and `{` on its own is a literal.
+
+use crate::regex::Flags;
+
+/// A set of code point ranges, like `[a-z\d]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Class {
+    /// Inclusive ranges of code points
+    pub ranges: Vec<(u32, u32)>,
+    /// True if the class matches everything except its ranges
+    pub negated: bool,
+}
+
+impl Class {
+    /// Returns true if the code point is in one of the ranges, ignoring negation
+    pub fn contains(&self, c: u32) -> bool {
+        self.ranges.iter().any(|&(start, end)| start <= c && c <= end)
+    }
+}
+
+/// A node of a parsed pattern
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    /// Matches the empty string
+    Empty,
+    /// A code point, or a code unit without the `u` flag
+    Char(u32),
+    /// `.`
+    Any,
+    /// A character class or class escape like `\d`
+    Class(Class),
+    /// `^`
+    LineStart,
+    /// `$`
+    LineEnd,
+    /// `\b`, or `\B` when negated
+    WordBoundary(bool),
+    /// A capturing group, with its index starting from 1
+    Group(Box<Node>, usize),
+    /// A sequence of nodes
+    Concat(Vec<Node>),
+    /// Alternatives, tried from left to right
+    Alternate(Vec<Node>),
+    /// A quantified node, which resets the captures in `groups` before each iteration
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+        groups: (usize, usize),
+    },
+    /// A backreference to a capturing group
+    BackReference(usize),
+    /// A backreference to a named group, which is resolved to a `BackReference` once every group is parsed
+    NamedBackReference(String),
+    /// A lookahead or lookbehind assertion
+    Look {
+        node: Box<Node>,
+        behind: bool,
+        negate: bool,
+    },
+}
+
+/// A parsed pattern
+#[derive(Debug)]
+pub struct Pattern {
+    /// The root node
+    pub node: Node,
+    /// The number of capturing groups
+    pub capture_count: usize,
+    /// The names of named groups, with their indices
+    pub group_names: Vec<(String, usize)>,
+}
+
+/// Parse a pattern, returning a description of the syntax error if it is invalid
+pub fn parse(pattern: &str, flags: &Flags) -> Result<Pattern, String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let (total_groups, has_named_groups) = scan_groups(&chars);
+    let mut parser = PatternParser {
+        chars,
+        pos: 0,
+        unicode: flags.unicode,
+        total_groups,
+        has_named_groups,
+        group_count: 0,
+        group_names: Vec::new(),
+        named_references: Vec::new(),
+    };
+    let mut node = parser.parse_disjunction()?;
+    if parser.pos < parser.chars.len() {
+        return Err(if parser.peek() == Some(')') {
+            "Unmatched ')'".to_string()
+        } else {
+            "Unexpected character".to_string()
+        });
+    }
+    for name in parser.named_references.iter() {
+        if !parser.group_names.iter().any(|(group, _)| group == name) {
+            return Err("Invalid named capture referenced".to_string());
+        }
+    }
+    resolve_named_references(&mut node, &parser.group_names);
+    Ok(Pattern {
+        node,
+        capture_count: parser.group_count,
+        group_names: parser.group_names,
+    })
+}
+
+/// Count the capturing groups before parsing, as backreferences can come before the group they refer to
+fn scan_groups(chars: &[char]) -> (usize, bool) {
+    let mut count = 0;
+    let mut named = false;
+    let mut in_class = false;
+    let mut index = 0;
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => index += 1,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class => {
+                if chars.get(index + 1) != Some(&'?') {
+                    count += 1;
+                } else if chars.get(index + 2) == Some(&'<')
+                    && !matches!(chars.get(index + 3), Some('=') | Some('!'))
+                {
+                    count += 1;
+                    named = true;
+                }
+            }
+            _ => (),
+        }
+        index += 1;
+    }
+    (count, named)
+}
+
+/// Replace named backreferences with references to the index of the group, now that every name is known
+fn resolve_named_references(node: &mut Node, names: &[(String, usize)]) {
+    match *node {
+        Node::NamedBackReference(ref name) => {
+            let index = names
+                .iter()
+                .find(|(group, _)| group == name)
+                .map_or(0, |&(_, index)| index);
+            *node = Node::BackReference(index);
+        }
+        Node::Group(ref mut inner, _) | Node::Look { node: ref mut inner, .. } => {
+            resolve_named_references(inner, names)
+        }
+        Node::Repeat { node: ref mut inner, .. } => resolve_named_references(inner, names),
+        Node::Concat(ref mut nodes) | Node::Alternate(ref mut nodes) => {
+            for node in nodes.iter_mut() {
+                resolve_named_references(node, names);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// The result of parsing an escape in a character class
+enum ClassAtom {
+    Char(u32),
+    Class(Class),
+}
+
+struct PatternParser {
+    chars: Vec<char>,
+    pos: usize,
+    unicode: bool,
+    /// The number of groups in the whole pattern
+    total_groups: usize,
+    has_named_groups: bool,
+    /// The number of groups opened so far
+    group_count: usize,
+    group_names: Vec<(String, usize)>,
+    named_references: Vec<String>,
+}
+
+impl PatternParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).cloned()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let matches = s.chars().enumerate().all(|(index, c)| self.peek_at(index) == Some(c));
+        if matches {
+            self.pos += s.chars().count();
+        }
+        matches
+    }
+
+    /// Make the node for a literal character, which is two code units without the `u` flag if it is outside the BMP
+    fn literal(&self, c: char) -> Node {
+        let code_point = c as u32;
+        if self.unicode || code_point < 0x10000 {
+            return Node::Char(code_point);
+        }
+        let mut units = [0; 2];
+        c.encode_utf16(&mut units);
+        Node::Concat(vec![Node::Char(u32::from(units[0])), Node::Char(u32::from(units[1]))])
+    }
+
+    fn parse_disjunction(&mut self) -> Result<Node, String> {
+        let mut alternatives = vec![self.parse_alternative()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_alternative()?);
+        }
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Node::Alternate(alternatives)
+        })
+    }
+
+    fn parse_alternative(&mut self) -> Result<Node, String> {
+        let mut terms = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            terms.push(self.parse_term()?);
+        }
+        Ok(match terms.len() {
+            0 => Node::Empty,
+            1 => terms.pop().unwrap(),
+            _ => Node::Concat(terms),
+        })
+    }
+
+    fn parse_term(&mut self) -> Result<Node, String> {
+        let groups_before = self.group_count;
+        let (atom, quantifiable) = match self.peek().unwrap() {
+            '^' => {
+                self.pos += 1;
+                (Node::LineStart, false)
+            }
+            '$' => {
+                self.pos += 1;
+                (Node::LineEnd, false)
+            }
+            '\\' if self.peek_at(1) == Some('b') => {
+                self.pos += 2;
+                (Node::WordBoundary(false), false)
+            }
+            '\\' if self.peek_at(1) == Some('B') => {
+                self.pos += 2;
+                (Node::WordBoundary(true), false)
+            }
+            '(' if self.peek_at(1) == Some('?') && matches!(self.peek_at(2), Some('=') | Some('!')) => {
+                let negate = self.peek_at(2) == Some('!');
+                self.pos += 3;
+                let node = self.parse_group_body()?;
+                let look = Node::Look { node: Box::new(node), behind: false, negate };
+                // Annex B allows quantified lookaheads
+                (look, !self.unicode)
+            }
+            '(' if self.peek_at(1) == Some('?')
+                && self.peek_at(2) == Some('<')
+                && matches!(self.peek_at(3), Some('=') | Some('!')) =>
+            {
+                let negate = self.peek_at(3) == Some('!');
+                self.pos += 4;
+                let node = self.parse_group_body()?;
+                (Node::Look { node: Box::new(node), behind: true, negate }, false)
+            }
+            _ => (self.parse_atom()?, true),
+        };
+        if !quantifiable {
+            if self.at_quantifier() {
+                return Err("Nothing to repeat".to_string());
+            }
+            return Ok(atom);
+        }
+        self.parse_quantifier(atom, (groups_before + 1, self.group_count))
+    }
+
+    /// Returns true if the next characters are a quantifier
+    fn at_quantifier(&self) -> bool {
+        match self.peek() {
+            Some('*') | Some('+') | Some('?') => true,
+            Some('{') => self.read_braced_quantifier().is_some(),
+            _ => false,
+        }
+    }
+
+    /// Read a `{n}`, `{n,}` or `{n,m}` quantifier without consuming it, returning its bounds and length
+    fn read_braced_quantifier(&self) -> Option<(u32, Option<u32>, usize)> {
+        let mut offset = 1;
+        let read_number = |offset: &mut usize| -> Option<u32> {
+            let start = *offset;
+            let mut value: u32 = 0;
+            while let Some(digit) = self.peek_at(*offset).and_then(|c| c.to_digit(10)) {
+                value = value.saturating_mul(10).saturating_add(digit);
+                *offset += 1;
+            }
+            if *offset == start { None } else { Some(value) }
+        };
+        let min = read_number(&mut offset)?;
+        let max = if self.peek_at(offset) == Some(',') {
+            offset += 1;
+            if self.peek_at(offset) == Some('}') {
+                None
+            } else {
+                Some(read_number(&mut offset)?)
+            }
+        } else {
+            Some(min)
+        };
+        if self.peek_at(offset) != Some('}') {
+            return None;
+        }
+        Some((min, max, offset + 1))
+    }
+
+    fn parse_quantifier(&mut self, atom: Node, groups: (usize, usize)) -> Result<Node, String> {
+        let (min, max) = match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                (0, None)
+            }
+            Some('+') => {
+                self.pos += 1;
+                (1, None)
+            }
+            Some('?') => {
+                self.pos += 1;
+                (0, Some(1))
+            }
+            Some('{') => match self.read_braced_quantifier() {
+                Some((min, max, length)) => {
+                    self.pos += length;
+                    if max.is_some_and(|max| max < min) {
+                        return Err("numbers out of order in {} quantifier".to_string());
+                    }
+                    (min, max)
+                }
+                None if self.unicode => return Err("Incomplete quantifier".to_string()),
+                None => return Ok(atom),
+            },
+            _ => return Ok(atom),
+        };
+        let greedy = !self.eat('?');
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+            greedy,
+            groups,
+        })
+    }
+
+    /// Parse the rest of a group after its opening, up to and including the `)`
+    fn parse_group_body(&mut self) -> Result<Node, String> {
+        let node = self.parse_disjunction()?;
+        if !self.eat(')') {
+            return Err("Unterminated group".to_string());
+        }
+        Ok(node)
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, String> {
+        let c = self.peek().unwrap();
+        self.pos += 1;
+        match c {
+            '.' => Ok(Node::Any),
+            '(' => {
+                if self.eat_str("?:") {
+                    return self.parse_group_body();
+                }
+                if self.eat_str("?<") {
+                    let name = self.parse_group_name()?;
+                    if self.group_names.iter().any(|(group, _)| *group == name) {
+                        return Err("Duplicate capture group name".to_string());
+                    }
+                    self.group_count += 1;
+                    let index = self.group_count;
+                    self.group_names.push((name, index));
+                    let node = self.parse_group_body()?;
+                    return Ok(Node::Group(Box::new(node), index));
+                }
+                if self.peek() == Some('?') {
+                    return Err("Invalid group".to_string());
+                }
+                self.group_count += 1;
+                let index = self.group_count;
+                let node = self.parse_group_body()?;
+                Ok(Node::Group(Box::new(node), index))
+            }
+            '[' => self.parse_class(),
+            '\\' => self.parse_atom_escape(),
+            '*' | '+' | '?' => Err("Nothing to repeat".to_string()),
+            '{' => {
+                self.pos -= 1;
+                if self.unicode || self.read_braced_quantifier().is_some() {
+                    return Err("Nothing to repeat".to_string());
+                }
+                self.pos += 1;
+                Ok(Node::Char('{' as u32))
+            }
+            ']' | '}' if self.unicode => Err("Lone quantifier brackets".to_string()),
+            c => Ok(self.literal(c)),
+        }
+    }
+
+    /// Parse the name of a named group or backreference, after the `<`
+    fn parse_group_name(&mut self) -> Result<String, String> {
+        let mut name = String::new();
+        loop {
+            match self.peek() {
+                Some('>') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(c) if c == '$' || c == '_' || c.is_alphanumeric() => {
+                    if name.is_empty() && c.is_numeric() {
+                        return Err("Invalid capture group name".to_string());
+                    }
+                    name.push(c);
+                    self.pos += 1;
+                }
+                _ => return Err("Invalid capture group name".to_string()),
+            }
+        }
+        if name.is_empty() {
+            return Err("Invalid capture group name".to_string());
+        }
+        Ok(name)
+    }
+
+    fn parse_atom_escape(&mut self) -> Result<Node, String> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return Err("\\ at end of pattern".to_string()),
+        };
+        // Backreferences
+        if c.is_ascii_digit() && c != '0' {
+            let start = self.pos;
+            let mut number: usize = 0;
+            while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+                number = number.saturating_mul(10).saturating_add(digit as usize);
+                self.pos += 1;
+            }
+            if number <= self.total_groups {
+                return Ok(Node::BackReference(number));
+            }
+            if self.unicode {
+                return Err("Invalid escape".to_string());
+            }
+            // Annex B: not a backreference, so it is an octal escape or an identity escape
+            self.pos = start;
+        }
+        if c == 'k' && (self.unicode || self.has_named_groups) {
+            self.pos += 1;
+            if !self.eat('<') {
+                return Err("Invalid named reference".to_string());
+            }
+            let name = self.parse_group_name()?;
+            self.named_references.push(name.clone());
+            return Ok(Node::NamedBackReference(name));
+        }
+        match self.parse_character_escape(false)? {
+            ClassAtom::Char(c) => Ok(Node::Char(c)),
+            ClassAtom::Class(class) => Ok(Node::Class(class)),
+        }
+    }
+
+    /// Parse an escape which stands for a character or a class, after the `\`
+    fn parse_character_escape(&mut self, in_class: bool) -> Result<ClassAtom, String> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return Err("\\ at end of pattern".to_string()),
+        };
+        self.pos += 1;
+        let char_atom = |c: char| Ok(ClassAtom::Char(c as u32));
+        match c {
+            'd' | 'D' | 'w' | 'W' | 's' | 'S' => Ok(ClassAtom::Class(class_escape(c))),
+            'p' | 'P' if self.unicode => {
+                if !self.eat('{') {
+                    return Err("Invalid property name".to_string());
+                }
+                let mut name = String::new();
+                loop {
+                    match self.peek() {
+                        Some('}') => break,
+                        Some(next) => name.push(next),
+                        None => return Err("Invalid property name".to_string()),
+                    }
+                    self.pos += 1;
+                }
+                self.pos += 1;
+                let mut class = property_class(&name)?;
+                class.negated = c == 'P';
+                Ok(ClassAtom::Class(class))
+            }
+            'f' => char_atom('\x0c'),
+            'n' => char_atom('\n'),
+            'r' => char_atom('\r'),
+            't' => char_atom('\t'),
+            'v' => char_atom('\x0b'),
+            'b' if in_class => char_atom('\x08'),
+            '-' if in_class && self.unicode => char_atom('-'),
+            'c' => match self.peek() {
+                Some(letter) if letter.is_ascii_alphabetic() => {
+                    self.pos += 1;
+                    Ok(ClassAtom::Char(letter as u32 % 32))
+                }
+                // Annex B allows digits and `_` in classes
+                Some(other) if in_class && !self.unicode && (other.is_ascii_digit() || other == '_') => {
+                    self.pos += 1;
+                    Ok(ClassAtom::Char(other as u32 % 32))
+                }
+                _ if self.unicode => Err("Invalid unicode escape".to_string()),
+                _ => {
+                    // `\c` is a literal backslash followed by `c`
+                    self.pos -= 1;
+                    char_atom('\\')
+                }
+            },
+            '0' if !self.peek().is_some_and(|c| c.is_ascii_digit()) => char_atom('\0'),
+            '0'..='7' if !self.unicode => {
+                // Annex B legacy octal escapes, up to \377
+                let mut value = c.to_digit(8).unwrap();
+                let max_digits = if c <= '3' { 2 } else { 1 };
+                for _ in 0..max_digits {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            self.pos += 1;
+                        }
+                        None => break,
+                    }
+                }
+                Ok(ClassAtom::Char(value))
+            }
+            'x' => match self.read_hex(2) {
+                Some(value) => Ok(ClassAtom::Char(value)),
+                None if self.unicode => Err("Invalid escape".to_string()),
+                None => char_atom('x'),
+            },
+            'u' => match self.parse_unicode_escape()? {
+                Some(value) => Ok(ClassAtom::Char(value)),
+                None if self.unicode => Err("Invalid Unicode escape".to_string()),
+                None => char_atom('u'),
+            },
+            _ if self.unicode => {
+                if "^$\\.*+?()[]{}|/".contains(c) {
+                    char_atom(c)
+                } else {
+                    Err("Invalid escape".to_string())
+                }
+            }
+            // Annex B identity escapes, where `\k` is `k` when there are no named groups
+            _ => char_atom(c),
+        }
+    }
+
+    /// Read a number of hex digits, without consuming anything if they aren't all there
+    fn read_hex(&mut self, digits: usize) -> Option<u32> {
+        let mut value = 0;
+        for offset in 0..digits {
+            value = value * 16 + self.peek_at(offset)?.to_digit(16)?;
+        }
+        self.pos += digits;
+        Some(value)
+    }
+
+    /// Parse the rest of a `\u` escape, combining surrogate pairs with the `u` flag
+    fn parse_unicode_escape(&mut self) -> Result<Option<u32>, String> {
+        if self.unicode && self.peek() == Some('{') {
+            let start = self.pos;
+            self.pos += 1;
+            let mut value: u32 = 0;
+            let mut digits = 0;
+            while let Some(digit) = self.peek().and_then(|c| c.to_digit(16)) {
+                value = value.saturating_mul(16).saturating_add(digit);
+                digits += 1;
+                self.pos += 1;
+            }
+            if digits == 0 || !self.eat('}') || value > 0x10FFFF {
+                self.pos = start;
+                return Err("Invalid Unicode escape".to_string());
+            }
+            return Ok(Some(value));
+        }
+        let lead = match self.read_hex(4) {
+            Some(lead) => lead,
+            None => return Ok(None),
+        };
+        if self.unicode && (0xD800..0xDC00).contains(&lead) && self.peek() == Some('\\') && self.peek_at(1) == Some('u') {
+            let start = self.pos;
+            self.pos += 2;
+            match self.read_hex(4) {
+                Some(trail) if (0xDC00..0xE000).contains(&trail) => {
+                    return Ok(Some(0x10000 + ((lead - 0xD800) << 10) + (trail - 0xDC00)));
+                }
+                _ => self.pos = start,
+            }
+        }
+        Ok(Some(lead))
+    }
+
+    fn parse_class(&mut self) -> Result<Node, String> {
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+        loop {
+            let first = match self.peek() {
+                None => return Err("Unterminated character class".to_string()),
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => self.parse_class_atom()?,
+            };
+            let is_range = self.peek() == Some('-') && self.peek_at(1).is_some_and(|c| c != ']');
+            if !is_range {
+                push_class_atom(&mut ranges, first);
+                continue;
+            }
+            self.pos += 1;
+            let second = self.parse_class_atom()?;
+            match (first, second) {
+                (ClassAtom::Char(start), ClassAtom::Char(end)) => {
+                    if start > end {
+                        return Err("Range out of order in character class".to_string());
+                    }
+                    ranges.push((start, end));
+                }
+                (first, second) => {
+                    if self.unicode {
+                        return Err("Invalid character class".to_string());
+                    }
+                    // Annex B: a range with a class escape is the atoms and a literal `-`
+                    push_class_atom(&mut ranges, first);
+                    ranges.push(('-' as u32, '-' as u32));
+                    push_class_atom(&mut ranges, second);
+                }
+            }
+        }
+        Ok(Node::Class(Class { ranges, negated }))
+    }
+
+    fn parse_class_atom(&mut self) -> Result<ClassAtom, String> {
+        let c = self.peek().unwrap();
+        self.pos += 1;
+        if c != '\\' {
+            let code_point = c as u32;
+            if !self.unicode && code_point >= 0x10000 {
+                // Without the `u` flag this is two code units, so the class gets both of them
+                let mut units = [0; 2];
+                c.encode_utf16(&mut units);
+                return Ok(ClassAtom::Class(Class {
+                    ranges: vec![(u32::from(units[0]), u32::from(units[0])), (u32::from(units[1]), u32::from(units[1]))],
+                    negated: false,
+                }));
+            }
+            return Ok(ClassAtom::Char(code_point));
+        }
+        self.parse_character_escape(true)
+    }
+}
+
+/// Add a class atom to a list of ranges
+fn push_class_atom(ranges: &mut Vec<(u32, u32)>, atom: ClassAtom) {
+    match atom {
+        ClassAtom::Char(c) => ranges.push((c, c)),
+        ClassAtom::Class(class) => {
+            let class = if class.negated { complement(&class.ranges) } else { class.ranges };
+            ranges.extend(class);
+        }
+    }
+}
+
+/// Get the ranges of every code point not in the given ranges
+fn complement(ranges: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort();
+    let mut result = Vec::new();
+    let mut next = 0;
+    for (start, end) in sorted {
+        if start > next {
+            result.push((next, start - 1));
+        }
+        next = next.max(end + 1);
+    }
+    if next <= 0x10FFFF {
+        result.push((next, 0x10FFFF));
+    }
+    result
+}
+
+/// The code points of `\s`
+/// https://tc39.es/ecma262/#sec-white-space
+const WHITE_SPACE: [(u32, u32); 10] = [
+    (0x09, 0x0D),
+    (0x20, 0x20),
+    (0xA0, 0xA0),
+    (0x1680, 0x1680),
+    (0x2000, 0x200A),
+    (0x2028, 0x2029),
+    (0x202F, 0x202F),
+    (0x205F, 0x205F),
+    (0x3000, 0x3000),
+    (0xFEFF, 0xFEFF),
+];
+
+/// The code points of `\w`
+const WORD: [(u32, u32); 4] = [(0x30, 0x39), (0x41, 0x5A), (0x5F, 0x5F), (0x61, 0x7A)];
+
+/// Get the class for `\d`, `\w` or `\s`, which is negated for the upper case letters
+fn class_escape(c: char) -> Class {
+    let ranges = match c.to_ascii_lowercase() {
+        'd' => vec![(0x30, 0x39)],
+        'w' => WORD.to_vec(),
+        _ => WHITE_SPACE.to_vec(),
+    };
+    Class { ranges, negated: c.is_ascii_uppercase() }
+}
+
+/// Get the class for a `\p{...}` property escape.
+/// Only the general categories and binary properties which Rust can tell us about are supported.
+fn property_class(name: &str) -> Result<Class, String> {
+    let name = name
+        .trim_start_matches("General_Category=")
+        .trim_start_matches("gc=")
+        .trim_start_matches("Script=")
+        .trim_start_matches("sc=");
+    let predicate: fn(char) -> bool = match name {
+        "L" | "Letter" | "Alphabetic" | "Alpha" => |c| c.is_alphabetic(),
+        "Lu" | "Uppercase_Letter" | "Uppercase" | "Upper" => |c| c.is_uppercase(),
+        "Ll" | "Lowercase_Letter" | "Lowercase" | "Lower" => |c| c.is_lowercase(),
+        "N" | "Number" => |c| c.is_numeric(),
+        "Nd" | "Decimal_Number" | "digit" => |c| c.is_ascii_digit() || ('\u{660}'..='\u{669}').contains(&c),
+        "White_Space" | "space" => |c| c.is_whitespace(),
+        "Cc" | "Control" | "cntrl" => |c| c.is_control(),
+        "ASCII" => |c| c.is_ascii(),
+        "Any" => |_| true,
+        "Latin" | "Latn" => |c| c.is_alphabetic() && (c as u32) < 0x0250,
+        "Greek" | "Grek" => |c| ('\u{0370}'..='\u{03FF}').contains(&c) || ('\u{1F00}'..='\u{1FFF}').contains(&c),
+        "Cyrillic" | "Cyrl" => |c| ('\u{0400}'..='\u{052F}').contains(&c),
+        "Han" | "Hani" => |c| ('\u{4E00}'..='\u{9FFF}').contains(&c) || ('\u{3400}'..='\u{4DBF}').contains(&c),
+        "Emoji" | "Extended_Pictographic" => |c| ('\u{1F300}'..='\u{1FAFF}').contains(&c) || ('\u{2600}'..='\u{27BF}').contains(&c),
+        _ => return Err("Invalid property name".to_string()),
+    };
+    // Build the ranges by walking every code point once
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for code_point in 0..=0x10FFFF {
+        let matches = std::char::from_u32(code_point).is_some_and(predicate);
+        if !matches {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.1 + 1 == code_point => last.1 = code_point,
+            _ => ranges.push((code_point, code_point)),
+        }
+    }
+    Ok(Class { ranges, negated: false })
+}
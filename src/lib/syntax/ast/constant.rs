@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use crate::js::bigint::BigInt;
 
 // PartialEq部分相等
 // Debug -> :?  Display -> {}
@@ -8,7 +9,9 @@ pub enum Const {
     String(String),
     Num(f64),
     Int(i32),
-    RegExp(String, bool, bool),
+    BigInt(BigInt),
+    /// 正则表达式的正文和标志
+    RegExp(String, String),
     Bool(bool),
     Null,
     Undefined,
@@ -21,7 +24,8 @@ impl Display for Const {
             Const::String(ref s) => write!(f, "\"{}\"", s),
             Const::Num(n) => write!(f, "{}", n),
             Const::Int(i) => write!(f, "{}", i),
-            Const::RegExp(ref reg, _, _) => write!(f, "{}", reg),
+            Const::BigInt(ref i) => write!(f, "{}n", i),
+            Const::RegExp(ref body, ref flags) => write!(f, "/{}/{}", body, flags),
             Const::Bool(b) => write!(f, "{}", b),
             Const::Null => write!(f, "null"),
             Const::Undefined => write!(f, "undefined"),
@@ -1,18 +1,29 @@
-use std::{fmt::{Display, Formatter, Result}, collections::BTreeMap};
+use std::fmt::{Display, Formatter, Result};
 use crate::syntax::ast::constant::Const;
+use crate::syntax::ast::module::{ExportDecl, ImportSpecifier};
 use crate::syntax::ast::op::{BinOp, Operator, UnaryOp};
+use crate::syntax::ast::pos::Position;
 
 #[derive(Clone, Trace, Finalize, Debug, PartialEq)]
 pub struct Expr {
  pub def: ExprDef,
+    /// 表达式在源码中的位置，用于错误的调用栈
+    pub pos: Option<Position>,
 }
 
 impl Expr {
     pub fn new(def: ExprDef) -> Expr {
         Expr {
             def: def,
+            pos: None,
         }
     }
+
+    /// 设置表达式在源码中的位置
+    pub fn at(mut self, pos: Position) -> Expr {
+        self.pos = Some(pos);
+        self
+    }
 }
 
 impl Display for Expr {
@@ -38,6 +49,8 @@ pub enum ExprDef {
     BlockExpr(Vec<Expr>),
     /// 本地变量
     LocalExpr(String),
+    /// this
+    ThisExpr,
     /// 获取属性 a.xx
     GetConstFieldExpr(Box<Expr>, String),
     /// 获取属性 a['']
@@ -46,18 +59,32 @@ pub enum ExprDef {
     CallExpr(Box<Expr>, Vec<Expr>),
     /// while
     WhileLoopExpr(Box<Expr>, Box<Expr>),
+    /// with (对象) 语句
+    WithExpr(Box<Expr>, Box<Expr>),
     /// if
     IfExpr(Box<Expr>, Box<Expr>, Option<Box<Expr>>),
     /// switch
     SwitchExpr(Box<Expr>, Vec<(Expr, Vec<Expr>)>, Option<Box<Expr>>),
     // 对象声明{a: {}}
-    ObjectDeclExpr(Box<BTreeMap<String, Expr>>),
+    ObjectDeclExpr(Box<Vec<(String, Expr)>>),
     /// 数组声明
     ArrayDeclExpr(Vec<Expr>),
-    /// 函数声明
-    FunctionDeclExpr(Option<String>, Vec<String>, Box<Expr>),
+    /// 函数声明, 最后是函数的源码
+    FunctionDeclExpr(Option<String>, Vec<String>, Box<Expr>, String),
     /// 箭头函数
-    ArrowFunctionDeclExpr(Vec<String>, Box<Expr>),
+    ArrowFunctionDeclExpr(Vec<String>, Box<Expr>, String),
+    /// async函数声明
+    AsyncFunctionDeclExpr(Option<String>, Vec<String>, Box<Expr>, String),
+    /// async箭头函数
+    AsyncArrowFunctionDeclExpr(Vec<String>, Box<Expr>, String),
+    /// await
+    AwaitExpr(Box<Expr>),
+    /// import声明, 带有导入的绑定和模块说明符
+    ImportDeclExpr(Vec<ImportSpecifier>, String),
+    /// export声明
+    ExportDeclExpr(ExportDecl),
+    /// 动态import()
+    ImportCallExpr(Box<Expr>),
     /// return
     ReturnExpr(Option<Box<Expr>>),
     /// throw
@@ -76,6 +103,7 @@ impl Operator for ExprDef {
             ExprDef::ConstructExpr(_, _)
             | ExprDef::UnaryOpExpr(_, _)
             | ExprDef::TypeOfExpr(_)
+            | ExprDef::AwaitExpr(_)
             | ExprDef::IfExpr(_, _, _)
             | ExprDef::AssignExpr(_, _) => false,
             _ => true,
@@ -91,7 +119,8 @@ impl Operator for ExprDef {
             | ExprDef::UnaryOpExpr(UnaryOp::DecrementPre, _) => 3,
             ExprDef::UnaryOpExpr(UnaryOp::Not, _)
             | ExprDef::UnaryOpExpr(UnaryOp::Minus, _)
-            | ExprDef::TypeOfExpr(_) => 4,
+            | ExprDef::TypeOfExpr(_)
+            | ExprDef::AwaitExpr(_) => 4,
             ExprDef::BinOpExpr(op, _, _) => op.get_precedence(),
             ExprDef::IfExpr(_, _, _) => 15,
             // 16 should be yield
@@ -114,6 +143,7 @@ impl Display for ExprDef {
                 write!(f, "{}", "}")
             }
             ExprDef::LocalExpr(ref s) => write!(f, "{}", s),
+            ExprDef::ThisExpr => write!(f, "this"),
             ExprDef::GetConstFieldExpr(ref ex, ref field) => write!(f, "{}.{}", ex, field),
             ExprDef::GetFieldExpr(ref ex, ref field) => write!(f, "{}[{}]", ex, field),
             ExprDef::CallExpr(ref ex, ref args) => {
@@ -135,6 +165,7 @@ impl Display for ExprDef {
                 f.write_str(")")
             }
             ExprDef::WhileLoopExpr(ref cond, ref expr) => write!(f, "while({}) {}", cond, expr),
+            ExprDef::WithExpr(ref obj, ref expr) => write!(f, "with({}) {}", obj, expr),
             ExprDef::IfExpr(ref cond, ref expr, None) => write!(f, "if({}) {}", cond, expr),
             ExprDef::IfExpr(ref cond, ref expr, Some(ref else_e)) => {
                 write!(f, "if({}) {} else {}", cond, expr, else_e)
@@ -171,13 +202,27 @@ impl Display for ExprDef {
                 join_expr(f, arr)?;
                 f.write_str("]")
             }
-            ExprDef::FunctionDeclExpr(ref name, ref args, ref expr) => match name {
+            ExprDef::FunctionDeclExpr(ref name, ref args, ref expr, _) => match name {
                 Some(val) => write!(f, "function {}({}){}", val, args.join(", "), expr),
                 None => write!(f, "function ({}){}", args.join(", "), expr),
             },
-            ExprDef::ArrowFunctionDeclExpr(ref args, ref expr) => {
+            ExprDef::ArrowFunctionDeclExpr(ref args, ref expr, _) => {
                 write!(f, "({}) => {}", args.join(", "), expr)
             }
+            ExprDef::AsyncFunctionDeclExpr(ref name, ref args, ref expr, _) => match name {
+                Some(val) => write!(f, "async function {}({}){}", val, args.join(", "), expr),
+                None => write!(f, "async function ({}){}", args.join(", "), expr),
+            },
+            ExprDef::AsyncArrowFunctionDeclExpr(ref args, ref expr, _) => {
+                write!(f, "async ({}) => {}", args.join(", "), expr)
+            }
+            ExprDef::AwaitExpr(ref e) => write!(f, "await {}", e),
+            ExprDef::ImportDeclExpr(ref specifiers, ref from) => {
+                let specifiers: Vec<String> = specifiers.iter().map(|s| s.to_string()).collect();
+                write!(f, "import {{ {} }} from \"{}\"", specifiers.join(", "), from)
+            }
+            ExprDef::ExportDeclExpr(ref decl) => write!(f, "{}", decl),
+            ExprDef::ImportCallExpr(ref e) => write!(f, "import({})", e),
             ExprDef::BinOpExpr(ref op, ref a, ref b) => write!(f, "{} {} {}", a, op, b),
             ExprDef::UnaryOpExpr(ref op, ref a) => write!(f, "{}{}", op, a),
             ExprDef::ReturnExpr(Some(ref ex)) => write!(f, "return {}", ex),
@@ -33,6 +33,8 @@ pub enum Keyword {
     Else,
     /// 关键词enum
     Enum,
+    /// 关键词export
+    Export,
     /// 关键词extends
     Extends,
     /// 关键词finally
@@ -112,6 +114,7 @@ impl FromStr for Keyword {
             "do" =>         Ok(Do),
             "else" =>       Ok(Else),
             "enum" =>       Ok(Enum),
+            "export" =>     Ok(Export),
             "extends" =>    Ok(Extends),
             "finally" =>    Ok(Finally),
             "for" =>        Ok(For),
@@ -157,6 +160,7 @@ impl Display for Keyword {
                 Do => "do",
                 Else => "else",
                 Enum => "enum",
+                Export => "export",
                 Extends => "extends",
                 Finally => "finally",
                 For => "for",
@@ -4,4 +4,5 @@ pub mod punc;
 pub mod pos;
 pub mod constant;
 pub mod expr;
-pub mod op;
\ No newline at end of file
+pub mod op;
+pub mod module;
\ No newline at end of file
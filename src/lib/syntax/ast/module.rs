@@ -0,0 +1,75 @@
+use std::fmt::{Display, Formatter, Result};
+use crate::syntax::ast::expr::Expr;
+
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+/// One binding brought in by an `import` declaration
+pub enum ImportSpecifier {
+    /// `import { imported as local } from "..."`, a default import uses "default" as the imported name
+    Named(String, String),
+    /// `import * as local from "..."`
+    Namespace(String),
+}
+
+impl ImportSpecifier {
+    /// The name of the binding made in the importing module
+    pub fn local_name(&self) -> &str {
+        match *self {
+            ImportSpecifier::Named(_, ref local) | ImportSpecifier::Namespace(ref local) => local,
+        }
+    }
+}
+
+impl Display for ImportSpecifier {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match *self {
+            ImportSpecifier::Named(ref imported, ref local) if imported == local => {
+                write!(f, "{}", local)
+            }
+            ImportSpecifier::Named(ref imported, ref local) => write!(f, "{} as {}", imported, local),
+            ImportSpecifier::Namespace(ref local) => write!(f, "* as {}", local),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+/// The different forms of `export` declaration
+pub enum ExportDecl {
+    /// `export { local as exported }`, re-exported from another module if it has a specifier
+    Named(Vec<(String, String)>, Option<String>),
+    /// `export * from "..."`, or `export * as name from "..."` if it has a name
+    All(Option<String>, String),
+    /// `export var ...`, `export function ...` and other declarations which export their own names
+    Decl(Box<Expr>),
+    /// `export default ...`
+    Default(Box<Expr>),
+}
+
+impl Display for ExportDecl {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match *self {
+            ExportDecl::Named(ref names, ref from) => {
+                let names: Vec<String> = names
+                    .iter()
+                    .map(|(local, exported)| {
+                        if local == exported {
+                            local.clone()
+                        } else {
+                            format!("{} as {}", local, exported)
+                        }
+                    })
+                    .collect();
+                write!(f, "export {{ {} }}", names.join(", "))?;
+                match from {
+                    Some(specifier) => write!(f, " from \"{}\"", specifier),
+                    None => Ok(()),
+                }
+            }
+            ExportDecl::All(Some(ref name), ref from) => {
+                write!(f, "export * as {} from \"{}\"", name, from)
+            }
+            ExportDecl::All(None, ref from) => write!(f, "export * from \"{}\"", from),
+            ExportDecl::Decl(ref decl) => write!(f, "export {}", decl),
+            ExportDecl::Default(ref expr) => write!(f, "export default {}", expr),
+        }
+    }
+}
@@ -128,6 +128,8 @@ pub enum CompOp {
     LessThan,
     /// `a <= b` - If `a` is less than or equal to `b`
     LessThanOrEqual,
+    /// `a instanceof b` - If `a` is an instance of `b`
+    InstanceOf,
 }
 
 impl Display for CompOp {
@@ -144,6 +146,7 @@ impl Display for CompOp {
                 CompOp::GreaterThanOrEqual => ">=",
                 CompOp::LessThan => "<",
                 CompOp::LessThanOrEqual => "<=",
+                CompOp::InstanceOf => "instanceof",
             }
         )
     }
@@ -196,7 +199,8 @@ impl Operator for BinOp {
             BinOp::Comp(CompOp::LessThan)
             | BinOp::Comp(CompOp::LessThanOrEqual)
             | BinOp::Comp(CompOp::GreaterThan)
-            | BinOp::Comp(CompOp::GreaterThanOrEqual) => 8,
+            | BinOp::Comp(CompOp::GreaterThanOrEqual)
+            | BinOp::Comp(CompOp::InstanceOf) => 8,
             BinOp::Comp(CompOp::Equal)
             | BinOp::Comp(CompOp::NotEqual)
             | BinOp::Comp(CompOp::StrictEqual)
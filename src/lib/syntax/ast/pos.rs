@@ -1,4 +1,4 @@
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Trace, Finalize)]
 // js中的代码位置
 pub struct Position {
     // 行号
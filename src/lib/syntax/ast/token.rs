@@ -2,6 +2,7 @@ use std::fmt::{Debug, Display, Result, Formatter};
 use crate::syntax::ast::keyword::Keyword;
 use crate::syntax::ast::pos::Position;
 use crate::syntax::ast::punc::Punctuator;
+use crate::js::bigint::BigInt;
 
 #[derive(Clone, PartialEq)]
 #[derive(Debug)]
@@ -9,6 +10,10 @@ use crate::syntax::ast::punc::Punctuator;
 pub struct Token {
     pub data: TokenData,
     pub pos: Position,
+    /// Token在源码中开头的字节偏移
+    pub start: usize,
+    /// Token在源码中结尾的字节偏移
+    pub end: usize,
 }
 
 impl Token {
@@ -17,6 +22,8 @@ impl Token {
         Token {
             data: data,
             pos: Position::new(line_number, column_number),
+            start: 0,
+            end: 0,
         }
     }
 }
@@ -55,12 +62,14 @@ pub enum TokenData {
     NullLiteral,
     /// 数字
     NumericLiteral(f64),
+    /// 大整数
+    BigIntLiteral(BigInt),
     /// 标点符号
     Punctuator(Punctuator),
     /// 字符串
     StringLiteral(String),
-    /// 正则
-    RegularExpression(String),
+    /// 正则，包括正文和标志
+    RegularExpression(String, String),
     /// 注释
     Comment(String),
 }
@@ -74,9 +83,10 @@ impl Display for TokenData {
             TokenData::Keyword(keyword) => write!(f, "{:?}", keyword),
             TokenData::NullLiteral => write!(f, "null"),
             TokenData::NumericLiteral(num) => write!(f, "{}", num),
+            TokenData::BigIntLiteral(num) => write!(f, "{}n", num),
             TokenData::Punctuator(punctuator) => write!(f, "{:?}", punctuator),
             TokenData::StringLiteral(str) => write!(f, "{}", str),
-            TokenData::RegularExpression(regex) => write!(f, "{}", regex),
+            TokenData::RegularExpression(body, flags) => write!(f, "/{}/{}", body, flags),
             TokenData::Comment(comment) => write!(f, "{}", comment),
         }
     }
@@ -2,7 +2,9 @@ use std::{iter::Peekable, fmt::Display, error, char::from_u32};
 use std::str::FromStr;
 use std::str::Chars;
 use crate::syntax::ast::punc::Punctuator;
+use crate::syntax::ast::keyword::Keyword;
 use crate::syntax::ast::token::{Token, TokenData};
+use crate::js::bigint::BigInt;
 
 #[allow(unused)]
 macro_rules! vop {
@@ -83,6 +85,12 @@ pub struct Lexer<'a> {
     pub line_number: u64,
     pub column_number: u64,
     pub buffer: Peekable<Chars<'a>>,
+    /// 已经读过的字节数
+    pub offset: usize,
+    /// 当前Token开头的字节偏移
+    token_start: usize,
+    /// 当前Token开头的列号
+    token_column: u64,
 }
 
 impl<'a> Lexer<'a> {
@@ -92,6 +100,9 @@ impl<'a> Lexer<'a> {
             buffer: buffer.chars().peekable(),
             line_number: 1,
             column_number: 0,
+            offset: 0,
+            token_start: 0,
+            token_column: 0,
         }
     }
 
@@ -107,7 +118,10 @@ impl<'a> Lexer<'a> {
 
     /// 添加一个Token
     pub fn push_token(&mut self, token_data: TokenData) {
-        self.tokens.push(Token::new(token_data, self.line_number, self.column_number))
+        let mut token = Token::new(token_data, self.line_number, self.token_column);
+        token.start = self.token_start;
+        token.end = self.offset;
+        self.tokens.push(token)
     }
 
     pub fn lex(&mut self) -> Result<(), LexerError> {
@@ -123,7 +137,8 @@ impl<'a> Lexer<'a> {
                 },
             }
 
-            self.column_number += 1;
+            self.token_start = self.offset;
+            self.token_column = self.column_number + 1;
             let ch = self.next()?;
 
             match ch {
@@ -147,13 +162,14 @@ impl<'a> Lexer<'a> {
                                         't' => '\t',
                                         'b' => '\x08',
                                         'f' => '\x0c',
+                                        'v' => '\x0b',
                                         '0' => '\0',
+                                        '\\' => '\\',
                                         'x' => {
                                             let mut nums = String::with_capacity(2);
                                             for _ in 0u8..2 {
                                                 nums.push(self.next()?);
                                             }
-                                            self.column_number += 2;
                                             let as_num = match u64::from_str_radix(&nums, 16) {
                                                 Ok(v) => v,
                                                 Err(_) => 0,
@@ -171,7 +187,6 @@ impl<'a> Lexer<'a> {
                                             for _ in 0u8..4 {
                                                 nums.push(self.next()?);
                                             }
-                                            self.column_number += 4;
                                             let as_num = match u64::from_str_radix(&nums, 16) {
                                                 Ok(v) => v,
                                                 Err(_) => 0,
@@ -196,24 +211,43 @@ impl<'a> Lexer<'a> {
                             ch => buf.push(ch),
                         }
                     }
-                    let str_length = buf.len() as u64;
                     self.push_token(TokenData::StringLiteral(buf));
-                    self.column_number += str_length + 1;
                 },
-                // 匹配16进制数字
+                // 匹配16进制、8进制和2进制数字
                 '0' => {
                     let mut buf = String::new();
-                    let num = if self.next_is('x')? {
+                    let radix = if self.next_is('x')? {
+                        16
+                    } else if self.next_is('o')? {
+                        8
+                    } else if self.next_is('b')? {
+                        2
+                    } else {
+                        0
+                    };
+                    let num = if radix != 0 {
                         loop {
                             let ch = self.preview_next()?;
                             match ch {
-                                ch if ch.is_digit(16) => {
+                                ch if ch.is_digit(radix) => {
                                     buf.push(self.next()?);
                                 }
                                 _ => break,
                             }
                         }
-                        u64::from_str_radix(&buf, 16).unwrap()
+                        let num = match BigInt::from_str_radix(&buf, radix) {
+                            Some(num) => num,
+                            None => return Err(LexerError::new("invalid numeric literal")),
+                        };
+                        // 大整数字面量以n结尾
+                        if self.next_is('n')? {
+                            self.push_token(TokenData::BigIntLiteral(num));
+                            continue;
+                        }
+                        num.to_f64()
+                    } else if self.next_is('n')? {
+                        self.push_token(TokenData::BigIntLiteral(BigInt::from_str_radix("0", 10).unwrap()));
+                        continue;
                     } else {
                         let mut gone_decimal = false;
                         loop {
@@ -231,18 +265,14 @@ impl<'a> Lexer<'a> {
                                 _ => break,
                             }
                         }
-                        if gone_decimal {
-                            u64::from_str(&buf).unwrap()
+                        if buf.is_empty() || gone_decimal {
+                            self.read_exponent(&mut buf)?;
+                            f64::from_str(&format!("0{}", buf)).unwrap()
                         } else {
-                            if buf.is_empty() {
-                                0
-                            } else {
-                                u64::from_str_radix(&buf, 8).unwrap()
-                            }
+                            u64::from_str_radix(&buf, 8).unwrap() as f64
                         }
-
                     };
-                    self.push_token(TokenData::NumericLiteral(num as f64))
+                    self.push_token(TokenData::NumericLiteral(num))
                 },
                 // 匹配数字字面量
                 _ if ch.is_digit(10) => {
@@ -259,6 +289,12 @@ impl<'a> Lexer<'a> {
                             _ => break,
                         }
                     }
+                    self.read_exponent(&mut buf)?;
+                    // 大整数字面量以n结尾
+                    if !buf.contains('.') && !buf.contains('e') && self.next_is('n')? {
+                        self.push_token(TokenData::BigIntLiteral(BigInt::from_str_radix(&buf, 10).unwrap()));
+                        continue;
+                    }
                     // TODO make this a bit more safe -------------------------------VVVV
                     self.push_token(TokenData::NumericLiteral(f64::from_str(&buf).unwrap()))
                 }
@@ -287,7 +323,6 @@ impl<'a> Lexer<'a> {
                             Err(_) => TokenData::Identifier(buf.clone()),
                         },
                     });
-                    self.column_number += (buf_compare.len() - 1) as u64;
                 }
                 ';' => self.push_punc(Punctuator::Semicolon),
                 ':' => self.push_punc(Punctuator::Colon),
@@ -324,8 +359,13 @@ impl<'a> Lexer<'a> {
                             }
                             TokenData::Comment(buf)
                         }
+                        // 正则字面量
+                        _ if self.regex_allowed() => self.read_regex()?,
                         // /= 计算
-                        '=' => TokenData::Punctuator(Punctuator::AssignDiv),
+                        '=' => {
+                            self.next()?;
+                            TokenData::Punctuator(Punctuator::AssignDiv)
+                        }
                         _ => TokenData::Punctuator(Punctuator::Div),
                     };
                     self.push_token(token)
@@ -359,12 +399,21 @@ impl<'a> Lexer<'a> {
                     }
                 }),
                 '<' => op!(self, Punctuator::LessThanOrEq, Punctuator::LessThan, {
-                    '<' => vop!(self, Punctuator::AssignLeftSh, Punctuator::LeftSh)
+                    '<' => {
+                        self.next()?;
+                        vop!(self, Punctuator::AssignLeftSh, Punctuator::LeftSh)
+                    }
                 }),
                 '>' => op!(self, Punctuator::GreaterThanOrEq, Punctuator::GreaterThan, {
-                    '>' => vop!(self, Punctuator::AssignRightSh, Punctuator::RightSh, {
-                        '>' => vop!(self, Punctuator::AssignURightSh, Punctuator::URightSh)
-                    })
+                    '>' => {
+                        self.next()?;
+                        vop!(self, Punctuator::AssignRightSh, Punctuator::RightSh, {
+                            '>' => {
+                                self.next()?;
+                                vop!(self, Punctuator::AssignURightSh, Punctuator::URightSh)
+                            }
+                        })
+                    }
                 }),
                 '!' => op!(
                     self,
@@ -390,7 +439,11 @@ impl<'a> Lexer<'a> {
 
     fn next(&mut self) -> Result<char, LexerError>{
         match self.buffer.next() {
-            Some(char) => Ok(char),
+            Some(char) => {
+                self.offset += char.len_utf8();
+                self.column_number += 1;
+                Ok(char)
+            }
             None => Err(LexerError::new("finished")),
         }
     }
@@ -420,10 +473,103 @@ impl<'a> Lexer<'a> {
         Ok(buf)
     }
 
+    /// 判断`/`是否开始一个正则字面量，而不是除法
+    /// 在值之后是除法，例如标识符、字面量、`)`、`]`和`}`之后
+    fn regex_allowed(&self) -> bool {
+        let previous = self
+            .tokens
+            .iter()
+            .rev()
+            .find(|token| !matches!(token.data, TokenData::Comment(_)));
+        match previous.map(|token| &token.data) {
+            None => true,
+            Some(TokenData::Punctuator(punc)) => !matches!(
+                punc,
+                Punctuator::CloseParen
+                    | Punctuator::CloseBracket
+                    | Punctuator::CloseBlock
+                    | Punctuator::Inc
+                    | Punctuator::Dec
+            ),
+            Some(TokenData::Keyword(keyword)) => *keyword != Keyword::This,
+            Some(_) => false,
+        }
+    }
+
+    /// 读取开头的`/`之后的正则字面量
+    fn read_regex(&mut self) -> Result<TokenData, LexerError> {
+        let mut body = String::new();
+        let mut in_class = false;
+        loop {
+            let ch = match self.buffer.next() {
+                Some(ch) if ch != '\n' && ch != '\r' => {
+                    self.offset += ch.len_utf8();
+                    ch
+                }
+                _ => {
+                    return Err(LexerError::new(&format!(
+                        "{}:{}: Unterminated regular expression",
+                        self.line_number, self.column_number
+                    )))
+                }
+            };
+            match ch {
+                '/' if !in_class => break,
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '\\' => {
+                    body.push(ch);
+                    let escaped = self.next()?;
+                    body.push(escaped);
+                    continue;
+                }
+                _ => (),
+            }
+            body.push(ch);
+        }
+        let mut flags = String::new();
+        while let Some(&ch) = self.buffer.peek() {
+            if !(ch.is_alphanumeric() || ch == '_' || ch == '$') {
+                break;
+            }
+            flags.push(ch);
+            self.next()?;
+        }
+        Ok(TokenData::RegularExpression(body, flags))
+    }
+
+    /// 读取数字字面量的指数部分，例如`1e21`中的`e21`
+    fn read_exponent(&mut self, buf: &mut String) -> Result<(), LexerError> {
+        let mut ahead = self.buffer.clone();
+        match ahead.next() {
+            Some('e') | Some('E') => (),
+            _ => return Ok(()),
+        }
+        let signed = match ahead.peek() {
+            Some('+') | Some('-') => {
+                ahead.next();
+                true
+            }
+            _ => false,
+        };
+        if !ahead.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            return Ok(());
+        }
+        self.next()?;
+        buf.push('e');
+        if signed {
+            buf.push(self.next()?);
+        }
+        while self.buffer.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            buf.push(self.next()?);
+        }
+        Ok(())
+    }
+
     fn next_is(&mut self, peek: char) -> Result<bool, LexerError> {
         let result = self.preview_next()? == peek;
         if result {
-            self.buffer.next();
+            self.next()?;
         }
         return Ok(result)
     }
@@ -434,7 +580,6 @@ impl<'a> Lexer<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::syntax::ast::keyword::Keyword;
 
     #[test]
     fn check_variable_definition_tokens() {
@@ -498,4 +643,57 @@ mod tests {
         );
     }
 
-}
\ No newline at end of file
+
+    #[test]
+    fn check_bigint_literals() {
+        let s = &String::from("12n 0x1fn 0o17n 0b11n 0n 1.5;");
+        let mut lexer = Lexer::new(s);
+        lexer.lex().expect("finished");
+        let bigint = |digits: &str| TokenData::BigIntLiteral(BigInt::from_str_radix(digits, 10).unwrap());
+        assert_eq!(lexer.tokens[0].data, bigint("12"));
+        assert_eq!(lexer.tokens[1].data, bigint("31"));
+        assert_eq!(lexer.tokens[2].data, bigint("15"));
+        assert_eq!(lexer.tokens[3].data, bigint("3"));
+        assert_eq!(lexer.tokens[4].data, bigint("0"));
+        assert_eq!(lexer.tokens[5].data, TokenData::NumericLiteral(1.5));
+    }
+
+    #[test]
+    fn check_number_literals() {
+        let s = &String::from("0.5 1e21 2.5E-3 0e0 1e 077;");
+        let mut lexer = Lexer::new(s);
+        lexer.lex().expect("finished");
+        assert_eq!(lexer.tokens[0].data, TokenData::NumericLiteral(0.5));
+        assert_eq!(lexer.tokens[1].data, TokenData::NumericLiteral(1e21));
+        assert_eq!(lexer.tokens[2].data, TokenData::NumericLiteral(2.5e-3));
+        assert_eq!(lexer.tokens[3].data, TokenData::NumericLiteral(0.0));
+        assert_eq!(lexer.tokens[4].data, TokenData::NumericLiteral(1.0));
+        assert_eq!(lexer.tokens[5].data, TokenData::Identifier("e".to_string()));
+        assert_eq!(lexer.tokens[6].data, TokenData::NumericLiteral(63.0));
+    }
+
+    #[test]
+    fn check_regex_or_division() {
+        let s = &String::from("a = /[/]+\\//gi; b = c / d / e; f /= (g) / h;");
+        let mut lexer = Lexer::new(s);
+        lexer.lex().expect("finished");
+        assert_eq!(
+            lexer.tokens[2].data,
+            TokenData::RegularExpression("[/]+\\/".to_string(), "gi".to_string())
+        );
+        assert_eq!(lexer.tokens[7].data, TokenData::Punctuator(Punctuator::Div));
+        assert_eq!(lexer.tokens[9].data, TokenData::Punctuator(Punctuator::Div));
+        assert_eq!(lexer.tokens[13].data, TokenData::Punctuator(Punctuator::AssignDiv));
+        assert_eq!(lexer.tokens[17].data, TokenData::Punctuator(Punctuator::Div));
+    }
+
+    #[test]
+    fn check_shift_operators() {
+        let s = &String::from("a << b >> c >>> d;");
+        let mut lexer = Lexer::new(s);
+        lexer.lex().expect("finished");
+        assert_eq!(lexer.tokens[1].data, TokenData::Punctuator(Punctuator::LeftSh));
+        assert_eq!(lexer.tokens[3].data, TokenData::Punctuator(Punctuator::RightSh));
+        assert_eq!(lexer.tokens[5].data, TokenData::Punctuator(Punctuator::URightSh));
+    }
+}
@@ -1,7 +1,7 @@
-use std::collections::btree_map::BTreeMap;
 use crate::syntax::ast::constant::Const;
 use crate::syntax::ast::expr::{Expr, ExprDef};
 use crate::syntax::ast::keyword::Keyword;
+use crate::syntax::ast::module::{ExportDecl, ImportSpecifier};
 use crate::syntax::ast::op::{BinOp, BitOp, CompOp, LogOp, NumOp, Operator, UnaryOp};
 use crate::syntax::ast::punc::Punctuator;
 use crate::syntax::ast::token::{Token, TokenData};
@@ -11,7 +11,7 @@ pub enum ParseError {
     /// When it expected a certain kind of token, but got another as part of something
     Expected(Vec<TokenData>, Token, &'static str),
     /// When it expected a certain expression, but got another
-    ExpectedExpr(&'static str, Expr),
+    ExpectedExpr(&'static str, Box<Expr>),
     /// When it didn't expect this keyword
     UnexpectedKeyword(Keyword),
     /// When there is an abrupt end to the parsing
@@ -23,6 +23,8 @@ pub type ParseResult = Result<Expr, ParseError>;
 pub struct Parser {
     tokens: Vec<Token>,
     pos: usize,
+    /// The source the tokens were lexed from, which functions keep for `toString`
+    source: String,
 }
 
 macro_rules! mk (
@@ -32,13 +34,19 @@ macro_rules! mk (
         }
     };
     ($this:expr, $def:expr, $first:expr) => {
-        Expr::new($def)
+        Expr::new($def).at($first.pos.clone())
     };
 );
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Parser {
-        Parser { tokens: tokens, pos: 0 }
+        Parser { tokens: tokens, pos: 0, source: String::new() }
+    }
+
+    /// Keep the source the tokens were lexed from, so functions can be printed as they were written
+    pub fn with_source(mut self, source: &str) -> Parser {
+        self.source = source.to_string();
+        self
     }
 
     pub fn parse_all(&mut self) -> ParseResult {
@@ -51,6 +59,19 @@ impl Parser {
         Ok(Expr::new(ExprDef::BlockExpr(exprs)))
     }
 
+    /// The source text from the token at `start` up to the last token read,
+    /// or an empty string if the parser doesn't have the source
+    fn source_from(&self, start: usize) -> String {
+        // The semicolon ending an arrow function's expression body isn't part of the function
+        let mut end = self.pos - 1;
+        while end > start && self.tokens[end].data == TokenData::Punctuator(Punctuator::Semicolon) {
+            end -= 1;
+        }
+        let from = self.tokens[start].start;
+        let to = self.tokens[end].end;
+        self.source.get(from..to).unwrap_or_default().to_string()
+    }
+
     fn get_token(&self, pos: usize) -> Result<Token, ParseError> {
         if pos < self.tokens.len() {
             Ok(self.tokens[pos].clone())
@@ -61,6 +82,7 @@ impl Parser {
 
     fn parse_struct(&mut self, keyword: Keyword) -> ParseResult {
         match keyword {
+            Keyword::This => Ok(mk!(self, ExprDef::ThisExpr)),
             Keyword::Throw => {
                 let thrown = self.parse()?;
                 Ok(mk!(self, ExprDef::ThrowExpr(Box::new(thrown))))
@@ -131,7 +153,7 @@ impl Parser {
                         self,
                         ExprDef::ConstructExpr(func.clone(), args.clone())
                     )),
-                    _ => Err(ParseError::ExpectedExpr("constructor", call)),
+                    _ => Err(ParseError::ExpectedExpr("constructor", Box::new(call))),
                 }
             }
             Keyword::TypeOf => Ok(mk!(self, ExprDef::TypeOfExpr(Box::new(self.parse()?)))),
@@ -165,6 +187,13 @@ impl Parser {
                     ExprDef::WhileLoopExpr(Box::new(cond), Box::new(expr))
                 ))
             }
+            Keyword::With => {
+                self.expect_punc(Punctuator::OpenParen, "with object")?;
+                let obj = self.parse()?;
+                self.expect_punc(Punctuator::CloseParen, "with object")?;
+                let expr = self.parse()?;
+                Ok(mk!(self, ExprDef::WithExpr(Box::new(obj), Box::new(expr))))
+            }
             Keyword::Switch => {
                 self.expect_punc(Punctuator::OpenParen, "switch value")?;
                 let value = self.parse();
@@ -232,6 +261,7 @@ impl Parser {
             }
             Keyword::Function => {
                 // function [identifier] () { etc }
+                let start = self.pos - 1;
                 let tk = self.get_token(self.pos)?;
                 let name = match tk.data {
                     TokenData::Identifier(ref name) => {
@@ -272,20 +302,215 @@ impl Parser {
                 }
                 self.pos += 1;
                 let block = self.parse()?;
+                let source = self.source_from(start);
                 Ok(mk!(
                     self,
-                    ExprDef::FunctionDeclExpr(name, args, Box::new(block))
+                    ExprDef::FunctionDeclExpr(name, args, Box::new(block), source)
                 ))
             }
+            Keyword::Import => {
+                match self.get_token(self.pos)?.data {
+                    // import(specifier)
+                    TokenData::Punctuator(Punctuator::OpenParen) => {
+                        self.pos += 1;
+                        let specifier = self.parse()?;
+                        self.expect_punc(Punctuator::CloseParen, "dynamic import")?;
+                        return Ok(mk!(self, ExprDef::ImportCallExpr(Box::new(specifier))));
+                    }
+                    // import "specifier", only run for its side effects
+                    TokenData::StringLiteral(_) => {
+                        let from = self.parse_module_specifier("import")?;
+                        return Ok(mk!(self, ExprDef::ImportDeclExpr(Vec::new(), from)));
+                    }
+                    _ => (),
+                }
+                let mut specifiers = Vec::new();
+                // import default, ...
+                if let TokenData::Identifier(ref name) = self.get_token(self.pos)?.data {
+                    self.pos += 1;
+                    specifiers.push(ImportSpecifier::Named("default".to_string(), name.clone()));
+                    if self.get_token(self.pos)?.data != TokenData::Punctuator(Punctuator::Comma) {
+                        self.expect_contextual("from", "import")?;
+                        let from = self.parse_module_specifier("import")?;
+                        return Ok(mk!(self, ExprDef::ImportDeclExpr(specifiers, from)));
+                    }
+                    self.pos += 1;
+                }
+                let tk = self.get_token(self.pos)?;
+                self.pos += 1;
+                match tk.data {
+                    // * as namespace
+                    TokenData::Punctuator(Punctuator::Mul) => {
+                        self.expect_contextual("as", "import")?;
+                        let name = self.parse_binding_name("import")?;
+                        specifiers.push(ImportSpecifier::Namespace(name));
+                    }
+                    // { imported as local, ... }
+                    TokenData::Punctuator(Punctuator::OpenBlock) => {
+                        for (imported, local) in self.parse_module_names("import")? {
+                            specifiers.push(ImportSpecifier::Named(imported, local));
+                        }
+                    }
+                    _ => {
+                        return Err(ParseError::Expected(
+                            vec![
+                                TokenData::Punctuator(Punctuator::Mul),
+                                TokenData::Punctuator(Punctuator::OpenBlock),
+                            ],
+                            tk,
+                            "import",
+                        ))
+                    }
+                }
+                self.expect_contextual("from", "import")?;
+                let from = self.parse_module_specifier("import")?;
+                Ok(mk!(self, ExprDef::ImportDeclExpr(specifiers, from)))
+            }
+            Keyword::Export => {
+                let tk = self.get_token(self.pos)?;
+                let decl = match tk.data {
+                    // export * from "specifier", export * as name from "specifier"
+                    TokenData::Punctuator(Punctuator::Mul) => {
+                        self.pos += 1;
+                        let name = if self.get_token(self.pos)?.data
+                            == TokenData::Identifier("as".to_string())
+                        {
+                            self.pos += 1;
+                            Some(self.parse_module_name("export")?)
+                        } else {
+                            None
+                        };
+                        self.expect_contextual("from", "export")?;
+                        ExportDecl::All(name, self.parse_module_specifier("export")?)
+                    }
+                    // export { local as exported, ... } [from "specifier"]
+                    TokenData::Punctuator(Punctuator::OpenBlock) => {
+                        self.pos += 1;
+                        let names = self.parse_module_names("export")?;
+                        let from = if self.pos < self.tokens.len()
+                            && self.get_token(self.pos)?.data
+                                == TokenData::Identifier("from".to_string())
+                        {
+                            self.pos += 1;
+                            Some(self.parse_module_specifier("export")?)
+                        } else {
+                            None
+                        };
+                        ExportDecl::Named(names, from)
+                    }
+                    TokenData::Keyword(Keyword::Default) => {
+                        self.pos += 1;
+                        ExportDecl::Default(Box::new(self.parse()?))
+                    }
+                    _ => {
+                        let decl = self.parse()?;
+                        match decl.def {
+                            ExprDef::VarDeclExpr(_)
+                            | ExprDef::LetDeclExpr(_)
+                            | ExprDef::ConstDeclExpr(_)
+                            | ExprDef::FunctionDeclExpr(Some(_), _, _, _)
+                            | ExprDef::AsyncFunctionDeclExpr(Some(_), _, _, _) => {
+                                ExportDecl::Decl(Box::new(decl))
+                            }
+                            _ => return Err(ParseError::ExpectedExpr("declaration", Box::new(decl))),
+                        }
+                    }
+                };
+                Ok(mk!(self, ExprDef::ExportDeclExpr(decl)))
+            }
             _ => Err(ParseError::UnexpectedKeyword(keyword)),
         }
     }
 
+    /// Expect an identifier with special meaning in this position, such as `from` or `as`
+    fn expect_contextual(&mut self, word: &str, routine: &'static str) -> Result<(), ParseError> {
+        self.expect(TokenData::Identifier(word.to_string()), routine)
+    }
+
+    /// Parse the name of a binding made by an import
+    fn parse_binding_name(&mut self, routine: &'static str) -> Result<String, ParseError> {
+        let tk = self.get_token(self.pos)?;
+        self.pos += 1;
+        match tk.data {
+            TokenData::Identifier(name) => Ok(name),
+            _ => Err(ParseError::Expected(
+                vec![TokenData::Identifier("identifier".to_string())],
+                tk,
+                routine,
+            )),
+        }
+    }
+
+    /// Parse an imported or exported name, which may be a reserved word such as `default`
+    fn parse_module_name(&mut self, routine: &'static str) -> Result<String, ParseError> {
+        let tk = self.get_token(self.pos)?;
+        self.pos += 1;
+        match tk.data {
+            TokenData::Identifier(name) | TokenData::StringLiteral(name) => Ok(name),
+            TokenData::Keyword(keyword) => Ok(keyword.to_string()),
+            _ => Err(ParseError::Expected(
+                vec![TokenData::Identifier("identifier".to_string())],
+                tk,
+                routine,
+            )),
+        }
+    }
+
+    /// Parse a list of names such as `{ a, b as c }`, after the opening brace.
+    /// Returns pairs of the name before and after `as`, which are the same if there is no `as`.
+    fn parse_module_names(&mut self, routine: &'static str) -> Result<Vec<(String, String)>, ParseError> {
+        let mut names = Vec::new();
+        loop {
+            if self.get_token(self.pos)?.data == TokenData::Punctuator(Punctuator::CloseBlock) {
+                self.pos += 1;
+                return Ok(names);
+            }
+            let name = self.parse_module_name(routine)?;
+            let alias = if self.get_token(self.pos)?.data == TokenData::Identifier("as".to_string()) {
+                self.pos += 1;
+                self.parse_module_name(routine)?
+            } else {
+                name.clone()
+            };
+            names.push((name, alias));
+            let tk = self.get_token(self.pos)?;
+            match tk.data {
+                TokenData::Punctuator(Punctuator::Comma) => self.pos += 1,
+                TokenData::Punctuator(Punctuator::CloseBlock) => (),
+                _ => {
+                    return Err(ParseError::Expected(
+                        vec![
+                            TokenData::Punctuator(Punctuator::Comma),
+                            TokenData::Punctuator(Punctuator::CloseBlock),
+                        ],
+                        tk,
+                        routine,
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Parse the string naming the module to import from
+    fn parse_module_specifier(&mut self, routine: &'static str) -> Result<String, ParseError> {
+        let tk = self.get_token(self.pos)?;
+        self.pos += 1;
+        match tk.data {
+            TokenData::StringLiteral(specifier) => Ok(specifier),
+            _ => Err(ParseError::Expected(
+                vec![TokenData::StringLiteral("module specifier".to_string())],
+                tk,
+                routine,
+            )),
+        }
+    }
+
     /// Parse a single expression
     pub fn parse(&mut self) -> ParseResult {
         if self.pos > self.tokens.len() {
             return Err(ParseError::AbruptEnd);
         }
+        let start = self.pos;
         let token = self.get_token(self.pos)?;
         self.pos += 1;
         let expr: Expr = match token.data {
@@ -298,12 +523,34 @@ impl Parser {
                 mk!(self, ExprDef::ConstExpr(Const::Undefined))
             }
             TokenData::NumericLiteral(num) => mk!(self, ExprDef::ConstExpr(Const::Num(num))),
+            TokenData::BigIntLiteral(num) => mk!(self, ExprDef::ConstExpr(Const::BigInt(num))),
             TokenData::NullLiteral => mk!(self, ExprDef::ConstExpr(Const::Null)),
             TokenData::StringLiteral(text) => mk!(self, ExprDef::ConstExpr(Const::String(text))),
+            TokenData::RegularExpression(body, flags) => mk!(self, ExprDef::ConstExpr(Const::RegExp(body, flags))),
             TokenData::BooleanLiteral(val) => mk!(self, ExprDef::ConstExpr(Const::Bool(val))),
             TokenData::Identifier(ref s) if s == "undefined" => {
                 mk!(self, ExprDef::ConstExpr(Const::Undefined))
             }
+            TokenData::Identifier(ref s) if s == "await" => {
+                mk!(self, ExprDef::AwaitExpr(Box::new(self.parse()?)))
+            }
+            TokenData::Identifier(ref s) if s == "async" && self.is_async_function()? => {
+                let func = self.parse()?;
+                let source = self.source_from(start);
+                match func.def {
+                    ExprDef::FunctionDeclExpr(ref name, ref args, ref body, _) => mk!(
+                        self,
+                        ExprDef::AsyncFunctionDeclExpr(name.clone(), args.clone(), body.clone(), source),
+                        token
+                    ),
+                    ExprDef::ArrowFunctionDeclExpr(ref args, ref body, _) => mk!(
+                        self,
+                        ExprDef::AsyncArrowFunctionDeclExpr(args.clone(), body.clone(), source),
+                        token
+                    ),
+                    _ => return Err(ParseError::ExpectedExpr("async function", Box::new(func))),
+                }
+            }
             TokenData::Identifier(s) => mk!(self, ExprDef::LocalExpr(s)),
             TokenData::Keyword(keyword) => self.parse_struct(keyword)?,
             TokenData::Punctuator(Punctuator::OpenParen) => {
@@ -314,9 +561,10 @@ impl Parser {
                     {
                         self.pos += 2;
                         let expr = self.parse()?;
+                        let source = self.source_from(start);
                         mk!(
                             self,
-                            ExprDef::ArrowFunctionDeclExpr(Vec::new(), Box::new(expr)),
+                            ExprDef::ArrowFunctionDeclExpr(Vec::new(), Box::new(expr), source),
                             token
                         )
                     }
@@ -380,9 +628,10 @@ impl Parser {
                                     "arrow function"
                                 )?;
                                 let expr = self.parse()?;
+                                let source = self.source_from(start);
                                 mk!(
                                     self,
-                                    ExprDef::ArrowFunctionDeclExpr(args, Box::new(expr)),
+                                    ExprDef::ArrowFunctionDeclExpr(args, Box::new(expr), source),
                                     token
                                 )
                             }
@@ -443,7 +692,7 @@ impl Parser {
                 self.pos += 1;
                 mk!(
                     self,
-                    ExprDef::ObjectDeclExpr(Box::new(BTreeMap::new())),
+                    ExprDef::ObjectDeclExpr(Box::default()),
                     token
                 )
             }
@@ -451,7 +700,7 @@ impl Parser {
                 if self.get_token(self.pos + 1)?.data
                     == TokenData::Punctuator(Punctuator::Colon) =>
             {
-                let mut map = Box::new(BTreeMap::new());
+                let mut map = Box::new(Vec::new());
                 while self.get_token(self.pos - 1)?.data
                     == TokenData::Punctuator(Punctuator::Comma)
                     || map.len() == 0
@@ -477,7 +726,7 @@ impl Parser {
                         "object declaration"
                     )?;
                     let value = self.parse()?;
-                    map.insert(name, value);
+                    map.push((name, value));
                     self.pos += 1;
                 }
                 mk!(self, ExprDef::ObjectDeclExpr(map), token)
@@ -518,6 +767,11 @@ impl Parser {
             ),
             _ => return Err(ParseError::Expected(Vec::new(), token.clone(), "script")),
         };
+        // 没有位置的表达式从它的第一个Token开始
+        let expr = match expr.pos {
+            Some(_) => expr,
+            None => expr.at(token.pos),
+        };
         if self.pos >= self.tokens.len() {
             Ok(expr)
         } else {
@@ -525,7 +779,26 @@ impl Parser {
         }
     }
 
+    /// Check if the tokens after an `async` identifier start a function, rather than `async` being a plain identifier
+    fn is_async_function(&self) -> Result<bool, ParseError> {
+        match self.get_token(self.pos)?.data {
+            TokenData::Keyword(Keyword::Function) => Ok(true),
+            TokenData::Identifier(_) => {
+                Ok(self.get_token(self.pos + 1)?.data == TokenData::Punctuator(Punctuator::Arrow))
+            }
+            TokenData::Punctuator(Punctuator::OpenParen) => {
+                let mut pos = self.pos + 1;
+                while self.get_token(pos)?.data != TokenData::Punctuator(Punctuator::CloseParen) {
+                    pos += 1;
+                }
+                Ok(self.get_token(pos + 1)?.data == TokenData::Punctuator(Punctuator::Arrow))
+            }
+            _ => Ok(false),
+        }
+    }
+
     fn parse_next(&mut self, expr: Expr) -> ParseResult {
+        let start = expr.pos.clone();
         let next = self.get_token(self.pos)?;
         let mut carry_on = true;
         let mut result = expr.clone();
@@ -537,7 +810,16 @@ impl Parser {
                     TokenData::Identifier(ref s) => {
                         result = mk!(
                             self,
-                            ExprDef::GetConstFieldExpr(Box::new(expr), s.to_string())
+                            ExprDef::GetConstFieldExpr(Box::new(expr), s.to_string()),
+                            tk
+                        )
+                    }
+                    // Reserved words are allowed as property names, e.g. `promise.catch`
+                    TokenData::Keyword(ref k) => {
+                        result = mk!(
+                            self,
+                            ExprDef::GetConstFieldExpr(Box::new(expr), k.to_string()),
+                            tk
                         )
                     }
                     _ => {
@@ -612,14 +894,17 @@ impl Parser {
                 result = mk!(self, ExprDef::AssignExpr(Box::new(expr), Box::new(next)));
             }
             TokenData::Punctuator(Punctuator::Arrow) => {
+                // The parameter is the identifier just before the arrow
+                let start = self.pos - 1;
                 self.pos += 1;
                 let mut args = Vec::with_capacity(1);
                 match result.def {
                     ExprDef::LocalExpr(ref name) => args.push((*name).clone()),
-                    _ => return Err(ParseError::ExpectedExpr("identifier", result)),
+                    _ => return Err(ParseError::ExpectedExpr("identifier", Box::new(result))),
                 }
                 let next = self.parse()?;
-                result = mk!(self, ExprDef::ArrowFunctionDeclExpr(args, Box::new(next)));
+                let source = self.source_from(start);
+                result = mk!(self, ExprDef::ArrowFunctionDeclExpr(args, Box::new(next), source));
             }
             TokenData::Punctuator(Punctuator::Add) => {
                 result = self.binop(BinOp::Num(NumOp::Add), expr)?
@@ -681,6 +966,9 @@ impl Parser {
             TokenData::Punctuator(Punctuator::GreaterThanOrEq) => {
                 result = self.binop(BinOp::Comp(CompOp::GreaterThanOrEqual), expr)?
             }
+            TokenData::Keyword(Keyword::InstanceOf) => {
+                result = self.binop(BinOp::Comp(CompOp::InstanceOf), expr)?
+            }
             TokenData::Punctuator(Punctuator::Inc) => {
                 result = mk!(
                     self,
@@ -695,6 +983,10 @@ impl Parser {
             }
             _ => carry_on = false,
         };
+        // 由左边的表达式继续的表达式从左边的表达式开始
+        if result.pos.is_none() {
+            result.pos = start;
+        }
         if carry_on && self.pos < self.tokens.len() {
             self.parse_next(result)
         } else {
@@ -0,0 +1,154 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::eval;
+
+#[test]
+fn check_length() {
+    assert_eq!(eval("const a = [1, 2]; a[5] = 3; a.length;"), "6");
+    assert_eq!(eval("const a = [1, 2, 3]; a.length = 1; a[1];"), "undefined");
+    assert_eq!(eval("const a = [1, 2, 3]; a.length = 1; a.join();"), "1");
+    assert_eq!(eval("const a = new Array(3); a.length;"), "3");
+    assert_eq!(eval("Array(1, 2).join(\"-\");"), "1-2");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "new Array(-1);").is_err());
+    assert!(forward(&mut engine, "const a = []; a.length = 1.5;").is_err());
+    // Shrinking the length stops at the first element from the end which can't be deleted
+    let script = "const b = [1, 2, 3]; Object.defineProperty(b, \"1\", { configurable: false }); b.length = 0; b.length + \"|\" + b.join();";
+    assert_eq!(forward(&mut engine, script).unwrap().to_string(), "2|1,2");
+    assert_eq!(forward(&mut engine, "Reflect.set(b, \"length\", 1) + \"|\" + b.length;").unwrap().to_string(), "false|2");
+    let err = forward(&mut engine, "Object.defineProperty(b, \"length\", { value: 0 });").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot redefine property: length");
+}
+
+#[test]
+fn check_statics() {
+    assert_eq!(eval("Array.isArray([]);"), "true");
+    assert_eq!(eval("Array.isArray({ length: 0 });"), "false");
+    assert_eq!(eval("Array.of(7).length;"), "1");
+    assert_eq!(eval("Array.from([\"a\", \"b\", \"c\"]).join();"), "a,b,c");
+    assert_eq!(eval("const o = { length: 2, \"0\": \"x\", \"1\": \"y\" }; Array.from(o).join();"), "x,y");
+    assert_eq!(eval("Array.from([1, 2], function(x) { return x * 2; }).join();"), "2,4");
+}
+
+fn from_async(call: &str) -> String {
+    let mut engine: Interpreter = Executor::new();
+    let script = format!("let out = \"pending\";
+    {}.then(function(a) {{ out = a.join(); }}, function(e) {{ out = \"rejected: \" + e; }});", call);
+    forward(&mut engine, &script).unwrap();
+    engine.run_jobs().unwrap();
+    forward(&mut engine, "out;").unwrap().to_string()
+}
+
+#[test]
+fn check_from_async() {
+    assert_eq!(from_async("Array.fromAsync([Promise.resolve(1), 2])"), "1,2");
+    assert_eq!(from_async("Array.fromAsync({ length: 2, \"0\": \"x\", \"1\": Promise.resolve(\"y\") })"), "x,y");
+    assert_eq!(from_async("Array.fromAsync([1, 2], function(x, i) { return Promise.resolve(x * 10 + i); })"), "10,21");
+    assert_eq!(from_async("Array.fromAsync([Promise.reject(\"bad\")])"), "rejected: bad");
+    assert!(from_async("Array.fromAsync(null)").starts_with("rejected: TypeError"));
+    // Async iterables are read through Symbol.asyncIterator, ahead of Symbol.iterator
+    let source = "const source = { i: 0 };
+    source[Symbol.iterator] = function() { return [\"sync\"][Symbol.iterator](); };
+    source[Symbol.asyncIterator] = function() {
+        return { next: function() { source.i = source.i + 1; return Promise.resolve({ value: source.i, done: source.i > 3 }); } };
+    };
+    Array.fromAsync(source)";
+    assert_eq!(from_async(source), "1,2,3");
+}
+
+#[test]
+fn check_mutators() {
+    assert_eq!(eval("const a = [1]; a.push(2, 3); a.join();"), "1,2,3");
+    assert_eq!(eval("[1, 2].push(3);"), "3");
+    assert_eq!(eval("const a = [1, 2]; a.pop() + a.length;"), "3");
+    assert_eq!(eval("const a = [1, 2, 3]; const first = a.shift(); first + a.join();"), "12,3");
+    assert_eq!(eval("const a = [3]; a.unshift(1, 2); a.join();"), "1,2,3");
+    assert_eq!(eval("[1, 2, 3].reverse().join();"), "3,2,1");
+    assert_eq!(eval("[1, 2, 3, 4].fill(0, 1, 3).join();"), "1,0,0,4");
+    let script = "const a = [1, 2, 3, 4, 5];
+    const removed = a.splice(1, 2, \"a\", \"b\", \"c\");
+    removed.join() + \"|\" + a.join();";
+    assert_eq!(eval(script), "2,3|1,a,b,c,4,5");
+    assert_eq!(eval("const a = [1, 2, 3]; a.splice(-1); a.join();"), "1,2");
+    // The mutators throw rather than silently leave frozen arrays or read-only lengths half changed
+    let mut engine: Interpreter = Executor::new();
+    let err = forward(&mut engine, "const frozen = Object.freeze([1, 2]); frozen.pop();").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot delete property '1' of object");
+    assert_eq!(forward(&mut engine, "frozen.join();").unwrap().to_string(), "1,2");
+    assert!(forward(&mut engine, "frozen.shift();").is_err());
+    assert_eq!(forward(&mut engine, "frozen.join();").unwrap().to_string(), "1,2");
+    assert!(forward(&mut engine, "frozen.push(3);").is_err());
+    assert!(forward(&mut engine, "frozen.reverse();").is_err());
+    assert_eq!(forward(&mut engine, "frozen.join();").unwrap().to_string(), "1,2");
+    let script = "const fixed = [1]; Object.defineProperty(fixed, \"length\", { writable: false }); fixed.push(2);";
+    let err = forward(&mut engine, script).unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot assign to read only property '1' of object");
+    assert_eq!(forward(&mut engine, "fixed.length;").unwrap().to_string(), "1");
+}
+
+#[test]
+fn check_sort() {
+    assert_eq!(eval("[10, 9, 1].sort().join();"), "1,10,9");
+    assert_eq!(eval("[3, 1, 2].sort(function(a, b) { return a - b; }).join();"), "1,2,3");
+    assert_eq!(eval("[undefined, 2, 1].sort().join();"), "1,2,");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "[2, 1].sort(function() { throw \"stop\"; });").is_err());
+    // Comparison results and lengths are converted with ToNumber, which throws for BigInts and symbols
+    let err = forward(&mut engine, "const a = [2, 1]; a.sort(function() { return 1n; });").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot convert a BigInt value to a number");
+    let err = forward(&mut engine, "Array.prototype.join.call({ length: Symbol() });").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot convert a Symbol value to a number");
+}
+
+#[test]
+fn check_accessors() {
+    assert_eq!(eval("[1, 2, 3, 4].slice(1, -1).join();"), "2,3");
+    assert_eq!(eval("[1].concat([2, 3], 4).join();"), "1,2,3,4");
+    assert_eq!(eval("[1, 2, 3].at(-1);"), "3");
+    assert_eq!(eval("[1, 2, 1].indexOf(1, 1);"), "2");
+    assert_eq!(eval("[1, 2].indexOf(\"1\");"), "-1");
+    assert_eq!(eval("const nan = 0 / 0; const a = [nan]; a.includes(nan);"), "true");
+    assert_eq!(eval("const nan = 0 / 0; const a = [nan]; a.indexOf(nan);"), "-1");
+    assert_eq!(eval("[1, [2, [3, [4]]]].flat(2).join(\"|\");"), "1|2|3|4");
+    assert_eq!(eval("[1, 2].flatMap(function(x) { return [x, x]; }).join();"), "1,1,2,2");
+    assert_eq!(eval("[1, null, 3].toString();"), "1,,3");
+}
+
+#[test]
+fn check_index_arguments() {
+    assert_eq!(eval("[1, 2, 3].slice(\"1\").join();"), "2,3");
+    assert_eq!(eval("[1, 2, 3].at(-1.5);"), "3");
+    assert_eq!(eval("const o = { valueOf: function() { return 2; } }; const a = [1, 2, 3]; a.at(o);"), "3");
+    let mut engine: Interpreter = Executor::new();
+    let err = forward(&mut engine, "const a = [1, 2]; a.slice(Symbol());").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot convert a Symbol value to a number");
+    let err = forward(&mut engine, "\"ab\".slice(Symbol());").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot convert a Symbol value to a number");
+}
+
+#[test]
+fn check_iteration_methods() {
+    assert_eq!(eval("[1, 2, 3].map(function(x) { return x * 2; }).join();"), "2,4,6");
+    assert_eq!(eval("[1, 2, 3, 4].filter(function(x) { return x % 2 == 0; }).join();"), "2,4");
+    assert_eq!(eval("[1, 2, 3].reduce(function(a, b) { return a + b; });"), "6");
+    assert_eq!(eval("[\"a\", \"b\"].reduceRight(function(a, b) { return a + b; }, \"\");"), "ba");
+    assert_eq!(eval("[5, 12, 8].find(function(x) { return x > 6; });"), "12");
+    assert_eq!(eval("[5, 12, 8].findIndex(function(x) { return x > 20; });"), "-1");
+    assert_eq!(eval("[1, 2].some(function(x) { return x > 1; });"), "true");
+    assert_eq!(eval("[1, 2].every(function(x) { return x > 1; });"), "false");
+    assert_eq!(eval("let sum = 0; const a = [1, 2, 3]; a.forEach(function(x) { sum = sum + x; }); sum;"), "6");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "[].reduce(function(a, b) { return a; });").is_err());
+    assert!(forward(&mut engine, "[1].map(1);").is_err());
+}
+
+#[test]
+fn check_iterators() {
+    assert_eq!(eval("const it = [\"a\", \"b\"].keys(); it.next().value + it.next().value;"), "1");
+    assert_eq!(eval("const it = [\"a\", \"b\"].entries(); it.next(); it.next().value.join();"), "1,b");
+    assert_eq!(eval("Array.from([\"a\", \"b\"].entries()).length;"), "2");
+    // The slots the iterator methods carry are internal, not properties
+    assert_eq!(eval("Object.getOwnPropertyNames([].values).join();"), "name");
+}
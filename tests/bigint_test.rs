@@ -0,0 +1,77 @@
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_bigint_literals() {
+    assert_eq!(eval("typeof 1n;"), "bigint");
+    assert_eq!(eval("9007199254740993n;"), "9007199254740993");
+    assert_eq!(eval("0xffn;"), "255");
+    assert_eq!(eval("0o17n;"), "15");
+    assert_eq!(eval("0b101n;"), "5");
+}
+
+#[test]
+fn check_bigint_arithmetic() {
+    assert_eq!(eval("9007199254740993n + 2n;"), "9007199254740995");
+    assert_eq!(eval("18446744073709551615n * 2n;"), "36893488147419103230");
+    assert_eq!(eval("7n / 2n;"), "3");
+    assert_eq!(eval("const a = -7n; const r = a / 2n; r;"), "-3");
+    assert_eq!(eval("const a = -7n; const r = a % 2n; r;"), "-1");
+    assert_eq!(eval("\"id:\" + 12n;"), "id:12");
+    assert_eq!(eval_err("1n + 1;"), "TypeError: Cannot mix BigInt and other types, use explicit conversions");
+    assert_eq!(eval_err("1n / 0n;"), "RangeError: Division by zero");
+    assert_eq!(eval_err("+1n;"), "TypeError: Cannot convert a BigInt value to a number");
+}
+
+#[test]
+fn check_bigint_bitwise() {
+    assert_eq!(eval("1n << 70n;"), "1180591620717411303424");
+    assert_eq!(eval("const a = -9n; const r = a >> 1n; r;"), "-5");
+    assert_eq!(eval("const a = -1n; const r = a & 255n; r;"), "255");
+    assert_eq!(eval("6n ^ 3n;"), "5");
+    assert_eq!(eval_err("1n | 1;"), "TypeError: Cannot mix BigInt and other types, use explicit conversions");
+}
+
+#[test]
+fn check_bigint_comparison() {
+    assert_eq!(eval("1n == 1;"), "true");
+    assert_eq!(eval("1n === 1;"), "false");
+    assert_eq!(eval("1n === 1n;"), "true");
+    assert_eq!(eval("2n == \"2\";"), "true");
+    assert_eq!(eval("2n < 3;"), "true");
+    assert_eq!(eval("2n > 1.5;"), "true");
+    assert_eq!(eval("2n < \"10\";"), "true");
+    assert_eq!(eval("9007199254740993n > 9007199254740992;"), "true");
+}
+
+#[test]
+fn check_bigint_function() {
+    assert_eq!(eval("BigInt(42);"), "42");
+    assert_eq!(eval("BigInt(\" 0x10 \");"), "16");
+    assert_eq!(eval("BigInt(true);"), "1");
+    assert_eq!(eval("(255n).toString(16);"), "ff");
+    assert_eq!(eval("(255n).toString();"), "255");
+    assert_eq!(eval("BigInt.asIntN(8, 255n);"), "-1");
+    assert_eq!(eval("const a = -1n; BigInt.asUintN(64, a);"), "18446744073709551615");
+    assert_eq!(
+        eval_err("BigInt(1.5);"),
+        "RangeError: The number 1.5 cannot be converted to a BigInt because it is not an integer"
+    );
+    assert_eq!(eval_err("BigInt(\"1.5\");"), "SyntaxError: Cannot convert 1.5 to a BigInt");
+    assert_eq!(eval_err("BigInt(undefined);"), "TypeError: Cannot convert undefined to a BigInt");
+    assert_eq!(eval_err("new BigInt(1);"), "TypeError: BigInt is not a constructor");
+    // Bit counts and radixes are converted with ToNumber, which throws for BigInts and symbols
+    assert_eq!(eval_err("BigInt.asIntN(8n, 255n);"), "TypeError: Cannot convert a BigInt value to a number");
+    assert_eq!(eval("BigInt.asUintN(undefined, 5n);"), "0");
+    assert_eq!(eval_err("BigInt.asIntN(-1, 5n);"), "RangeError: Invalid value: not (convertible to) a safe integer");
+    assert_eq!(eval_err("(255n).toString(Symbol());"), "TypeError: Cannot convert a Symbol value to a number");
+}
+
+#[test]
+fn check_bigint_json() {
+    assert_eq!(eval_err("JSON.stringify(1n);"), "TypeError: Do not know how to serialize a BigInt");
+    assert_eq!(
+        eval_err("JSON.stringify({ id: 1n });"),
+        "TypeError: Do not know how to serialize a BigInt"
+    );
+}
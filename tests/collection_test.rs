@@ -0,0 +1,93 @@
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_map() {
+    let script = "const m = new Map([[1, \"a\"], [\"1\", \"b\"]]);
+    m.set(NaN, 3).set(-0, 4);
+    const r = [m.size, m.get(1), m.get(\"1\"), m.get(NaN), m.get(0), m.has(+0), m.get(2)];
+    r.join();";
+    assert_eq!(eval(script), "4,a,b,3,4,true,");
+    let script = "const m = new Map(); const k = {};
+    m.set(k, 1);
+    m.set({}, 2);
+    const r = [m.get(k), m.size, m.get({}), m.delete(k), m.delete(k), m.size];
+    r.join();";
+    assert_eq!(eval(script), "1,2,,true,false,1");
+    assert_eq!(eval("const m = new Map([[1, 2]]); m.clear(); m.size;"), "0");
+    assert_eq!(eval("const m = new Map(); Object.prototype.toString.call(m);"), "[object Map]");
+    assert_eq!(eval("Map.prototype.constructor === Map;"), "true");
+    assert_eq!(eval_err("Map();"), "TypeError: Constructor Map requires 'new'");
+    assert_eq!(eval_err("new Map([1]);"), "TypeError: Iterator value 1 is not an entry object");
+    assert_eq!(eval_err("Map.prototype.get.call({}, 1);"), "TypeError: Map.prototype.get requires that 'this' be a Map");
+}
+
+#[test]
+fn check_map_iteration() {
+    let script = "const m = new Map([[\"a\", 1], [\"b\", 2], [\"c\", 3]]);
+    m.delete(\"b\");
+    m.set(\"d\", 4);
+    m.set(\"a\", 9);
+    Array.from(m).join(\"|\");";
+    assert_eq!(eval(script), "a,9|c,3|d,4");
+    let script = "const m = new Map([[1, 2], [3, 4]]);
+    const r = [Array.from(m.keys()).join(), Array.from(m.values()).join(), Map.prototype[Symbol.iterator] === Map.prototype.entries];
+    r.join();";
+    assert_eq!(eval(script), "1,3,2,4,true");
+    let script = "const m = new Map([[1, 1]]); const out = [];
+    m.forEach(function(v, k, map) { out.push(k); if (k < 3) { map.set(k + 1, v); } map.delete(k); });
+    const r = [out.join(), m.size];
+    r.join();";
+    assert_eq!(eval(script), "1,2,3,0");
+    // An iterator sees entries added after it was made, until it is exhausted
+    let script = "const m = new Map(); const it = m.keys();
+    m.set(1, 1);
+    const first = it.next().value;
+    it.next();
+    m.set(2, 2);
+    const r = [first, it.next().done];
+    r.join();";
+    assert_eq!(eval(script), "1,true");
+    assert_eq!(eval("const m = new Map(); Object.prototype.toString.call(m.entries());"), "[object Map Iterator]");
+}
+
+#[test]
+fn check_set() {
+    let script = "const s = new Set([1, 2, 2, 3, NaN, NaN]);
+    const r = [s.size, s.has(NaN), s.has(4), Array.from(s).join(\"|\")];
+    r.join();";
+    assert_eq!(eval(script), "4,true,false,1|2|3|NaN");
+    let script = "const s = new Set([1]); const out = [];
+    s.forEach(function(v, k, set) { out.push(v + k); if (v < 3) { set.add(v + 1); } });
+    out.join();";
+    assert_eq!(eval(script), "2,4,6");
+    let script = "const s = new Set([\"a\", \"b\"]);
+    const r = [s.delete(\"a\"), s.delete(\"a\"), Array.from(s.entries()).join(\"|\"), Set.prototype.keys === Set.prototype.values];
+    r.join();";
+    assert_eq!(eval(script), "true,false,b,b,true");
+    assert_eq!(eval("const s = new Set(\"hello\"); s.size;"), "4");
+    assert_eq!(eval("const s = new Set(); Object.prototype.toString.call(s.values());"), "[object Set Iterator]");
+    assert_eq!(eval_err("Set.prototype.add.call(new Map(), 1);"), "TypeError: Set.prototype.add requires that 'this' be a Set");
+}
+
+#[test]
+fn check_weak_collections() {
+    let script = "const wm = new WeakMap(); const k = {}; const f = function() {};
+    wm.set(k, 1).set(f, 2);
+    const r = [wm.get(k), wm.get(f), wm.has(k), wm.delete(k), wm.has(k), wm.get({}), wm.has(1)];
+    r.join();";
+    assert_eq!(eval(script), "1,2,true,true,false,,false");
+    let script = "const k = {}; const wm = new WeakMap([[k, \"v\"]]); const other = new WeakMap();
+    const r = [wm.get(k), other.has(k), Object.keys(k).length];
+    r.join();";
+    assert_eq!(eval(script), "v,false,0");
+    let script = "const ws = new WeakSet(); const k = {};
+    ws.add(k);
+    const r = [ws.has(k), ws.has({}), ws.delete(k), ws.has(k)];
+    r.join();";
+    assert_eq!(eval(script), "true,false,true,false");
+    assert_eq!(eval("const ws = new WeakSet(); Object.prototype.toString.call(ws);"), "[object WeakSet]");
+    assert_eq!(eval_err("const wm = new WeakMap(); wm.set(1, 2);"), "TypeError: Invalid value used as weak map key: 1");
+    assert_eq!(eval_err("const ws = new WeakSet(); ws.add(\"x\");"), "TypeError: Invalid value used in weak set: x");
+    assert_eq!(eval_err("WeakSet();"), "TypeError: Constructor WeakSet requires 'new'");
+}
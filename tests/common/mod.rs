@@ -0,0 +1,29 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use engine::engine::{forward, run_script};
+use engine::exec::{Executor, Interpreter};
+use engine::js::promise::{promise_state, PromiseState};
+use engine::js::value::Value;
+
+/// Run a script in a new interpreter, along with the jobs it queues, and get its completion value as a string
+pub fn eval(script: &str) -> String {
+    run_script(script.to_string()).to_string()
+}
+
+/// Run a script in a new interpreter that is expected to throw, and get the thrown value as a string
+pub fn eval_err(script: &str) -> String {
+    let mut engine: Interpreter = Executor::new();
+    forward(&mut engine, script).unwrap_err().to_string()
+}
+
+/// Import a module and run the jobs it queues, then get its namespace object or the error it was rejected with
+pub fn import(engine: &mut Interpreter, specifier: &str) -> Result<Value, Value> {
+    let promise = engine.import_module(specifier)?;
+    engine.run_jobs()?;
+    match promise_state(&promise) {
+        PromiseState::Fulfilled(namespace) => Ok(namespace),
+        PromiseState::Rejected(reason) => Err(reason),
+        PromiseState::Pending => panic!("{} never finished evaluating", specifier),
+    }
+}
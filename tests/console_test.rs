@@ -0,0 +1,129 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+use engine::js::console::{LogLevel, MemorySink};
+use engine::js::date::FixedClock;
+
+/// Run a script, returning what it wrote to the console
+fn console_output(script: &str) -> MemorySink {
+    let mut engine: Interpreter = Executor::new();
+    let sink = MemorySink::new();
+    engine.set_console_sink(Box::new(sink.clone()));
+    engine.set_clock(Box::new(FixedClock::new(0.0, 0)));
+    forward(&mut engine, script).unwrap();
+    sink
+}
+
+fn output(script: &str) -> String {
+    console_output(script).output()
+}
+
+#[test]
+fn check_format_specifiers() {
+    assert_eq!(output("console.log(\"%s is %d years\", \"Bob\", 42);"), "Bob is 42 years");
+    assert_eq!(output("console.log(\"%i|%f|%d\", 3.9, \"1.5\", 10n);"), "3|1.5|10n");
+    assert_eq!(output("console.log(\"%o and %O\", [1], { a: \"x\" });"), "[ 1 ] and { a: 'x' }");
+    assert_eq!(output("console.log(\"%cstyled\", \"color: red\");"), "styled");
+    assert_eq!(output("console.log(\"100%% %s\", \"done\", \"extra\", 5);"), "100% done extra 5");
+    // Specifiers without an argument are left as they are
+    assert_eq!(output("console.log(\"%s and %s\", \"one\");"), "one and %s");
+    assert_eq!(output("console.log(1, \"a\", null, undefined, -0, 10n, Symbol(\"s\"));"), "1 a null undefined -0 10n Symbol(s)");
+}
+
+#[test]
+fn check_inspect() {
+    assert_eq!(
+        output("console.log({ a: 1, b: \"x\", c: [1, 2, { d: { e: 1 } }] });"),
+        "{ a: 1, b: 'x', c: [ 1, 2, { d: [Object] } ] }"
+    );
+    assert_eq!(output("console.log([], {}, Object.create(null), { \"a-b\": 1 });"), "[] {} [Object: null prototype] {} { 'a-b': 1 }");
+    assert_eq!(output("function Foo() { this.a = 1; } console.log(new Foo());"), "Foo { a: 1 }");
+    assert_eq!(
+        output("console.log(function foo() {}, () => 1, new Map([[1, \"a\"]]), new Set([1, 2]));"),
+        "[Function: foo] [Function (anonymous)] Map(1) { 1 => 'a' } Set(2) { 1, 2 }"
+    );
+    assert_eq!(
+        output("console.log(new Date(0), /a+/g, new Number(3), new String(\"ab\"), new Uint8Array(2));"),
+        "1970-01-01T00:00:00.000Z /a+/g [Number: 3] [String: 'ab'] Uint8Array(2) [ 0, 0 ]"
+    );
+    assert_eq!(output("console.log(Promise.resolve(4), Object.defineProperty({}, \"g\", { get: function() {}, enumerable: true }));"), "Promise { 4 } { g: [Getter] }");
+    assert_eq!(output("const o = { x: 1 }; o.self = o; console.log(o);"), "<ref *1> { x: 1, self: [Circular *1] }");
+    assert_eq!(output("const a = [1]; const b = { a: a }; a.push(b); console.log(b);"), "<ref *1> { a: [ 1, [Circular *1] ] }");
+    assert_eq!(
+        output("console.log({ first: \"aaaaaaaaaaaaaaaaaaaa\", second: \"bbbbbbbbbbbbbbbbbbbbbbbb\", third: [1, 2, 3] });"),
+        "{\n  first: 'aaaaaaaaaaaaaaaaaaaa',\n  second: 'bbbbbbbbbbbbbbbbbbbbbbbb',\n  third: [ 1, 2, 3 ]\n}"
+    );
+}
+
+#[test]
+fn check_levels() {
+    let sink = console_output("console.log(1); console.info(2); console.debug(3); console.warn(4); console.error(5); console.dir(\"s\");");
+    assert_eq!(
+        sink.messages(),
+        vec![
+            (LogLevel::Log, "1".to_string()),
+            (LogLevel::Info, "2".to_string()),
+            (LogLevel::Debug, "3".to_string()),
+            (LogLevel::Warn, "4".to_string()),
+            (LogLevel::Error, "5".to_string()),
+            (LogLevel::Log, "'s'".to_string()),
+        ]
+    );
+    assert!(!LogLevel::Info.is_error());
+    assert!(LogLevel::Warn.is_error());
+    let sink = console_output("function f() { console.trace(\"here %d\", 1); } f();");
+    assert_eq!(sink.messages()[0].0, LogLevel::Trace);
+    assert_eq!(sink.output(), "Trace: here 1\n    at f (<anonymous>:1:24)\n    at <anonymous>:1:47");
+    assert_eq!(output("console.dir({ a: { b: { c: 1 } } }, { depth: 0 });"), "{ a: [Object] }");
+    assert_eq!(output("console.dir({ a: { b: { c: { d: 1 } } } }, { depth: null });"), "{ a: { b: { c: { d: 1 } } } }");
+}
+
+#[test]
+fn check_groups() {
+    let script = "console.group(\"Outer\");
+    console.log(\"in\");
+    console.group();
+    console.info(\"two\\nlines\");
+    console.groupEnd();
+    console.groupEnd();
+    console.groupEnd();
+    console.log(\"out\");";
+    assert_eq!(output(script), "Outer\n  in\n    two\n    lines\nout");
+}
+
+#[test]
+fn check_counters_and_timers() {
+    let script = "console.count(); console.count(); console.count(\"x\"); console.countReset(); console.count(); console.countReset(\"nope\");";
+    assert_eq!(output(script), "default: 1\ndefault: 2\nx: 1\ndefault: 1\nWarning: Count for 'nope' does not exist");
+    let script = "console.time(\"t\"); console.time(\"t\"); console.timeLog(\"t\", \"data\", 1); console.timeEnd(\"t\"); console.timeEnd(\"t\");";
+    assert_eq!(
+        output(script),
+        "Warning: Label 't' already exists for console.time()\nt: 0.000ms data 1\nt: 0.000ms\nWarning: No such label 't' for console.timeEnd()"
+    );
+    let sink = console_output("console.timeLog();");
+    assert_eq!(sink.messages()[0].0, LogLevel::Warn);
+}
+
+#[test]
+fn check_assert_and_table() {
+    let sink = console_output("console.assert(true, \"no\"); console.assert(false, \"failed %s\", \"here\"); console.assert(0, { a: 1 }); console.assert(false);");
+    assert_eq!(sink.output(), "Assertion failed: failed here\nAssertion failed { a: 1 }\nAssertion failed");
+    assert!(sink.messages().iter().all(|(level, _)| *level == LogLevel::Error));
+    assert_eq!(
+        output("console.table([{ a: 1, b: \"x\" }, { a: 2, c: true }]);"),
+        "┌─────────┬───┬─────┬──────┐
+│ (index) │ a │  b  │  c   │
+├─────────┼───┼─────┼──────┤
+│    0    │ 1 │ 'x' │      │
+│    1    │ 2 │     │ true │
+└─────────┴───┴─────┴──────┘"
+    );
+    assert_eq!(
+        output("console.table({ r: 5 });"),
+        "┌─────────┬────────┐
+│ (index) │ Values │
+├─────────┼────────┤
+│    r    │   5    │
+└─────────┴────────┘"
+    );
+    assert_eq!(output("console.table(\"not tabular\");"), "not tabular");
+}
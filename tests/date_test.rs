@@ -0,0 +1,115 @@
+use engine::engine::{forward, run_script};
+use engine::exec::{Executor, Interpreter};
+use engine::js::date::FixedClock;
+
+mod common;
+use common::eval_err;
+
+/// 2024-01-02T09:00:00.000Z, a Tuesday
+const NOW: f64 = 1_704_186_000_000.0;
+
+/// Run a script with the clock stopped at `NOW`, in a time zone an hour ahead of UTC
+fn eval(script: &str) -> String {
+    let mut engine: Interpreter = Executor::new();
+    engine.set_clock(Box::new(FixedClock::new(NOW, 60).with_name("Central European Standard Time")));
+    forward(&mut engine, script).unwrap().to_string()
+}
+
+#[test]
+fn check_date_constructor() {
+    assert_eq!(eval("Date.now();"), "1704186000000");
+    assert_eq!(eval("const d = new Date(); d.getTime();"), "1704186000000");
+    assert_eq!(eval("const d = new Date(0); d.getTime();"), "0");
+    assert_eq!(eval("const d = new Date(2024, 0, 2, 10); d.getTime();"), "1704186000000");
+    assert_eq!(eval("const d = new Date(99, 11); d.getFullYear();"), "1999");
+    assert_eq!(eval("const d = new Date(2024, 13, 1); d.getMonth();"), "1");
+    assert_eq!(eval("const d = new Date(\"2024-01-02\"); d.getTime();"), "1704153600000");
+    assert_eq!(eval("const a = new Date(5); const b = new Date(a); b.getTime();"), "5");
+    assert_eq!(eval("const d = new Date(8.64e15 + 1); d.getTime();"), "NaN");
+    assert_eq!(eval("Date();"), "Tue Jan 02 2024 10:00:00 GMT+0100 (Central European Standard Time)");
+    assert_eq!(eval("Date.length;"), "7");
+    assert_eq!(eval("const d = new Date(); Object.prototype.toString.call(d);"), "[object Date]");
+    assert_eq!(eval("Date.UTC(2024, 0, 2, 9);"), "1704186000000");
+    assert_eq!(eval("Date.UTC(2024);"), "1704067200000");
+}
+
+#[test]
+fn check_date_getters() {
+    let script = "const d = new Date(2024, 1, 29, 23, 59, 58, 7);
+    const r = [d.getFullYear(), d.getMonth(), d.getDate(), d.getDay(), d.getHours(), d.getMinutes(), d.getSeconds(), d.getMilliseconds()];
+    r.join();";
+    assert_eq!(eval(script), "2024,1,29,4,23,59,58,7");
+    let script = "const d = new Date(2024, 0, 1, 0, 30);
+    const r = [d.getUTCFullYear(), d.getUTCMonth(), d.getUTCDate(), d.getUTCDay(), d.getUTCHours(), d.getUTCMinutes(), d.getTimezoneOffset(), d.getYear()];
+    r.join();";
+    assert_eq!(eval(script), "2023,11,31,0,23,30,-60,124");
+    assert_eq!(eval("const d = new Date(-1); d.getUTCFullYear() + \",\" + d.getUTCMilliseconds();"), "1969,999");
+    assert_eq!(eval("const d = new Date(NaN); d.getDate();"), "NaN");
+    assert_eq!(eval_err("Date.prototype.getTime.call({});"), "TypeError: this is not a Date object.");
+}
+
+#[test]
+fn check_date_setters() {
+    let script = "const d = new Date(2024, 0, 31);
+    d.setMonth(1);
+    const r = [d.getMonth(), d.getDate()];
+    r.join();";
+    assert_eq!(eval(script), "2,2");
+    let script = "const d = new Date(0);
+    const r = [d.setUTCHours(25, 1), d.getUTCDate(), d.setUTCMilliseconds(-1), d.getUTCSeconds()];
+    r.join();";
+    assert_eq!(eval(script), "90060000,2,90059999,59");
+    assert_eq!(eval("const d = new Date(NaN); d.setHours(1);"), "NaN");
+    assert_eq!(eval("const d = new Date(NaN); d.setFullYear(2000); d.toISOString();"), "2000-01-01T00:00:00.000Z");
+    assert_eq!(eval("const d = new Date(0); d.setTime(\"12\"); d.getTime();"), "12");
+    assert_eq!(eval("const d = new Date(2024, 5, 1); d.setYear(95); d.getFullYear();"), "1995");
+    assert_eq!(eval("const d = new Date(0); d.setDate(); d.getTime();"), "NaN");
+}
+
+#[test]
+fn check_date_formatting() {
+    let script = "const d = new Date(2024, 0, 2, 10, 5, 6, 7);
+    const r = [d.toISOString(), d.toUTCString(), d.toDateString(), d.toTimeString()];
+    r.join(\"|\");";
+    assert_eq!(
+        eval(script),
+        "2024-01-02T09:05:06.007Z|Tue, 02 Jan 2024 09:05:06 GMT|Tue Jan 02 2024|10:05:06 GMT+0100 (Central European Standard Time)"
+    );
+    let script = "const d = new Date(2024, 0, 2, 15, 5, 6);
+    const r = [d.toLocaleString(), d.toLocaleDateString(), d.toLocaleTimeString()];
+    r.join(\"|\");";
+    assert_eq!(eval(script), "1/2/2024, 3:05:06 PM|1/2/2024|3:05:06 PM");
+    assert_eq!(eval("const d = new Date(Date.UTC(-1, 0)); d.toISOString();"), "-000001-01-01T00:00:00.000Z");
+    assert_eq!(eval("const d = new Date(Date.UTC(10000, 0)); d.toISOString();"), "+010000-01-01T00:00:00.000Z");
+    assert_eq!(eval("const d = new Date(NaN); d.toString();"), "Invalid Date");
+    assert_eq!(eval_err("const d = new Date(NaN); d.toISOString();"), "RangeError: Invalid time value");
+    assert_eq!(eval("const d = new Date(0); d.toJSON();"), "1970-01-01T00:00:00.000Z");
+    assert_eq!(eval("const d = new Date(NaN); d.toJSON();"), "null");
+}
+
+#[test]
+fn check_date_conversion() {
+    assert_eq!(eval("const d = new Date(0); d + 1;"), "Thu Jan 01 1970 01:00:00 GMT+0100 (Central European Standard Time)1");
+    assert_eq!(eval("const d = new Date(5); d - 1;"), "4");
+    assert_eq!(eval("const a = new Date(5); const b = new Date(7); b > a;"), "true");
+    assert_eq!(eval("const d = new Date(5); d[Symbol.toPrimitive](\"number\");"), "5");
+    assert_eq!(eval_err("const d = new Date(5); d[Symbol.toPrimitive](\"nope\");"), "TypeError: Invalid hint");
+}
+
+#[test]
+fn check_date_parse() {
+    assert_eq!(eval("Date.parse(\"2024-01-02T09:00:00Z\");"), "1704186000000");
+    assert_eq!(eval("Date.parse(\"2024-01-02T10:00:00.000+01:00\");"), "1704186000000");
+    assert_eq!(eval("Date.parse(\"2024-01-02T10:00\");"), "1704186000000");
+    assert_eq!(eval("Date.parse(\"2024-01\");"), "1704067200000");
+    assert_eq!(eval("Date.parse(\"+002024-01-02T09:00:00Z\");"), "1704186000000");
+    assert_eq!(eval("Date.parse(\"Tue, 02 Jan 2024 09:00:00 GMT\");"), "1704186000000");
+    assert_eq!(eval("Date.parse(\"Tue, 2 Jan 2024 10:00:00 +0100\");"), "1704186000000");
+    assert_eq!(eval("Date.parse(\"Tue Jan 02 2024 10:00:00 GMT+0100 (Central European Standard Time)\");"), "1704186000000");
+    assert_eq!(eval("Date.parse(\"2024-02-30\");"), "NaN");
+    assert_eq!(eval("Date.parse(\"2024-01-02T25:00Z\");"), "NaN");
+    assert_eq!(eval("Date.parse(\"-000000-01-01T00:00:00Z\");"), "NaN");
+    assert_eq!(eval("Date.parse(\"not a date\");"), "NaN");
+    // Dates with no clock injected use the system clock
+    assert_eq!(run_script("Date.now() > 1704186000000;".to_string()).to_string(), "true");
+}
@@ -0,0 +1,109 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+use engine::js::error::stack_frames;
+use engine::module::MemoryModuleLoader;
+
+mod common;
+use common::{eval, eval_err, import};
+
+#[test]
+fn check_error_objects() {
+    let script = "const e = new TypeError(\"bad\");
+    const r = [e.name, e.message, e.toString(), e instanceof TypeError, e instanceof Error];
+    r.join();";
+    assert_eq!(eval(script), "TypeError,bad,TypeError: bad,true,true");
+    assert_eq!(eval("new Error().toString();"), "Error");
+    assert_eq!(eval("const e = RangeError(\"x\"); e instanceof RangeError;"), "true");
+    assert_eq!(eval("const e = new Error(\"m\"); e.name = \"Custom\"; e.toString();"), "Custom: m");
+    assert_eq!(eval("Object.keys(new Error(\"m\")).length;"), "0");
+    assert_eq!(eval("Object.prototype.toString.call(new URIError());"), "[object Error]");
+}
+
+#[test]
+fn check_prototype_chains() {
+    for name in ["EvalError", "RangeError", "ReferenceError", "SyntaxError", "TypeError", "URIError", "AggregateError"] {
+        let script = format!(
+            "const r = [Object.getPrototypeOf({0}) === Error, Object.getPrototypeOf({0}.prototype) === Error.prototype, {0}.prototype.name, {0}.name];
+            r.join();",
+            name
+        );
+        assert_eq!(eval(&script), format!("true,true,{0},{0}", name));
+    }
+    assert_eq!(eval("Object.getPrototypeOf(Error.prototype) === Object.prototype;"), "true");
+    assert_eq!(eval("const r = [Error.length, TypeError.length, AggregateError.length]; r.join();"), "1,1,2");
+}
+
+#[test]
+fn check_cause_and_errors() {
+    assert_eq!(eval("const e = new Error(\"a\", {cause: 5}); e.cause;"), "5");
+    assert_eq!(eval("const e = new Error(\"a\"); e.hasOwnProperty(\"cause\");"), "false");
+    let script = "const e = new AggregateError([1, 2], \"m\");
+    const r = [e.errors.join(), e.message, e.name, e instanceof Error];
+    r.join();";
+    assert_eq!(eval(script), "1,2,m,AggregateError,true");
+    let mut engine: Interpreter = Executor::new();
+    let script = "let any = 0;
+    Promise.any([Promise.reject(1), Promise.reject(2)]).catch(function(e) { any = e; });";
+    forward(&mut engine, script).unwrap();
+    engine.run_jobs().unwrap();
+    let result = forward(&mut engine, "const r = [any instanceof AggregateError, any.errors.join()]; r.join();");
+    assert_eq!(result.unwrap().to_string(), "true,1,2");
+}
+
+#[test]
+fn check_internal_errors() {
+    assert_eq!(eval_err("null.x;"), "TypeError: Cannot read properties of null (reading 'x')");
+    assert_eq!(eval_err("undefined();"), "TypeError: undefined is not a function");
+    assert_eq!(eval_err("missing;"), "ReferenceError: missing is not defined");
+    assert_eq!(eval("typeof missing;"), "undefined");
+    assert_eq!(eval_err("const c = 1; c = 2;"), "TypeError: Assignment to constant variable.");
+    assert_eq!(eval_err("let d = 1; let d = 2;"), "SyntaxError: Identifier 'd' has already been declared");
+    assert_eq!(eval_err("new Array(-1);"), "RangeError: Invalid array length");
+    assert_eq!(eval_err("decodeURI(\"%\");"), "URIError: URI malformed");
+    assert!(eval_err("JSON.parse(\"{\");").starts_with("SyntaxError"));
+    let mut engine: Interpreter = Executor::new();
+    let script = "let caught = 0;
+    async function f() { return null.x; }
+    f().catch(function(e) { caught = e instanceof TypeError; });";
+    forward(&mut engine, script).unwrap();
+    engine.run_jobs().unwrap();
+    assert_eq!(forward(&mut engine, "caught;").unwrap().to_string(), "true");
+}
+
+#[test]
+fn check_stack() {
+    let script = "function make() {\n  return new Error(\"boom\");\n}\nconst e = make();\ne.stack;";
+    assert_eq!(eval(script), "Error: boom\n    at make (<anonymous>:2:10)\n    at <anonymous>:4:11");
+    let script = "function P() { const e = new TypeError(\"t\"); this.stack = e.stack; } const p = new P(); p.stack;";
+    assert_eq!(eval(script), "TypeError: t\n    at new P (<anonymous>:1:26)\n    at <anonymous>:1:80");
+    assert_eq!(eval("const f = () => new Error(\"a\"); f().stack;"), "Error: a\n    at f (<anonymous>:1:17)\n    at <anonymous>:1:33");
+    assert_eq!(eval("Object.keys(new Error(\"x\")).length;"), "0");
+    assert_eq!(eval("const e = new Error(\"x\"); e.hasOwnProperty(\"stack\");"), "true");
+}
+
+#[test]
+fn check_stack_frames() {
+    let mut engine: Interpreter = Executor::new();
+    let script = "function inner() {\n  return null.x;\n}\nfunction outer() {\n  return inner();\n}\nouter();";
+    let err = forward(&mut engine, script).unwrap_err();
+    let frames = stack_frames(&err);
+    let names: Vec<_> = frames.iter().map(|frame| frame.function_name.clone()).collect();
+    assert_eq!(names, vec![Some("inner".to_string()), Some("outer".to_string()), None]);
+    let positions: Vec<_> = frames.iter().map(|frame| (frame.line_number, frame.column_number)).collect();
+    assert_eq!(positions, vec![(2, 15), (5, 10), (7, 1)]);
+    assert_eq!(frames[0].to_string(), "inner (<anonymous>:2:15)");
+    assert_eq!(frames[2].to_string(), "<anonymous>:7:1");
+    assert!(stack_frames(&forward(&mut engine, "1;").unwrap()).is_empty());
+}
+
+#[test]
+fn check_module_stack() {
+    let mut loader = MemoryModuleLoader::new();
+    loader.insert("lib.js", "export function fail() {\n  return undefined();\n}");
+    loader.insert("main.js", "import { fail } from \"./lib.js\";\nfail();");
+    let mut engine: Interpreter = Executor::new();
+    engine.set_module_loader(Box::new(loader));
+    let err = import(&mut engine, "main.js").unwrap_err();
+    let frames: Vec<_> = stack_frames(&err).iter().map(|frame| frame.to_string()).collect();
+    assert_eq!(frames[..2], ["fail (lib.js:2:10)".to_string(), "main.js:2:1".to_string()]);
+}
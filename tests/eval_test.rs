@@ -0,0 +1,60 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_eval() {
+    assert_eq!(eval("eval(\"1 + 2\");"), "3");
+    assert_eq!(eval("typeof eval(\"\");"), "undefined");
+    assert_eq!(eval("eval(5);"), "5");
+    assert_eq!(eval("const o = {}; eval(o) === o;"), "true");
+    assert_eq!(eval("eval(\"function h() { return 7; }\"); h();"), "7");
+    assert_eq!(eval_err("eval(\"1 +\");"), "SyntaxError: AbruptEnd");
+}
+
+#[test]
+fn check_direct_eval() {
+    assert_eq!(eval("function f() { const a = 10; return eval(\"a * 2\"); } f();"), "20");
+    assert_eq!(eval("function f() { return eval(\"this.x\"); } const o = { x: 4, f: f }; o.f();"), "4");
+    // `var` is declared in the calling function, while `let` stays inside the eval code
+    assert_eq!(eval("function f() { eval(\"var v = 3\"); return v; } f();"), "3");
+    assert_eq!(eval("function f() { eval(\"var w = 3\"); return w; } f(); typeof w;"), "undefined");
+    assert_eq!(eval("eval(\"let q = 1; q + 1\") + typeof q;"), "2undefined");
+}
+
+#[test]
+fn check_indirect_eval() {
+    assert_eq!(eval("function f() { const a = 1; const e = eval; return e(\"typeof a\"); } f();"), "undefined");
+    assert_eq!(eval("var g = 1; function f() { const g = 2; const e = eval; return e(\"g\"); } f();"), "1");
+    assert_eq!(eval("function f() { const e = eval; e(\"var u = 5\"); } f(); u;"), "5");
+}
+
+#[test]
+fn check_function_constructor() {
+    assert_eq!(eval("const add = new Function(\"a\", \"b\", \"return a + b\"); add(2, 3);"), "5");
+    assert_eq!(eval("Function(\"a, b\", \"c\", \"return a + b + c\")(1, 2, 3);"), "6");
+    assert_eq!(eval("const f = new Function(\"a\", \"b\", \"return 1\"); f.name + f.length;"), "anonymous2");
+    assert_eq!(eval("const f = new Function(\"a\", \"return a\"); f.toString();"), "function anonymous(a\n) {\nreturn a\n}");
+    assert_eq!(eval("const f = new Function(\"return 1\"); f instanceof Function;"), "true");
+    // The function closes over the global scope, not the one it was made in
+    let script = "const x = \"global\";
+    function f() { const x = \"local\"; const h = new Function(\"return x\"); return h(); }
+    f();";
+    assert_eq!(eval(script), "global");
+    assert_eq!(eval_err("new Function(\"}); (function() {\");"), "SyntaxError: Unexpected token in function source");
+}
+
+#[test]
+fn check_dynamic_code_policy() {
+    let mut engine: Interpreter = Executor::new();
+    assert!(engine.dynamic_code_allowed());
+    engine.set_dynamic_code_allowed(false);
+    let err = forward(&mut engine, "eval(\"1\");").unwrap_err().to_string();
+    assert_eq!(err, "EvalError: Code generation from strings disallowed for this context");
+    let err = forward(&mut engine, "Function(\"return 1\");").unwrap_err().to_string();
+    assert_eq!(err, "EvalError: Code generation from strings disallowed for this context");
+    // Values which aren't strings aren't code, so they are still returned
+    assert_eq!(forward(&mut engine, "eval(3);").unwrap().to_string(), "3");
+}
@@ -0,0 +1,92 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::eval;
+
+#[test]
+fn check_this() {
+    assert_eq!(eval("const o = {x: 3, f: function() { return this.x; }}; o.f();"), "3");
+    assert_eq!(eval("function f() { return this; } f() === globalThis;"), "true");
+    assert_eq!(eval("function f() { return typeof this; } f.call(5);"), "object");
+    let script = "const o = {x: 3, f: function() { const g = () => this.x; return g(); }};
+    o.f();";
+    assert_eq!(eval(script), "3");
+    assert_eq!(eval("function P(x) { this.x = x; } const p = new P(4); p.x;"), "4");
+}
+
+#[test]
+fn check_duplicate_parameters() {
+    // Outside of strict code a parameter name can repeat, and the last argument for it wins
+    assert_eq!(eval("function f(a, a) { return a; } f(1, 2);"), "2");
+    assert_eq!(eval("function f(a, a) { return a; } f(1);"), "undefined");
+    assert_eq!(eval("function f(a, b, a) { return a + b; } f(1, 2, 3);"), "5");
+}
+
+#[test]
+fn check_call_and_apply() {
+    assert_eq!(eval("function add(a, b) { return a + b; } add.call(null, 1, 2);"), "3");
+    assert_eq!(eval("const o = {x: 3}; function getX() { return this.x; } getX.call(o);"), "3");
+    assert_eq!(eval("function add(a, b) { return a + b; } add.apply(null, [3, 4]);"), "7");
+    assert_eq!(eval("function one() { return 1; } one.apply(null);"), "1");
+    assert_eq!(eval("Math.max.apply(null, {length: 2, \"0\": 5, \"1\": 9});"), "9");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "Function.prototype.call.call(1);").is_err());
+    assert!(forward(&mut engine, "Math.max.apply(null, 1);").is_err());
+}
+
+#[test]
+fn check_bind() {
+    let script = "function add(a, b) { return a + b + this.c; }
+    const inc = add.bind({c: 10}, 1);
+    const r = [inc(5), inc.name, inc.length, inc.bind(null, 1).length];
+    r.join();";
+    assert_eq!(eval(script), "16,bound add,1,0");
+    let script = "function P(x, y) { this.x = x; this.y = y; }
+    const B = P.bind({x: 0}, 1);
+    const p = new B(2);
+    const r = [p.x, p.y, p instanceof P];
+    r.join();";
+    assert_eq!(eval(script), "1,2,true");
+    assert_eq!(eval("const f = Math.max.bind(null, 7); f(1);"), "7");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "Function.prototype.bind.call({});").is_err());
+}
+
+#[test]
+fn check_name_and_length() {
+    assert_eq!(eval("function add(a, b) {} const r = [add.name, add.length]; r.join();"), "add,2");
+    assert_eq!(eval("const f = function() {}; f.name;"), "f");
+    assert_eq!(eval("const f = (a, b, c) => a; const r = [f.name, f.length]; r.join();"), "f,3");
+    assert_eq!(eval("const o = {g: function() {}}; o.g.name;"), "g");
+    assert_eq!(eval("const f = function() {}; const g = f; g.name;"), "f");
+    assert_eq!(eval("function f() {} f.name = \"g\"; f.name;"), "f");
+    assert_eq!(eval("Math.max.name;"), "max");
+    assert_eq!(eval("function f() {} Object.keys(f).length;"), "0");
+    assert_eq!(eval("function f() {} Object.getPrototypeOf(f) === Function.prototype;"), "true");
+    assert_eq!(eval("Object.getPrototypeOf(Math.max) === Function.prototype;"), "true");
+}
+
+#[test]
+fn check_to_string() {
+    assert_eq!(eval("function add(a, b) { return a + b; } add.toString();"), "function add(a, b) { return a + b; }");
+    assert_eq!(eval("async function f() { return 1; } f.toString();"), "async function f() { return 1; }");
+    assert_eq!(eval("const f = x => x * 2; f.toString();"), "x => x * 2");
+    assert_eq!(eval("const f = async (a, b) => { return a; }; f.toString();"), "async (a, b) => { return a; }");
+    assert_eq!(eval("Math.max.toString();"), "function max() { [native code] }");
+    assert_eq!(eval("function f() {} f.bind(null).toString();"), "function bound f() { [native code] }");
+}
+
+#[test]
+fn check_instanceof() {
+    // A prototype which is itself a function is still an object
+    let script = "function F() {}
+    const p = function() {};
+    F.prototype = p;
+    const o = Object.create(p);
+    o instanceof F;";
+    assert_eq!(eval(script), "true");
+    let mut engine: Interpreter = Executor::new();
+    let err = forward(&mut engine, "function G() {} G.prototype = 1; const o = {}; o instanceof G;").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Function has non-object prototype in instanceof check");
+}
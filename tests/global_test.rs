@@ -0,0 +1,67 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::eval;
+
+#[test]
+fn check_value_properties() {
+    assert_eq!(eval("NaN;"), "NaN");
+    assert_eq!(eval("-Infinity;"), "-Infinity");
+    assert_eq!(eval("typeof undefined;"), "undefined");
+    assert_eq!(eval("globalThis.Math.abs(-2);"), "2");
+    assert_eq!(eval("globalThis.answer = 42; answer;"), "42");
+}
+
+#[test]
+fn check_parse_int() {
+    assert_eq!(eval("parseInt(\"  42px\");"), "42");
+    assert_eq!(eval("parseInt(\"-0x1A\");"), "-26");
+    assert_eq!(eval("parseInt(\"0x1A\", 10);"), "0");
+    assert_eq!(eval("parseInt(\"0x1A\", 16);"), "26");
+    assert_eq!(eval("parseInt(\"ff\", 16);"), "255");
+    assert_eq!(eval("parseInt(\"101\", 2);"), "5");
+    assert_eq!(eval("parseInt(\"z\", 37);"), "NaN");
+    assert_eq!(eval("parseInt(\"10\", 1);"), "NaN");
+    assert_eq!(eval("parseInt(\"\");"), "NaN");
+    assert_eq!(eval("parseInt(0.0000005);"), "5");
+    assert_eq!(eval("parseInt(\"123456789012345678901234567890\");"), "1.2345678901234568e+29");
+    assert_eq!(eval("Number.parseInt === parseInt;"), "true");
+}
+
+#[test]
+fn check_parse_float() {
+    assert_eq!(eval("parseFloat(\"\\n 1.5e3kg\");"), "1500");
+    assert_eq!(eval("parseFloat(\"-Infinityx\");"), "-Infinity");
+    assert_eq!(eval("parseFloat(\".x\");"), "NaN");
+    assert_eq!(eval("Number.parseFloat === parseFloat;"), "true");
+}
+
+#[test]
+fn check_is_nan_and_is_finite() {
+    assert_eq!(eval("isNaN(\"abc\");"), "true");
+    assert_eq!(eval("isNaN(\"12\");"), "false");
+    assert_eq!(eval("isNaN();"), "true");
+    assert_eq!(eval("isFinite(\"1e3\");"), "true");
+    assert_eq!(eval("isFinite(Infinity);"), "false");
+    assert_eq!(eval("isFinite(null);"), "true");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "isNaN(Symbol());").is_err());
+    assert!(forward(&mut engine, "isFinite(1n);").is_err());
+}
+
+#[test]
+fn check_uri_encoding() {
+    assert_eq!(eval("encodeURI(\"http://x.org/a b?q=1&r=é#top\");"), "http://x.org/a%20b?q=1&r=%C3%A9#top");
+    assert_eq!(eval("encodeURIComponent(\"a b&c/d\");"), "a%20b%26c%2Fd");
+    assert_eq!(eval("encodeURIComponent(\"😀\");"), "%F0%9F%98%80");
+    assert_eq!(eval("encodeURIComponent(\"-_.!~*'()\");"), "-_.!~*'()");
+    assert_eq!(eval("decodeURIComponent(\"%F0%9F%98%80%20%2f\");"), "😀 /");
+    assert_eq!(eval("decodeURI(\"a%20b%2Fc%23\");"), "a b%2Fc%23");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "decodeURIComponent(\"%\");").is_err());
+    assert!(forward(&mut engine, "decodeURIComponent(\"%E0%A4%A\");").is_err());
+    assert!(forward(&mut engine, "decodeURIComponent(\"%C0%80\");").is_err());
+    assert!(forward(&mut engine, "decodeURIComponent(\"%ED%A0%80\");").is_err());
+    assert!(forward(&mut engine, "decodeURIComponent(\"%80\");").is_err());
+}
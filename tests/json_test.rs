@@ -0,0 +1,82 @@
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_stringify() {
+    assert_eq!(
+        eval("JSON.stringify({ b: 1, a: [1, \"x\", null, true], c: { d: undefined, e: function() {} } });"),
+        "{\"b\":1,\"a\":[1,\"x\",null,true],\"c\":{}}"
+    );
+    assert_eq!(eval("JSON.stringify([undefined, function() {}, Symbol(\"s\")]);"), "[null,null,null]");
+    assert_eq!(eval("JSON.stringify(NaN) + JSON.stringify(-0) + JSON.stringify(Infinity);"), "null0null");
+    assert_eq!(eval("JSON.stringify(\"a\\\"b\\n\\u0001\");"), "\"a\\\"b\\n\\u0001\"");
+    assert_eq!(eval("JSON.stringify([new Number(3), new String(\"s\"), new Boolean(false)]);"), "[3,\"s\",false]");
+    assert_eq!(eval("JSON.stringify(undefined);"), "undefined");
+    assert_eq!(eval("JSON.stringify(function() {});"), "undefined");
+    assert_eq!(eval("JSON.stringify(new Proxy([1, { a: 2 }], {}));"), "[1,{\"a\":2}]");
+    assert_eq!(eval_err("const o = {}; o.self = o; JSON.stringify(o);"), "TypeError: Converting circular structure to JSON");
+}
+
+#[test]
+fn check_stringify_to_json() {
+    assert_eq!(eval("JSON.stringify(new Date(0));"), "\"1970-01-01T00:00:00.000Z\"");
+    assert_eq!(eval("JSON.stringify({ d: new Date(NaN) });"), "{\"d\":null}");
+    assert_eq!(eval("JSON.stringify({ x: { toJSON: function(k) { return \"key:\" + k; } } });"), "{\"x\":\"key:x\"}");
+}
+
+#[test]
+fn check_stringify_replacer() {
+    assert_eq!(eval("JSON.stringify({ a: 1, b: 2, c: 3 }, [\"c\", \"a\", \"c\"]);"), "{\"c\":3,\"a\":1}");
+    assert_eq!(eval("JSON.stringify({ \"1\": 1, b: 2 }, [1]);"), "{\"1\":1}");
+    assert_eq!(
+        eval("JSON.stringify({ a: 1, b: \"x\" }, function(k, v) { return (k === \"a\") ? v * 2 : v; });"),
+        "{\"a\":2,\"b\":\"x\"}"
+    );
+    assert_eq!(eval("JSON.stringify({ a: 1, b: \"x\" }, function(k, v) { return (k === \"b\") ? undefined : v; });"), "{\"a\":1}");
+}
+
+#[test]
+fn check_stringify_indent() {
+    assert_eq!(
+        eval("JSON.stringify({ a: [1, 2], b: {} }, null, 2);"),
+        "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}"
+    );
+    assert_eq!(eval("JSON.stringify({ a: [1] }, null, \"--\");"), "{\n--\"a\": [\n----1\n--]\n}");
+    // The indent is at most 10 characters
+    assert_eq!(eval("JSON.stringify([1], null, 20);"), "[\n          1\n]");
+    assert_eq!(eval("JSON.stringify([1], null, \"abcdefghijkl\");"), "[\nabcdefghij1\n]");
+    assert_eq!(eval("JSON.stringify([1], null, new Number(1));"), "[\n 1\n]");
+    assert_eq!(eval("JSON.stringify([1], null, -1);"), "[1]");
+}
+
+#[test]
+fn check_parse() {
+    assert_eq!(eval("const a = JSON.parse(\"[1, 2, 3]\"); a.map(x => x * 2).join();"), "2,4,6");
+    assert_eq!(eval("Array.isArray(JSON.parse(\"[]\"));"), "true");
+    assert_eq!(eval("Object.keys(JSON.parse(\"{\\\"b\\\": 1, \\\"a\\\": 2}\")).join();"), "b,a");
+    assert_eq!(eval("JSON.parse(\"{\\\"a\\\": 1}\").hasOwnProperty(\"a\");"), "true");
+    assert_eq!(eval("JSON.parse(\"\\\"\\\\u0041\\\\ud83d\\\\ude00\\\"\");"), "A😀");
+    assert_eq!(eval("JSON.parse(\"1e3\") + JSON.parse(\" -0.5 \");"), "999.5");
+    assert_eq!(eval("JSON.stringify(JSON.parse(\"{\\\"x\\\": [true, false, null]}\"));"), "{\"x\":[true,false,null]}");
+    assert_eq!(eval_err("JSON.parse(\"{\");"), "SyntaxError: Unexpected end of JSON input");
+    assert_eq!(eval_err("JSON.parse(\"[1,]\");"), "SyntaxError: Unexpected token ] in JSON at position 3");
+    assert_eq!(eval_err("JSON.parse(\"01\");"), "SyntaxError: Unexpected token 1 in JSON at position 1");
+    assert_eq!(eval_err("JSON.parse(\"'a'\");"), "SyntaxError: Unexpected token ' in JSON at position 0");
+}
+
+#[test]
+fn check_parse_reviver() {
+    assert_eq!(
+        eval("JSON.parse(\"{\\\"a\\\": [1, {\\\"b\\\": 2}]}\", function(k, v) { return (k === \"b\") ? v + 1 : v; }).a[1].b;"),
+        "3"
+    );
+    let script = "const o = JSON.parse(\"{\\\"a\\\": 1, \\\"b\\\": 2}\", function(k, v) { return (k === \"a\") ? undefined : v; });
+    Object.keys(o).join();";
+    assert_eq!(eval(script), "b");
+    // Values are revived from the innermost out, and the root last with an empty key
+    let script = "const log = [];
+    JSON.parse(\"[5, [6]]\", function(k, v) { log.push(k); return v; });
+    log.join(\"|\");";
+    assert_eq!(eval(script), "0|0|1|");
+    assert_eq!(eval("JSON.parse(\"[1, 2]\", function(k, v) { return (k === \"\") ? v.length : v; });"), "2");
+}
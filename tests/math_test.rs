@@ -0,0 +1,90 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+use engine::js::math::SeededRandom;
+
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_constants() {
+    assert_eq!(eval("Math.PI;"), "3.141592653589793");
+    assert_eq!(eval("Math.E + \",\" + Math.LN2 + \",\" + Math.LN10;"), "2.718281828459045,0.6931471805599453,2.302585092994046");
+    assert_eq!(eval("Math.LOG2E + \",\" + Math.LOG10E;"), "1.4426950408889634,0.4342944819032518");
+    assert_eq!(eval("Math.SQRT2 + \",\" + Math.SQRT1_2;"), "1.4142135623730951,0.7071067811865476");
+    // The constants can't be changed or deleted
+    assert_eq!(eval("Math.PI = 3; Math.PI;"), "3.141592653589793");
+    let script = "const d = Object.getOwnPropertyDescriptor(Math, \"PI\");
+    d.writable + \",\" + d.enumerable + \",\" + d.configurable;";
+    assert_eq!(eval(script), "false,false,false");
+    assert_eq!(eval("Math.max.length + \",\" + Math.hypot.length + \",\" + Math.random.length + \",\" + Math.abs.length;"), "2,2,0,1");
+}
+
+#[test]
+fn check_max_min() {
+    assert_eq!(eval("Math.max();"), "-Infinity");
+    assert_eq!(eval("Math.min();"), "Infinity");
+    assert_eq!(eval("Math.max(1, 3, 2);"), "3");
+    assert_eq!(eval("Math.min(1, \"-3\", 2);"), "-3");
+    assert_eq!(eval("Math.max(1, NaN, 3);"), "NaN");
+    assert_eq!(eval("Math.min(NaN, -Infinity);"), "NaN");
+    assert_eq!(eval("1 / Math.max(-0, 0);"), "Infinity");
+    assert_eq!(eval("1 / Math.min(0, -0);"), "-Infinity");
+    // Every argument is converted, even after a NaN
+    assert_eq!(eval("let n = 0; Math.max(NaN, { valueOf: function() { n = n + 1; return 1; } }); n;"), "1");
+}
+
+#[test]
+fn check_rounding() {
+    assert_eq!(eval("Math.round(2.5) + \",\" + Math.round(-2.5) + \",\" + Math.round(-2.6);"), "3,-2,-3");
+    assert_eq!(eval("Math.round(0.49999999999999994);"), "0");
+    assert_eq!(eval("1 / Math.round(-0.4);"), "-Infinity");
+    assert_eq!(eval("Math.round(9007199254740991);"), "9007199254740991");
+    assert_eq!(eval("Math.trunc(-4.7) + \",\" + Math.trunc(4.7);"), "-4,4");
+    assert_eq!(eval("1 / Math.trunc(-0.5);"), "-Infinity");
+    assert_eq!(eval("Math.sign(-3) + \",\" + Math.sign(7) + \",\" + Math.sign(NaN);"), "-1,1,NaN");
+    assert_eq!(eval("1 / Math.sign(-0);"), "-Infinity");
+    assert_eq!(eval("Math.fround(5.5) + \",\" + Math.fround(5.05);"), "5.5,5.050000190734863");
+    assert_eq!(eval("Math.ceil(-0.5);"), "0");
+}
+
+#[test]
+fn check_functions() {
+    assert_eq!(eval("Math.hypot(3, 4);"), "5");
+    assert_eq!(eval("Math.hypot() + \",\" + Math.hypot(NaN, Infinity) + \",\" + Math.hypot(NaN, 1);"), "0,Infinity,NaN");
+    assert_eq!(eval("Math.hypot(3, 4, 12) + \",\" + Math.hypot(-3);"), "13,3");
+    // Large arguments don't overflow when they are squared
+    assert_eq!(eval("Math.hypot(1e300, 1e300) < Infinity;"), "true");
+    assert_eq!(eval("Math.imul(3, 4) + \",\" + Math.imul(0xffffffff, 5);"), "12,-5");
+    assert_eq!(eval("Math.clz32(1) + \",\" + Math.clz32(0) + \",\" + Math.clz32(-1);"), "31,32,0");
+    assert_eq!(eval("Math.log2(8) + \",\" + Math.log10(1000) + \",\" + Math.log1p(0) + \",\" + Math.expm1(0);"), "3,3,0,0");
+    assert_eq!(eval("Math.sinh(0) + \",\" + Math.cosh(0) + \",\" + Math.tanh(Infinity);"), "0,1,1");
+    assert_eq!(eval("Math.asinh(0) + \",\" + Math.acosh(1) + \",\" + Math.atanh(1);"), "0,0,Infinity");
+    assert_eq!(eval("1 / Math.asinh(-0);"), "-Infinity");
+    assert_eq!(eval("Math.pow(2, 10) + \",\" + Math.pow(1, Infinity) + \",\" + Math.pow(NaN, 0);"), "1024,NaN,1");
+    assert_eq!(eval("Math.atan2(1, 1) * 4;"), "3.141592653589793");
+    assert_eq!(eval("Math.abs(\"-2\") + Math.abs({ valueOf: function() { return -3; } });"), "5");
+    assert_eq!(eval("Math.abs();"), "NaN");
+    assert_eq!(eval_err("Math.abs(1n);"), "TypeError: Cannot convert a BigInt value to a number");
+}
+
+#[test]
+fn check_random() {
+    let run = |seed: u64| {
+        let mut engine: Interpreter = Executor::new();
+        engine.set_random_source(Box::new(SeededRandom::new(seed)));
+        forward(&mut engine, "[Math.random(), Math.random(), Math.random()].join();").unwrap().to_string()
+    };
+    assert_eq!(run(42), run(42));
+    assert_ne!(run(42), run(43));
+    let mut engine: Interpreter = Executor::new();
+    for _ in 0..200 {
+        let r: f64 = forward(&mut engine, "Math.random();").unwrap().to_string().parse().unwrap();
+        assert!((0.0..1.0).contains(&r));
+    }
+    let mut engine: Interpreter = Executor::new();
+    engine.set_random_source(Box::new(SeededRandom::new(7)));
+    for _ in 0..200 {
+        let r: f64 = forward(&mut engine, "Math.random();").unwrap().to_string().parse().unwrap();
+        assert!((0.0..1.0).contains(&r));
+    }
+}
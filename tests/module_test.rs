@@ -0,0 +1,150 @@
+use engine::engine::forward;
+use engine::event_loop::EventLoop;
+use engine::exec::{Executor, Interpreter};
+use engine::js::promise::{promise_state, PromiseState};
+use engine::module::{resolve_path, MemoryModuleLoader};
+
+mod common;
+use common::import;
+
+fn loader(modules: &[(&str, &str)]) -> MemoryModuleLoader {
+    let mut loader = MemoryModuleLoader::new();
+    for (path, source) in modules {
+        loader.insert(path, source);
+    }
+    loader
+}
+
+fn interpreter(modules: &[(&str, &str)]) -> Interpreter {
+    let mut engine: Interpreter = Executor::new();
+    engine.set_module_loader(Box::new(loader(modules)));
+    engine
+}
+
+#[test]
+fn check_resolve_path() {
+    assert_eq!(resolve_path("./b.js", Some("lib/a.js")), "lib/b.js");
+    assert_eq!(resolve_path("../c.js", Some("lib/a.js")), "c.js");
+    assert_eq!(resolve_path("/d.js", Some("lib/a.js")), "d.js");
+    assert_eq!(resolve_path("main.js", None), "main.js");
+}
+
+#[test]
+fn check_named_and_default_imports() {
+    let mut engine = interpreter(&[
+        ("math.js", "export const two = 2;
+        export function double(x) { return x * two; }
+        export default 10;"),
+        ("main.js", "import ten, { double, two as TWO } from \"./math.js\";
+        export let result = double(ten) + TWO;"),
+    ]);
+    let ns = import(&mut engine, "main.js").unwrap();
+    assert_eq!(engine.get_field(&ns, "result".to_string()).unwrap().to_string(), "22");
+}
+
+#[test]
+fn check_namespace_and_live_bindings() {
+    let mut engine = interpreter(&[
+        ("counter.js", "export let count = 0;
+        export function increment() { count = count + 1; }"),
+        ("main.js", "import * as counter from \"./counter.js\";
+        import { count, increment } from \"./counter.js\";
+        increment();
+        increment();
+        export let fromNamespace = counter.count;
+        export let fromBinding = count;"),
+    ]);
+    let ns = import(&mut engine, "main.js").unwrap();
+    assert_eq!(engine.get_field(&ns, "fromNamespace".to_string()).unwrap().to_string(), "2");
+    assert_eq!(engine.get_field(&ns, "fromBinding".to_string()).unwrap().to_string(), "2");
+}
+
+#[test]
+fn check_re_exports() {
+    let mut engine = interpreter(&[
+        ("a.js", "export const a = \"a\"; export const shared = 1;"),
+        ("b.js", "export const b = \"b\";"),
+        ("index.js", "export * from \"./a.js\";
+        export { b as renamed } from \"./b.js\";
+        export * as bModule from \"./b.js\";"),
+        ("main.js", "import { a, renamed, bModule } from \"./index.js\";
+        export let result = a + renamed + bModule.b;"),
+    ]);
+    let ns = import(&mut engine, "main.js").unwrap();
+    assert_eq!(engine.get_field(&ns, "result".to_string()).unwrap().to_string(), "abb");
+}
+
+#[test]
+fn check_cycles() {
+    let mut engine = interpreter(&[
+        ("a.js", "import { b } from \"./b.js\";
+        export const a = \"a\";
+        export function readB() { return b; }"),
+        ("b.js", "import { a, readB } from \"./a.js\";
+        export const b = \"b\";
+        export function readA() { return a; }"),
+        ("main.js", "import { readB } from \"./a.js\";
+        import { readA } from \"./b.js\";
+        export let result = readA() + readB();"),
+    ]);
+    let ns = import(&mut engine, "main.js").unwrap();
+    assert_eq!(engine.get_field(&ns, "result".to_string()).unwrap().to_string(), "ab");
+}
+
+#[test]
+fn check_missing_export() {
+    let mut engine = interpreter(&[
+        ("a.js", "export const a = 1;"),
+        ("main.js", "import { b } from \"./a.js\";"),
+    ]);
+    let err = import(&mut engine, "main.js").unwrap_err().to_string();
+    assert!(err.starts_with("SyntaxError"), "{}", err);
+    let err = import(&mut engine, "missing.js").unwrap_err().to_string();
+    assert_eq!(err, "Error: Cannot find module 'missing.js'");
+    let mut engine = interpreter(&[
+        ("a.js", "export const a = 1;"),
+        ("main.js", "import * as ns from \"./a.js\";
+        import * as ns from \"./a.js\";"),
+    ]);
+    let err = import(&mut engine, "main.js").unwrap_err().to_string();
+    assert_eq!(err, "SyntaxError: Identifier 'ns' has already been declared");
+}
+
+#[test]
+fn check_top_level_await() {
+    let mut engine = interpreter(&[
+        ("config.js", "export const value = await Promise.resolve(42);"),
+        ("main.js", "import { value } from \"./config.js\";
+        export const doubled = value * 2;"),
+    ]);
+    let ns = import(&mut engine, "main.js").unwrap();
+    assert_eq!(engine.get_field(&ns, "doubled".to_string()).unwrap().to_string(), "84");
+}
+
+#[test]
+fn check_top_level_await_timer() {
+    let engine = interpreter(&[
+        ("config.js", "export const value = await new Promise(function(resolve) { setTimeout(resolve, 100, 42); });"),
+        ("main.js", "import { value } from \"./config.js\";
+        export const doubled = value * 2;"),
+    ]);
+    let mut event_loop = EventLoop::with_fake_clock(engine);
+    let promise = event_loop.interpreter().import_module("main.js").unwrap();
+    event_loop.advance(50).unwrap();
+    assert!(matches!(promise_state(&promise), PromiseState::Pending));
+    event_loop.advance(50).unwrap();
+    let ns = match promise_state(&promise) {
+        PromiseState::Fulfilled(ns) => ns,
+        _ => panic!("main.js should have finished evaluating"),
+    };
+    assert_eq!(event_loop.interpreter().get_field(&ns, "doubled".to_string()).unwrap().to_string(), "84");
+}
+
+#[test]
+fn check_dynamic_import() {
+    let mut engine = interpreter(&[("lib/greet.js", "export default function greet(name) { return \"hi \" + name; }")]);
+    forward(&mut engine, "let message = \"\";
+    import(\"lib/greet.js\").then(function(ns) { message = ns.default(\"bob\"); });").unwrap();
+    engine.run_jobs().unwrap();
+    assert_eq!(forward(&mut engine, "message;").unwrap().to_string(), "hi bob");
+}
@@ -0,0 +1,74 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::eval;
+
+#[test]
+fn check_number_formatting() {
+    assert_eq!(eval("const a = 1e21; a;"), "1e+21");
+    assert_eq!(eval("const a = 0.000001; a;"), "0.000001");
+    assert_eq!(eval("const a = 1e-7; a;"), "1e-7");
+    assert_eq!(eval("const a = -0; a;"), "0");
+    assert_eq!(eval("const a = 0.1 + 0.2; a;"), "0.30000000000000004");
+    assert_eq!(eval("const a = 1 / 3; \"\" + a;"), "0.3333333333333333");
+}
+
+#[test]
+fn check_number_constructor() {
+    assert_eq!(eval("Number(\"  0x10 \");"), "16");
+    assert_eq!(eval("Number(\"\");"), "0");
+    assert_eq!(eval("Number(\"12px\");"), "NaN");
+    assert_eq!(eval("Number(true);"), "1");
+    assert_eq!(eval("Number();"), "0");
+    assert_eq!(eval("const n = new Number(5); typeof n;"), "object");
+    assert_eq!(eval("const n = new Number(5); n + 1;"), "6");
+    assert_eq!(eval("const n = new Number(1); n.toString = ({}).toString; n.toString();"), "[object Number]");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "Number(Symbol());").is_err());
+}
+
+#[test]
+fn check_number_statics() {
+    assert_eq!(eval("Number.MAX_SAFE_INTEGER;"), "9007199254740991");
+    assert_eq!(eval("Number.EPSILON;"), "2.220446049250313e-16");
+    assert_eq!(eval("Number.MIN_VALUE;"), "5e-324");
+    assert_eq!(eval("Number.isInteger(5);"), "true");
+    assert_eq!(eval("Number.isInteger(\"5\");"), "false");
+    assert_eq!(eval("Number.isSafeInteger(9007199254740992);"), "false");
+    assert_eq!(eval("Number.parseFloat(\"  3.14abc\");"), "3.14");
+    assert_eq!(eval("Number.parseFloat(\"-.5e1x\");"), "-5");
+    assert_eq!(eval("Number.parseFloat(\"abc\");"), "NaN");
+}
+
+#[test]
+fn check_number_prototype() {
+    assert_eq!(eval("(255).toString(16);"), "ff");
+    assert_eq!(eval("(255).toString(2);"), "11111111");
+    assert_eq!(eval("(0.5).toString(2);"), "0.1");
+    assert_eq!(eval("(0.1).toFixed(20);"), "0.10000000000000000555");
+    assert_eq!(eval("(1.005).toFixed(2);"), "1.00");
+    assert_eq!(eval("(123.456).toFixed(1);"), "123.5");
+    assert_eq!(eval("(1e21).toFixed(2);"), "1e+21");
+    assert_eq!(eval("(123.456).toExponential(2);"), "1.23e+2");
+    assert_eq!(eval("(12345).toExponential();"), "1.2345e+4");
+    assert_eq!(eval("(123.456).toPrecision(4);"), "123.5");
+    assert_eq!(eval("(0.00001).toPrecision(1);"), "0.00001");
+    assert_eq!(eval("(99.99).toPrecision(3);"), "100");
+    assert_eq!(eval("(1e21).toPrecision(3);"), "1.00e+21");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "(1).toString(1);").is_err());
+    assert!(forward(&mut engine, "(1).toFixed(101);").is_err());
+    assert!(forward(&mut engine, "(1).toPrecision(0);").is_err());
+}
+
+#[test]
+fn check_boolean() {
+    assert_eq!(eval("Boolean(0);"), "false");
+    assert_eq!(eval("Boolean(\"a\");"), "true");
+    assert_eq!(eval("Boolean();"), "false");
+    assert_eq!(eval("true.toString();"), "true");
+    assert_eq!(eval("const b = new Boolean(false); typeof b;"), "object");
+    assert_eq!(eval("const b = new Boolean(false); b.valueOf();"), "false");
+    assert_eq!(eval("const b = new Boolean(true); b.toString = ({}).toString; b.toString();"), "[object Boolean]");
+}
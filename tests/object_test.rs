@@ -0,0 +1,113 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::eval;
+
+#[test]
+fn check_key_order() {
+    assert_eq!(eval("const o = {b: 1, a: 2}; o[2] = 0; o[1] = 0; Object.keys(o).join();"), "1,2,b,a");
+    assert_eq!(eval("const o = {b: 1, a: 2}; o.b = 3; Object.values(o).join();"), "3,2");
+    assert_eq!(eval("const o = {x: 1}; const e = Object.entries(o); e[0].join();"), "x,1");
+    assert_eq!(eval("Object.keys(\"ab\").join();"), "0,1");
+    assert_eq!(eval("Object.getOwnPropertyNames(\"ab\").join();"), "0,1,length");
+    assert_eq!(eval("const s = Symbol(); const o = {a: 1}; o[s] = 2; Object.getOwnPropertySymbols(o).length;"), "1");
+    assert_eq!(eval("Object.keys(Math).length;"), "0");
+    assert_eq!(eval("Object.keys([5, 6]).join();"), "0,1");
+}
+
+#[test]
+fn check_descriptors() {
+    let script = "const o = {a: 1};
+    Object.defineProperty(o, \"h\", {value: 5});
+    const d = Object.getOwnPropertyDescriptor(o, \"a\");
+    const h = Object.getOwnPropertyDescriptor(o, \"h\");
+    o.h = 9;
+    const r = [d.value, d.writable, d.enumerable, d.configurable, h.writable, h.enumerable, h.configurable, o.h];
+    r.join();";
+    assert_eq!(eval(script), "1,true,true,true,false,false,false,5");
+    let script = "const o = {};
+    Object.defineProperty(o, \"x\", {get: function() { return 7; }, configurable: true});
+    const before = o.x;
+    Object.defineProperty(o, \"x\", {enumerable: true});
+    const d = Object.getOwnPropertyDescriptor(o, \"x\");
+    Object.defineProperty(o, \"x\", {value: 1});
+    const r = [before, typeof d.get, d.enumerable, o.x, Object.getOwnPropertyDescriptor(o, \"x\").writable];
+    r.join();";
+    assert_eq!(eval(script), "7,function,true,1,false");
+    assert_eq!(eval("Object.getOwnPropertyDescriptor({}, \"a\");"), "undefined");
+    assert_eq!(eval("const d = Object.getOwnPropertyDescriptors({a: 1}); d.a.value;"), "1");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "const o = {}; Object.defineProperty(o, \"x\", {value: 1}); Object.defineProperty(o, \"x\", {value: 2});").is_err());
+    assert!(forward(&mut engine, "Object.defineProperty(1, \"x\", {});").is_err());
+    assert!(forward(&mut engine, "Object.defineProperty({}, \"x\", {get: 1});").is_err());
+    assert!(forward(&mut engine, "Object.defineProperty({}, \"x\", {get: function() {}, value: 1});").is_err());
+}
+
+#[test]
+fn check_array_descriptors() {
+    let script = "const a = [1, 2, 3];
+    Object.defineProperty(a, \"length\", {value: 1});
+    Object.defineProperty(a, \"4\", {value: 9, enumerable: true});
+    const r = [a.length, a[0], a[1], a[4]];
+    r.join();";
+    assert_eq!(eval(script), "5,1,,9");
+    assert_eq!(eval("const a = [1]; Object.defineProperty(a, \"length\", {writable: false}); a[3] = 1; a.length;"), "1");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "Object.defineProperty([], \"length\", {value: -1});").is_err());
+}
+
+#[test]
+fn check_integrity_levels() {
+    let script = "const o = Object.freeze({a: 1});
+    o.a = 2;
+    o.b = 3;
+    const r = [o.a, o.b, Object.isFrozen(o), Object.isSealed(o), Object.isExtensible(o)];
+    r.join();";
+    assert_eq!(eval(script), "1,,true,true,false");
+    let script = "const o = Object.seal({a: 1});
+    o.a = 2;
+    o.b = 3;
+    const r = [o.a, o.b, Object.isFrozen(o), Object.isSealed(o)];
+    r.join();";
+    assert_eq!(eval(script), "2,,false,true");
+    let script = "const o = Object.preventExtensions({a: 1});
+    o.b = 1;
+    const r = [o.b, Object.isExtensible(o), Object.isSealed(o), Object.isFrozen(1), Object.isExtensible(1)];
+    r.join();";
+    assert_eq!(eval(script), ",false,false,true,false");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "const o = Object.preventExtensions({}); Object.defineProperty(o, \"x\", {value: 1});").is_err());
+    assert!(forward(&mut engine, "Object.setPrototypeOf(Object.preventExtensions({}), {});").is_err());
+}
+
+#[test]
+fn check_create_and_prototypes() {
+    let script = "const c = Object.create({p: 1}, {q: {value: 2, enumerable: true}});
+    const r = [c.p, c.q, Object.keys(c).join(), c.hasOwnProperty(\"p\"), Object.hasOwn(c, \"q\")];
+    r.join();";
+    assert_eq!(eval(script), "1,2,q,false,true");
+    assert_eq!(eval("const o = Object.create(null); Object.getPrototypeOf(o);"), "null");
+    assert_eq!(eval("const p = {}; const o = Object.create(p); p.isPrototypeOf(o);"), "true");
+    assert_eq!(eval("Object.getPrototypeOf(\"a\") === String.prototype;"), "true");
+    assert_eq!(eval("const o = {a: 1}; o.propertyIsEnumerable(\"a\");"), "true");
+    assert_eq!(eval("typeof Object(1);"), "object");
+    assert_eq!(eval("const o = {}; Object(o) === o;"), "true");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "Object.create(1);").is_err());
+    assert!(forward(&mut engine, "const o = {}; Object.setPrototypeOf(Object.prototype, o);").is_err());
+    assert!(forward(&mut engine, "Object.keys(null);").is_err());
+}
+
+#[test]
+fn check_statics() {
+    assert_eq!(eval("const o = Object.assign({a: 1}, {b: 2}, null, {a: 3}); Object.entries(o).join(\";\");"), "a,3;b,2");
+    assert_eq!(eval("const o = Object.fromEntries([[\"x\", 1], [\"y\", 2]]); o.x + o.y;"), "3");
+    let script = "const g = Object.groupBy([1, 2, 3, 4], function(n) { return n > 2; });
+    const r = [g[\"true\"].join(), g[\"false\"].join(), Object.getPrototypeOf(g)];
+    r.join(\"|\");";
+    assert_eq!(eval(script), "3,4|1,2|");
+    assert_eq!(eval("Object.is(NaN, NaN);"), "true");
+    assert_eq!(eval("Object.is(0, -0);"), "false");
+    assert_eq!(eval("const o = {}; Object.is(o, o);"), "true");
+}
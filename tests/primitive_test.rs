@@ -0,0 +1,56 @@
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_string_own_properties() {
+    assert_eq!(eval("\"abc\".length;"), "3");
+    assert_eq!(eval("\"abc\"[1];"), "b");
+    assert_eq!(eval("\"abc\"[3];"), "undefined");
+    assert_eq!(eval("\"abc\".foo;"), "undefined");
+    assert_eq!(eval("\"abc\".hasOwnProperty(\"length\");"), "true");
+    assert_eq!(eval("\"abc\".hasOwnProperty(\"charAt\");"), "false");
+    assert_eq!(eval("Object.getOwnPropertyNames(\"abc\").join();"), "0,1,2,length");
+    let script = "const d = Object.getOwnPropertyDescriptor(\"abc\", \"length\");
+    const r = [d.value, d.writable, d.enumerable, d.configurable];
+    r.join();";
+    assert_eq!(eval(script), "3,false,false,false");
+}
+
+#[test]
+fn check_primitive_methods() {
+    assert_eq!(eval("\"abc\".charAt(1);"), "b");
+    assert_eq!(eval("\"abc\".toString === String.prototype.toString;"), "true");
+    assert_eq!(eval("const n = 5; n.toFixed(2);"), "5.00");
+    assert_eq!(eval("true.toString();"), "true");
+    assert_eq!(eval("Symbol(\"x\").description;"), "x");
+    assert_eq!(eval("const b = 10n; b.toString();"), "10");
+}
+
+#[test]
+fn check_primitive_constructor() {
+    assert_eq!(eval("\"abc\".constructor === String;"), "true");
+    assert_eq!(eval("const n = 5; n.constructor === Number;"), "true");
+    assert_eq!(eval("true.constructor === Boolean;"), "true");
+    assert_eq!(eval("Symbol().constructor === Symbol;"), "true");
+    assert_eq!(eval("const b = 1n; b.constructor === BigInt;"), "true");
+}
+
+#[test]
+fn check_prototype_changes() {
+    assert_eq!(eval("String.prototype.foo = 2; \"abc\".foo;"), "2");
+    assert_eq!(eval("Number.prototype.foo = 1; const n = 5; n.foo;"), "1");
+    let script = "Object.defineProperty(String.prototype, \"me\", { get: function() { return typeof this; } });
+    \"a\".me;";
+    assert_eq!(eval(script), "object");
+    // Primitives are looked up through the original prototypes, even if the global constructors are replaced
+    assert_eq!(eval("globalThis.String = null; \"abc\".charAt(0);"), "a");
+    assert_eq!(eval("globalThis.Number = null; const n = 5; n.toFixed(1);"), "5.0");
+}
+
+#[test]
+fn check_primitive_assignment() {
+    assert_eq!(eval("const s = \"abc\"; s.x = 1; s.x;"), "undefined");
+    assert_eq!(eval("const s = \"abc\"; s.length = 10; s.length;"), "3");
+    assert_eq!(eval_err("const u = undefined; u.foo;"), "TypeError: Cannot read properties of undefined (reading 'foo')");
+    assert_eq!(eval_err("const z = null; z.foo;"), "TypeError: Cannot read properties of null (reading 'foo')");
+}
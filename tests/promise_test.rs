@@ -0,0 +1,128 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::eval_err;
+
+fn run_with_jobs(script: &str, result: &str) -> String {
+    let mut engine: Interpreter = Executor::new();
+    forward(&mut engine, script).unwrap();
+    engine.run_jobs().unwrap();
+    forward(&mut engine, result).unwrap().to_string()
+}
+
+#[test]
+fn check_jobs_wait_for_run_jobs() {
+    let mut engine: Interpreter = Executor::new();
+    let script = "let log = \"\";
+    Promise.resolve(\"job\").then(function(v) { log = v; });
+    log;";
+    assert_eq!(forward(&mut engine, script).unwrap().to_string(), "");
+    assert!(engine.has_pending_jobs());
+    engine.run_jobs().unwrap();
+    assert!(!engine.has_pending_jobs());
+    assert_eq!(forward(&mut engine, "log;").unwrap().to_string(), "job");
+}
+
+#[test]
+fn check_then_chain() {
+    let script = "let log = \"\";
+    let p = new Promise(function(resolve, reject) { resolve(1); });
+    p.then(function(v) { log = log + v; return v + 1; }).then(function(v) { log = log + v; });
+    log = log + \"s\";";
+    assert_eq!(run_with_jobs(script, "log;"), "s12");
+}
+
+#[test]
+fn check_catch_finally() {
+    let script = "let log = \"\";
+    Promise.reject(\"bad\").catch(function(e) { log = e; }).finally(function() { log = log + \"!\"; });";
+    assert_eq!(run_with_jobs(script, "log;"), "bad!");
+}
+
+#[test]
+fn check_combinators() {
+    let script = "let all = 0;
+    let race = 0;
+    let any = 0;
+    let settled = 0;
+    Promise.all([Promise.resolve(1), 2, new Promise(function(r) { r(3); })]).then(function(vs) { all = vs[2]; });
+    Promise.race([new Promise(function(r) {}), Promise.resolve(\"r\")]).then(function(v) { race = v; });
+    Promise.any([Promise.reject(1), Promise.resolve(\"a\")]).then(function(v) { any = v; });
+    Promise.allSettled([Promise.reject(1), Promise.resolve(2)]).then(function(rs) { settled = rs[0].status; });";
+    assert_eq!(run_with_jobs(script, "all;"), "3");
+    assert_eq!(run_with_jobs(script, "race;"), "r");
+    assert_eq!(run_with_jobs(script, "any;"), "a");
+    assert_eq!(run_with_jobs(script, "settled;"), "rejected");
+}
+
+#[test]
+fn check_async_await() {
+    let script = "let res = 0;
+    async function add(a, b) {
+        let x = await a;
+        return x + b;
+    }
+    let double = async (x) => x * 2;
+    add(Promise.resolve(1), 2).then(function(v) { res = v; return double(4); }).then((v) => { res = res * v; });";
+    assert_eq!(run_with_jobs(script, "res;"), "24");
+}
+
+#[test]
+fn check_async_rejection() {
+    let script = "let res = \"\";
+    async function fail() {
+        await Promise.reject(\"nope\");
+    }
+    fail().catch(function(e) { res = e; });";
+    assert_eq!(run_with_jobs(script, "res;"), "nope");
+}
+
+#[test]
+fn check_await_suspends() {
+    let script = "let log = \"\";
+    async function f() {
+        log = log + \"1\";
+        await null;
+        log = log + \"3\";
+    }
+    f();
+    log = log + \"2\";";
+    assert_eq!(run_with_jobs(script, "log;"), "123");
+    // The rest of the body runs as a job, after reactions which were queued before it
+    let script = "let log = \"\";
+    const p = Promise.resolve();
+    async function f() {
+        await p;
+        log = log + \"b\";
+        await p;
+        log = log + \"d\";
+    }
+    p.then(function() { log = log + \"a\"; });
+    f();
+    p.then(function() { log = log + \"c\"; });";
+    assert_eq!(run_with_jobs(script, "log;"), "abcd");
+}
+
+#[test]
+fn check_await_in_statements() {
+    let script = "let log = \"\";
+    async function f(n) {
+        let i = 0;
+        while (i < n) {
+            log = log + (await Promise.resolve(i));
+            i = i + 1;
+        }
+        if ((await n) > 2) { log = log + \"!\"; }
+        return log;
+    }
+    f(3).then(function(v) { log = v + \"-\" + v.length; });";
+    assert_eq!(run_with_jobs(script, "log;"), "012!-4");
+}
+
+#[test]
+fn check_top_level_await() {
+    let script = "async function get() { return 5; }
+    let t = await get();";
+    assert_eq!(eval_err(script), "SyntaxError: await is only valid in async functions and the top level bodies of modules");
+}
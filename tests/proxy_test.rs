@@ -0,0 +1,154 @@
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_proxy_traps() {
+    let script = "const log = [];
+    const p = new Proxy({ a: 1 }, { get: function(t, k, r) { log.push(k); return t[k] * 2; } });
+    p.a + log.join();";
+    assert_eq!(eval(script), "2a");
+    assert_eq!(eval("const t = {}; const p = new Proxy(t, { set: function(t, k, v) { t[k] = v + 1; return true; } }); p.x = 3; t.x;"), "4");
+    assert_eq!(eval("const p = new Proxy({ a: 1, b: 2 }, { ownKeys: function() { return [\"b\"]; } }); Object.keys(p).join();"), "b");
+    assert_eq!(eval("const p = new Proxy({ a: 1 }, { has: function(t, k) { return k === \"z\"; } }); Reflect.has(p, \"z\");"), "true");
+    assert_eq!(eval("const p = new Proxy({}, { getPrototypeOf: function() { return Array.prototype; } }); p instanceof Array;"), "true");
+    assert_eq!(
+        eval("const p = new Proxy({}, { getOwnPropertyDescriptor: function() { return { value: 5, configurable: true }; } }); Object.getOwnPropertyDescriptor(p, \"q\").value;"),
+        "5"
+    );
+    let script = "const log = [];
+    const p = new Proxy({}, { defineProperty: function(t, k, d) { log.push(k, d.value); return Reflect.defineProperty(t, k, d); } });
+    Object.defineProperty(p, \"x\", { value: 1 });
+    log.join();";
+    assert_eq!(eval(script), "x,1");
+    assert_eq!(eval("const p = new Proxy({ a: 1 }, { deleteProperty: function() { return false; } }); Reflect.deleteProperty(p, \"a\");"), "false");
+    assert_eq!(eval("const p = new Proxy({}, { isExtensible: function(t) { return true; } }); Object.isExtensible(p);"), "true");
+    // The array methods go through the traps for every element
+    let script = "const log = [];
+    const p = new Proxy([1, 2], { deleteProperty: function(t, k) { log.push(k); return Reflect.deleteProperty(t, k); } });
+    p.pop();
+    log.join() + \"|\" + p.length;";
+    assert_eq!(eval(script), "1|1");
+    let script = "const log = [];
+    const p = new Proxy([1, 2], { has: function(t, k) { log.push(k); return Reflect.has(t, k); } });
+    p.indexOf(2);
+    log.join();";
+    assert_eq!(eval(script), "0,1");
+    assert_eq!(
+        eval_err("const p = new Proxy([1, 2], { deleteProperty: function() { return false; } }); p.pop();"),
+        "TypeError: Cannot delete property '1' of object"
+    );
+    // Without traps, every operation goes to the target
+    assert_eq!(eval("const t = {}; const p = new Proxy(t, {}); p.x = 3; t.x + p.x;"), "6");
+    assert_eq!(eval("const p = new Proxy({ a: 1 }, {}); Object.freeze(p); Object.isFrozen(p);"), "true");
+    assert_eq!(eval_err("const p = new Proxy({}, { get: 1 }); p.x;"), "TypeError: 'get' on proxy: trap is not a function");
+}
+
+#[test]
+fn check_proxy_functions() {
+    let script = "const f = new Proxy(function(a, b) { return a + b; }, {
+        apply: function(t, self, args) { return t(args[0], args[1]) * 10; }
+    });
+    f(1, 2);";
+    assert_eq!(eval(script), "30");
+    assert_eq!(eval("function C(x) { this.x = x; } const P = new Proxy(C, {}); const o = new P(5); o.x;"), "5");
+    assert_eq!(eval("function C(x) { this.x = x; } const P = new Proxy(C, {}); const o = new P(5); o instanceof C;"), "true");
+    let script = "const P = new Proxy(function() {}, { construct: function(t, args, newTarget) { return { y: args[0] }; } });
+    const o = new P(7);
+    o.y;";
+    assert_eq!(eval(script), "7");
+    assert_eq!(eval("const f = new Proxy(function() {}, {}); typeof f;"), "function");
+    assert_eq!(eval("const p = new Proxy({}, {}); typeof p;"), "object");
+    assert_eq!(eval("Array.isArray(new Proxy([], {}));"), "true");
+    assert_eq!(eval("Object.prototype.toString.call(new Proxy([], {}));"), "[object Array]");
+    assert_eq!(
+        eval_err("const P = new Proxy(function() {}, { construct: function() { return 1; } }); new P();"),
+        "TypeError: 'construct' on proxy: trap returned non-object ('1')"
+    );
+}
+
+#[test]
+fn check_proxy_invariants() {
+    let script = "const t = {};
+    Object.defineProperty(t, \"x\", { value: 1 });
+    const p = new Proxy(t, { get: function() { return 2; } });
+    p.x;";
+    assert_eq!(
+        eval_err(script),
+        "TypeError: 'get' on proxy: property 'x' is a read-only and non-configurable data property on the proxy target but the proxy did not return its actual value (expected '1' but got '2')"
+    );
+    let script = "const t = {};
+    Object.defineProperty(t, \"x\", { value: 1 });
+    const p = new Proxy(t, { ownKeys: function() { return []; } });
+    Object.keys(p);";
+    assert_eq!(eval_err(script), "TypeError: 'ownKeys' on proxy: trap result did not include 'x'");
+    assert_eq!(
+        eval_err("const p = new Proxy({}, { ownKeys: function() { return [\"a\", \"a\"]; } }); Object.keys(p);"),
+        "TypeError: 'ownKeys' on proxy: trap returned duplicate entries"
+    );
+    assert_eq!(
+        eval_err("const p = new Proxy({}, { ownKeys: function() { return [\"a\"]; } }); Object.preventExtensions(p); Object.keys(p);"),
+        "TypeError: 'ownKeys' on proxy: trap returned extra keys but proxy target is non-extensible"
+    );
+    assert_eq!(
+        eval_err("const p = new Proxy({}, { defineProperty: function() { return true; } }); Object.defineProperty(p, \"x\", { value: 1, configurable: false });"),
+        "TypeError: 'defineProperty' on proxy: trap returned truish for defining non-configurable property 'x' which is either non-existent or configurable in the proxy target"
+    );
+    let script = "const t = Object.preventExtensions({});
+    const p = new Proxy(t, { getPrototypeOf: function() { return Array.prototype; } });
+    Object.getPrototypeOf(p);";
+    assert_eq!(
+        eval_err(script),
+        "TypeError: 'getPrototypeOf' on proxy: proxy target is non-extensible but the trap did not return its actual prototype"
+    );
+    assert_eq!(
+        eval_err("const p = new Proxy({}, { isExtensible: function() { return false; } }); Object.isExtensible(p);"),
+        "TypeError: 'isExtensible' on proxy: trap result does not reflect extensibility of proxy target (which is 'true')"
+    );
+    assert_eq!(
+        eval_err("const t = Object.freeze({ a: 1 }); const p = new Proxy(t, { has: function() { return false; } }); Reflect.has(p, \"a\");"),
+        "TypeError: 'has' on proxy: trap returned falsish for property 'a' which exists in the proxy target as non-configurable"
+    );
+    assert_eq!(
+        eval_err("const p = new Proxy({}, { setPrototypeOf: function() { return false; } }); Object.setPrototypeOf(p, null);"),
+        "TypeError: 'setPrototypeOf' on proxy: trap returned falsish"
+    );
+}
+
+#[test]
+fn check_proxy_constructor() {
+    assert_eq!(eval("Proxy.length;"), "2");
+    assert_eq!(eval("Proxy.prototype;"), "undefined");
+    assert_eq!(eval_err("Proxy({}, {});"), "TypeError: Constructor Proxy requires 'new'");
+    assert_eq!(eval_err("new Proxy(1, {});"), "TypeError: Cannot create proxy with a non-object as target or handler");
+    assert_eq!(eval("const r = Proxy.revocable({ a: 1 }, {}); r.proxy.a;"), "1");
+    assert_eq!(
+        eval_err("const r = Proxy.revocable({}, {}); r.revoke(); r.proxy.x;"),
+        "TypeError: Cannot perform 'get' on a proxy that has been revoked"
+    );
+    assert_eq!(
+        eval_err("const r = Proxy.revocable([], {}); r.revoke(); Array.isArray(r.proxy);"),
+        "TypeError: Cannot perform 'IsArray' on a proxy that has been revoked"
+    );
+}
+
+#[test]
+fn check_reflect() {
+    assert_eq!(eval("Reflect.apply(Math.max, null, [1, 3, 2]);"), "3");
+    let script = "function C() { this.x = 1; }
+    function D() {}
+    const o = Reflect.construct(C, [], D);
+    Object.getPrototypeOf(o) === D.prototype;";
+    assert_eq!(eval(script), "true");
+    assert_eq!(eval("const o = {}; Reflect.defineProperty(o, \"x\", { value: 1 }) + \",\" + Reflect.set(o, \"x\", 2);"), "true,false");
+    assert_eq!(eval("const o = { a: 1 }; Reflect.deleteProperty(o, \"a\") + \",\" + o.a;"), "true,undefined");
+    assert_eq!(eval("const o = {}; Reflect.preventExtensions(o); Reflect.isExtensible(o);"), "false");
+    assert_eq!(eval("Reflect.getPrototypeOf([]) === Array.prototype;"), "true");
+    assert_eq!(eval("const o = Object.defineProperty({}, \"x\", { get: function() { return this.y; } }); Reflect.get(o, \"x\", { y: 9 });"), "9");
+    assert_eq!(eval("const s = Symbol(\"s\"); const o = { b: 1, \"1\": 2 }; o[s] = 3; Reflect.ownKeys(o).length;"), "3");
+    assert_eq!(eval("Reflect.ownKeys({ b: 1, \"1\": 2 }).join();"), "1,b");
+    assert_eq!(eval("Reflect.getOwnPropertyDescriptor({ a: 1 }, \"a\").writable;"), "true");
+    assert_eq!(eval("Reflect.setPrototypeOf({}, null);"), "true");
+    assert_eq!(eval("Object.prototype.toString.call(Reflect);"), "[object Reflect]");
+    assert_eq!(eval_err("Reflect.get(1, \"a\");"), "TypeError: Reflect.get called on non-object");
+    assert_eq!(eval_err("Reflect.setPrototypeOf({}, 1);"), "TypeError: Object prototype may only be an Object or null: 1");
+}
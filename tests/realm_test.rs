@@ -0,0 +1,80 @@
+use engine::engine::{forward, forward_in_realm};
+use engine::exec::{Executor, Interpreter};
+use engine::realm::Realm;
+
+/// Make an interpreter with a second realm, whose global object is `other` in the main realm
+fn two_realms() -> (Interpreter, Realm) {
+    let mut engine: Interpreter = Executor::new();
+    let other = engine.create_realm();
+    let main = engine.current_realm();
+    main.global_object().set_field_slice("other", other.global_object());
+    (engine, other)
+}
+
+fn eval(engine: &mut Interpreter, script: &str) -> String {
+    forward(engine, script).unwrap().to_string()
+}
+
+#[test]
+fn check_realm_globals() {
+    let (mut engine, other) = two_realms();
+    assert!(engine.current_realm() != other);
+    forward_in_realm(&mut engine, &other, "var hidden = 1; globalThis.shared = 2; leaked = 3;").unwrap();
+    assert_eq!(eval(&mut engine, "const names = [typeof hidden, typeof shared, typeof leaked]; names.join();"), "undefined,undefined,undefined");
+    assert_eq!(eval(&mut engine, "other.shared;"), "2");
+    assert_eq!(forward_in_realm(&mut engine, &other, "hidden + leaked;").unwrap().to_string(), "4");
+    assert_eq!(eval(&mut engine, "other.Array === Array;"), "false");
+    assert_eq!(eval(&mut engine, "other.Object.prototype === Object.prototype;"), "false");
+    // Each global object inherits from its own realm's `Object.prototype`
+    assert_eq!(eval(&mut engine, "Object.getPrototypeOf(globalThis) === Object.prototype;"), "true");
+    assert_eq!(eval(&mut engine, "Object.getPrototypeOf(other) === other.Object.prototype;"), "true");
+    assert_eq!(eval(&mut engine, "String(globalThis);"), "[object Object]");
+    assert_eq!(eval(&mut engine, "globalThis.hasOwnProperty(\"Array\");"), "true");
+}
+
+#[test]
+fn check_cross_realm_prototypes() {
+    let (mut engine, other) = two_realms();
+    forward_in_realm(&mut engine, &other, "globalThis.arr = [1, 2];").unwrap();
+    assert_eq!(eval(&mut engine, "other.arr instanceof Array;"), "false");
+    assert_eq!(eval(&mut engine, "other.arr instanceof other.Array;"), "true");
+    assert_eq!(eval(&mut engine, "Array.isArray(other.arr);"), "true");
+    assert_eq!(eval(&mut engine, "Object.getPrototypeOf(other.arr) === other.Array.prototype;"), "true");
+    assert_eq!(eval(&mut engine, "other.arr.map(x => x * 2).join();"), "2,4");
+}
+
+#[test]
+fn check_functions_run_in_their_realm() {
+    let (mut engine, other) = two_realms();
+    forward_in_realm(&mut engine, &other, "globalThis.make = function() { return [{}]; };").unwrap();
+    assert_eq!(eval(&mut engine, "const a = other.make(); a instanceof other.Array;"), "true");
+    assert_eq!(eval(&mut engine, "const o = other.make()[0]; o instanceof other.Object;"), "true");
+    assert_eq!(eval(&mut engine, "o instanceof Object;"), "false");
+    // Built-in functions make their objects from their own realm too
+    assert_eq!(eval(&mut engine, "other.Array.of(1) instanceof other.Array;"), "true");
+    assert_eq!(eval(&mut engine, "other.eval(\"[]\") instanceof other.Array;"), "true");
+    assert_eq!(eval(&mut engine, "const f = other.Function(\"return {}\"); f() instanceof other.Object;"), "true");
+}
+
+#[test]
+fn check_function_realm() {
+    let (mut engine, other) = two_realms();
+    forward_in_realm(&mut engine, &other, "globalThis.f = function() {};").unwrap();
+    let f = other.global_object().get_field_slice("f");
+    assert!(engine.function_realm(&f) == other);
+    let array = other.global_object().get_field_slice("Array");
+    assert!(engine.function_realm(&array) == other);
+    // Constructors whose prototype isn't an object make objects from their own realm's `Object.prototype`
+    assert_eq!(eval(&mut engine, "other.f.prototype = 1; const x = new other.f(); x instanceof other.Object;"), "true");
+}
+
+#[test]
+fn check_intrinsics() {
+    let (mut engine, other) = two_realms();
+    let array_prototype = other.intrinsic("Array.prototype");
+    forward_in_realm(&mut engine, &other, "globalThis.Array = null;").unwrap();
+    assert!(array_prototype.get_field_slice("map").is_function());
+    assert_eq!(eval(&mut engine, "other.Array === null;"), "true");
+    assert!(other.intrinsic("Math").get_field_slice("max").is_function());
+    assert!(other.intrinsic("NotABuiltin").is_undefined());
+}
@@ -0,0 +1,105 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_literals() {
+    assert_eq!(eval("const r = /a\\/b/g; r.toString();"), "/a\\/b/g");
+    assert_eq!(eval("const x = 10; const y = 2; x / y / 1;"), "5");
+    assert_eq!(eval("/[/]+/.test(\"//\");"), "true");
+    assert_eq!(eval("const r = /a/; r.toString = ({}).toString; r.toString();"), "[object RegExp]");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "let r = /(/;").is_err());
+    assert!(forward(&mut engine, "let r = /a/gg;").is_err());
+}
+
+#[test]
+fn check_constructor() {
+    assert_eq!(eval("const r = new RegExp(\"a/b\", \"gi\"); r.source;"), "a\\/b");
+    assert_eq!(eval("const r = new RegExp(\"\"); r.toString();"), "/(?:)/");
+    assert_eq!(eval("const r = new RegExp(/ab/g, \"i\"); r.flags;"), "i");
+    assert_eq!(eval("const r = RegExp(\"a+\", \"g\"); r.global;"), "true");
+    assert_eq!(eval("const r = /x/yusmig; r.flags;"), "gimsuy");
+    // The slots the flag getters and `[Symbol.matchAll]` carry are internal, not properties
+    assert_eq!(eval("Object.getOwnPropertyNames(Object.getOwnPropertyDescriptor(RegExp.prototype, \"global\").get).length;"), "0");
+    assert_eq!(eval("Object.getOwnPropertyNames(RegExp.prototype[Symbol.matchAll]).join();"), "name");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "new RegExp(\"a**\");").is_err());
+    assert!(forward(&mut engine, "new RegExp(\"a\", \"x\");").is_err());
+}
+
+#[test]
+fn check_exec() {
+    let script = "const m = /(?<year>\\d{4})-(\\d\\d)/.exec(\"on 2024-05!\");
+    m.index + \"|\" + m.groups.year + \"|\" + m[2] + \"|\" + m.input;";
+    assert_eq!(eval(script), "3|2024|05|on 2024-05!");
+    assert_eq!(eval("/z/.exec(\"abc\");"), "null");
+    assert_eq!(eval("const m = /(a)(z)?/d.exec(\"ba\"); m.indices[0].join() + \"|\" + m.indices[2];"), "1,2|undefined");
+    assert_eq!(eval("/^.$/u.test(\"😀\") + \",\" + /^.$/.test(\"😀\");"), "true,false");
+}
+
+#[test]
+fn check_last_index() {
+    assert_eq!(eval("const r = /a/g; r.test(\"ba\"); r.lastIndex;"), "2");
+    assert_eq!(eval("const r = /a/y; r.test(\"ba\");"), "false");
+    assert_eq!(eval("const r = /a/; r.test(\"ba\"); r.lastIndex;"), "0");
+    // lastIndex and split limits are converted with ToNumber, which throws for BigInts and symbols
+    let mut engine: Interpreter = Executor::new();
+    let err = forward(&mut engine, "const r = /a/g; r.lastIndex = 1n; r.exec(\"a\");").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot convert a BigInt value to a number");
+    assert!(forward(&mut engine, "\"a,b\".split(/,/, Symbol());").is_err());
+    let script = "const re = /o/g;
+    let n = 0;
+    while (re.exec(\"foo boo\") !== null) { n = n + 1; }
+    n;";
+    assert_eq!(eval(script), "4");
+}
+
+#[test]
+fn check_string_methods() {
+    assert_eq!(eval("\"10-20 30-40\".match(/(\\d+)-(\\d+)/g).join(\"|\");"), "10-20|30-40");
+    assert_eq!(eval("\"abc\".match(/z/);"), "null");
+    assert_eq!(eval("\"xAbAc\".search(/a/i);"), "1");
+    assert_eq!(eval("const it = \"a1b22\".matchAll(/\\d+/g); it.next(); it.next().value.index;"), "3");
+    assert_eq!(eval("const it = \"a1\".matchAll(/\\d/g); it.next(); it.next().done;"), "true");
+    assert_eq!(eval("\"a1b2c3\".split(/\\d/).join(\"|\");"), "a|b|c|");
+    assert_eq!(eval("\"a1b2\".split(/(\\d)/).join(\"|\");"), "a|1|b|2|");
+    assert_eq!(eval("\"test\".split(/(?:)/, 2).join();"), "t,e");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "\"a\".startsWith(/a/);").is_err());
+    assert!(forward(&mut engine, "\"aaa\".matchAll(/a/);").is_err());
+}
+
+#[test]
+fn check_replace() {
+    assert_eq!(eval("\"2024-05\".replace(/(?<y>\\d+)-(?<m>\\d+)/, \"$<m>/$<y>\");"), "05/2024");
+    assert_eq!(eval("\"abc\".replace(/b/, \"[$`|$&|$']\");"), "a[a|b|c]c");
+    assert_eq!(eval("\"aaa\".replace(/a/g, function(m, i) { return i; });"), "012");
+    assert_eq!(eval("\"aBc\".replace(/b/i, function(m) { return m.toLowerCase() + \"!\"; });"), "ab!c");
+    assert_eq!(eval("\"aaa\".replaceAll(/a/g, \"b\");"), "bbb");
+    assert_eq!(eval("\"a.b\".replace(\".\", \"-\");"), "a-b");
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "\"aaa\".replaceAll(/a/, \"b\");").is_err());
+}
+
+#[test]
+fn check_long_inputs() {
+    // Quantified characters and fixed sequences of them loop rather than recursing, so long inputs match
+    assert_eq!(eval("/a*/.exec(\"a\".repeat(100000))[0].length;"), "100000");
+    assert_eq!(eval("/a+?b/.exec(\"a\".repeat(100000) + \"b\")[0].length;"), "100001");
+    assert_eq!(eval("/[^,]*,x/.exec(\"y\".repeat(100000) + \",x\").index;"), "0");
+    assert_eq!(eval("/(?:ab){2,}a/.exec(\"ab\".repeat(100000))[0].length;"), "199999");
+    assert_eq!(eval("/(?:a|b)*/.exec(\"ab\".repeat(100))[0].length;"), "200");
+    assert_eq!(eval("/((((a))))*/.exec(\"a\".repeat(60))[0].length;"), "60");
+    // Other patterns give up with a RangeError once they backtrack too deeply, rather than crashing
+    assert_eq!(
+        eval_err("/(?:a|b)*/.exec(\"ab\".repeat(100000));"),
+        "RangeError: Maximum call stack size exceeded"
+    );
+    assert_eq!(
+        eval_err("/(a)(?:\\1|b)*c/.test(\"ab\".repeat(100000));"),
+        "RangeError: Maximum call stack size exceeded"
+    );
+}
@@ -1,5 +1,7 @@
-use engine::{engine::run_script, js::{value::ValueData, string::_create}};
+use engine::{engine::{forward, run_script}, exec::{Executor, Interpreter}, js::{value::ValueData, string::_create}};
 
+mod common;
+use common::eval;
 
 #[test]
 fn check_new_string() {
@@ -33,3 +35,108 @@ fn check_char_code_at() {
     assert_eq!(res.to_string(), "101");
 }
 
+#[test]
+fn check_utf16_length() {
+    assert_eq!(eval("\"héllo\".length;"), "5");
+    assert_eq!(eval("\"😀\".length;"), "2");
+    assert_eq!(eval("\"héllo\"[1];"), "é");
+    assert_eq!(eval("\"😀\".codePointAt(0);"), "128512");
+    assert_eq!(eval("\"😀\".charCodeAt(1);"), "56832");
+    assert_eq!(eval("const s = new String(\"héllo\"); s.length;"), "5");
+}
+
+#[test]
+fn check_lone_surrogates() {
+    // Strings hold UTF-16 code units, so the halves of a surrogate pair survive being split apart
+    assert_eq!(eval("\"😀\".charAt(0).charCodeAt(0);"), "55357");
+    assert_eq!(eval("\"😀\"[0] + \"😀\"[1] === \"😀\";"), "true");
+    assert_eq!(eval("String.fromCharCode(0xD83D).charCodeAt(0);"), "55357");
+    assert_eq!(eval("String.fromCharCode(0xD83D) == String.fromCharCode(0xD83E);"), "false");
+    assert_eq!(eval("\"😀\".slice(0, 1).concat(\"😀\".slice(1)) === \"😀\";"), "true");
+    assert_eq!(eval("[\"😀\"[0], \"😀\"[1]].join(\"\") === \"😀\";"), "true");
+    assert_eq!(eval("Array.from(\"a\" + \"😀\"[1]).length;"), "2");
+    assert_eq!(eval("JSON.stringify(\"😀\"[0]);"), "\"\\ud83d\"");
+    assert_eq!(eval("JSON.parse(\"\\\"\\\\ud83d\\\"\").charCodeAt(0);"), "55357");
+}
+
+#[test]
+fn check_primitive_methods() {
+    assert_eq!(eval("\"abc\".toUpperCase();"), "ABC");
+    assert_eq!(eval("\"abc\".at(-1);"), "c");
+    assert_eq!(eval("typeof String(1);"), "string");
+    assert_eq!(eval("typeof new String(1);"), "object");
+    assert_eq!(eval("String(Symbol(\"a\"));"), "Symbol(a)");
+}
+
+#[test]
+fn check_substrings() {
+    assert_eq!(eval("\"hello world\".slice(-5);"), "world");
+    assert_eq!(eval("\"hello\".slice(1, -1);"), "ell");
+    assert_eq!(eval("\"hello\".substring(4, 1);"), "ell");
+    assert_eq!(eval("\"hello\".substr(-4, 2);"), "el");
+}
+
+#[test]
+fn check_searching() {
+    assert_eq!(eval("\"banana\".indexOf(\"an\");"), "1");
+    assert_eq!(eval("\"banana\".indexOf(\"an\", 2);"), "3");
+    assert_eq!(eval("\"banana\".lastIndexOf(\"an\");"), "3");
+    assert_eq!(eval("\"banana\".indexOf(\"x\");"), "-1");
+    assert_eq!(eval("\"banana\".includes(\"nan\");"), "true");
+    assert_eq!(eval("\"banana\".startsWith(\"ban\");"), "true");
+    assert_eq!(eval("\"banana\".endsWith(\"ban\", 3);"), "true");
+}
+
+#[test]
+fn check_split() {
+    assert_eq!(eval("\"a,b,c\".split(\",\").length;"), "3");
+    assert_eq!(eval("\"a,b,c\".split(\",\", 2).join(\"|\");"), "a|b");
+    assert_eq!(eval("\"abc\".split(\"\").join(\"|\");"), "a|b|c");
+    assert_eq!(eval("\"abc\".split().length;"), "1");
+}
+
+#[test]
+fn check_formatting() {
+    assert_eq!(eval("\"  hi \\n\".trim();"), "hi");
+    assert_eq!(eval("\"  hi \".trimStart();"), "hi ");
+    assert_eq!(eval("\"  hi \".trimEnd();"), "  hi");
+    assert_eq!(eval("\"5\".padStart(3, \"0\");"), "005");
+    assert_eq!(eval("\"abc\".padEnd(6, \"12\");"), "abc121");
+    assert_eq!(eval("\"ab\".repeat(3);"), "ababab");
+    assert_eq!(eval("\"e\\u0301\".normalize().length;"), "1");
+    assert_eq!(eval("\"a\".localeCompare(\"b\");"), "-1");
+}
+
+#[test]
+fn check_replace() {
+    assert_eq!(eval("\"aXbX\".replace(\"X\", \"-\");"), "a-bX");
+    assert_eq!(eval("\"aXbX\".replaceAll(\"X\", \"-\");"), "a-b-");
+    assert_eq!(eval("\"abc\".replace(\"b\", \"[$&$`$']\");"), "a[bac]c");
+    assert_eq!(eval("\"abc\".replace(\"b\", function(m, i) { return m + i; });"), "ab1c");
+}
+
+#[test]
+fn check_static_methods() {
+    assert_eq!(eval("String.fromCharCode(104, 105);"), "hi");
+    assert_eq!(eval("String.fromCodePoint(128512).length;"), "2");
+    assert_eq!(eval("const t = { raw: [\"a\", \"c\"] }; String.raw(t, \"b\");"), "abc");
+    assert_eq!(eval("Array.from(\"a😀\").length;"), "2");
+    // The slot the iterator method carries is internal, not a property
+    assert_eq!(eval("Object.getOwnPropertyNames(String.prototype[Symbol.iterator]).join();"), "name");
+}
+
+#[test]
+fn check_string_errors() {
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "\"a\".repeat(-1);").is_err());
+    assert!(forward(&mut engine, "String.fromCodePoint(-1);").is_err());
+    assert!(forward(&mut engine, "\"a\".normalize(\"X\");").is_err());
+    // Arguments are converted with ToNumber, which throws for BigInts and symbols
+    let err = forward(&mut engine, "String.fromCharCode(65n);").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot convert a BigInt value to a number");
+    let err = forward(&mut engine, "String.fromCharCode(Symbol());").unwrap_err();
+    assert_eq!(err.to_string(), "TypeError: Cannot convert a Symbol value to a number");
+    assert!(forward(&mut engine, "String.fromCodePoint(65n);").is_err());
+    assert!(forward(&mut engine, "\"a\".lastIndexOf(\"a\", Symbol());").is_err());
+    assert!(forward(&mut engine, "\"a,b\".split(\",\", 1n);").is_err());
+}
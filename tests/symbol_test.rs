@@ -0,0 +1,101 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+
+mod common;
+use common::eval;
+
+#[test]
+fn check_symbol_values() {
+    assert_eq!(eval("typeof Symbol(\"a\");"), "symbol");
+    assert_eq!(eval("Symbol(\"a\").description;"), "a");
+    assert_eq!(eval("Symbol().description;"), "undefined");
+    assert_eq!(eval("Symbol(\"a\").toString();"), "Symbol(a)");
+    assert_eq!(eval("Symbol(\"a\") === Symbol(\"a\");"), "false");
+    assert_eq!(eval("const s = Symbol(\"a\"); s === s;"), "true");
+    assert_eq!(eval("Symbol.iterator.description;"), "Symbol.iterator");
+}
+
+#[test]
+fn check_symbol_registry() {
+    assert_eq!(eval("Symbol.for(\"app\") === Symbol.for(\"app\");"), "true");
+    assert_eq!(eval("Symbol.keyFor(Symbol.for(\"app\"));"), "app");
+    assert_eq!(eval("Symbol.keyFor(Symbol(\"app\"));"), "undefined");
+}
+
+#[test]
+fn check_symbol_errors() {
+    let mut engine: Interpreter = Executor::new();
+    assert!(forward(&mut engine, "new Symbol();").is_err());
+    assert!(forward(&mut engine, "Symbol() + 1;").is_err());
+    assert!(forward(&mut engine, "\"a\" + Symbol();").is_err());
+}
+
+#[test]
+fn check_symbol_keyed_properties() {
+    let script = "const key = Symbol(\"key\");
+    const obj = {};
+    obj[key] = 1;
+    obj.key = 2;
+    obj[key] + obj.key;";
+    assert_eq!(eval(script), "3");
+    assert_eq!(eval("const obj = {}; obj[Symbol(\"a\")] = 1; obj[Symbol(\"a\")];"), "undefined");
+}
+
+#[test]
+fn check_to_string_tag() {
+    assert_eq!(eval("({}).toString();"), "[object Object]");
+    assert_eq!(eval("const a = []; a.toString = ({}).toString; a.toString();"), "[object Array]");
+    assert_eq!(eval("Promise.resolve(1).toString();"), "[object Promise]");
+    assert_eq!(eval("Math.toString();"), "[object Math]");
+    let script = "const obj = {};
+    obj[Symbol.toStringTag] = \"Custom\";
+    obj.toString();";
+    assert_eq!(eval(script), "[object Custom]");
+}
+
+#[test]
+fn check_to_primitive() {
+    let script = "const obj = {};
+    obj[Symbol.toPrimitive] = function(hint) { return hint; };
+    \"\" + obj;";
+    assert_eq!(eval(script), "default");
+    let script = "const obj = {};
+    obj.valueOf = function() { return 20; };
+    obj * 2;";
+    assert_eq!(eval(script), "40");
+    let script = "const obj = {};
+    obj.valueOf = function() { return 3; };
+    obj > 2;";
+    assert_eq!(eval(script), "true");
+}
+
+#[test]
+fn check_instanceof() {
+    assert_eq!(eval("[] instanceof Array;"), "true");
+    assert_eq!(eval("({}) instanceof Array;"), "false");
+    assert_eq!(eval("function Foo() { let a = 1; }; const foo = new Foo(); foo instanceof Foo;"), "true");
+    let script = "const Even = {};
+    Even[Symbol.hasInstance] = function(n) { return n % 2 == 0; };
+    2 instanceof Even;";
+    assert_eq!(eval(script), "true");
+}
+
+#[test]
+fn check_iterator() {
+    let script = "const it = [\"a\", \"b\"][Symbol.iterator]();
+    it.next().value + it.next().value + it.next().done;";
+    assert_eq!(eval(script), "abtrue");
+    assert_eq!(eval("[][Symbol.iterator]().toString();"), "[object Array Iterator]");
+}
+
+#[test]
+fn check_promise_all_uses_iterator() {
+    let mut engine: Interpreter = Executor::new();
+    let script = "let result = \"\";
+    const iterable = {};
+    iterable[Symbol.iterator] = function() { return [1, 2][Symbol.iterator](); };
+    Promise.all(iterable).then(function(values) { result = values[0] + values[1]; });";
+    forward(&mut engine, script).unwrap();
+    engine.run_jobs().unwrap();
+    assert_eq!(forward(&mut engine, "result;").unwrap().to_string(), "3");
+}
@@ -0,0 +1,111 @@
+use engine::engine::forward;
+use engine::event_loop::EventLoop;
+use engine::exec::Executor;
+
+fn fake_loop(script: &str) -> EventLoop {
+    let mut event_loop = EventLoop::with_fake_clock(Executor::new());
+    forward(event_loop.interpreter(), script).unwrap();
+    event_loop
+}
+
+fn eval(event_loop: &mut EventLoop, script: &str) -> String {
+    forward(event_loop.interpreter(), script).unwrap().to_string()
+}
+
+#[test]
+fn check_set_timeout_advance() {
+    let mut event_loop = fake_loop("let log = \"\";
+    setTimeout(function() { log = log + \"b\"; }, 100);
+    setTimeout(function() { log = log + \"a\"; }, 50);");
+    event_loop.advance(49).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "");
+    event_loop.advance(1).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "a");
+    event_loop.advance(50).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "ab");
+    assert_eq!(event_loop.now(), 100);
+    assert!(!event_loop.has_pending_work());
+}
+
+#[test]
+fn check_timers_fire_in_order() {
+    let mut event_loop = fake_loop("let log = \"\";
+    setTimeout(function(x) { log = log + x; }, 10, \"1\");
+    setTimeout(function(x) { log = log + x; }, 10, \"2\");
+    setTimeout(function(x) { log = log + x; }, 0, \"0\");");
+    event_loop.run().unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "012");
+}
+
+#[test]
+fn check_microtasks_before_macrotasks() {
+    let mut event_loop = fake_loop("let log = \"\";
+    setTimeout(function() {
+        log = log + \"t\";
+        queueMicrotask(function() { log = log + \"m\"; });
+    }, 0);
+    setTimeout(function() { log = log + \"u\"; }, 0);
+    Promise.resolve(\"p\").then(function(v) { log = log + v; });");
+    event_loop.run().unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "ptmu");
+}
+
+#[test]
+fn check_await_timer() {
+    let mut event_loop = fake_loop("let log = \"\";
+    async function sleep(ms) {
+        await new Promise(function(resolve) { setTimeout(resolve, ms); });
+        log = log + ms;
+    }
+    sleep(100);
+    log = log + \"s\";");
+    event_loop.advance(50).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "s");
+    event_loop.advance(60).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "s100");
+    assert!(!event_loop.has_pending_work());
+}
+
+#[test]
+fn check_clear_timeout() {
+    let mut event_loop = fake_loop("let fired = false;
+    let id = setTimeout(function() { fired = true; }, 10);
+    clearTimeout(id);");
+    event_loop.run().unwrap();
+    assert_eq!(eval(&mut event_loop, "fired;"), "false");
+    assert_eq!(event_loop.now(), 0);
+}
+
+#[test]
+fn check_set_interval() {
+    let mut event_loop = fake_loop("let count = 0;
+    let id = setInterval(function() {
+        count = count + 1;
+        if (count == 3) {
+            clearInterval(id);
+        }
+    }, 20);");
+    event_loop.advance(45).unwrap();
+    assert_eq!(eval(&mut event_loop, "count;"), "2");
+    event_loop.run().unwrap();
+    assert_eq!(eval(&mut event_loop, "count;"), "3");
+    assert_eq!(event_loop.now(), 60);
+}
+
+#[test]
+fn check_delays_are_converted_to_numbers() {
+    let mut event_loop = fake_loop("let log = \"\";
+    setTimeout(function() { log = log + \"s\"; }, \"100\");
+    setTimeout(function() { log = log + \"o\"; }, { valueOf: function() { return 50; } });
+    setTimeout(function() { log = log + \"n\"; }, \"soon\");
+    let id = setTimeout(function() { log = log + \"x\"; }, 10);
+    clearTimeout(String(id));");
+    event_loop.advance(1).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "n");
+    event_loop.advance(49).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "no");
+    event_loop.advance(49).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "no");
+    event_loop.advance(1).unwrap();
+    assert_eq!(eval(&mut event_loop, "log;"), "nos");
+}
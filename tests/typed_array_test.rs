@@ -0,0 +1,168 @@
+use engine::engine::forward;
+use engine::exec::{Executor, Interpreter};
+use engine::js::array_buffer::{create_array_buffer, ByteBuffer};
+
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_array_buffer() {
+    assert_eq!(eval("const b = new ArrayBuffer(8); b.byteLength;"), "8");
+    assert_eq!(eval("const b = new ArrayBuffer(8); b.resizable;"), "false");
+    assert_eq!(eval("const b = new ArrayBuffer(4); const s = b.slice(1, -1); s.byteLength;"), "2");
+    assert_eq!(eval("const b = new ArrayBuffer(2, { maxByteLength: 8 }); b.resize(6); b.byteLength + b.maxByteLength;"), "14");
+    assert_eq!(eval("const b = new ArrayBuffer(8); ArrayBuffer.isView(b);"), "false");
+    assert_eq!(eval("const b = new ArrayBuffer(8); Object.prototype.toString.call(b);"), "[object ArrayBuffer]");
+    assert_eq!(eval_err("new ArrayBuffer(-1);"), "RangeError: Invalid array buffer length");
+    assert_eq!(eval_err("ArrayBuffer(1);"), "TypeError: Constructor ArrayBuffer requires 'new'");
+    assert_eq!(
+        eval_err("const b = new ArrayBuffer(2); b.resize(4);"),
+        "TypeError: ArrayBuffer.prototype.resize requires that 'this' be a resizable ArrayBuffer"
+    );
+    assert_eq!(
+        eval_err("const b = new ArrayBuffer(2, { maxByteLength: 4 }); b.resize(5);"),
+        "RangeError: ArrayBuffer.prototype.resize: Invalid length parameter"
+    );
+}
+
+#[test]
+fn check_typed_array_elements() {
+    assert_eq!(eval("const a = new Uint8Array(4); a[0] = 257; a[1] = -1; a.join();"), "1,255,0,0");
+    assert_eq!(eval("const a = new Int8Array([127, 128, -129]); a.join();"), "127,-128,127");
+    assert_eq!(eval("const a = new Uint8ClampedArray([300, -5, 1.5, 2.5]); a.join();"), "255,0,2,2");
+    assert_eq!(eval("const a = new Float32Array([1.1]); a[0];"), "1.100000023841858");
+    assert_eq!(eval("const a = new BigInt64Array(2); a[0] = -1n; a[1] = 9223372036854775808n; a.join();"), "-1,-9223372036854775808");
+    assert_eq!(eval("const a = new BigUint64Array(1); a[0] = -1n; a[0];"), "18446744073709551615");
+    assert_eq!(eval("const a = new Uint8Array(2); a[0] = { valueOf: function() { return 7; } }; a[0];"), "7");
+    // Numeric keys which aren't elements are never properties
+    assert_eq!(eval("const a = new Uint8Array(2); a[5] = 1; a[5];"), "undefined");
+    assert_eq!(eval("const a = new Uint8Array(2); a[\"1.5\"] = 1; a[\"1.5\"];"), "undefined");
+    assert_eq!(eval("const a = new Uint8Array(2); a[\"-0\"] = 1; a[\"-0\"];"), "undefined");
+    assert_eq!(eval("const a = new Uint8Array(2); a.foo = 1; a.foo;"), "1");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); Object.keys(a).join();"), "0,1,2");
+    assert_eq!(eval("const a = new Uint8Array([1, 2]); Object.defineProperty(a, \"0\", { value: 9 }); a[0];"), "9");
+    assert_eq!(
+        eval_err("const a = new Uint8Array([1, 2]); Object.defineProperty(a, \"0\", { value: 9, writable: false });"),
+        "TypeError: Cannot redefine property: 0"
+    );
+    assert_eq!(eval_err("const a = new BigInt64Array(1); a[0] = 1;"), "TypeError: Cannot convert 1 to a BigInt");
+}
+
+#[test]
+fn check_typed_array_constructors() {
+    assert_eq!(eval("const a = new Int16Array(3); a.length + a.byteLength;"), "9");
+    assert_eq!(eval("const b = new ArrayBuffer(8); const a = new Int16Array(b, 2); a.length;"), "3");
+    assert_eq!(eval("const a = new Uint8Array([1, 2]); const b = new Float32Array(a); b.join();"), "1,2");
+    assert_eq!(eval("const a = new Uint8Array(new Set([4, 5])); a.join();"), "4,5");
+    assert_eq!(eval("const a = new Uint8Array({ length: 2, \"0\": 9 }); a.join();"), "9,0");
+    assert_eq!(eval("const a = Uint8Array.from([1, 2], x => x + 1); a.join();"), "2,3");
+    assert_eq!(eval("const a = Int16Array.of(1, -2); a.join();"), "1,-2");
+    assert_eq!(eval("Uint8Array.BYTES_PER_ELEMENT + Float64Array.prototype.BYTES_PER_ELEMENT;"), "9");
+    assert_eq!(eval("Uint8Array.name + Uint8Array.length;"), "Uint8Array3");
+    assert_eq!(eval("Object.getPrototypeOf(Uint8Array).name;"), "TypedArray");
+    assert_eq!(eval("const a = new Uint8Array(1); Object.prototype.toString.call(a);"), "[object Uint8Array]");
+    assert_eq!(eval_err("Uint8Array(2);"), "TypeError: Constructor Uint8Array requires 'new'");
+    assert_eq!(eval_err("new Uint8Array(-1);"), "RangeError: Invalid typed array length: -1");
+    assert_eq!(
+        eval_err("const b = new ArrayBuffer(8); new Int16Array(b, 1);"),
+        "RangeError: start offset of Int16Array should be a multiple of 2"
+    );
+    assert_eq!(
+        eval_err("const a = new Uint8Array([1, 2]); new BigInt64Array(a);"),
+        "TypeError: Cannot mix BigInt and other types, use explicit conversions"
+    );
+    assert_eq!(
+        eval_err("const T = Object.getPrototypeOf(Uint8Array); new T();"),
+        "TypeError: Abstract class TypedArray not directly constructable"
+    );
+}
+
+#[test]
+fn check_typed_array_methods() {
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); a.map(x => x * 100).join();"), "100,200,44");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); a.filter(x => x > 1).join();"), "2,3");
+    assert_eq!(eval("const a = new Float64Array([3, NaN, 0, 1]); a.sort(); a.join();"), "0,1,3,NaN");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3, 4]); const s = a.subarray(1, 3); s[0] = 9; a.join();"), "1,9,3,4");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3, 4]); a.slice(-2).join();"), "3,4");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3, 4]); a.set([7, 8], 2); a.join();"), "1,2,7,8");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3, 4]); a.copyWithin(0, 2); a.join();"), "3,4,3,4");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3, 4]); a.fill(5, 1, 3); a.join();"), "1,5,5,4");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); a.with(-1, 9).join() + a.toReversed().join();"), "1,2,93,2,1");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3, 2]); a.lastIndexOf(2) + a.findLast(x => x < 3);"), "5");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); a.reduce((x, y) => x + y);"), "6");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); a.toString();"), "1,2,3");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); Array.from(a.entries()).join(\";\");"), "0,1;1,2;2,3");
+    assert_eq!(eval("const a = new Uint8Array([1, 2, 3]); a.values === a[Symbol.iterator];"), "true");
+    assert_eq!(eval("Object.getOwnPropertyNames(Uint8Array.prototype.keys).join();"), "name");
+    assert_eq!(eval_err("const a = new Uint8Array([1, 2, 3, 4]); a.set([7, 8], 3);"), "RangeError: offset is out of bounds");
+    assert_eq!(eval_err("const a = new Uint8Array(1); a.with(1, 0);"), "RangeError: Invalid typed array index");
+    assert_eq!(eval_err("Uint8Array.prototype.join.call([1]);"), "TypeError: this is not a typed array.");
+}
+
+#[test]
+fn check_resizable_buffers() {
+    let script = "const b = new ArrayBuffer(4, { maxByteLength: 8 });
+    const a = new Uint8Array(b);
+    b.resize(8);
+    a.length;";
+    assert_eq!(eval(script), "8");
+    let script = "const b = new ArrayBuffer(4, { maxByteLength: 8 });
+    const a = new Uint8Array(b, 0, 4);
+    b.resize(2);
+    const r = [a.length, a.byteLength, a[0]];
+    r.join();";
+    assert_eq!(eval(script), "0,0,");
+    let script = "const b = new ArrayBuffer(4, { maxByteLength: 8 });
+    const a = new Uint8Array(b, 0, 4);
+    b.resize(2);
+    a.join();";
+    assert_eq!(
+        eval_err(script),
+        "TypeError: Cannot perform %TypedArray%.prototype.join on an out of bounds TypedArray"
+    );
+}
+
+#[test]
+fn check_data_view() {
+    assert_eq!(eval("const b = new ArrayBuffer(8); const d = new DataView(b); d.setInt16(0, -2); d.getUint16(0);"), "65534");
+    let script = "const b = new ArrayBuffer(4);
+    const d = new DataView(b);
+    d.setUint32(0, 0x01020304, true);
+    const u = new Uint8Array(b);
+    u.join();";
+    assert_eq!(eval(script), "4,3,2,1");
+    let script = "const b = new ArrayBuffer(4);
+    const d = new DataView(b);
+    d.setUint32(0, 0x01020304);
+    const u = new Uint8Array(b);
+    u.join();";
+    assert_eq!(eval(script), "1,2,3,4");
+    assert_eq!(eval("const d = new DataView(new ArrayBuffer(8)); d.setFloat64(0, Math.PI); d.getFloat64(0);"), "3.141592653589793");
+    assert_eq!(eval("const d = new DataView(new ArrayBuffer(8)); d.setBigInt64(0, -5n, true); d.getBigUint64(0, true);"), "18446744073709551611");
+    assert_eq!(eval("const d = new DataView(new ArrayBuffer(8), 2, 4); d.byteOffset + d.byteLength;"), "6");
+    assert_eq!(eval("const d = new DataView(new ArrayBuffer(8)); ArrayBuffer.isView(d);"), "true");
+    assert_eq!(
+        eval_err("const d = new DataView(new ArrayBuffer(8), 2); d.getInt32(3);"),
+        "RangeError: Offset is outside the bounds of the DataView"
+    );
+    assert_eq!(eval_err("new DataView({});"), "TypeError: First argument to DataView constructor must be an ArrayBuffer");
+    assert_eq!(
+        eval_err("new DataView(new ArrayBuffer(8), 9);"),
+        "RangeError: Start offset 9 is outside the bounds of the buffer"
+    );
+    assert_eq!(eval_err("new DataView(new ArrayBuffer(8), 2, 7);"), "RangeError: Invalid DataView length 7");
+}
+
+#[test]
+fn check_shared_bytes() {
+    let mut engine: Interpreter = Executor::new();
+    let bytes = ByteBuffer::new(vec![1, 2, 3, 4]);
+    let buffer = create_array_buffer(&engine.get_global_object(), bytes.clone());
+    engine.get_global_object().set_field_slice("shared", buffer);
+    let result = forward(&mut engine, "const a = new Uint8Array(shared); a[0] = 9; a.join();").unwrap();
+    assert_eq!(result.to_string(), "9,2,3,4");
+    // The script wrote into the same bytes the embedder holds, and can see what the embedder writes
+    assert_eq!(*bytes.borrow(), vec![9, 2, 3, 4]);
+    bytes.borrow_mut()[3] = 7;
+    assert_eq!(forward(&mut engine, "a[3];").unwrap().to_string(), "7");
+}
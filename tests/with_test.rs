@@ -0,0 +1,60 @@
+mod common;
+use common::{eval, eval_err};
+
+#[test]
+fn check_with_bindings() {
+    assert_eq!(eval("const o = { a: 1, b: 2 }; with (o) { a + b; }"), "3");
+    assert_eq!(eval("const o = { a: 1 }; with (o) { a = 5; } o.a;"), "5");
+    assert_eq!(eval("const o = { a: 1 }; let b = 2; with (o) { b = a + b; } b;"), "3");
+    assert_eq!(eval("with (\"abc\") { length; }"), "3");
+    assert_eq!(eval("const o = {}; with (o) { typeof missing; }"), "undefined");
+    assert_eq!(eval_err("const o = {}; with (o) { missing; }"), "ReferenceError: missing is not defined");
+    assert_eq!(eval_err("with (null) { 1; }"), "TypeError: Cannot convert null to object");
+}
+
+#[test]
+fn check_with_declarations() {
+    // `var` is declared in the enclosing function, but assigned to the object's property if it has one
+    assert_eq!(eval("const o = { x: 1 }; with (o) { var x = 7; } o.x;"), "7");
+    assert_eq!(eval("function f() { const o = {}; with (o) { var y = 3; } return y; } f();"), "3");
+    assert_eq!(eval("const o = {}; with (o) { var z = 3; } Object.keys(o).length;"), "0");
+    assert_eq!(eval_err("const o = {}; with (o) { const c = 1; } c;"), "ReferenceError: c is not defined");
+    // Functions made inside the body keep seeing the object's properties
+    assert_eq!(eval("const o = { a: 1 }; let g; with (o) { g = function() { return a; }; } o.a = 9; g();"), "9");
+}
+
+#[test]
+fn check_with_this() {
+    let script = "const o = { name: \"o\", who: function() { return this.name; } };
+    with (o) { who(); }";
+    assert_eq!(eval(script), "o");
+    let script = "globalThis.name = \"global\";
+    function who() { return this.name; }
+    const o = {};
+    with (o) { who(); }";
+    assert_eq!(eval(script), "global");
+}
+
+#[test]
+fn check_with_internal_methods() {
+    let script = "const o = {};
+    Object.defineProperty(o, \"g\", { get: function() { return 42; } });
+    with (o) { g; }";
+    assert_eq!(eval(script), "42");
+    let script = "const p = new Proxy({}, {
+        has: function(t, k) { return k === \"magic\"; },
+        get: function(t, k) { return 99; }
+    });
+    with (p) { magic; }";
+    assert_eq!(eval(script), "99");
+    assert_eq!(eval("const o = { a: 1 }; with (o) { eval(\"a + 1\"); }"), "2");
+}
+
+#[test]
+fn check_unscopables() {
+    assert_eq!(eval("typeof Symbol.unscopables;"), "symbol");
+    assert_eq!(eval("const o = { a: 1 }; o[Symbol.unscopables] = { a: true }; const a = \"outer\"; with (o) { a; }"), "outer");
+    assert_eq!(eval("const arr = [1, 2]; const values = \"outer\"; with (arr) { values + length; }"), "outer2");
+    assert_eq!(eval("Object.getPrototypeOf(Array.prototype[Symbol.unscopables]);"), "null");
+    assert_eq!(eval("Array.prototype[Symbol.unscopables].includes;"), "true");
+}